@@ -0,0 +1,382 @@
+//! Typed functions behind the commands which are used by services. They take
+//! values instead of command line arguments and print nothing
+
+use crate::{
+    client::Client,
+    error::{CliError, Result},
+    pda,
+};
+use anchor_client::solana_sdk::{pubkey::Pubkey, signature::Signature, signer::Signer};
+use chill_nft::state::{ChillNftMetadata, Config, NftType, NftTypeRegistry, Recipient, UiFees};
+use chill_staking::{
+    lazy_vector::LazyVector,
+    state::{StakingInfo, UserInfo, SEC_PER_DAY},
+    utils, ReconcileReport, SolvencyReport,
+};
+use mpl_token_metadata::state::Metadata;
+use std::{cell::RefCell, cmp, rc::Rc};
+
+pub struct MintInfo {
+    pub authority: Option<Pubkey>,
+    pub decimals: u8,
+    pub fees: UiFees,
+    pub recipients: Vec<Recipient>,
+}
+
+pub struct NftInfo {
+    pub metadata: Metadata,
+    pub chill_metadata: ChillNftMetadata,
+    pub nft_type_name: Option<String>,
+}
+
+pub struct SnapshotEntry {
+    pub user: Pubkey,
+    pub staked_amount: u64,
+    pub pending_amount: u64,
+    pub rewarded_amount: u64,
+}
+
+pub struct LegacyAccounts {
+    pub staking_info: bool,
+    pub user_infos: Vec<Pubkey>,
+}
+
+pub struct ListedNft {
+    pub mint: Pubkey,
+    pub info: NftInfo,
+    /// `None` if the NFT is burned
+    pub owner: Option<Pubkey>,
+}
+
+pub fn balance(client: &Client, mint: Pubkey, account: Pubkey) -> Result<f64> {
+    client.ui_token_balance(account, mint)
+}
+
+pub fn info(client: &Client, mint: Pubkey, program_id: Pubkey) -> Result<MintInfo> {
+    let config = client.config(mint, program_id)?;
+    let mint_account = client.mint_account(mint)?;
+
+    Ok(MintInfo {
+        authority: mint_account.mint_authority.into(),
+        decimals: mint_account.decimals,
+        fees: config.fees.to_ui(mint_account.decimals),
+        recipients: config.recipients,
+    })
+}
+
+/// Returns the address of the created proxy wallet
+pub fn create_wallet(
+    client: &Client,
+    payer: Rc<dyn Signer>,
+    account: Pubkey,
+    primary_wallet: Pubkey,
+    program_id: Pubkey,
+) -> Result<(Pubkey, Signature)> {
+    let proxy_wallet = pda::proxy_wallet(account, primary_wallet, program_id);
+    let signature = client.create_wallet(payer, account, primary_wallet, program_id)?;
+
+    Ok((proxy_wallet, signature))
+}
+
+pub fn staking_audit(
+    client: &Client,
+    payer: Rc<dyn Signer>,
+    staking_info: Pubkey,
+    program_id: Pubkey,
+) -> Result<SolvencyReport> {
+    client.staking_verify_pool_solvency(payer, staking_info, program_id)
+}
+
+/// Reconciles all accounts of the staking in batches and sums their reports
+pub fn staking_reconcile(
+    client: &Client,
+    payer: Pubkey,
+    staking_info: Pubkey,
+    program_id: Pubkey,
+) -> Result<ReconcileReport> {
+    let user_infos = client
+        .all_user_infos(staking_info, program_id)?
+        .into_iter()
+        .map(|(pubkey, _)| pubkey)
+        .collect::<Vec<_>>();
+
+    let redistribution = pda::redistribution(staking_info, program_id);
+    let referrals = user_infos
+        .iter()
+        .map(|user_info| pda::referral(*user_info, program_id))
+        .chain([redistribution])
+        .collect::<Vec<_>>();
+
+    let existing = client.existing_accounts(&referrals)?;
+
+    // Referral infos must follow their user infos in the same batch
+    let mut groups = user_infos
+        .iter()
+        .zip(referrals.iter().zip(&existing))
+        .map(|(user_info, (referral, exists))| match exists {
+            true => vec![*user_info, *referral],
+            false => vec![*user_info],
+        })
+        .collect::<Vec<_>>();
+
+    if existing.last() == Some(&true) {
+        groups.push(vec![redistribution]);
+    }
+
+    let batch_size = client.staking_reconcile_batch_size(payer, staking_info, program_id);
+    let mut batches = vec![Vec::new()];
+    for group in groups {
+        let batch = batches.last_mut().unwrap();
+        if batch.len() + group.len() > batch_size {
+            batches.push(group);
+        } else {
+            batch.extend(group);
+        }
+    }
+
+    let mut total: Option<ReconcileReport> = None;
+    for batch in batches {
+        let report = client.staking_reconcile(payer, staking_info, &batch, program_id)?;
+        total = Some(match total {
+            None => report,
+            Some(total) => ReconcileReport {
+                users_number: total.users_number + report.users_number,
+                users_staked_amount: total.users_staked_amount + report.users_staked_amount,
+                users_rewarded_amount: total.users_rewarded_amount + report.users_rewarded_amount,
+                users_active_stakes_number: total.users_active_stakes_number
+                    + report.users_active_stakes_number,
+                ..report
+            },
+        });
+    }
+
+    Ok(total.unwrap())
+}
+
+/// Tokens of all users of the staking. Ended stakes are settled locally the
+/// way the program settles them on the next update of the user
+pub fn staking_snapshot(
+    client: &Client,
+    staking_info: Pubkey,
+    program_id: Pubkey,
+) -> Result<Vec<SnapshotEntry>> {
+    let mut staking_data = client
+        .account_data(staking_info)
+        .map_err(|_| CliError::StakingInfoNotFound(staking_info))?;
+    let staking = StakingInfo::try_deserialize_versioned(&staking_data)
+        .map_err(|_| CliError::StakingInfoDataError)?;
+
+    // The clock offset is only set by devnet tools
+    let timestamp = client.clock()?.unix_timestamp as u64;
+    let current_day = (timestamp + staking.clock_offset) / SEC_PER_DAY;
+
+    let total_days = staking.total_days()?;
+    let days_in_window = staking.days_in_window();
+    let days_len = total_days as usize * std::mem::size_of::<u64>();
+    let staking_version =
+        utils::layout_version(staking_data.len(), days_len, StakingInfo::fields_len)?;
+    let staked_amounts = LazyVector::<u64>::new(
+        StakingInfo::fields_len(staking_version).unwrap(),
+        total_days as usize,
+        std::mem::size_of::<u64>(),
+        Rc::new(RefCell::new(staking_data.as_mut_slice())),
+    )?;
+
+    let mut snapshot = Vec::new();
+    for (_, mut account) in client.all_user_info_accounts(staking_info, program_id)? {
+        let user_info = UserInfo::try_deserialize_versioned(&account.data)
+            .map_err(|_| CliError::UserInfoDataError)?;
+
+        let mut entry = SnapshotEntry {
+            user: user_info.user,
+            staked_amount: user_info.staked_amount,
+            pending_amount: user_info.pending_amount,
+            rewarded_amount: user_info.rewarded_amount,
+        };
+
+        if let Some(start_day) = user_info.start_day {
+            let stake_end_day = cmp::min(start_day + days_in_window, staking.end_day);
+            if current_day >= stake_end_day {
+                let boosted_days_number = days_in_window as usize;
+                let user_version = utils::layout_version(
+                    account.data.len(),
+                    boosted_days_number,
+                    UserInfo::fields_len,
+                )?;
+                let boosted_days = LazyVector::<bool>::new(
+                    UserInfo::fields_len(user_version).unwrap(),
+                    boosted_days_number,
+                    std::mem::size_of::<bool>(),
+                    Rc::new(RefCell::new(account.data.as_mut_slice())),
+                )?;
+
+                let (reward, _) = utils::calculate_user_reward_with_unspent_rewards(
+                    user_info.staked_amount,
+                    start_day - staking.start_day,
+                    &boosted_days,
+                    &staked_amounts,
+                    total_days,
+                    days_in_window,
+                    user_info.daily_staking_reward,
+                )?;
+
+                entry.rewarded_amount += reward;
+                entry.pending_amount += entry.staked_amount;
+                entry.staked_amount = 0;
+            }
+        }
+
+        snapshot.push(entry);
+    }
+
+    Ok(snapshot)
+}
+
+/// Accounts of the staking which still have a layout older than the current
+/// one of the program. Versions are found by the lengths of the accounts
+pub fn staking_legacy_accounts(
+    client: &Client,
+    staking_info: Pubkey,
+    program_id: Pubkey,
+) -> Result<LegacyAccounts> {
+    let staking_data = client
+        .account_data(staking_info)
+        .map_err(|_| CliError::StakingInfoNotFound(staking_info))?;
+    let staking = StakingInfo::try_deserialize_versioned(&staking_data)
+        .map_err(|_| CliError::StakingInfoDataError)?;
+
+    let days_len = staking.total_days()? as usize * std::mem::size_of::<u64>();
+    let staking_version =
+        utils::layout_version(staking_data.len(), days_len, StakingInfo::fields_len)?;
+
+    let days_in_window = staking.days_in_window() as usize;
+    let user_infos = client
+        .all_user_info_accounts(staking_info, program_id)?
+        .into_iter()
+        .filter_map(|(pubkey, account)| {
+            utils::layout_version(account.data.len(), days_in_window, UserInfo::fields_len)
+                .ok()
+                .filter(|version| *version < UserInfo::VERSION)
+                .map(|_| pubkey)
+        })
+        .collect();
+
+    Ok(LegacyAccounts {
+        staking_info: staking_version < StakingInfo::VERSION,
+        user_infos,
+    })
+}
+
+pub fn configs(client: &Client, program_id: Pubkey) -> Result<Vec<(Pubkey, Config)>> {
+    client.registered_configs(program_id)
+}
+
+/// Resolves built-in NFT types by their names and custom ones by the registry
+/// of the config
+pub fn nft_type(client: &Client, name: &str, mint: Pubkey, program_id: Pubkey) -> Result<NftType> {
+    if let Ok(nft_type) = NftType::try_from(name) {
+        return Ok(nft_type);
+    }
+
+    client
+        .nft_type_registry(mint, program_id)?
+        .and_then(|registry| registry.find(name))
+        .ok_or_else(|| CliError::UnknownNftType(name.to_owned()).into())
+}
+
+/// Returns both metadata accounts of the NFT. The name of a custom type is
+/// looked up in the registry of the config that minted the NFT
+pub fn nft(
+    client: &Client,
+    nft_mint: Pubkey,
+    chill_mint: Pubkey,
+    program_id: Pubkey,
+) -> Result<NftInfo> {
+    let metadata = client.metadata_account(nft_mint)?;
+    let chill_metadata = client.chill_metadata(nft_mint, program_id)?;
+
+    let registry = match chill_metadata.nft_type {
+        NftType::Custom(_) => client.nft_type_registry(chill_mint, program_id)?,
+        _ => None,
+    };
+
+    Ok(NftInfo {
+        metadata,
+        nft_type_name: nft_type_name(chill_metadata.nft_type, registry.as_ref()),
+        chill_metadata,
+    })
+}
+
+fn nft_type_name(nft_type: NftType, registry: Option<&NftTypeRegistry>) -> Option<String> {
+    match nft_type {
+        NftType::Custom(index) => {
+            registry.and_then(|registry| {
+                registry
+                    .types
+                    .get(usize::from(index))
+                    .map(|t| t.name.clone())
+            })
+        }
+        NftType::Character => Some("character".to_owned()),
+        NftType::Pet => Some("pet".to_owned()),
+        NftType::Emote => Some("emote".to_owned()),
+        NftType::Tileset => Some("tileset".to_owned()),
+        NftType::Item => Some("item".to_owned()),
+        NftType::World => Some("world".to_owned()),
+    }
+}
+
+/// Lists NFTs minted by the program for the primary wallet, which is the
+/// update authority of their Metaplex metadata. NFTs are sorted by their names
+pub fn nfts(
+    client: &Client,
+    primary_wallet: Pubkey,
+    chill_mint: Pubkey,
+    owner: Option<Pubkey>,
+    nft_type: Option<NftType>,
+    program_id: Pubkey,
+) -> Result<Vec<ListedNft>> {
+    let mut chill_metadata_accounts = client.chill_metadata_accounts(nft_type, program_id)?;
+    let owned_mints = owner
+        .map(|owner| client.owned_nft_mints(owner))
+        .transpose()?;
+
+    let mut nfts = Vec::new();
+    for metadata in client.metadata_accounts_by_update_authority(primary_wallet)? {
+        let mint = metadata.mint;
+        if owned_mints.as_ref().is_some_and(|mints| !mints.contains(&mint)) {
+            continue;
+        }
+
+        // NFTs of other types and of other programs have no chill metadata here
+        let chill_metadata_pubkey = pda::chill_metadata(mint, program_id);
+        if let Some(chill_metadata) = chill_metadata_accounts.remove(&chill_metadata_pubkey) {
+            nfts.push((mint, metadata, chill_metadata));
+        }
+    }
+
+    nfts.sort_by(|(_, a, _), (_, b, _)| a.data.name.cmp(&b.data.name));
+
+    let registry = client.nft_type_registry(chill_mint, program_id)?;
+    let owners = match owner {
+        Some(owner) => vec![Some(owner); nfts.len()],
+        None => {
+            let mints = nfts.iter().map(|(mint, _, _)| *mint).collect::<Vec<_>>();
+            client.nft_owners(&mints)?
+        }
+    };
+
+    Ok(nfts
+        .into_iter()
+        .zip(owners)
+        .map(|((mint, metadata, chill_metadata), owner)| ListedNft {
+            mint,
+            info: NftInfo {
+                metadata,
+                nft_type_name: nft_type_name(chill_metadata.nft_type, registry.as_ref()),
+                chill_metadata,
+            },
+            owner,
+        })
+        .collect())
+}
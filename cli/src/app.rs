@@ -1,12 +1,20 @@
 use crate::{
+    api,
     cli::{Cli, CliCommand},
-    client::Client,
+    client::{Client, TransactionMode},
+    diff::{self, FieldDiff},
+    drop::{self, DropTree},
     error::{AppError, CliError, Result},
+    manifest::{self, ManifestNft},
+    metadata_uri,
+    output::Output,
     pda,
+    qr::QrCode,
 };
 use anchor_client::{
     solana_sdk::{
-        native_token::sol_to_lamports,
+        instruction::Instruction,
+        native_token::{lamports_to_sol, sol_to_lamports},
         program_option::COption,
         pubkey::Pubkey,
         signature::{Keypair, Signature},
@@ -14,10 +22,30 @@ use anchor_client::{
     },
     Cluster,
 };
-use chill_nft::state::Fees;
+use chill_drop::state::DropKind;
+use chill_nft::{
+    state::{Config, Fees, NftType},
+    utils::{BatchNft, NftArgs, NftCreator},
+};
+use chill_staking::{
+    state::{StakingInfo, UserInfo, SEC_PER_DAY},
+    SolvencyReport,
+};
+use chill_wallet::state::{DailyLimits, SpendingLimit};
 use colored::Colorize;
+use serde_json::{json, Map, Value};
+use spl_associated_token_account::get_associated_token_address;
 use spl_token::native_mint;
-use std::{fs, path::Path, process::exit, rc::Rc};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    io,
+    path::Path,
+    process::exit,
+    rc::Rc,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
 use std::fmt::Write as FmtWrite;
 use std::io::Write as IoWrite;
 pub enum ProcessedData {
@@ -28,37 +56,74 @@ pub enum ProcessedData {
         wallet: Pubkey,
         signature: Signature
     },
+    CreateWallets {
+        wallets: Vec<Pubkey>,
+        failed: Vec<Pubkey>,
+    },
+    DepositAddress {
+        wallet: Pubkey,
+        token_account: Option<Pubkey>,
+    },
+    StakingAudit(SolvencyReport),
+    Configs(Vec<(Pubkey, Config)>),
 }
 
 pub struct App<'cli> {
     cli: Cli<'cli>,
     client: Client,
+    output: Output,
 }
 
 impl App<'_> {
     pub fn init() -> Self {
         let cli = Cli::init();
-        let client = Client::init(&cli.rpc_url());
+        let output = Output::new(cli.output_format());
+        let client = Self::client(&cli).unwrap_or_else(|error| {
+            output.finish(Some(&error));
+            exit(1);
+        });
 
-        App { cli, client }
+        App { cli, client, output }
     }
 
     pub fn init_from_save(arguments: &[&str]) -> Result<Self> {
         let cli = Cli::init_from_save(arguments)?;
-        let client = Client::init(&cli.rpc_url());
+        let client = Self::client(&cli)?;
+        let output = Output::new(cli.output_format());
+
+        Ok(App { cli, client, output })
+    }
 
-        Ok(App { cli, client })
+    pub fn init_with_signers(
+        arguments: &[&str],
+        signers: HashMap<String, Rc<dyn Signer>>,
+    ) -> Result<Self> {
+        let cli = Cli::init_with_signers(arguments, signers)?;
+        let client = Self::client(&cli)?;
+        let output = Output::new(cli.output_format());
+
+        Ok(App { cli, client, output })
+    }
+
+    fn client(cli: &Cli) -> Result<Client> {
+        Ok(Client::init(&cli.rpc_url(), cli.rpc_budget())
+            .with_transaction_mode(cli.transaction_mode())
+            .with_blockhash(cli.blockhash())
+            .with_compute_budget(cli.compute_budget())
+            .with_send_retries(cli.send_retries())
+            .with_nonce(cli.nonce()?))
     }
 
     fn on_error(&self, error: AppError) -> ! {
-        println!("{}", error);
+        self.output.finish(Some(&error));
         exit(1);
     }
 
     fn try_to_airdrop(&self, address: Pubkey) -> Result<()> {
         if self.client.balance(address)? == 0 {
             if self.cli.cluster() == Cluster::Mainnet {
-                println!("{}", "You have to top up your balance".red());
+                self.output.message("You have to top up your balance".red());
+                self.output.finish(None);
                 exit(0);
             } else {
                 self.client.airdrop(address, sol_to_lamports(1.0))?;
@@ -78,7 +143,7 @@ impl App<'_> {
         let path = Path::new(save_path);
         let full_path = fs::canonicalize(path).unwrap();
         let full_path_str = full_path.as_os_str().to_str().unwrap();
-        println!("{} \"{}\"", "Mint file:".cyan(), full_path_str);
+        self.output.value("Mint file:".cyan(), full_path_str);
         Ok(())
     }
 
@@ -118,35 +183,301 @@ impl App<'_> {
         }
 
         let mint = self.client.create_mint(authority, payer, decimals)?;
-        println!("{} {}", "Mint:".cyan(), mint);
+        self.output.value("Mint:".cyan(), mint.to_string());
 
         self.save_mint(mint)?;
         Ok(mint)
     }
 
+    /// Transactions are neither simulated nor signed only to submit them later
+    fn is_sending(&self) -> bool {
+        self.cli.transaction_mode() == TransactionMode::Send
+    }
+
+    /// Signatures are always an array in JSON, some commands send several transactions.
+    /// Simulated and signed only transactions are not sent, so they have no signatures to print
     fn print_signature(&self, signature: &Signature) {
-        println!("{} {}", "Signature:".cyan(), signature);
+        if !self.is_sending() {
+            return;
+        }
+
+        if self.output.is_json() {
+            self.output.push("signatures", signature.to_string().into());
+        } else {
+            println!("{} {}", "Signature:".cyan(), signature);
+        }
+    }
+
+    fn print_simulations(&self) {
+        for simulation in self.client.simulations() {
+            if self.output.is_json() {
+                self.output.push("simulations", json!({
+                    "units_consumed": simulation.units_consumed,
+                    "logs": simulation.logs,
+                    "error": simulation.error,
+                }));
+                continue;
+            }
+
+            match simulation.error {
+                Some(error) => println!("{} {}", "Simulation failed:".red(), error),
+                None => println!("{}", "Simulation succeeded".green()),
+            }
+
+            if let Some(units_consumed) = simulation.units_consumed {
+                println!("{} {}", "Compute units:".cyan(), units_consumed);
+            }
+
+            println!("{}", "Logs:".cyan());
+            for log in simulation.logs {
+                println!("  {}", log);
+            }
+        }
+    }
+
+    /// Signed only transactions are printed with their signatures, so the rest of the
+    /// signers know what to sign
+    fn print_signed_transactions(&self) {
+        for transaction in self.client.signed_transactions() {
+            let encoded = base64::encode(bincode::serialize(&transaction).unwrap());
+            let absent_signers = Client::absent_signers(&transaction);
+            let signers = transaction
+                .signatures
+                .iter()
+                .zip(&transaction.message.account_keys)
+                .filter(|(_, pubkey)| !absent_signers.contains(pubkey))
+                .map(|(signature, pubkey)| format!("{}={}", pubkey, signature))
+                .collect::<Vec<_>>();
+
+            if self.output.is_json() {
+                self.output.push("transactions", json!({
+                    "transaction": encoded,
+                    "blockhash": transaction.message.recent_blockhash.to_string(),
+                    "signers": signers,
+                    "absent_signers": absent_signers
+                        .iter()
+                        .map(|pubkey| pubkey.to_string())
+                        .collect::<Vec<_>>(),
+                }));
+                continue;
+            }
+
+            println!("{} {}", "Transaction:".cyan(), encoded);
+            println!("{} {}", "Blockhash:".cyan(), transaction.message.recent_blockhash);
+            println!("{}", "Signers:".cyan());
+            for signer in signers {
+                println!("  {}", signer);
+            }
+
+            if !absent_signers.is_empty() {
+                println!("{}", "Absent signers:".yellow());
+                for pubkey in absent_signers {
+                    println!("  {}", pubkey);
+                }
+            }
+        }
+    }
+
+    fn print_rpc_usage(&self) {
+        let usage = self.client.rpc_usage();
+        let total = usage.iter().map(|(_, count)| count).sum::<u64>();
+
+        self.output.value("RPC calls:".cyan(), total);
+        if self.output.is_json() {
+            let calls = usage
+                .into_iter()
+                .map(|(method, count)| (method, count.into()))
+                .collect::<Map<_, _>>();
+            self.output.record("rpc_usage", Value::Object(calls));
+            return;
+        }
+
+        for (method, count) in usage {
+            println!("  {}: {}", method, count);
+        }
+    }
+
+    fn save_staking_info(&self, staking_info: Pubkey) -> Result<()> {
+        let file_name = "staking_info.pubkey";
+        let mut file = fs::OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(file_name)?;
+
+        writeln!(file, "{}", staking_info)
+            .map_err(|_| CliError::CannotWriteToFile(file_name.to_owned()).into())
+    }
+
+    // Remaining reward tokens are only released after the end day of the staking
+    fn assert_staking_finished(&self, staking_info: Pubkey, staking: &StakingInfo) -> Result<()> {
+        let current_day = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            / SEC_PER_DAY;
+
+        if current_day < staking.end_day {
+            return Err(CliError::StakingIsNotFinished(staking_info).into());
+        }
+
+        Ok(())
+    }
+
+    fn save_admin_audit_record(&self, action: &str, program_id: Pubkey, result: &str) -> Result<()> {
+        let file_name = "admin-audit.log";
+        let mut file = fs::OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(file_name)?;
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        writeln!(
+            file,
+            "{} {} {} {} {} {}",
+            timestamp,
+            self.cli.rpc_url(),
+            action,
+            program_id,
+            self.cli.primary_wallet_pubkey()?,
+            result
+        )
+        .map_err(|_| CliError::CannotWriteToFile(file_name.to_owned()).into())
+    }
+
+    fn confirm_transaction(&self) -> Result<()> {
+        if self.cli.yes() || !self.is_sending() {
+            return Ok(());
+        }
+
+        // The prompt goes to stderr to keep the JSON output parsable
+        eprint!("Send the transaction? [y/N] ");
+        io::stderr().flush()?;
+
+        let mut answer = String::new();
+        io::stdin().read_line(&mut answer)?;
+        match answer.trim().to_lowercase().as_str() {
+            "y" | "yes" => Ok(()),
+            _ => Err(CliError::TransactionCancelled.into()),
+        }
+    }
+
+    /// All privileged operations are confirmed or exported for a multisig and logged locally
+    fn process_admin(
+        &self,
+        action: &str,
+        instructions: &[Instruction],
+        program_id: Pubkey,
+    ) -> Result<ProcessedData> {
+        self.output.value("Action:".cyan(), action);
+        self.output.value("Program:".cyan(), program_id.to_string());
+        self.output.value("Primary wallet:".cyan(), self.cli.primary_wallet_pubkey()?.to_string());
+
+        if self.cli.export() {
+            let payer = self.cli.payer_pubkey()?;
+            let message = self.client.export_message(instructions, payer)?;
+            self.output.value("Message:".cyan(), message);
+
+            self.save_admin_audit_record(action, program_id, "exported")?;
+            return Ok(ProcessedData::Other);
+        }
+
+        self.confirm_transaction()?;
+
+        let payer = self.cli.payer()?;
+        let mut signers = self.cli.admin_signers()?;
+        signers.push(self.cli.primary_wallet()?);
+
+        let signature = self
+            .client
+            .send_admin_transaction(instructions, payer, &signers)?;
+
+        // Simulated and signed only operations are not recorded, nothing is sent
+        if !self.is_sending() {
+            return Ok(ProcessedData::Other);
+        }
+
+        self.print_signature(&signature);
+        self.save_admin_audit_record(action, program_id, &signature.to_string())?;
+
+        Ok(ProcessedData::Other)
+    }
+
+    /// Selects signers of the admin config of the primary wallet among the ones
+    /// passed with `--admin-signer` or the primary wallet itself
+    fn admin_signers(
+        &self,
+        primary_wallet: Pubkey,
+        program_id: Pubkey,
+    ) -> Result<Vec<Rc<dyn Signer>>> {
+        let mut candidates = self.cli.admin_signers()?;
+        if candidates.is_empty() {
+            candidates.push(self.cli.primary_wallet()?);
+        }
+
+        self.client.admin_signers(primary_wallet, &candidates, program_id)
+    }
+
+    /// Appends signers of the admin config which approve the instruction, they
+    /// sign the transaction along with the primary wallet
+    fn append_admin_signers(&self, ix: &mut Instruction, primary_wallet: Pubkey) -> Result<()> {
+        let signers = self.admin_signers(primary_wallet, chill_nft::ID)?;
+        let signers = signers.iter().map(|s| s.pubkey()).collect::<Vec<_>>();
+        ix.accounts.extend(chill_sdk::instruction::admin_signer_metas(&signers));
+        Ok(())
+    }
+
+    /// Built-in types are resolved by name, other ones are looked up in the NFT
+    /// type registry of the config
+    fn resolve_nft_type(&self, name: &str, mint: Pubkey, program_id: Pubkey) -> Result<NftType> {
+        api::nft_type(&self.client, name, mint, program_id)
+    }
+
+    fn read_pubkeys(&self, path: &str) -> Result<Vec<Pubkey>> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| CliError::CannotParseFile(path.to_owned(), e.to_string()))?;
+
+        let mut unique = HashSet::new();
+        let mut pubkeys = Vec::new();
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let pubkey = Pubkey::from_str(line).map_err(|e| {
+                CliError::CannotParseFile(path.to_owned(), format!("line {}: {}", index + 1, e))
+            })?;
+
+            if unique.insert(pubkey) {
+                pubkeys.push(pubkey);
+            }
+        }
+
+        Ok(pubkeys)
     }
 
     fn print_balance(&self, address: Pubkey, mint: Pubkey) -> Result<ProcessedData> {
-        let balance = self.client.ui_token_balance(address, mint)?;
-        println!("{} {} tokens", "Balance:".green().bold(), balance);
+        let balance = api::balance(&self.client, mint, address)?;
+        self.output.value_with_unit("Balance:".green().bold(), balance, "tokens");
 
         Ok(ProcessedData::Balance(balance))
     }
 
     fn print_info(&self, mint: Pubkey, program_id: Pubkey) -> Result<ProcessedData> {
-        let config = self.client.config(mint, program_id)?;
-        let mint_account = self.client.mint_account(mint)?;
+        let info = api::info(&self.client, mint, program_id)?;
 
         let mut print_string = String::new();
         writeln!(&mut print_string,
             "{0} {1}",
             "Authority:".green().bold(),
-            mint_account.mint_authority.unwrap()
+            info.authority.unwrap()
         )?;
 
-        let fees = config.fees.to_ui(mint_account.decimals);
+        let fees = info.fees;
         writeln!(&mut print_string, "\n{0}", "======= MINT FEES =======".cyan().bold())?;
         writeln!(&mut print_string, "{0:>10} {1}", "Character:".cyan(), fees.character)?;
         writeln!(&mut print_string, "{0:>10} {1}", "Pet:".cyan(), fees.pet)?;
@@ -155,7 +486,7 @@ impl App<'_> {
         writeln!(&mut print_string, "{0:>10} {1}", "Item:".cyan(), fees.item)?;
         writeln!(&mut print_string, "{0:>10} {1}", "World:".cyan(), fees.world)?;
 
-        let recipients = config.recipients;
+        let recipients = info.recipients;
         if !recipients.is_empty() {
             writeln!(&mut print_string, "\n{0}", "======= RECIPIENTS =======".bright_blue().bold())?;
             let recipients_info = recipients
@@ -175,7 +506,31 @@ impl App<'_> {
 
             writeln!(&mut print_string, "{}", recipients_info.trim())?;
         }
-        print!("{}", print_string);
+        if self.output.is_json() {
+            self.output.record("authority", info.authority.unwrap().to_string().into());
+            self.output.record("fees", json!({
+                "character": fees.character,
+                "pet": fees.pet,
+                "emote": fees.emote,
+                "tileset": fees.tileset,
+                "item": fees.item,
+                "world": fees.world,
+            }));
+
+            let recipients = recipients
+                .iter()
+                .map(|r| {
+                    json!({
+                        "address": r.address.to_string(),
+                        "mint_share": r.mint_share,
+                        "transaction_share": r.transaction_share,
+                    })
+                })
+                .collect();
+            self.output.record("recipients", Value::Array(recipients));
+        } else {
+            print!("{}", print_string);
+        }
 
         Ok(ProcessedData::Info(print_string))
     }
@@ -214,8 +569,15 @@ impl App<'_> {
 
         let mint_chill = self.get_mint()?;
         let args = self.cli.mint_args()?;
-        let nft_type = self.cli.nft_type();
+        let min_game_version = self.cli.min_game_version().unwrap_or_default();
+        let collection = self.cli.collection()?;
+        let uses = self.cli.uses();
         let program_id = self.cli.nft_program_id();
+        let nft_type = self.resolve_nft_type(self.cli.nft_type(), mint_chill, program_id)?;
+
+        if self.cli.validate_uri() {
+            self.validate_metadata_uri(&args)?;
+        }
 
         let (nft_mint, _nft_token) = self.client.create_mint_and_token_nft(
             primary_wallet.clone(),
@@ -223,7 +585,7 @@ impl App<'_> {
             recipient,
         )?;
 
-        println!("{0} {1}", "NFT Mint:".green(), nft_mint);
+        self.output.value("NFT Mint:".green(), nft_mint.to_string());
 
         let signature = self.client.mint_nft(
             primary_wallet,
@@ -233,6 +595,9 @@ impl App<'_> {
             nft_mint,
             nft_type,
             args,
+            min_game_version,
+            collection,
+            uses,
             program_id,
         )?;
 
@@ -241,126 +606,202 @@ impl App<'_> {
         Ok(ProcessedData::Other)
     }
 
-    fn process_update_nft(&self) -> Result<ProcessedData> {
-        let payer = self.cli.payer()?;
-        let primary_wallet = self.cli.primary_wallet()?;
-        let nft_mint = self.get_mint()?;
-        let args = self.cli.mint_args()?;
-        let program_id = self.cli.nft_program_id();
-
-        let signature =
-            self.client
-                .update_nft(payer, primary_wallet, nft_mint, args, program_id)?;
-
-        self.print_signature(&signature);
+    /// Missing fields abort minting, mismatches with the name or the symbol
+    /// abort it only on mainnet
+    fn validate_metadata_uri(&self, args: &NftArgs) -> Result<()> {
+        let json = metadata_uri::fetch(&args.uri)?;
+        let problems = metadata_uri::check(&json, &args.name, &args.symbol);
+        for problem in &problems {
+            self.output.message(format!("{} {}", "Warning:".yellow(), problem));
+        }
 
-        Ok(ProcessedData::Other)
-    }
+        let is_mainnet = self.cli.cluster() == Cluster::Mainnet;
+        if problems.iter().any(|problem| problem.is_fatal() || is_mainnet) {
+            return Err(CliError::InvalidMetadataUri(args.uri.clone()).into());
+        }
 
-    fn process_print_info(&self) -> Result<ProcessedData> {
-        let mint = self.get_mint()?;
-        let program_id = self.cli.nft_program_id();
-        self.print_info(mint, program_id)
+        Ok(())
     }
 
-    fn process_print_balance(&self) -> Result<ProcessedData> {
-        let account = self.cli.account();
-        let mint = self.get_mint()?;
-        self.print_balance(account, mint)
+    /// Sends the transaction again until it succeeds or the retries run out
+    fn retry<T>(&self, f: impl Fn() -> Result<T>) -> Result<T> {
+        let retries = self.cli.retries();
+        let mut attempt = 0;
+        loop {
+            match f() {
+                Err(error) if attempt < retries && !self.client.rpc_budget_exhausted() => {
+                    attempt += 1;
+                    eprintln!("{} {}: {}", "Retrying".yellow(), attempt, error);
+                }
+                result => return result,
+            }
+        }
     }
 
-    fn process_transfer(&self) -> Result<ProcessedData> {
-        let primary_wallet = self.cli.primary_wallet()?;
+    fn process_mint_nft_batch(&self) -> Result<ProcessedData> {
         let payer = self.cli.payer()?;
-        let mint = self.get_mint()?;
-
-        let ui_amount = self.cli.ui_amount();
-        let recipient = self.cli.recipient();
+        let primary_wallet = self.cli.primary_wallet()?;
+        let manifest_path = self.cli.manifest();
 
-        if ui_amount == 0.0 {
-            return Err(CliError::TransferZeroTokens.into());
-        }
+        self.try_to_airdrop(payer.pubkey())?;
 
-        let current_balance = self
-            .client
-            .ui_token_balance(primary_wallet.pubkey(), mint)?;
+        let mint_chill = self.get_mint()?;
+        let fees = self.cli.nft_fees()?;
+        let min_game_version = self.cli.min_game_version().unwrap_or_default();
+        let collection = self.cli.collection()?;
+        let program_id = self.cli.nft_program_id();
 
-        if ui_amount > current_balance {
-            return Err(CliError::InsufficientTokens(ui_amount, current_balance).into());
+        let mut nfts = manifest::read(manifest_path)?;
+        let mut nft_types = HashMap::new();
+        for nft in &nfts {
+            if !nft_types.contains_key(&nft.nft_type) {
+                let nft_type = self.resolve_nft_type(&nft.nft_type, mint_chill, program_id)?;
+                nft_types.insert(nft.nft_type.clone(), nft_type);
+            }
         }
 
-        let mint_account = self.client.mint_account(mint)?;
-        let decimals = mint_account.decimals;
-        let amount = spl_token::ui_amount_to_amount(ui_amount, decimals);
+        let minted_before = nfts.iter().filter(|nft| nft.is_minted()).count();
+        self.output.value("Minted before:".cyan(), minted_before);
 
-        let primary_wallet_pubkey = primary_wallet.pubkey();
-        let signature =
-            self.client
-                .transfer_tokens(primary_wallet, payer, mint, recipient, amount)?;
+        // Mints are created first, the NFTs created by a previous run keep their mints
+        let pending = (0..nfts.len())
+            .filter(|index| nfts[*index].mint.is_none())
+            .collect::<Vec<_>>();
+        let batch_size = self
+            .client
+            .create_nft_mints_batch_size(payer.pubkey(), primary_wallet.pubkey());
+
+        for batch in pending.chunks(batch_size) {
+            let recipients = batch.iter().map(|index| nfts[*index].recipient).collect::<Vec<_>>();
+            match self.retry(|| {
+                self.client
+                    .create_nft_mints(primary_wallet.clone(), payer.clone(), &recipients)
+            }) {
+                Ok((mints, signature)) => {
+                    self.print_signature(&signature);
+                    for (index, mint) in batch.iter().zip(mints) {
+                        nfts[*index].mint = Some(mint);
+                    }
+                }
+                Err(error) => {
+                    self.output.message(&error);
+                    for index in batch {
+                        nfts[*index].error = Some(error.to_string());
+                    }
+                }
+            }
+        }
 
-        self.print_signature(&signature);
-        self.print_balance(primary_wallet_pubkey, mint)?;
-        Ok(ProcessedData::Other)
-    }
+        let pending = (0..nfts.len())
+            .filter(|index| nfts[*index].mint.is_some() && !nfts[*index].is_minted())
+            .collect::<Vec<_>>();
+        let nft_mints = pending
+            .iter()
+            .map(|index| nfts[*index].mint.unwrap())
+            .collect::<Vec<_>>();
+        let batch_nfts = pending
+            .iter()
+            .map(|index| {
+                let nft = &nfts[*index];
+                BatchNft {
+                    nft_type: nft_types[&nft.nft_type],
+                    args: NftArgs {
+                        name: nft.name.clone(),
+                        symbol: nft.symbol.clone(),
+                        uri: nft.uri.clone(),
+                        fees,
+                    },
+                    creator: None,
+                    min_game_version,
+                    uses: None,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut start = 0;
+        while start < pending.len() {
+            let batch_size = self.client.mint_nft_batch_size(
+                payer.pubkey(),
+                primary_wallet.pubkey(),
+                mint_chill,
+                &nft_mints[start..],
+                &batch_nfts[start..],
+                collection,
+                program_id,
+            )?;
+
+            // An NFT which doesn't fit alone is sent to report the error
+            let end = start + batch_size.max(1);
+            let result = self.retry(|| {
+                self.client.mint_nft_batch(
+                    primary_wallet.clone(),
+                    payer.clone(),
+                    mint_chill,
+                    &nft_mints[start..end],
+                    batch_nfts[start..end].to_vec(),
+                    collection,
+                    program_id,
+                )
+            });
+
+            for index in &pending[start..end] {
+                let nft = &mut nfts[*index];
+                match &result {
+                    Ok(signature) => {
+                        nft.signature = Some(*signature);
+                        nft.error = None;
+                    }
+                    Err(error) => nft.error = Some(error.to_string()),
+                }
+            }
 
-    pub fn process_nft_initialize(&self) -> Result<ProcessedData> {
-        let payer = self.cli.payer()?;
-        let primary_wallet = self.cli.primary_wallet()?;
-        let mint = self.get_mint()?;
-        let program_id = self.cli.nft_program_id();
+            match result {
+                Ok(signature) => self.print_signature(&signature),
+                Err(error) => self.output.message(error),
+            }
 
-        self.assert_mint_authority(mint, primary_wallet.pubkey())?;
+            start = end;
+        }
 
-        let ui_fees = self.cli.fees();
+        let results_path = manifest::results_path(manifest_path);
+        manifest::write(&results_path, &nfts)?;
 
-        let recipients = self.cli.multiple_recipients()?;
-        let mint_account = self.client.mint_account(mint)?;
-        let fees = Fees::from_ui(ui_fees, mint_account.decimals);
+        let failed = nfts.iter().filter(|nft| !nft.is_minted()).collect::<Vec<&ManifestNft>>();
+        self.output.value("Minted NFTs:".green(), nfts.len() - failed.len());
+        if !failed.is_empty() {
+            self.output.value("Failed NFTs:".red(), failed.len());
+        }
 
-        self.client
-            .initialize(primary_wallet, payer, mint, fees, recipients, program_id)?;
+        self.output.value("Results:".cyan(), results_path);
+        self.print_rpc_usage();
 
-        self.print_info(mint, program_id)?;
         Ok(ProcessedData::Other)
     }
 
-    pub fn process_create_wallet(&self) -> Result<ProcessedData> {
+    fn process_create_collection(&self) -> Result<ProcessedData> {
         let payer = self.cli.payer()?;
-        let primary_wallet = self.cli.primary_wallet_pubkey();
-        let account = self.cli.account();
-        let program_id = self.cli.wallet_program_id();
-
-        let proxy_wallet = pda::proxy_wallet(account, primary_wallet, program_id);
-
-        let signature =
-            self.client
-                .create_wallet(payer, account, proxy_wallet, primary_wallet, program_id)?;
-
-        println!("{} {}", "Wallet:".green(), proxy_wallet);
-        self.print_signature(&signature);
+        let primary_wallet = self.cli.primary_wallet()?;
 
-        Ok(ProcessedData::CreateWallet { wallet: proxy_wallet, signature: signature })
-    }
+        self.try_to_airdrop(payer.pubkey())?;
 
-    pub fn process_withdraw_lamports(&self) -> Result<ProcessedData> {
-        let account = self.cli.account();
-        let authority = self.cli.authority()?;
-        let payer = self.cli.payer()?;
-        let primary_wallet = self.cli.primary_wallet_pubkey();
-        let recipient = self.cli.recipient();
-        let program_id = self.cli.wallet_program_id();
+        let mint_chill = self.get_mint()?;
+        let args = self.cli.mint_args()?;
+        let program_id = self.cli.nft_program_id();
 
-        let ui_amount = self.cli.ui_amount();
-        let amount = spl_token::ui_amount_to_amount(ui_amount, native_mint::DECIMALS);
+        let (collection_mint, _collection_token) = self.client.create_mint_and_token_nft(
+            primary_wallet.clone(),
+            payer.clone(),
+            primary_wallet.pubkey(),
+        )?;
 
-        let proxy_wallet = pda::proxy_wallet(account, primary_wallet, program_id);
+        self.output.value("Collection Mint:".green(), collection_mint.to_string());
 
-        let signature = self.client.withdraw_lamports(
+        let signature = self.client.create_collection(
+            primary_wallet,
             payer,
-            authority,
-            proxy_wallet,
-            recipient,
-            amount,
+            mint_chill,
+            collection_mint,
+            args,
             program_id,
         )?;
 
@@ -369,27 +810,36 @@ impl App<'_> {
         Ok(ProcessedData::Other)
     }
 
-    pub fn process_withdraw_ft(&self) -> Result<ProcessedData> {
-        let account = self.cli.account();
-        let authority = self.cli.authority()?;
+    fn process_update_nft(&self) -> Result<ProcessedData> {
         let payer = self.cli.payer()?;
-        let primary_wallet = self.cli.primary_wallet_pubkey();
-        let recipient = self.cli.recipient();
-        let mint = self.get_mint()?;
-        let program_id = self.cli.wallet_program_id();
-
-        let ui_amount = self.cli.ui_amount();
-        let amount = spl_token::ui_amount_to_amount(ui_amount, native_mint::DECIMALS);
-
-        let proxy_wallet = pda::proxy_wallet(account, primary_wallet, program_id);
+        let primary_wallet = self.cli.primary_wallet()?;
+        let nft_mint = self.get_mint()?;
+        let args = self.cli.mint_args()?;
+        let min_game_version = self.cli.min_game_version();
+        let primary_sale_happened = self.cli.primary_sale_happened();
+        let program_id = self.cli.nft_program_id();
 
-        let signature = self.client.withdraw_ft(
+        let creators = self.cli.creator_share().map(|(creator, share)| {
+            vec![
+                NftCreator {
+                    address: primary_wallet.pubkey(),
+                    share: 100 - share,
+                },
+                NftCreator {
+                    address: creator,
+                    share,
+                },
+            ]
+        });
+
+        let signature = self.client.update_nft(
             payer,
-            authority,
-            proxy_wallet,
-            recipient,
-            mint,
-            amount,
+            primary_wallet,
+            nft_mint,
+            args,
+            creators,
+            primary_sale_happened,
+            min_game_version,
             program_id,
         )?;
 
@@ -398,94 +848,1798 @@ impl App<'_> {
         Ok(ProcessedData::Other)
     }
 
-    pub fn process_withdraw_nft(&self) -> Result<ProcessedData> {
-        let account = self.cli.account();
-        let authority = self.cli.authority()?;
-        let payer = self.cli.payer()?;
-        let primary_wallet = self.cli.primary_wallet_pubkey();
-        let recipient = self.cli.recipient();
-        let mint = self.get_mint()?;
-        let program_id = self.cli.wallet_program_id();
+    fn process_admin_nft_finalize(&self) -> Result<ProcessedData> {
+        let primary_wallet = self.cli.primary_wallet_pubkey()?;
+        let nft_mint = self.get_mint()?;
+        let program_id = self.cli.nft_program_id();
+
+        let ix = Client::finalize_nft_instruction(primary_wallet, nft_mint, program_id);
+        self.process_admin("nft-finalize", &[ix], program_id)
+    }
+
+    fn process_admin_nft_set_min_game_version(&self) -> Result<ProcessedData> {
+        let primary_wallet = self.cli.primary_wallet_pubkey()?;
+        let nft_mint = self.get_mint()?;
+        let min_game_version = self.cli.min_game_version().unwrap();
+        let program_id = self.cli.nft_program_id();
+
+        let ix = Client::update_min_game_version_instruction(
+            primary_wallet,
+            nft_mint,
+            min_game_version,
+            program_id,
+        );
+
+        self.process_admin("nft-set-min-game-version", &[ix], program_id)
+    }
+
+    fn process_admin_config_update(&self) -> Result<ProcessedData> {
+        let primary_wallet = self.cli.primary_wallet_pubkey()?;
+        let mint = self.get_mint()?;
+        let program_id = self.cli.nft_program_id();
+
+        let config = self.client.config(mint, program_id)?;
+        let decimals = self.client.mint_account(mint)?.decimals;
+
+        let mut instructions = Vec::new();
+        if let Some(ui_fees) = self.cli.updated_fees(config.fees.to_ui(decimals)) {
+            let fees = Fees::from_ui(ui_fees, decimals);
+            let mut ix = chill_sdk::instruction::update_fees(
+                primary_wallet,
+                mint,
+                fees,
+                program_id,
+            );
+            self.append_admin_signers(&mut ix, primary_wallet)?;
+            instructions.push(ix);
+        }
+
+        if let Some(recipients) = self.cli.updated_recipients()? {
+            let mut ix = chill_sdk::instruction::update_recipients(
+                primary_wallet,
+                mint,
+                recipients,
+                program_id,
+            );
+            self.append_admin_signers(&mut ix, primary_wallet)?;
+            instructions.push(ix);
+        }
+
+        if instructions.is_empty() {
+            return Err(CliError::NothingToUpdate.into());
+        }
+
+        self.process_admin("config-update", &instructions, program_id)
+    }
+
+    fn process_admin_config_register_type(&self) -> Result<ProcessedData> {
+        let primary_wallet = self.cli.primary_wallet_pubkey()?;
+        let payer = self.cli.payer_pubkey()?;
+        let mint = self.get_mint()?;
+        let name = self.cli.nft_type_name();
+        let program_id = self.cli.nft_program_id();
+
+        if self.resolve_nft_type(name, mint, program_id).is_ok() {
+            return Err(CliError::NftTypeExists(name.to_owned()).into());
+        }
+
+        let decimals = self.client.mint_account(mint)?.decimals;
+        let fee = spl_token::ui_amount_to_amount(self.cli.fee(), decimals);
+
+        let mut ix = chill_sdk::instruction::register_nft_type(
+            primary_wallet,
+            payer,
+            mint,
+            name.to_owned(),
+            fee,
+            program_id,
+        );
+        self.append_admin_signers(&mut ix, primary_wallet)?;
+
+        self.process_admin("config-register-type", &[ix], program_id)
+    }
+
+    fn process_admin_wallet_freeze(&self) -> Result<ProcessedData> {
+        let primary_wallet = self.cli.primary_wallet_pubkey()?;
+        let program_id = self.cli.wallet_program_id();
+        let proxy_wallet = pda::proxy_wallet(self.cli.user(), primary_wallet, program_id);
+
+        let ix = chill_sdk::instruction::freeze_wallet(primary_wallet, proxy_wallet, program_id);
+        self.process_admin("wallet-freeze", &[ix], program_id)
+    }
+
+    fn process_admin_wallet_unfreeze(&self) -> Result<ProcessedData> {
+        let primary_wallet = self.cli.primary_wallet_pubkey()?;
+        let program_id = self.cli.wallet_program_id();
+        let proxy_wallet = pda::proxy_wallet(self.cli.user(), primary_wallet, program_id);
+
+        let ix = chill_sdk::instruction::unfreeze_wallet(primary_wallet, proxy_wallet, program_id);
+        self.process_admin("wallet-unfreeze", &[ix], program_id)
+    }
+
+    fn process_admin_wallet_set_fee(&self) -> Result<ProcessedData> {
+        let primary_wallet = self.cli.primary_wallet_pubkey()?;
+        let program_id = self.cli.wallet_program_id();
+        let proxy_wallet = pda::proxy_wallet(self.cli.user(), primary_wallet, program_id);
+
+        let mut ix = chill_sdk::instruction::set_withdrawal_fee(
+            primary_wallet,
+            proxy_wallet,
+            self.cli.fee_bps(),
+            self.cli.fee_receiver(),
+            program_id,
+        );
+        self.append_admin_signers(&mut ix, primary_wallet)?;
+
+        self.process_admin("wallet-set-fee", &[ix], program_id)
+    }
+
+    fn process_admin_wallet_set_limits(&self) -> Result<ProcessedData> {
+        let primary_wallet = self.cli.primary_wallet_pubkey()?;
+        let user = self.cli.user();
+        let program_id = self.cli.wallet_program_id();
+        let proxy_wallet = pda::proxy_wallet(user, primary_wallet, program_id);
+
+        // Tokens of proxy wallets are assumed to have as many decimals as SOL
+        let daily_limits = |(lamports, ft)| DailyLimits {
+            lamports: sol_to_lamports(lamports),
+            ft: spl_token::ui_amount_to_amount(ft, native_mint::DECIMALS),
+        };
+
+        let mut ix = chill_sdk::instruction::set_spending_limits(
+            primary_wallet,
+            proxy_wallet,
+            daily_limits(self.cli.user_daily_limits()),
+            daily_limits(self.cli.primary_wallet_daily_limits()),
+            program_id,
+        );
+        self.append_admin_signers(&mut ix, primary_wallet)?;
+
+        self.process_admin("wallet-set-limits", &[ix], program_id)
+    }
+
+    fn process_admin_signers_set(&self) -> Result<ProcessedData> {
+        let primary_wallet = self.cli.primary_wallet_pubkey()?;
+        let signers = self.cli.signers();
+        let threshold = self.cli.threshold();
+        let program_id = self.cli.nft_program_id();
+
+        let ix = match self.client.admin_config(primary_wallet, program_id)? {
+            None => chill_sdk::instruction::initialize_admin_config(
+                primary_wallet,
+                self.cli.payer_pubkey()?,
+                signers,
+                threshold,
+                program_id,
+            ),
+            Some(_) => {
+                let approving_signers = self
+                    .admin_signers(primary_wallet, program_id)?
+                    .iter()
+                    .map(|s| s.pubkey())
+                    .collect::<Vec<_>>();
+
+                chill_sdk::instruction::update_admin_config(
+                    primary_wallet,
+                    &approving_signers,
+                    signers,
+                    threshold,
+                    program_id,
+                )
+            }
+        };
+
+        self.process_admin("signers-set", &[ix], program_id)
+    }
+
+    fn process_admin_staking_pause(&self) -> Result<ProcessedData> {
+        let primary_wallet = self.cli.primary_wallet_pubkey()?;
+        let staking_info = self.cli.staking_info();
+        let program_id = self.cli.staking_program_id();
+
+        let ix = chill_sdk::instruction::pause(primary_wallet, staking_info, program_id);
+        self.process_admin("staking-pause", &[ix], program_id)
+    }
+
+    fn process_admin_staking_resume(&self) -> Result<ProcessedData> {
+        let primary_wallet = self.cli.primary_wallet_pubkey()?;
+        let staking_info = self.cli.staking_info();
+        let program_id = self.cli.staking_program_id();
+
+        let ix = chill_sdk::instruction::resume(primary_wallet, staking_info, program_id);
+        self.process_admin("staking-resume", &[ix], program_id)
+    }
+
+    fn process_reclaim_metadata(&self) -> Result<ProcessedData> {
+        let payer = self.cli.payer()?;
+        let nft_mint = self.get_mint()?;
+        let program_id = self.cli.nft_program_id();
+
+        let supply = self.client.mint_account(nft_mint)?.supply;
+        if supply != 0 {
+            return Err(CliError::NftIsNotBurned(nft_mint).into());
+        }
+
+        let signature = self.client.reclaim_metadata(payer, nft_mint, program_id)?;
+        self.print_signature(&signature);
+
+        Ok(ProcessedData::Other)
+    }
+
+    fn process_print_info(&self) -> Result<ProcessedData> {
+        let mint = self.get_mint()?;
+        let program_id = self.cli.nft_program_id();
+        self.print_info(mint, program_id)
+    }
+
+    fn process_list_configs(&self) -> Result<ProcessedData> {
+        let program_id = self.cli.nft_program_id();
+        let configs = api::configs(&self.client, program_id)?;
+
+        for (pubkey, config) in &configs {
+            if self.output.is_json() {
+                self.output.push("configs", json!({
+                    "config": pubkey.to_string(),
+                    "mint": config.mint.to_string(),
+                    "primary_wallet": config.primary_wallet.to_string(),
+                }));
+                continue;
+            }
+
+            println!("{} {}", "Config:".green().bold(), pubkey);
+            println!("{:>16} {}", "Mint:".cyan(), config.mint);
+            println!("{:>16} {}", "Primary wallet:".cyan(), config.primary_wallet);
+        }
+
+        Ok(ProcessedData::Configs(configs))
+    }
+
+    fn process_list_nfts(&self) -> Result<ProcessedData> {
+        let primary_wallet = self.cli.primary_wallet_pubkey()?;
+        let mint_chill = self.get_mint()?;
+        let program_id = self.cli.nft_program_id();
+        let nft_type = self
+            .cli
+            .nft_type_filter()
+            .map(|name| self.resolve_nft_type(name, mint_chill, program_id))
+            .transpose()?;
+
+        let nfts = api::nfts(
+            &self.client,
+            primary_wallet,
+            mint_chill,
+            self.cli.owner(),
+            nft_type,
+            program_id,
+        )?;
+
+        if !self.output.is_json() && !nfts.is_empty() {
+            println!(
+                "{:<44} {:<12} {:<44} {}",
+                "Mint".cyan(),
+                "Type".cyan(),
+                "Owner".cyan(),
+                "Name".cyan()
+            );
+        }
+
+        for nft in &nfts {
+            let data = &nft.info.metadata.data;
+            let name = data.name.trim_matches('\0');
+            let nft_type = nft.info.nft_type_name.as_deref().unwrap_or("unknown");
+            let owner = nft.owner.map_or_else(|| "burned".to_owned(), |owner| owner.to_string());
+
+            if self.output.is_json() {
+                self.output.push("nfts", json!({
+                    "mint": nft.mint.to_string(),
+                    "type": nft.info.nft_type_name,
+                    "owner": nft.owner.map(|owner| owner.to_string()),
+                    "name": name,
+                    "symbol": data.symbol.trim_matches('\0'),
+                    "uri": data.uri.trim_matches('\0'),
+                    "min_game_version": nft.info.chill_metadata.min_game_version,
+                }));
+                continue;
+            }
+
+            println!("{:<44} {:<12} {:<44} {}", nft.mint, nft_type, owner, name);
+        }
+
+        self.output.value("NFTs:".green(), nfts.len());
+        Ok(ProcessedData::Other)
+    }
+
+    fn process_print_balance(&self) -> Result<ProcessedData> {
+        let account = self.cli.account();
+        let mint = self.get_mint()?;
+        self.print_balance(account, mint)
+    }
+
+    fn process_transfer(&self) -> Result<ProcessedData> {
+        let primary_wallet = self.cli.primary_wallet()?;
+        let payer = self.cli.payer()?;
+        let mint = self.get_mint()?;
+
+        let ui_amount = self.cli.ui_amount();
+        let recipient = self.cli.recipient();
+
+        if ui_amount == 0.0 {
+            return Err(CliError::TransferZeroTokens.into());
+        }
+
+        let current_balance = self
+            .client
+            .ui_token_balance(primary_wallet.pubkey(), mint)?;
+
+        if ui_amount > current_balance {
+            return Err(CliError::InsufficientTokens(ui_amount, current_balance).into());
+        }
+
+        let mint_account = self.client.mint_account(mint)?;
+        let decimals = mint_account.decimals;
+        let amount = spl_token::ui_amount_to_amount(ui_amount, decimals);
+
+        let primary_wallet_pubkey = primary_wallet.pubkey();
+        let signature =
+            self.client
+                .transfer_tokens(primary_wallet, payer, mint, recipient, amount)?;
+
+        self.print_signature(&signature);
+        self.print_balance(primary_wallet_pubkey, mint)?;
+        Ok(ProcessedData::Other)
+    }
+
+    pub fn process_nft_initialize(&self) -> Result<ProcessedData> {
+        let payer = self.cli.payer()?;
+        let primary_wallet = self.cli.primary_wallet()?;
+        let mint = self.get_mint()?;
+        let program_id = self.cli.nft_program_id();
+
+        self.assert_mint_authority(mint, primary_wallet.pubkey())?;
+
+        let ui_fees = self.cli.fees();
+
+        let recipients = self.cli.multiple_recipients()?;
+        let mint_account = self.client.mint_account(mint)?;
+        let fees = Fees::from_ui(ui_fees, mint_account.decimals);
+
+        let primary_wallet = primary_wallet.pubkey();
+        let admin_signers = self.admin_signers(primary_wallet, program_id)?;
+
+        self.client.initialize(
+            primary_wallet,
+            &admin_signers,
+            payer,
+            mint,
+            fees,
+            recipients,
+            program_id,
+        )?;
+
+        self.print_info(mint, program_id)?;
+        Ok(ProcessedData::Other)
+    }
+
+    pub fn process_create_wallet(&self) -> Result<ProcessedData> {
+        let payer = self.cli.payer()?;
+        let primary_wallet = self.cli.primary_wallet_pubkey()?;
+        let account = self.cli.account();
+        let program_id = self.cli.wallet_program_id();
+
+        let (proxy_wallet, signature) =
+            api::create_wallet(&self.client, payer, account, primary_wallet, program_id)?;
+
+        self.output.value("Wallet:".green(), proxy_wallet.to_string());
+        self.print_signature(&signature);
+
+        Ok(ProcessedData::CreateWallet { wallet: proxy_wallet, signature: signature })
+    }
+
+    pub fn process_create_wallets(&self) -> Result<ProcessedData> {
+        let payer = self.cli.payer()?;
+        let primary_wallet_pubkey = self.cli.primary_wallet_pubkey()?;
+        let users_file = self.cli.users_file();
+        let program_id = self.cli.wallet_program_id();
+
+        let admin_signers = self.admin_signers(primary_wallet_pubkey, chill_nft::ID)?;
+        let admin_signer_pubkeys = admin_signers.iter().map(|s| s.pubkey()).collect::<Vec<_>>();
+
+        let users = self.read_pubkeys(users_file)?;
+        let proxy_wallets = users
+            .iter()
+            .map(|user| pda::proxy_wallet(*user, primary_wallet_pubkey, program_id))
+            .collect::<Vec<_>>();
+
+        // Wallets created by previous runs are skipped
+        let existing = self.client.existing_accounts(&proxy_wallets)?;
+        let pending_users = users
+            .iter()
+            .zip(existing)
+            .filter_map(|(user, exists)| (!exists).then_some(*user))
+            .collect::<Vec<_>>();
+
+        self.output.value("Existing wallets:".cyan(), users.len() - pending_users.len());
+
+        let batch_size = self.client.create_wallets_batch_size(
+            payer.pubkey(),
+            primary_wallet_pubkey,
+            &admin_signer_pubkeys,
+            program_id,
+        );
+
+        let mut wallets = Vec::with_capacity(pending_users.len());
+        let mut failed = Vec::new();
+        let mut batches = pending_users.chunks(batch_size);
+        for batch in batches.by_ref() {
+            match self.client.create_wallets(
+                payer.clone(),
+                primary_wallet_pubkey,
+                &admin_signers,
+                batch,
+                program_id,
+            ) {
+                Ok(signature) => {
+                    self.print_signature(&signature);
+                    wallets.extend(
+                        batch
+                            .iter()
+                            .map(|user| pda::proxy_wallet(*user, primary_wallet_pubkey, program_id)),
+                    );
+                }
+                Err(error) => {
+                    self.output.message(error);
+                    failed.extend_from_slice(batch);
+
+                    // Remaining users are written to the file of failed ones to retry them later
+                    if self.client.rpc_budget_exhausted() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        failed.extend(batches.flatten());
+        self.output.value("Created wallets:".green(), wallets.len());
+
+        if !failed.is_empty() {
+            let failed_file = format!("{}.failed", users_file);
+            let content = failed
+                .iter()
+                .map(|user| format!("{}\n", user))
+                .collect::<String>();
+
+            fs::write(&failed_file, content)
+                .map_err(|_| CliError::CannotWriteToFile(failed_file.clone()))?;
+
+            if self.output.is_json() {
+                self.output.record("failed_wallets", failed.len().into());
+                self.output.record("failed_file", failed_file.into());
+            } else {
+                println!(
+                    "{} {} \"{}\"",
+                    "Failed wallets:".red(),
+                    failed.len(),
+                    failed_file
+                );
+            }
+        }
+
+        self.print_rpc_usage();
+        Ok(ProcessedData::CreateWallets { wallets, failed })
+    }
+
+    pub fn process_withdraw_lamports(&self) -> Result<ProcessedData> {
+        let account = self.cli.account();
+        let authority = self.cli.authority()?;
+        let payer = self.cli.payer()?;
+        let primary_wallet = self.cli.primary_wallet_pubkey()?;
+        let recipient = self.cli.recipient();
+        let program_id = self.cli.wallet_program_id();
+
+        let ui_amount = self.cli.ui_amount();
+        let amount = spl_token::ui_amount_to_amount(ui_amount, native_mint::DECIMALS);
+
+        let proxy_wallet = pda::proxy_wallet(account, primary_wallet, program_id);
+
+        let signature = self.client.withdraw_lamports(
+            payer,
+            authority,
+            proxy_wallet,
+            recipient,
+            amount,
+            program_id,
+        )?;
+
+        self.print_signature(&signature);
+
+        Ok(ProcessedData::Other)
+    }
+
+    pub fn process_withdraw_ft(&self) -> Result<ProcessedData> {
+        let account = self.cli.account();
+        let authority = self.cli.authority()?;
+        let payer = self.cli.payer()?;
+        let primary_wallet = self.cli.primary_wallet_pubkey()?;
+        let recipient = self.cli.recipient();
+        let mint = self.get_mint()?;
+        let program_id = self.cli.wallet_program_id();
+
+        let ui_amount = self.cli.ui_amount();
+        let amount = spl_token::ui_amount_to_amount(ui_amount, native_mint::DECIMALS);
+
+        let proxy_wallet = pda::proxy_wallet(account, primary_wallet, program_id);
+
+        let signature = self.client.withdraw_ft(
+            payer,
+            authority,
+            proxy_wallet,
+            recipient,
+            mint,
+            amount,
+            program_id,
+        )?;
+
+        self.print_signature(&signature);
+
+        Ok(ProcessedData::Other)
+    }
+
+    pub fn process_withdraw_nft(&self) -> Result<ProcessedData> {
+        let account = self.cli.account();
+        let authority = self.cli.authority()?;
+        let payer = self.cli.payer()?;
+        let primary_wallet = self.cli.primary_wallet_pubkey()?;
+        let recipient = self.cli.recipient();
+        let mint = self.get_mint()?;
+        let program_id = self.cli.wallet_program_id();
+
+        let proxy_wallet = pda::proxy_wallet(account, primary_wallet, program_id);
+
+        let signature = self.client.withdraw_nft(
+            payer,
+            authority,
+            proxy_wallet,
+            recipient,
+            mint,
+            program_id,
+        )?;
+
+        self.print_signature(&signature);
+
+        Ok(ProcessedData::Other)
+    }
+
+    pub fn process_schedule_withdrawal(&self) -> Result<ProcessedData> {
+        let account = self.cli.account();
+        let authority = self.cli.authority()?;
+        let payer = self.cli.payer()?;
+        let primary_wallet = self.cli.primary_wallet_pubkey()?;
+        let recipient = self.cli.recipient();
+        let interval = self.cli.interval();
+        let program_id = self.cli.wallet_program_id();
+
+        let ui_amount = self.cli.ui_amount();
+        let amount = spl_token::ui_amount_to_amount(ui_amount, native_mint::DECIMALS);
+
+        let proxy_wallet = pda::proxy_wallet(account, primary_wallet, program_id);
+        let schedule = pda::withdrawal_schedule(proxy_wallet, recipient, program_id);
+
+        let signature = self.client.schedule_withdrawal(
+            payer,
+            authority,
+            proxy_wallet,
+            recipient,
+            amount,
+            interval,
+            program_id,
+        )?;
+
+        self.output.value("Schedule:".green(), schedule.to_string());
+        self.print_signature(&signature);
+
+        Ok(ProcessedData::Other)
+    }
+
+    pub fn process_execute_withdrawal(&self) -> Result<ProcessedData> {
+        let account = self.cli.account();
+        let payer = self.cli.payer()?;
+        let primary_wallet = self.cli.primary_wallet_pubkey()?;
+        let recipient = self.cli.recipient();
+        let program_id = self.cli.wallet_program_id();
+
+        let proxy_wallet = pda::proxy_wallet(account, primary_wallet, program_id);
+
+        let signature =
+            self.client
+                .execute_due_withdrawal(payer, proxy_wallet, recipient, program_id)?;
+
+        self.print_signature(&signature);
+
+        Ok(ProcessedData::Other)
+    }
+
+    pub fn process_cancel_withdrawal(&self) -> Result<ProcessedData> {
+        let account = self.cli.account();
+        let authority = self.cli.authority()?;
+        let payer = self.cli.payer()?;
+        let primary_wallet = self.cli.primary_wallet_pubkey()?;
+        let recipient = self.cli.recipient();
+        let program_id = self.cli.wallet_program_id();
+
+        let proxy_wallet = pda::proxy_wallet(account, primary_wallet, program_id);
+
+        let signature = self.client.cancel_scheduled_withdrawal(
+            payer,
+            authority,
+            proxy_wallet,
+            recipient,
+            program_id,
+        )?;
+
+        self.print_signature(&signature);
+
+        Ok(ProcessedData::Other)
+    }
+
+    pub fn process_wallet_deposit_address(&self) -> Result<ProcessedData> {
+        let user = self.cli.user();
+        let primary_wallet = self.cli.primary_wallet_pubkey()?;
+        let mint = self.cli.explicit_mint()?;
+        let program_id = self.cli.wallet_program_id();
+
+        let proxy_wallet = pda::proxy_wallet(user, primary_wallet, program_id);
+        self.output.value("Wallet:".green(), proxy_wallet.to_string());
+
+        if self.client.balance(proxy_wallet)? == 0 {
+            self.output.message(
+                "Proxy wallet doesn't exist, create it with the 'create-wallet' command".yellow()
+            );
+        }
+
+        let mut token_account = None;
+        let mut payment_uri = format!("solana:{}", proxy_wallet);
+
+        if let Some(mint) = mint {
+            self.client.mint_account(mint)?;
+
+            let associated_token_account = get_associated_token_address(&proxy_wallet, &mint);
+            self.output.value("Token account:".green(), associated_token_account.to_string());
+
+            if self.client.token_account(associated_token_account).is_err() {
+                if self.cli.create_token_account() {
+                    let payer = self.cli.payer()?;
+                    self.client
+                        .create_associated_token_account(proxy_wallet, mint, payer)?;
+                    self.output.message("Token account has been created".green());
+                } else {
+                    self.output.message(
+                        "Token account doesn't exist, rerun with '--create-token-account' to create it"
+                            .yellow()
+                    );
+                }
+            }
+
+            token_account = Some(associated_token_account);
+            write!(payment_uri, "?spl-token={}", mint).unwrap();
+        }
+
+        if self.cli.qr() {
+            // The longest payment URI is far below the capacity of the encoder
+            if self.output.is_json() {
+                self.output.record("payment_uri", payment_uri.into());
+            } else {
+                let qr = QrCode::encode(payment_uri.as_bytes()).unwrap();
+                println!("{}", qr.to_terminal_string());
+            }
+        }
+
+        Ok(ProcessedData::DepositAddress {
+            wallet: proxy_wallet,
+            token_account,
+        })
+    }
+
+    pub fn process_wallet_list(&self) -> Result<ProcessedData> {
+        let primary_wallet = self.cli.primary_wallet_pubkey()?;
+        let mint = self.cli.explicit_mint()?;
+        let program_id = self.cli.wallet_program_id();
+
+        let mut wallets = self.client.all_proxy_wallets(primary_wallet, program_id)?;
+        wallets.sort_by_key(|(_, proxy_wallet, _)| proxy_wallet.user);
+
+        // Balances of the mint are fetched by batches of the token accounts
+        let token_balances = match mint {
+            Some(mint) => {
+                let decimals = self.client.mint_account(mint)?.decimals;
+                let token_accounts = wallets
+                    .iter()
+                    .map(|(address, _, _)| get_associated_token_address(address, &mint))
+                    .collect::<Vec<_>>();
+                let balances = self.client.token_balances(&token_accounts)?;
+                Some((balances, decimals))
+            }
+            None => None,
+        };
+
+        if !self.output.is_json() && !wallets.is_empty() {
+            println!(
+                "{:<44} {:<44} {:>14} {:>14}",
+                "User".cyan(),
+                "Wallet".cyan(),
+                "SOL".cyan(),
+                if mint.is_some() { "Tokens" } else { "" }.cyan()
+            );
+        }
+
+        for (index, (address, proxy_wallet, lamports)) in wallets.iter().enumerate() {
+            let sol = lamports_to_sol(*lamports);
+            let tokens = token_balances.as_ref().map(|(balances, decimals)| {
+                spl_token::amount_to_ui_amount(balances[index], *decimals)
+            });
+
+            if self.output.is_json() {
+                self.output.push("wallets", json!({
+                    "wallet": address.to_string(),
+                    "user": proxy_wallet.user.to_string(),
+                    "sol": sol,
+                    "tokens": tokens,
+                    "frozen": proxy_wallet.frozen,
+                }));
+                continue;
+            }
+
+            let tokens = tokens.map(|tokens| tokens.to_string()).unwrap_or_default();
+            let frozen = if proxy_wallet.frozen { "frozen" } else { "" };
+            println!(
+                "{:<44} {:<44} {:>14} {:>14} {}",
+                proxy_wallet.user,
+                address,
+                sol,
+                tokens,
+                frozen.red()
+            );
+        }
+
+        self.output.value("Wallets:".green(), wallets.len());
+        Ok(ProcessedData::Other)
+    }
+
+    pub fn process_wallet_info(&self) -> Result<ProcessedData> {
+        let user = self.cli.user();
+        let primary_wallet = self.cli.primary_wallet_pubkey()?;
+        let program_id = self.cli.wallet_program_id();
+
+        let address = pda::proxy_wallet(user, primary_wallet, program_id);
+        let proxy_wallet = self.client.proxy_wallet(address)?;
+        let lamports = self.client.balance(address)?;
+
+        self.output.value("Wallet:".green(), address.to_string());
+        self.output.value("User:".cyan(), user.to_string());
+        self.output.value_with_unit("Balance:".cyan(), lamports_to_sol(lamports), "SOL");
+        if proxy_wallet.frozen {
+            self.output.message("Wallet is frozen".red());
+        }
+
+        let token_accounts = self.client.token_accounts_by_owner(address)?;
+        if !self.output.is_json() && !token_accounts.is_empty() {
+            println!("{}", "Tokens:".cyan());
+        }
+
+        for (token_account, account) in token_accounts {
+            let decimals = self.client.mint_account(account.mint)?.decimals;
+            let amount = spl_token::amount_to_ui_amount(account.amount, decimals);
+
+            if self.output.is_json() {
+                self.output.push("tokens", json!({
+                    "mint": account.mint.to_string(),
+                    "token_account": token_account.to_string(),
+                    "amount": amount,
+                }));
+            } else {
+                println!("  {} {}", account.mint, amount);
+            }
+        }
+
+        let deposited = lamports_to_sol(proxy_wallet.total_money_deposited);
+        self.output.value_with_unit("Deposited SOL:".cyan(), deposited, "SOL");
+        self.output.value("Deposited tokens:".cyan(), proxy_wallet.total_ft_deposited);
+
+        let withdrawn = [
+            (
+                "User",
+                proxy_wallet.total_money_withdrawn_user,
+                proxy_wallet.total_ft_withdrawn_user,
+                proxy_wallet.total_nft_withdrawn_user,
+                &proxy_wallet.user_limit,
+            ),
+            (
+                "Primary wallet",
+                proxy_wallet.total_money_withdrawn_primary_wallet,
+                proxy_wallet.total_ft_withdrawn_primary_wallet,
+                proxy_wallet.total_nft_withdrawn_primary_wallet,
+                &proxy_wallet.primary_wallet_limit,
+            ),
+        ];
+
+        for (authority, lamports, ft, nfts, limit) in withdrawn {
+            let label = |name: &str| format!("{} {}:", authority, name).cyan();
+            self.output.value_with_unit(label("withdrew SOL"), lamports_to_sol(lamports), "SOL");
+            self.output.value(label("withdrew tokens"), ft);
+            self.output.value(label("withdrew NFTs"), nfts);
+            self.print_spending_limit(authority, limit);
+        }
+
+        let withdrawals = proxy_wallet.withdrawals();
+        if !self.output.is_json() && !withdrawals.is_empty() {
+            println!("{}", "Last withdrawals:".cyan());
+        }
+
+        for entry in withdrawals {
+            let is_lamports = entry.mint == Pubkey::default();
+            if self.output.is_json() {
+                self.output.push("withdrawals", json!({
+                    "timestamp": entry.timestamp,
+                    "authority": entry.authority.to_string(),
+                    "mint": (!is_lamports).then(|| entry.mint.to_string()),
+                    "amount": entry.amount,
+                }));
+            } else {
+                let date = chill_sdk::view::day_to_date(entry.timestamp as u64 / SEC_PER_DAY);
+                let (amount, unit) = if is_lamports {
+                    (lamports_to_sol(entry.amount).to_string(), "SOL".to_owned())
+                } else {
+                    (entry.amount.to_string(), entry.mint.to_string())
+                };
+                println!("  {} {} {} {}", date, entry.authority, amount, unit);
+            }
+        }
+
+        Ok(ProcessedData::Other)
+    }
+
+    /// Amounts of the previous days are not spent today, they are reset by the next withdrawal
+    fn print_spending_limit(&self, authority: &str, limit: &SpendingLimit) {
+        let label = |name: &str| format!("{} {}:", authority, name).cyan();
+        let today = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64
+            / SpendingLimit::SECONDS_PER_DAY;
+        let (spent_lamports, spent_ft) = if limit.day == today {
+            (limit.spent_lamports, limit.spent_ft)
+        } else {
+            (0, 0)
+        };
+
+        if limit.limits.lamports != 0 {
+            let daily = lamports_to_sol(limit.limits.lamports);
+            self.output.value_with_unit(label("daily SOL limit"), daily, "SOL");
+            let spent = lamports_to_sol(spent_lamports);
+            self.output.value_with_unit(label("spent SOL today"), spent, "SOL");
+        }
+
+        if limit.limits.ft != 0 {
+            self.output.value(label("daily token limit"), limit.limits.ft);
+            self.output.value(label("spent tokens today"), spent_ft);
+        }
+    }
+
+    pub fn process_verify_deployment(&self) -> Result<ProcessedData> {
+        let payer = self.cli.payer()?.pubkey();
+        let is_mainnet = self.cli.cluster() == Cluster::Mainnet;
+        let staking_program_id = self.cli.staking_program_id();
+
+        let programs = [
+            ("NFT", self.cli.nft_program_id()),
+            ("Wallet", self.cli.wallet_program_id()),
+            ("Staking", staking_program_id),
+        ];
+
+        for (name, program_id) in programs {
+            if !self.client.is_program_deployed(program_id)? {
+                return Err(CliError::ProgramNotDeployed(program_id).into());
+            }
+
+            let devnet_tools_enabled = self.client.devnet_tools_enabled(payer, program_id)?;
+            if self.output.is_json() {
+                self.output.push("programs", json!({
+                    "name": name,
+                    "program_id": program_id.to_string(),
+                    "devnet_tools": devnet_tools_enabled,
+                }));
+            }
+
+            let label = format!("{} program {}:", name, program_id);
+            if !devnet_tools_enabled {
+                if !self.output.is_json() {
+                    println!("{} devnet tools are disabled", label.green());
+                }
+                continue;
+            }
+
+            if !self.output.is_json() {
+                println!("{} devnet tools are enabled", label.red());
+            }
+            if is_mainnet {
+                return Err(CliError::DevnetToolsOnMainnet(program_id).into());
+            }
+        }
+
+        // The faucet mint can only be created by a build with devnet tools
+        let faucet_mint = pda::devnet_faucet_mint(staking_program_id);
+        if self.client.balance(faucet_mint)? != 0 {
+            self.output.value("Devnet faucet mint:".red(), faucet_mint.to_string());
+            if is_mainnet {
+                return Err(CliError::DevnetToolsOnMainnet(staking_program_id).into());
+            }
+        }
+
+        Ok(ProcessedData::Other)
+    }
+
+    fn print_diff(&self, title: &str, diffs: &[FieldDiff]) -> usize {
+        let mismatches = diffs.iter().filter(|diff| !diff.is_equal()).count();
+        if self.output.is_json() {
+            let fields = diffs
+                .iter()
+                .map(|diff| {
+                    json!({
+                        "field": diff.field,
+                        "a": diff.a,
+                        "b": diff.b,
+                    })
+                })
+                .collect();
+            self.output.record(&title.to_lowercase(), Value::Array(fields));
+            return mismatches;
+        }
+
+        println!("{}", title.cyan());
+        for diff in diffs {
+            let label = format!("{}:", diff.field);
+            let a = diff.a.as_deref().unwrap_or("-");
+            let b = diff.b.as_deref().unwrap_or("-");
+
+            if diff.is_equal() {
+                println!("  {} {}", label, a);
+            } else {
+                println!("  {} {} | {}", label.red(), a, b);
+            }
+        }
+
+        mismatches
+    }
+
+    pub fn process_diff_config(&self) -> Result<ProcessedData> {
+        let (url_a, url_b) = self.cli.diff_clusters();
+        let client_a = Client::init(&url_a, self.cli.rpc_budget());
+        let client_b = Client::init(&url_b, self.cli.rpc_budget());
+
+        self.output.value("Cluster A:".cyan(), url_a);
+        self.output.value("Cluster B:".cyan(), url_b);
+
+        let (mint_a, mint_b) = self.cli.diff_mints()?;
+        let mint_a = mint_a.ok_or(CliError::MintNotSpecified)?;
+        let mint_b = mint_b.ok_or(CliError::MintNotSpecified)?;
+
+        let nft_program_id = self.cli.nft_program_id();
+        let config_a = client_a.config(mint_a, nft_program_id)?;
+        let config_b = client_b.config(mint_b, nft_program_id)?;
+
+        let mut mismatches = self.print_diff(
+            "Config",
+            &diff::diff(
+                &diff::config_fields(&config_a),
+                &diff::config_fields(&config_b),
+            ),
+        );
+
+        if let Some((staking_info_a, staking_info_b)) = self.cli.diff_staking_infos() {
+            let staking_info_a = client_a.staking_info(staking_info_a)?;
+            let staking_info_b = client_b.staking_info(staking_info_b)?;
+
+            mismatches += self.print_diff(
+                "Staking",
+                &diff::diff(
+                    &diff::staking_fields(&staking_info_a),
+                    &diff::staking_fields(&staking_info_b),
+                ),
+            );
+        }
+
+        let programs = [
+            ("nft", nft_program_id),
+            ("wallet", self.cli.wallet_program_id()),
+            ("staking", self.cli.staking_program_id()),
+        ];
+
+        let mut programs_a = Vec::with_capacity(programs.len());
+        let mut programs_b = Vec::with_capacity(programs.len());
+        for (name, program_id) in programs {
+            programs_a.push(diff::program_hash_field(name, client_a.program_hash(program_id)?));
+            programs_b.push(diff::program_hash_field(name, client_b.program_hash(program_id)?));
+        }
+
+        mismatches += self.print_diff("Programs", &diff::diff(&programs_a, &programs_b));
+
+        if mismatches > 0 {
+            return Err(CliError::ConfigMismatch(mismatches).into());
+        }
+
+        self.output.message("Configurations are equal".green());
+        Ok(ProcessedData::Other)
+    }
+
+    pub fn process_submit(&self) -> Result<ProcessedData> {
+        let transactions = self.cli.transactions()?;
+        self.confirm_transaction()?;
+
+        let signature = self
+            .client
+            .submit_transaction(&transactions, &self.cli.presigners())?;
+
+        self.print_signature(&signature);
+        Ok(ProcessedData::Other)
+    }
+
+    pub fn process_staking_initialize(&self) -> Result<ProcessedData> {
+        let primary_wallet = self.cli.primary_wallet()?;
+        let payer = self.cli.payer()?;
+        let mint = self.get_mint()?;
+        let start_time = self.cli.start_time();
+        let end_time = self.cli.end_time();
+        let min_stake_size_ui = self.cli.min_stake_size();
+        let claim_fee = self.cli.claim_fee();
+        let days_in_window = self.cli.days_in_window();
+        let referral_fee = self.cli.referral_fee();
+        let program_id = self.cli.staking_program_id();
+
+        let mint_account = self.client.mint_account(mint)?;
+        let decimals = mint_account.decimals;
+        let min_stake_size = spl_token::ui_amount_to_amount(min_stake_size_ui, decimals);
+
+        let args = chill_staking::InitializeArgs {
+            start_time,
+            end_time,
+            min_stake_size,
+            claim_fee,
+            days_in_window,
+            referral_fee,
+        };
+
+        let staking_info = Keypair::new();
+        self.output.value("StakingInfo:".green(), staking_info.pubkey().to_string());
+
+        let signature = self.client.staking_initialize(
+            &staking_info,
+            primary_wallet,
+            payer,
+            mint,
+            args,
+            program_id,
+        )?;
+
+        self.save_staking_info(staking_info.pubkey())?;
+        self.print_signature(&signature);
+
+        Ok(ProcessedData::Other)
+    }
+
+    pub fn process_staking_clone(&self) -> Result<ProcessedData> {
+        let primary_wallet = self.cli.primary_wallet()?;
+        let payer = self.cli.payer()?;
+        let existing_staking_info = self.cli.staking_info();
+        let start_time = self.cli.start_time();
+        let end_time = self.cli.end_time();
+        let transfer_remaining = self.cli.transfer_remaining();
+        let program_id = self.cli.staking_program_id();
+
+        let existing = self.client.staking_info(existing_staking_info)?;
+        let remaining_amount = existing.free_reward_amount()?;
+
+        if transfer_remaining {
+            self.assert_staking_finished(existing_staking_info, &existing)?;
+        }
+
+        let args = chill_staking::InitializeArgs {
+            start_time,
+            end_time,
+            min_stake_size: existing.min_stake_size,
+            claim_fee: existing.claim_fee,
+            days_in_window: existing.days_in_window() as u16,
+            referral_fee: existing.referral_fee,
+        };
+
+        let staking_info = Keypair::new();
+        self.output.value("StakingInfo:".green(), staking_info.pubkey().to_string());
+
+        let signature = self.client.staking_initialize(
+            &staking_info,
+            primary_wallet.clone(),
+            payer.clone(),
+            existing.mint,
+            args,
+            program_id,
+        )?;
+
+        self.save_staking_info(staking_info.pubkey())?;
+        self.print_signature(&signature);
+
+        if transfer_remaining && remaining_amount > 0 {
+            let admin_signers = self.admin_signers(primary_wallet.pubkey(), chill_nft::ID)?;
+            let signature = self.client.staking_transfer_remaining_reward_tokens(
+                primary_wallet,
+                &admin_signers,
+                payer,
+                existing_staking_info,
+                staking_info.pubkey(),
+                existing.mint,
+                remaining_amount,
+                program_id,
+            )?;
+
+            let decimals = self.client.mint_account(existing.mint)?.decimals;
+            self.output.value(
+                "Transferred reward tokens:".green(),
+                spl_token::amount_to_ui_amount(remaining_amount, decimals),
+            );
+            self.print_signature(&signature);
+        }
+
+        Ok(ProcessedData::Other)
+    }
+
+    pub fn process_staking_add_reward_tokens(&self) -> Result<ProcessedData> {
+        let primary_wallet = self.cli.primary_wallet()?;
+        let payer = self.cli.payer()?;
+        let mint = self.get_mint()?;
+        let staking_info = self.cli.staking_info();
+        let program_id = self.cli.staking_program_id();
+
+        let mint_account = self.client.mint_account(mint)?;
+        let decimals = mint_account.decimals;
+        let ui_amount = self.cli.ui_amount();
+        let amount = spl_token::ui_amount_to_amount(ui_amount, decimals);
+
+        let admin_signers = self.admin_signers(primary_wallet.pubkey(), chill_nft::ID)?;
+        let signature = self.client.staking_add_token_reward(
+            primary_wallet,
+            &admin_signers,
+            payer,
+            staking_info,
+            mint,
+            amount,
+            program_id,
+        )?;
+
+        self.print_signature(&signature);
+
+        Ok(ProcessedData::Other)
+    }
+
+    pub fn process_staking_redeem_remaining(&self) -> Result<ProcessedData> {
+        let primary_wallet = self.cli.primary_wallet()?;
+        let payer = self.cli.payer()?;
+        let staking_info = self.cli.staking_info();
+        let program_id = self.cli.staking_program_id();
+
+        let staking = self.client.staking_info(staking_info)?;
+        self.assert_staking_finished(staking_info, &staking)?;
+
+        let decimals = self.client.mint_account(staking.mint)?.decimals;
+        let amount = staking.free_reward_amount()?;
+        if amount == 0 {
+            self.output.message("No remaining reward tokens to redeem".yellow());
+            return Ok(ProcessedData::Other);
+        }
+
+        let admin_signers = self.admin_signers(primary_wallet.pubkey(), chill_nft::ID)?;
+        let signature = self.client.staking_redeem_remaining_reward_tokens(
+            primary_wallet,
+            &admin_signers,
+            payer,
+            staking_info,
+            staking.mint,
+            amount,
+            program_id,
+        )?;
+
+        self.output.value(
+            "Redeemed reward tokens:".green(),
+            spl_token::amount_to_ui_amount(amount, decimals),
+        );
+        self.print_signature(&signature);
+
+        Ok(ProcessedData::Other)
+    }
 
-        let proxy_wallet = pda::proxy_wallet(account, primary_wallet, program_id);
+    pub fn process_staking_stake(&self) -> Result<ProcessedData> {
+        let user = self.cli.user_signer()?;
+        let payer = self.cli.payer()?;
+        let staking_info = self.cli.staking_info();
+        let referrer = self.cli.referrer();
+        let program_id = self.cli.staking_program_id();
 
-        let signature = self.client.withdraw_nft(
+        let mint = self.client.staking_info(staking_info)?.mint;
+        let decimals = self.client.mint_account(mint)?.decimals;
+        let amount = spl_token::ui_amount_to_amount(self.cli.ui_amount(), decimals);
+
+        let signature = match self.cli.stake_beneficiary() {
+            Some(beneficiary) => self.client.staking_stake_for(
+                user,
+                payer,
+                beneficiary,
+                staking_info,
+                mint,
+                amount,
+                program_id,
+            )?,
+            None => self.client.staking_stake(
+                user,
+                payer,
+                staking_info,
+                mint,
+                amount,
+                referrer,
+                self.cli.stake_nft(),
+                program_id,
+            )?,
+        };
+
+        self.print_signature(&signature);
+
+        Ok(ProcessedData::Other)
+    }
+
+    pub fn process_staking_claim(&self) -> Result<ProcessedData> {
+        let user = self.cli.user_signer()?;
+        let payer = self.cli.payer()?;
+        let staking_info = self.cli.staking_info();
+        let program_id = self.cli.staking_program_id();
+
+        let amount = if self.cli.claim_all() {
+            None
+        } else {
+            let mint = self.client.staking_info(staking_info)?.mint;
+            let decimals = self.client.mint_account(mint)?.decimals;
+            Some(spl_token::ui_amount_to_amount(self.cli.ui_amount(), decimals))
+        };
+
+        let signature = self
+            .client
+            .staking_claim(user, payer, staking_info, amount, program_id)?;
+
+        self.print_signature(&signature);
+
+        Ok(ProcessedData::Other)
+    }
+
+    pub fn process_staking_boost(&self) -> Result<ProcessedData> {
+        let user = self.cli.user_signer()?;
+        let payer = self.cli.payer()?;
+        let staking_info = self.cli.staking_info();
+        let program_id = self.cli.staking_program_id();
+
+        let signature = self
+            .client
+            .staking_boost(user, payer, staking_info, program_id)?;
+
+        self.print_signature(&signature);
+
+        Ok(ProcessedData::Other)
+    }
+
+    pub fn process_staking_cancel(&self) -> Result<ProcessedData> {
+        let user = self.cli.user_signer()?;
+        let payer = self.cli.payer()?;
+        let staking_info = self.cli.staking_info();
+        let program_id = self.cli.staking_program_id();
+
+        let signature = self
+            .client
+            .staking_cancel(user, payer, staking_info, program_id)?;
+
+        self.print_signature(&signature);
+
+        Ok(ProcessedData::Other)
+    }
+
+    pub fn process_staking_close_user_info(&self) -> Result<ProcessedData> {
+        let user = self.cli.user_signer()?;
+        let payer = self.cli.payer()?;
+        let staking_info = self.cli.staking_info();
+        let recipient = self.cli.recipient();
+        let program_id = self.cli.staking_program_id();
+
+        let user_info = pda::user_info(staking_info, user.pubkey(), program_id);
+        let rent = self.client.balance(user_info)?;
+
+        let signature = self.client.staking_close_user_info(
+            user,
             payer,
-            authority,
-            proxy_wallet,
+            staking_info,
             recipient,
-            mint,
             program_id,
         )?;
 
+        self.output.value_with_unit("Reclaimed rent:".green(), lamports_to_sol(rent), "SOL");
         self.print_signature(&signature);
 
         Ok(ProcessedData::Other)
     }
 
-    pub fn process_staking_initialize(&self) -> Result<ProcessedData> {
+    pub fn process_staking_close_user_infos(&self) -> Result<ProcessedData> {
         let primary_wallet = self.cli.primary_wallet()?;
         let payer = self.cli.payer()?;
-        let mint = self.get_mint()?;
-        let start_time = self.cli.start_time();
-        let end_time = self.cli.end_time();
-        let min_stake_size_ui = self.cli.min_stake_size();
+        let staking_info = self.cli.staking_info();
+        let treasury = self.cli.recipient();
         let program_id = self.cli.staking_program_id();
 
-        let mint_account = self.client.mint_account(mint)?;
-        let decimals = mint_account.decimals;
-        let min_stake_size = spl_token::ui_amount_to_amount(min_stake_size_ui, decimals);
+        let staking = self.client.staking_info(staking_info)?;
+        self.assert_staking_finished(staking_info, &staking)?;
 
-        let args = chill_staking::InitializeArgs {
-            start_time,
-            end_time,
-            min_stake_size,
+        // Users which still have tokens must claim them first
+        let user_infos = self
+            .client
+            .all_user_info_accounts(staking_info, program_id)?
+            .into_iter()
+            .filter_map(|(pubkey, account)| {
+                UserInfo::try_deserialize_versioned(&account.data)
+                    .ok()
+                    .filter(|user_info| {
+                        user_info.staked_amount == 0
+                            && user_info.pending_amount == 0
+                            && user_info.rewarded_amount == 0
+                    })
+                    .map(|_| (pubkey, account.lamports))
+            })
+            .collect::<Vec<_>>();
+
+        let batch_size = self.client.staking_close_batch_size(
+            primary_wallet.pubkey(),
+            payer.pubkey(),
+            staking_info,
+            treasury,
+            program_id,
+        );
+
+        let mut rent = 0;
+        for batch in user_infos.chunks(batch_size) {
+            let pubkeys = batch.iter().map(|(pubkey, _)| *pubkey).collect::<Vec<_>>();
+            let signature = self.client.staking_close_user_infos(
+                primary_wallet.clone(),
+                payer.clone(),
+                staking_info,
+                treasury,
+                &pubkeys,
+                program_id,
+            )?;
+            self.print_signature(&signature);
+
+            rent += batch.iter().map(|(_, lamports)| lamports).sum::<u64>();
+        }
+
+        self.output.value("Closed user infos:".green(), user_infos.len());
+        self.output.value_with_unit("Reclaimed rent:".green(), lamports_to_sol(rent), "SOL");
+
+        Ok(ProcessedData::Other)
+    }
+
+    pub fn process_staking_close_staking_info(&self) -> Result<ProcessedData> {
+        let primary_wallet = self.cli.primary_wallet()?;
+        let payer = self.cli.payer()?;
+        let staking_info = self.cli.staking_info();
+        let recipient = self.cli.recipient();
+        let program_id = self.cli.staking_program_id();
+
+        let staking = self.client.staking_info(staking_info)?;
+        self.assert_staking_finished(staking_info, &staking)?;
+        let rent = self.client.balance(staking_info)?;
+
+        let signature = self.client.staking_close_staking_info(
+            primary_wallet,
+            payer,
+            staking_info,
+            recipient,
+            program_id,
+        )?;
+
+        self.output.value_with_unit("Reclaimed rent:".green(), lamports_to_sol(rent), "SOL");
+        self.print_signature(&signature);
+
+        Ok(ProcessedData::Other)
+    }
+
+    pub fn process_staking_user_info(&self) -> Result<ProcessedData> {
+        let staking_info = self.cli.staking_info();
+        let user = self.cli.user();
+        let program_id = self.cli.staking_program_id();
+
+        let user_info_pubkey = pda::user_info(staking_info, user, program_id);
+        let user_info = self.client.staking_user_info(user_info_pubkey)?;
+        let mint = self.client.staking_info(staking_info)?.mint;
+        let decimals = self.client.mint_account(mint)?.decimals;
+        let ui_amount = |amount| spl_token::amount_to_ui_amount(amount, decimals);
+
+        self.output.value("User info:".green(), user_info_pubkey.to_string());
+        match user_info.start_day {
+            Some(start_day) => self.output.value("Active stake since day:".green(), start_day),
+            None => self.output.value("Active stake:".green(), "none"),
+        }
+        self.output.value("Staked amount:".green(), ui_amount(user_info.staked_amount));
+        self.output.value("Pending amount:".green(), ui_amount(user_info.pending_amount));
+        self.output.value("Rewarded amount:".green(), ui_amount(user_info.rewarded_amount));
+        self.output.value(
+            "Daily staking reward:".green(),
+            ui_amount(user_info.daily_staking_reward),
+        );
+        self.output.value("Total staked amount:".green(), ui_amount(user_info.total_staked_amount));
+        self.output.value(
+            "Total rewarded amount:".green(),
+            ui_amount(user_info.total_rewarded_amount),
+        );
+        self.output.value("Total boost number:".green(), user_info.total_boost_number);
+
+        Ok(ProcessedData::Other)
+    }
+
+    pub fn process_staking_audit(&self) -> Result<ProcessedData> {
+        let payer = self.cli.payer()?;
+        let staking_info = self.cli.staking_info();
+        let program_id = self.cli.staking_program_id();
+
+        let report = api::staking_audit(&self.client, payer, staking_info, program_id)?;
+
+        self.output.value("Token account amount:".green(), report.token_account_amount);
+        self.output.value("Free reward amount:".green(), report.free_reward_amount);
+        self.output.value("Pending withdrawal amount:".green(), report.pending_withdrawal_amount);
+        self.output.value("Required amount:".green(), report.required_amount);
+
+        if report.is_solvent {
+            self.output.message("Staking is solvent".green().bold());
+        } else {
+            self.output.message("Staking is insolvent".red().bold());
+        }
+
+        Ok(ProcessedData::StakingAudit(report))
+    }
+
+    pub fn process_staking_reconcile(&self) -> Result<ProcessedData> {
+        let payer = self.cli.payer()?;
+        let staking_info = self.cli.staking_info();
+        let program_id = self.cli.staking_program_id();
+
+        let report =
+            api::staking_reconcile(&self.client, payer.pubkey(), staking_info, program_id)?;
+
+        self.output.value("Users:".green(), report.users_number);
+        self.output.value("Total staked amount:".green(), report.total_staked_amount);
+        self.output.value("Staked amount of days:".green(), report.days_staked_amount);
+        self.output.value("Staked amount of users:".green(), report.users_staked_amount);
+        self.output.value("Total rewarded amount:".green(), report.total_rewarded_amount);
+        self.output.value("Rewarded amount of users:".green(), report.users_rewarded_amount);
+        self.output.value("Active stakes:".green(), report.active_stakes_number);
+        self.output.value("Active stakes of users:".green(), report.users_active_stakes_number);
+
+        // Closed user infos take their totals with them, so only exceeding
+        // sums of users are drift
+        let mut divergences = Vec::new();
+        if report.days_staked_amount != report.total_staked_amount {
+            divergences.push("staked amount of days");
+        }
+
+        if report.users_staked_amount > report.total_staked_amount {
+            divergences.push("staked amount of users");
+        }
+
+        if report.users_rewarded_amount > report.total_rewarded_amount {
+            divergences.push("rewarded amount of users");
+        }
+
+        if report.users_active_stakes_number != report.active_stakes_number {
+            divergences.push("active stakes of users");
+        }
+
+        if divergences.is_empty() {
+            self.output.message("Staking counters are consistent".green().bold());
+        } else {
+            let message = format!("Staking counters diverge: {}", divergences.join(", "));
+            self.output.message(message.red().bold());
+        }
+
+        Ok(ProcessedData::Other)
+    }
+
+    pub fn process_staking_migrate(&self) -> Result<ProcessedData> {
+        let payer = self.cli.payer()?;
+        let staking_info = self.cli.staking_info();
+        let program_id = self.cli.staking_program_id();
+
+        let legacy = api::staking_legacy_accounts(&self.client, staking_info, program_id)?;
+
+        // User infos are migrated against the staking info of the current layout
+        if legacy.staking_info {
+            let signature =
+                self.client
+                    .staking_migrate_staking_info(payer.clone(), staking_info, program_id)?;
+            self.print_signature(&signature);
+        }
+
+        let batch_size =
+            self.client
+                .staking_migrate_batch_size(payer.pubkey(), staking_info, program_id);
+
+        for batch in legacy.user_infos.chunks(batch_size) {
+            let signature = self.client.staking_migrate_user_infos(
+                payer.clone(),
+                staking_info,
+                batch,
+                program_id,
+            )?;
+            self.print_signature(&signature);
+        }
+
+        self.output.value("Migrated staking info:".green(), legacy.staking_info);
+        self.output.value("Migrated user infos:".green(), legacy.user_infos.len());
+
+        Ok(ProcessedData::Other)
+    }
+
+    pub fn process_staking_snapshot(&self) -> Result<ProcessedData> {
+        let staking_info = self.cli.staking_info();
+        let program_id = self.cli.staking_program_id();
+        let out = self.cli.out();
+
+        let mint = self.client.staking_info(staking_info)?.mint;
+        let decimals = self.client.mint_account(mint)?.decimals;
+        let snapshot = api::staking_snapshot(&self.client, staking_info, program_id)?;
+
+        // Amounts are in UI units as the claims file of drops
+        let ui_amount = |amount| spl_token::amount_to_ui_amount(amount, decimals);
+        let mut content = "# user,staked,pending,rewarded\n".to_owned();
+        for entry in &snapshot {
+            content += &format!(
+                "{},{},{},{}\n",
+                entry.user,
+                ui_amount(entry.staked_amount),
+                ui_amount(entry.pending_amount),
+                ui_amount(entry.rewarded_amount),
+            );
+        }
+
+        fs::write(out, content).map_err(|_| CliError::CannotWriteToFile(out.to_owned()))?;
+
+        self.output.value("Users:".green(), snapshot.len());
+        self.output.value("Snapshot:".green(), out);
+
+        Ok(ProcessedData::Other)
+    }
+
+    pub fn process_staking_redistribute(&self) -> Result<ProcessedData> {
+        let primary_wallet = self.cli.primary_wallet()?;
+        let payer = self.cli.payer()?;
+        let staking_info = self.cli.staking_info();
+        let program_id = self.cli.staking_program_id();
+
+        let staking = self.client.staking_info(staking_info)?;
+        let decimals = self.client.mint_account(staking.mint)?.decimals;
+        let user_infos = self.client.staking_user_infos(
+            staking_info,
+            staking.days_in_window(),
+            program_id,
+        )?;
+        let batch_size =
+            self.client
+                .staking_redistribution_batch_size(payer.pubkey(), staking_info, program_id);
+
+        let redistribution = match self.client.staking_redistribution(staking_info, program_id)? {
+            Some(redistribution) => redistribution,
+            None => {
+                // Rewards of all stakes must be known before the unspent amount is calculated
+                let active_stakes = user_infos
+                    .iter()
+                    .filter_map(|(pubkey, user_info)| user_info.start_day.map(|_| *pubkey))
+                    .collect::<Vec<_>>();
+
+                for batch in active_stakes.chunks(batch_size) {
+                    let signature = self.client.staking_settle_stakes(
+                        payer.clone(),
+                        staking_info,
+                        batch,
+                        program_id,
+                    )?;
+                    self.print_signature(&signature);
+                }
+
+                let signature = self.client.staking_start_redistribution(
+                    primary_wallet,
+                    payer.clone(),
+                    staking_info,
+                    program_id,
+                )?;
+                self.print_signature(&signature);
+
+                self.client
+                    .staking_redistribution(staking_info, program_id)?
+                    .ok_or(CliError::RedistributionDataError)?
+            }
         };
 
-        let staking_info = Keypair::new();
-        println!("{} {}", "StakingInfo:".green(), staking_info.pubkey());
+        self.output.value(
+            "Redistributed amount:".cyan(),
+            spl_token::amount_to_ui_amount(redistribution.amount, decimals),
+        );
+
+        // Users credited by previous runs already have receipts
+        let weighted_users = user_infos
+            .iter()
+            .filter_map(|(pubkey, user_info)| (user_info.total_staked_amount > 0).then_some(*pubkey))
+            .collect::<Vec<_>>();
+
+        let receipts = weighted_users
+            .iter()
+            .map(|user_info| pda::redistribution_receipt(*user_info, program_id))
+            .collect::<Vec<_>>();
+
+        let existing = self.client.existing_accounts(&receipts)?;
+        let pending_users = weighted_users
+            .iter()
+            .zip(existing)
+            .filter_map(|(user_info, exists)| (!exists).then_some(*user_info))
+            .collect::<Vec<_>>();
+
+        let mut credited = 0;
+        let mut failed = 0;
+        let mut batches = pending_users.chunks(batch_size);
+        for batch in batches.by_ref() {
+            match self
+                .client
+                .staking_redistribute(payer.clone(), staking_info, batch, program_id)
+            {
+                Ok(signature) => {
+                    self.print_signature(&signature);
+                    credited += batch.len();
+                }
+                Err(error) => {
+                    self.output.message(error);
+                    failed += batch.len();
+
+                    if self.client.rpc_budget_exhausted() {
+                        break;
+                    }
+                }
+            }
+        }
 
-        let signature = self.client.staking_initialize(
-            &staking_info,
+        failed += batches.map(<[Pubkey]>::len).sum::<usize>();
+        self.output.value("Credited users:".green(), credited);
+
+        if failed > 0 {
+            self.output.value("Failed users:".red(), failed);
+        }
+
+        self.print_rpc_usage();
+        Ok(ProcessedData::Other)
+    }
+
+    pub fn process_drop_generate_tree(&self) -> Result<ProcessedData> {
+        let claims_file = self.cli.claims_file();
+        let tree_file = self.cli.tree_file();
+        let decimals = self.cli.decimals();
+
+        let claims = drop::read_claims_csv(claims_file, decimals)?;
+        let tree = DropTree::new(&claims, decimals);
+        tree.write(tree_file)?;
+
+        self.output.value("Merkle root:".green(), chill_sdk::merkle::to_hex(&tree.root));
+        self.output.value("Claims:".cyan(), tree.claims.len());
+        self.output.value(
+            "Total amount:".cyan(),
+            spl_token::amount_to_ui_amount(tree.total_amount, decimals),
+        );
+
+        Ok(ProcessedData::Other)
+    }
+
+    pub fn process_drop_initialize(&self) -> Result<ProcessedData> {
+        let primary_wallet = self.cli.primary_wallet()?;
+        let payer = self.cli.payer()?;
+        let mint = self.get_mint()?;
+        let tree = DropTree::read(self.cli.tree_file())?;
+        let program_id = self.cli.drop_program_id();
+
+        let kind = if self.cli.whitelist() {
+            DropKind::WhitelistSlots
+        } else {
+            let decimals = self.client.mint_account(mint)?.decimals;
+            if decimals != tree.decimals {
+                self.output.message(format!(
+                    "{} the tree is built for {} decimals, the mint has {}",
+                    "Warning:".yellow(),
+                    tree.decimals,
+                    decimals
+                ));
+            }
+
+            DropKind::Tokens
+        };
+
+        let distributor = pda::distributor(primary_wallet.pubkey(), tree.root, program_id);
+        self.output.value("Distributor:".green(), distributor.to_string());
+
+        let signature = self.client.drop_initialize(
             primary_wallet,
             payer,
             mint,
-            args,
+            tree.root,
+            tree.total_amount,
+            kind,
             program_id,
         )?;
 
-        let file_name = "staking_info.pubkey";
-        let mut file = fs::OpenOptions::new()
-            .append(true)
-            .create(true)
-            .open(file_name)?;
+        self.print_signature(&signature);
+
+        Ok(ProcessedData::Other)
+    }
 
-        writeln!(file, "{}", staking_info.pubkey())
-            .map_err(|_| CliError::CannotWriteToFile(file_name.to_owned()))?;
+    pub fn process_drop_claim(&self) -> Result<ProcessedData> {
+        let claimant = self.cli.claimant()?;
+        let payer = self.cli.payer()?;
+        let distributor = self.cli.distributor();
+        let tree = DropTree::read(self.cli.tree_file())?;
+        let program_id = self.cli.drop_program_id();
+
+        if self.client.distributor(distributor)?.merkle_root != tree.root {
+            return Err(CliError::DropTreeMismatch(distributor).into());
+        }
+
+        let claim = tree
+            .claim(claimant.pubkey())
+            .ok_or_else(|| CliError::NotInDrop(claimant.pubkey()))?;
+
+        let signature = self.client.drop_claim(
+            claimant,
+            payer,
+            distributor,
+            claim.index,
+            claim.amount,
+            claim.proof.clone(),
+            program_id,
+        )?;
 
         self.print_signature(&signature);
 
         Ok(ProcessedData::Other)
     }
 
-    pub fn process_staking_add_reward_tokens(&self) -> Result<ProcessedData> {
+    pub fn process_vesting_initialize(&self) -> Result<ProcessedData> {
         let primary_wallet = self.cli.primary_wallet()?;
         let payer = self.cli.payer()?;
+        let beneficiary = self.cli.beneficiary_pubkey();
         let mint = self.get_mint()?;
-        let staking_info = self.cli.staking_info();
-        let program_id = self.cli.staking_program_id();
+        let program_id = self.cli.vesting_program_id();
+
+        let decimals = self.client.mint_account(mint)?.decimals;
+        let total_amount = spl_token::ui_amount_to_amount(self.cli.ui_amount(), decimals);
+
+        let args = chill_vesting::InitializeVestingArgs {
+            start_time: self.cli.start_time(),
+            cliff_time: self.cli.cliff_time(),
+            end_time: self.cli.end_time(),
+            period: self.cli.period(),
+            total_amount,
+            revocable: self.cli.revocable(),
+        };
 
-        let mint_account = self.client.mint_account(mint)?;
-        let decimals = mint_account.decimals;
-        let ui_amount = self.cli.ui_amount();
-        let amount = spl_token::ui_amount_to_amount(ui_amount, decimals);
+        let vesting = pda::vesting(primary_wallet.pubkey(), beneficiary, mint, program_id);
+        self.output.value("Vesting:".green(), vesting.to_string());
 
-        let signature = self.client.staking_add_token_reward(
+        let signature = self.client.vesting_initialize(
             primary_wallet,
             payer,
-            staking_info,
+            beneficiary,
             mint,
-            amount,
+            args,
             program_id,
         )?;
 
@@ -494,29 +2648,126 @@ impl App<'_> {
         Ok(ProcessedData::Other)
     }
 
+    pub fn process_vesting_claim(&self) -> Result<ProcessedData> {
+        let beneficiary = self.cli.beneficiary()?;
+        let payer = self.cli.payer()?;
+        let vesting = self.cli.vesting();
+        let program_id = self.cli.vesting_program_id();
+
+        let signature = self
+            .client
+            .vesting_claim(beneficiary, payer, vesting, program_id)?;
+
+        self.print_signature(&signature);
+
+        Ok(ProcessedData::Other)
+    }
+
+    pub fn process_vesting_revoke(&self) -> Result<ProcessedData> {
+        let primary_wallet = self.cli.primary_wallet()?;
+        let payer = self.cli.payer()?;
+        let vesting = self.cli.vesting();
+        let program_id = self.cli.vesting_program_id();
+
+        let signature = self
+            .client
+            .vesting_revoke(primary_wallet, payer, vesting, program_id)?;
+
+        self.print_signature(&signature);
+
+        Ok(ProcessedData::Other)
+    }
+
+    pub fn process_completions(&self) -> Result<ProcessedData> {
+        print!("{}", self.cli.completions());
+        Ok(ProcessedData::Other)
+    }
+
+    pub fn process_generate_man(&self) -> Result<ProcessedData> {
+        print!("{}", self.cli.man_page());
+        Ok(ProcessedData::Other)
+    }
+
     pub fn run_with_result(&self) -> Result<ProcessedData> {
         match self.cli.command() {
+            CliCommand::AdminConfigRegisterType => self.process_admin_config_register_type(),
+            CliCommand::AdminConfigUpdate => self.process_admin_config_update(),
+            CliCommand::AdminNftFinalize => self.process_admin_nft_finalize(),
+            CliCommand::AdminNftSetMinGameVersion => self.process_admin_nft_set_min_game_version(),
+            CliCommand::AdminSignersSet => self.process_admin_signers_set(),
+            CliCommand::AdminStakingPause => self.process_admin_staking_pause(),
+            CliCommand::AdminStakingResume => self.process_admin_staking_resume(),
+            CliCommand::AdminWalletFreeze => self.process_admin_wallet_freeze(),
+            CliCommand::AdminWalletSetFee => self.process_admin_wallet_set_fee(),
+            CliCommand::AdminWalletSetLimits => self.process_admin_wallet_set_limits(),
+            CliCommand::AdminWalletUnfreeze => self.process_admin_wallet_unfreeze(),
             CliCommand::Balance => self.process_print_balance(),
+            CliCommand::Completions => self.process_completions(),
+            CliCommand::CreateCollection => self.process_create_collection(),
+            CliCommand::GenerateMan => self.process_generate_man(),
             CliCommand::Info => self.process_print_info(),
             CliCommand::Initialize => self.process_nft_initialize(),
+            CliCommand::ListConfigs => self.process_list_configs(),
+            CliCommand::ListNfts => self.process_list_nfts(),
             CliCommand::Mint => self.process_mint(),
             CliCommand::MintNft => self.process_mint_nft(),
+            CliCommand::MintNftBatch => self.process_mint_nft_batch(),
             CliCommand::UpdateNft => self.process_update_nft(),
+            CliCommand::ReclaimMetadata => self.process_reclaim_metadata(),
             CliCommand::Transfer => self.process_transfer(),
             CliCommand::CreateWallet => self.process_create_wallet(),
+            CliCommand::CreateWallets => self.process_create_wallets(),
             CliCommand::WithdrawLamports => self.process_withdraw_lamports(),
             CliCommand::WithdrawFt => self.process_withdraw_ft(),
             CliCommand::WithdrawNft => self.process_withdraw_nft(),
+            CliCommand::ScheduleWithdrawal => self.process_schedule_withdrawal(),
+            CliCommand::ExecuteWithdrawal => self.process_execute_withdrawal(),
+            CliCommand::CancelWithdrawal => self.process_cancel_withdrawal(),
+            CliCommand::WalletDepositAddress => self.process_wallet_deposit_address(),
+            CliCommand::WalletInfo => self.process_wallet_info(),
+            CliCommand::WalletList => self.process_wallet_list(),
+            CliCommand::VerifyDeployment => self.process_verify_deployment(),
+            CliCommand::DiffConfig => self.process_diff_config(),
+            CliCommand::Submit => self.process_submit(),
+            CliCommand::DropGenerateTree => self.process_drop_generate_tree(),
+            CliCommand::DropInitialize => self.process_drop_initialize(),
+            CliCommand::DropClaim => self.process_drop_claim(),
+            CliCommand::VestingInitialize => self.process_vesting_initialize(),
+            CliCommand::VestingClaim => self.process_vesting_claim(),
+            CliCommand::VestingRevoke => self.process_vesting_revoke(),
             CliCommand::StakingInitialize => self.process_staking_initialize(),
             CliCommand::StakingAddRewardTokens => self.process_staking_add_reward_tokens(),
+            CliCommand::StakingAudit => self.process_staking_audit(),
+            CliCommand::StakingMigrate => self.process_staking_migrate(),
+            CliCommand::StakingReconcile => self.process_staking_reconcile(),
+            CliCommand::StakingSnapshot => self.process_staking_snapshot(),
+            CliCommand::StakingClone => self.process_staking_clone(),
+            CliCommand::StakingRedistribute => self.process_staking_redistribute(),
+            CliCommand::StakingRedeemRemaining => self.process_staking_redeem_remaining(),
+            CliCommand::StakingStake => self.process_staking_stake(),
+            CliCommand::StakingClaim => self.process_staking_claim(),
+            CliCommand::StakingBoost => self.process_staking_boost(),
+            CliCommand::StakingCancel => self.process_staking_cancel(),
+            CliCommand::StakingInfo => self.process_staking_user_info(),
+            CliCommand::StakingCloseUserInfo => self.process_staking_close_user_info(),
+            CliCommand::StakingCloseUserInfos => self.process_staking_close_user_infos(),
+            CliCommand::StakingCloseStakingInfo => self.process_staking_close_staking_info(),
         }
     }
 
     pub fn run(&self) {
         let result = self.run_with_result();
 
+        match self.cli.transaction_mode() {
+            TransactionMode::Simulate => self.print_simulations(),
+            TransactionMode::SignOnly => self.print_signed_transactions(),
+            TransactionMode::Send => {}
+        }
+
         if let Err(error) = result {
             self.on_error(error);
         }
+
+        self.output.finish(None);
     }
 }
@@ -1,26 +1,42 @@
-use crate::error::{CliError, Result};
+use crate::{
+    client::{ComputeBudget, Nonce, RpcBudget, SendRetries, TransactionMode},
+    config::CliConfig,
+    error::{CliError, Result},
+    man,
+    output::OutputFormat,
+};
 use anchor_client::{
-    solana_sdk::{pubkey::Pubkey, signature::Signer},
+    solana_sdk::{
+        hash::Hash,
+        pubkey::Pubkey,
+        signature::{Signature, Signer},
+        transaction::Transaction,
+    },
     Cluster,
 };
 use chill_nft::{
-    state::{Config, NftType, Recipient, UiFees},
-    utils::NftArgs,
+    state::{Config, Recipient, UiFees},
+    utils::{NftArgs, UseMethod, Uses},
 };
 use clap::{
     crate_description, crate_name, crate_version, value_t_or_exit, values_t_or_exit, App,
-    AppSettings, Arg, ArgMatches, SubCommand,
+    AppSettings, Arg, ArgMatches, ErrorKind, Shell, SubCommand,
 };
 use lazy_static::lazy_static;
 use solana_clap_utils::{
-    input_parsers::{pubkey_of, pubkeys_of, unix_timestamp_from_rfc3339_datetime},
+    input_parsers::{
+        pubkey_of, pubkeys_of, pubkeys_sigs_of, unix_timestamp_from_rfc3339_datetime, value_of,
+    },
     input_validators::{
-        is_pubkey, is_pubkey_or_keypair, is_rfc3339_datetime, is_url_or_moniker, is_valid_signer,
+        is_derivation, is_parsable, is_pubkey, is_pubkey_or_keypair, is_pubkey_sig,
+        is_rfc3339_datetime, is_url_or_moniker, is_valid_percentage, is_valid_signer,
         normalize_to_url_if_moniker,
     },
-    keypair::signer_from_path,
+    keypair::{pubkey_from_path, signer_from_path, ASK_KEYWORD, SKIP_SEED_PHRASE_VALIDATION_ARG},
+    nonce::{nonce_authority_arg, NONCE_ARG, NONCE_AUTHORITY_ARG},
+    offline::{blockhash_arg, sign_only_arg, BLOCKHASH_ARG, SIGNER_ARG, SIGN_ONLY_ARG},
 };
-use std::{error, fs, path::Path, rc::Rc, str::FromStr};
+use std::{collections::HashMap, error, fs, path::Path, rc::Rc, str::FromStr, time::Duration};
 
 lazy_static! {
     pub static ref DEFAULT_KEYPAIR: Option<String> = {
@@ -32,28 +48,142 @@ lazy_static! {
 }
 
 pub const COMMAND_BALANCE: &str = "balance";
+const COMMAND_CANCEL_WITHDRAWAL: &str = "cancel-withdrawal";
+const COMMAND_COMPLETIONS: &str = "completions";
+const COMMAND_CREATE_COLLECTION: &str = "create-collection";
 pub const COMMAND_CREATE_WALLET: &str = "create-wallet";
+const COMMAND_CREATE_WALLETS: &str = "create-wallets";
+const COMMAND_DIFF_CONFIG: &str = "diff-config";
+const COMMAND_EXECUTE_WITHDRAWAL: &str = "execute-withdrawal";
+const COMMAND_GENERATE_MAN: &str = "generate-man";
 pub const COMMAND_INFO: &str = "info";
 const COMMAND_INITIALIZE: &str = "initialize";
+const COMMAND_LIST_CONFIGS: &str = "list-configs";
+const COMMAND_LIST_NFTS: &str = "list-nfts";
 const COMMAND_MINT: &str = "mint";
 const COMMAND_MINT_NFT: &str = "mint-nft";
+const COMMAND_MINT_NFT_BATCH: &str = "mint-nft-batch";
+const COMMAND_RECLAIM_METADATA: &str = "reclaim-metadata";
+const COMMAND_SCHEDULE_WITHDRAWAL: &str = "schedule-withdrawal";
+const COMMAND_SUBMIT: &str = "submit";
 const COMMAND_TRANSFER: &str = "transfer";
 const COMMAND_UPDATE_NFT: &str = "update-nft";
+const COMMAND_VERIFY_DEPLOYMENT: &str = "verify-deployment";
 const COMMAND_WITHDRAW_FT: &str = "withdraw-ft";
 const COMMAND_WITHDRAW_LAMPORTS: &str = "withdraw-lamports";
 const COMMAND_WITHDRAW_NFT: &str = "withdraw-nft";
 
 const COMMAND_STAKING: &str = "staking";
 const COMMAND_ADD_REWARD_TOKENS: &str = "add-reward-tokens";
+const COMMAND_AUDIT: &str = "audit";
+const COMMAND_BOOST: &str = "boost";
+const COMMAND_CANCEL: &str = "cancel";
+const COMMAND_CLONE: &str = "clone";
+const COMMAND_CLOSE_STAKING_INFO: &str = "close-staking-info";
+const COMMAND_CLOSE_USER_INFO: &str = "close-user-info";
+const COMMAND_CLOSE_USER_INFOS: &str = "close-user-infos";
+const COMMAND_MIGRATE: &str = "migrate";
+const COMMAND_RECONCILE: &str = "reconcile";
+const COMMAND_REDEEM_REMAINING: &str = "redeem-remaining";
+const COMMAND_REDISTRIBUTE: &str = "redistribute";
+const COMMAND_SNAPSHOT: &str = "snapshot";
+const COMMAND_STAKE: &str = "stake";
 const COMMAND_STAKING_INITIALIZE: &str = "staking-initialize";
 const COMMAND_STAKING_ADD_REWARD_TOKENS: &str = "staking-add-reward-tokens";
+const COMMAND_STAKING_AUDIT: &str = "staking-audit";
+const COMMAND_STAKING_CLONE: &str = "staking-clone";
+const COMMAND_STAKING_CLOSE_STAKING_INFO: &str = "staking-close-staking-info";
+const COMMAND_STAKING_CLOSE_USER_INFO: &str = "staking-close-user-info";
+const COMMAND_STAKING_CLOSE_USER_INFOS: &str = "staking-close-user-infos";
+const COMMAND_STAKING_MIGRATE: &str = "staking-migrate";
+const COMMAND_STAKING_RECONCILE: &str = "staking-reconcile";
+const COMMAND_STAKING_REDEEM_REMAINING: &str = "staking-redeem-remaining";
+const COMMAND_STAKING_REDISTRIBUTE: &str = "staking-redistribute";
+const COMMAND_STAKING_SNAPSHOT: &str = "staking-snapshot";
+const COMMAND_STAKING_STAKE: &str = "staking-stake";
+const COMMAND_STAKING_CLAIM: &str = "staking-claim";
+const COMMAND_STAKING_BOOST: &str = "staking-boost";
+const COMMAND_STAKING_CANCEL: &str = "staking-cancel";
+const COMMAND_STAKING_INFO: &str = "staking-info";
+
+const COMMAND_WALLET: &str = "wallet";
+const COMMAND_DEPOSIT_ADDRESS: &str = "deposit-address";
+const COMMAND_LIST: &str = "list";
+const COMMAND_WALLET_DEPOSIT_ADDRESS: &str = "wallet-deposit-address";
+const COMMAND_WALLET_INFO: &str = "wallet-info";
+const COMMAND_WALLET_LIST: &str = "wallet-list";
+
+const COMMAND_DROP: &str = "drop";
+const COMMAND_GENERATE_TREE: &str = "generate-tree";
+const COMMAND_CLAIM: &str = "claim";
+const COMMAND_DROP_GENERATE_TREE: &str = "drop-generate-tree";
+const COMMAND_DROP_INITIALIZE: &str = "drop-initialize";
+const COMMAND_DROP_CLAIM: &str = "drop-claim";
+
+const COMMAND_VESTING: &str = "vesting";
+const COMMAND_REVOKE: &str = "revoke";
+const COMMAND_VESTING_CLAIM: &str = "vesting-claim";
+const COMMAND_VESTING_INITIALIZE: &str = "vesting-initialize";
+const COMMAND_VESTING_REVOKE: &str = "vesting-revoke";
+
+const COMMAND_ADMIN: &str = "admin";
+const COMMAND_NFT: &str = "nft";
+const COMMAND_SET_MIN_GAME_VERSION: &str = "set-min-game-version";
+const COMMAND_ADMIN_NFT_SET_MIN_GAME_VERSION: &str = "admin-nft-set-min-game-version";
+const COMMAND_FINALIZE: &str = "finalize";
+const COMMAND_ADMIN_NFT_FINALIZE: &str = "admin-nft-finalize";
+const COMMAND_PAUSE: &str = "pause";
+const COMMAND_RESUME: &str = "resume";
+const COMMAND_ADMIN_STAKING_PAUSE: &str = "admin-staking-pause";
+const COMMAND_ADMIN_STAKING_RESUME: &str = "admin-staking-resume";
+const COMMAND_SIGNERS: &str = "signers";
+const COMMAND_SET: &str = "set";
+const COMMAND_ADMIN_SIGNERS_SET: &str = "admin-signers-set";
+const COMMAND_CONFIG: &str = "config";
+const COMMAND_UPDATE: &str = "update";
+const COMMAND_ADMIN_CONFIG_UPDATE: &str = "admin-config-update";
+const COMMAND_REGISTER_TYPE: &str = "register-type";
+const COMMAND_ADMIN_CONFIG_REGISTER_TYPE: &str = "admin-config-register-type";
+const COMMAND_SET_LIMITS: &str = "set-limits";
+const COMMAND_ADMIN_WALLET_SET_LIMITS: &str = "admin-wallet-set-limits";
+const COMMAND_SET_FEE: &str = "set-fee";
+const COMMAND_ADMIN_WALLET_SET_FEE: &str = "admin-wallet-set-fee";
+const COMMAND_FREEZE: &str = "freeze";
+const COMMAND_UNFREEZE: &str = "unfreeze";
+const COMMAND_ADMIN_WALLET_FREEZE: &str = "admin-wallet-freeze";
+const COMMAND_ADMIN_WALLET_UNFREEZE: &str = "admin-wallet-unfreeze";
 
 pub const ACCOUNT: &str = "account";
+const ADMIN_SIGNER: &str = "admin-signer";
+const ALL: &str = "all";
 const AMOUNT: &str = "amount";
 const AUTHORITY: &str = "authority";
+const BENEFICIARY: &str = "beneficiary";
+const CREATE_TOKEN_ACCOUNT: &str = "create-token-account";
 const CREATOR: &str = "creator";
+const CREATOR_SHARE: &str = "creator-share";
 const DECIMALS: &str = "decimals";
+const CLAIM_FEE: &str = "claim-fee";
+const CLAIMANT: &str = "claimant";
+const CLAIMS_FILE: &str = "claims-file";
+const CLUSTER_A: &str = "a";
+const COLLECTION: &str = "collection";
+const CLUSTER_B: &str = "b";
+const CLIFF_TIMESTAMP: &str = "cliff";
+const COMPUTE_UNITS: &str = "compute-units";
+const CONFIG_FILE: &str = "config";
+const DAYS_IN_WINDOW: &str = "days-in-window";
+const DERIVATION: &str = "derivation";
+const DISTRIBUTOR: &str = "distributor";
+const DAILY_FT: &str = "daily-ft";
+const DAILY_LAMPORTS: &str = "daily-lamports";
+const PRIMARY_WALLET_DAILY_FT: &str = "primary-wallet-daily-ft";
+const PRIMARY_WALLET_DAILY_LAMPORTS: &str = "primary-wallet-daily-lamports";
 const END_TIMESTAMP: &str = "end";
+const EXPORT: &str = "export";
+const FEE: &str = "fee";
+const FEE_BPS: &str = "fee-bps";
+const FEE_RECEIVER: &str = "fee-receiver";
 const FEES: &str = "fees";
 const FEES_CHARACTER: &str = "character";
 const FEES_EMOTE: &str = "emote";
@@ -61,34 +191,127 @@ const FEES_ITEM: &str = "item";
 const FEES_PET: &str = "pet";
 const FEES_TILESET: &str = "tileset";
 const FEES_WORLD: &str = "world";
+const GENERATE_MAN: &str = "generate-man";
+const INTERVAL: &str = "interval";
+const MANIFEST: &str = "manifest";
 pub const MINT: &str = "mint-address";
+const MINT_A: &str = "mint-a";
+const MINT_B: &str = "mint-b";
 const MINT_SHARE: &str = "mint-share";
+const MIN_GAME_VERSION: &str = "min-game-version";
 const MIN_STAKE_SIZE: &str = "min-stake-size";
 const NAME: &str = "name";
+const NFT: &str = "nft";
+const NFT_PROGRAM_ID: &str = "nft-program-id";
 const NFT_TYPE: &str = "type";
+const OUT: &str = "out";
+const OUTPUT: &str = "output";
+const OWNER: &str = "owner";
+const PERIOD: &str = "period";
+const PRIORITY_FEE: &str = "priority-fee";
 pub const PAYER: &str = "payer";
+const PRIMARY_SALE_HAPPENED: &str = "primary-sale-happened";
 pub const PRIMARY_WALLET: &str = "primary-wallet";
 pub const PROGRAM_ID: &str = "program-id";
+const QR: &str = "qr";
 const RECIPIENT: &str = "recipient";
+const RETRIES: &str = "retries";
+const RETRY_BACKOFF: &str = "retry-backoff";
+const REFERRAL_FEE: &str = "referral-fee";
+const REFERRER: &str = "referrer";
+const REVOCABLE: &str = "revocable";
 pub const RPC_URL: &str = "url";
+const RPC_BUDGET: &str = "rpc-budget";
+const RPC_BUDGET_ABORT: &str = "rpc-budget-abort";
 const SAVE_PATH: &str = "save-path";
+const SEND_RETRIES: &str = "send-retries";
+const SHELL: &str = "shell";
+const SIGNERS: &str = "signers";
+const SIMULATE: &str = "simulate";
+const STAKING_PROGRAM_ID: &str = "staking-program-id";
 const STAKING_INFO: &str = "staking-info";
+const STAKING_INFO_A: &str = "staking-info-a";
+const STAKING_INFO_B: &str = "staking-info-b";
 const START_TIMESTAMP: &str = "start";
 const SYMBOL: &str = "symbol";
+const THRESHOLD: &str = "threshold";
+const TRANSACTION: &str = "transaction";
 const TRANSACTION_SHARE: &str = "transaction-share";
+const TRANSFER_REMAINING: &str = "transfer-remaining";
+const TREE_FILE: &str = "tree-file";
 const URI: &str = "uri";
+const USE_METHOD: &str = "use-method";
+const USER: &str = "user";
+const USERS_FILE: &str = "users-file";
+const USES: &str = "uses";
+const VALIDATE_URI: &str = "validate-uri";
+const VESTING: &str = "vesting";
+const WALLET_PROGRAM_ID: &str = "wallet-program-id";
+const WHITELIST: &str = "whitelist";
+const YES: &str = "yes";
+
+const BIP44_SOLANA_PREFIX: &str = "m/44'/501'";
 
 pub enum CliCommand {
+    AdminConfigRegisterType,
+    AdminConfigUpdate,
+    AdminNftFinalize,
+    AdminNftSetMinGameVersion,
+    AdminSignersSet,
+    AdminStakingPause,
+    AdminStakingResume,
+    AdminWalletFreeze,
+    AdminWalletSetFee,
+    AdminWalletSetLimits,
+    AdminWalletUnfreeze,
     Balance,
+    CancelWithdrawal,
+    Completions,
+    CreateCollection,
     CreateWallet,
+    CreateWallets,
+    DiffConfig,
+    DropClaim,
+    DropGenerateTree,
+    DropInitialize,
+    ExecuteWithdrawal,
+    GenerateMan,
     Info,
     Initialize,
+    ListConfigs,
+    ListNfts,
     Mint,
     MintNft,
+    MintNftBatch,
+    ReclaimMetadata,
     StakingAddRewardTokens,
+    StakingAudit,
+    StakingBoost,
+    StakingCancel,
+    StakingClaim,
+    StakingClone,
+    StakingCloseStakingInfo,
+    StakingCloseUserInfo,
+    StakingCloseUserInfos,
+    StakingInfo,
     StakingInitialize,
+    StakingMigrate,
+    StakingReconcile,
+    StakingRedeemRemaining,
+    StakingRedistribute,
+    StakingSnapshot,
+    StakingStake,
+    ScheduleWithdrawal,
+    Submit,
     Transfer,
     UpdateNft,
+    VerifyDeployment,
+    VestingClaim,
+    VestingInitialize,
+    VestingRevoke,
+    WalletDepositAddress,
+    WalletInfo,
+    WalletList,
     WithdrawFt,
     WithdrawLamports,
     WithdrawNft,
@@ -96,6 +319,8 @@ pub enum CliCommand {
 
 pub struct Cli<'a> {
     matches: ArgMatches<'a>,
+    signers: HashMap<String, Rc<dyn Signer>>,
+    config: CliConfig,
 }
 
 fn is_mint_pubkey(string: String) -> core::result::Result<(), String> {
@@ -121,18 +346,105 @@ fn is_mint_pubkey(string: String) -> core::result::Result<(), String> {
     ))
 }
 
+pub fn mint_file(cluster: &Cluster) -> &'static str {
+    match cluster {
+        Cluster::Testnet => "mint.testnet.pubkey",
+        Cluster::Mainnet => "mint.mainnet.pubkey",
+        Cluster::Devnet => "mint.devnet.pubkey",
+        Cluster::Localnet => "mint.localnet.pubkey",
+        Cluster::Debug => "mint.debug.pubkey",
+        Cluster::Custom(_, _) => "mint.url.pubkey",
+    }
+}
+
+/// Parses the mint pubkey or reads it from the file of the path
+pub fn parse_mint(mint: &str) -> Result<Option<Pubkey>> {
+    let mint = mint.trim();
+    if let Ok(pubkey) = Pubkey::from_str(mint) {
+        return Ok(Some(pubkey));
+    }
+
+    if Path::new(mint).is_file() {
+        let pubkey_from_file = fs::read_to_string(mint)?;
+        let pubkey = Pubkey::from_str(pubkey_from_file.trim())
+            .map_err(|e| CliError::CannotParseFile(mint.to_string(), e.to_string()))?;
+        return Ok(Some(pubkey));
+    }
+
+    Ok(None)
+}
+
+fn derivation_key(path: &str) -> &str {
+    path.strip_prefix(BIP44_SOLANA_PREFIX)
+        .map(|key| key.trim_start_matches('/'))
+        .unwrap_or(path)
+}
+
+fn is_derivation_path(string: String) -> core::result::Result<(), String> {
+    let key = derivation_key(&string);
+    if key.is_empty() {
+        return Ok(());
+    }
+
+    is_derivation(key)
+}
+
 impl<'a> Cli<'a> {
     pub fn init() -> Self {
         let app = Self::build_app();
-        Self {
-            matches: app.get_matches(),
-        }
+        let matches = app.get_matches();
+        Self::check_command(&matches).unwrap_or_else(|error| error.exit());
+
+        let mut cli = Self {
+            matches,
+            signers: HashMap::new(),
+            config: CliConfig::default(),
+        };
+
+        let config_file = cli.get_matches().1.value_of(CONFIG_FILE);
+        cli.config = CliConfig::load(config_file).unwrap_or_else(|error| {
+            clap::Error::with_description(&error.to_string(), ErrorKind::InvalidValue).exit()
+        });
+
+        cli
     }
 
     pub fn init_from_save(arguments: &[&str]) -> Result<Self> {
+        Self::init_with_signers(arguments, HashMap::new())
+    }
+
+    /// The signers replace keypair arguments with the same names, so a service
+    /// doesn't have to store its keypairs in files. Services don't read the
+    /// config file of the operator
+    pub fn init_with_signers(
+        arguments: &[&str],
+        signers: HashMap<String, Rc<dyn Signer>>,
+    ) -> Result<Self> {
         let app = Self::build_app();
         let matches = app.get_matches_from_safe(arguments)?;
-        Ok(Self { matches, })
+        Self::check_command(&matches)?;
+
+        Ok(Self {
+            matches,
+            signers,
+            config: CliConfig::default(),
+        })
+    }
+
+    // A subcommand is required unless the man page is generated
+    fn check_command(matches: &ArgMatches) -> clap::Result<()> {
+        if matches.subcommand_name().is_some() || matches.is_present(GENERATE_MAN) {
+            return Ok(());
+        }
+
+        let mut help = Vec::new();
+        Self::build_app().write_help(&mut help)?;
+
+        Err(clap::Error {
+            message: String::from_utf8_lossy(&help).into_owned(),
+            kind: ErrorKind::MissingArgumentOrSubcommand,
+            info: None,
+        })
     }
 
     fn build_app<'b, 'c>() -> App<'b, 'c> {
@@ -143,7 +455,10 @@ impl<'a> Cli<'a> {
             "   * a path to a keypair file\n",
             "   * a hyphen; signals a JSON-encoded keypair on stdin\n",
             "   * the 'ASK' keyword; to recover a keypair via its seed phrase\n",
+            "   * a seed phrase prompt URL with a derivation path (i.e. prompt://?key=0/0)\n",
             "   * a hardware wallet keypair URL (i.e. usb://ledger)\n\n",
+            "The '--derivation' argument applies to every seed phrase and hardware wallet\n",
+            "keypair above which does not specify its own derivation path\n\n",
             "<MINT_ADDRESS> is one of:\n",
             "   * a base58-encoded public key\n",
             "   * a path to a pubkey file"
@@ -157,6 +472,15 @@ impl<'a> Cli<'a> {
             .validator(is_valid_signer)
             .help("Primary wallet keypair");
 
+        let admin_signer = Arg::with_name(ADMIN_SIGNER)
+            .long(ADMIN_SIGNER)
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1)
+            .value_name(account_address)
+            .validator(is_valid_signer)
+            .help("Signer of the admin config, defaults to the primary wallet");
+
         let mut payer = Arg::with_name(PAYER)
             .long(PAYER)
             .short("P")
@@ -245,10 +569,125 @@ impl<'a> Cli<'a> {
             .default_value("devnet")
             .help("URL for Solana's JSON RPC or moniker (or their first letter)");
 
+        let config_file = Arg::with_name(CONFIG_FILE)
+            .long(CONFIG_FILE)
+            .short("C")
+            .global(true)
+            .takes_value(true)
+            .value_name("PATH")
+            .help("Config file with defaults of the arguments [default: ~/.config/chill/config.toml]");
+
+        let rpc_budget = Arg::with_name(RPC_BUDGET)
+            .long(RPC_BUDGET)
+            .global(true)
+            .takes_value(true)
+            .value_name("CALLS")
+            .validator(is_parsable::<u64>)
+            .help("Warns when the command makes more RPC calls than the given number");
+
+        let rpc_budget_abort = Arg::with_name(RPC_BUDGET_ABORT)
+            .long(RPC_BUDGET_ABORT)
+            .global(true)
+            .requires(RPC_BUDGET)
+            .help("Aborts the command instead of warning when the RPC budget is exceeded");
+
+        let priority_fee = Arg::with_name(PRIORITY_FEE)
+            .long(PRIORITY_FEE)
+            .global(true)
+            .takes_value(true)
+            .value_name("MICROLAMPORTS")
+            .validator(is_parsable::<u64>)
+            .help("Price of a compute unit paid by transactions to get into blocks under congestion");
+
+        let compute_units = Arg::with_name(COMPUTE_UNITS)
+            .long(COMPUTE_UNITS)
+            .global(true)
+            .takes_value(true)
+            .value_name("UNITS")
+            .validator(is_parsable::<u32>)
+            .help("Limit of compute units of transactions, the priority fee is paid for each of them");
+
+        let send_retries = Arg::with_name(SEND_RETRIES)
+            .long(SEND_RETRIES)
+            .global(true)
+            .takes_value(true)
+            .value_name("N")
+            .validator(is_parsable::<u32>)
+            .default_value("3")
+            .help("Sends a transaction again with a fresh blockhash if the blockhash expired or the node is behind");
+
+        let retry_backoff = Arg::with_name(RETRY_BACKOFF)
+            .long(RETRY_BACKOFF)
+            .global(true)
+            .takes_value(true)
+            .value_name("MILLISECONDS")
+            .validator(is_parsable::<u64>)
+            .default_value("500")
+            .help("Delay before the first retry of a transaction, it doubles after every attempt");
+
+        let simulate = Arg::with_name(SIMULATE)
+            .long(SIMULATE)
+            .global(true)
+            .help("Simulates transactions instead of sending them and prints their compute units and logs");
+
+        let sign_only = sign_only_arg()
+            .global(true)
+            .conflicts_with(SIMULATE)
+            .help("Signs transactions with the available signers and prints them instead of sending");
+
+        let blockhash = blockhash_arg()
+            .global(true)
+            .help("Blockhash of the transactions, so signers on other machines sign the same ones");
+
+        let nonce = Arg::with_name(NONCE_ARG.name)
+            .long(NONCE_ARG.long)
+            .global(true)
+            .takes_value(true)
+            .value_name("PUBKEY")
+            .validator(is_pubkey_or_keypair)
+            .help("Nonce account used instead of a recent blockhash, so transactions signed offline don't expire");
+
+        let nonce_authority = nonce_authority_arg()
+            .global(true)
+            .requires(NONCE_ARG.name)
+            .help("Authority of the nonce account [default: payer]");
+
+        let presigner = Arg::with_name(SIGNER_ARG.name)
+            .long(SIGNER_ARG.long)
+            .global(true)
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1)
+            .value_name("PUBKEY=SIGNATURE")
+            .validator(is_pubkey_sig)
+            .help("Signature of the signer made on another machine");
+
+        let output = Arg::with_name(OUTPUT)
+            .long(OUTPUT)
+            .global(true)
+            .takes_value(true)
+            .value_name("FORMAT")
+            .possible_values(&["display", "json"])
+            .default_value("display")
+            .help("Prints results as colored text or as a single JSON object");
+
+        let derivation = Arg::with_name(DERIVATION)
+            .long(DERIVATION)
+            .global(true)
+            .takes_value(true)
+            .value_name("PATH")
+            .validator(is_derivation_path)
+            .help("BIP44 derivation path for seed phrase and hardware wallet keypairs (i.e. m/44'/501'/0'/1')");
+
+        let skip_seed_phrase_validation = Arg::with_name(SKIP_SEED_PHRASE_VALIDATION_ARG.name)
+            .long(SKIP_SEED_PHRASE_VALIDATION_ARG.long)
+            .global(true)
+            .help(SKIP_SEED_PHRASE_VALIDATION_ARG.help);
+
         let mint_command = SubCommand::with_name(COMMAND_MINT)
             .args(&[
                 amount_mint,
-                decimals,
+                decimals.clone(),
                 mint.clone(),
                 recipient.clone(),
                 primary_wallet.clone(),
@@ -266,7 +705,7 @@ impl<'a> Cli<'a> {
             .after_help(account_address_help);
 
         let info_command = SubCommand::with_name(COMMAND_INFO)
-            .args(&[mint.clone()])
+            .arg(mint.clone())
             .about("Prints the information about smart-contract state");
 
         let transfer_command = SubCommand::with_name(COMMAND_TRANSFER)
@@ -372,21 +811,46 @@ impl<'a> Cli<'a> {
             .args(&[
                 mint.clone(),
                 primary_wallet.clone(),
+                admin_signer.clone(),
                 payer.clone(),
-                multiple_recipients,
-                mint_share,
-                transaction_share,
-                fees_character,
-                fees_pet,
-                fees_emote,
-                fees_tileset,
-                fees_item,
-                fees_world,
+                multiple_recipients.clone(),
+                mint_share.clone(),
+                transaction_share.clone(),
+                fees_character.clone(),
+                fees_pet.clone(),
+                fees_emote.clone(),
+                fees_tileset.clone(),
+                fees_item.clone(),
+                fees_world.clone(),
                 nft_program_id.clone(),
             ])
             .about("Initializes the Chill smart-contract")
             .after_help(account_address_help);
 
+        let list_configs_command = SubCommand::with_name(COMMAND_LIST_CONFIGS)
+            .arg(nft_program_id.clone())
+            .about("Prints all configs created by the NFT program");
+
+        let list_nfts_command = SubCommand::with_name(COMMAND_LIST_NFTS)
+            .args(&[
+                Arg::with_name(OWNER)
+                    .long(OWNER)
+                    .takes_value(true)
+                    .value_name("PUBKEY")
+                    .validator(is_pubkey)
+                    .help("Lists only NFTs held by the account"),
+                Arg::with_name(NFT_TYPE)
+                    .long(NFT_TYPE)
+                    .takes_value(true)
+                    .value_name("TYPE")
+                    .help("Lists only NFTs of the built-in or registered type"),
+                mint.clone(),
+                primary_wallet.clone(),
+                nft_program_id.clone(),
+            ])
+            .about("Prints NFTs minted by the primary wallet with their types and owners")
+            .after_help(account_address_help);
+
         //
         // MintNft
         //
@@ -394,9 +858,11 @@ impl<'a> Cli<'a> {
         let nft_type = Arg::with_name(NFT_TYPE)
             .takes_value(true)
             .value_name("TYPE")
-            .possible_values(&["character", "pet", "emote", "tileset", "item", "world"])
             .required(true)
-            .help("Nft type");
+            .help(concat!(
+                "Nft type: character, pet, emote, tileset, item, world ",
+                "or a type registered in the config"
+            ));
 
         let name = Arg::with_name(NAME)
             .takes_value(true)
@@ -433,31 +899,129 @@ impl<'a> Cli<'a> {
             .value_name("PERCENT")
             .default_value("2");
 
+        let min_game_version = Arg::with_name(MIN_GAME_VERSION)
+            .long(MIN_GAME_VERSION)
+            .short("g")
+            .takes_value(true)
+            .value_name("VERSION")
+            .validator(is_parsable::<u32>)
+            .help("Minimum game version which is able to render the NFT");
+
+        let collection = Arg::with_name(COLLECTION)
+            .long(COLLECTION)
+            .takes_value(true)
+            .value_name("MINT_ADDRESS")
+            .validator(is_mint_pubkey)
+            .help("Mint of the collection NFT to mint the NFT into");
+
+        let uses = Arg::with_name(USES)
+            .long(USES)
+            .takes_value(true)
+            .value_name("NUMBER")
+            .validator(is_parsable::<u64>)
+            .help("Makes the NFT consumable with the given number of uses");
+
+        let use_method = Arg::with_name(USE_METHOD)
+            .long(USE_METHOD)
+            .takes_value(true)
+            .value_name("METHOD")
+            .possible_values(&["burn", "multiple", "single"])
+            .default_value("multiple")
+            .help("How the NFT is consumed when it runs out of uses");
+
         let mint_nft_command = SubCommand::with_name(COMMAND_MINT_NFT)
             .args(&[
+                collection.clone(),
+                uses,
+                use_method,
                 fees.clone(),
                 mint.clone(),
                 nft_type,
                 name.clone(),
-                creator,
+                creator.clone(),
+                min_game_version.clone().default_value("0"),
                 payer.clone(),
                 recipient.clone(),
                 primary_wallet.clone(),
                 symbol.clone(),
                 uri.clone(),
                 nft_program_id.clone(),
+                Arg::with_name(VALIDATE_URI)
+                    .long(VALIDATE_URI)
+                    .help("Checks the metadata JSON of the URI before minting"),
             ])
             .about("Creates a new NFT")
             .after_help(account_address_help);
 
+        let mint_nft_batch_command = SubCommand::with_name(COMMAND_MINT_NFT_BATCH)
+            .args(&[
+                Arg::with_name(MANIFEST)
+                    .long(MANIFEST)
+                    .required(true)
+                    .takes_value(true)
+                    .value_name("PATH")
+                    .help("JSON array of NFTs or CSV lines 'type,name,symbol,uri,recipient'"),
+                Arg::with_name(RETRIES)
+                    .long(RETRIES)
+                    .takes_value(true)
+                    .value_name("NUMBER")
+                    .validator(is_parsable::<u32>)
+                    .default_value("3")
+                    .help("Number of times a failed transaction is sent again"),
+                collection.help("Mint of the collection NFT to mint the NFTs into"),
+                fees.clone(),
+                mint.clone(),
+                min_game_version.clone().default_value("0"),
+                payer.clone(),
+                primary_wallet.clone(),
+                nft_program_id.clone(),
+            ])
+            .about("Creates NFTs listed in the manifest and writes the results manifest")
+            .after_help(concat!(
+                "NFTs of the JSON manifest are objects with the fields 'type', 'name', 'symbol', ",
+                "'uri' and 'recipient'. The results manifest '<MANIFEST>.results.json' adds the ",
+                "'mint', 'signature' and 'error' of every NFT, pass it again to retry failed NFTs"
+            ));
+
+        //
+        // CreateCollection
+        //
+
+        let create_collection_command = SubCommand::with_name(COMMAND_CREATE_COLLECTION)
+            .args(&[
+                fees.clone(),
+                mint.clone(),
+                name.clone(),
+                payer.clone(),
+                primary_wallet.clone(),
+                symbol.clone(),
+                uri.clone(),
+                nft_program_id.clone(),
+            ])
+            .about("Creates a collection NFT owned by the primary wallet")
+            .after_help(account_address_help);
+
         //
         // UpdateNft
         //
 
         let update_nft_command = SubCommand::with_name(COMMAND_UPDATE_NFT)
             .args(&[
+                creator.help("An account replacing other creators besides the primary wallet"),
+                Arg::with_name(CREATOR_SHARE)
+                    .long(CREATOR_SHARE)
+                    .takes_value(true)
+                    .value_name("PERCENT")
+                    .requires(CREATOR)
+                    .validator(is_valid_percentage)
+                    .default_value("98")
+                    .help("Royalty share of the creator, the primary wallet gets the rest"),
+                Arg::with_name(PRIMARY_SALE_HAPPENED)
+                    .long(PRIMARY_SALE_HAPPENED)
+                    .help("Marks the primary sale of the NFT, it cannot be unmarked"),
                 fees.clone(),
                 required_mint.clone(),
+                min_game_version,
                 name,
                 payer.clone(),
                 primary_wallet.clone(),
@@ -468,6 +1032,11 @@ impl<'a> Cli<'a> {
             .about("Updates an NFT metadata")
             .after_help(account_address_help);
 
+        let reclaim_metadata_command = SubCommand::with_name(COMMAND_RECLAIM_METADATA)
+            .args(&[required_mint.clone(), payer.clone(), nft_program_id.clone()])
+            .about("Closes the chill metadata of a burned NFT and returns its rent")
+            .after_help(account_address_help);
+
         //
         // Proxy wallets
         //
@@ -481,9 +1050,29 @@ impl<'a> Cli<'a> {
                 payer.clone(),
                 wallets_program_id.clone(),
             ])
-            .about("Creates a proxy wallet")
+            .about("Creates a proxy wallet. The payer must be either the account or the primary wallet")
             .after_help(account_address_help);
 
+        let users_file = Arg::with_name(USERS_FILE)
+            .required(true)
+            .takes_value(true)
+            .value_name("PATH")
+            .help("The path to the file with user pubkeys, one per line");
+
+        let create_wallets_command = SubCommand::with_name(COMMAND_CREATE_WALLETS)
+            .args(&[
+                users_file,
+                primary_wallet.clone(),
+                admin_signer.clone(),
+                payer.clone(),
+                wallets_program_id.clone(),
+            ])
+            .about("Creates proxy wallets in batches, skipping existing ones")
+            .after_help(
+                "Users of failed batches are written to the file <USERS_FILE>.failed, \
+                 run the command with this file to retry",
+            );
+
         let withdraw_lamports_command = SubCommand::with_name(COMMAND_WITHDRAW_LAMPORTS)
             .args(&[
                 account.clone(),
@@ -524,6 +1113,99 @@ impl<'a> Cli<'a> {
             .about("Withdraws NFT from proxy wallet")
             .after_help(account_address_help);
 
+        let interval = Arg::with_name(INTERVAL)
+            .long(INTERVAL)
+            .short("i")
+            .required(true)
+            .takes_value(true)
+            .value_name("SECONDS")
+            .validator(is_parsable::<u64>)
+            .help("Time between withdrawals");
+
+        let schedule_withdrawal_command = SubCommand::with_name(COMMAND_SCHEDULE_WITHDRAWAL)
+            .args(&[
+                account.clone(),
+                amount_transfer.clone(),
+                authority.clone(),
+                interval,
+                payer.clone(),
+                primary_wallet.clone(),
+                recipient.clone(),
+                wallets_program_id.clone(),
+            ])
+            .about("Schedules a recurring lamports withdrawal from proxy wallet. The authority pays for the schedule account")
+            .after_help(account_address_help);
+
+        let execute_withdrawal_command = SubCommand::with_name(COMMAND_EXECUTE_WITHDRAWAL)
+            .args(&[
+                account.clone(),
+                payer.clone(),
+                primary_wallet.clone(),
+                recipient.clone(),
+                wallets_program_id.clone(),
+            ])
+            .about("Executes a due scheduled withdrawal. Anyone can pay for the transaction")
+            .after_help(account_address_help);
+
+        let cancel_withdrawal_command = SubCommand::with_name(COMMAND_CANCEL_WITHDRAWAL)
+            .args(&[
+                account.clone(),
+                authority.clone(),
+                payer.clone(),
+                primary_wallet.clone(),
+                recipient.clone(),
+                wallets_program_id.clone(),
+            ])
+            .about("Cancels a scheduled withdrawal and returns the rent to the account which scheduled it")
+            .after_help(account_address_help);
+
+        let user = Arg::with_name(USER)
+            .required(true)
+            .takes_value(true)
+            .value_name(account_address)
+            .validator(is_pubkey_or_keypair)
+            .help("The owner of the proxy wallet");
+
+        let qr = Arg::with_name(QR)
+            .long(QR)
+            .help("Prints a Solana Pay QR code of the deposit address");
+
+        let create_token_account = Arg::with_name(CREATE_TOKEN_ACCOUNT)
+            .long(CREATE_TOKEN_ACCOUNT)
+            .help("Creates the associated token account of the proxy wallet if it doesn't exist");
+
+        let wallet_deposit_address = SubCommand::with_name(COMMAND_DEPOSIT_ADDRESS)
+            .args(&[
+                user.clone(),
+                mint.clone(),
+                primary_wallet.clone(),
+                payer.clone(),
+                qr,
+                create_token_account,
+                wallets_program_id.clone(),
+            ])
+            .about("Prints the proxy wallet address and its token account for the mint")
+            .after_help(account_address_help);
+
+        let wallet_list = SubCommand::with_name(COMMAND_LIST)
+            .args(&[
+                primary_wallet.clone(),
+                mint.clone().help("Prints token balances of the wallets for the mint"),
+                wallets_program_id.clone(),
+            ])
+            .about("Prints all proxy wallets of the primary wallet with their balances")
+            .after_help(account_address_help);
+
+        let wallet_info = SubCommand::with_name(COMMAND_INFO)
+            .args(&[user.clone(), primary_wallet.clone(), wallets_program_id.clone()])
+            .about("Prints balances, withdrawal counters and limits of the user's proxy wallet")
+            .after_help(account_address_help);
+
+        let wallet_command = SubCommand::with_name(COMMAND_WALLET)
+            .about("Manages proxy wallets")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommands(vec![wallet_deposit_address, wallet_info, wallet_list]);
+
         //
         // Staking
         //
@@ -563,66 +1245,847 @@ impl<'a> Cli<'a> {
             .default_value("0")
             .help("Minimum stake size");
 
+        let claim_fee = Arg::with_name(CLAIM_FEE)
+            .long(CLAIM_FEE)
+            .takes_value(true)
+            .value_name("BASIS_POINTS")
+            .default_value("0")
+            .validator(is_parsable::<u16>)
+            .help("Fee deducted from claimed rewards and transferred to the NFT config recipients");
+
+        let days_in_window = Arg::with_name(DAYS_IN_WINDOW)
+            .long(DAYS_IN_WINDOW)
+            .takes_value(true)
+            .value_name("DAYS")
+            .default_value("7")
+            .validator(is_parsable::<u16>)
+            .help("Number of days every stake lasts, from 1 to 30");
+
+        let referral_fee = Arg::with_name(REFERRAL_FEE)
+            .long(REFERRAL_FEE)
+            .takes_value(true)
+            .value_name("BASIS_POINTS")
+            .default_value("0")
+            .validator(is_parsable::<u16>)
+            .help("Share of rewards of referred users paid to their referrers");
+
         let staking_initialize_command = SubCommand::with_name(COMMAND_INITIALIZE)
             .args(&[
                 primary_wallet.clone(),
                 mint.clone(),
                 payer.clone(),
                 min_stake_size,
-                start_timestamp,
-                end_timestamp,
+                claim_fee,
+                days_in_window,
+                referral_fee,
+                start_timestamp.clone(),
+                end_timestamp.clone(),
                 staking_program_id.clone(),
             ])
             .about("Initializes staking")
             .after_help(account_address_help);
 
+        let transfer_remaining = Arg::with_name(TRANSFER_REMAINING)
+            .long(TRANSFER_REMAINING)
+            .help("Moves remaining reward tokens of the finished staking to the new one");
+
+        let staking_clone = SubCommand::with_name(COMMAND_CLONE)
+            .args(&[
+                primary_wallet.clone(),
+                admin_signer.clone(),
+                payer.clone(),
+                staking_info.clone().help("StakingInfo pubkey of the staking to clone"),
+                start_timestamp.clone(),
+                end_timestamp.clone(),
+                transfer_remaining,
+                staking_program_id.clone(),
+            ])
+            .about("Initializes a new staking with the mint, minimum stake size and window length of an existing one")
+            .after_help(account_address_help);
+
         let staking_add_reward_tokens = SubCommand::with_name(COMMAND_ADD_REWARD_TOKENS)
             .args(&[
-                primary_wallet,
-                mint,
-                payer,
-                amount_transfer,
-                staking_info,
+                primary_wallet.clone(),
+                admin_signer.clone(),
+                mint.clone(),
+                payer.clone(),
+                amount_transfer.clone(),
+                staking_info.clone(),
                 staking_program_id.clone(),
             ])
             .about("Adds reward tokens to staking")
             .after_help(account_address_help);
 
-        let staking_command = SubCommand::with_name(COMMAND_STAKING)
-            .about("Manages staking")
-            .setting(AppSettings::SubcommandRequiredElseHelp)
-            .subcommands(vec![staking_initialize_command, staking_add_reward_tokens]);
+        let staking_audit = SubCommand::with_name(COMMAND_AUDIT)
+            .args(&[payer.clone(), staking_info.clone(), staking_program_id.clone()])
+            .about("Checks that staking token account covers all rewards and pending withdrawals");
 
-        App::new(crate_name!())
-            .about(crate_description!())
-            .version(crate_version!())
-            .args(&[rpc, program_id])
-            .subcommands(vec![
-                staking_command,
-                balance_command,
-                info_command,
-                initialize_command,
-                mint_command,
-                mint_nft_command,
-                update_nft_command,
-                transfer_command,
-                create_wallet_command,
-                withdraw_lamports_command,
-                withdraw_ft_command,
-                withdraw_nft_command,
+        let staking_migrate = SubCommand::with_name(COMMAND_MIGRATE)
+            .args(&[payer.clone(), staking_info.clone(), staking_program_id.clone()])
+            .about("Migrates the staking info and user infos of staking to the current layout");
+
+        let staking_reconcile = SubCommand::with_name(COMMAND_RECONCILE)
+            .args(&[payer.clone(), staking_info.clone(), staking_program_id.clone()])
+            .about("Recomputes staked and rewarded totals of staking from its accounts and prints divergences");
+
+        let staking_snapshot = SubCommand::with_name(COMMAND_SNAPSHOT)
+            .args(&[
+                staking_info.clone(),
+                Arg::with_name(OUT)
+                    .long(OUT)
+                    .required(true)
+                    .takes_value(true)
+                    .value_name("FILE")
+                    .help("The path to the CSV file of 'user,staked,pending,rewarded' lines"),
+                staking_program_id.clone(),
             ])
-            .setting(AppSettings::SubcommandRequiredElseHelp)
-    }
+            .about("Writes staked, pending and rewarded tokens of all users of staking to a CSV file");
 
-    fn get_matches(&self) -> (&'static str, &ArgMatches<'a>) {
-        match self.matches.subcommand() {
-            (COMMAND_BALANCE, Some(matcher)) => (COMMAND_BALANCE, matcher),
-            (COMMAND_CREATE_WALLET, Some(matcher)) => (COMMAND_CREATE_WALLET, matcher),
-            (COMMAND_INFO, Some(matcher)) => (COMMAND_INFO, matcher),
-            (COMMAND_INITIALIZE, Some(matcher)) => (COMMAND_INITIALIZE, matcher),
-            (COMMAND_MINT, Some(matcher)) => (COMMAND_MINT, matcher),
-            (COMMAND_MINT_NFT, Some(matcher)) => (COMMAND_MINT_NFT, matcher),
-            (COMMAND_UPDATE_NFT, Some(matcher)) => (COMMAND_UPDATE_NFT, matcher),
+        let staking_redistribute = SubCommand::with_name(COMMAND_REDISTRIBUTE)
+            .args(&[
+                primary_wallet.clone(),
+                payer.clone(),
+                staking_info.clone(),
+                staking_program_id.clone(),
+            ])
+            .about("Shares unspent reward tokens of the finished staking between its users");
+
+        let staking_redeem_remaining = SubCommand::with_name(COMMAND_REDEEM_REMAINING)
+            .args(&[
+                primary_wallet.clone(),
+                admin_signer.clone(),
+                payer.clone(),
+                staking_info.clone(),
+                staking_program_id.clone(),
+            ])
+            .about("Returns reward tokens which are not spent by the finished staking to the primary wallet")
+            .after_help(account_address_help);
+
+        let mut staking_user = Arg::with_name(USER)
+            .long(USER)
+            .takes_value(true)
+            .value_name(account_address)
+            .validator(is_valid_signer)
+            .help("The staking user");
+
+        staking_user = match *DEFAULT_KEYPAIR {
+            Some(ref file) => staking_user.default_value(file),
+            None => staking_user.required(true),
+        };
+
+        let referrer = Arg::with_name(REFERRER)
+            .long(REFERRER)
+            .takes_value(true)
+            .value_name("PUBKEY")
+            .validator(is_pubkey)
+            .help("User who referred the staking user, takes a share of the rewards");
+
+        let stake_beneficiary = Arg::with_name(BENEFICIARY)
+            .long(BENEFICIARY)
+            .takes_value(true)
+            .value_name("PUBKEY")
+            .validator(is_pubkey)
+            .conflicts_with(REFERRER)
+            .help("Owner of the stake, tokens are taken from the staking user");
+
+        let stake_nft = Arg::with_name(NFT)
+            .long(NFT)
+            .takes_value(true)
+            .value_name("MINT")
+            .validator(is_pubkey)
+            .conflicts_with(BENEFICIARY)
+            .help("NFT of the registered collection which gives the reward bonus");
+
+        let staking_stake = SubCommand::with_name(COMMAND_STAKE)
+            .args(&[
+                staking_info.clone(),
+                amount_transfer.clone().help("Amount of tokens to stake"),
+                staking_user.clone(),
+                referrer,
+                stake_beneficiary,
+                stake_nft,
+                payer.clone(),
+                staking_program_id.clone(),
+            ])
+            .about("Stakes tokens of the user")
+            .after_help(account_address_help);
+
+        let claim_all = Arg::with_name(ALL)
+            .long(ALL)
+            .conflicts_with(AMOUNT)
+            .help("Claims all rewarded and pending tokens instead of the amount");
+
+        let staking_claim = SubCommand::with_name(COMMAND_CLAIM)
+            .args(&[
+                staking_info.clone(),
+                amount_transfer
+                    .clone()
+                    .required_unless(ALL)
+                    .help("Amount of reward tokens to claim"),
+                claim_all,
+                staking_user.clone(),
+                payer.clone(),
+                staking_program_id.clone(),
+            ])
+            .about("Claims rewarded and pending tokens of the user")
+            .after_help(account_address_help);
+
+        let staking_boost = SubCommand::with_name(COMMAND_BOOST)
+            .args(&[
+                staking_info.clone(),
+                staking_user.clone(),
+                payer.clone(),
+                staking_program_id.clone(),
+            ])
+            .about("Boosts the current day of the active stake of the user")
+            .after_help(account_address_help);
+
+        let staking_cancel = SubCommand::with_name(COMMAND_CANCEL)
+            .args(&[
+                staking_info.clone(),
+                staking_user.clone(),
+                payer.clone(),
+                staking_program_id.clone(),
+            ])
+            .about("Cancels the active stake of the user")
+            .after_help(account_address_help);
+
+        let rent_recipient = recipient.clone().help("An account that will receive the rent");
+
+        let staking_close_user_info = SubCommand::with_name(COMMAND_CLOSE_USER_INFO)
+            .args(&[
+                staking_info.clone(),
+                staking_user.clone(),
+                rent_recipient.clone(),
+                payer.clone(),
+                staking_program_id.clone(),
+            ])
+            .about("Closes the user info without tokens to withdraw and returns its rent")
+            .after_help(account_address_help);
+
+        let staking_close_user_infos = SubCommand::with_name(COMMAND_CLOSE_USER_INFOS)
+            .args(&[
+                staking_info.clone(),
+                primary_wallet.clone(),
+                rent_recipient.clone(),
+                payer.clone(),
+                staking_program_id.clone(),
+            ])
+            .about("Closes user infos of the finished staking without tokens to withdraw")
+            .after_help(account_address_help);
+
+        let staking_close_staking_info = SubCommand::with_name(COMMAND_CLOSE_STAKING_INFO)
+            .args(&[
+                staking_info.clone(),
+                primary_wallet.clone(),
+                rent_recipient,
+                payer.clone(),
+                staking_program_id.clone(),
+            ])
+            .about("Closes the finished staking and returns its rent")
+            .after_help(account_address_help);
+
+        let staking_user_info = SubCommand::with_name(COMMAND_INFO)
+            .args(&[
+                staking_info.clone(),
+                user.clone().help("The staking user"),
+                staking_program_id.clone(),
+            ])
+            .about("Prints the stake and the rewards of the user")
+            .after_help(account_address_help);
+
+        let staking_command = SubCommand::with_name(COMMAND_STAKING)
+            .about("Manages staking")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommands(vec![
+                staking_initialize_command,
+                staking_add_reward_tokens,
+                staking_audit,
+                staking_clone,
+                staking_migrate,
+                staking_reconcile,
+                staking_redistribute,
+                staking_snapshot,
+                staking_redeem_remaining,
+                staking_stake,
+                staking_claim,
+                staking_boost,
+                staking_cancel,
+                staking_close_user_info,
+                staking_close_user_infos,
+                staking_close_staking_info,
+                staking_user_info,
+            ]);
+
+        //
+        // Drop
+        //
+
+        let drop_program_id = program_id.clone().help("Drop program id");
+
+        let tree_file = Arg::with_name(TREE_FILE)
+            .required(true)
+            .takes_value(true)
+            .value_name("PATH")
+            .help("The path to the JSON file with the merkle tree of the drop");
+
+        let drop_generate_tree = SubCommand::with_name(COMMAND_GENERATE_TREE)
+            .args(&[
+                Arg::with_name(CLAIMS_FILE)
+                    .required(true)
+                    .takes_value(true)
+                    .value_name("PATH")
+                    .help("The path to the CSV file with 'address,amount' lines"),
+                tree_file.clone(),
+                decimals
+                    .clone()
+                    .validator(is_parsable::<u8>)
+                    .help("Decimals of the mint, use 0 for whitelist slots"),
+            ])
+            .about("Builds the merkle tree of a drop, the tree is saved to <TREE_FILE>");
+
+        let drop_initialize = SubCommand::with_name(COMMAND_INITIALIZE)
+            .args(&[
+                tree_file.clone(),
+                primary_wallet.clone(),
+                mint.clone(),
+                payer.clone(),
+                Arg::with_name(WHITELIST)
+                    .long(WHITELIST)
+                    .help("Drops whitelist slots of the mint instead of its tokens"),
+                drop_program_id.clone(),
+            ])
+            .about("Creates a drop of the tree owned by the primary wallet")
+            .after_help(account_address_help);
+
+        let mut claimant = Arg::with_name(CLAIMANT)
+            .long(CLAIMANT)
+            .takes_value(true)
+            .value_name(account_address)
+            .validator(is_valid_signer)
+            .help("Account which claims its share of the drop");
+
+        claimant = match *DEFAULT_KEYPAIR {
+            Some(ref file) => claimant.default_value(file),
+            None => claimant.required(true),
+        };
+
+        let drop_claim = SubCommand::with_name(COMMAND_CLAIM)
+            .args(&[
+                tree_file,
+                Arg::with_name(DISTRIBUTOR)
+                    .required(true)
+                    .takes_value(true)
+                    .value_name("PUBKEY")
+                    .validator(is_pubkey)
+                    .help("Distributor pubkey"),
+                claimant,
+                payer.clone(),
+                drop_program_id,
+            ])
+            .about("Claims tokens or whitelist slots of the claimant from the drop")
+            .after_help(account_address_help);
+
+        let drop_command = SubCommand::with_name(COMMAND_DROP)
+            .about("Manages merkle drops")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommands(vec![drop_generate_tree, drop_initialize, drop_claim]);
+
+        //
+        // Vesting
+        //
+
+        let vesting_program_id = program_id.clone().help("Vesting program id");
+
+        let vesting = Arg::with_name(VESTING)
+            .required(true)
+            .takes_value(true)
+            .value_name("PUBKEY")
+            .validator(is_pubkey)
+            .help("Vesting pubkey");
+
+        let vesting_initialize = SubCommand::with_name(COMMAND_INITIALIZE)
+            .args(&[
+                Arg::with_name(BENEFICIARY)
+                    .required(true)
+                    .takes_value(true)
+                    .value_name(account_address)
+                    .validator(is_pubkey_or_keypair)
+                    .help("Account which receives unlocked tokens"),
+                amount_transfer.clone().help("Amount of tokens to vest"),
+                primary_wallet.clone(),
+                mint.clone(),
+                payer.clone(),
+                start_timestamp.help("Vesting start time"),
+                Arg::with_name(CLIFF_TIMESTAMP)
+                    .long(CLIFF_TIMESTAMP)
+                    .takes_value(true)
+                    .value_name("TIMESTAMP")
+                    .validator(is_rfc3339_datetime)
+                    .help("Nothing is unlocked before this time, defaults to the start time"),
+                end_timestamp.help("Time when all tokens are unlocked"),
+                Arg::with_name(PERIOD)
+                    .long(PERIOD)
+                    .takes_value(true)
+                    .value_name("SECONDS")
+                    .default_value("0")
+                    .validator(is_parsable::<u64>)
+                    .help("Tokens are unlocked in steps of the period, 0 unlocks them linearly"),
+                Arg::with_name(REVOCABLE)
+                    .long(REVOCABLE)
+                    .help("Allows the primary wallet to revoke unvested tokens"),
+                vesting_program_id.clone(),
+            ])
+            .about("Locks tokens of the primary wallet in a vesting of the beneficiary")
+            .after_help(account_address_help);
+
+        let mut beneficiary = Arg::with_name(BENEFICIARY)
+            .long(BENEFICIARY)
+            .takes_value(true)
+            .value_name(account_address)
+            .validator(is_valid_signer)
+            .help("Beneficiary of the vesting");
+
+        beneficiary = match *DEFAULT_KEYPAIR {
+            Some(ref file) => beneficiary.default_value(file),
+            None => beneficiary.required(true),
+        };
+
+        let vesting_claim = SubCommand::with_name(COMMAND_CLAIM)
+            .args(&[
+                vesting.clone(),
+                beneficiary,
+                payer.clone(),
+                vesting_program_id.clone(),
+            ])
+            .about("Claims unlocked tokens of the vesting")
+            .after_help(account_address_help);
+
+        let vesting_revoke = SubCommand::with_name(COMMAND_REVOKE)
+            .args(&[vesting, primary_wallet.clone(), payer.clone(), vesting_program_id])
+            .about("Returns unvested tokens of a revocable vesting to the primary wallet")
+            .after_help(account_address_help);
+
+        let vesting_command = SubCommand::with_name(COMMAND_VESTING)
+            .about("Manages vestings of team and investor allocations")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommands(vec![vesting_initialize, vesting_claim, vesting_revoke]);
+
+        //
+        // Deployment
+        //
+
+        let named_program_id = |name, help| {
+            Arg::with_name(name)
+                .long(name)
+                .takes_value(true)
+                .value_name("PUBKEY")
+                .validator(is_pubkey)
+                .help(help)
+        };
+
+        //
+        // Admin
+        //
+
+        let export = Arg::with_name(EXPORT)
+            .long(EXPORT)
+            .help("Prints the transaction message for a multisig instead of sending it");
+
+        let yes = Arg::with_name(YES)
+            .long(YES)
+            .short("y")
+            .conflicts_with(EXPORT)
+            .help("Sends the transaction without confirmation");
+
+        let admin_args = [payer.clone(), primary_wallet.clone(), admin_signer, export, yes];
+
+        let admin_nft_set_min_game_version = SubCommand::with_name(COMMAND_SET_MIN_GAME_VERSION)
+            .args(&admin_args)
+            .args(&[
+                required_mint.clone().help("NFT mint pubkey"),
+                Arg::with_name(MIN_GAME_VERSION)
+                    .required(true)
+                    .takes_value(true)
+                    .value_name("VERSION")
+                    .validator(is_parsable::<u32>)
+                    .help("Minimum game version which is able to render the NFT"),
+                nft_program_id.clone(),
+            ])
+            .about("Hides the NFT from game clients older than the given version")
+            .after_help(account_address_help);
+
+        let admin_nft_finalize = SubCommand::with_name(COMMAND_FINALIZE)
+            .args(&admin_args)
+            .args(&[
+                required_mint.clone().help("NFT mint pubkey"),
+                nft_program_id.clone(),
+            ])
+            .about("Makes the NFT metadata immutable, it cannot be updated anymore")
+            .after_help(account_address_help);
+
+        let admin_nft_command = SubCommand::with_name(COMMAND_NFT)
+            .about("Privileged operations of the NFT program")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommands(vec![admin_nft_finalize, admin_nft_set_min_game_version]);
+
+        let admin_staking_pause = SubCommand::with_name(COMMAND_PAUSE)
+            .args(&admin_args)
+            .args(&[staking_info.clone(), staking_program_id.clone()])
+            .about("Stops new stakes and boosts, users are still able to claim and cancel");
+
+        let admin_staking_resume = SubCommand::with_name(COMMAND_RESUME)
+            .args(&admin_args)
+            .args(&[staking_info, staking_program_id.clone()])
+            .about("Resumes the paused staking");
+
+        let admin_staking_command = SubCommand::with_name(COMMAND_STAKING)
+            .about("Privileged operations of the staking program")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommands(vec![admin_staking_pause, admin_staking_resume]);
+
+        let daily_limit_arg = |name, help| {
+            Arg::with_name(name)
+                .long(name)
+                .takes_value(true)
+                .value_name("AMOUNT")
+                .default_value("0")
+                .validator(is_parsable::<f64>)
+                .help(help)
+        };
+
+        let admin_wallet_freeze = SubCommand::with_name(COMMAND_FREEZE)
+            .args(&admin_args)
+            .args(&[user.clone(), wallets_program_id.clone()])
+            .about("Blocks all withdrawals from the proxy wallet of a compromised account");
+
+        let admin_wallet_unfreeze = SubCommand::with_name(COMMAND_UNFREEZE)
+            .args(&admin_args)
+            .args(&[user.clone(), wallets_program_id.clone()])
+            .about("Allows withdrawals from the frozen proxy wallet again");
+
+        let admin_wallet_set_fee = SubCommand::with_name(COMMAND_SET_FEE)
+            .args(&admin_args)
+            .args(&[
+                user.clone(),
+                Arg::with_name(FEE_BPS)
+                    .long(FEE_BPS)
+                    .required(true)
+                    .takes_value(true)
+                    .value_name("BASIS_POINTS")
+                    .validator(is_parsable::<u16>)
+                    .help("Share of every withdrawal of SOL and tokens, 0 removes the fee"),
+                Arg::with_name(FEE_RECEIVER)
+                    .long(FEE_RECEIVER)
+                    .required(true)
+                    .takes_value(true)
+                    .value_name("PUBKEY")
+                    .validator(is_pubkey)
+                    .help("Account which receives the fees"),
+                wallets_program_id.clone(),
+            ])
+            .about("Sets the fee which withdrawals from the proxy wallet of the user pay")
+            .after_help("Token fees are sent to associated token accounts of the fee receiver");
+
+        let admin_wallet_set_limits = SubCommand::with_name(COMMAND_SET_LIMITS)
+            .args(&admin_args)
+            .args(&[
+                user,
+                daily_limit_arg(
+                    DAILY_LAMPORTS,
+                    "SOL the user withdraws per day, 0 removes the limit",
+                ),
+                daily_limit_arg(
+                    DAILY_FT,
+                    "Tokens the user withdraws per day, 0 removes the limit",
+                ),
+                daily_limit_arg(
+                    PRIMARY_WALLET_DAILY_LAMPORTS,
+                    "SOL the primary wallet withdraws per day, 0 removes the limit",
+                ),
+                daily_limit_arg(
+                    PRIMARY_WALLET_DAILY_FT,
+                    "Tokens the primary wallet withdraws per day, 0 removes the limit",
+                ),
+                wallets_program_id.clone(),
+            ])
+            .about("Sets daily withdrawal limits of the user and the primary wallet")
+            .after_help("Token limits are summed over all fungible mints of the proxy wallet");
+
+        let admin_wallet_command = SubCommand::with_name(COMMAND_WALLET)
+            .about("Privileged operations of the proxy wallets program")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommands(vec![
+                admin_wallet_freeze,
+                admin_wallet_set_fee,
+                admin_wallet_set_limits,
+                admin_wallet_unfreeze,
+            ]);
+
+        let admin_config_update = SubCommand::with_name(COMMAND_UPDATE)
+            .args(&admin_args)
+            .args(&[
+                mint.clone(),
+                fees_character.required(false),
+                fees_pet.required(false),
+                fees_emote.required(false),
+                fees_tileset.required(false),
+                fees_item.required(false),
+                fees_world.required(false),
+                multiple_recipients,
+                mint_share,
+                transaction_share,
+                nft_program_id.clone(),
+            ])
+            .about("Updates mint fees and recipients of the NFT program config")
+            .after_help(concat!(
+                "Fees which are not specified keep their current values. ",
+                "Recipients are replaced only if at least one is specified"
+            ));
+
+        let admin_config_register_type = SubCommand::with_name(COMMAND_REGISTER_TYPE)
+            .args(&admin_args)
+            .args(&[
+                Arg::with_name(NAME)
+                    .required(true)
+                    .takes_value(true)
+                    .value_name("NAME")
+                    .help("Name of the NFT type, up to 32 characters"),
+                Arg::with_name(FEE)
+                    .long(FEE)
+                    .required(true)
+                    .takes_value(true)
+                    .value_name(fees_value_name)
+                    .validator(is_parsable::<f64>)
+                    .help("Fees for mint an NFT of the type"),
+                mint,
+                nft_program_id.clone(),
+            ])
+            .about("Registers a new NFT type with its own mint fee in the NFT program config");
+
+        let admin_config_command = SubCommand::with_name(COMMAND_CONFIG)
+            .about("Privileged operations of the NFT program config")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommands(vec![admin_config_register_type, admin_config_update]);
+
+        let admin_signers_set = SubCommand::with_name(COMMAND_SET)
+            .args(&admin_args)
+            .args(&[
+                Arg::with_name(SIGNERS)
+                    .required(true)
+                    .takes_value(true)
+                    .multiple(true)
+                    .value_name("PUBKEY")
+                    .validator(is_pubkey)
+                    .help("Signers of the admin config, up to 5"),
+                Arg::with_name(THRESHOLD)
+                    .long(THRESHOLD)
+                    .short("m")
+                    .required(true)
+                    .takes_value(true)
+                    .value_name("NUMBER")
+                    .validator(is_parsable::<u8>)
+                    .help("Number of signers required to approve a privileged operation"),
+                nft_program_id.clone(),
+            ])
+            .about("Creates or replaces the M-of-N signer set of the primary wallet")
+            .after_help(concat!(
+                "Replacing an existing signer set must be approved by its signers, ",
+                "pass them with --admin-signer"
+            ));
+
+        let admin_signers_command = SubCommand::with_name(COMMAND_SIGNERS)
+            .about("Manages signers who approve privileged operations of the primary wallet")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(admin_signers_set);
+
+        let admin_command = SubCommand::with_name(COMMAND_ADMIN)
+            .about("Runs privileged operations signed by the primary wallet")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommands(vec![
+                admin_config_command,
+                admin_nft_command,
+                admin_signers_command,
+                admin_staking_command,
+                admin_wallet_command,
+            ]);
+
+        let verify_deployment_command = SubCommand::with_name(COMMAND_VERIFY_DEPLOYMENT)
+            .args(&[
+                payer,
+                named_program_id(NFT_PROGRAM_ID, "NFT program id"),
+                named_program_id(WALLET_PROGRAM_ID, "Proxy wallets program id"),
+                named_program_id(STAKING_PROGRAM_ID, "Staking program id"),
+            ])
+            .about("Checks that the programs are deployed and refuses Mainnet deployments with devnet tools")
+            .after_help(account_address_help);
+
+        let cluster_arg = |name, help| {
+            Arg::with_name(name)
+                .long(name)
+                .required(true)
+                .takes_value(true)
+                .value_name("URL_OR_MONIKER")
+                .validator(is_url_or_moniker)
+                .help(help)
+        };
+
+        let mint_arg = |name, help| {
+            Arg::with_name(name)
+                .long(name)
+                .takes_value(true)
+                .value_name("MINT_ADDRESS")
+                .validator(is_mint_pubkey)
+                .help(help)
+        };
+
+        let staking_info_arg = |name, other, help| {
+            Arg::with_name(name)
+                .long(name)
+                .requires(other)
+                .takes_value(true)
+                .value_name("PUBKEY")
+                .validator(is_pubkey)
+                .help(help)
+        };
+
+        let diff_config_command = SubCommand::with_name(COMMAND_DIFF_CONFIG)
+            .args(&[
+                cluster_arg(CLUSTER_A, "Cluster with the reviewed configuration"),
+                cluster_arg(CLUSTER_B, "Cluster to compare with"),
+                mint_arg(MINT_A, "Chill mint in the cluster A"),
+                mint_arg(MINT_B, "Chill mint in the cluster B"),
+                staking_info_arg(STAKING_INFO_A, STAKING_INFO_B, "StakingInfo in the cluster A"),
+                staking_info_arg(STAKING_INFO_B, STAKING_INFO_A, "StakingInfo in the cluster B"),
+                named_program_id(NFT_PROGRAM_ID, "NFT program id"),
+                named_program_id(WALLET_PROGRAM_ID, "Proxy wallets program id"),
+                named_program_id(STAKING_PROGRAM_ID, "Staking program id"),
+            ])
+            .about("Compares configs, stakings and program builds of two clusters")
+            .after_help(concat!(
+                "By default, mints are read from the files 'mint.<cluster>.pubkey'. ",
+                "Stakings are compared if their addresses are specified for both clusters"
+            ));
+
+        let submit_command = SubCommand::with_name(COMMAND_SUBMIT)
+            .arg(
+                Arg::with_name(TRANSACTION)
+                    .required(true)
+                    .takes_value(true)
+                    .multiple(true)
+                    .value_name("TRANSACTION")
+                    .help("Base64 transaction printed by a command with '--sign-only'"),
+            )
+            .about("Merges signatures of a transaction signed on several machines and sends it")
+            .after_help(concat!(
+                "Copies of the same transaction signed by different signers are merged. ",
+                "Signatures of the rest of the signers are given with '--signer'"
+            ));
+
+        //
+        // Documentation
+        //
+
+        let completions_command = SubCommand::with_name(COMMAND_COMPLETIONS)
+            .arg(
+                Arg::with_name(SHELL)
+                    .required(true)
+                    .takes_value(true)
+                    .possible_values(&["bash", "zsh", "fish"])
+                    .help("Shell to generate completions for"),
+            )
+            .about("Prints the shell completion script");
+
+        let generate_man = Arg::with_name(GENERATE_MAN)
+            .long(GENERATE_MAN)
+            .help("Prints the man page");
+
+        App::new(crate_name!())
+            .about(crate_description!())
+            .version(crate_version!())
+            .args(&[
+                rpc,
+                config_file,
+                rpc_budget,
+                rpc_budget_abort,
+                priority_fee,
+                compute_units,
+                send_retries,
+                retry_backoff,
+                simulate,
+                sign_only,
+                blockhash,
+                nonce,
+                nonce_authority,
+                presigner,
+                output,
+                derivation,
+                skip_seed_phrase_validation,
+                program_id,
+                generate_man,
+            ])
+            .subcommands(vec![
+                admin_command,
+                staking_command,
+                wallet_command,
+                drop_command,
+                vesting_command,
+                balance_command,
+                info_command,
+                initialize_command,
+                list_configs_command,
+                list_nfts_command,
+                mint_command,
+                mint_nft_command,
+                mint_nft_batch_command,
+                create_collection_command,
+                update_nft_command,
+                reclaim_metadata_command,
+                transfer_command,
+                create_wallet_command,
+                create_wallets_command,
+                withdraw_lamports_command,
+                withdraw_ft_command,
+                withdraw_nft_command,
+                schedule_withdrawal_command,
+                execute_withdrawal_command,
+                cancel_withdrawal_command,
+                verify_deployment_command,
+                diff_config_command,
+                submit_command,
+                completions_command,
+            ])
+            .setting(AppSettings::ArgRequiredElseHelp)
+    }
+
+    fn get_matches(&self) -> (&'static str, &ArgMatches<'a>) {
+        match self.matches.subcommand() {
+            (COMMAND_BALANCE, Some(matcher)) => (COMMAND_BALANCE, matcher),
+            (COMMAND_CANCEL_WITHDRAWAL, Some(matcher)) => (COMMAND_CANCEL_WITHDRAWAL, matcher),
+            (COMMAND_COMPLETIONS, Some(matcher)) => (COMMAND_COMPLETIONS, matcher),
+            (COMMAND_CREATE_COLLECTION, Some(matcher)) => (COMMAND_CREATE_COLLECTION, matcher),
+            (COMMAND_CREATE_WALLET, Some(matcher)) => (COMMAND_CREATE_WALLET, matcher),
+            (COMMAND_CREATE_WALLETS, Some(matcher)) => (COMMAND_CREATE_WALLETS, matcher),
+            (COMMAND_DIFF_CONFIG, Some(matcher)) => (COMMAND_DIFF_CONFIG, matcher),
+            (COMMAND_DROP, Some(matcher)) => match matcher.subcommand() {
+                (COMMAND_CLAIM, Some(matcher)) => (COMMAND_DROP_CLAIM, matcher),
+                (COMMAND_GENERATE_TREE, Some(matcher)) => (COMMAND_DROP_GENERATE_TREE, matcher),
+                (COMMAND_INITIALIZE, Some(matcher)) => (COMMAND_DROP_INITIALIZE, matcher),
+                _ => unimplemented!(),
+            },
+            (COMMAND_EXECUTE_WITHDRAWAL, Some(matcher)) => (COMMAND_EXECUTE_WITHDRAWAL, matcher),
+            (COMMAND_INFO, Some(matcher)) => (COMMAND_INFO, matcher),
+            (COMMAND_INITIALIZE, Some(matcher)) => (COMMAND_INITIALIZE, matcher),
+            (COMMAND_LIST_CONFIGS, Some(matcher)) => (COMMAND_LIST_CONFIGS, matcher),
+            (COMMAND_LIST_NFTS, Some(matcher)) => (COMMAND_LIST_NFTS, matcher),
+            (COMMAND_MINT, Some(matcher)) => (COMMAND_MINT, matcher),
+            (COMMAND_MINT_NFT, Some(matcher)) => (COMMAND_MINT_NFT, matcher),
+            (COMMAND_MINT_NFT_BATCH, Some(matcher)) => (COMMAND_MINT_NFT_BATCH, matcher),
+            (COMMAND_RECLAIM_METADATA, Some(matcher)) => (COMMAND_RECLAIM_METADATA, matcher),
+            (COMMAND_SCHEDULE_WITHDRAWAL, Some(matcher)) => (COMMAND_SCHEDULE_WITHDRAWAL, matcher),
+            (COMMAND_SUBMIT, Some(matcher)) => (COMMAND_SUBMIT, matcher),
+            (COMMAND_UPDATE_NFT, Some(matcher)) => (COMMAND_UPDATE_NFT, matcher),
+            (COMMAND_VERIFY_DEPLOYMENT, Some(matcher)) => (COMMAND_VERIFY_DEPLOYMENT, matcher),
             (COMMAND_TRANSFER, Some(matcher)) => (COMMAND_TRANSFER, matcher),
             (COMMAND_WITHDRAW_FT, Some(matcher)) => (COMMAND_WITHDRAW_FT, matcher),
             (COMMAND_WITHDRAW_LAMPORTS, Some(matcher)) => (COMMAND_WITHDRAW_LAMPORTS, matcher),
@@ -632,24 +2095,146 @@ impl<'a> Cli<'a> {
                 (COMMAND_ADD_REWARD_TOKENS, Some(matcher)) => {
                     (COMMAND_STAKING_ADD_REWARD_TOKENS, matcher)
                 }
+                (COMMAND_AUDIT, Some(matcher)) => (COMMAND_STAKING_AUDIT, matcher),
+                (COMMAND_CLONE, Some(matcher)) => (COMMAND_STAKING_CLONE, matcher),
+                (COMMAND_MIGRATE, Some(matcher)) => (COMMAND_STAKING_MIGRATE, matcher),
+                (COMMAND_RECONCILE, Some(matcher)) => (COMMAND_STAKING_RECONCILE, matcher),
+                (COMMAND_REDISTRIBUTE, Some(matcher)) => (COMMAND_STAKING_REDISTRIBUTE, matcher),
+                (COMMAND_SNAPSHOT, Some(matcher)) => (COMMAND_STAKING_SNAPSHOT, matcher),
+                (COMMAND_REDEEM_REMAINING, Some(matcher)) => {
+                    (COMMAND_STAKING_REDEEM_REMAINING, matcher)
+                }
+                (COMMAND_STAKE, Some(matcher)) => (COMMAND_STAKING_STAKE, matcher),
+                (COMMAND_CLAIM, Some(matcher)) => (COMMAND_STAKING_CLAIM, matcher),
+                (COMMAND_BOOST, Some(matcher)) => (COMMAND_STAKING_BOOST, matcher),
+                (COMMAND_CANCEL, Some(matcher)) => (COMMAND_STAKING_CANCEL, matcher),
+                (COMMAND_INFO, Some(matcher)) => (COMMAND_STAKING_INFO, matcher),
+                (COMMAND_CLOSE_USER_INFO, Some(matcher)) => {
+                    (COMMAND_STAKING_CLOSE_USER_INFO, matcher)
+                }
+                (COMMAND_CLOSE_USER_INFOS, Some(matcher)) => {
+                    (COMMAND_STAKING_CLOSE_USER_INFOS, matcher)
+                }
+                (COMMAND_CLOSE_STAKING_INFO, Some(matcher)) => {
+                    (COMMAND_STAKING_CLOSE_STAKING_INFO, matcher)
+                }
+                _ => unimplemented!(),
+            },
+            (COMMAND_VESTING, Some(matcher)) => match matcher.subcommand() {
+                (COMMAND_CLAIM, Some(matcher)) => (COMMAND_VESTING_CLAIM, matcher),
+                (COMMAND_INITIALIZE, Some(matcher)) => (COMMAND_VESTING_INITIALIZE, matcher),
+                (COMMAND_REVOKE, Some(matcher)) => (COMMAND_VESTING_REVOKE, matcher),
+                _ => unimplemented!(),
+            },
+            (COMMAND_ADMIN, Some(matcher)) => match matcher.subcommand() {
+                (COMMAND_CONFIG, Some(matcher)) => match matcher.subcommand() {
+                    (COMMAND_REGISTER_TYPE, Some(matcher)) => {
+                        (COMMAND_ADMIN_CONFIG_REGISTER_TYPE, matcher)
+                    }
+                    (COMMAND_UPDATE, Some(matcher)) => (COMMAND_ADMIN_CONFIG_UPDATE, matcher),
+                    _ => unimplemented!(),
+                },
+                (COMMAND_NFT, Some(matcher)) => match matcher.subcommand() {
+                    (COMMAND_FINALIZE, Some(matcher)) => (COMMAND_ADMIN_NFT_FINALIZE, matcher),
+                    (COMMAND_SET_MIN_GAME_VERSION, Some(matcher)) => {
+                        (COMMAND_ADMIN_NFT_SET_MIN_GAME_VERSION, matcher)
+                    }
+                    _ => unimplemented!(),
+                },
+                (COMMAND_SIGNERS, Some(matcher)) => match matcher.subcommand() {
+                    (COMMAND_SET, Some(matcher)) => (COMMAND_ADMIN_SIGNERS_SET, matcher),
+                    _ => unimplemented!(),
+                },
+                (COMMAND_STAKING, Some(matcher)) => match matcher.subcommand() {
+                    (COMMAND_PAUSE, Some(matcher)) => (COMMAND_ADMIN_STAKING_PAUSE, matcher),
+                    (COMMAND_RESUME, Some(matcher)) => (COMMAND_ADMIN_STAKING_RESUME, matcher),
+                    _ => unimplemented!(),
+                },
+                (COMMAND_WALLET, Some(matcher)) => match matcher.subcommand() {
+                    (COMMAND_FREEZE, Some(matcher)) => (COMMAND_ADMIN_WALLET_FREEZE, matcher),
+                    (COMMAND_SET_FEE, Some(matcher)) => (COMMAND_ADMIN_WALLET_SET_FEE, matcher),
+                    (COMMAND_SET_LIMITS, Some(matcher)) => {
+                        (COMMAND_ADMIN_WALLET_SET_LIMITS, matcher)
+                    }
+                    (COMMAND_UNFREEZE, Some(matcher)) => (COMMAND_ADMIN_WALLET_UNFREEZE, matcher),
+                    _ => unimplemented!(),
+                },
                 _ => unimplemented!(),
             },
+            (COMMAND_WALLET, Some(matcher)) => match matcher.subcommand() {
+                (COMMAND_DEPOSIT_ADDRESS, Some(matcher)) => {
+                    (COMMAND_WALLET_DEPOSIT_ADDRESS, matcher)
+                }
+                (COMMAND_INFO, Some(matcher)) => (COMMAND_WALLET_INFO, matcher),
+                (COMMAND_LIST, Some(matcher)) => (COMMAND_WALLET_LIST, matcher),
+                _ => unimplemented!(),
+            },
+            _ if self.matches.is_present(GENERATE_MAN) => (COMMAND_GENERATE_MAN, &self.matches),
             _ => unimplemented!(),
         }
     }
 
     pub fn command(&self) -> CliCommand {
         match self.get_matches().0 {
+            COMMAND_ADMIN_CONFIG_REGISTER_TYPE => CliCommand::AdminConfigRegisterType,
+            COMMAND_ADMIN_CONFIG_UPDATE => CliCommand::AdminConfigUpdate,
+            COMMAND_ADMIN_NFT_FINALIZE => CliCommand::AdminNftFinalize,
+            COMMAND_ADMIN_NFT_SET_MIN_GAME_VERSION => CliCommand::AdminNftSetMinGameVersion,
+            COMMAND_ADMIN_SIGNERS_SET => CliCommand::AdminSignersSet,
+            COMMAND_ADMIN_STAKING_PAUSE => CliCommand::AdminStakingPause,
+            COMMAND_ADMIN_STAKING_RESUME => CliCommand::AdminStakingResume,
+            COMMAND_ADMIN_WALLET_FREEZE => CliCommand::AdminWalletFreeze,
+            COMMAND_ADMIN_WALLET_SET_FEE => CliCommand::AdminWalletSetFee,
+            COMMAND_ADMIN_WALLET_SET_LIMITS => CliCommand::AdminWalletSetLimits,
+            COMMAND_ADMIN_WALLET_UNFREEZE => CliCommand::AdminWalletUnfreeze,
             COMMAND_BALANCE => CliCommand::Balance,
+            COMMAND_CANCEL_WITHDRAWAL => CliCommand::CancelWithdrawal,
+            COMMAND_COMPLETIONS => CliCommand::Completions,
+            COMMAND_CREATE_COLLECTION => CliCommand::CreateCollection,
             COMMAND_CREATE_WALLET => CliCommand::CreateWallet,
+            COMMAND_CREATE_WALLETS => CliCommand::CreateWallets,
+            COMMAND_DIFF_CONFIG => CliCommand::DiffConfig,
+            COMMAND_DROP_CLAIM => CliCommand::DropClaim,
+            COMMAND_DROP_GENERATE_TREE => CliCommand::DropGenerateTree,
+            COMMAND_DROP_INITIALIZE => CliCommand::DropInitialize,
+            COMMAND_EXECUTE_WITHDRAWAL => CliCommand::ExecuteWithdrawal,
+            COMMAND_GENERATE_MAN => CliCommand::GenerateMan,
             COMMAND_INFO => CliCommand::Info,
             COMMAND_INITIALIZE => CliCommand::Initialize,
+            COMMAND_LIST_CONFIGS => CliCommand::ListConfigs,
+            COMMAND_LIST_NFTS => CliCommand::ListNfts,
             COMMAND_MINT => CliCommand::Mint,
             COMMAND_MINT_NFT => CliCommand::MintNft,
+            COMMAND_MINT_NFT_BATCH => CliCommand::MintNftBatch,
+            COMMAND_RECLAIM_METADATA => CliCommand::ReclaimMetadata,
             COMMAND_STAKING_ADD_REWARD_TOKENS => CliCommand::StakingAddRewardTokens,
+            COMMAND_STAKING_AUDIT => CliCommand::StakingAudit,
+            COMMAND_STAKING_BOOST => CliCommand::StakingBoost,
+            COMMAND_STAKING_CANCEL => CliCommand::StakingCancel,
+            COMMAND_STAKING_CLAIM => CliCommand::StakingClaim,
+            COMMAND_STAKING_CLONE => CliCommand::StakingClone,
+            COMMAND_STAKING_CLOSE_STAKING_INFO => CliCommand::StakingCloseStakingInfo,
+            COMMAND_STAKING_CLOSE_USER_INFO => CliCommand::StakingCloseUserInfo,
+            COMMAND_STAKING_CLOSE_USER_INFOS => CliCommand::StakingCloseUserInfos,
+            COMMAND_STAKING_INFO => CliCommand::StakingInfo,
             COMMAND_STAKING_INITIALIZE => CliCommand::StakingInitialize,
+            COMMAND_STAKING_MIGRATE => CliCommand::StakingMigrate,
+            COMMAND_STAKING_RECONCILE => CliCommand::StakingReconcile,
+            COMMAND_STAKING_REDEEM_REMAINING => CliCommand::StakingRedeemRemaining,
+            COMMAND_STAKING_REDISTRIBUTE => CliCommand::StakingRedistribute,
+            COMMAND_STAKING_SNAPSHOT => CliCommand::StakingSnapshot,
+            COMMAND_STAKING_STAKE => CliCommand::StakingStake,
+            COMMAND_SCHEDULE_WITHDRAWAL => CliCommand::ScheduleWithdrawal,
+            COMMAND_SUBMIT => CliCommand::Submit,
             COMMAND_TRANSFER => CliCommand::Transfer,
             COMMAND_UPDATE_NFT => CliCommand::UpdateNft,
+            COMMAND_VERIFY_DEPLOYMENT => CliCommand::VerifyDeployment,
+            COMMAND_VESTING_CLAIM => CliCommand::VestingClaim,
+            COMMAND_VESTING_INITIALIZE => CliCommand::VestingInitialize,
+            COMMAND_VESTING_REVOKE => CliCommand::VestingRevoke,
+            COMMAND_WALLET_DEPOSIT_ADDRESS => CliCommand::WalletDepositAddress,
+            COMMAND_WALLET_INFO => CliCommand::WalletInfo,
+            COMMAND_WALLET_LIST => CliCommand::WalletList,
             COMMAND_WITHDRAW_FT => CliCommand::WithdrawFt,
             COMMAND_WITHDRAW_LAMPORTS => CliCommand::WithdrawLamports,
             COMMAND_WITHDRAW_NFT => CliCommand::WithdrawNft,
@@ -678,6 +2263,31 @@ impl<'a> Cli<'a> {
         value_t_or_exit!(matches, AMOUNT, f64)
     }
 
+    /// Defaults to the start time
+    pub fn cliff_time(&self) -> u64 {
+        let matches = self.get_matches().1;
+        if matches.is_present(CLIFF_TIMESTAMP) {
+            self.timestamp(CLIFF_TIMESTAMP)
+        } else {
+            self.start_time()
+        }
+    }
+
+    pub fn period(&self) -> u64 {
+        let matches = self.get_matches().1;
+        value_t_or_exit!(matches, PERIOD, u64)
+    }
+
+    pub fn revocable(&self) -> bool {
+        let matches = self.get_matches().1;
+        matches.is_present(REVOCABLE)
+    }
+
+    pub fn interval(&self) -> u64 {
+        let matches = self.get_matches().1;
+        value_t_or_exit!(matches, INTERVAL, u64)
+    }
+
     pub fn decimals(&self) -> u8 {
         let matches = self.get_matches().1;
         value_t_or_exit!(matches, DECIMALS, u8)
@@ -690,20 +2300,36 @@ impl<'a> Cli<'a> {
             .unwrap_or_else(|| self.default_mint_file())
     }
 
-    pub fn nft_type(&self) -> NftType {
+    /// Name of a built-in NFT type or of a type registered in the config
+    pub fn nft_type(&self) -> &str {
         let matches = self.get_matches().1;
-        let nft_type_str = matches.value_of(NFT_TYPE).unwrap();
-        NftType::try_from(nft_type_str).unwrap()
+        matches.value_of(NFT_TYPE).unwrap()
     }
 
-    pub fn mint_args(&self) -> Result<NftArgs> {
+    pub fn nft_type_name(&self) -> &str {
+        let matches = self.get_matches().1;
+        matches.value_of(NAME).unwrap()
+    }
+
+    pub fn fee(&self) -> f64 {
+        let matches = self.get_matches().1;
+        value_t_or_exit!(matches, FEE, f64)
+    }
+
+    /// Royalties of the NFT, 10000 is 100%
+    pub fn nft_fees(&self) -> Result<u16> {
         let matches = self.get_matches().1;
         let ui_fees = value_t_or_exit!(matches, FEES, f32);
         if !(0.0..=100.0).contains(&ui_fees) {
             return Err(CliError::FeesOutOfRange.into());
         }
 
-        let fees = (ui_fees * 100.0).round() as u16;
+        Ok((ui_fees * 100.0).round() as u16)
+    }
+
+    pub fn mint_args(&self) -> Result<NftArgs> {
+        let matches = self.get_matches().1;
+        let fees = self.nft_fees()?;
         let name = matches.value_of(NAME).unwrap().to_owned();
         let symbol = matches.value_of(SYMBOL).unwrap().to_owned();
         let uri = matches.value_of(URI).unwrap().to_owned();
@@ -716,10 +2342,81 @@ impl<'a> Cli<'a> {
         })
     }
 
+    pub fn uses(&self) -> Option<Uses> {
+        let matches = self.get_matches().1;
+        if !matches.is_present(USES) {
+            return None;
+        }
+
+        let total = value_t_or_exit!(matches, USES, u64);
+        let method = matches.value_of(USE_METHOD).unwrap();
+        Some(Uses {
+            method: UseMethod::try_from(method).unwrap(),
+            remaining: total,
+            total,
+        })
+    }
+
+    pub fn validate_uri(&self) -> bool {
+        let matches = self.get_matches().1;
+        matches.is_present(VALIDATE_URI)
+    }
+
+    pub fn min_game_version(&self) -> Option<u32> {
+        let matches = self.get_matches().1;
+        if !matches.is_present(MIN_GAME_VERSION) {
+            return None;
+        }
+        Some(value_t_or_exit!(matches, MIN_GAME_VERSION, u32))
+    }
+
+    fn signer_path(&self, key: &str) -> String {
+        let matches = self.get_matches().1;
+        let signer_path = match &self.config.primary_wallet {
+            Some(primary_wallet) if key == PRIMARY_WALLET && !self.is_explicit(key) => {
+                primary_wallet.as_str()
+            }
+            _ => matches.value_of(key).unwrap(),
+        };
+
+        let derivation = match matches.value_of(DERIVATION) {
+            Some(derivation) if !signer_path.contains('?') => derivation_key(derivation),
+            _ => return signer_path.to_owned(),
+        };
+
+        let query = if derivation.is_empty() {
+            String::new()
+        } else {
+            format!("?key={}", derivation)
+        };
+
+        if signer_path == ASK_KEYWORD || signer_path.starts_with("prompt:") {
+            format!("prompt://{}", query)
+        } else if signer_path.starts_with("usb:") {
+            format!("{}{}", signer_path, query)
+        } else {
+            signer_path.to_owned()
+        }
+    }
+
     fn get_signer(&self, key: &str) -> core::result::Result<Rc<dyn Signer>, Box<dyn error::Error>> {
+        if let Some(signer) = self.signers.get(key) {
+            return Ok(signer.clone());
+        }
+
+        let matches = self.get_matches().1;
+        let signer_path = self.signer_path(key);
+        signer_from_path(matches, &signer_path, key, &mut None).map(Rc::from)
+    }
+
+    fn get_signer_pubkey(&self, key: &str) -> core::result::Result<Pubkey, Box<dyn error::Error>> {
+        if let Some(signer) = self.signers.get(key) {
+            return Ok(signer.pubkey());
+        }
+
         let matches = self.get_matches().1;
-        let signer_path = matches.value_of(key).unwrap();
-        signer_from_path(matches, signer_path, key, &mut None).map(Rc::from)
+        let signer_path = self.signer_path(key);
+        pubkey_from_path(matches, &signer_path, key, &mut None)
     }
 
     fn get_pubkey(&self, key: &str) -> Pubkey {
@@ -731,10 +2428,66 @@ impl<'a> Cli<'a> {
         self.get_pubkey(ACCOUNT)
     }
 
+    pub fn user(&self) -> Pubkey {
+        self.get_pubkey(USER)
+    }
+
+    pub fn manifest(&self) -> &str {
+        let matches = self.get_matches().1;
+        matches.value_of(MANIFEST).unwrap()
+    }
+
+    pub fn retries(&self) -> u32 {
+        let matches = self.get_matches().1;
+        value_t_or_exit!(matches, RETRIES, u32)
+    }
+
+    pub fn owner(&self) -> Option<Pubkey> {
+        let matches = self.get_matches().1;
+        pubkey_of(matches, OWNER)
+    }
+
+    pub fn nft_type_filter(&self) -> Option<&str> {
+        let matches = self.get_matches().1;
+        matches.value_of(NFT_TYPE)
+    }
+
+    pub fn users_file(&self) -> &str {
+        let matches = self.get_matches().1;
+        matches.value_of(USERS_FILE).unwrap()
+    }
+
     pub fn recipient(&self) -> Pubkey {
         self.get_pubkey(RECIPIENT)
     }
 
+    pub fn claims_file(&self) -> &str {
+        let matches = self.get_matches().1;
+        matches.value_of(CLAIMS_FILE).unwrap()
+    }
+
+    pub fn tree_file(&self) -> &str {
+        let matches = self.get_matches().1;
+        matches.value_of(TREE_FILE).unwrap()
+    }
+
+    pub fn vesting(&self) -> Pubkey {
+        self.get_pubkey(VESTING)
+    }
+
+    pub fn beneficiary_pubkey(&self) -> Pubkey {
+        self.get_pubkey(BENEFICIARY)
+    }
+
+    pub fn distributor(&self) -> Pubkey {
+        self.get_pubkey(DISTRIBUTOR)
+    }
+
+    pub fn whitelist(&self) -> bool {
+        let matches = self.get_matches().1;
+        matches.is_present(WHITELIST)
+    }
+
     pub fn creator(&self) -> Option<Pubkey> {
         let matches = self.get_matches().1;
         if !matches.is_present(CREATOR) {
@@ -743,8 +2496,21 @@ impl<'a> Cli<'a> {
         Some(pubkey_of(matches, RECIPIENT).unwrap())
     }
 
-    pub fn primary_wallet_pubkey(&self) -> Pubkey {
-        self.get_pubkey(PRIMARY_WALLET)
+    /// The creator of `update-nft` with its share of royalties
+    pub fn creator_share(&self) -> Option<(Pubkey, u8)> {
+        let matches = self.get_matches().1;
+        let creator = pubkey_of(matches, CREATOR)?;
+        Some((creator, value_t_or_exit!(matches, CREATOR_SHARE, u8)))
+    }
+
+    pub fn primary_sale_happened(&self) -> Option<bool> {
+        let matches = self.get_matches().1;
+        matches.is_present(PRIMARY_SALE_HAPPENED).then_some(true)
+    }
+
+    pub fn primary_wallet_pubkey(&self) -> Result<Pubkey> {
+        self.get_signer_pubkey(PRIMARY_WALLET)
+            .map_err(|e| CliError::CannotGetPrimaryWallet(e.to_string()).into())
     }
 
     pub fn primary_wallet(&self) -> Result<Rc<dyn Signer>> {
@@ -752,6 +2518,86 @@ impl<'a> Cli<'a> {
             .map_err(|e| CliError::CannotGetPrimaryWallet(e.to_string()).into())
     }
 
+    /// Signers of the admin config passed with `--admin-signer`
+    pub fn admin_signers(&self) -> Result<Vec<Rc<dyn Signer>>> {
+        let matches = self.get_matches().1;
+        let paths = match matches.values_of(ADMIN_SIGNER) {
+            Some(paths) => paths,
+            None => return Ok(Vec::new()),
+        };
+
+        paths
+            .map(|path| {
+                signer_from_path(matches, path, ADMIN_SIGNER, &mut None)
+                    .map(Rc::from)
+                    .map_err(|e| CliError::CannotGetAdminSigner(e.to_string()).into())
+            })
+            .collect()
+    }
+
+    pub fn signers(&self) -> Vec<Pubkey> {
+        let matches = self.get_matches().1;
+        pubkeys_of(matches, SIGNERS).unwrap()
+    }
+
+    /// UI amounts of lamports and tokens the user withdraws per day
+    pub fn user_daily_limits(&self) -> (f64, f64) {
+        let matches = self.get_matches().1;
+        (
+            value_t_or_exit!(matches, DAILY_LAMPORTS, f64),
+            value_t_or_exit!(matches, DAILY_FT, f64),
+        )
+    }
+
+    /// UI amounts of lamports and tokens the primary wallet withdraws per day
+    pub fn primary_wallet_daily_limits(&self) -> (f64, f64) {
+        let matches = self.get_matches().1;
+        (
+            value_t_or_exit!(matches, PRIMARY_WALLET_DAILY_LAMPORTS, f64),
+            value_t_or_exit!(matches, PRIMARY_WALLET_DAILY_FT, f64),
+        )
+    }
+
+    pub fn threshold(&self) -> u8 {
+        let matches = self.get_matches().1;
+        value_t_or_exit!(matches, THRESHOLD, u8)
+    }
+
+    pub fn beneficiary(&self) -> Result<Rc<dyn Signer>> {
+        self.get_signer(BENEFICIARY)
+            .map_err(|e| CliError::CannotGetBeneficiary(e.to_string()).into())
+    }
+
+    pub fn user_signer(&self) -> Result<Rc<dyn Signer>> {
+        self.get_signer(USER)
+            .map_err(|e| CliError::CannotGetUser(e.to_string()).into())
+    }
+
+    pub fn stake_nft(&self) -> Option<Pubkey> {
+        let matches = self.get_matches().1;
+        pubkey_of(matches, NFT)
+    }
+
+    pub fn stake_beneficiary(&self) -> Option<Pubkey> {
+        let matches = self.get_matches().1;
+        pubkey_of(matches, BENEFICIARY)
+    }
+
+    pub fn referrer(&self) -> Option<Pubkey> {
+        let matches = self.get_matches().1;
+        pubkey_of(matches, REFERRER)
+    }
+
+    pub fn claimant(&self) -> Result<Rc<dyn Signer>> {
+        self.get_signer(CLAIMANT)
+            .map_err(|e| CliError::CannotGetClaimant(e.to_string()).into())
+    }
+
+    pub fn payer_pubkey(&self) -> Result<Pubkey> {
+        self.get_signer_pubkey(PAYER)
+            .map_err(|e| CliError::CannotGetPayer(e.to_string()).into())
+    }
+
     pub fn payer(&self) -> Result<Rc<dyn Signer>> {
         self.get_signer(PAYER)
             .map_err(|e| CliError::CannotGetPayer(e.to_string()).into())
@@ -767,35 +2613,91 @@ impl<'a> Cli<'a> {
         value_t_or_exit!(matches, MIN_STAKE_SIZE, f64)
     }
 
+    pub fn claim_fee(&self) -> u16 {
+        let matches = self.get_matches().1;
+        value_t_or_exit!(matches, CLAIM_FEE, u16)
+    }
+
+    /// Withdrawal fee of the proxy wallet, 10000 is 100%
+    pub fn fee_bps(&self) -> u16 {
+        let matches = self.get_matches().1;
+        value_t_or_exit!(matches, FEE_BPS, u16)
+    }
+
+    pub fn fee_receiver(&self) -> Pubkey {
+        self.get_pubkey(FEE_RECEIVER)
+    }
+
+    pub fn referral_fee(&self) -> u16 {
+        let matches = self.get_matches().1;
+        value_t_or_exit!(matches, REFERRAL_FEE, u16)
+    }
+
+    pub fn days_in_window(&self) -> u16 {
+        let matches = self.get_matches().1;
+        value_t_or_exit!(matches, DAYS_IN_WINDOW, u16)
+    }
+
+    pub fn export(&self) -> bool {
+        let matches = self.get_matches().1;
+        matches.is_present(EXPORT)
+    }
+
+    pub fn yes(&self) -> bool {
+        let matches = self.get_matches().1;
+        matches.is_present(YES)
+    }
+
     pub fn staking_info(&self) -> Pubkey {
         self.get_pubkey(STAKING_INFO)
     }
 
+    pub fn claim_all(&self) -> bool {
+        let matches = self.get_matches().1;
+        matches.is_present(ALL)
+    }
+
+    pub fn transfer_remaining(&self) -> bool {
+        let matches = self.get_matches().1;
+        matches.is_present(TRANSFER_REMAINING)
+    }
+
+    pub fn qr(&self) -> bool {
+        let matches = self.get_matches().1;
+        matches.is_present(QR)
+    }
+
+    pub fn create_token_account(&self) -> bool {
+        let matches = self.get_matches().1;
+        matches.is_present(CREATE_TOKEN_ACCOUNT)
+    }
+
     fn default_mint_file(&self) -> &str {
-        match self.cluster() {
-            Cluster::Testnet => "mint.testnet.pubkey",
-            Cluster::Mainnet => "mint.mainnet.pubkey",
-            Cluster::Devnet => "mint.devnet.pubkey",
-            Cluster::Localnet => "mint.localnet.pubkey",
-            Cluster::Debug => "mint.debug.pubkey",
-            Cluster::Custom(_, _) => "mint.url.pubkey",
+        match &self.config.mint_file {
+            Some(mint_file) => mint_file,
+            None => mint_file(&self.cluster()),
         }
     }
 
     fn parse_mint(&self, mint: &str) -> Result<Option<Pubkey>> {
-        let mint = mint.trim();
-        if let Ok(pubkey) = Pubkey::from_str(mint) {
-            return Ok(Some(pubkey));
-        }
+        parse_mint(mint)
+    }
 
-        if Path::new(mint).is_file() {
-            let pubkey_from_file = fs::read_to_string(mint)?;
-            let pubkey = Pubkey::from_str(pubkey_from_file.trim())
-                .map_err(|e| CliError::CannotParseFile(mint.to_string(), e.to_string()))?;
-            return Ok(Some(pubkey));
+    pub fn collection(&self) -> Result<Option<Pubkey>> {
+        let matches = self.get_matches().1;
+        match matches.value_of(COLLECTION) {
+            Some(collection) => self.parse_mint(collection),
+            None => Ok(None),
         }
+    }
 
-        Ok(None)
+    /// Unlike `mint`, ignores the default mint file
+    pub fn explicit_mint(&self) -> Result<Option<Pubkey>> {
+        let matches = self.get_matches().1;
+        match matches.value_of(MINT) {
+            Some(mint) => self.parse_mint(mint),
+            None => Ok(None),
+        }
     }
 
     pub fn mint(&self) -> Result<Option<Pubkey>> {
@@ -805,6 +2707,36 @@ impl<'a> Cli<'a> {
         self.parse_mint(mint)
     }
 
+    pub fn diff_clusters(&self) -> (String, String) {
+        let matches = self.get_matches().1;
+        let a = matches.value_of(CLUSTER_A).unwrap();
+        let b = matches.value_of(CLUSTER_B).unwrap();
+        (normalize_to_url_if_moniker(a), normalize_to_url_if_moniker(b))
+    }
+
+    pub fn diff_mints(&self) -> Result<(Option<Pubkey>, Option<Pubkey>)> {
+        let matches = self.get_matches().1;
+        let mint = |key, cluster_key| {
+            let cluster = Cluster::from_str(matches.value_of(cluster_key).unwrap()).unwrap();
+            let mint = matches.value_of(key).unwrap_or_else(|| mint_file(&cluster));
+            self.parse_mint(mint)
+        };
+
+        Ok((mint(MINT_A, CLUSTER_A)?, mint(MINT_B, CLUSTER_B)?))
+    }
+
+    pub fn diff_staking_infos(&self) -> Option<(Pubkey, Pubkey)> {
+        let matches = self.get_matches().1;
+        if !matches.is_present(STAKING_INFO_A) {
+            return None;
+        }
+
+        Some((
+            self.get_pubkey(STAKING_INFO_A),
+            self.get_pubkey(STAKING_INFO_B),
+        ))
+    }
+
     pub fn fees(&self) -> UiFees {
         let matches = self.get_matches().1;
         UiFees {
@@ -817,6 +2749,51 @@ impl<'a> Cli<'a> {
         }
     }
 
+    /// Replaces the given fees with the specified ones, returns `None` if no
+    /// fee is specified
+    pub fn updated_fees(&self, fees: UiFees) -> Option<UiFees> {
+        let matches = self.get_matches().1;
+        let names = [
+            FEES_CHARACTER,
+            FEES_PET,
+            FEES_EMOTE,
+            FEES_TILESET,
+            FEES_ITEM,
+            FEES_WORLD,
+        ];
+
+        if !names.iter().any(|name| matches.is_present(name)) {
+            return None;
+        }
+
+        let fee = |name, current| {
+            if matches.is_present(name) {
+                value_t_or_exit!(matches, name, f64)
+            } else {
+                current
+            }
+        };
+
+        Some(UiFees {
+            character: fee(FEES_CHARACTER, fees.character),
+            pet: fee(FEES_PET, fees.pet),
+            emote: fee(FEES_EMOTE, fees.emote),
+            tileset: fee(FEES_TILESET, fees.tileset),
+            item: fee(FEES_ITEM, fees.item),
+            world: fee(FEES_WORLD, fees.world),
+        })
+    }
+
+    /// Returns `None` if no recipient is specified
+    pub fn updated_recipients(&self) -> Result<Option<Vec<Recipient>>> {
+        let matches = self.get_matches().1;
+        if !matches.is_present(RECIPIENT) {
+            return Ok(None);
+        }
+
+        self.multiple_recipients().map(Some)
+    }
+
     pub fn multiple_recipients(&self) -> Result<Vec<Recipient>> {
         let matches = self.get_matches().1;
         if !matches.is_present(RECIPIENT) {
@@ -852,36 +2829,164 @@ impl<'a> Cli<'a> {
             .collect())
     }
 
+    /// Global arguments given before the subcommand are propagated to it
+    fn is_explicit(&self, key: &str) -> bool {
+        self.get_matches().1.occurrences_of(key) > 0 || self.matches.occurrences_of(key) > 0
+    }
+
+    fn url_or_moniker(&self) -> &str {
+        match &self.config.url {
+            Some(url) if !self.is_explicit(RPC_URL) => url,
+            _ => self.get_matches().1.value_of(RPC_URL).unwrap(),
+        }
+    }
+
     pub fn cluster(&self) -> Cluster {
-        let matches = self.get_matches().1;
-        let cluster = matches.value_of(RPC_URL).unwrap();
-        Cluster::from_str(cluster).unwrap()
+        Cluster::from_str(self.url_or_moniker()).unwrap()
     }
 
-    fn program_id(&self, default: Pubkey) -> Pubkey {
+    // Commands which work with all programs take a separate argument for each
+    // program id instead of the common one
+    fn program_id(&self, key: &str, configured: Option<Pubkey>, default: Pubkey) -> Pubkey {
         let matches = self.get_matches().1;
-        if matches.is_present(PROGRAM_ID) {
+        if matches.is_present(key) {
+            self.get_pubkey(key)
+        } else if matches.is_present(PROGRAM_ID) {
             self.get_pubkey(PROGRAM_ID)
         } else {
-            default
+            configured.unwrap_or(default)
         }
     }
 
     pub fn nft_program_id(&self) -> Pubkey {
-        self.program_id(chill_nft::ID)
+        self.program_id(NFT_PROGRAM_ID, self.config.nft_program_id, chill_nft::ID)
     }
 
     pub fn wallet_program_id(&self) -> Pubkey {
-        self.program_id(chill_wallet::ID)
+        self.program_id(WALLET_PROGRAM_ID, self.config.wallet_program_id, chill_wallet::ID)
     }
 
     pub fn staking_program_id(&self) -> Pubkey {
-        self.program_id(chill_staking::ID)
+        self.program_id(STAKING_PROGRAM_ID, self.config.staking_program_id, chill_staking::ID)
+    }
+
+    pub fn drop_program_id(&self) -> Pubkey {
+        self.program_id(PROGRAM_ID, self.config.drop_program_id, chill_drop::ID)
+    }
+
+    pub fn vesting_program_id(&self) -> Pubkey {
+        self.program_id(PROGRAM_ID, self.config.vesting_program_id, chill_vesting::ID)
     }
 
     pub fn rpc_url(&self) -> String {
+        normalize_to_url_if_moniker(self.url_or_moniker())
+    }
+
+    pub fn rpc_budget(&self) -> RpcBudget {
+        let matches = self.get_matches().1;
+        let max_calls = matches
+            .value_of(RPC_BUDGET)
+            .map(|_| value_t_or_exit!(matches, RPC_BUDGET, u64));
+
+        RpcBudget {
+            max_calls,
+            abort: matches.is_present(RPC_BUDGET_ABORT),
+        }
+    }
+
+    pub fn compute_budget(&self) -> ComputeBudget {
+        let matches = self.get_matches().1;
+        ComputeBudget {
+            priority_fee: value_of(matches, PRIORITY_FEE),
+            compute_units: value_of(matches, COMPUTE_UNITS),
+        }
+    }
+
+    pub fn send_retries(&self) -> SendRetries {
+        let matches = self.get_matches().1;
+        SendRetries {
+            max_retries: value_t_or_exit!(matches, SEND_RETRIES, u32),
+            backoff: Duration::from_millis(value_t_or_exit!(matches, RETRY_BACKOFF, u64)),
+        }
+    }
+
+    pub fn transaction_mode(&self) -> TransactionMode {
         let matches = self.get_matches().1;
-        let url_or_moniker = matches.value_of(RPC_URL).unwrap();
-        normalize_to_url_if_moniker(url_or_moniker)
+        if matches.is_present(SIGN_ONLY_ARG.name) {
+            TransactionMode::SignOnly
+        } else if matches.is_present(SIMULATE) {
+            TransactionMode::Simulate
+        } else {
+            TransactionMode::Send
+        }
+    }
+
+    pub fn blockhash(&self) -> Option<Hash> {
+        let matches = self.get_matches().1;
+        value_of(matches, BLOCKHASH_ARG.name)
+    }
+
+    pub fn nonce(&self) -> Result<Option<Nonce>> {
+        let matches = self.get_matches().1;
+        let account = match pubkey_of(matches, NONCE_ARG.name) {
+            Some(account) => account,
+            None => return Ok(None),
+        };
+
+        let authority = if matches.is_present(NONCE_AUTHORITY_ARG.name) {
+            let authority = self
+                .get_signer(NONCE_AUTHORITY_ARG.name)
+                .map_err(|e| CliError::CannotGetNonceAuthority(e.to_string()))?;
+            Some(authority)
+        } else {
+            None
+        };
+
+        Ok(Some(Nonce { account, authority }))
+    }
+
+    pub fn presigners(&self) -> Vec<(Pubkey, Signature)> {
+        let matches = self.get_matches().1;
+        pubkeys_sigs_of(matches, SIGNER_ARG.name).unwrap_or_default()
+    }
+
+    pub fn transactions(&self) -> Result<Vec<Transaction>> {
+        let matches = self.get_matches().1;
+        matches
+            .values_of(TRANSACTION)
+            .unwrap()
+            .map(|transaction| {
+                let data = base64::decode(transaction)
+                    .map_err(|e| CliError::CannotParseTransaction(e.to_string()))?;
+                bincode::deserialize(&data)
+                    .map_err(|e| CliError::CannotParseTransaction(e.to_string()).into())
+            })
+            .collect()
+    }
+
+    pub fn out(&self) -> &str {
+        let matches = self.get_matches().1;
+        matches.value_of(OUT).unwrap()
+    }
+
+    pub fn output_format(&self) -> OutputFormat {
+        let matches = self.get_matches().1;
+        match matches.value_of(OUTPUT) {
+            Some("json") => OutputFormat::Json,
+            _ => OutputFormat::Display,
+        }
+    }
+
+    pub fn completions(&self) -> String {
+        let matches = self.get_matches().1;
+        let shell = value_t_or_exit!(matches, SHELL, Shell);
+
+        let mut script = Vec::new();
+        Self::build_app().gen_completions_to(crate_name!(), shell, &mut script);
+        String::from_utf8(script).unwrap()
+    }
+
+    pub fn man_page(&self) -> String {
+        man::render(&Self::build_app())
     }
 }
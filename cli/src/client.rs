@@ -3,11 +3,30 @@ use crate::{
     pda,
 };
 use anchor_client::{
-    anchor_lang::AccountDeserialize,
-    solana_client::{rpc_client::RpcClient, rpc_request::TokenAccountsFilter},
+    anchor_lang::{
+        AccountDeserialize, AnchorDeserialize, AnchorSerialize, Discriminator, InstructionData,
+        ToAccountMetas,
+    },
+    solana_client::{
+        nonce_utils,
+        rpc_client::RpcClient,
+        rpc_config::{
+            RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcSimulateTransactionConfig,
+        },
+        rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType},
+        rpc_request::{RpcRequest, TokenAccountsFilter},
+    },
     solana_sdk::{
+        account::{from_account, Account as SolanaAccount},
+        bs58,
+        bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+        clock::Clock,
         commitment_config::CommitmentConfig,
+        compute_budget,
+        hash::{hash, Hash},
         instruction::{AccountMeta, Instruction},
+        message::Message,
+        packet::PACKET_DATA_SIZE,
         program_pack::Pack,
         pubkey::Pubkey,
         rent::Rent,
@@ -20,39 +39,276 @@ use anchor_client::{
     },
     Client as AnchorClient, Cluster, Program,
 };
-use anchor_spl::associated_token;
+use chill_drop::state::{Distributor, DropKind};
 use chill_nft::{
     self,
-    state::{ChillNftMetadata, Config, Fees, NftType, Recipient, AUTHORITY_SHARE},
-    utils::NftArgs,
+    state::{
+        AdminConfig, ChillNftMetadata, Config, ConfigRegistry, Fees, NftType, NftTypeRegistry,
+        Recipient, AUTHORITY_SHARE,
+    },
+    utils::{BatchNft, NftArgs, NftCreator, Uses},
+};
+use chill_staking::{
+    state::{Redistribution, StakingInfo, UserInfo, DESCRIMINATOR_LEN},
+    InitializeArgs as StakingInitializeArgs, ReconcileReport, SolvencyReport,
 };
-use chill_staking::{state::StakingInfo, InitializeArgs as StakingInitializeArgs};
+use chill_vesting::{state::Vesting, InitializeVestingArgs};
+use chill_wallet::state::{ProxyWallet, WithdrawalSchedule};
+use colored::Colorize;
 use mpl_token_metadata::{
     state::{Creator, DataV2, Key, Metadata, TokenStandard, MAX_METADATA_LEN},
     utils::try_from_slice_checked,
 };
+use solana_account_decoder::UiAccountEncoding;
 use spl_associated_token_account::{create_associated_token_account, get_associated_token_address};
 use spl_token::{
     amount_to_ui_amount, instruction as spl_instruction,
     state::{Account, Mint},
 };
-use std::{convert::TryInto, rc::Rc, str::FromStr};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    convert::TryInto,
+    iter,
+    rc::Rc,
+    str::FromStr,
+    thread,
+    time::Duration,
+};
+
+/// Limits the number of RPC calls made by a single command
+#[derive(Clone, Copy, Default)]
+pub struct RpcBudget {
+    pub max_calls: Option<u64>,
+    pub abort: bool,
+}
+
+/// Retries of transactions failed because of an expired blockhash or a node
+/// behind the cluster. The delay doubles after every attempt
+#[derive(Clone, Copy)]
+pub struct SendRetries {
+    pub max_retries: u32,
+    pub backoff: Duration,
+}
+
+impl Default for SendRetries {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            backoff: Duration::from_millis(500),
+        }
+    }
+}
+
+/// Compute budget requested by every transaction. The priority fee is paid for
+/// each compute unit, so transactions get into blocks under congestion
+#[derive(Clone, Copy, Default)]
+pub struct ComputeBudget {
+    /// Price of a compute unit in micro-lamports
+    pub priority_fee: Option<u64>,
+    pub compute_units: Option<u32>,
+}
+
+impl ComputeBudget {
+    // Instructions of the compute budget program are encoded by hand, since
+    // the SDK of this Solana version has only the deprecated `RequestUnits`
+    const SET_COMPUTE_UNIT_LIMIT: u8 = 2;
+    const SET_COMPUTE_UNIT_PRICE: u8 = 3;
+
+    pub fn instructions(&self) -> Vec<Instruction> {
+        let mut instructions = Vec::new();
+        if let Some(units) = self.compute_units {
+            let data = iter::once(Self::SET_COMPUTE_UNIT_LIMIT)
+                .chain(units.to_le_bytes())
+                .collect::<Vec<_>>();
+            instructions.push(Instruction::new_with_bytes(compute_budget::id(), &data, vec![]));
+        }
+
+        if let Some(priority_fee) = self.priority_fee {
+            let data = iter::once(Self::SET_COMPUTE_UNIT_PRICE)
+                .chain(priority_fee.to_le_bytes())
+                .collect::<Vec<_>>();
+            instructions.push(Instruction::new_with_bytes(compute_budget::id(), &data, vec![]));
+        }
+
+        instructions
+    }
+}
+
+/// Durable nonce used instead of a recent blockhash, so transactions signed
+/// offline don't expire until the nonce is advanced
+#[derive(Clone)]
+pub struct Nonce {
+    pub account: Pubkey,
+    /// The payer of the transaction advances the nonce if it's absent
+    pub authority: Option<Rc<dyn Signer>>,
+}
+
+impl Nonce {
+    fn authority_pubkey(&self, payer: Pubkey) -> Pubkey {
+        self.authority
+            .as_ref()
+            .map_or(payer, |authority| authority.pubkey())
+    }
+}
+
+#[derive(Default)]
+struct RpcUsage {
+    calls: HashMap<RpcRequest, u64>,
+    total: u64,
+    warned: bool,
+}
+
+/// What the client does with its transactions once they are signed
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TransactionMode {
+    Send,
+    Simulate,
+    /// Transactions are partially signed by the available signers and kept to
+    /// submit them after the rest of the signers sign them elsewhere
+    SignOnly,
+}
+
+/// Result of a transaction simulated instead of being sent
+#[derive(Clone)]
+pub struct Simulation {
+    pub units_consumed: Option<u64>,
+    pub logs: Vec<String>,
+    pub error: Option<String>,
+}
 
 pub struct Client {
     url: String,
     commitment: CommitmentConfig,
     rpc_client: RpcClient,
+    rpc_budget: RpcBudget,
+    rpc_usage: RefCell<RpcUsage>,
+    transaction_mode: TransactionMode,
+    blockhash: Option<Hash>,
+    compute_budget: ComputeBudget,
+    nonce: Option<Nonce>,
+    send_retries: SendRetries,
+    simulations: RefCell<Vec<Simulation>>,
+    signed_transactions: RefCell<Vec<Transaction>>,
 }
 
 impl Client {
-    pub fn init(url: &str) -> Self {
+    pub fn init(url: &str, rpc_budget: RpcBudget) -> Self {
         let commitment = CommitmentConfig::confirmed();
 
         Self {
             url: url.to_string(),
             commitment,
             rpc_client: RpcClient::new_with_commitment(url, commitment),
+            rpc_budget,
+            rpc_usage: RefCell::default(),
+            transaction_mode: TransactionMode::Send,
+            blockhash: None,
+            compute_budget: ComputeBudget::default(),
+            nonce: None,
+            send_retries: SendRetries::default(),
+            simulations: RefCell::default(),
+            signed_transactions: RefCell::default(),
+        }
+    }
+
+    /// Results of simulated transactions are returned by `simulations`, signed
+    /// only ones by `signed_transactions`
+    pub fn with_transaction_mode(mut self, transaction_mode: TransactionMode) -> Self {
+        self.transaction_mode = transaction_mode;
+        self
+    }
+
+    /// Transactions are signed with the blockhash instead of the latest one, so
+    /// signatures made elsewhere with the same blockhash fit them
+    pub fn with_blockhash(mut self, blockhash: Option<Hash>) -> Self {
+        self.blockhash = blockhash;
+        self
+    }
+
+    /// Instructions of the compute budget are prepended to every transaction
+    pub fn with_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+
+    /// Nonced transactions advance the nonce by their first instruction
+    pub fn with_nonce(mut self, nonce: Option<Nonce>) -> Self {
+        self.nonce = nonce;
+        self
+    }
+
+    pub fn with_send_retries(mut self, send_retries: SendRetries) -> Self {
+        self.send_retries = send_retries;
+        self
+    }
+
+    fn transaction_instructions(
+        &self,
+        instructions: &[Instruction],
+        payer: Pubkey,
+    ) -> Vec<Instruction> {
+        let mut transaction_instructions = Vec::new();
+        if let Some(nonce) = &self.nonce {
+            let authority = nonce.authority_pubkey(payer);
+            transaction_instructions.push(system_instruction::advance_nonce_account(
+                &nonce.account,
+                &authority,
+            ));
+        }
+
+        transaction_instructions.extend(self.compute_budget.instructions());
+        transaction_instructions.extend_from_slice(instructions);
+        transaction_instructions
+    }
+
+    /// The given blockhash is used as is, so transactions are signed offline. Otherwise
+    /// nonced transactions take the blockhash stored in the nonce account
+    fn recent_blockhash(&self, payer: Pubkey) -> Result<Hash> {
+        if let Some(blockhash) = self.blockhash {
+            return Ok(blockhash);
+        }
+
+        let nonce = match &self.nonce {
+            Some(nonce) => nonce,
+            None => {
+                return Ok(self
+                    .rpc(RpcRequest::GetLatestBlockhash)?
+                    .get_latest_blockhash()?)
+            }
+        };
+
+        let error = |message: String| CliError::InvalidNonceAccount(nonce.account, message);
+        let rpc_client = self.rpc(RpcRequest::GetAccountInfo)?;
+        let data =
+            nonce_utils::get_account_with_commitment(rpc_client, &nonce.account, self.commitment)
+                .and_then(|account| nonce_utils::data_from_account(&account))
+                .map_err(|e| error(e.to_string()))?;
+
+        if data.authority != nonce.authority_pubkey(payer) {
+            return Err(error(format!("the authority is {}", data.authority)).into());
+        }
+
+        Ok(data.blockhash())
+    }
+
+    /// The nonce authority signs along with the signers of the instructions
+    fn sign_nonce(&self, transaction: &mut Transaction, blockhash: Hash) -> Result<()> {
+        if let Some(authority) = self.nonce.as_ref().and_then(|nonce| nonce.authority.as_ref()) {
+            transaction
+                .try_partial_sign(&[authority.as_ref()], blockhash)
+                .map_err(anyhow::Error::new)?;
         }
+
+        Ok(())
+    }
+
+    pub fn simulations(&self) -> Vec<Simulation> {
+        self.simulations.borrow().clone()
+    }
+
+    pub fn signed_transactions(&self) -> Vec<Transaction> {
+        self.signed_transactions.borrow().clone()
     }
 
     pub fn program(&self, payer: Rc<dyn Signer>, program_id: Pubkey) -> Result<Program> {
@@ -61,8 +317,52 @@ impl Client {
         Ok(anchor_client.program(program_id))
     }
 
-    pub fn rpc(&self) -> RpcClient {
-        RpcClient::new_with_commitment(&self.url, self.commitment)
+    /// Accounts a call of the RPC method and returns the client to make it. The RPC client
+    /// might send several requests for a single method, i.e. to confirm a transaction, but
+    /// they are accounted as one call
+    fn rpc(&self, request: RpcRequest) -> Result<&RpcClient> {
+        let mut usage = self.rpc_usage.borrow_mut();
+        if let Some(max_calls) = self.rpc_budget.max_calls {
+            if usage.total >= max_calls {
+                if self.rpc_budget.abort {
+                    return Err(CliError::RpcBudgetExceeded(max_calls).into());
+                }
+
+                if !usage.warned {
+                    usage.warned = true;
+                    eprintln!(
+                        "{} RPC budget of {} calls is exceeded",
+                        "warning:".yellow().bold(),
+                        max_calls
+                    );
+                }
+            }
+        }
+
+        *usage.calls.entry(request).or_default() += 1;
+        usage.total += 1;
+
+        Ok(&self.rpc_client)
+    }
+
+    /// Returns the number of calls made by every RPC method sorted by the method name
+    pub fn rpc_usage(&self) -> Vec<(String, u64)> {
+        let mut calls = self
+            .rpc_usage
+            .borrow()
+            .calls
+            .iter()
+            .map(|(request, count)| (request.to_string(), *count))
+            .collect::<Vec<_>>();
+
+        calls.sort();
+        calls
+    }
+
+    /// Checks that the command was aborted because of the RPC budget
+    pub fn rpc_budget_exhausted(&self) -> bool {
+        let total = self.rpc_usage.borrow().total;
+        self.rpc_budget.abort && self.rpc_budget.max_calls.is_some_and(|max| total >= max)
     }
 
     fn run_transaction(
@@ -71,24 +371,286 @@ impl Client {
         payer: Pubkey,
         signers: &impl Signers,
     ) -> Result<Signature> {
-        let blockhash = self.rpc_client.get_latest_blockhash()?;
-        let transaction =
-            Transaction::new_signed_with_payer(instructions, Some(&payer), signers, blockhash);
-        self.rpc_client
-            .send_and_confirm_transaction(&transaction)
+        let instructions = self.transaction_instructions(instructions, payer);
+        let sign = || -> Result<Transaction> {
+            let blockhash = self.recent_blockhash(payer)?;
+            let message = Message::new_with_blockhash(&instructions, Some(&payer), &blockhash);
+            let mut transaction = Transaction::new_unsigned(message);
+            self.sign_nonce(&mut transaction, blockhash)?;
+
+            if self.transaction_mode == TransactionMode::SignOnly {
+                transaction.try_partial_sign(signers, blockhash)
+            } else {
+                transaction.try_sign(signers, blockhash)
+            }
+            .map_err(anyhow::Error::new)?;
+
+            Ok(transaction)
+        };
+
+        let transaction = sign()?;
+        if self.transaction_mode == TransactionMode::SignOnly {
+            self.signed_transactions
+                .borrow_mut()
+                .push(transaction.clone());
+            return Ok(transaction.signatures[0]);
+        }
+
+        // The blockhash given explicitly and the durable nonce can't be refreshed
+        if self.blockhash.is_none() && self.nonce.is_none() {
+            self.send_with_retries(transaction, Some(&sign))
+        } else {
+            self.send_with_retries(transaction, None)
+        }
+    }
+
+    /// Sends the transaction again if it fails because of a retryable error. The
+    /// transaction is signed again with a fresh blockhash by `sign`, otherwise the
+    /// same transaction is sent
+    fn send_with_retries(
+        &self,
+        mut transaction: Transaction,
+        sign: Option<&dyn Fn() -> Result<Transaction>>,
+    ) -> Result<Signature> {
+        let SendRetries {
+            max_retries,
+            backoff,
+        } = self.send_retries;
+
+        let mut attempt = 0;
+        loop {
+            let error = match self.send_transaction(&transaction) {
+                Err(error) if attempt < max_retries => error,
+                result => return result,
+            };
+
+            let reason = match error.retry_reason() {
+                Some(reason) => reason,
+                None => return Err(error),
+            };
+
+            attempt += 1;
+            let delay = backoff.saturating_mul(2u32.saturating_pow(attempt - 1));
+            eprintln!(
+                "{} attempt {} of {} failed: {}, retrying in {} ms",
+                "warning:".yellow().bold(),
+                attempt,
+                max_retries + 1,
+                reason,
+                delay.as_millis()
+            );
+
+            thread::sleep(delay);
+            if let Some(sign) = sign {
+                transaction = sign()?;
+            }
+        }
+    }
+
+    fn send_transaction(&self, transaction: &Transaction) -> Result<Signature> {
+        if self.transaction_mode == TransactionMode::Simulate {
+            return self.simulate_transaction(transaction);
+        }
+
+        self.rpc(RpcRequest::SendTransaction)?
+            .send_and_confirm_transaction(transaction)
             .map_err(|e| e.into())
     }
 
+    /// Merges signatures of the copies of a partially signed transaction and
+    /// the signatures made elsewhere, then sends the transaction
+    pub fn submit_transaction(
+        &self,
+        copies: &[Transaction],
+        signatures: &[(Pubkey, Signature)],
+    ) -> Result<Signature> {
+        let (first, rest) = copies.split_first().ok_or(CliError::TransactionsMismatch)?;
+        let mut transaction = first.clone();
+        let default_signature = Signature::default();
+
+        for copy in rest {
+            if copy.message != transaction.message {
+                return Err(CliError::TransactionsMismatch.into());
+            }
+
+            for (signature, copy_signature) in transaction.signatures.iter_mut().zip(&copy.signatures) {
+                if *copy_signature != default_signature {
+                    *signature = *copy_signature;
+                }
+            }
+        }
+
+        let signers = &transaction.message.account_keys[..transaction.signatures.len()];
+        for (pubkey, signature) in signatures {
+            let index = signers
+                .iter()
+                .position(|signer| signer == pubkey)
+                .ok_or(CliError::UnexpectedSigner(*pubkey))?;
+            transaction.signatures[index] = *signature;
+        }
+
+        let absent_signers = Self::absent_signers(&transaction);
+        if !absent_signers.is_empty() {
+            return Err(CliError::MissingSignatures(absent_signers.len()).into());
+        }
+
+        transaction
+            .verify()
+            .map_err(|_| CliError::InvalidSignatures)?;
+        self.send_with_retries(transaction, None)
+    }
+
+    pub fn absent_signers(transaction: &Transaction) -> Vec<Pubkey> {
+        let default_signature = Signature::default();
+        transaction
+            .signatures
+            .iter()
+            .zip(&transaction.message.account_keys)
+            .filter(|(signature, _)| **signature == default_signature)
+            .map(|(_, pubkey)| *pubkey)
+            .collect()
+    }
+
+    /// The returned signature is the one the transaction would have if it was sent
+    fn simulate_transaction(&self, transaction: &Transaction) -> Result<Signature> {
+        let config = RpcSimulateTransactionConfig {
+            sig_verify: true,
+            commitment: Some(self.commitment),
+            ..RpcSimulateTransactionConfig::default()
+        };
+
+        let result = self
+            .rpc(RpcRequest::SimulateTransaction)?
+            .simulate_transaction_with_config(transaction, config)?
+            .value;
+
+        let error = result.err.map(|error| error.to_string());
+        self.simulations.borrow_mut().push(Simulation {
+            units_consumed: result.units_consumed,
+            logs: result.logs.unwrap_or_default(),
+            error: error.clone(),
+        });
+
+        if let Some(error) = error {
+            return Err(CliError::SimulationFailed(error).into());
+        }
+
+        Ok(transaction.signatures[0])
+    }
+
+    /// Sends instructions which require the signature of the primary wallet or
+    /// signers of its admin config. Signers which the instructions don't require
+    /// are skipped
+    pub fn send_admin_transaction(
+        &self,
+        instructions: &[Instruction],
+        payer: Rc<dyn Signer>,
+        signers: &[Rc<dyn Signer>],
+    ) -> Result<Signature> {
+        let message = Message::new(instructions, Some(&payer.pubkey()));
+        let required = &message.account_keys[..usize::from(message.header.num_required_signatures)];
+
+        let mut keys = Vec::new();
+        let mut required_signers: Vec<&dyn Signer> = Vec::new();
+        for signer in iter::once(&payer).chain(signers) {
+            let key = signer.pubkey();
+            if required.contains(&key) && !keys.contains(&key) {
+                keys.push(key);
+                required_signers.push(signer.as_ref());
+            }
+        }
+
+        self.run_transaction(instructions, payer.pubkey(), &required_signers)
+    }
+
+    /// Serializes the transaction message to sign it elsewhere, i.e. by a multisig
+    pub fn export_message(&self, instructions: &[Instruction], payer: Pubkey) -> Result<String> {
+        let blockhash = self.recent_blockhash(payer)?;
+        let instructions = self.transaction_instructions(instructions, payer);
+        let message = Message::new_with_blockhash(&instructions, Some(&payer), &blockhash);
+        Ok(base64::encode(message.serialize()))
+    }
+
+    /// Serializes the unsigned transaction to sign it elsewhere, i.e. by a browser wallet
+    pub fn export_transaction(&self, instructions: &[Instruction], payer: Pubkey) -> Result<String> {
+        let blockhash = self.recent_blockhash(payer)?;
+        let instructions = self.transaction_instructions(instructions, payer);
+        let message = Message::new_with_blockhash(&instructions, Some(&payer), &blockhash);
+        let transaction = Transaction::new_unsigned(message);
+        Ok(base64::encode(bincode::serialize(&transaction).unwrap()))
+    }
+
+    /// Serializes the transaction signed only by the given signers, the rest
+    /// of the signatures are added elsewhere
+    pub fn export_partially_signed_transaction(
+        &self,
+        instructions: &[Instruction],
+        payer: Pubkey,
+        signers: &impl Signers,
+    ) -> Result<String> {
+        let blockhash = self.recent_blockhash(payer)?;
+        let instructions = self.transaction_instructions(instructions, payer);
+        let message = Message::new_with_blockhash(&instructions, Some(&payer), &blockhash);
+        let mut transaction = Transaction::new_unsigned(message);
+        self.sign_nonce(&mut transaction, blockhash)?;
+        transaction
+            .try_partial_sign(signers, blockhash)
+            .map_err(anyhow::Error::new)?;
+        Ok(base64::encode(bincode::serialize(&transaction).unwrap()))
+    }
+
+    fn simulate_view<T: AnchorDeserialize>(
+        &self,
+        instructions: &[Instruction],
+        payer: Pubkey,
+        program_id: Pubkey,
+    ) -> Result<T> {
+        let transaction = Transaction::new_with_payer(instructions, Some(&payer));
+        let config = RpcSimulateTransactionConfig {
+            sig_verify: false,
+            replace_recent_blockhash: true,
+            commitment: Some(self.commitment),
+            ..RpcSimulateTransactionConfig::default()
+        };
+
+        let result = self
+            .rpc(RpcRequest::SimulateTransaction)?
+            .simulate_transaction_with_config(&transaction, config)?
+            .value;
+
+        if let Some(error) = result.err {
+            return Err(CliError::SimulationFailed(error.to_string()).into());
+        }
+
+        let prefix = format!("Program return: {} ", program_id);
+        let data = result
+            .logs
+            .unwrap_or_default()
+            .iter()
+            .rev()
+            .find_map(|log| log.strip_prefix(&prefix).map(base64::decode))
+            .ok_or(CliError::ReturnDataNotFound)?
+            .map_err(|_| CliError::ReturnDataNotFound)?;
+
+        T::try_from_slice(&data).map_err(|_| CliError::ReturnDataNotFound.into())
+    }
+
     pub fn airdrop(&self, address: Pubkey, lamports: u64) -> Result<()> {
-        let signature = self.rpc_client.request_airdrop(&address, lamports)?;
-        let blockhash = self.rpc_client.get_latest_blockhash()?;
-        self.rpc_client
+        let signature = self
+            .rpc(RpcRequest::RequestAirdrop)?
+            .request_airdrop(&address, lamports)?;
+        let blockhash = self
+            .rpc(RpcRequest::GetLatestBlockhash)?
+            .get_latest_blockhash()?;
+        self.rpc(RpcRequest::GetSignatureStatuses)?
             .confirm_transaction_with_spinner(&signature, &blockhash, CommitmentConfig::confirmed())
             .map_err(|e| e.into())
     }
 
     pub fn balance(&self, address: Pubkey) -> Result<u64> {
-        self.rpc_client.get_balance(&address).map_err(|e| e.into())
+        self.rpc(RpcRequest::GetBalance)?
+            .get_balance(&address)
+            .map_err(|e| e.into())
     }
 
     //
@@ -96,14 +658,28 @@ impl Client {
     //
 
     pub fn account_data(&self, address: Pubkey) -> Result<Vec<u8>> {
-        self.rpc_client
+        self.rpc(RpcRequest::GetAccountInfo)?
             .get_account_data(&address)
             .map_err(|e| e.into())
     }
 
+    pub fn slot(&self) -> Result<u64> {
+        self.rpc(RpcRequest::GetSlot)?
+            .get_slot()
+            .map_err(|e| e.into())
+    }
+
+    pub fn clock(&self) -> Result<Clock> {
+        let account = self
+            .rpc(RpcRequest::GetAccountInfo)?
+            .get_account(&Clock::id())?;
+
+        from_account(&account).ok_or_else(|| CliError::ClockDataError.into())
+    }
+
     pub fn mint_account(&self, address: Pubkey) -> Result<Mint> {
         let data = self
-            .rpc_client
+            .rpc(RpcRequest::GetAccountInfo)?
             .get_account_data(&address)
             .map_err(|_| CliError::MintNotFound(address))?;
         let mint = Mint::unpack(&data).map_err(|_| CliError::AccountIsNotMint)?;
@@ -112,7 +688,7 @@ impl Client {
 
     pub fn token_account(&self, address: Pubkey) -> Result<Account> {
         let data = self
-            .rpc_client
+            .rpc(RpcRequest::GetAccountInfo)?
             .get_account_data(&address)
             .map_err(|_| CliError::TokenNotInitialized(address))?;
 
@@ -124,7 +700,7 @@ impl Client {
     pub fn metadata_account(&self, mint: Pubkey) -> Result<Metadata> {
         let metadata_pubkey = pda::metadata(mint);
         let data = self
-            .rpc_client
+            .rpc(RpcRequest::GetAccountInfo)?
             .get_account_data(&metadata_pubkey)
             .map_err(|_| CliError::MetadataNotFound(mint))?;
 
@@ -136,7 +712,7 @@ impl Client {
         let config_pubkey = pda::config(mint, program_id);
 
         let config_data = self
-            .rpc_client
+            .rpc(RpcRequest::GetAccountInfo)?
             .get_account_data(&config_pubkey)
             .map_err(|_| CliError::ConfigNotFound)?;
 
@@ -144,85 +720,501 @@ impl Client {
             .map_err(|_| CliError::ConfigDataError.into())
     }
 
+    pub fn nft_type_registry(
+        &self,
+        mint: Pubkey,
+        program_id: Pubkey,
+    ) -> Result<Option<NftTypeRegistry>> {
+        let config_pubkey = pda::config(mint, program_id);
+        let registry_pubkey = pda::nft_type_registry(config_pubkey, program_id);
+        let account = self
+            .rpc(RpcRequest::GetAccountInfo)?
+            .get_account_with_commitment(&registry_pubkey, self.commitment)?
+            .value;
+
+        account
+            .map(|account| NftTypeRegistry::try_deserialize(&mut account.data.as_ref()))
+            .transpose()
+            .map_err(|_| CliError::NftTypeRegistryDataError.into())
+    }
+
+    pub fn admin_config(
+        &self,
+        primary_wallet: Pubkey,
+        program_id: Pubkey,
+    ) -> Result<Option<AdminConfig>> {
+        let admin_config_pubkey = pda::admin_config(primary_wallet, program_id);
+        let account = self
+            .rpc(RpcRequest::GetAccountInfo)?
+            .get_account_with_commitment(&admin_config_pubkey, self.commitment)?
+            .value;
+
+        account
+            .map(|account| AdminConfig::try_deserialize(&mut account.data.as_ref()))
+            .transpose()
+            .map_err(|_| CliError::AdminConfigNotFound(primary_wallet).into())
+    }
+
+    /// Picks as many signers of the admin config of the primary wallet as its
+    /// threshold requires
+    pub fn admin_signers(
+        &self,
+        primary_wallet: Pubkey,
+        candidates: &[Rc<dyn Signer>],
+        program_id: Pubkey,
+    ) -> Result<Vec<Rc<dyn Signer>>> {
+        let admin_config = self
+            .admin_config(primary_wallet, program_id)?
+            .ok_or(CliError::AdminConfigNotFound(primary_wallet))?;
+
+        let mut signers: Vec<Rc<dyn Signer>> = Vec::new();
+        for candidate in candidates {
+            let key = candidate.pubkey();
+            if admin_config.signers.contains(&key) && signers.iter().all(|s| s.pubkey() != key) {
+                signers.push(candidate.clone());
+            }
+        }
+
+        if signers.len() < usize::from(admin_config.threshold) {
+            return Err(CliError::NotEnoughAdminSigners(admin_config.threshold).into());
+        }
+
+        signers.truncate(admin_config.threshold.into());
+        Ok(signers)
+    }
+
+    pub fn staking_info(&self, address: Pubkey) -> Result<StakingInfo> {
+        let data = self
+            .rpc(RpcRequest::GetAccountInfo)?
+            .get_account_data(&address)
+            .map_err(|_| CliError::StakingInfoNotFound(address))?;
+
+        StakingInfo::try_deserialize_versioned(&data)
+            .map_err(|_| CliError::StakingInfoDataError.into())
+    }
+
+    pub fn staking_user_info(&self, address: Pubkey) -> Result<UserInfo> {
+        let data = self
+            .rpc(RpcRequest::GetAccountInfo)?
+            .get_account_data(&address)
+            .map_err(|_| CliError::UserInfoNotFound(address))?;
+
+        UserInfo::try_deserialize_versioned(&data)
+            .map_err(|_| CliError::UserInfoDataError.into())
+    }
+
+    pub fn config_registry(&self, program_id: Pubkey) -> Result<ConfigRegistry> {
+        let config_registry_pubkey = pda::config_registry(program_id);
+
+        let config_registry_data = self
+            .rpc(RpcRequest::GetAccountInfo)?
+            .get_account_data(&config_registry_pubkey)
+            .map_err(|_| CliError::ConfigRegistryNotFound)?;
+
+        ConfigRegistry::try_deserialize(&mut config_registry_data.as_ref())
+            .map_err(|_| CliError::ConfigRegistryDataError.into())
+    }
+
+    pub fn registered_configs(&self, program_id: Pubkey) -> Result<Vec<(Pubkey, Config)>> {
+        let config_registry = self.config_registry(program_id)?;
+        let mut configs = Vec::with_capacity(config_registry.configs.len());
+
+        // RPC nodes limit the number of accounts requested at once
+        for chunk in config_registry.configs.chunks(100) {
+            let accounts = self
+                .rpc(RpcRequest::GetMultipleAccounts)?
+                .get_multiple_accounts(chunk)?;
+            for (pubkey, account) in chunk.iter().zip(accounts) {
+                let account = account.ok_or(CliError::ConfigNotFound)?;
+                let config = Config::try_deserialize(&mut account.data.as_ref())
+                    .map_err(|_| CliError::ConfigDataError)?;
+                configs.push((*pubkey, config));
+            }
+        }
+
+        Ok(configs)
+    }
+
     pub fn chill_metadata(&self, nft_mint: Pubkey, program_id: Pubkey) -> Result<ChillNftMetadata> {
         let chill_metadata_pubkey = pda::chill_metadata(nft_mint, program_id);
         let chill_metadata_data = self
-            .rpc_client
+            .rpc(RpcRequest::GetAccountInfo)?
             .get_account_data(&chill_metadata_pubkey)
             .map_err(|_| CliError::ChillMetadataNotFound)?;
 
-        ChillNftMetadata::try_deserialize(&mut chill_metadata_data.as_ref())
+        ChillNftMetadata::try_deserialize_versioned(&chill_metadata_data)
             .map_err(|_| CliError::ChillMetadataDataError.into())
     }
 
-    //
-    // Mint & Token accounts functions
-    //
-
-    pub fn create_mint_and_token_nft(
+    /// Returns the NFTs from the list which the game of the given version is able to render
+    pub fn compatible_nfts(
         &self,
-        primary_wallet: Rc<dyn Signer>,
-        payer: Rc<dyn Signer>,
-        recipient: Pubkey,
-    ) -> Result<(Pubkey, Pubkey)> {
-        let mint = Keypair::new();
-        let token = get_associated_token_address(&recipient, &mint.pubkey());
+        nft_mints: &[Pubkey],
+        game_version: u32,
+        program_id: Pubkey,
+    ) -> Result<Vec<Pubkey>> {
+        let mut compatible_nfts = Vec::with_capacity(nft_mints.len());
+
+        // RPC nodes limit the number of accounts requested at once
+        for chunk in nft_mints.chunks(100) {
+            let chill_metadata_pubkeys = chunk
+                .iter()
+                .map(|mint| pda::chill_metadata(*mint, program_id))
+                .collect::<Vec<_>>();
+
+            let accounts = self
+                .rpc(RpcRequest::GetMultipleAccounts)?
+                .get_multiple_accounts(&chill_metadata_pubkeys)?;
+            for (mint, account) in chunk.iter().zip(accounts) {
+                let account = account.ok_or(CliError::ChillMetadataNotFound)?;
+                let chill_metadata = ChillNftMetadata::try_deserialize_versioned(&account.data)
+                    .map_err(|_| CliError::ChillMetadataDataError)?;
+
+                if chill_metadata.is_supported_by(game_version) {
+                    compatible_nfts.push(*mint);
+                }
+            }
+        }
 
-        let space = Mint::LEN;
-        let lamports = self
-            .rpc_client
-            .get_minimum_balance_for_rent_exemption(space)?;
+        Ok(compatible_nfts)
+    }
 
-        let ixs = &[
-            system_instruction::create_account(
-                &payer.pubkey(),
-                &mint.pubkey(),
-                lamports,
-                space.try_into().unwrap(),
-                &spl_token::ID,
-            ),
-            spl_instruction::initialize_mint(
-                &spl_token::ID,
-                &mint.pubkey(),
-                &primary_wallet.pubkey(),
-                None,
-                0,
-            )
-            .unwrap(),
-            create_associated_token_account(&payer.pubkey(), &recipient, &mint.pubkey()),
-            spl_instruction::mint_to(
-                &spl_token::ID,
-                &mint.pubkey(),
-                &token,
-                &primary_wallet.pubkey(),
-                &[],
-                1,
-            )
-            .unwrap(),
-        ];
+    fn program_accounts(
+        &self,
+        program_id: Pubkey,
+        filters: Vec<RpcFilterType>,
+    ) -> Result<Vec<(Pubkey, SolanaAccount)>> {
+        let config = RpcProgramAccountsConfig {
+            filters: Some(filters),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                commitment: Some(self.commitment),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        };
 
-        self.run_transaction(
-            ixs,
-            payer.pubkey(),
-            &[&mint, payer.as_ref(), primary_wallet.as_ref()],
-        )?;
+        self.rpc(RpcRequest::GetProgramAccounts)?
+            .get_program_accounts_with_config(&program_id, config)
+            .map_err(|e| e.into())
+    }
 
-        Ok((mint.pubkey(), token))
+    fn memcmp(offset: usize, bytes: &[u8]) -> RpcFilterType {
+        RpcFilterType::Memcmp(Memcmp {
+            offset,
+            bytes: MemcmpEncodedBytes::Base58(bs58::encode(bytes).into_string()),
+            encoding: None,
+        })
     }
 
-    pub fn create_mint(
+    /// Returns chill metadata accounts of all NFTs minted by the program, only
+    /// of the type if it's specified
+    pub fn chill_metadata_accounts(
         &self,
-        authority: Rc<dyn Signer>,
-        payer: Rc<dyn Signer>,
-        decimals: u8,
-    ) -> Result<Pubkey> {
-        let mint = Keypair::new();
-        let space = Mint::LEN;
-        let lamports = self
-            .rpc_client
-            .get_minimum_balance_for_rent_exemption(space)?;
+        nft_type: Option<NftType>,
+        program_id: Pubkey,
+    ) -> Result<HashMap<Pubkey, ChillNftMetadata>> {
+        let mut filters = vec![Self::memcmp(0, &ChillNftMetadata::discriminator())];
 
-        let ixs = &[
+        // The type follows the discriminator and the bump
+        if let Some(nft_type) = nft_type {
+            filters.push(Self::memcmp(DESCRIMINATOR_LEN + 1, &nft_type.try_to_vec().unwrap()));
+        }
+
+        self.program_accounts(program_id, filters)?
+            .into_iter()
+            .map(|(pubkey, account)| {
+                ChillNftMetadata::try_deserialize_versioned(&account.data)
+                    .map(|chill_metadata| (pubkey, chill_metadata))
+                    .map_err(|_| CliError::ChillMetadataDataError.into())
+            })
+            .collect()
+    }
+
+    /// Returns Metaplex metadata accounts of all NFTs with the update authority
+    pub fn metadata_accounts_by_update_authority(
+        &self,
+        update_authority: Pubkey,
+    ) -> Result<Vec<Metadata>> {
+        let filters = vec![
+            Self::memcmp(0, &[Key::MetadataV1 as u8]),
+            Self::memcmp(1, update_authority.as_ref()),
+        ];
+
+        self.program_accounts(mpl_token_metadata::ID, filters)?
+            .into_iter()
+            .map(|(_, account)| {
+                try_from_slice_checked(&account.data, Key::MetadataV1, MAX_METADATA_LEN)
+                    .map_err(|_| CliError::AccountIsNotMetadata.into())
+            })
+            .collect()
+    }
+
+    /// Returns all token accounts of the owner
+    pub fn token_accounts_by_owner(&self, owner: Pubkey) -> Result<Vec<(Pubkey, Account)>> {
+        // The owner of a token account follows its mint
+        let filters = vec![
+            RpcFilterType::DataSize(Account::LEN as u64),
+            Self::memcmp(32, owner.as_ref()),
+        ];
+
+        self.program_accounts(spl_token::ID, filters)?
+            .into_iter()
+            .map(|(pubkey, account)| {
+                Account::unpack(&account.data)
+                    .map(|token_account| (pubkey, token_account))
+                    .map_err(|_| CliError::AccountIsNotToken.into())
+            })
+            .collect()
+    }
+
+    /// Returns mints of the NFTs held by the owner
+    pub fn owned_nft_mints(&self, owner: Pubkey) -> Result<HashSet<Pubkey>> {
+        Ok(self
+            .token_accounts_by_owner(owner)?
+            .into_iter()
+            .filter(|(_, token_account)| token_account.amount == 1)
+            .map(|(_, token_account)| token_account.mint)
+            .collect())
+    }
+
+    /// Returns the owner of every NFT, `None` if the NFT is burned
+    pub fn nft_owners(&self, nft_mints: &[Pubkey]) -> Result<Vec<Option<Pubkey>>> {
+        let mut owners = Vec::with_capacity(nft_mints.len());
+        for nft_mint in nft_mints {
+            let filters = vec![
+                RpcFilterType::DataSize(Account::LEN as u64),
+                Self::memcmp(0, nft_mint.as_ref()),
+            ];
+
+            let owner = self
+                .program_accounts(spl_token::ID, filters)?
+                .into_iter()
+                .filter_map(|(_, account)| Account::unpack(&account.data).ok())
+                .find(|token_account| token_account.amount == 1)
+                .map(|token_account| token_account.owner);
+            owners.push(owner);
+        }
+
+        Ok(owners)
+    }
+
+    pub fn proxy_wallet(&self, address: Pubkey) -> Result<ProxyWallet> {
+        let data = self
+            .rpc(RpcRequest::GetAccountInfo)?
+            .get_account_data(&address)
+            .map_err(|_| CliError::ProxyWalletNotFound(address))?;
+
+        ProxyWallet::try_deserialize_versioned(&data)
+            .map_err(|_| CliError::ProxyWalletDataError.into())
+    }
+
+    /// The fee receiver of the proxy wallet if a withdrawal of the amount pays
+    /// a fee
+    pub fn withdrawal_fee_receiver(
+        &self,
+        proxy_wallet: Pubkey,
+        amount: u64,
+    ) -> Result<Option<Pubkey>> {
+        let proxy_wallet = self.proxy_wallet(proxy_wallet)?;
+        Ok((proxy_wallet.withdrawal_fee(amount) > 0).then_some(proxy_wallet.fee_receiver))
+    }
+
+    /// Returns all proxy wallets of the primary wallet with their balances in lamports
+    pub fn all_proxy_wallets(
+        &self,
+        primary_wallet: Pubkey,
+        program_id: Pubkey,
+    ) -> Result<Vec<(Pubkey, ProxyWallet, u64)>> {
+        let filters = Self::proxy_wallet_filters(primary_wallet);
+        Self::decode_proxy_wallets(self.program_accounts(program_id, filters)?)
+    }
+
+    fn proxy_wallet_filters(primary_wallet: Pubkey) -> Vec<RpcFilterType> {
+        // Wallets of older layouts are shorter, so their size isn't filtered.
+        // The primary wallet follows the discriminator and the bump
+        vec![
+            Self::memcmp(0, &ProxyWallet::discriminator()),
+            Self::memcmp(DESCRIMINATOR_LEN + 1, primary_wallet.as_ref()),
+        ]
+    }
+
+    fn decode_proxy_wallets(
+        accounts: Vec<(Pubkey, SolanaAccount)>,
+    ) -> Result<Vec<(Pubkey, ProxyWallet, u64)>> {
+        accounts
+            .into_iter()
+            .map(|(pubkey, account)| {
+                ProxyWallet::try_deserialize_versioned(&account.data)
+                    .map(|proxy_wallet| (pubkey, proxy_wallet, account.lamports))
+                    .map_err(|_| CliError::ProxyWalletDataError.into())
+            })
+            .collect()
+    }
+
+    /// Returns balances of the token accounts, zero for accounts which don't exist
+    pub fn token_balances(&self, token_accounts: &[Pubkey]) -> Result<Vec<u64>> {
+        let mut balances = Vec::with_capacity(token_accounts.len());
+
+        // RPC nodes limit the number of accounts requested at once
+        for chunk in token_accounts.chunks(100) {
+            let accounts = self
+                .rpc(RpcRequest::GetMultipleAccounts)?
+                .get_multiple_accounts(chunk)?;
+            balances.extend(accounts.into_iter().map(|account| {
+                account
+                    .and_then(|account| Account::unpack(&account.data).ok())
+                    .map_or(0, |token_account| token_account.amount)
+            }));
+        }
+
+        Ok(balances)
+    }
+
+    pub fn withdrawal_schedule(&self, address: Pubkey) -> Result<WithdrawalSchedule> {
+        let data = self
+            .rpc(RpcRequest::GetAccountInfo)?
+            .get_account_data(&address)
+            .map_err(|_| CliError::WithdrawalScheduleNotFound(address))?;
+
+        WithdrawalSchedule::try_deserialize(&mut data.as_ref())
+            .map_err(|_| CliError::WithdrawalScheduleDataError.into())
+    }
+
+    pub fn distributor(&self, address: Pubkey) -> Result<Distributor> {
+        let data = self
+            .rpc(RpcRequest::GetAccountInfo)?
+            .get_account_data(&address)
+            .map_err(|_| CliError::DistributorNotFound(address))?;
+
+        Distributor::try_deserialize(&mut data.as_ref())
+            .map_err(|_| CliError::DistributorDataError.into())
+    }
+
+    pub fn vesting(&self, address: Pubkey) -> Result<Vesting> {
+        let data = self
+            .rpc(RpcRequest::GetAccountInfo)?
+            .get_account_data(&address)
+            .map_err(|_| CliError::VestingNotFound(address))?;
+
+        Vesting::try_deserialize(&mut data.as_ref())
+            .map_err(|_| CliError::VestingDataError.into())
+    }
+
+    //
+    // Mint & Token accounts functions
+    //
+
+    /// Creates the mint of an NFT and mints its single token to the recipient
+    fn create_nft_mint_instructions(
+        payer: Pubkey,
+        primary_wallet: Pubkey,
+        mint: Pubkey,
+        recipient: Pubkey,
+        lamports: u64,
+    ) -> Vec<Instruction> {
+        vec![
+            system_instruction::create_account(
+                &payer,
+                &mint,
+                lamports,
+                Mint::LEN.try_into().unwrap(),
+                &spl_token::ID,
+            ),
+            spl_instruction::initialize_mint(&spl_token::ID, &mint, &primary_wallet, None, 0)
+                .unwrap(),
+            create_associated_token_account(&payer, &recipient, &mint),
+            spl_instruction::mint_to(
+                &spl_token::ID,
+                &mint,
+                &get_associated_token_address(&recipient, &mint),
+                &primary_wallet,
+                &[],
+                1,
+            )
+            .unwrap(),
+        ]
+    }
+
+    pub fn create_mint_and_token_nft(
+        &self,
+        primary_wallet: Rc<dyn Signer>,
+        payer: Rc<dyn Signer>,
+        recipient: Pubkey,
+    ) -> Result<(Pubkey, Pubkey)> {
+        let (mints, _signature) = self.create_nft_mints(primary_wallet, payer, &[recipient])?;
+        let mint = mints[0];
+
+        Ok((mint, get_associated_token_address(&recipient, &mint)))
+    }
+
+    /// Creates an NFT mint for every recipient in a single transaction
+    pub fn create_nft_mints(
+        &self,
+        primary_wallet: Rc<dyn Signer>,
+        payer: Rc<dyn Signer>,
+        recipients: &[Pubkey],
+    ) -> Result<(Vec<Pubkey>, Signature)> {
+        let lamports = self
+            .rpc(RpcRequest::GetMinimumBalanceForRentExemption)?
+            .get_minimum_balance_for_rent_exemption(Mint::LEN)?;
+
+        let mints = recipients.iter().map(|_| Keypair::new()).collect::<Vec<_>>();
+        let instructions = mints
+            .iter()
+            .zip(recipients)
+            .flat_map(|(mint, recipient)| {
+                Self::create_nft_mint_instructions(
+                    payer.pubkey(),
+                    primary_wallet.pubkey(),
+                    mint.pubkey(),
+                    *recipient,
+                    lamports,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let signers = mints
+            .iter()
+            .map(|mint| mint as &dyn Signer)
+            .chain([payer.as_ref(), primary_wallet.as_ref()])
+            .collect::<Vec<_>>();
+        let signature = self.run_transaction(&instructions, payer.pubkey(), &signers)?;
+
+        Ok((mints.iter().map(Keypair::pubkey).collect(), signature))
+    }
+
+    /// The maximum number of NFT mints created by a single transaction
+    pub fn create_nft_mints_batch_size(&self, payer: Pubkey, primary_wallet: Pubkey) -> usize {
+        self.batch_size(payer, |mints| {
+            mints
+                .iter()
+                .flat_map(|mint| {
+                    Self::create_nft_mint_instructions(
+                        payer,
+                        primary_wallet,
+                        *mint,
+                        Pubkey::new_unique(),
+                        0,
+                    )
+                })
+                .collect()
+        })
+    }
+
+    pub fn create_mint(
+        &self,
+        authority: Rc<dyn Signer>,
+        payer: Rc<dyn Signer>,
+        decimals: u8,
+    ) -> Result<Pubkey> {
+        let mint = Keypair::new();
+        let space = Mint::LEN;
+        let lamports = self
+            .rpc(RpcRequest::GetMinimumBalanceForRentExemption)?
+            .get_minimum_balance_for_rent_exemption(space)?;
+
+        let ixs = &[
             system_instruction::create_account(
                 &payer.pubkey(),
                 &mint.pubkey(),
@@ -276,6 +1268,15 @@ impl Client {
             return Ok(found_token_pubkey);
         }
 
+        self.create_associated_token_account(owner, mint, payer)
+    }
+
+    pub fn create_associated_token_account(
+        &self,
+        owner: Pubkey,
+        mint: Pubkey,
+        payer: Rc<dyn Signer>,
+    ) -> Result<Pubkey> {
         let token_pubkey = get_associated_token_address(&owner, &mint);
         let ix = create_associated_token_account(&payer.pubkey(), &owner, &mint);
         self.run_transaction(&[ix], payer.pubkey(), &[payer.as_ref()])?;
@@ -285,7 +1286,7 @@ impl Client {
     pub fn find_token_address(&self, address: Pubkey, mint: Pubkey) -> Result<Option<Pubkey>> {
         let filter = TokenAccountsFilter::Mint(mint);
         let token_accounts = self
-            .rpc_client
+            .rpc(RpcRequest::GetTokenAccountsByOwner)?
             .get_token_accounts_by_owner(&address, filter)?;
 
         if token_accounts.is_empty() {
@@ -309,7 +1310,7 @@ impl Client {
     pub fn token_balance(&self, owner: Pubkey, mint: Pubkey) -> Result<u64> {
         let filter = TokenAccountsFilter::Mint(mint);
         let token_accounts = self
-            .rpc_client
+            .rpc(RpcRequest::GetTokenAccountsByOwner)?
             .get_token_accounts_by_owner(&owner, filter)?;
         let addresses = token_accounts
             .iter()
@@ -434,9 +1435,11 @@ impl Client {
     // Program instructions
     //
 
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize(
         &self,
-        primary_wallet: Rc<dyn Signer>,
+        primary_wallet: Pubkey,
+        admin_signers: &[Rc<dyn Signer>],
         payer: Rc<dyn Signer>,
         chill_mint: Pubkey,
         fees: Fees,
@@ -445,33 +1448,56 @@ impl Client {
     ) -> Result<Signature> {
         let program = self.program(payer.clone(), program_id)?;
         let config = pda::config(chill_mint, program_id);
+        let config_registry = pda::config_registry(program_id);
 
-        program
-            .request()
+        let mut request = program.request();
+        if self
+            .rpc(RpcRequest::GetAccountInfo)?
+            .get_account(&config_registry)
+            .is_err()
+        {
+            let accounts = chill_nft::accounts::InitializeConfigRegistry {
+                payer: payer.pubkey(),
+                config_registry,
+                system_program: system_program::id(),
+            };
+
+            request = request.instruction(Instruction {
+                program_id,
+                accounts: accounts.to_account_metas(None),
+                data: chill_nft::instruction::InitializeConfigRegistry {}.data(),
+            });
+        }
+
+        let mut instructions = request
             .args(chill_nft::instruction::Initialize { fees, recipients })
             .accounts(chill_nft::accounts::Initialize {
-                primary_wallet: primary_wallet.pubkey(),
+                primary_wallet,
+                admin_config: pda::admin_config(primary_wallet, program_id),
                 payer: payer.pubkey(),
                 config,
+                config_registry,
                 chill_mint,
                 system_program: system_program::id(),
             })
-            .send()
-            .map_err(Into::into)
+            .instructions()?;
+
+        Self::append_admin_signers(instructions.last_mut().unwrap(), admin_signers);
+        self.send_admin_transaction(&instructions, payer, admin_signers)
     }
 
-    #[allow(clippy::too_many_arguments)]
-    pub fn mint_nft(
+    fn append_admin_signers(ix: &mut Instruction, admin_signers: &[Rc<dyn Signer>]) {
+        let signers = admin_signers.iter().map(|s| s.pubkey()).collect::<Vec<_>>();
+        ix.accounts.extend(chill_sdk::instruction::admin_signer_metas(&signers));
+    }
+
+    /// Token accounts of the config recipients, missing accounts are created
+    fn recipients_token_accounts(
         &self,
-        primary_wallet: Rc<dyn Signer>,
         payer: Rc<dyn Signer>,
         chill_mint: Pubkey,
-        creator: Option<Pubkey>,
-        nft_mint: Pubkey,
-        nft_type: NftType,
-        args: NftArgs,
         program_id: Pubkey,
-    ) -> Result<Signature> {
+    ) -> Result<Vec<AccountMeta>> {
         let config = self.config(chill_mint, program_id)?;
         let mut recipients_token_accounts = Vec::with_capacity(config.recipients.len());
         for recipient in config.recipients {
@@ -497,6 +1523,27 @@ impl Client {
             };
         }
 
+        Ok(recipients_token_accounts)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_nft(
+        &self,
+        primary_wallet: Rc<dyn Signer>,
+        payer: Rc<dyn Signer>,
+        chill_mint: Pubkey,
+        creator: Option<Pubkey>,
+        nft_mint: Pubkey,
+        nft_type: NftType,
+        args: NftArgs,
+        min_game_version: u32,
+        collection: Option<Pubkey>,
+        uses: Option<Uses>,
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let recipients_token_accounts =
+            self.recipients_token_accounts(payer.clone(), chill_mint, program_id)?;
+
         let program = self.program(payer.clone(), program_id)?;
         let config_pubkey = pda::config(chill_mint, program_id);
 
@@ -508,12 +1555,15 @@ impl Client {
             .find_token_address(primary_wallet.pubkey(), chill_mint)?
             .ok_or_else(|| CliError::TokenAccountNotFound(primary_wallet.pubkey()))?;
 
-        program
+        let instructions = program
             .request()
             .args(chill_nft::instruction::MintNft {
                 nft_type,
                 args,
                 creator,
+                min_game_version,
+                collection,
+                uses,
             })
             .accounts(chill_nft::accounts::MintNft {
                 primary_wallet: primary_wallet.pubkey(),
@@ -522,6 +1572,7 @@ impl Client {
                 chill_payer_token_account: primary_wallet_token,
                 config: config_pubkey,
                 chill_mint,
+                nft_type_registry: pda::nft_type_registry(config_pubkey, program_id),
                 nft_mint,
                 nft_metadata,
                 nft_master_edition,
@@ -532,91 +1583,462 @@ impl Client {
                 token_metadata_program: mpl_token_metadata::ID,
             })
             .accounts(recipients_token_accounts)
-            .signer(primary_wallet.as_ref())
-            .send()
-            .map_err(Into::into)
+            .accounts(collection.map(chill_sdk::instruction::collection_metas).unwrap_or_default())
+            .instructions()?;
+
+        self.run_transaction(
+            &instructions,
+            payer.pubkey(),
+            &[payer.as_ref(), primary_wallet.as_ref()],
+        )
     }
 
-    pub fn update_nft(
+    /// Builds the transaction which creates the NFT mint and mints the NFT to
+    /// the recipient. Missing token accounts of the fee recipients are created
+    /// by the payer. The transaction is signed by the new mint and `signers`
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_nft_transaction(
         &self,
-        payer: Rc<dyn Signer>,
-        primary_wallet: Rc<dyn Signer>,
-        nft_mint: Pubkey,
+        primary_wallet: Pubkey,
+        payer: Pubkey,
+        recipient: Pubkey,
+        chill_mint: Pubkey,
+        nft_type: NftType,
         args: NftArgs,
+        min_game_version: u32,
         program_id: Pubkey,
-    ) -> Result<Signature> {
-        let program = self.program(payer.clone(), program_id)?;
-        let nft_metadata = pda::metadata(nft_mint);
+        signers: &[&dyn Signer],
+    ) -> Result<(Pubkey, String)> {
+        let nft_mint = Keypair::new();
+        let space = Mint::LEN;
+        let lamports = self
+            .rpc(RpcRequest::GetMinimumBalanceForRentExemption)?
+            .get_minimum_balance_for_rent_exemption(space)?;
 
-        program
-            .request()
-            .args(chill_nft::instruction::UpdateNft { args })
-            .accounts(chill_nft::accounts::UpdateNft {
-                primary_wallet: primary_wallet.pubkey(),
-                nft_metadata,
-                token_metadata_program: mpl_token_metadata::ID,
-            })
-            .signer(primary_wallet.as_ref())
-            .send()
-            .map_err(Into::into)
+        let primary_wallet_token = self
+            .find_token_address(primary_wallet, chill_mint)?
+            .ok_or(CliError::TokenAccountNotFound(primary_wallet))?;
+
+        let mut instructions = Self::create_nft_mint_instructions(
+            payer,
+            primary_wallet,
+            nft_mint.pubkey(),
+            recipient,
+            lamports,
+        );
+
+        let config = self.config(chill_mint, program_id)?;
+        let mut recipients_token_accounts = Vec::with_capacity(config.recipients.len());
+        for recipient in config.recipients {
+            let token_address = match self.find_token_address(recipient.address, chill_mint)? {
+                Some(token_address) => token_address,
+                None => {
+                    instructions.push(create_associated_token_account(
+                        &payer,
+                        &recipient.address,
+                        &chill_mint,
+                    ));
+                    get_associated_token_address(&recipient.address, &chill_mint)
+                }
+            };
+
+            recipients_token_accounts.push(token_address);
+        }
+
+        instructions.push(chill_sdk::instruction::mint_nft(
+            primary_wallet,
+            payer,
+            primary_wallet,
+            primary_wallet_token,
+            chill_mint,
+            nft_mint.pubkey(),
+            nft_type,
+            args,
+            None,
+            min_game_version,
+            &recipients_token_accounts,
+            None,
+            None,
+            program_id,
+        ));
+
+        let signers = iter::once(&nft_mint as &dyn Signer)
+            .chain(signers.iter().copied())
+            .collect::<Vec<_>>();
+        let transaction =
+            self.export_partially_signed_transaction(&instructions, payer, &signers)?;
+
+        Ok((nft_mint.pubkey(), transaction))
     }
 
-    pub fn create_wallet(
+    /// The number of the first NFTs minted by a single transaction. Names and
+    /// URIs are a part of the instruction, so the number depends on the NFTs
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_nft_batch_size(
         &self,
-        payer: Rc<dyn Signer>,
-        account: Pubkey,
-        proxy_wallet: Pubkey,
+        payer: Pubkey,
         primary_wallet: Pubkey,
+        chill_mint: Pubkey,
+        nft_mints: &[Pubkey],
+        nfts: &[BatchNft],
+        collection: Option<Pubkey>,
         program_id: Pubkey,
-    ) -> Result<Signature> {
-        let program = self.program(payer.clone(), program_id)?;
+    ) -> Result<usize> {
+        let config = self.config(chill_mint, program_id)?;
+        let recipients_token_accounts = config
+            .recipients
+            .iter()
+            .map(|_| Pubkey::new_unique())
+            .collect::<Vec<_>>();
 
-        program
-            .request()
-            .args(chill_wallet::instruction::CreateWallet)
-            .accounts(chill_wallet::accounts::CreateWallet {
+        let fits = |number: usize| {
+            let instruction = chill_sdk::instruction::mint_nft_batch(
                 primary_wallet,
-                user: account,
-                payer: payer.pubkey(),
-                proxy_wallet,
-                system_program: system_program::ID,
-            })
-            .send()
-            .map_err(Into::into)
+                payer,
+                primary_wallet,
+                Pubkey::new_unique(),
+                chill_mint,
+                &nft_mints[..number],
+                nfts[..number].to_vec(),
+                &recipients_token_accounts,
+                collection,
+                program_id,
+            );
+            self.fits_in_transaction(payer, &[instruction])
+        };
+
+        let number = (1..=nfts.len())
+            .find(|number| !fits(*number))
+            .map_or(nfts.len(), |number| number - 1);
+        Ok(number)
     }
 
-    pub fn withdraw_lamports(
+    /// Mints NFTs into the existing mints in a single transaction
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_nft_batch(
         &self,
+        primary_wallet: Rc<dyn Signer>,
         payer: Rc<dyn Signer>,
-        authority: Rc<dyn Signer>,
-        proxy_wallet: Pubkey,
-        recipient: Pubkey,
-        amount: u64,
+        chill_mint: Pubkey,
+        nft_mints: &[Pubkey],
+        nfts: Vec<BatchNft>,
+        collection: Option<Pubkey>,
         program_id: Pubkey,
     ) -> Result<Signature> {
-        let program = self.program(payer.clone(), program_id)?;
+        let recipients_token_accounts = self
+            .recipients_token_accounts(payer.clone(), chill_mint, program_id)?
+            .into_iter()
+            .map(|meta| meta.pubkey)
+            .collect::<Vec<_>>();
 
-        program
-            .request()
-            .args(chill_wallet::instruction::WithdrawLamports { amount })
-            .accounts(chill_wallet::accounts::WithdrawLamports {
-                authority: authority.pubkey(),
-                proxy_wallet,
-                receiver: recipient,
-            })
-            .signer(authority.as_ref())
-            .send()
-            .map_err(Into::into)
+        let primary_wallet_token = self
+            .find_token_address(primary_wallet.pubkey(), chill_mint)?
+            .ok_or_else(|| CliError::TokenAccountNotFound(primary_wallet.pubkey()))?;
+
+        let instruction = chill_sdk::instruction::mint_nft_batch(
+            primary_wallet.pubkey(),
+            payer.pubkey(),
+            primary_wallet.pubkey(),
+            primary_wallet_token,
+            chill_mint,
+            nft_mints,
+            nfts,
+            &recipients_token_accounts,
+            collection,
+            program_id,
+        );
+
+        self.run_transaction(
+            &[instruction],
+            payer.pubkey(),
+            &[payer.as_ref(), primary_wallet.as_ref()],
+        )
     }
 
-    #[allow(clippy::too_many_arguments)]
-    pub fn withdraw_ft(
+    pub fn create_collection(
         &self,
+        primary_wallet: Rc<dyn Signer>,
         payer: Rc<dyn Signer>,
-        authority: Rc<dyn Signer>,
-        proxy_wallet: Pubkey,
-        recipient: Pubkey,
-        mint: Pubkey,
+        chill_mint: Pubkey,
+        collection_mint: Pubkey,
+        args: NftArgs,
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let instruction = chill_sdk::instruction::create_collection(
+            primary_wallet.pubkey(),
+            payer.pubkey(),
+            chill_mint,
+            collection_mint,
+            args,
+            program_id,
+        );
+
+        self.run_transaction(
+            &[instruction],
+            payer.pubkey(),
+            &[payer.as_ref(), primary_wallet.as_ref()],
+        )
+    }
+
+    pub fn update_min_game_version_instruction(
+        primary_wallet: Pubkey,
+        nft_mint: Pubkey,
+        min_game_version: u32,
+        program_id: Pubkey,
+    ) -> Instruction {
+        Instruction {
+            program_id,
+            accounts: chill_nft::accounts::UpdateMinGameVersion {
+                primary_wallet,
+                nft_metadata: pda::metadata(nft_mint),
+                nft_chill_metadata: pda::chill_metadata(nft_mint, program_id),
+            }
+            .to_account_metas(None),
+            data: chill_nft::instruction::UpdateMinGameVersion { min_game_version }.data(),
+        }
+    }
+
+    pub fn finalize_nft_instruction(
+        primary_wallet: Pubkey,
+        nft_mint: Pubkey,
+        program_id: Pubkey,
+    ) -> Instruction {
+        Instruction {
+            program_id,
+            accounts: chill_nft::accounts::FinalizeNft {
+                primary_wallet,
+                nft_metadata: pda::metadata(nft_mint),
+                nft_chill_metadata: pda::chill_metadata(nft_mint, program_id),
+                token_metadata_program: mpl_token_metadata::ID,
+            }
+            .to_account_metas(None),
+            data: chill_nft::instruction::FinalizeNft.data(),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_nft(
+        &self,
+        payer: Rc<dyn Signer>,
+        primary_wallet: Rc<dyn Signer>,
+        nft_mint: Pubkey,
+        args: NftArgs,
+        creators: Option<Vec<NftCreator>>,
+        primary_sale_happened: Option<bool>,
+        min_game_version: Option<u32>,
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let program = self.program(payer.clone(), program_id)?;
+        let nft_metadata = pda::metadata(nft_mint);
+
+        let mut request = program.request();
+        if let Some(min_game_version) = min_game_version {
+            request = request.instruction(Self::update_min_game_version_instruction(
+                primary_wallet.pubkey(),
+                nft_mint,
+                min_game_version,
+                program_id,
+            ));
+        }
+
+        let instructions = request
+            .args(chill_nft::instruction::UpdateNft {
+                args,
+                creators,
+                primary_sale_happened,
+            })
+            .accounts(chill_nft::accounts::UpdateNft {
+                primary_wallet: primary_wallet.pubkey(),
+                nft_metadata,
+                nft_chill_metadata: pda::chill_metadata(nft_mint, program_id),
+                token_metadata_program: mpl_token_metadata::ID,
+            })
+            .instructions()?;
+
+        self.run_transaction(
+            &instructions,
+            payer.pubkey(),
+            &[payer.as_ref(), primary_wallet.as_ref()],
+        )
+    }
+
+    pub fn reclaim_metadata(
+        &self,
+        payer: Rc<dyn Signer>,
+        nft_mint: Pubkey,
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let program = self.program(payer.clone(), program_id)?;
+        let chill_metadata = self.chill_metadata(nft_mint, program_id)?;
+
+        let instructions = program
+            .request()
+            .args(chill_nft::instruction::ReclaimMetadata)
+            .accounts(chill_nft::accounts::ReclaimMetadata {
+                nft_mint,
+                nft_chill_metadata: pda::chill_metadata(nft_mint, program_id),
+                rent_receiver: chill_metadata.rent_receiver,
+            })
+            .instructions()?;
+
+        self.run_transaction(&instructions, payer.pubkey(), &[payer.as_ref()])
+    }
+
+    pub fn create_wallet(
+        &self,
+        payer: Rc<dyn Signer>,
+        account: Pubkey,
+        primary_wallet: Pubkey,
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let program = self.program(payer.clone(), program_id)?;
+        let ix = chill_sdk::instruction::create_wallet(
+            payer.pubkey(),
+            account,
+            primary_wallet,
+            program_id,
+        );
+
+        let instructions = program.request().instruction(ix).instructions()?;
+
+        self.run_transaction(&instructions, payer.pubkey(), &[payer.as_ref()])
+    }
+
+    pub fn existing_accounts(&self, pubkeys: &[Pubkey]) -> Result<Vec<bool>> {
+        let mut existing = Vec::with_capacity(pubkeys.len());
+
+        // RPC nodes limit the number of accounts requested at once
+        for chunk in pubkeys.chunks(100) {
+            let accounts = self
+                .rpc(RpcRequest::GetMultipleAccounts)?
+                .get_multiple_accounts(chunk)?;
+            existing.extend(accounts.iter().map(Option::is_some));
+        }
+
+        Ok(existing)
+    }
+
+    fn create_wallets_instruction(
+        payer: Pubkey,
+        primary_wallet: Pubkey,
+        admin_signers: &[Pubkey],
+        users: &[Pubkey],
+        program_id: Pubkey,
+    ) -> Instruction {
+        chill_sdk::instruction::create_wallets(
+            payer,
+            primary_wallet,
+            admin_signers,
+            users,
+            program_id,
+        )
+    }
+
+    fn fits_in_transaction(&self, payer: Pubkey, instructions: &[Instruction]) -> bool {
+        let instructions = self.transaction_instructions(instructions, payer);
+        let message = Message::new(&instructions, Some(&payer));
+
+        // Signatures are prefixed by their number which takes a single byte
+        let signatures_len = 1 + 64 * usize::from(message.header.num_required_signatures);
+        signatures_len + message.serialize().len() <= PACKET_DATA_SIZE
+    }
+
+    /// The maximum number of accounts processed by a single transaction
+    fn batch_size(
+        &self,
+        payer: Pubkey,
+        instructions: impl Fn(&[Pubkey]) -> Vec<Instruction>,
+    ) -> usize {
+        let mut accounts = Vec::new();
+        loop {
+            accounts.push(Pubkey::new_unique());
+            if !self.fits_in_transaction(payer, &instructions(&accounts)) {
+                return accounts.len() - 1;
+            }
+        }
+    }
+
+    /// The maximum number of wallets created by a single transaction
+    pub fn create_wallets_batch_size(
+        &self,
+        payer: Pubkey,
+        primary_wallet: Pubkey,
+        admin_signers: &[Pubkey],
+        program_id: Pubkey,
+    ) -> usize {
+        self.batch_size(payer, |users| {
+            vec![Self::create_wallets_instruction(
+                payer,
+                primary_wallet,
+                admin_signers,
+                users,
+                program_id,
+            )]
+        })
+    }
+
+    pub fn create_wallets(
+        &self,
+        payer: Rc<dyn Signer>,
+        primary_wallet: Pubkey,
+        admin_signers: &[Rc<dyn Signer>],
+        users: &[Pubkey],
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let program = self.program(payer.clone(), program_id)?;
+        let ix = Self::create_wallets_instruction(
+            payer.pubkey(),
+            primary_wallet,
+            &admin_signers.iter().map(|s| s.pubkey()).collect::<Vec<_>>(),
+            users,
+            program_id,
+        );
+
+        let instructions = program.request().instruction(ix).instructions()?;
+        self.send_admin_transaction(&instructions, payer, admin_signers)
+    }
+
+    pub fn withdraw_lamports(
+        &self,
+        payer: Rc<dyn Signer>,
+        authority: Rc<dyn Signer>,
+        proxy_wallet: Pubkey,
+        recipient: Pubkey,
+        amount: u64,
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let program = self.program(payer.clone(), program_id)?;
+
+        let fee_accounts = self
+            .withdrawal_fee_receiver(proxy_wallet, amount)?
+            .map(chill_sdk::instruction::fee_receiver_meta);
+
+        let instructions = program
+            .request()
+            .args(chill_wallet::instruction::WithdrawLamports { amount })
+            .accounts(chill_wallet::accounts::WithdrawLamports {
+                authority: authority.pubkey(),
+                proxy_wallet,
+                receiver: recipient,
+            })
+            .accounts(fee_accounts.into_iter().collect::<Vec<_>>())
+            .instructions()?;
+
+        self.run_transaction(
+            &instructions,
+            payer.pubkey(),
+            &[payer.as_ref(), authority.as_ref()],
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn withdraw_ft(
+        &self,
+        payer: Rc<dyn Signer>,
+        authority: Rc<dyn Signer>,
+        proxy_wallet: Pubkey,
+        recipient: Pubkey,
+        mint: Pubkey,
         amount: u64,
         program_id: Pubkey,
     ) -> Result<Signature> {
@@ -626,9 +2048,18 @@ impl Client {
             .find_token_address(proxy_wallet, mint)?
             .ok_or(CliError::TokenAccountNotFound(proxy_wallet))?;
 
-        let receiver_token_account = self.get_or_create_token_account(recipient, mint, payer)?;
+        let receiver_token_account =
+            self.get_or_create_token_account(recipient, mint, payer.clone())?;
+
+        let fee_accounts = self
+            .withdrawal_fee_receiver(proxy_wallet, amount)?
+            .map(|fee_receiver| {
+                self.get_or_create_token_account(fee_receiver, mint, payer.clone())
+                    .map(|fee_token_account| AccountMeta::new(fee_token_account, false))
+            })
+            .transpose()?;
 
-        program
+        let instructions = program
             .request()
             .args(chill_wallet::instruction::WithdrawFt { amount })
             .accounts(chill_wallet::accounts::WithdrawFt {
@@ -639,9 +2070,14 @@ impl Client {
                 receiver_token_account,
                 token_program: spl_token::ID,
             })
-            .signer(authority.as_ref())
-            .send()
-            .map_err(Into::into)
+            .accounts(fee_accounts.into_iter().collect::<Vec<_>>())
+            .instructions()?;
+
+        self.run_transaction(
+            &instructions,
+            payer.pubkey(),
+            &[payer.as_ref(), authority.as_ref()],
+        )
     }
 
     pub fn withdraw_nft(
@@ -660,9 +2096,35 @@ impl Client {
             .ok_or(CliError::TokenAccountNotFound(proxy_wallet))?;
 
         let receiver_token_account =
-            self.get_or_create_token_account(recipient, nft_mint, payer)?;
+            self.get_or_create_token_account(recipient, nft_mint, payer.clone())?;
+
+        // The primary wallet marks the first sale of its NFT in the same
+        // transaction, the same way as `transfer_tokens` does
+        let primary_wallet = self.proxy_wallet(proxy_wallet)?.primary_wallet;
+        if primary_wallet == authority.pubkey() && self.metadata_account(nft_mint).is_ok() {
+            let instructions = program
+                .request()
+                .args(chill_wallet::instruction::TransferNftWithUpdate)
+                .accounts(chill_wallet::accounts::TransferNftWithUpdate {
+                    primary_wallet,
+                    proxy_wallet,
+                    nft_mint,
+                    nft_metadata: pda::metadata(nft_mint),
+                    proxy_wallet_token_account,
+                    receiver_token_account,
+                    token_program: spl_token::ID,
+                    token_metadata_program: mpl_token_metadata::ID,
+                })
+                .instructions()?;
+
+            return self.run_transaction(
+                &instructions,
+                payer.pubkey(),
+                &[payer.as_ref(), authority.as_ref()],
+            );
+        }
 
-        program
+        let instructions = program
             .request()
             .args(chill_wallet::instruction::WithdrawNft)
             .accounts(chill_wallet::accounts::WithdrawNft {
@@ -673,9 +2135,100 @@ impl Client {
                 receiver_token_account,
                 token_program: spl_token::ID,
             })
-            .signer(authority.as_ref())
-            .send()
-            .map_err(Into::into)
+            .instructions()?;
+
+        self.run_transaction(
+            &instructions,
+            payer.pubkey(),
+            &[payer.as_ref(), authority.as_ref()],
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn schedule_withdrawal(
+        &self,
+        payer: Rc<dyn Signer>,
+        authority: Rc<dyn Signer>,
+        proxy_wallet: Pubkey,
+        recipient: Pubkey,
+        amount: u64,
+        interval: u64,
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let program = self.program(payer.clone(), program_id)?;
+        let ix = chill_sdk::instruction::schedule_withdrawal(
+            authority.pubkey(),
+            proxy_wallet,
+            recipient,
+            amount,
+            interval,
+            program_id,
+        );
+
+        let instructions = program.request().instruction(ix).instructions()?;
+
+        self.run_transaction(
+            &instructions,
+            payer.pubkey(),
+            &[payer.as_ref(), authority.as_ref()],
+        )
+    }
+
+    pub fn execute_due_withdrawal(
+        &self,
+        payer: Rc<dyn Signer>,
+        proxy_wallet: Pubkey,
+        recipient: Pubkey,
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let program = self.program(payer.clone(), program_id)?;
+        let schedule = pda::withdrawal_schedule(proxy_wallet, recipient, program_id);
+        let amount = self.withdrawal_schedule(schedule)?.amount;
+        let fee_accounts = self
+            .withdrawal_fee_receiver(proxy_wallet, amount)?
+            .map(chill_sdk::instruction::fee_receiver_meta);
+
+        let instructions = program
+            .request()
+            .args(chill_wallet::instruction::ExecuteDueWithdrawal)
+            .accounts(chill_wallet::accounts::ExecuteDueWithdrawal {
+                proxy_wallet,
+                schedule,
+                receiver: recipient,
+            })
+            .accounts(fee_accounts.into_iter().collect::<Vec<_>>())
+            .instructions()?;
+
+        self.run_transaction(&instructions, payer.pubkey(), &[payer.as_ref()])
+    }
+
+    pub fn cancel_scheduled_withdrawal(
+        &self,
+        payer: Rc<dyn Signer>,
+        authority: Rc<dyn Signer>,
+        proxy_wallet: Pubkey,
+        recipient: Pubkey,
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let program = self.program(payer.clone(), program_id)?;
+        let schedule = pda::withdrawal_schedule(proxy_wallet, recipient, program_id);
+        let schedule_authority = self.withdrawal_schedule(schedule)?.authority;
+
+        let ix = chill_sdk::instruction::cancel_scheduled_withdrawal(
+            authority.pubkey(),
+            proxy_wallet,
+            recipient,
+            schedule_authority,
+            program_id,
+        );
+
+        let instructions = program.request().instruction(ix).instructions()?;
+
+        self.run_transaction(
+            &instructions,
+            payer.pubkey(),
+            &[payer.as_ref(), authority.as_ref()],
+        )
     }
 
     pub fn staking_initialize(
@@ -689,13 +2242,9 @@ impl Client {
     ) -> Result<Signature> {
         let program = self.program(payer.clone(), program_id)?;
 
-        let staking_token_authority =
-            pda::staking_token_authority(staking_info.pubkey(), program_id);
-        let staking_token_account = get_associated_token_address(&staking_token_authority, &mint);
-
-        let space = StakingInfo::LEN + args.total_days() * 8;
+        let space = StakingInfo::LEN + args.total_days()? * 8;
         let lamports = self
-            .rpc_client
+            .rpc(RpcRequest::GetMinimumBalanceForRentExemption)?
             .get_minimum_balance_for_rent_exemption(space)?;
 
         let ix = system_instruction::create_account(
@@ -706,59 +2255,1013 @@ impl Client {
             &program_id,
         );
 
-        program
+        let initialize_ix = chill_sdk::instruction::initialize_staking(
+            primary_wallet.pubkey(),
+            payer.pubkey(),
+            staking_info.pubkey(),
+            mint,
+            args,
+            program_id,
+        );
+
+        let instructions = program
             .request()
-            .args(chill_staking::instruction::Initialize { args })
-            .accounts(chill_staking::accounts::Initialize {
-                primary_wallet: primary_wallet.pubkey(),
-                payer: payer.pubkey(),
-                staking_info: staking_info.pubkey(),
-                staking_token_authority,
-                staking_token_account,
-                mint,
-                system_program: system_program::ID,
-                rent: Rent::id(),
-                token_program: spl_token::ID,
-                associated_token_program: associated_token::ID,
-            })
+            .instruction(initialize_ix)
             .instruction(ix)
-            .signer(primary_wallet.as_ref())
-            .signer(staking_info)
-            .send()
-            .map_err(Into::into)
+            .instructions()?;
+
+        self.run_transaction(
+            &instructions,
+            payer.pubkey(),
+            &[payer.as_ref(), primary_wallet.as_ref(), staking_info],
+        )
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn staking_add_token_reward(
         &self,
         primary_wallet: Rc<dyn Signer>,
+        admin_signers: &[Rc<dyn Signer>],
         payer: Rc<dyn Signer>,
         staking_info: Pubkey,
         mint: Pubkey,
         amount: u64,
         program_id: Pubkey,
     ) -> Result<Signature> {
-        let program = self.program(payer.clone(), program_id)?;
         let primary_wallet_token_account = self
             .find_token_address(primary_wallet.pubkey(), mint)?
             .ok_or_else(|| CliError::TokenAccountNotFound(primary_wallet.pubkey()))?;
 
-        let staking_token_authority = pda::staking_token_authority(staking_info, program_id);
-        let staking_token_account = get_associated_token_address(&staking_token_authority, &mint);
+        let mut ix = chill_sdk::instruction::add_reward_tokens(
+            primary_wallet.pubkey(),
+            primary_wallet.pubkey(),
+            primary_wallet_token_account,
+            staking_info,
+            mint,
+            amount,
+            program_id,
+        );
 
-        program
-            .request()
-            .args(chill_staking::instruction::AddRewardTokens { amount })
-            .accounts(chill_staking::accounts::AddRewardTokens {
-                primary_wallet: primary_wallet.pubkey(),
-                token_account_authority: primary_wallet.pubkey(),
-                token_account: primary_wallet_token_account,
-                staking_info,
-                staking_token_authority,
-                staking_token_account,
-                token_program: spl_token::ID,
-            })
-            .signer(primary_wallet.as_ref())
-            .send()
-            .map_err(Into::into)
+        Self::append_admin_signers(&mut ix, admin_signers);
+
+        let signers = [&[primary_wallet], admin_signers].concat();
+        self.send_admin_transaction(&[ix], payer, &signers)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn staking_redeem_remaining_reward_tokens(
+        &self,
+        primary_wallet: Rc<dyn Signer>,
+        admin_signers: &[Rc<dyn Signer>],
+        payer: Rc<dyn Signer>,
+        staking_info: Pubkey,
+        mint: Pubkey,
+        amount: u64,
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let primary_wallet_token_account =
+            self.get_or_create_token_account(primary_wallet.pubkey(), mint, payer.clone())?;
+
+        let mut ix = chill_sdk::instruction::redeem_remaining_reward_tokens(
+            primary_wallet.pubkey(),
+            primary_wallet_token_account,
+            staking_info,
+            mint,
+            amount,
+            program_id,
+        );
+
+        Self::append_admin_signers(&mut ix, admin_signers);
+
+        let signers = [&[primary_wallet], admin_signers].concat();
+        self.send_admin_transaction(&[ix], payer, &signers)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn staking_transfer_remaining_reward_tokens(
+        &self,
+        primary_wallet: Rc<dyn Signer>,
+        admin_signers: &[Rc<dyn Signer>],
+        payer: Rc<dyn Signer>,
+        from_staking_info: Pubkey,
+        to_staking_info: Pubkey,
+        mint: Pubkey,
+        amount: u64,
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let primary_wallet_token_account =
+            self.get_or_create_token_account(primary_wallet.pubkey(), mint, payer.clone())?;
+
+        // Redeem and add tokens in one transaction to never leave them on the primary wallet
+        let mut redeem_ix = chill_sdk::instruction::redeem_remaining_reward_tokens(
+            primary_wallet.pubkey(),
+            primary_wallet_token_account,
+            from_staking_info,
+            mint,
+            amount,
+            program_id,
+        );
+
+        Self::append_admin_signers(&mut redeem_ix, admin_signers);
+
+        let add_ix = chill_sdk::instruction::add_reward_tokens(
+            primary_wallet.pubkey(),
+            primary_wallet.pubkey(),
+            primary_wallet_token_account,
+            to_staking_info,
+            mint,
+            amount,
+            program_id,
+        );
+
+        let signers = [&[primary_wallet], admin_signers].concat();
+        self.send_admin_transaction(&[redeem_ix, add_ix], payer, &signers)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn staking_stake(
+        &self,
+        user: Rc<dyn Signer>,
+        payer: Rc<dyn Signer>,
+        staking_info: Pubkey,
+        mint: Pubkey,
+        amount: u64,
+        referrer: Option<Pubkey>,
+        nft_mint: Option<Pubkey>,
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let user_token_account = self
+            .find_token_address(user.pubkey(), mint)?
+            .ok_or_else(|| CliError::TokenAccountNotFound(user.pubkey()))?;
+
+        let mut ix = chill_sdk::instruction::stake(
+            user.pubkey(),
+            user.pubkey(),
+            payer.pubkey(),
+            user_token_account,
+            staking_info,
+            mint,
+            amount,
+            referrer,
+            program_id,
+        );
+
+        if let Some(nft_mint) = nft_mint {
+            ix.accounts.extend(chill_sdk::instruction::nft_holder_accounts(
+                user.pubkey(),
+                staking_info,
+                nft_mint,
+                program_id,
+            ));
+        }
+
+        self.run_transaction(&[ix], payer.pubkey(), &[payer.as_ref(), user.as_ref()])
+    }
+
+    /// Stakes tokens of the user for the beneficiary
+    #[allow(clippy::too_many_arguments)]
+    pub fn staking_stake_for(
+        &self,
+        user: Rc<dyn Signer>,
+        payer: Rc<dyn Signer>,
+        beneficiary: Pubkey,
+        staking_info: Pubkey,
+        mint: Pubkey,
+        amount: u64,
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let user_token_account = self
+            .find_token_address(user.pubkey(), mint)?
+            .ok_or_else(|| CliError::TokenAccountNotFound(user.pubkey()))?;
+
+        let ix = chill_sdk::instruction::stake_for(
+            beneficiary,
+            user.pubkey(),
+            payer.pubkey(),
+            user_token_account,
+            staking_info,
+            mint,
+            amount,
+            program_id,
+        );
+
+        self.run_transaction(&[ix], payer.pubkey(), &[payer.as_ref(), user.as_ref()])
+    }
+
+    /// Claims all rewarded and pending tokens if the amount is not specified
+    pub fn staking_claim(
+        &self,
+        user: Rc<dyn Signer>,
+        payer: Rc<dyn Signer>,
+        staking_info: Pubkey,
+        amount: Option<u64>,
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let staking = self.staking_info(staking_info)?;
+        let user_token_account =
+            self.get_or_create_token_account(user.pubkey(), staking.mint, payer.clone())?;
+
+        // The claim fee is shared between the recipients of the NFT config
+        let mut fee_config = None;
+        let mut recipients_token_accounts = Vec::new();
+        if staking.claim_fee > 0 {
+            let config = self.config(staking.mint, chill_nft::ID)?;
+            fee_config = Some(pda::config(staking.mint, chill_nft::ID));
+            for recipient in config.recipients {
+                let token_account = self.get_or_create_token_account(
+                    recipient.address,
+                    staking.mint,
+                    payer.clone(),
+                )?;
+                recipients_token_accounts.push(token_account);
+            }
+        }
+
+        let ix = match amount {
+            Some(amount) => chill_sdk::instruction::claim(
+                user.pubkey(),
+                user_token_account,
+                staking_info,
+                staking.mint,
+                amount,
+                fee_config,
+                &recipients_token_accounts,
+                program_id,
+            ),
+            None => chill_sdk::instruction::claim_all(
+                user.pubkey(),
+                user_token_account,
+                staking_info,
+                staking.mint,
+                fee_config,
+                &recipients_token_accounts,
+                program_id,
+            ),
+        };
+
+        self.run_transaction(&[ix], payer.pubkey(), &[payer.as_ref(), user.as_ref()])
+    }
+
+    pub fn staking_boost(
+        &self,
+        user: Rc<dyn Signer>,
+        payer: Rc<dyn Signer>,
+        staking_info: Pubkey,
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let ix = chill_sdk::instruction::boost(user.pubkey(), staking_info, program_id);
+        self.run_transaction(&[ix], payer.pubkey(), &[payer.as_ref(), user.as_ref()])
+    }
+
+    pub fn staking_cancel(
+        &self,
+        user: Rc<dyn Signer>,
+        payer: Rc<dyn Signer>,
+        staking_info: Pubkey,
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let ix = chill_sdk::instruction::cancel(user.pubkey(), staking_info, program_id);
+        self.run_transaction(&[ix], payer.pubkey(), &[payer.as_ref(), user.as_ref()])
+    }
+
+    pub fn staking_close_user_info(
+        &self,
+        user: Rc<dyn Signer>,
+        payer: Rc<dyn Signer>,
+        staking_info: Pubkey,
+        recipient: Pubkey,
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let ix = chill_sdk::instruction::close_user_info(
+            user.pubkey(),
+            staking_info,
+            recipient,
+            program_id,
+        );
+
+        self.run_transaction(&[ix], payer.pubkey(), &[payer.as_ref(), user.as_ref()])
+    }
+
+    /// The maximum number of user infos closed by a single transaction
+    pub fn staking_close_batch_size(
+        &self,
+        primary_wallet: Pubkey,
+        payer: Pubkey,
+        staking_info: Pubkey,
+        treasury: Pubkey,
+        program_id: Pubkey,
+    ) -> usize {
+        self.batch_size(payer, |user_infos| {
+            vec![chill_sdk::instruction::close_staking_accounts_batch(
+                primary_wallet,
+                staking_info,
+                treasury,
+                user_infos,
+                program_id,
+            )]
+        })
+    }
+
+    pub fn staking_close_user_infos(
+        &self,
+        primary_wallet: Rc<dyn Signer>,
+        payer: Rc<dyn Signer>,
+        staking_info: Pubkey,
+        treasury: Pubkey,
+        user_infos: &[Pubkey],
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let ix = chill_sdk::instruction::close_staking_accounts_batch(
+            primary_wallet.pubkey(),
+            staking_info,
+            treasury,
+            user_infos,
+            program_id,
+        );
+
+        self.run_transaction(
+            &[ix],
+            payer.pubkey(),
+            &[payer.as_ref(), primary_wallet.as_ref()],
+        )
+    }
+
+    pub fn staking_close_staking_info(
+        &self,
+        primary_wallet: Rc<dyn Signer>,
+        payer: Rc<dyn Signer>,
+        staking_info: Pubkey,
+        recipient: Pubkey,
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let ix = chill_sdk::instruction::close_staking_info(
+            primary_wallet.pubkey(),
+            staking_info,
+            recipient,
+            program_id,
+        );
+
+        self.run_transaction(
+            &[ix],
+            payer.pubkey(),
+            &[payer.as_ref(), primary_wallet.as_ref()],
+        )
+    }
+
+    pub fn is_program_deployed(&self, program_id: Pubkey) -> Result<bool> {
+        let account = self
+            .rpc(RpcRequest::GetAccountInfo)?
+            .get_account_with_commitment(&program_id, self.commitment)?
+            .value;
+
+        Ok(account.is_some_and(|account| account.executable))
+    }
+
+    /// Hash of the executable data of an upgradeable program. Builds of the
+    /// same source produce the same hash in all clusters
+    pub fn program_hash(&self, program_id: Pubkey) -> Result<Option<Hash>> {
+        let (programdata, _) =
+            Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+
+        let account = self
+            .rpc(RpcRequest::GetAccountInfo)?
+            .get_account_with_commitment(&programdata, self.commitment)?
+            .value;
+
+        let offset = UpgradeableLoaderState::programdata_data_offset().unwrap();
+        Ok(account.and_then(|account| {
+            // The account is usually allocated with a space for upgrades,
+            // which is filled with zeros
+            let data = account.data.get(offset..)?;
+            let len = data.iter().rposition(|byte| *byte != 0).map_or(0, |i| i + 1);
+            Some(hash(&data[..len]))
+        }))
+    }
+
+    pub fn devnet_tools_enabled(&self, payer: Pubkey, program_id: Pubkey) -> Result<bool> {
+        // The view has the same name in all programs, so its data is the same too
+        let ix = Instruction {
+            program_id,
+            accounts: Vec::new(),
+            data: chill_staking::instruction::ViewDevnetToolsEnabled {}.data(),
+        };
+
+        self.simulate_view(&[ix], payer, program_id)
+    }
+
+    pub fn staking_user_infos(
+        &self,
+        staking_info: Pubkey,
+        days_in_window: u64,
+        program_id: Pubkey,
+    ) -> Result<Vec<(Pubkey, UserInfo)>> {
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![
+                RpcFilterType::DataSize((UserInfo::LEN as u64) + days_in_window),
+                // UserInfo starts with the user pubkey which is followed by the staking info one
+                RpcFilterType::Memcmp(Memcmp {
+                    offset: DESCRIMINATOR_LEN + 32,
+                    bytes: MemcmpEncodedBytes::Base58(staking_info.to_string()),
+                    encoding: None,
+                }),
+            ]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                commitment: Some(self.commitment),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        };
+
+        let accounts = self
+            .rpc(RpcRequest::GetProgramAccounts)?
+            .get_program_accounts_with_config(&program_id, config)?;
+
+        accounts
+            .into_iter()
+            .map(|(pubkey, account)| {
+                UserInfo::try_deserialize(&mut account.data.as_ref())
+                    .map(|user_info| (pubkey, user_info))
+                    .map_err(|_| CliError::UserInfoDataError.into())
+            })
+            .collect()
+    }
+
+    /// Returns user infos of all users of the staking, whatever the size of
+    /// their windows of days is
+    pub fn all_user_infos(
+        &self,
+        staking_info: Pubkey,
+        program_id: Pubkey,
+    ) -> Result<Vec<(Pubkey, UserInfo)>> {
+        Self::decode_user_infos(self.all_user_info_accounts(staking_info, program_id)?)
+    }
+
+    /// Accounts keep boosted days of the stakes after the user infos
+    pub fn all_user_info_accounts(
+        &self,
+        staking_info: Pubkey,
+        program_id: Pubkey,
+    ) -> Result<Vec<(Pubkey, SolanaAccount)>> {
+        self.program_accounts(program_id, Self::user_info_filters(staking_info))
+    }
+
+    fn user_info_filters(staking_info: Pubkey) -> Vec<RpcFilterType> {
+        // The staking info follows the discriminator and the user pubkey
+        vec![
+            Self::memcmp(0, &UserInfo::discriminator()),
+            Self::memcmp(DESCRIMINATOR_LEN + 32, staking_info.as_ref()),
+        ]
+    }
+
+    fn decode_user_infos(
+        accounts: Vec<(Pubkey, SolanaAccount)>,
+    ) -> Result<Vec<(Pubkey, UserInfo)>> {
+        accounts
+            .into_iter()
+            .map(|(pubkey, account)| {
+                UserInfo::try_deserialize_versioned(&account.data)
+                    .map(|user_info| (pubkey, user_info))
+                    .map_err(|_| CliError::UserInfoDataError.into())
+            })
+            .collect()
+    }
+
+    /// Returns all staking pools of the mint
+    pub fn all_staking_pools(
+        &self,
+        mint: Pubkey,
+        program_id: Pubkey,
+    ) -> Result<Vec<(Pubkey, StakingInfo)>> {
+        let filters = Self::staking_pool_filters(mint);
+        Self::decode_staking_pools(self.program_accounts(program_id, filters)?)
+    }
+
+    fn staking_pool_filters(mint: Pubkey) -> Vec<RpcFilterType> {
+        // The mint follows the discriminator and the primary wallet
+        vec![
+            Self::memcmp(0, &StakingInfo::discriminator()),
+            Self::memcmp(DESCRIMINATOR_LEN + 32, mint.as_ref()),
+        ]
+    }
+
+    fn decode_staking_pools(
+        accounts: Vec<(Pubkey, SolanaAccount)>,
+    ) -> Result<Vec<(Pubkey, StakingInfo)>> {
+        accounts
+            .into_iter()
+            .map(|(pubkey, account)| {
+                StakingInfo::try_deserialize_versioned(&account.data)
+                    .map(|staking_info| (pubkey, staking_info))
+                    .map_err(|_| CliError::StakingInfoDataError.into())
+            })
+            .collect()
+    }
+
+    pub fn staking_redistribution(
+        &self,
+        staking_info: Pubkey,
+        program_id: Pubkey,
+    ) -> Result<Option<Redistribution>> {
+        let redistribution = pda::redistribution(staking_info, program_id);
+        let account = self
+            .rpc(RpcRequest::GetAccountInfo)?
+            .get_account_with_commitment(&redistribution, self.commitment)?
+            .value;
+
+        account
+            .map(|account| {
+                Redistribution::try_deserialize(&mut account.data.as_ref())
+                    .map_err(|_| CliError::RedistributionDataError.into())
+            })
+            .transpose()
+    }
+
+    /// The maximum number of users processed by a single redistribution transaction
+    pub fn staking_redistribution_batch_size(
+        &self,
+        payer: Pubkey,
+        staking_info: Pubkey,
+        program_id: Pubkey,
+    ) -> usize {
+        self.batch_size(payer, |user_infos| {
+            user_infos
+                .iter()
+                .map(|user_info| {
+                    chill_sdk::instruction::redistribute_unspent_reward(
+                        payer,
+                        *user_info,
+                        staking_info,
+                        program_id,
+                    )
+                })
+                .collect()
+        })
+    }
+
+    pub fn staking_settle_stakes(
+        &self,
+        payer: Rc<dyn Signer>,
+        staking_info: Pubkey,
+        user_infos: &[Pubkey],
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let instructions = user_infos
+            .iter()
+            .map(|user_info| {
+                chill_sdk::instruction::settle_stake(*user_info, staking_info, program_id)
+            })
+            .collect::<Vec<_>>();
+
+        self.run_transaction(&instructions, payer.pubkey(), &[payer.as_ref()])
+    }
+
+    pub fn staking_start_redistribution(
+        &self,
+        primary_wallet: Rc<dyn Signer>,
+        payer: Rc<dyn Signer>,
+        staking_info: Pubkey,
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let ix = chill_sdk::instruction::start_redistribution(
+            primary_wallet.pubkey(),
+            payer.pubkey(),
+            staking_info,
+            program_id,
+        );
+
+        self.run_transaction(
+            &[ix],
+            payer.pubkey(),
+            &[payer.as_ref(), primary_wallet.as_ref()],
+        )
+    }
+
+    pub fn staking_redistribute(
+        &self,
+        payer: Rc<dyn Signer>,
+        staking_info: Pubkey,
+        user_infos: &[Pubkey],
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let instructions = user_infos
+            .iter()
+            .map(|user_info| {
+                chill_sdk::instruction::redistribute_unspent_reward(
+                    payer.pubkey(),
+                    *user_info,
+                    staking_info,
+                    program_id,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        self.run_transaction(&instructions, payer.pubkey(), &[payer.as_ref()])
+    }
+
+    pub fn staking_migrate_staking_info(
+        &self,
+        payer: Rc<dyn Signer>,
+        staking_info: Pubkey,
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let mint = self.staking_info(staking_info)?.mint;
+        let ix = chill_sdk::instruction::migrate_staking_info(
+            payer.pubkey(),
+            staking_info,
+            mint,
+            program_id,
+        );
+        self.run_transaction(&[ix], payer.pubkey(), &[payer.as_ref()])
+    }
+
+    /// The maximum number of user infos migrated by a single transaction
+    pub fn staking_migrate_batch_size(
+        &self,
+        payer: Pubkey,
+        staking_info: Pubkey,
+        program_id: Pubkey,
+    ) -> usize {
+        self.batch_size(payer, |user_infos| {
+            user_infos
+                .iter()
+                .map(|user_info| {
+                    chill_sdk::instruction::migrate_user_info(
+                        payer,
+                        *user_info,
+                        staking_info,
+                        program_id,
+                    )
+                })
+                .collect()
+        })
+    }
+
+    pub fn staking_migrate_user_infos(
+        &self,
+        payer: Rc<dyn Signer>,
+        staking_info: Pubkey,
+        user_infos: &[Pubkey],
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let instructions = user_infos
+            .iter()
+            .map(|user_info| {
+                chill_sdk::instruction::migrate_user_info(
+                    payer.pubkey(),
+                    *user_info,
+                    staking_info,
+                    program_id,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        self.run_transaction(&instructions, payer.pubkey(), &[payer.as_ref()])
+    }
+
+    pub fn staking_verify_pool_solvency(
+        &self,
+        payer: Rc<dyn Signer>,
+        staking_info: Pubkey,
+        program_id: Pubkey,
+    ) -> Result<SolvencyReport> {
+        let mint = self.staking_info(staking_info)?.mint;
+        let ix = chill_sdk::instruction::verify_pool_solvency(staking_info, mint, program_id);
+
+        self.simulate_view(&[ix], payer.pubkey(), program_id)
+    }
+
+    /// The maximum number of accounts reconciled by a single simulation
+    pub fn staking_reconcile_batch_size(
+        &self,
+        payer: Pubkey,
+        staking_info: Pubkey,
+        program_id: Pubkey,
+    ) -> usize {
+        self.batch_size(payer, |accounts| {
+            vec![chill_sdk::instruction::reconcile(
+                staking_info,
+                accounts,
+                program_id,
+            )]
+        })
+    }
+
+    pub fn staking_reconcile(
+        &self,
+        payer: Pubkey,
+        staking_info: Pubkey,
+        accounts: &[Pubkey],
+        program_id: Pubkey,
+    ) -> Result<ReconcileReport> {
+        let ix = chill_sdk::instruction::reconcile(staking_info, accounts, program_id);
+        self.simulate_view(&[ix], payer, program_id)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn drop_initialize(
+        &self,
+        authority: Rc<dyn Signer>,
+        payer: Rc<dyn Signer>,
+        mint: Pubkey,
+        merkle_root: [u8; 32],
+        total_amount: u64,
+        kind: DropKind,
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let ix = match kind {
+            DropKind::Tokens => {
+                let authority_token_account = self
+                    .find_token_address(authority.pubkey(), mint)?
+                    .ok_or(CliError::TokenAccountNotFound(authority.pubkey()))?;
+
+                chill_sdk::instruction::initialize_token_drop(
+                    authority.pubkey(),
+                    payer.pubkey(),
+                    mint,
+                    authority_token_account,
+                    merkle_root,
+                    total_amount,
+                    program_id,
+                )
+            }
+            DropKind::WhitelistSlots => chill_sdk::instruction::initialize_whitelist_drop(
+                authority.pubkey(),
+                payer.pubkey(),
+                mint,
+                merkle_root,
+                total_amount,
+                program_id,
+            ),
+        };
+
+        self.run_transaction(
+            &[ix],
+            payer.pubkey(),
+            &[payer.as_ref(), authority.as_ref()],
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn drop_claim(
+        &self,
+        claimant: Rc<dyn Signer>,
+        payer: Rc<dyn Signer>,
+        distributor: Pubkey,
+        index: u64,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let distributor_account = self.distributor(distributor)?;
+
+        let ix = match distributor_account.kind {
+            DropKind::Tokens => {
+                let mint = distributor_account.mint;
+                let claimant_token_account =
+                    self.get_or_create_token_account(claimant.pubkey(), mint, payer.clone())?;
+
+                chill_sdk::instruction::claim_drop(
+                    claimant.pubkey(),
+                    payer.pubkey(),
+                    distributor,
+                    mint,
+                    claimant_token_account,
+                    index,
+                    amount,
+                    proof,
+                    program_id,
+                )
+            }
+            DropKind::WhitelistSlots => chill_sdk::instruction::claim_slots(
+                claimant.pubkey(),
+                payer.pubkey(),
+                distributor,
+                index,
+                amount,
+                proof,
+                program_id,
+            ),
+        };
+
+        self.run_transaction(
+            &[ix],
+            payer.pubkey(),
+            &[payer.as_ref(), claimant.as_ref()],
+        )
+    }
+
+    pub fn vesting_initialize(
+        &self,
+        authority: Rc<dyn Signer>,
+        payer: Rc<dyn Signer>,
+        beneficiary: Pubkey,
+        mint: Pubkey,
+        args: InitializeVestingArgs,
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let authority_token_account = self
+            .find_token_address(authority.pubkey(), mint)?
+            .ok_or(CliError::TokenAccountNotFound(authority.pubkey()))?;
+
+        let ix = chill_sdk::instruction::initialize_vesting(
+            authority.pubkey(),
+            payer.pubkey(),
+            beneficiary,
+            mint,
+            authority_token_account,
+            args,
+            program_id,
+        );
+
+        self.run_transaction(
+            &[ix],
+            payer.pubkey(),
+            &[payer.as_ref(), authority.as_ref()],
+        )
+    }
+
+    pub fn vesting_claim(
+        &self,
+        beneficiary: Rc<dyn Signer>,
+        payer: Rc<dyn Signer>,
+        vesting: Pubkey,
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let mint = self.vesting(vesting)?.mint;
+        let beneficiary_token_account =
+            self.get_or_create_token_account(beneficiary.pubkey(), mint, payer.clone())?;
+
+        let ix = chill_sdk::instruction::claim_vested(
+            beneficiary.pubkey(),
+            vesting,
+            mint,
+            beneficiary_token_account,
+            program_id,
+        );
+
+        self.run_transaction(
+            &[ix],
+            payer.pubkey(),
+            &[payer.as_ref(), beneficiary.as_ref()],
+        )
+    }
+
+    pub fn vesting_revoke(
+        &self,
+        authority: Rc<dyn Signer>,
+        payer: Rc<dyn Signer>,
+        vesting: Pubkey,
+        program_id: Pubkey,
+    ) -> Result<Signature> {
+        let mint = self.vesting(vesting)?.mint;
+        let treasury_token_account =
+            self.get_or_create_token_account(authority.pubkey(), mint, payer.clone())?;
+
+        let ix = chill_sdk::instruction::revoke_vesting(
+            authority.pubkey(),
+            vesting,
+            mint,
+            treasury_token_account,
+            program_id,
+        );
+
+        self.run_transaction(
+            &[ix],
+            payer.pubkey(),
+            &[payer.as_ref(), authority.as_ref()],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::AppError;
+    use anchor_client::anchor_lang::AccountSerialize;
+
+    /// Accounts of the programs have no `Default`, so they are decoded from
+    /// zeroed data
+    fn zeroed<T: AccountDeserialize + Discriminator>(len: usize) -> T {
+        let mut data = vec![0; len];
+        data[..DESCRIMINATOR_LEN].copy_from_slice(&T::discriminator());
+        T::try_deserialize(&mut data.as_ref()).unwrap()
+    }
+
+    fn account(data: Vec<u8>, lamports: u64) -> (Pubkey, SolanaAccount) {
+        let account = SolanaAccount {
+            lamports,
+            data,
+            ..Default::default()
+        };
+        (Pubkey::new_unique(), account)
+    }
+
+    fn serialize<T: AccountSerialize>(account: &T) -> Vec<u8> {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    fn matches(filters: &[RpcFilterType], data: &[u8]) -> bool {
+        filters.iter().all(|filter| match filter {
+            RpcFilterType::Memcmp(memcmp) => memcmp.bytes_match(data),
+            RpcFilterType::DataSize(size) => data.len() as u64 == *size,
+        })
+    }
+
+    fn is_cli_error(result: Result<impl Sized>, expected: CliError) -> bool {
+        match result {
+            Err(AppError::InternalError(error)) => error.to_string() == expected.to_string(),
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn proxy_wallets() {
+        let primary_wallet = Pubkey::new_unique();
+        let mut proxy_wallet = zeroed::<ProxyWallet>(ProxyWallet::LEN);
+        proxy_wallet.primary_wallet = primary_wallet;
+        proxy_wallet.user = Pubkey::new_unique();
+        let data = serialize(&proxy_wallet);
+
+        assert!(matches(&Client::proxy_wallet_filters(primary_wallet), &data));
+        assert!(!matches(&Client::proxy_wallet_filters(proxy_wallet.user), &data));
+        assert!(!matches(
+            &Client::proxy_wallet_filters(primary_wallet),
+            &serialize(&zeroed::<UserInfo>(UserInfo::LEN))
+        ));
+
+        // Wallets of the first layout are shorter, but still listed
+        let legacy = data[..ProxyWallet::LEN_V1].to_vec();
+        assert!(matches(&Client::proxy_wallet_filters(primary_wallet), &legacy));
+
+        let accounts = vec![account(data, 10), account(legacy, 20)];
+        let pubkeys = accounts.iter().map(|(pubkey, _)| *pubkey).collect::<Vec<_>>();
+        let decoded = Client::decode_proxy_wallets(accounts).unwrap();
+        assert_eq!(decoded.len(), 2);
+        for ((pubkey, wallet, lamports), (expected, expected_lamports)) in
+            decoded.iter().zip(pubkeys.iter().zip([10, 20]))
+        {
+            assert_eq!(pubkey, expected);
+            assert_eq!(*lamports, expected_lamports);
+            assert_eq!(wallet.primary_wallet, primary_wallet);
+            assert_eq!(wallet.user, proxy_wallet.user);
+        }
+
+        assert!(is_cli_error(
+            Client::decode_proxy_wallets(vec![account(vec![1, 2, 3], 0)]),
+            CliError::ProxyWalletDataError
+        ));
+    }
+
+    #[test]
+    fn user_infos() {
+        let staking_info = Pubkey::new_unique();
+        let mut user_info = zeroed::<UserInfo>(UserInfo::LEN);
+        user_info.user = Pubkey::new_unique();
+        user_info.staking_info = staking_info;
+        user_info.staked_amount = 1000;
+        let data = serialize(&user_info);
+
+        assert!(matches(&Client::user_info_filters(staking_info), &data));
+        assert!(!matches(&Client::user_info_filters(user_info.user), &data));
+
+        let decoded = Client::decode_user_infos(vec![account(data, 0)]).unwrap();
+        assert_eq!(decoded[0].1.user, user_info.user);
+        assert_eq!(decoded[0].1.staked_amount, 1000);
+
+        let mut data = serialize(&user_info);
+        data[..DESCRIMINATOR_LEN].copy_from_slice(&StakingInfo::discriminator());
+        assert!(!matches(&Client::user_info_filters(staking_info), &data));
+        assert!(is_cli_error(
+            Client::decode_user_infos(vec![account(data, 0)]),
+            CliError::UserInfoDataError
+        ));
+    }
+
+    #[test]
+    fn staking_pools() {
+        let mint = Pubkey::new_unique();
+        let mut staking_info = zeroed::<StakingInfo>(StakingInfo::LEN);
+        staking_info.primary_wallet = Pubkey::new_unique();
+        staking_info.mint = mint;
+        staking_info.end_day = 30;
+        let data = serialize(&staking_info);
+
+        assert!(matches(&Client::staking_pool_filters(mint), &data));
+        assert!(!matches(
+            &Client::staking_pool_filters(staking_info.primary_wallet),
+            &data
+        ));
+
+        let decoded = Client::decode_staking_pools(vec![account(data, 0)]).unwrap();
+        assert_eq!(decoded[0].1.mint, mint);
+        assert_eq!(decoded[0].1.end_day, 30);
+
+        assert!(is_cli_error(
+            Client::decode_staking_pools(vec![account(Vec::new(), 0)]),
+            CliError::StakingInfoDataError
+        ));
     }
 }
@@ -0,0 +1,91 @@
+//! Defaults of the command line arguments read from `~/.config/chill/config.toml`,
+//! so operators don't pass the same cluster, primary wallet and mint to every
+//! command. Arguments given explicitly override the file
+
+use crate::error::CliError;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use serde::Deserialize;
+use solana_clap_utils::input_validators::is_url_or_moniker;
+use std::{fs, io::ErrorKind, path::PathBuf, str::FromStr};
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    url: Option<String>,
+    primary_wallet: Option<String>,
+    mint_file: Option<String>,
+    nft_program_id: Option<String>,
+    wallet_program_id: Option<String>,
+    staking_program_id: Option<String>,
+    drop_program_id: Option<String>,
+    vesting_program_id: Option<String>,
+}
+
+#[derive(Default)]
+pub struct CliConfig {
+    /// URL of the RPC node or its moniker
+    pub url: Option<String>,
+    /// Keypair path of the primary wallet in any format accepted by `--primary-wallet`
+    pub primary_wallet: Option<String>,
+    /// Replaces the default `mint.<cluster>.pubkey` file
+    pub mint_file: Option<String>,
+    pub nft_program_id: Option<Pubkey>,
+    pub wallet_program_id: Option<Pubkey>,
+    pub staking_program_id: Option<Pubkey>,
+    pub drop_program_id: Option<Pubkey>,
+    pub vesting_program_id: Option<Pubkey>,
+}
+
+impl CliConfig {
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::home_dir().map(|mut path| {
+            path.extend(&[".config", "chill", "config.toml"]);
+            path
+        })
+    }
+
+    /// The file of the given path must exist, while the default one is optional
+    pub fn load(path: Option<&str>) -> Result<Self, CliError> {
+        let (path, required) = match path {
+            Some(path) => (PathBuf::from(path), true),
+            None => match Self::default_path() {
+                Some(path) => (path, false),
+                None => return Ok(Self::default()),
+            },
+        };
+
+        let path_string = path.to_string_lossy().into_owned();
+        let error = |message: String| CliError::CannotReadConfig(path_string.clone(), message);
+
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound && !required => {
+                return Ok(Self::default());
+            }
+            Err(e) => return Err(error(e.to_string())),
+        };
+
+        let file: ConfigFile = toml::from_str(&content).map_err(|e| error(e.to_string()))?;
+        if let Some(url) = &file.url {
+            is_url_or_moniker(url).map_err(|e| error(format!("url: {}", e)))?;
+        }
+
+        let pubkey = |value: Option<String>, key: &str| {
+            value
+                .map(|value| Pubkey::from_str(&value))
+                .transpose()
+                .map_err(|e| error(format!("{}: {}", key, e)))
+        };
+
+        Ok(Self {
+            nft_program_id: pubkey(file.nft_program_id, "nft_program_id")?,
+            wallet_program_id: pubkey(file.wallet_program_id, "wallet_program_id")?,
+            staking_program_id: pubkey(file.staking_program_id, "staking_program_id")?,
+            drop_program_id: pubkey(file.drop_program_id, "drop_program_id")?,
+            vesting_program_id: pubkey(file.vesting_program_id, "vesting_program_id")?,
+            url: file.url,
+            primary_wallet: file.primary_wallet,
+            mint_file: file.mint_file,
+        })
+    }
+}
@@ -0,0 +1,171 @@
+//! Field-by-field comparison of the program configurations deployed in two
+//! clusters
+
+use anchor_client::solana_sdk::hash::Hash;
+use chill_nft::state::Config;
+use chill_sdk::view::day_to_date;
+use chill_staking::state::StakingInfo;
+
+pub type Fields = Vec<(String, String)>;
+
+pub struct FieldDiff {
+    pub field: String,
+    pub a: Option<String>,
+    pub b: Option<String>,
+}
+
+impl FieldDiff {
+    pub fn is_equal(&self) -> bool {
+        self.a == self.b
+    }
+}
+
+/// Addresses of the config and its mint differ between clusters, so only fees
+/// and recipients are compared
+pub fn config_fields(config: &Config) -> Fields {
+    let fees = &config.fees;
+    let mut fields = vec![
+        ("fees.character".to_owned(), fees.character.to_string()),
+        ("fees.pet".to_owned(), fees.pet.to_string()),
+        ("fees.emote".to_owned(), fees.emote.to_string()),
+        ("fees.tileset".to_owned(), fees.tileset.to_string()),
+        ("fees.item".to_owned(), fees.item.to_string()),
+        ("fees.world".to_owned(), fees.world.to_string()),
+    ];
+
+    for (index, recipient) in config.recipients.iter().enumerate() {
+        let prefix = format!("recipients.{}", index);
+        fields.extend([
+            (format!("{}.address", prefix), recipient.address.to_string()),
+            (
+                format!("{}.mint_share", prefix),
+                recipient.mint_share.to_string(),
+            ),
+            (
+                format!("{}.transaction_share", prefix),
+                recipient.transaction_share.to_string(),
+            ),
+        ]);
+    }
+
+    fields
+}
+
+pub fn staking_fields(staking_info: &StakingInfo) -> Fields {
+    vec![
+        ("start".to_owned(), day_to_date(staking_info.start_day)),
+        ("end".to_owned(), day_to_date(staking_info.end_day)),
+        (
+            "min_stake_size".to_owned(),
+            staking_info.min_stake_size.to_string(),
+        ),
+        (
+            "days_in_window".to_owned(),
+            staking_info.days_in_window().to_string(),
+        ),
+        ("claim_fee".to_owned(), staking_info.claim_fee.to_string()),
+        (
+            "referral_fee".to_owned(),
+            staking_info.referral_fee.to_string(),
+        ),
+        (
+            "reward_tokens_amount".to_owned(),
+            staking_info.reward_tokens_amount.to_string(),
+        ),
+        ("paused".to_owned(), staking_info.paused.to_string()),
+    ]
+}
+
+pub fn program_hash_field(name: &str, hash: Option<Hash>) -> (String, String) {
+    let hash = hash.map_or_else(|| "not deployed".to_owned(), |hash| hash.to_string());
+    (format!("{}.hash", name), hash)
+}
+
+/// Pairs fields by name in the order of the first list. Fields which only one
+/// list contains are paired with `None`
+pub fn diff(a: &[(String, String)], b: &[(String, String)]) -> Vec<FieldDiff> {
+    let find = |fields: &[(String, String)], field: &str| {
+        fields
+            .iter()
+            .find(|(name, _)| name == field)
+            .map(|(_, value)| value.clone())
+    };
+
+    let only_in_b = b
+        .iter()
+        .filter(|(field, _)| find(a, field).is_none())
+        .map(|(field, _)| field);
+
+    a.iter()
+        .map(|(field, _)| field)
+        .chain(only_in_b)
+        .map(|field| FieldDiff {
+            field: field.clone(),
+            a: find(a, field),
+            b: find(b, field),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_client::solana_sdk::pubkey::Pubkey;
+    use chill_nft::state::{Fees, Recipient};
+
+    fn config(recipients: Vec<Recipient>) -> Config {
+        Config {
+            bump: 0,
+            primary_wallet: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            fees: Fees {
+                character: 1,
+                pet: 2,
+                emote: 3,
+                tileset: 4,
+                item: 5,
+                world: 6,
+            },
+            recipients,
+        }
+    }
+
+    #[test]
+    fn config_diff() {
+        let address = Pubkey::new_unique();
+        let recipient = |mint_share| Recipient {
+            address,
+            mint_share,
+            transaction_share: 100,
+        };
+
+        let a = config_fields(&config(vec![recipient(100)]));
+        let b = config_fields(&config(vec![
+            recipient(50),
+            Recipient {
+                address: Pubkey::new_unique(),
+                mint_share: 50,
+                transaction_share: 0,
+            },
+        ]));
+
+        let diffs = diff(&a, &b);
+        let mismatched = diffs
+            .iter()
+            .filter(|diff| !diff.is_equal())
+            .map(|diff| diff.field.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(diffs.len(), 12);
+        assert_eq!(
+            mismatched,
+            [
+                "recipients.0.mint_share",
+                "recipients.1.address",
+                "recipients.1.mint_share",
+                "recipients.1.transaction_share",
+            ]
+        );
+        assert!(diffs.last().unwrap().a.is_none());
+    }
+}
@@ -0,0 +1,162 @@
+//! Tree files of merkle drops. The file keeps the root and the proof of every
+//! claim, so claimants don't need the whole list of recipients to claim
+
+use crate::error::{CliError, Result};
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use chill_sdk::merkle::{self, MerkleTree};
+use serde_json::{json, Value};
+use std::{collections::HashSet, fs, str::FromStr};
+
+pub struct DropClaim {
+    pub index: u64,
+    pub claimant: Pubkey,
+    pub amount: u64,
+    pub proof: Vec<[u8; 32]>,
+}
+
+pub struct DropTree {
+    pub root: [u8; 32],
+    pub decimals: u8,
+    pub total_amount: u64,
+    pub claims: Vec<DropClaim>,
+}
+
+impl DropTree {
+    pub fn new(claims: &[(Pubkey, u64)], decimals: u8) -> Self {
+        let tree = MerkleTree::new(claims);
+        let total_amount = claims
+            .iter()
+            .fold(0u64, |total, (_, amount)| total.saturating_add(*amount));
+
+        let claims = claims
+            .iter()
+            .enumerate()
+            .map(|(index, (claimant, amount))| DropClaim {
+                index: index as u64,
+                claimant: *claimant,
+                amount: *amount,
+                proof: tree.proof(index),
+            })
+            .collect();
+
+        Self {
+            root: tree.root(),
+            decimals,
+            total_amount,
+            claims,
+        }
+    }
+
+    pub fn claim(&self, claimant: Pubkey) -> Option<&DropClaim> {
+        self.claims.iter().find(|claim| claim.claimant == claimant)
+    }
+
+    pub fn read(path: &str) -> Result<Self> {
+        let error =
+            |message: &str| CliError::CannotParseDropTree(path.to_owned(), message.to_owned());
+        let content = fs::read_to_string(path).map_err(|e| error(&e.to_string()))?;
+        let value: Value = serde_json::from_str(&content).map_err(|e| error(&e.to_string()))?;
+
+        let hash = |value: &Value| value.as_str().and_then(merkle::from_hex);
+        let amount = |value: &Value| value.as_str().and_then(|amount| amount.parse().ok());
+
+        let root = hash(&value["root"]).ok_or_else(|| error("wrong root"))?;
+        let decimals = value["decimals"]
+            .as_u64()
+            .and_then(|decimals| decimals.try_into().ok())
+            .ok_or_else(|| error("wrong decimals"))?;
+        let total_amount = amount(&value["total"]).ok_or_else(|| error("wrong total"))?;
+
+        let claims = value["claims"]
+            .as_array()
+            .ok_or_else(|| error("claims not found"))?
+            .iter()
+            .map(|claim| {
+                let proof = claim["proof"]
+                    .as_array()
+                    .and_then(|proof| proof.iter().map(hash).collect::<Option<Vec<_>>>());
+
+                Some(DropClaim {
+                    index: claim["index"].as_u64()?,
+                    claimant: Pubkey::from_str(claim["claimant"].as_str()?).ok()?,
+                    amount: amount(&claim["amount"])?,
+                    proof: proof?,
+                })
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| error("wrong claim"))?;
+
+        Ok(Self {
+            root,
+            decimals,
+            total_amount,
+            claims,
+        })
+    }
+
+    pub fn write(&self, path: &str) -> Result<()> {
+        let claims = self
+            .claims
+            .iter()
+            .map(|claim| {
+                json!({
+                    "index": claim.index,
+                    "claimant": claim.claimant.to_string(),
+                    "amount": claim.amount.to_string(),
+                    "proof": claim.proof.iter().map(merkle::to_hex).collect::<Vec<_>>(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let value = json!({
+            "root": merkle::to_hex(&self.root),
+            "decimals": self.decimals,
+            "total": self.total_amount.to_string(),
+            "claims": claims,
+        });
+
+        let content = serde_json::to_string_pretty(&value).unwrap();
+        fs::write(path, content).map_err(|_| CliError::CannotWriteToFile(path.to_owned()).into())
+    }
+}
+
+/// Parses the CSV file of `address,amount` lines, amounts are in UI units
+pub fn read_claims_csv(path: &str, decimals: u8) -> Result<Vec<(Pubkey, u64)>> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| CliError::CannotParseFile(path.to_owned(), e.to_string()))?;
+
+    let mut unique = HashSet::new();
+    let mut claims = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let error = |message: String| {
+            CliError::CannotParseFile(path.to_owned(), format!("line {}: {}", index + 1, message))
+        };
+
+        let (address, ui_amount) = line
+            .split_once(',')
+            .ok_or_else(|| error("expected 'address,amount'".to_owned()))?;
+        let claimant = Pubkey::from_str(address.trim()).map_err(|e| error(e.to_string()))?;
+        let ui_amount = ui_amount
+            .trim()
+            .parse::<f64>()
+            .map_err(|e| error(e.to_string()))?;
+
+        let amount = spl_token::ui_amount_to_amount(ui_amount, decimals);
+        if amount == 0 {
+            return Err(error("amount must be greater than zero".to_owned()).into());
+        }
+
+        if !unique.insert(claimant) {
+            return Err(error(format!("duplicated address {}", claimant)).into());
+        }
+
+        claims.push((claimant, amount));
+    }
+
+    Ok(claims)
+}
@@ -1,12 +1,14 @@
 use anchor_client::{
     solana_client::{
         client_error::{ClientError, ClientErrorKind},
+        rpc_custom_error::JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY,
         rpc_request::{RpcError, RpcResponseErrorData},
         rpc_response::RpcSimulateTransactionResult,
     },
     solana_sdk::{
         program_error::ProgramError,
         pubkey::{ParsePubkeyError, Pubkey},
+        transaction::TransactionError,
     },
     ClientError as AnchorClientError,
 };
@@ -59,6 +61,12 @@ pub enum CliError {
     #[error("Data cannot be parsed as config")]
     ConfigDataError,
 
+    #[error("Data cannot be parsed as config registry")]
+    ConfigRegistryDataError,
+
+    #[error("Config registry not found. Initialize any config with \"initialize\" command")]
+    ConfigRegistryNotFound,
+
     #[error("Chill metadata account not found")]
     ChillMetadataNotFound,
 
@@ -86,6 +94,12 @@ pub enum CliError {
     #[error("Cannot get authority: {0}")]
     CannotGetAuthority(String),
 
+    #[error("Cannot get beneficiary: {0}")]
+    CannotGetBeneficiary(String),
+
+    #[error("Cannot get claimant: {0}")]
+    CannotGetClaimant(String),
+
     #[error("Cannot get recipient: {0}")]
     CannotGetRecipient(String),
 
@@ -112,10 +126,171 @@ pub enum CliError {
 
     #[error("Specify shares for all recipients")]
     NotEnoughShares,
+
+    #[error("StakingInfo account '{0}' not found")]
+    StakingInfoNotFound(Pubkey),
+
+    #[error("Data cannot be parsed as staking info")]
+    StakingInfoDataError,
+
+    #[error("Simulation failed: {0}")]
+    SimulationFailed(String),
+
+    #[error("Program did not return any data")]
+    ReturnDataNotFound,
+
+    #[error("Staking '{0}' is not finished yet, its remaining reward tokens cannot be transferred")]
+    StakingIsNotFinished(Pubkey),
+
+    #[error("Withdrawal schedule '{0}' not found")]
+    WithdrawalScheduleNotFound(Pubkey),
+
+    #[error("Data cannot be parsed as withdrawal schedule")]
+    WithdrawalScheduleDataError,
+
+    #[error("Program '{0}' is not deployed")]
+    ProgramNotDeployed(Pubkey),
+
+    #[error("Devnet tools are detected in Mainnet deployment of the program '{0}'")]
+    DevnetToolsOnMainnet(Pubkey),
+
+    #[error("NFT '{0}' is not burned")]
+    NftIsNotBurned(Pubkey),
+
+    #[error("RPC budget of {0} calls is exceeded")]
+    RpcBudgetExceeded(u64),
+
+    #[error("Transaction is cancelled")]
+    TransactionCancelled,
+
+    #[error("Data cannot be parsed as user info")]
+    UserInfoDataError,
+
+    #[error("Data cannot be parsed as redistribution")]
+    RedistributionDataError,
+
+    #[error("Configurations of the clusters differ in {0} fields")]
+    ConfigMismatch(usize),
+
+    #[error("Admin config of the primary wallet {0} is not found")]
+    AdminConfigNotFound(Pubkey),
+
+    #[error("Admin config requires {0} signers, specify them with --admin-signer")]
+    NotEnoughAdminSigners(u8),
+
+    #[error("Cannot get admin signer: {0}")]
+    CannotGetAdminSigner(String),
+
+    #[error("Specify fees or recipients to update")]
+    NothingToUpdate,
+
+    #[error("NFT type \"{0}\" is not registered in the config")]
+    UnknownNftType(String),
+
+    #[error("NFT type \"{0}\" already exists")]
+    NftTypeExists(String),
+
+    #[error("Data cannot be parsed as NFT type registry")]
+    NftTypeRegistryDataError,
+
+    #[error("Cannot parse drop tree from the file '{0}' - {1}")]
+    CannotParseDropTree(String, String),
+
+    #[error("Distributor account '{0}' not found")]
+    DistributorNotFound(Pubkey),
+
+    #[error("Data cannot be parsed as distributor")]
+    DistributorDataError,
+
+    #[error("Merkle root of the distributor '{0}' doesn't match the tree file")]
+    DropTreeMismatch(Pubkey),
+
+    #[error("Account '{0}' is not in the drop")]
+    NotInDrop(Pubkey),
+
+    #[error("Vesting account '{0}' not found")]
+    VestingNotFound(Pubkey),
+
+    #[error("Data cannot be parsed as vesting")]
+    VestingDataError,
+
+    #[error("Clock of the cluster cannot be read")]
+    ClockDataError,
+
+    #[error("User info account '{0}' not found")]
+    UserInfoNotFound(Pubkey),
+
+    #[error("Cannot get user: {0}")]
+    CannotGetUser(String),
+
+    #[error("Cannot parse transaction - {0}")]
+    CannotParseTransaction(String),
+
+    #[error("Transactions to submit must be copies of the same transaction")]
+    TransactionsMismatch,
+
+    #[error("Account '{0}' doesn't sign the transaction")]
+    UnexpectedSigner(Pubkey),
+
+    #[error("Transaction lacks {0} signatures")]
+    MissingSignatures(usize),
+
+    #[error("Transaction has invalid signatures")]
+    InvalidSignatures,
+
+    #[error("Proxy wallet '{0}' not found")]
+    ProxyWalletNotFound(Pubkey),
+
+    #[error("Proxy wallet data error")]
+    ProxyWalletDataError,
+
+    #[error("Cannot read the config file '{0}' - {1}")]
+    CannotReadConfig(String, String),
+
+    #[error("Invalid nonce account '{0}' - {1}")]
+    InvalidNonceAccount(Pubkey, String),
+
+    #[error("Cannot get nonce authority: {0}")]
+    CannotGetNonceAuthority(String),
+
+    #[error("Cannot fetch the URI '{0}' - {1}")]
+    CannotFetchUri(String, String),
+
+    #[error("Metadata JSON of the URI '{0}' doesn't match the NFT")]
+    InvalidMetadataUri(String),
 }
 
 impl std::error::Error for AppError {}
 
+impl AppError {
+    /// Describes the error of a transaction which might succeed if it's sent again
+    pub fn retry_reason(&self) -> Option<&'static str> {
+        let client_error = match self {
+            AppError::ClientError(e) => e,
+            AppError::AnchorClientError(AnchorClientError::SolanaClientError(e)) => e,
+            _ => return None,
+        };
+
+        if client_error.get_transaction_error() == Some(TransactionError::BlockhashNotFound) {
+            return Some("blockhash not found");
+        }
+
+        match client_error.kind() {
+            ClientErrorKind::RpcError(RpcError::RpcResponseError {
+                code: JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY,
+                ..
+            }) => Some("the node is behind the cluster"),
+            // The blockhash of the transaction expired before it was confirmed
+            ClientErrorKind::RpcError(RpcError::ForUser(message))
+                if message.starts_with("unable to confirm transaction") =>
+            {
+                Some("the transaction is not confirmed")
+            }
+            _ => None,
+        }
+    }
+}
+
 impl From<CliError> for AppError {
     fn from(error: CliError) -> Self {
         AppError::InternalError(error.into())
@@ -128,6 +303,13 @@ impl From<ProgramError> for AppError {
     }
 }
 
+// Helpers of the program state return errors of the program
+impl From<anchor_client::anchor_lang::error::Error> for AppError {
+    fn from(error: anchor_client::anchor_lang::error::Error) -> Self {
+        AppError::InternalError(error.into())
+    }
+}
+
 impl From<std::io::Error> for AppError {
     fn from(error: std::io::Error) -> Self {
         AppError::InternalError(error.into())
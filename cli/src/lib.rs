@@ -1,5 +1,16 @@
+pub mod api;
 pub mod app;
 pub mod cli;
 pub mod client;
+pub mod config;
+pub mod diff;
+pub mod drop;
 pub mod error;
-pub mod pda;
\ No newline at end of file
+pub mod man;
+pub mod manifest;
+pub mod metadata_uri;
+#[cfg(feature = "nonblocking")]
+pub mod nonblocking;
+pub mod output;
+pub mod pda;
+pub mod qr;
@@ -1,10 +1,19 @@
 use crate::app::App;
 
+pub mod api;
 pub mod app;
 pub mod cli;
 pub mod client;
+pub mod config;
+pub mod diff;
+pub mod drop;
 pub mod error;
+pub mod man;
+pub mod manifest;
+pub mod metadata_uri;
+pub mod output;
 pub mod pda;
+pub mod qr;
 
 pub fn main() {
     let app = App::init();
@@ -0,0 +1,90 @@
+use clap::App;
+use std::fmt::Write;
+
+// Roff treats backslashes as escapes, hyphens as breakable and lines started
+// with a dot or a quote as requests
+fn escape(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            let line = line.replace('\\', "\\\\").replace('-', "\\-");
+            if line.starts_with('.') || line.starts_with('\'') {
+                format!("\\&{}", line)
+            } else {
+                line
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn write_arg(page: &mut String, arg: String, help: Option<&str>) {
+    writeln!(page, ".TP\n\\fB{}\\fR", escape(&arg)).unwrap();
+    if let Some(help) = help {
+        writeln!(page, "{}", escape(help)).unwrap();
+    }
+}
+
+fn write_args(page: &mut String, app: &App) {
+    for (_, arg) in app.p.positionals.iter() {
+        write_arg(page, arg.to_string(), arg.b.help);
+    }
+
+    for arg in &app.p.flags {
+        write_arg(page, arg.to_string(), arg.b.help);
+    }
+
+    for arg in &app.p.opts {
+        write_arg(page, arg.to_string(), arg.b.help);
+    }
+}
+
+fn write_command(page: &mut String, parent: &str, app: &App) {
+    let name = format!("{} {}", parent, app.p.meta.name);
+    writeln!(page, ".SS \"{}\"", escape(name.trim())).unwrap();
+    if let Some(about) = app.p.meta.about {
+        writeln!(page, "{}", escape(about)).unwrap();
+    }
+
+    write_args(page, app);
+
+    for subcommand in &app.p.subcommands {
+        write_command(page, &name, subcommand);
+    }
+}
+
+/// Renders a man page in roff format with all commands and arguments of the app
+pub fn render(app: &App) -> String {
+    let meta = &app.p.meta;
+    let mut page = String::new();
+
+    writeln!(
+        page,
+        ".TH {} 1 \"\" \"{} {}\"",
+        escape(&meta.name.to_uppercase()),
+        escape(&meta.name),
+        meta.version.unwrap_or_default()
+    )
+    .unwrap();
+
+    writeln!(page, ".SH NAME").unwrap();
+    writeln!(
+        page,
+        "{} \\- {}",
+        escape(&meta.name),
+        escape(meta.about.unwrap_or_default())
+    )
+    .unwrap();
+
+    writeln!(page, ".SH SYNOPSIS").unwrap();
+    writeln!(page, "\\fB{}\\fR [OPTIONS] <COMMAND>", escape(&meta.name)).unwrap();
+
+    writeln!(page, ".SH OPTIONS").unwrap();
+    write_args(&mut page, app);
+
+    writeln!(page, ".SH COMMANDS").unwrap();
+    for subcommand in &app.p.subcommands {
+        write_command(&mut page, "", subcommand);
+    }
+
+    page
+}
@@ -0,0 +1,156 @@
+//! Manifests of NFTs minted by `mint-nft-batch`. The results manifest keeps the
+//! mint and the signature of every NFT, so passing it again mints only the NFTs
+//! which failed
+
+use crate::error::{CliError, Result};
+use anchor_client::solana_sdk::{pubkey::Pubkey, signature::Signature};
+use serde_json::{json, Value};
+use std::{fs, str::FromStr};
+
+const RESULTS_SUFFIX: &str = ".results.json";
+
+pub struct ManifestNft {
+    pub nft_type: String,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub recipient: Pubkey,
+    /// The mint is created before the NFT is minted into it
+    pub mint: Option<Pubkey>,
+    pub signature: Option<Signature>,
+    pub error: Option<String>,
+}
+
+impl ManifestNft {
+    pub fn is_minted(&self) -> bool {
+        self.signature.is_some()
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "type": self.nft_type,
+            "name": self.name,
+            "symbol": self.symbol,
+            "uri": self.uri,
+            "recipient": self.recipient.to_string(),
+            "mint": self.mint.map(|mint| mint.to_string()),
+            "signature": self.signature.map(|signature| signature.to_string()),
+            "error": self.error,
+        })
+    }
+}
+
+/// Reads a JSON array of NFTs or CSV lines in the format
+/// `type,name,symbol,uri,recipient`. Values of CSV can't contain commas
+pub fn read(path: &str) -> Result<Vec<ManifestNft>> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| CliError::CannotParseFile(path.to_owned(), e.to_string()))?;
+
+    if path.ends_with(".csv") {
+        read_csv(path, &content)
+    } else {
+        read_json(path, &content)
+    }
+}
+
+fn read_json(path: &str, content: &str) -> Result<Vec<ManifestNft>> {
+    let error = |message: String| CliError::CannotParseFile(path.to_owned(), message);
+    let value: Value = serde_json::from_str(content).map_err(|e| error(e.to_string()))?;
+
+    value
+        .as_array()
+        .ok_or_else(|| error("expected an array of NFTs".to_owned()))?
+        .iter()
+        .enumerate()
+        .map(|(index, nft)| {
+            let error = |message: &str| error(format!("NFT {}: {}", index, message));
+            let string = |key: &str| {
+                nft[key]
+                    .as_str()
+                    .map(str::to_owned)
+                    .ok_or_else(|| error(&format!("wrong {}", key)))
+            };
+            let pubkey = |key: &str| match &nft[key] {
+                Value::Null => Ok(None),
+                value => value
+                    .as_str()
+                    .and_then(|value| Pubkey::from_str(value).ok())
+                    .map(Some)
+                    .ok_or_else(|| error(&format!("wrong {}", key))),
+            };
+
+            let signature = match &nft["signature"] {
+                Value::Null => None,
+                value => Some(
+                    value
+                        .as_str()
+                        .and_then(|value| Signature::from_str(value).ok())
+                        .ok_or_else(|| error("wrong signature"))?,
+                ),
+            };
+
+            Ok(ManifestNft {
+                nft_type: string("type")?,
+                name: string("name")?,
+                symbol: string("symbol")?,
+                uri: string("uri")?,
+                recipient: pubkey("recipient")?.ok_or_else(|| error("wrong recipient"))?,
+                mint: pubkey("mint")?,
+                signature,
+                error: None,
+            })
+        })
+        .collect()
+}
+
+fn read_csv(path: &str, content: &str) -> Result<Vec<ManifestNft>> {
+    let mut nfts = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let error = |message: String| {
+            CliError::CannotParseFile(path.to_owned(), format!("line {}: {}", index + 1, message))
+        };
+
+        let values = line.split(',').map(str::trim).collect::<Vec<_>>();
+        let [nft_type, name, symbol, uri, recipient] = values[..] else {
+            return Err(error("expected 'type,name,symbol,uri,recipient'".to_owned()).into());
+        };
+
+        nfts.push(ManifestNft {
+            nft_type: nft_type.to_owned(),
+            name: name.to_owned(),
+            symbol: symbol.to_owned(),
+            uri: uri.to_owned(),
+            recipient: Pubkey::from_str(recipient).map_err(|e| error(e.to_string()))?,
+            mint: None,
+            signature: None,
+            error: None,
+        });
+    }
+
+    Ok(nfts)
+}
+
+/// Results of a manifest are written next to it, a results manifest is
+/// overwritten
+pub fn results_path(path: &str) -> String {
+    if path.ends_with(RESULTS_SUFFIX) {
+        return path.to_owned();
+    }
+
+    let stem = path
+        .strip_suffix(".json")
+        .or_else(|| path.strip_suffix(".csv"))
+        .unwrap_or(path);
+    format!("{}{}", stem, RESULTS_SUFFIX)
+}
+
+pub fn write(path: &str, nfts: &[ManifestNft]) -> Result<()> {
+    let value = Value::Array(nfts.iter().map(ManifestNft::to_json).collect());
+    fs::write(path, serde_json::to_string_pretty(&value).unwrap())
+        .map_err(|_| CliError::CannotWriteToFile(path.to_owned()).into())
+}
@@ -0,0 +1,155 @@
+//! Checks of the JSON which the URI of an NFT points to. Wallets and
+//! marketplaces read the name, the image and the attributes of the NFT from it,
+//! so a broken JSON breaks the NFT after it's minted
+
+use crate::error::{CliError, Result};
+use serde_json::Value;
+use std::fmt::{self, Display};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum UriProblem {
+    MissingField(&'static str),
+    WrongAttribute(usize),
+    NameMismatch(String),
+    SymbolMismatch(String),
+}
+
+impl UriProblem {
+    /// Missing fields break the NFT, mismatches only confuse its owners
+    pub fn is_fatal(&self) -> bool {
+        matches!(self, Self::MissingField(_) | Self::WrongAttribute(_))
+    }
+}
+
+impl Display for UriProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingField(field) => write!(f, "The '{}' field is missing", field),
+            Self::WrongAttribute(index) => write!(
+                f,
+                "The attribute {} has no 'trait_type' or 'value' field",
+                index
+            ),
+            Self::NameMismatch(name) => write!(f, "The name in the JSON is '{}'", name),
+            Self::SymbolMismatch(symbol) => write!(f, "The symbol in the JSON is '{}'", symbol),
+        }
+    }
+}
+
+pub fn fetch(uri: &str) -> Result<Value> {
+    let error = |e: reqwest::Error| CliError::CannotFetchUri(uri.to_owned(), e.to_string());
+    reqwest::blocking::get(uri)
+        .and_then(|response| response.error_for_status())
+        .map_err(error)?
+        .json()
+        .map_err(|e| error(e).into())
+}
+
+/// The symbol is optional in the Metaplex standard, it's compared only if
+/// the JSON has it
+pub fn check(json: &Value, name: &str, symbol: &str) -> Vec<UriProblem> {
+    let mut problems = Vec::new();
+
+    match json.get("name").and_then(Value::as_str) {
+        Some(json_name) if json_name != name => {
+            problems.push(UriProblem::NameMismatch(json_name.to_owned()))
+        }
+        Some(_) => {}
+        None => problems.push(UriProblem::MissingField("name")),
+    }
+
+    if let Some(json_symbol) = json.get("symbol").and_then(Value::as_str) {
+        if json_symbol != symbol {
+            problems.push(UriProblem::SymbolMismatch(json_symbol.to_owned()));
+        }
+    }
+
+    let image = json.get("image").and_then(Value::as_str);
+    if image.is_none_or(str::is_empty) {
+        problems.push(UriProblem::MissingField("image"));
+    }
+
+    match json.get("attributes").and_then(Value::as_array) {
+        Some(attributes) => {
+            for (index, attribute) in attributes.iter().enumerate() {
+                if attribute.get("trait_type").is_none() || attribute.get("value").is_none() {
+                    problems.push(UriProblem::WrongAttribute(index));
+                }
+            }
+        }
+        None => problems.push(UriProblem::MissingField("attributes")),
+    }
+
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn valid_json() {
+        let json = json!({
+            "name": "Bob the cat",
+            "image": "https://arweave.net/image.png",
+            "attributes": [{ "trait_type": "Color", "value": "Red" }],
+        });
+        assert!(check(&json, "Bob the cat", "CHILL").is_empty());
+
+        let json = json!({
+            "name": "Bob the cat",
+            "symbol": "CHILL",
+            "image": "https://arweave.net/image.png",
+            "attributes": [],
+        });
+        assert!(check(&json, "Bob the cat", "CHILL").is_empty());
+    }
+
+    #[test]
+    fn mismatches() {
+        let json = json!({
+            "name": "Tom the cat",
+            "symbol": "CAT",
+            "image": "https://arweave.net/image.png",
+            "attributes": [],
+        });
+
+        let problems = check(&json, "Bob the cat", "CHILL");
+        assert_eq!(
+            problems,
+            vec![
+                UriProblem::NameMismatch("Tom the cat".to_owned()),
+                UriProblem::SymbolMismatch("CAT".to_owned()),
+            ]
+        );
+        assert!(problems.iter().all(|problem| !problem.is_fatal()));
+    }
+
+    #[test]
+    fn missing_fields() {
+        let json = json!({
+            "image": "",
+            "attributes": [{ "trait_type": "Color" }],
+        });
+        assert_eq!(
+            check(&json, "Bob the cat", "CHILL"),
+            vec![
+                UriProblem::MissingField("name"),
+                UriProblem::MissingField("image"),
+                UriProblem::WrongAttribute(0),
+            ]
+        );
+
+        let problems = check(&json!([]), "Bob the cat", "CHILL");
+        assert_eq!(
+            problems,
+            vec![
+                UriProblem::MissingField("name"),
+                UriProblem::MissingField("image"),
+                UriProblem::MissingField("attributes"),
+            ]
+        );
+        assert!(problems.iter().all(UriProblem::is_fatal));
+    }
+}
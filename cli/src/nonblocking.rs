@@ -0,0 +1,76 @@
+//! Async client for services running on tokio. The RPC client of this Solana
+//! version is blocking, so every call runs a blocking [`Client`] on the thread
+//! pool of tokio, and the async workers keep serving other requests meanwhile
+
+use crate::{
+    client::{Client, RpcBudget},
+    error::Result,
+};
+use anchor_client::solana_sdk::{clock::Clock, instruction::Instruction, pubkey::Pubkey};
+use chill_nft::state::Config;
+use chill_staking::state::StakingInfo;
+
+/// Calls are independent, so the client is cheap to clone and concurrent calls
+/// don't wait for each other. RPC budget is accounted per call
+#[derive(Clone)]
+pub struct AsyncClient {
+    url: String,
+    rpc_budget: RpcBudget,
+}
+
+impl AsyncClient {
+    pub fn init(url: &str, rpc_budget: RpcBudget) -> Self {
+        Self {
+            url: url.to_owned(),
+            rpc_budget,
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Runs the calls of the blocking client on a separate thread. Signers are
+    /// not `Send`, so they are created by the closure
+    pub async fn run<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Client) -> Result<T> + Send + 'static,
+    {
+        let url = self.url.clone();
+        let rpc_budget = self.rpc_budget;
+        tokio::task::spawn_blocking(move || f(&Client::init(&url, rpc_budget)))
+            .await
+            .map_err(anyhow::Error::new)?
+    }
+
+    pub async fn balance(&self, address: Pubkey) -> Result<u64> {
+        self.run(move |client| client.balance(address)).await
+    }
+
+    pub async fn slot(&self) -> Result<u64> {
+        self.run(|client| client.slot()).await
+    }
+
+    pub async fn clock(&self) -> Result<Clock> {
+        self.run(|client| client.clock()).await
+    }
+
+    pub async fn config(&self, mint: Pubkey, program_id: Pubkey) -> Result<Config> {
+        self.run(move |client| client.config(mint, program_id))
+            .await
+    }
+
+    pub async fn staking_info(&self, address: Pubkey) -> Result<StakingInfo> {
+        self.run(move |client| client.staking_info(address)).await
+    }
+
+    pub async fn export_transaction(
+        &self,
+        instructions: Vec<Instruction>,
+        payer: Pubkey,
+    ) -> Result<String> {
+        self.run(move |client| client.export_transaction(&instructions, payer))
+            .await
+    }
+}
@@ -0,0 +1,140 @@
+//! Output of the commands. Results are printed as colored text, or collected
+//! into a single JSON object printed when the command finishes with
+//! `--output json`, so scripts don't need to parse the text
+
+use colored::ColoredString;
+use serde_json::{Map, Value};
+use std::{cell::RefCell, fmt::Display};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Display,
+    Json,
+}
+
+pub struct Output {
+    format: OutputFormat,
+    values: RefCell<Map<String, Value>>,
+}
+
+impl Output {
+    pub fn new(format: OutputFormat) -> Self {
+        // Errors and messages become plain text in JSON
+        if format == OutputFormat::Json {
+            colored::control::set_override(false);
+        }
+
+        Self {
+            format,
+            values: RefCell::new(Map::new()),
+        }
+    }
+
+    pub fn is_json(&self) -> bool {
+        self.format == OutputFormat::Json
+    }
+
+    /// Prints the labeled value. In JSON it's stored by the key derived from
+    /// the label, e.g. "StakingInfo:" is stored as "staking_info"
+    pub fn value(&self, label: ColoredString, value: impl Into<Value>) {
+        self.value_with_unit(label, value, "");
+    }
+
+    pub fn value_with_unit(&self, label: ColoredString, value: impl Into<Value>, unit: &str) {
+        let value = value.into();
+        if self.is_json() {
+            self.record(&key(&label), value);
+            return;
+        }
+
+        let value = match value {
+            Value::String(value) => value,
+            value => value.to_string(),
+        };
+
+        if unit.is_empty() {
+            println!("{} {}", label, value);
+        } else {
+            println!("{} {} {}", label, value, unit);
+        }
+    }
+
+    /// Stores the value by the key in JSON, nothing is printed as text. A
+    /// repeated key collects its values into an array
+    pub fn record(&self, key: &str, value: Value) {
+        let mut values = self.values.borrow_mut();
+        match values.get_mut(key) {
+            Some(Value::Array(array)) => array.push(value),
+            Some(existing) => *existing = Value::Array(vec![existing.take(), value]),
+            None => {
+                values.insert(key.to_owned(), value);
+            }
+        }
+    }
+
+    /// Appends the value to the array stored by the key in JSON, so the key
+    /// is an array even for a single value
+    pub fn push(&self, key: &str, value: Value) {
+        let mut values = self.values.borrow_mut();
+        match values
+            .entry(key)
+            .or_insert_with(|| Value::Array(Vec::new()))
+        {
+            Value::Array(array) => array.push(value),
+            existing => *existing = Value::Array(vec![existing.take(), value]),
+        }
+    }
+
+    /// Notes, warnings and errors of single items are collected into the
+    /// "messages" array in JSON
+    pub fn message(&self, message: impl Display) {
+        if self.is_json() {
+            self.push("messages", Value::String(message.to_string()));
+        } else {
+            println!("{}", message);
+        }
+    }
+
+    /// Prints the JSON object of the collected values, the error is added to
+    /// the values stored before it
+    pub fn finish(&self, error: Option<&dyn Display>) {
+        if !self.is_json() {
+            if let Some(error) = error {
+                println!("{}", error);
+            }
+
+            return;
+        }
+
+        if let Some(error) = error {
+            let error = error.to_string();
+            let error = error.strip_prefix("error: ").unwrap_or(&error);
+            self.record("error", Value::String(error.to_owned()));
+        }
+
+        let values = Value::Object(self.values.borrow().clone());
+        println!("{}", serde_json::to_string_pretty(&values).unwrap());
+    }
+}
+
+fn key(label: &str) -> String {
+    let label = label.trim().trim_end_matches(':');
+    let mut key = String::with_capacity(label.len());
+    let mut previous = None;
+
+    for c in label.chars() {
+        if c.is_alphanumeric() {
+            // Splits camel case words, "StakingInfo" becomes "staking_info"
+            if c.is_uppercase() && previous.is_some_and(char::is_lowercase) {
+                key.push('_');
+            }
+            key.extend(c.to_lowercase());
+        } else if !key.ends_with('_') {
+            key.push('_');
+        }
+
+        previous = Some(c);
+    }
+
+    key.trim_matches('_').to_owned()
+}
@@ -0,0 +1,504 @@
+//! Minimal QR code encoder (byte mode, error correction level M) used to
+//! print deposit addresses in a terminal
+
+const MAX_VERSION: usize = 10;
+
+// Indexed by version, level M only
+const ECC_CODEWORDS_PER_BLOCK: [usize; MAX_VERSION + 1] =
+    [0, 10, 16, 26, 18, 24, 16, 18, 22, 22, 26];
+const NUM_ERROR_CORRECTION_BLOCKS: [usize; MAX_VERSION + 1] = [0, 1, 1, 1, 2, 2, 4, 4, 4, 5, 5];
+
+// Format bits of the error correction level M
+const ECC_LEVEL_M_BITS: u32 = 0;
+
+pub struct QrCode {
+    size: usize,
+    modules: Vec<Vec<bool>>,
+    is_function: Vec<Vec<bool>>,
+}
+
+impl QrCode {
+    /// Returns `None` if the data does not fit into the largest supported version
+    pub fn encode(data: &[u8]) -> Option<Self> {
+        let version = (1..=MAX_VERSION).find(|&version| {
+            let capacity_bits = num_data_codewords(version) * 8;
+            4 + char_count_bits(version) + data.len() * 8 <= capacity_bits
+        })?;
+
+        let mut bits = BitBuffer::default();
+        bits.append(0b0100, 4);
+        bits.append(data.len() as u32, char_count_bits(version));
+        data.iter().for_each(|byte| bits.append(*byte as u32, 8));
+
+        let capacity_bits = num_data_codewords(version) * 8;
+        let terminator_len = std::cmp::min(4, capacity_bits - bits.len());
+        bits.append(0, terminator_len);
+        bits.append(0, (8 - bits.len() % 8) % 8);
+
+        let mut codewords = bits.into_bytes();
+        for pad in [0xEC, 0x11].iter().cycle() {
+            if codewords.len() >= num_data_codewords(version) {
+                break;
+            }
+            codewords.push(*pad);
+        }
+
+        let size = version * 4 + 17;
+        let mut qr = QrCode {
+            size,
+            modules: vec![vec![false; size]; size],
+            is_function: vec![vec![false; size]; size],
+        };
+
+        qr.draw_function_patterns(version);
+        qr.draw_codewords(&add_ecc_and_interleave(&codewords, version));
+
+        let mask = (0..8)
+            .min_by_key(|&mask| {
+                qr.apply_mask(mask);
+                qr.draw_format_bits(mask);
+                let penalty = qr.penalty_score();
+                qr.apply_mask(mask);
+                penalty
+            })
+            .unwrap();
+
+        qr.apply_mask(mask);
+        qr.draw_format_bits(mask);
+
+        Some(qr)
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        self.modules[y][x]
+    }
+
+    /// Renders the code with half block characters. Light modules are drawn
+    /// with blocks, so the code is readable on terminals with dark background
+    pub fn to_terminal_string(&self) -> String {
+        const QUIET_ZONE: isize = 2;
+
+        let size = self.size as isize;
+        let is_light = |x: isize, y: isize| {
+            x < 0 || y < 0 || x >= size || y >= size || !self.is_dark(x as usize, y as usize)
+        };
+
+        let mut result = String::new();
+        for y in (-QUIET_ZONE..size + QUIET_ZONE).step_by(2) {
+            for x in -QUIET_ZONE..size + QUIET_ZONE {
+                let symbol = match (is_light(x, y), is_light(x, y + 1)) {
+                    (true, true) => '█',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (false, false) => ' ',
+                };
+                result.push(symbol);
+            }
+            result.push('\n');
+        }
+
+        result
+    }
+
+    fn set_function_module(&mut self, x: usize, y: usize, is_dark: bool) {
+        self.modules[y][x] = is_dark;
+        self.is_function[y][x] = true;
+    }
+
+    fn draw_function_patterns(&mut self, version: usize) {
+        let size = self.size;
+
+        for i in 0..size {
+            self.set_function_module(6, i, i % 2 == 0);
+            self.set_function_module(i, 6, i % 2 == 0);
+        }
+
+        self.draw_finder_pattern(3, 3);
+        self.draw_finder_pattern(size - 4, 3);
+        self.draw_finder_pattern(3, size - 4);
+
+        let positions = alignment_pattern_positions(version);
+        let last = positions.len().saturating_sub(1);
+        for (i, y) in positions.iter().enumerate() {
+            for (j, x) in positions.iter().enumerate() {
+                let is_finder_corner = (i == 0 && (j == 0 || j == last)) || (i == last && j == 0);
+                if !is_finder_corner {
+                    self.draw_alignment_pattern(*x, *y);
+                }
+            }
+        }
+
+        // Reserves the format area, real bits are drawn after masking
+        self.draw_format_bits(0);
+        self.draw_version(version);
+    }
+
+    fn draw_finder_pattern(&mut self, center_x: usize, center_y: usize) {
+        for dy in -4isize..=4 {
+            for dx in -4isize..=4 {
+                let x = center_x as isize + dx;
+                let y = center_y as isize + dy;
+                if x < 0 || y < 0 || x >= self.size as isize || y >= self.size as isize {
+                    continue;
+                }
+
+                let distance = std::cmp::max(dx.abs(), dy.abs());
+                self.set_function_module(x as usize, y as usize, distance != 2 && distance != 4);
+            }
+        }
+    }
+
+    fn draw_alignment_pattern(&mut self, center_x: usize, center_y: usize) {
+        for dy in -2isize..=2 {
+            for dx in -2isize..=2 {
+                let x = (center_x as isize + dx) as usize;
+                let y = (center_y as isize + dy) as usize;
+                self.set_function_module(x, y, std::cmp::max(dx.abs(), dy.abs()) != 1);
+            }
+        }
+    }
+
+    fn draw_format_bits(&mut self, mask: u32) {
+        let bits = format_bits(mask);
+        let bit = |i: usize| (bits >> i) & 1 != 0;
+        let size = self.size;
+
+        for i in 0..=5 {
+            self.set_function_module(8, i, bit(i));
+        }
+        self.set_function_module(8, 7, bit(6));
+        self.set_function_module(8, 8, bit(7));
+        self.set_function_module(7, 8, bit(8));
+        for i in 9..15 {
+            self.set_function_module(14 - i, 8, bit(i));
+        }
+
+        for i in 0..8 {
+            self.set_function_module(size - 1 - i, 8, bit(i));
+        }
+        for i in 8..15 {
+            self.set_function_module(8, size - 15 + i, bit(i));
+        }
+        self.set_function_module(8, size - 8, true);
+    }
+
+    fn draw_version(&mut self, version: usize) {
+        if version < 7 {
+            return;
+        }
+
+        let mut remainder = version as u32;
+        for _ in 0..12 {
+            remainder = (remainder << 1) ^ ((remainder >> 11) * 0x1F25);
+        }
+        let bits = (version as u32) << 12 | remainder;
+
+        for i in 0..18 {
+            let bit = (bits >> i) & 1 != 0;
+            let a = self.size - 11 + i % 3;
+            let b = i / 3;
+            self.set_function_module(a, b, bit);
+            self.set_function_module(b, a, bit);
+        }
+    }
+
+    fn draw_codewords(&mut self, codewords: &[u8]) {
+        let size = self.size;
+        let total_bits = codewords.len() * 8;
+        let mut i = 0;
+
+        let mut right = size - 1;
+        while right >= 1 {
+            if right == 6 {
+                right = 5;
+            }
+
+            for vertical in 0..size {
+                for j in 0..2 {
+                    let x = right - j;
+                    let upward = (right + 1) & 2 == 0;
+                    let y = if upward {
+                        size - 1 - vertical
+                    } else {
+                        vertical
+                    };
+
+                    if !self.is_function[y][x] && i < total_bits {
+                        self.modules[y][x] = (codewords[i >> 3] >> (7 - (i & 7))) & 1 != 0;
+                        i += 1;
+                    }
+                }
+            }
+
+            if right < 2 {
+                break;
+            }
+            right -= 2;
+        }
+    }
+
+    fn apply_mask(&mut self, mask: u32) {
+        for y in 0..self.size {
+            for x in 0..self.size {
+                let invert = match mask {
+                    0 => (x + y) % 2 == 0,
+                    1 => y % 2 == 0,
+                    2 => x % 3 == 0,
+                    3 => (x + y) % 3 == 0,
+                    4 => (x / 3 + y / 2) % 2 == 0,
+                    5 => x * y % 2 + x * y % 3 == 0,
+                    6 => (x * y % 2 + x * y % 3) % 2 == 0,
+                    _ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+                };
+
+                if invert && !self.is_function[y][x] {
+                    self.modules[y][x] = !self.modules[y][x];
+                }
+            }
+        }
+    }
+
+    fn penalty_score(&self) -> usize {
+        const FINDER_LIKE: [bool; 11] = [
+            true, false, true, true, true, false, true, false, false, false, false,
+        ];
+
+        let size = self.size;
+        let lines = (0..size)
+            .map(|y| (0..size).map(|x| self.modules[y][x]).collect::<Vec<_>>())
+            .chain((0..size).map(|x| (0..size).map(|y| self.modules[y][x]).collect()));
+
+        let mut penalty = 0;
+        for line in lines {
+            // Runs of five or more modules of the same color
+            let mut run_length = 1;
+            for i in 1..=size {
+                if i < size && line[i] == line[i - 1] {
+                    run_length += 1;
+                    continue;
+                }
+                if run_length >= 5 {
+                    penalty += run_length - 2;
+                }
+                run_length = 1;
+            }
+
+            // Patterns which look like finder patterns
+            for window in line.windows(FINDER_LIKE.len()) {
+                let is_forward = window.iter().eq(FINDER_LIKE.iter());
+                let is_backward = window.iter().eq(FINDER_LIKE.iter().rev());
+                if is_forward || is_backward {
+                    penalty += 40;
+                }
+            }
+        }
+
+        // 2x2 blocks of the same color
+        for y in 1..size {
+            for x in 1..size {
+                let color = self.modules[y][x];
+                if color == self.modules[y - 1][x]
+                    && color == self.modules[y][x - 1]
+                    && color == self.modules[y - 1][x - 1]
+                {
+                    penalty += 3;
+                }
+            }
+        }
+
+        // Balance of dark and light modules
+        let total = size * size;
+        let dark = self.modules.iter().flatten().filter(|m| **m).count();
+        let deviation = (dark * 20).abs_diff(total * 10);
+        let k = deviation.div_ceil(total).saturating_sub(1);
+        penalty + k * 10
+    }
+}
+
+#[derive(Default)]
+struct BitBuffer {
+    bits: Vec<bool>,
+}
+
+impl BitBuffer {
+    fn len(&self) -> usize {
+        self.bits.len()
+    }
+
+    fn append(&mut self, value: u32, len: usize) {
+        for i in (0..len).rev() {
+            self.bits.push((value >> i) & 1 != 0);
+        }
+    }
+
+    fn into_bytes(self) -> Vec<u8> {
+        self.bits
+            .chunks(8)
+            .map(|chunk| chunk.iter().fold(0, |byte, bit| byte << 1 | *bit as u8))
+            .collect()
+    }
+}
+
+fn char_count_bits(version: usize) -> usize {
+    if version <= 9 {
+        8
+    } else {
+        16
+    }
+}
+
+fn num_raw_data_modules(version: usize) -> usize {
+    let mut result = (16 * version + 128) * version + 64;
+    if version >= 2 {
+        let num_align = version / 7 + 2;
+        result -= (25 * num_align - 10) * num_align - 55;
+        if version >= 7 {
+            result -= 36;
+        }
+    }
+    result
+}
+
+fn num_data_codewords(version: usize) -> usize {
+    num_raw_data_modules(version) / 8
+        - ECC_CODEWORDS_PER_BLOCK[version] * NUM_ERROR_CORRECTION_BLOCKS[version]
+}
+
+fn alignment_pattern_positions(version: usize) -> Vec<usize> {
+    if version == 1 {
+        return Vec::new();
+    }
+
+    let num_align = version / 7 + 2;
+    let step = (version * 4 + num_align * 2 + 1) / (num_align * 2 - 2) * 2;
+    let last = version * 4 + 17 - 7;
+
+    let mut result = (0..num_align - 1)
+        .map(|i| last - i * step)
+        .collect::<Vec<_>>();
+    result.push(6);
+    result.reverse();
+    result
+}
+
+fn format_bits(mask: u32) -> u32 {
+    let data = ECC_LEVEL_M_BITS << 3 | mask;
+    let mut remainder = data;
+    for _ in 0..10 {
+        remainder = (remainder << 1) ^ ((remainder >> 9) * 0x537);
+    }
+    (data << 10 | remainder) ^ 0x5412
+}
+
+fn add_ecc_and_interleave(data: &[u8], version: usize) -> Vec<u8> {
+    let num_blocks = NUM_ERROR_CORRECTION_BLOCKS[version];
+    let block_ecc_len = ECC_CODEWORDS_PER_BLOCK[version];
+    let raw_codewords = num_raw_data_modules(version) / 8;
+    let num_short_blocks = num_blocks - raw_codewords % num_blocks;
+    let short_block_len = raw_codewords / num_blocks;
+
+    let divisor = reed_solomon_divisor(block_ecc_len);
+    let mut blocks = Vec::with_capacity(num_blocks);
+    let mut offset = 0;
+
+    for i in 0..num_blocks {
+        let data_len = short_block_len - block_ecc_len + usize::from(i >= num_short_blocks);
+        let mut block = data[offset..offset + data_len].to_vec();
+        offset += data_len;
+
+        let ecc = reed_solomon_remainder(&block, &divisor);
+        if i < num_short_blocks {
+            block.push(0);
+        }
+        block.extend(ecc);
+        blocks.push(block);
+    }
+
+    let mut result = Vec::with_capacity(raw_codewords);
+    for i in 0..=short_block_len {
+        for (j, block) in blocks.iter().enumerate() {
+            // Skips the padding byte of short blocks
+            if i != short_block_len - block_ecc_len || j >= num_short_blocks {
+                result.push(block[i]);
+            }
+        }
+    }
+
+    result
+}
+
+fn gf_multiply(x: u8, y: u8) -> u8 {
+    let mut z: u16 = 0;
+    for i in (0..8).rev() {
+        z = (z << 1) ^ ((z >> 7) * 0x11D);
+        z ^= ((y as u16 >> i) & 1) * x as u16;
+    }
+    z as u8
+}
+
+fn reed_solomon_divisor(degree: usize) -> Vec<u8> {
+    let mut result = vec![0; degree];
+    result[degree - 1] = 1;
+
+    let mut root = 1;
+    for _ in 0..degree {
+        for j in 0..degree {
+            result[j] = gf_multiply(result[j], root);
+            if j + 1 < degree {
+                result[j] ^= result[j + 1];
+            }
+        }
+        root = gf_multiply(root, 0x02);
+    }
+
+    result
+}
+
+fn reed_solomon_remainder(data: &[u8], divisor: &[u8]) -> Vec<u8> {
+    let mut result = vec![0; divisor.len()];
+    for byte in data {
+        let factor = byte ^ result.remove(0);
+        result.push(0);
+        for (r, d) in result.iter_mut().zip(divisor) {
+            *r ^= gf_multiply(*d, factor);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_correction_codewords() {
+        // "HELLO WORLD" encoded as version 1-M
+        let data = [
+            32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236, 17, 236, 17,
+        ];
+        let divisor = reed_solomon_divisor(10);
+        assert_eq!(
+            reed_solomon_remainder(&data, &divisor),
+            vec![196, 35, 39, 119, 235, 215, 231, 226, 93, 23]
+        );
+    }
+
+    #[test]
+    fn test_format_bits() {
+        assert_eq!(format_bits(0), 0b101010000010010);
+        assert_eq!(format_bits(5), 0b100000011001110);
+    }
+
+    #[test]
+    fn test_version_selection() {
+        let address = "solana:7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU?spl-token=EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+        let qr = QrCode::encode(address.as_bytes()).unwrap();
+        assert_eq!(qr.size(), 6 * 4 + 17);
+
+        assert!(QrCode::encode(&[0; 214]).is_none());
+    }
+}
@@ -192,6 +192,10 @@ class CliTest(unittest.TestCase):
         _, code = runCli(args)
         self.assertEqual(code, 0)
 
+        output, code = runCli('list-configs')
+        self.assertEqual(code, 0)
+        self.assertTrue(str(default_mint_pubkey()) in output)
+
     def test_mint_nft(self):
         self.test_initialization()
         nft_types = ("character", "pet", "emote", "tileset", "item")
@@ -215,6 +219,40 @@ class CliTest(unittest.TestCase):
             default_authority(), mint_address), 0)
         self.assertEqual(client.token_amount(recipient, mint_address), 1)
 
+    def test_admin(self):
+        self.test_initialization()
+        uri = "https://arweave.org/" + str(Keypair.generate().public_key)
+        output, code = runCli(f"mint-nft pet NAME {uri}")
+        self.assertEqual(code, 0)
+        mint_address = output.splitlines()[0].split(': ')[1]
+
+        audit_log = Path.cwd() / 'admin-audit.log'
+        command = f"admin nft set-min-game-version {mint_address} 5"
+
+        output, code = runCli(f"{command} --export")
+        self.assertEqual(code, 0)
+        self.assertIn('Message: ', output)
+        self.assertNotIn('Signature: ', output)
+
+        output, code = runCli(f"{command} --yes")
+        self.assertEqual(code, 0)
+        signature = output.splitlines()[-1].split(': ')[1]
+
+        records = audit_log.read_text().splitlines()
+        self.assertTrue(records[-2].endswith(' exported'))
+        self.assertTrue(records[-1].endswith(f' {signature}'))
+
+    def test_completions(self):
+        for shell in ('bash', 'zsh', 'fish'):
+            output, code = runCli(f'completions {shell}')
+            self.assertEqual(code, 0)
+            self.assertIn('staking', output)
+
+        output, code = runCli('--generate-man')
+        self.assertEqual(code, 0)
+        self.assertTrue(output.startswith('.TH CHILL\\-CLI 1'))
+        self.assertIn('.SS "staking initialize"', output)
+
     def test_withdraw_ft(self):
         balance = 10000
         runCli(f'mint {balance}')
@@ -239,6 +277,84 @@ class CliTest(unittest.TestCase):
         self.assertEqual(client.token_amount(wallet, mint), 0)
         self.assertEqual(client.token_amount(authority, mint), balance)
 
+    def test_schedule_withdrawal(self):
+        account = Keypair.generate().public_key
+        recipient = Keypair.generate().public_key
+
+        _, code = runCli(f"create-wallet --account {account}")
+        self.assertEqual(code, 0)
+
+        _, code = runCli(
+            f"schedule-withdrawal 0.001 --interval 3600 --account {account} --recipient {recipient}")
+        self.assertEqual(code, 0)
+
+        _, code = runCli(
+            f"execute-withdrawal --account {account} --recipient {recipient}")
+        self.assertNotEqual(code, 0)
+
+        _, code = runCli(
+            f"cancel-withdrawal --account {account} --recipient {recipient}")
+        self.assertEqual(code, 0)
+
+    def test_create_wallets(self):
+        users = [Keypair.generate().public_key for _ in range(20)]
+        users_file = PROJECT_DIR / f'{uuid.uuid4()}.users'
+        users_file.write_text('\n'.join(str(user) for user in users))
+
+        def count(output: str, name: str) -> int:
+            line = next(l for l in output.splitlines() if l.startswith(name))
+            return int(line.split(': ')[1])
+
+        try:
+            output, code = runCli(f"create-wallets {users_file}")
+            self.assertEqual(code, 0)
+            self.assertEqual(count(output, 'Existing wallets'), 0)
+            self.assertEqual(count(output, 'Created wallets'), len(users))
+
+            output, code = runCli(f"create-wallets {users_file}")
+            self.assertEqual(code, 0)
+            self.assertEqual(count(output, 'Existing wallets'), len(users))
+            self.assertEqual(count(output, 'Created wallets'), 0)
+        finally:
+            os.remove(users_file)
+
+    def test_rpc_budget(self):
+        users = [Keypair.generate().public_key for _ in range(3)]
+        users_file = PROJECT_DIR / f'{uuid.uuid4()}.users'
+        failed_file = PROJECT_DIR / f'{users_file.name}.failed'
+        users_file.write_text('\n'.join(str(user) for user in users))
+
+        try:
+            output, code = runCli(
+                f"create-wallets {users_file} --rpc-budget 1 --rpc-budget-abort")
+            self.assertEqual(code, 0)
+            self.assertIn('Created wallets: 0', output)
+            self.assertIn('RPC calls: 1', output)
+            self.assertEqual(
+                set(failed_file.read_text().split()),
+                set(str(user) for user in users))
+        finally:
+            os.remove(users_file)
+            if failed_file.exists():
+                os.remove(failed_file)
+
+    def test_deposit_address(self):
+        runCli('mint 1')
+        account = Keypair.generate().public_key
+
+        output, code = runCli(f"create-wallet --account {account}")
+        self.assertEqual(code, 0)
+        wallet = output.splitlines()[0].split(': ')[1]
+
+        output, code = runCli(
+            f"wallet deposit-address {account} --mint-address {default_mint_pubkey()} --create-token-account --qr")
+        self.assertEqual(code, 0)
+        self.assertEqual(output.splitlines()[0].split(': ')[1], wallet)
+
+        client = Client()
+        self.assertTrue(client.token_account_exists(
+            PublicKey(wallet), default_mint_pubkey()))
+
 
 if __name__ == '__main__':
     unittest.main()
@@ -0,0 +1,625 @@
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use chill_drop::{event as drop, state::DropKind};
+use chill_governance::{event as governance, state::ProposalStatus};
+use chill_nft::{event as nft, state::NftAttributes};
+use chill_staking::event as staking;
+use chill_vesting::event as vesting;
+use chill_wallet::event as wallet;
+use serde_json::{json, Value};
+
+pub enum Event {
+    MintNft(nft::MintNft),
+    CreateCollection(nft::CreateCollection),
+    UpdateNft(nft::UpdateNft),
+    UpdateMinGameVersion(nft::UpdateMinGameVersion),
+    ReclaimMetadata(nft::ReclaimMetadata),
+    BurnNft(nft::BurnNft),
+    UtilizeNft(nft::UtilizeNft),
+    FinalizeNft(nft::FinalizeNft),
+    ListNft(nft::ListNft),
+    DelistNft(nft::DelistNft),
+    BuyNft(nft::BuyNft),
+    BurnChillForAction(nft::BurnChillForAction),
+    UpgradeNftAttributes(nft::UpgradeNftAttributes),
+    MigrateNftMetadata(nft::MigrateNftMetadata),
+    SetAdminConfig(nft::SetAdminConfig),
+    UpdateFees(nft::UpdateFees),
+    UpdateRecipients(nft::UpdateRecipients),
+    RegisterNftType(nft::RegisterNftType),
+    ProposeConfigPrimaryWallet(nft::ProposeConfigPrimaryWallet),
+    AcceptConfigPrimaryWallet(nft::AcceptConfigPrimaryWallet),
+    AddRewardTokens(staking::AddRewardTokens),
+    Stake(staking::Stake),
+    Claim(staking::Claim),
+    ClaimFee(staking::ClaimFee),
+    ClaimAndRestake(staking::ClaimAndRestake),
+    TransferRewardToPendingAmount(staking::TransferRewardToPendingAmount),
+    Boost(staking::Boost),
+    PaidBoost(staking::PaidBoost),
+    Cancel(staking::Cancel),
+    Unstake(staking::Unstake),
+    StartRedistribution(staking::StartRedistribution),
+    RedistributeUnspentReward(staking::RedistributeUnspentReward),
+    RegisterReferrer(staking::RegisterReferrer),
+    ClaimReferralReward(staking::ClaimReferralReward),
+    SetDelegate(staking::SetDelegate),
+    RevokeDelegate(staking::RevokeDelegate),
+    ProposeStakingPrimaryWallet(staking::ProposeStakingPrimaryWallet),
+    AcceptStakingPrimaryWallet(staking::AcceptStakingPrimaryWallet),
+    Pause(staking::Pause),
+    Resume(staking::Resume),
+    CreateWallet(wallet::CreateWallet),
+    MigrateWallet(wallet::MigrateWallet),
+    DepositLamports(wallet::DepositLamports),
+    DepositFt(wallet::DepositFt),
+    SetSpendingLimits(wallet::SetSpendingLimits),
+    CreateSessionKey(wallet::CreateSessionKey),
+    RevokeSessionKey(wallet::RevokeSessionKey),
+    WrapSol(wallet::WrapSol),
+    UnwrapSol(wallet::UnwrapSol),
+    StakeFromWallet(wallet::StakeFromWallet),
+    MintNftFromWallet(wallet::MintNftFromWallet),
+    FreezeWallet(wallet::FreezeWallet),
+    UnfreezeWallet(wallet::UnfreezeWallet),
+    SetWithdrawalFee(wallet::SetWithdrawalFee),
+    WithdrawalFee(wallet::WithdrawalFee),
+    WithdrawLamports(wallet::WithdrawLamports),
+    WithdrawFt(wallet::WithdrawFt),
+    WithdrawNft(wallet::WithdrawNft),
+    ScheduleWithdrawal(wallet::ScheduleWithdrawal),
+    ExecuteScheduledWithdrawal(wallet::ExecuteScheduledWithdrawal),
+    CancelScheduledWithdrawal(wallet::CancelScheduledWithdrawal),
+    LendNft(wallet::LendNft),
+    RentNft(wallet::RentNft),
+    ReclaimNft(wallet::ReclaimNft),
+    InitializeDistributor(drop::InitializeDistributor),
+    ClaimDrop(drop::ClaimDrop),
+    WithdrawUnclaimed(drop::WithdrawUnclaimed),
+    InitializeVesting(vesting::InitializeVesting),
+    ClaimVested(vesting::ClaimVested),
+    RevokeVesting(vesting::RevokeVesting),
+    InitializeGovernance(governance::InitializeGovernance),
+    UpdateGovernance(governance::UpdateGovernance),
+    CreateProposal(governance::CreateProposal),
+    Vote(governance::Vote),
+    FinalizeProposal(governance::FinalizeProposal),
+}
+
+pub fn nft_attributes_to_json(attributes: &NftAttributes) -> Value {
+    json!({
+        "level": attributes.level,
+        "rarity": attributes.rarity,
+        "xp": attributes.xp.to_string(),
+        "custom": attributes.custom.iter().map(ToString::to_string).collect::<Vec<_>>(),
+    })
+}
+
+fn try_decode<T: AnchorDeserialize + Discriminator>(data: &[u8]) -> Option<T> {
+    let (discriminator, mut event) = (data.get(..8)?, data.get(8..)?);
+    if discriminator != T::discriminator() {
+        return None;
+    }
+
+    T::deserialize(&mut event).ok()
+}
+
+impl Event {
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        try_decode(data)
+            .map(Event::MintNft)
+            .or_else(|| try_decode(data).map(Event::CreateCollection))
+            .or_else(|| try_decode(data).map(Event::UpdateNft))
+            .or_else(|| try_decode(data).map(Event::UpdateMinGameVersion))
+            .or_else(|| try_decode(data).map(Event::ReclaimMetadata))
+            .or_else(|| try_decode(data).map(Event::BurnNft))
+            .or_else(|| try_decode(data).map(Event::UtilizeNft))
+            .or_else(|| try_decode(data).map(Event::FinalizeNft))
+            .or_else(|| try_decode(data).map(Event::ListNft))
+            .or_else(|| try_decode(data).map(Event::DelistNft))
+            .or_else(|| try_decode(data).map(Event::BuyNft))
+            .or_else(|| try_decode(data).map(Event::BurnChillForAction))
+            .or_else(|| try_decode(data).map(Event::UpgradeNftAttributes))
+            .or_else(|| try_decode(data).map(Event::MigrateNftMetadata))
+            .or_else(|| try_decode(data).map(Event::SetAdminConfig))
+            .or_else(|| try_decode(data).map(Event::UpdateFees))
+            .or_else(|| try_decode(data).map(Event::UpdateRecipients))
+            .or_else(|| try_decode(data).map(Event::RegisterNftType))
+            .or_else(|| try_decode(data).map(Event::ProposeConfigPrimaryWallet))
+            .or_else(|| try_decode(data).map(Event::AcceptConfigPrimaryWallet))
+            .or_else(|| try_decode(data).map(Event::AddRewardTokens))
+            .or_else(|| try_decode(data).map(Event::Stake))
+            .or_else(|| try_decode(data).map(Event::Claim))
+            .or_else(|| try_decode(data).map(Event::ClaimFee))
+            .or_else(|| try_decode(data).map(Event::ClaimAndRestake))
+            .or_else(|| try_decode(data).map(Event::TransferRewardToPendingAmount))
+            .or_else(|| try_decode(data).map(Event::Boost))
+            .or_else(|| try_decode(data).map(Event::PaidBoost))
+            .or_else(|| try_decode(data).map(Event::Cancel))
+            .or_else(|| try_decode(data).map(Event::Unstake))
+            .or_else(|| try_decode(data).map(Event::StartRedistribution))
+            .or_else(|| try_decode(data).map(Event::RedistributeUnspentReward))
+            .or_else(|| try_decode(data).map(Event::RegisterReferrer))
+            .or_else(|| try_decode(data).map(Event::ClaimReferralReward))
+            .or_else(|| try_decode(data).map(Event::SetDelegate))
+            .or_else(|| try_decode(data).map(Event::RevokeDelegate))
+            .or_else(|| try_decode(data).map(Event::ProposeStakingPrimaryWallet))
+            .or_else(|| try_decode(data).map(Event::AcceptStakingPrimaryWallet))
+            .or_else(|| try_decode(data).map(Event::Pause))
+            .or_else(|| try_decode(data).map(Event::Resume))
+            .or_else(|| try_decode(data).map(Event::CreateWallet))
+            .or_else(|| try_decode(data).map(Event::MigrateWallet))
+            .or_else(|| try_decode(data).map(Event::DepositLamports))
+            .or_else(|| try_decode(data).map(Event::DepositFt))
+            .or_else(|| try_decode(data).map(Event::SetSpendingLimits))
+            .or_else(|| try_decode(data).map(Event::CreateSessionKey))
+            .or_else(|| try_decode(data).map(Event::RevokeSessionKey))
+            .or_else(|| try_decode(data).map(Event::WrapSol))
+            .or_else(|| try_decode(data).map(Event::UnwrapSol))
+            .or_else(|| try_decode(data).map(Event::StakeFromWallet))
+            .or_else(|| try_decode(data).map(Event::MintNftFromWallet))
+            .or_else(|| try_decode(data).map(Event::FreezeWallet))
+            .or_else(|| try_decode(data).map(Event::UnfreezeWallet))
+            .or_else(|| try_decode(data).map(Event::SetWithdrawalFee))
+            .or_else(|| try_decode(data).map(Event::WithdrawalFee))
+            .or_else(|| try_decode(data).map(Event::WithdrawLamports))
+            .or_else(|| try_decode(data).map(Event::WithdrawFt))
+            .or_else(|| try_decode(data).map(Event::WithdrawNft))
+            .or_else(|| try_decode(data).map(Event::ScheduleWithdrawal))
+            .or_else(|| try_decode(data).map(Event::ExecuteScheduledWithdrawal))
+            .or_else(|| try_decode(data).map(Event::CancelScheduledWithdrawal))
+            .or_else(|| try_decode(data).map(Event::LendNft))
+            .or_else(|| try_decode(data).map(Event::RentNft))
+            .or_else(|| try_decode(data).map(Event::ReclaimNft))
+            .or_else(|| try_decode(data).map(Event::InitializeDistributor))
+            .or_else(|| try_decode(data).map(Event::ClaimDrop))
+            .or_else(|| try_decode(data).map(Event::WithdrawUnclaimed))
+            .or_else(|| try_decode(data).map(Event::InitializeVesting))
+            .or_else(|| try_decode(data).map(Event::ClaimVested))
+            .or_else(|| try_decode(data).map(Event::RevokeVesting))
+            .or_else(|| try_decode(data).map(Event::InitializeGovernance))
+            .or_else(|| try_decode(data).map(Event::UpdateGovernance))
+            .or_else(|| try_decode(data).map(Event::CreateProposal))
+            .or_else(|| try_decode(data).map(Event::Vote))
+            .or_else(|| try_decode(data).map(Event::FinalizeProposal))
+    }
+
+    pub fn to_json(&self) -> Value {
+        match self {
+            Event::MintNft(e) => json!({
+                "name": "MintNft",
+                "mint": e.mint.to_string(),
+                "nftType": format!("{:?}", e.nft_type).to_lowercase(),
+                "collection": e.collection.map(|collection| collection.to_string()),
+            }),
+            Event::CreateCollection(e) => json!({
+                "name": "CreateCollection",
+                "config": e.config.to_string(),
+                "mint": e.mint.to_string(),
+            }),
+            Event::UpdateNft(e) => json!({
+                "name": "UpdateNft",
+                "mint": e.mint.to_string(),
+            }),
+            Event::UpdateMinGameVersion(e) => json!({
+                "name": "UpdateMinGameVersion",
+                "mint": e.mint.to_string(),
+                "minGameVersion": e.min_game_version,
+            }),
+            Event::ReclaimMetadata(e) => json!({
+                "name": "ReclaimMetadata",
+                "mint": e.mint.to_string(),
+            }),
+            Event::BurnNft(e) => json!({
+                "name": "BurnNft",
+                "mint": e.mint.to_string(),
+                "owner": e.owner.to_string(),
+            }),
+            Event::UtilizeNft(e) => json!({
+                "name": "UtilizeNft",
+                "mint": e.mint.to_string(),
+                "owner": e.owner.to_string(),
+                "numberOfUses": e.number_of_uses.to_string(),
+                "remaining": e.remaining.to_string(),
+            }),
+            Event::FinalizeNft(e) => json!({
+                "name": "FinalizeNft",
+                "mint": e.mint.to_string(),
+            }),
+            Event::ListNft(e) => json!({
+                "name": "ListNft",
+                "config": e.config.to_string(),
+                "mint": e.mint.to_string(),
+                "price": e.price.to_string(),
+            }),
+            Event::DelistNft(e) => json!({
+                "name": "DelistNft",
+                "mint": e.mint.to_string(),
+            }),
+            Event::BuyNft(e) => json!({
+                "name": "BuyNft",
+                "mint": e.mint.to_string(),
+                "buyer": e.buyer.to_string(),
+                "price": e.price.to_string(),
+            }),
+            Event::BurnChillForAction(e) => json!({
+                "name": "BurnChillForAction",
+                "config": e.config.to_string(),
+                "owner": e.owner.to_string(),
+                "amount": e.amount.to_string(),
+                "actionId": e.action_id.to_string(),
+                "memo": e.memo,
+            }),
+            Event::UpgradeNftAttributes(e) => json!({
+                "name": "UpgradeNftAttributes",
+                "mint": e.mint.to_string(),
+                "attributes": nft_attributes_to_json(&e.attributes),
+            }),
+            Event::MigrateNftMetadata(e) => json!({
+                "name": "MigrateNftMetadata",
+                "mint": e.mint.to_string(),
+                "version": e.version,
+            }),
+            Event::SetAdminConfig(e) => json!({
+                "name": "SetAdminConfig",
+                "primaryWallet": e.primary_wallet.to_string(),
+                "threshold": e.threshold,
+                "signers": e.signers.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            }),
+            Event::UpdateFees(e) => json!({
+                "name": "UpdateFees",
+                "config": e.config.to_string(),
+                "fees": {
+                    "character": e.fees.character.to_string(),
+                    "pet": e.fees.pet.to_string(),
+                    "emote": e.fees.emote.to_string(),
+                    "tileset": e.fees.tileset.to_string(),
+                    "item": e.fees.item.to_string(),
+                    "world": e.fees.world.to_string(),
+                },
+            }),
+            Event::UpdateRecipients(e) => json!({
+                "name": "UpdateRecipients",
+                "config": e.config.to_string(),
+                "recipients": e
+                    .recipients
+                    .iter()
+                    .map(|r| json!({
+                        "address": r.address.to_string(),
+                        "mintShare": r.mint_share,
+                        "transactionShare": r.transaction_share,
+                    }))
+                    .collect::<Vec<_>>(),
+            }),
+            Event::RegisterNftType(e) => json!({
+                "name": "RegisterNftType",
+                "config": e.config.to_string(),
+                "nftType": format!("{:?}", e.nft_type).to_lowercase(),
+                "typeName": e.name,
+                "fee": e.fee.to_string(),
+            }),
+            Event::ProposeConfigPrimaryWallet(e) => json!({
+                "name": "ProposeConfigPrimaryWallet",
+                "config": e.config.to_string(),
+                "primaryWallet": e.primary_wallet.to_string(),
+                "newPrimaryWallet": e.new_primary_wallet.to_string(),
+            }),
+            Event::AcceptConfigPrimaryWallet(e) => json!({
+                "name": "AcceptConfigPrimaryWallet",
+                "config": e.config.to_string(),
+                "oldPrimaryWallet": e.old_primary_wallet.to_string(),
+                "newPrimaryWallet": e.new_primary_wallet.to_string(),
+            }),
+            Event::AddRewardTokens(e) => json!({
+                "name": "AddRewardTokens",
+                "amount": e.amount.to_string(),
+            }),
+            Event::Stake(e) => json!({
+                "name": "Stake",
+                "user": e.user.to_string(),
+                "amount": e.amount.to_string(),
+            }),
+            Event::Claim(e) => json!({
+                "name": "Claim",
+                "user": e.user.to_string(),
+                "amount": e.amount.to_string(),
+            }),
+            Event::ClaimFee(e) => json!({
+                "name": "ClaimFee",
+                "user": e.user.to_string(),
+                "amount": e.amount.to_string(),
+            }),
+            Event::ClaimAndRestake(e) => json!({
+                "name": "ClaimAndRestake",
+                "user": e.user.to_string(),
+                "amount": e.amount.to_string(),
+            }),
+            Event::TransferRewardToPendingAmount(e) => json!({
+                "name": "TransferRewardToPendingAmount",
+                "user": e.user.to_string(),
+                "amount": e.amount.to_string(),
+            }),
+            Event::Boost(e) => json!({
+                "name": "Boost",
+                "user": e.user.to_string(),
+            }),
+            Event::PaidBoost(e) => json!({
+                "name": "PaidBoost",
+                "user": e.user.to_string(),
+                "day": e.day,
+                "amount": e.amount.to_string(),
+            }),
+            Event::Cancel(e) => json!({
+                "name": "Cancel",
+                "user": e.user.to_string(),
+            }),
+            Event::Unstake(e) => json!({
+                "name": "Unstake",
+                "user": e.user.to_string(),
+                "amount": e.amount.to_string(),
+            }),
+            Event::StartRedistribution(e) => json!({
+                "name": "StartRedistribution",
+                "amount": e.amount.to_string(),
+            }),
+            Event::RedistributeUnspentReward(e) => json!({
+                "name": "RedistributeUnspentReward",
+                "user": e.user.to_string(),
+                "amount": e.amount.to_string(),
+            }),
+            Event::RegisterReferrer(e) => json!({
+                "name": "RegisterReferrer",
+                "user": e.user.to_string(),
+                "referrer": e.referrer.to_string(),
+            }),
+            Event::ClaimReferralReward(e) => json!({
+                "name": "ClaimReferralReward",
+                "referrer": e.referrer.to_string(),
+                "user": e.user.to_string(),
+                "amount": e.amount.to_string(),
+            }),
+            Event::SetDelegate(e) => json!({
+                "name": "SetDelegate",
+                "user": e.user.to_string(),
+                "delegate": e.delegate.to_string(),
+            }),
+            Event::RevokeDelegate(e) => json!({
+                "name": "RevokeDelegate",
+                "user": e.user.to_string(),
+                "delegate": e.delegate.to_string(),
+            }),
+            Event::ProposeStakingPrimaryWallet(e) => json!({
+                "name": "ProposeStakingPrimaryWallet",
+                "primaryWallet": e.primary_wallet.to_string(),
+                "newPrimaryWallet": e.new_primary_wallet.to_string(),
+            }),
+            Event::AcceptStakingPrimaryWallet(e) => json!({
+                "name": "AcceptStakingPrimaryWallet",
+                "oldPrimaryWallet": e.old_primary_wallet.to_string(),
+                "newPrimaryWallet": e.new_primary_wallet.to_string(),
+            }),
+            Event::Pause(_) => json!({
+                "name": "Pause",
+            }),
+            Event::Resume(_) => json!({
+                "name": "Resume",
+            }),
+            Event::CreateWallet(e) => json!({
+                "name": "CreateWallet",
+                "user": e.user.to_string(),
+            }),
+            Event::MigrateWallet(e) => json!({
+                "name": "MigrateWallet",
+                "user": e.user.to_string(),
+            }),
+            Event::DepositLamports(e) => json!({
+                "name": "DepositLamports",
+                "depositor": e.depositor.to_string(),
+                "proxyWallet": e.proxy_wallet.to_string(),
+                "amount": e.amount.to_string(),
+            }),
+            Event::DepositFt(e) => json!({
+                "name": "DepositFt",
+                "depositor": e.depositor.to_string(),
+                "proxyWallet": e.proxy_wallet.to_string(),
+                "mint": e.mint.to_string(),
+                "amount": e.amount.to_string(),
+            }),
+            Event::SetSpendingLimits(e) => json!({
+                "name": "SetSpendingLimits",
+                "proxyWallet": e.proxy_wallet.to_string(),
+                "user": {
+                    "lamports": e.user.lamports.to_string(),
+                    "ft": e.user.ft.to_string(),
+                },
+                "primaryWallet": {
+                    "lamports": e.primary_wallet.lamports.to_string(),
+                    "ft": e.primary_wallet.ft.to_string(),
+                },
+            }),
+            Event::CreateSessionKey(e) => json!({
+                "name": "CreateSessionKey",
+                "proxyWallet": e.proxy_wallet.to_string(),
+                "signer": e.signer.to_string(),
+                "expiresAtSlot": e.expires_at_slot.to_string(),
+                "permissions": e.permissions,
+            }),
+            Event::RevokeSessionKey(e) => json!({
+                "name": "RevokeSessionKey",
+                "proxyWallet": e.proxy_wallet.to_string(),
+                "signer": e.signer.to_string(),
+            }),
+            Event::WrapSol(e) => json!({
+                "name": "WrapSol",
+                "proxyWallet": e.proxy_wallet.to_string(),
+                "amount": e.amount.to_string(),
+            }),
+            Event::UnwrapSol(e) => json!({
+                "name": "UnwrapSol",
+                "proxyWallet": e.proxy_wallet.to_string(),
+                "amount": e.amount.to_string(),
+            }),
+            Event::StakeFromWallet(e) => json!({
+                "name": "StakeFromWallet",
+                "proxyWallet": e.proxy_wallet.to_string(),
+                "amount": e.amount.to_string(),
+            }),
+            Event::MintNftFromWallet(e) => json!({
+                "name": "MintNftFromWallet",
+                "proxyWallet": e.proxy_wallet.to_string(),
+                "nftMint": e.nft_mint.to_string(),
+                "fee": e.fee.to_string(),
+            }),
+            Event::FreezeWallet(e) => json!({
+                "name": "FreezeWallet",
+                "proxyWallet": e.proxy_wallet.to_string(),
+            }),
+            Event::UnfreezeWallet(e) => json!({
+                "name": "UnfreezeWallet",
+                "proxyWallet": e.proxy_wallet.to_string(),
+            }),
+            Event::SetWithdrawalFee(e) => json!({
+                "name": "SetWithdrawalFee",
+                "proxyWallet": e.proxy_wallet.to_string(),
+                "feeBps": e.fee_bps,
+                "feeReceiver": e.fee_receiver.to_string(),
+            }),
+            Event::WithdrawalFee(e) => json!({
+                "name": "WithdrawalFee",
+                "proxyWallet": e.proxy_wallet.to_string(),
+                "mint": e.mint.map(|mint| mint.to_string()),
+                "amount": e.amount.to_string(),
+            }),
+            Event::WithdrawLamports(e) => json!({
+                "name": "WithdrawLamports",
+                "authority": e.authority.to_string(),
+                "amount": e.amount.to_string(),
+            }),
+            Event::WithdrawFt(e) => json!({
+                "name": "WithdrawFt",
+                "authority": e.authority.to_string(),
+                "amount": e.amount.to_string(),
+            }),
+            Event::WithdrawNft(e) => json!({
+                "name": "WithdrawNft",
+                "authority": e.authority.to_string(),
+            }),
+            Event::ScheduleWithdrawal(e) => json!({
+                "name": "ScheduleWithdrawal",
+                "authority": e.authority.to_string(),
+                "receiver": e.receiver.to_string(),
+                "amount": e.amount.to_string(),
+                "interval": e.interval.to_string(),
+            }),
+            Event::ExecuteScheduledWithdrawal(e) => json!({
+                "name": "ExecuteScheduledWithdrawal",
+                "receiver": e.receiver.to_string(),
+                "amount": e.amount.to_string(),
+            }),
+            Event::CancelScheduledWithdrawal(e) => json!({
+                "name": "CancelScheduledWithdrawal",
+                "authority": e.authority.to_string(),
+                "receiver": e.receiver.to_string(),
+            }),
+            Event::LendNft(e) => json!({
+                "name": "LendNft",
+                "proxyWallet": e.proxy_wallet.to_string(),
+                "mint": e.mint.to_string(),
+                "price": e.price.to_string(),
+                "duration": e.duration.to_string(),
+            }),
+            Event::RentNft(e) => json!({
+                "name": "RentNft",
+                "mint": e.mint.to_string(),
+                "renter": e.renter.to_string(),
+                "expiresAt": e.expires_at,
+            }),
+            Event::ReclaimNft(e) => json!({
+                "name": "ReclaimNft",
+                "proxyWallet": e.proxy_wallet.to_string(),
+                "mint": e.mint.to_string(),
+            }),
+            Event::InitializeDistributor(e) => json!({
+                "name": "InitializeDistributor",
+                "distributor": e.distributor.to_string(),
+                "mint": e.mint.to_string(),
+                "kind": match e.kind {
+                    DropKind::Tokens => "tokens",
+                    DropKind::WhitelistSlots => "whitelistSlots",
+                },
+                "totalAmount": e.total_amount.to_string(),
+            }),
+            Event::ClaimDrop(e) => json!({
+                "name": "ClaimDrop",
+                "distributor": e.distributor.to_string(),
+                "claimant": e.claimant.to_string(),
+                "index": e.index.to_string(),
+                "amount": e.amount.to_string(),
+            }),
+            Event::WithdrawUnclaimed(e) => json!({
+                "name": "WithdrawUnclaimed",
+                "distributor": e.distributor.to_string(),
+                "amount": e.amount.to_string(),
+            }),
+            Event::InitializeVesting(e) => json!({
+                "name": "InitializeVesting",
+                "vesting": e.vesting.to_string(),
+                "beneficiary": e.beneficiary.to_string(),
+                "mint": e.mint.to_string(),
+                "totalAmount": e.total_amount.to_string(),
+                "startTime": e.start_time,
+                "cliffTime": e.cliff_time,
+                "endTime": e.end_time,
+            }),
+            Event::ClaimVested(e) => json!({
+                "name": "ClaimVested",
+                "vesting": e.vesting.to_string(),
+                "beneficiary": e.beneficiary.to_string(),
+                "amount": e.amount.to_string(),
+            }),
+            Event::RevokeVesting(e) => json!({
+                "name": "RevokeVesting",
+                "vesting": e.vesting.to_string(),
+                "unvestedAmount": e.unvested_amount.to_string(),
+            }),
+            Event::InitializeGovernance(e) => json!({
+                "name": "InitializeGovernance",
+                "governance": e.governance.to_string(),
+                "stakingInfo": e.staking_info.to_string(),
+                "quorum": e.quorum.to_string(),
+                "votingDuration": e.voting_duration,
+            }),
+            Event::UpdateGovernance(e) => json!({
+                "name": "UpdateGovernance",
+                "governance": e.governance.to_string(),
+                "quorum": e.quorum.to_string(),
+                "votingDuration": e.voting_duration,
+            }),
+            Event::CreateProposal(e) => json!({
+                "name": "CreateProposal",
+                "governance": e.governance.to_string(),
+                "proposal": e.proposal.to_string(),
+                "proposer": e.proposer.to_string(),
+                "index": e.index.to_string(),
+                "votingEndTime": e.voting_end_time,
+            }),
+            Event::Vote(e) => json!({
+                "name": "Vote",
+                "proposal": e.proposal.to_string(),
+                "user": e.user.to_string(),
+                "weight": e.weight.to_string(),
+                "approve": e.approve,
+            }),
+            Event::FinalizeProposal(e) => json!({
+                "name": "FinalizeProposal",
+                "proposal": e.proposal.to_string(),
+                "status": match e.status {
+                    ProposalStatus::Voting => "voting",
+                    ProposalStatus::Approved => "approved",
+                    ProposalStatus::Rejected => "rejected",
+                },
+                "yesWeight": e.yes_weight.to_string(),
+                "noWeight": e.no_weight.to_string(),
+            }),
+        }
+    }
+}
@@ -0,0 +1,41 @@
+//! Decoding of the events which Chill programs emit, shared by the indexer,
+//! the notifier, the REST service and the browser SDK
+
+pub mod event;
+pub mod logs;
+
+use event::Event;
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+/// Returns the base64 decoded data of a `Program data: ` log line
+pub fn log_data(log: &str) -> Option<Vec<u8>> {
+    base64::decode(log.strip_prefix(PROGRAM_DATA)?).ok()
+}
+
+/// Returns `None` for log lines which don't hold an event of the programs
+pub fn parse_event(log: &str) -> Option<Event> {
+    Event::decode(&log_data(log)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{prelude::Pubkey, AnchorSerialize, Discriminator};
+    use chill_nft::event::DelistNft;
+
+    #[test]
+    fn parse_logs() {
+        let mint = Pubkey::new_unique();
+        let mut data = DelistNft::discriminator().to_vec();
+        data.extend(DelistNft { mint }.try_to_vec().unwrap());
+        let log = format!("Program data: {}", base64::encode(&data));
+
+        assert_eq!(log_data(&log), Some(data));
+        assert!(matches!(parse_event(&log), Some(Event::DelistNft(e)) if e.mint == mint));
+
+        assert!(parse_event("Program log: Instruction: DelistNft").is_none());
+        assert!(parse_event("Program data: not base64").is_none());
+        assert!(parse_event(&format!("Program data: {}", base64::encode([0; 8]))).is_none());
+    }
+}
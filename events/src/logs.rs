@@ -0,0 +1,103 @@
+//! Extraction of the Chill events from the logs of a transaction
+
+use crate::{event::Event, parse_event};
+use anchor_lang::prelude::Pubkey;
+use std::str::FromStr;
+
+pub struct LoggedEvent {
+    /// Index of the log line, unique inside the transaction
+    pub index: usize,
+    pub program: Pubkey,
+    pub event: Event,
+}
+
+enum Log {
+    Invoke(Pubkey),
+    Exit,
+    Other,
+}
+
+fn parse(log: &str) -> Log {
+    let mut words = log.split_whitespace();
+    if words.next() != Some("Program") {
+        return Log::Other;
+    }
+
+    let program = words.next().and_then(|id| Pubkey::from_str(id).ok());
+    match (program, words.next()) {
+        (Some(program), Some("invoke")) => Log::Invoke(program),
+        (Some(_), Some("success")) | (Some(_), Some("failed:")) => Log::Exit,
+        _ => Log::Other,
+    }
+}
+
+/// Returns events emitted by the programs, an event belongs to the program
+/// which is on the top of the invocation stack at its log line
+pub fn events(logs: &[String], programs: &[Pubkey]) -> Vec<LoggedEvent> {
+    let mut stack = Vec::new();
+    let mut events = Vec::new();
+
+    for (index, log) in logs.iter().enumerate() {
+        match parse(log) {
+            Log::Invoke(program) => stack.push(program),
+            Log::Exit => {
+                stack.pop();
+            }
+            Log::Other => {
+                let program = match stack.last() {
+                    Some(program) if programs.contains(program) => *program,
+                    _ => continue,
+                };
+
+                if let Some(event) = parse_event(log) {
+                    events.push(LoggedEvent {
+                        index,
+                        program,
+                        event,
+                    });
+                }
+            }
+        }
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{AnchorSerialize, Discriminator};
+    use chill_nft::event::DelistNft;
+
+    fn data_log(mint: Pubkey) -> String {
+        let mut data = DelistNft::discriminator().to_vec();
+        data.extend(DelistNft { mint }.try_to_vec().unwrap());
+        format!("Program data: {}", base64::encode(data))
+    }
+
+    #[test]
+    fn events_of_programs() {
+        let mint = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let logs = vec![
+            format!("Program {} invoke [1]", chill_nft::ID),
+            "Program log: Instruction: DelistNft".to_owned(),
+            format!("Program {} invoke [2]", other),
+            data_log(Pubkey::new_unique()),
+            format!("Program {} success", other),
+            data_log(mint),
+            format!(
+                "Program {} consumed 1000 of 200000 compute units",
+                chill_nft::ID
+            ),
+            format!("Program {} success", chill_nft::ID),
+            data_log(Pubkey::new_unique()),
+        ];
+
+        let events = events(&logs, &[chill_nft::ID]);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].index, 5);
+        assert_eq!(events[0].program, chill_nft::ID);
+        assert!(matches!(&events[0].event, Event::DelistNft(e) if e.mint == mint));
+    }
+}
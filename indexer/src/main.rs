@@ -0,0 +1,181 @@
+mod storage;
+
+use anyhow::Result;
+use chill_events::logs;
+use solana_client::{
+    pubsub_client::PubsubClient,
+    rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient},
+    rpc_config::{RpcTransactionConfig, RpcTransactionLogsConfig, RpcTransactionLogsFilter},
+};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+use solana_transaction_status::UiTransactionEncoding;
+use std::{str::FromStr, sync::mpsc, thread};
+use storage::Storage;
+
+pub const INDEXER_RPC_URL_ENV: &str = "INDEXER_RPC_URL";
+pub const INDEXER_WS_URL_ENV: &str = "INDEXER_WS_URL";
+pub const INDEXER_DATABASE_ENV: &str = "INDEXER_DATABASE";
+
+pub const INDEXER_RPC_URL_DEFAULT: &str = "http://localhost:8899";
+pub const INDEXER_WS_URL_DEFAULT: &str = "ws://localhost:8900";
+pub const INDEXER_DATABASE_DEFAULT: &str = "chill-indexer.sqlite";
+
+const SIGNATURES_PAGE_LIMIT: usize = 1000;
+
+const PROGRAMS: [Pubkey; 3] = [chill_staking::ID, chill_nft::ID, chill_wallet::ID];
+
+/// Logs of a successful transaction which mentions the program
+struct Notification {
+    program: Pubkey,
+    slot: u64,
+    signature: String,
+    logs: Vec<String>,
+}
+
+fn env_or(name: &str, default: &str) -> String {
+    std::env::var(name).unwrap_or_else(|_| {
+        println!("{name} wasn't set, use default {default}");
+        default.to_owned()
+    })
+}
+
+fn index(storage: &mut Storage, notification: Notification) -> Result<()> {
+    let events = logs::events(&notification.logs, &PROGRAMS);
+    let saved = storage.save(
+        &notification.program,
+        notification.slot,
+        &notification.signature,
+        &events,
+    )?;
+
+    if saved > 0 {
+        println!(
+            "slot {}: {} new events of {}",
+            notification.slot, saved, notification.signature
+        );
+    }
+
+    Ok(())
+}
+
+/// Indexes transactions of the program which were made after its cursor,
+/// from the oldest to the newest one
+fn backfill(rpc: &RpcClient, storage: &mut Storage, program: Pubkey) -> Result<()> {
+    let until = match storage.cursor(&program)? {
+        Some(cursor) => {
+            println!("resume {} from slot {}", program, cursor.slot);
+            Some(Signature::from_str(&cursor.signature)?)
+        }
+        None => None,
+    };
+
+    let mut signatures = Vec::new();
+    let mut before = None;
+    loop {
+        let page = rpc.get_signatures_for_address_with_config(
+            &program,
+            GetConfirmedSignaturesForAddress2Config {
+                before,
+                until,
+                limit: Some(SIGNATURES_PAGE_LIMIT),
+                commitment: Some(CommitmentConfig::confirmed()),
+            },
+        )?;
+
+        let last_page = page.len() < SIGNATURES_PAGE_LIMIT;
+        before = match page.last() {
+            Some(status) => Some(Signature::from_str(&status.signature)?),
+            None => break,
+        };
+
+        signatures.extend(page.into_iter().filter(|status| status.err.is_none()));
+        if last_page {
+            break;
+        }
+    }
+
+    for status in signatures.into_iter().rev() {
+        let transaction = rpc.get_transaction_with_config(
+            &Signature::from_str(&status.signature)?,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Json),
+                commitment: Some(CommitmentConfig::confirmed()),
+            },
+        )?;
+
+        let logs = transaction
+            .transaction
+            .meta
+            .and_then(|meta| meta.log_messages)
+            .unwrap_or_default();
+
+        let notification = Notification {
+            program,
+            slot: status.slot,
+            signature: status.signature,
+            logs,
+        };
+
+        index(storage, notification)?;
+    }
+
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let rpc_url = env_or(INDEXER_RPC_URL_ENV, INDEXER_RPC_URL_DEFAULT);
+    let ws_url = env_or(INDEXER_WS_URL_ENV, INDEXER_WS_URL_DEFAULT);
+    let database = env_or(INDEXER_DATABASE_ENV, INDEXER_DATABASE_DEFAULT);
+
+    let mut storage = Storage::open(&database)?;
+    println!("{} events are indexed", storage.events_number()?);
+
+    // Subscriptions are made before the backfill, so no transaction is missed
+    // between them. Transactions received twice are skipped by the storage
+    let (sender, receiver) = mpsc::channel();
+    let mut subscriptions = Vec::new();
+    for program in PROGRAMS {
+        let (subscription, responses) = PubsubClient::logs_subscribe(
+            &ws_url,
+            RpcTransactionLogsFilter::Mentions(vec![program.to_string()]),
+            RpcTransactionLogsConfig {
+                commitment: Some(CommitmentConfig::confirmed()),
+            },
+        )?;
+
+        let sender = sender.clone();
+        thread::spawn(move || {
+            for response in responses {
+                if response.value.err.is_some() {
+                    continue;
+                }
+
+                let notification = Notification {
+                    program,
+                    slot: response.context.slot,
+                    signature: response.value.signature,
+                    logs: response.value.logs,
+                };
+
+                if sender.send(notification).is_err() {
+                    break;
+                }
+            }
+        });
+
+        subscriptions.push(subscription);
+    }
+    drop(sender);
+
+    let rpc = RpcClient::new(rpc_url);
+    for program in PROGRAMS {
+        backfill(&rpc, &mut storage, program)?;
+    }
+
+    println!("listen to events of {} programs", subscriptions.len());
+    for notification in receiver {
+        index(&mut storage, notification)?;
+    }
+
+    anyhow::bail!("subscriptions are closed")
+}
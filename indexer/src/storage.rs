@@ -0,0 +1,167 @@
+//! SQLite storage of the indexed events and of the cursors of the programs
+
+use chill_events::logs::LoggedEvent;
+use rusqlite::{params, Connection, OptionalExtension, Result};
+use solana_sdk::pubkey::Pubkey;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS events (
+        id INTEGER PRIMARY KEY,
+        signature TEXT NOT NULL,
+        log_index INTEGER NOT NULL,
+        slot INTEGER NOT NULL,
+        program TEXT NOT NULL,
+        name TEXT NOT NULL,
+        data TEXT NOT NULL,
+        UNIQUE (signature, log_index)
+    );
+    CREATE INDEX IF NOT EXISTS events_name ON events (name, slot);
+    CREATE TABLE IF NOT EXISTS cursors (
+        program TEXT PRIMARY KEY,
+        slot INTEGER NOT NULL,
+        signature TEXT NOT NULL
+    );
+";
+
+/// The latest indexed transaction of a program, indexing resumes after it
+#[derive(Debug, PartialEq)]
+pub struct Cursor {
+    pub slot: u64,
+    pub signature: String,
+}
+
+pub struct Storage {
+    connection: Connection,
+}
+
+impl Storage {
+    pub fn open(path: &str) -> Result<Self> {
+        Self::new(Connection::open(path)?)
+    }
+
+    #[cfg(test)]
+    pub fn open_in_memory() -> Result<Self> {
+        Self::new(Connection::open_in_memory()?)
+    }
+
+    fn new(connection: Connection) -> Result<Self> {
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self { connection })
+    }
+
+    pub fn cursor(&self, program: &Pubkey) -> Result<Option<Cursor>> {
+        self.connection
+            .query_row(
+                "SELECT slot, signature FROM cursors WHERE program = ?1",
+                params![program.to_string()],
+                |row| {
+                    Ok(Cursor {
+                        slot: row.get::<_, i64>(0)? as u64,
+                        signature: row.get(1)?,
+                    })
+                },
+            )
+            .optional()
+    }
+
+    /// Saves events of the transaction and moves the cursor of the program to
+    /// it. Events which are already saved are skipped, so the same transaction
+    /// can be received from several subscriptions. Returns the number of the
+    /// new events
+    pub fn save(
+        &mut self,
+        program: &Pubkey,
+        slot: u64,
+        signature: &str,
+        events: &[LoggedEvent],
+    ) -> Result<usize> {
+        let transaction = self.connection.transaction()?;
+        let mut saved = 0;
+
+        for logged in events {
+            let data = logged.event.to_json();
+            let name = data["name"].as_str().unwrap_or_default().to_owned();
+            saved += transaction.execute(
+                "INSERT OR IGNORE INTO events (signature, log_index, slot, program, name, data)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    signature,
+                    logged.index as i64,
+                    slot as i64,
+                    logged.program.to_string(),
+                    name,
+                    data.to_string(),
+                ],
+            )?;
+        }
+
+        transaction.execute(
+            "INSERT INTO cursors (program, slot, signature) VALUES (?1, ?2, ?3)
+             ON CONFLICT (program) DO UPDATE SET slot = excluded.slot, signature = excluded.signature
+             WHERE excluded.slot >= cursors.slot",
+            params![program.to_string(), slot as i64, signature],
+        )?;
+
+        transaction.commit()?;
+        Ok(saved)
+    }
+
+    pub fn events_number(&self) -> Result<u64> {
+        self.connection
+            .query_row("SELECT COUNT(*) FROM events", [], |row| {
+                row.get::<_, i64>(0)
+            })
+            .map(|number| number as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chill_events::event::Event;
+    use chill_nft::event::DelistNft;
+
+    fn delist_event(index: usize) -> LoggedEvent {
+        LoggedEvent {
+            index,
+            program: chill_nft::ID,
+            event: Event::DelistNft(DelistNft {
+                mint: Pubkey::new_unique(),
+            }),
+        }
+    }
+
+    #[test]
+    fn save_events() {
+        let mut storage = Storage::open_in_memory().unwrap();
+        assert_eq!(storage.cursor(&chill_nft::ID).unwrap(), None);
+
+        let events = [delist_event(2), delist_event(5)];
+        assert_eq!(
+            storage.save(&chill_nft::ID, 10, "first", &events).unwrap(),
+            2
+        );
+        assert_eq!(
+            storage.save(&chill_nft::ID, 10, "first", &events).unwrap(),
+            0
+        );
+        assert_eq!(storage.events_number().unwrap(), 2);
+
+        let cursor = Cursor {
+            slot: 10,
+            signature: "first".to_owned(),
+        };
+        assert_eq!(storage.cursor(&chill_nft::ID).unwrap(), Some(cursor));
+    }
+
+    #[test]
+    fn cursor_does_not_move_back() {
+        let mut storage = Storage::open_in_memory().unwrap();
+        storage.save(&chill_nft::ID, 20, "second", &[]).unwrap();
+        storage.save(&chill_nft::ID, 10, "first", &[]).unwrap();
+
+        let cursor = storage.cursor(&chill_nft::ID).unwrap().unwrap();
+        assert_eq!(cursor.slot, 20);
+        assert_eq!(cursor.signature, "second");
+    }
+}
@@ -0,0 +1,131 @@
+mod webhook;
+
+use anyhow::{Context, Result};
+use chill_events::logs;
+use serde_json::json;
+use solana_client::{
+    pubsub_client::PubsubClient,
+    rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter},
+};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+use std::{sync::mpsc, thread};
+use webhook::Webhook;
+
+pub const NOTIFIER_WS_URL_ENV: &str = "NOTIFIER_WS_URL";
+pub const NOTIFIER_WEBHOOK_URL_ENV: &str = "NOTIFIER_WEBHOOK_URL";
+pub const NOTIFIER_WEBHOOK_SECRET_ENV: &str = "NOTIFIER_WEBHOOK_SECRET";
+pub const NOTIFIER_EVENTS_ENV: &str = "NOTIFIER_EVENTS";
+
+pub const NOTIFIER_WS_URL_DEFAULT: &str = "ws://localhost:8900";
+
+const PROGRAMS: [Pubkey; 3] = [chill_staking::ID, chill_nft::ID, chill_wallet::ID];
+
+/// Logs of a successful transaction which mentions the program
+struct Notification {
+    program: Pubkey,
+    slot: u64,
+    signature: String,
+    logs: Vec<String>,
+}
+
+fn env_or(name: &str, default: &str) -> String {
+    std::env::var(name).unwrap_or_else(|_| {
+        println!("{name} wasn't set, use default {default}");
+        default.to_owned()
+    })
+}
+
+fn env(name: &str) -> Result<String> {
+    std::env::var(name).with_context(|| format!("{name} isn't set"))
+}
+
+/// Names of the posted events, all of them are posted if it's empty
+fn event_filter() -> Vec<String> {
+    std::env::var(NOTIFIER_EVENTS_ENV)
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+fn notify(webhook: &Webhook, filter: &[String], notification: Notification) {
+    // A transaction might mention several programs, so only events of the
+    // subscribed one are taken to post them once
+    for logged in logs::events(&notification.logs, &[notification.program]) {
+        let event = logged.event.to_json();
+        if !filter.is_empty() && !filter.iter().any(|name| event["name"] == **name) {
+            continue;
+        }
+
+        let body = json!({
+            "program": notification.program.to_string(),
+            "slot": notification.slot,
+            "signature": notification.signature,
+            "logIndex": logged.index,
+            "event": event,
+        })
+        .to_string();
+
+        // The event is dropped, so a webhook which is down doesn't hold up the
+        // next ones
+        if let Err(error) = webhook.post(body) {
+            eprintln!(
+                "{} of {} isn't delivered: {}",
+                event["name"], notification.signature, error
+            );
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    let ws_url = env_or(NOTIFIER_WS_URL_ENV, NOTIFIER_WS_URL_DEFAULT);
+    let webhook = Webhook::new(
+        env(NOTIFIER_WEBHOOK_URL_ENV)?,
+        &env(NOTIFIER_WEBHOOK_SECRET_ENV)?,
+    )?;
+    let filter = event_filter();
+
+    let (sender, receiver) = mpsc::channel();
+    let mut subscriptions = Vec::new();
+    for program in PROGRAMS {
+        let (subscription, responses) = PubsubClient::logs_subscribe(
+            &ws_url,
+            RpcTransactionLogsFilter::Mentions(vec![program.to_string()]),
+            RpcTransactionLogsConfig {
+                commitment: Some(CommitmentConfig::confirmed()),
+            },
+        )?;
+
+        let sender = sender.clone();
+        thread::spawn(move || {
+            for response in responses {
+                if response.value.err.is_some() {
+                    continue;
+                }
+
+                let notification = Notification {
+                    program,
+                    slot: response.context.slot,
+                    signature: response.value.signature,
+                    logs: response.value.logs,
+                };
+
+                if sender.send(notification).is_err() {
+                    break;
+                }
+            }
+        });
+
+        subscriptions.push(subscription);
+    }
+    drop(sender);
+
+    println!("notify about events of {} programs", subscriptions.len());
+    for notification in receiver {
+        notify(&webhook, &filter, notification);
+    }
+
+    anyhow::bail!("subscriptions are closed")
+}
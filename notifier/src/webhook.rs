@@ -0,0 +1,97 @@
+//! Delivery of the events to the webhook. Bodies are signed with HMAC-SHA256
+//! of the shared secret, so the receiver can check they come from the notifier
+
+use anyhow::{bail, Result};
+use reqwest::{
+    blocking::Client,
+    header::{CONTENT_TYPE, USER_AGENT},
+    StatusCode,
+};
+use ring::hmac;
+use std::{fmt::Write, thread, time::Duration};
+
+pub const SIGNATURE_HEADER: &str = "X-Chill-Signature";
+
+const MAX_ATTEMPTS: u32 = 5;
+const FIRST_RETRY_DELAY: Duration = Duration::from_millis(500);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Value of the signature header, "sha256=" followed by the hex encoded HMAC
+pub fn signature(key: &hmac::Key, body: &[u8]) -> String {
+    let tag = hmac::sign(key, body);
+    tag.as_ref()
+        .iter()
+        .fold(String::from("sha256="), |mut hex, byte| {
+            write!(hex, "{:02x}", byte).unwrap();
+            hex
+        })
+}
+
+pub struct Webhook {
+    client: Client,
+    url: String,
+    key: hmac::Key,
+}
+
+impl Webhook {
+    pub fn new(url: String, secret: &str) -> Result<Self> {
+        let client = Client::builder().timeout(REQUEST_TIMEOUT).build()?;
+        let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+        Ok(Self { client, url, key })
+    }
+
+    /// Failed requests are retried with an exponential backoff, except the
+    /// ones rejected by the webhook
+    pub fn post(&self, body: String) -> Result<()> {
+        let signature = signature(&self.key, body.as_bytes());
+        let mut delay = FIRST_RETRY_DELAY;
+        let mut attempt = 1;
+
+        loop {
+            let response = self
+                .client
+                .post(&self.url)
+                .header(CONTENT_TYPE, "application/json")
+                .header(USER_AGENT, "chill-notifier")
+                .header(SIGNATURE_HEADER, &signature)
+                .body(body.clone())
+                .send();
+
+            let error = match response {
+                Ok(response) if response.status().is_success() => return Ok(()),
+                Ok(response)
+                    if response.status().is_client_error()
+                        && response.status() != StatusCode::TOO_MANY_REQUESTS =>
+                {
+                    bail!("webhook rejected the event: {}", response.status())
+                }
+                Ok(response) => response.status().to_string(),
+                Err(error) => error.to_string(),
+            };
+
+            if attempt == MAX_ATTEMPTS {
+                bail!("webhook failed {attempt} times, the last error: {error}");
+            }
+
+            println!("attempt {attempt} failed: {error}, retry in {delay:?}");
+            thread::sleep(delay);
+            delay *= 2;
+            attempt += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hmac_signature() {
+        // Test case 2 of RFC 4231
+        let key = hmac::Key::new(hmac::HMAC_SHA256, b"Jefe");
+        assert_eq!(
+            signature(&key, b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
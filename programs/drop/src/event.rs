@@ -0,0 +1,24 @@
+use crate::state::DropKind;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct InitializeDistributor {
+    pub distributor: Pubkey,
+    pub mint: Pubkey,
+    pub kind: DropKind,
+    pub total_amount: u64,
+}
+
+#[event]
+pub struct ClaimDrop {
+    pub distributor: Pubkey,
+    pub claimant: Pubkey,
+    pub index: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct WithdrawUnclaimed {
+    pub distributor: Pubkey,
+    pub amount: u64,
+}
@@ -0,0 +1,302 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount},
+};
+use state::{ClaimStatus, Distributor, DropKind};
+
+declare_id!("E79imHk9fCZJh1KptvRM554aiJde2AUie4XQfjVu7t5E");
+
+pub mod event;
+pub mod state;
+pub mod utils;
+
+#[program]
+pub mod chill_drop {
+
+    use super::*;
+
+    /// Creates a drop of tokens and moves their total amount to its vault
+    pub fn initialize_token_drop(
+        ctx: Context<InitializeTokenDrop>,
+        merkle_root: [u8; 32],
+        total_amount: u64,
+    ) -> Result<()> {
+        require_gt!(total_amount, 0, ErrorCode::ZeroAmount);
+
+        let distributor = &mut ctx.accounts.distributor;
+        distributor.bump = ctx.bumps["distributor"];
+        distributor.authority = ctx.accounts.authority.key();
+        distributor.mint = ctx.accounts.mint.key();
+        distributor.kind = DropKind::Tokens;
+        distributor.merkle_root = merkle_root;
+        distributor.total_amount = total_amount;
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.authority_token_account.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ),
+            total_amount,
+        )?;
+
+        emit!(event::InitializeDistributor {
+            distributor: distributor.key(),
+            mint: distributor.mint,
+            kind: distributor.kind,
+            total_amount,
+        });
+
+        Ok(())
+    }
+
+    /// Creates a drop of whitelist slots for the mint, nothing is transferred
+    /// by their claims
+    pub fn initialize_whitelist_drop(
+        ctx: Context<InitializeWhitelistDrop>,
+        merkle_root: [u8; 32],
+        total_slots: u64,
+    ) -> Result<()> {
+        require_gt!(total_slots, 0, ErrorCode::ZeroAmount);
+
+        let distributor = &mut ctx.accounts.distributor;
+        distributor.bump = ctx.bumps["distributor"];
+        distributor.authority = ctx.accounts.authority.key();
+        distributor.mint = ctx.accounts.mint.key();
+        distributor.kind = DropKind::WhitelistSlots;
+        distributor.merkle_root = merkle_root;
+        distributor.total_amount = total_slots;
+
+        emit!(event::InitializeDistributor {
+            distributor: distributor.key(),
+            mint: distributor.mint,
+            kind: distributor.kind,
+            total_amount: total_slots,
+        });
+
+        Ok(())
+    }
+
+    pub fn claim(ctx: Context<Claim>, index: u64, amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+        let distributor = &mut ctx.accounts.distributor;
+        require!(
+            distributor.kind == DropKind::Tokens,
+            ErrorCode::WrongDropKind
+        );
+
+        utils::register_claim(
+            distributor,
+            &mut ctx.accounts.claim_status,
+            ctx.accounts.claimant.key(),
+            index,
+            amount,
+            &proof,
+            ctx.bumps["claim_status"],
+        )?;
+
+        let seeds = &[
+            Distributor::SEED,
+            distributor.authority.as_ref(),
+            distributor.merkle_root.as_ref(),
+            &[distributor.bump],
+        ];
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.claimant_token_account.to_account_info(),
+                    authority: distributor.to_account_info(),
+                },
+            )
+            .with_signer(&[seeds]),
+            amount,
+        )
+    }
+
+    pub fn claim_slots(
+        ctx: Context<ClaimSlots>,
+        index: u64,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let distributor = &mut ctx.accounts.distributor;
+        require!(
+            distributor.kind == DropKind::WhitelistSlots,
+            ErrorCode::WrongDropKind
+        );
+
+        utils::register_claim(
+            distributor,
+            &mut ctx.accounts.claim_status,
+            ctx.accounts.claimant.key(),
+            index,
+            amount,
+            &proof,
+            ctx.bumps["claim_status"],
+        )?;
+
+        Ok(())
+    }
+
+    /// Returns tokens nobody claimed to the authority of the drop
+    pub fn withdraw_unclaimed(ctx: Context<WithdrawUnclaimed>) -> Result<()> {
+        let distributor = &ctx.accounts.distributor;
+        let amount = ctx.accounts.vault.amount;
+        let seeds = &[
+            Distributor::SEED,
+            distributor.authority.as_ref(),
+            distributor.merkle_root.as_ref(),
+            &[distributor.bump],
+        ];
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.recipient_token_account.to_account_info(),
+                    authority: distributor.to_account_info(),
+                },
+            )
+            .with_signer(&[seeds]),
+            amount,
+        )?;
+
+        emit!(event::WithdrawUnclaimed {
+            distributor: distributor.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(merkle_root: [u8; 32])]
+pub struct InitializeTokenDrop<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(init, payer = payer, space = Distributor::LEN,
+              seeds = [Distributor::SEED, authority.key().as_ref(), merkle_root.as_ref()], bump)]
+    pub distributor: Account<'info, Distributor>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut, token::authority = authority, token::mint = mint)]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    #[account(init, payer = payer, associated_token::mint = mint, associated_token::authority = distributor)]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+#[instruction(merkle_root: [u8; 32])]
+pub struct InitializeWhitelistDrop<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(init, payer = payer, space = Distributor::LEN,
+              seeds = [Distributor::SEED, authority.key().as_ref(), merkle_root.as_ref()], bump)]
+    pub distributor: Account<'info, Distributor>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(index: u64)]
+pub struct Claim<'info> {
+    pub claimant: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    pub distributor: Account<'info, Distributor>,
+
+    #[account(init, payer = payer, space = ClaimStatus::LEN,
+              seeds = [ClaimStatus::SEED, distributor.key().as_ref(), index.to_le_bytes().as_ref()], bump)]
+    pub claim_status: Account<'info, ClaimStatus>,
+
+    #[account(mut, associated_token::mint = distributor.mint, associated_token::authority = distributor)]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = distributor.mint)]
+    pub claimant_token_account: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(index: u64)]
+pub struct ClaimSlots<'info> {
+    pub claimant: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    pub distributor: Account<'info, Distributor>,
+
+    #[account(init, payer = payer, space = ClaimStatus::LEN,
+              seeds = [ClaimStatus::SEED, distributor.key().as_ref(), index.to_le_bytes().as_ref()], bump)]
+    pub claim_status: Account<'info, ClaimStatus>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawUnclaimed<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(has_one = authority @ ErrorCode::WrongAuthority)]
+    pub distributor: Account<'info, Distributor>,
+
+    #[account(mut, associated_token::mint = distributor.mint, associated_token::authority = distributor)]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = distributor.mint)]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Amount must be greater than zero")]
+    ZeroAmount,
+
+    #[msg("Merkle proof doesn't match the root of the drop")]
+    InvalidProof,
+
+    #[msg("Instruction doesn't match the kind of the drop")]
+    WrongDropKind,
+
+    #[msg("Claims exceed the total amount of the drop")]
+    DropAmountExceeded,
+
+    #[msg("Wrong authority")]
+    WrongAuthority,
+}
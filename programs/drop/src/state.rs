@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+pub const DESCRIMINATOR_LEN: usize = 8;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DropKind {
+    Tokens,
+    WhitelistSlots,
+}
+
+#[account]
+pub struct Distributor {
+    pub bump: u8,
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub kind: DropKind,
+    pub merkle_root: [u8; 32],
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub claims_number: u64,
+}
+
+impl Distributor {
+    pub const LEN: usize = DESCRIMINATOR_LEN + 1 + 32 + 32 + 1 + 32 + 8 + 8 + 8;
+    pub const SEED: &'static [u8] = b"distributor";
+}
+
+/// Created by a claim, so every leaf of the tree is claimed once. Whitelist
+/// slots are granted by the existence of this account
+#[account]
+pub struct ClaimStatus {
+    pub bump: u8,
+    pub distributor: Pubkey,
+    pub claimant: Pubkey,
+    pub index: u64,
+    pub amount: u64,
+}
+
+impl ClaimStatus {
+    pub const LEN: usize = DESCRIMINATOR_LEN + 1 + 32 + 32 + 8 + 8;
+    pub const SEED: &'static [u8] = b"claim";
+}
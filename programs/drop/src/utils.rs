@@ -0,0 +1,74 @@
+use crate::{
+    state::{ClaimStatus, Distributor},
+    ErrorCode,
+};
+use anchor_lang::{prelude::*, solana_program::keccak};
+
+// Leaves and nodes are hashed with different prefixes, so a node can't be
+// passed off as a leaf
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+pub fn leaf(index: u64, claimant: Pubkey, amount: u64) -> [u8; 32] {
+    keccak::hashv(&[
+        LEAF_PREFIX,
+        &index.to_le_bytes(),
+        claimant.as_ref(),
+        &amount.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// Children are sorted, so a proof doesn't have to tell their order
+pub fn node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    keccak::hashv(&[NODE_PREFIX, left, right]).to_bytes()
+}
+
+pub fn verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(leaf, |computed, sibling| node(&computed, sibling));
+    &computed == root
+}
+
+pub fn register_claim(
+    distributor: &mut Account<Distributor>,
+    claim_status: &mut Account<ClaimStatus>,
+    claimant: Pubkey,
+    index: u64,
+    amount: u64,
+    proof: &[[u8; 32]],
+    bump: u8,
+) -> Result<()> {
+    require!(
+        verify(
+            proof,
+            &distributor.merkle_root,
+            leaf(index, claimant, amount)
+        ),
+        ErrorCode::InvalidProof
+    );
+
+    distributor.claimed_amount = distributor
+        .claimed_amount
+        .checked_add(amount)
+        .filter(|claimed| *claimed <= distributor.total_amount)
+        .ok_or(ErrorCode::DropAmountExceeded)?;
+    distributor.claims_number = distributor.claims_number.checked_add(1).unwrap();
+
+    claim_status.bump = bump;
+    claim_status.distributor = distributor.key();
+    claim_status.claimant = claimant;
+    claim_status.index = index;
+    claim_status.amount = amount;
+
+    emit!(crate::event::ClaimDrop {
+        distributor: distributor.key(),
+        claimant,
+        index,
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use crate::state::ProposalStatus;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct InitializeGovernance {
+    pub governance: Pubkey,
+    pub staking_info: Pubkey,
+    pub quorum: u64,
+    pub voting_duration: u64,
+}
+
+#[event]
+pub struct UpdateGovernance {
+    pub governance: Pubkey,
+    pub quorum: u64,
+    pub voting_duration: u64,
+}
+
+#[event]
+pub struct CreateProposal {
+    pub governance: Pubkey,
+    pub proposal: Pubkey,
+    pub proposer: Pubkey,
+    pub index: u64,
+    pub voting_end_time: u64,
+}
+
+#[event]
+pub struct Vote {
+    pub proposal: Pubkey,
+    pub user: Pubkey,
+    pub weight: u64,
+    pub approve: bool,
+}
+
+#[event]
+pub struct FinalizeProposal {
+    pub proposal: Pubkey,
+    pub status: ProposalStatus,
+    pub yes_weight: u64,
+    pub no_weight: u64,
+}
@@ -0,0 +1,280 @@
+use anchor_lang::prelude::*;
+use chill_staking::state::{StakingInfo, UserInfo};
+use state::{Governance, Proposal, ProposalStatus, VoteRecord, MAX_TITLE_LEN, MAX_URI_LEN};
+
+declare_id!("27ex1HvaagFPkJZsSZ89opg3Q2nZ39CD1i6EGyk6gjw7");
+
+pub mod event;
+pub mod state;
+
+fn now() -> Result<u64> {
+    Ok(Clock::get()?.unix_timestamp as u64)
+}
+
+#[program]
+pub mod chill_governance {
+
+    use super::*;
+
+    pub fn initialize_governance(
+        ctx: Context<InitializeGovernance>,
+        quorum: u64,
+        voting_duration: u64,
+    ) -> Result<()> {
+        require_gt!(voting_duration, 0, ErrorCode::ZeroVotingDuration);
+
+        let governance = &mut ctx.accounts.governance;
+        governance.bump = ctx.bumps["governance"];
+        governance.authority = ctx.accounts.primary_wallet.key();
+        governance.staking_info = ctx.accounts.staking_info.key();
+        governance.quorum = quorum;
+        governance.voting_duration = voting_duration;
+
+        emit!(event::InitializeGovernance {
+            governance: governance.key(),
+            staking_info: governance.staking_info,
+            quorum,
+            voting_duration,
+        });
+
+        Ok(())
+    }
+
+    /// New values don't affect proposals which are already created
+    pub fn update_governance(
+        ctx: Context<UpdateGovernance>,
+        quorum: u64,
+        voting_duration: u64,
+    ) -> Result<()> {
+        require_gt!(voting_duration, 0, ErrorCode::ZeroVotingDuration);
+
+        let governance = &mut ctx.accounts.governance;
+        governance.quorum = quorum;
+        governance.voting_duration = voting_duration;
+
+        emit!(event::UpdateGovernance {
+            governance: governance.key(),
+            quorum,
+            voting_duration,
+        });
+
+        Ok(())
+    }
+
+    /// Any user with an active stake is able to create a proposal
+    pub fn create_proposal(ctx: Context<CreateProposal>, title: String, uri: String) -> Result<()> {
+        require_gte!(MAX_TITLE_LEN, title.len(), ErrorCode::TitleTooLong);
+        require_gte!(MAX_URI_LEN, uri.len(), ErrorCode::UriTooLong);
+        require!(
+            ctx.accounts.user_info.has_active_stake(),
+            ErrorCode::NoActiveStake
+        );
+
+        let governance = &mut ctx.accounts.governance;
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.bump = ctx.bumps["proposal"];
+        proposal.governance = governance.key();
+        proposal.proposer = ctx.accounts.proposer.key();
+        proposal.index = governance.proposals_number;
+        proposal.title = title;
+        proposal.uri = uri;
+        proposal.created_day = ctx.accounts.staking_info.current_day()?;
+        proposal.voting_end_time = now()?.checked_add(governance.voting_duration).unwrap();
+        proposal.quorum = governance.quorum;
+        proposal.status = ProposalStatus::Voting;
+
+        governance.proposals_number = governance.proposals_number.checked_add(1).unwrap();
+
+        emit!(event::CreateProposal {
+            governance: governance.key(),
+            proposal: proposal.key(),
+            proposer: proposal.proposer,
+            index: proposal.index,
+            voting_end_time: proposal.voting_end_time,
+        });
+
+        Ok(())
+    }
+
+    /// The weight of a vote is the staked amount of the user. Stakes started
+    /// on the day of the proposal or later don't vote, so tokens which already
+    /// voted can't be cancelled, restaked from another account and vote again
+    pub fn vote(ctx: Context<Vote>, approve: bool) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        require!(
+            proposal.status == ProposalStatus::Voting && now()? < proposal.voting_end_time,
+            ErrorCode::VotingEnded
+        );
+
+        let user_info = &ctx.accounts.user_info;
+        let start_day = user_info.start_day.ok_or(ErrorCode::NoActiveStake)?;
+        require_gt!(
+            proposal.created_day,
+            start_day,
+            ErrorCode::StakeAfterProposal
+        );
+
+        let weight = user_info.staked_amount;
+        require_gt!(weight, 0, ErrorCode::NoActiveStake);
+
+        if approve {
+            proposal.yes_weight = proposal.yes_weight.checked_add(weight).unwrap();
+        } else {
+            proposal.no_weight = proposal.no_weight.checked_add(weight).unwrap();
+        }
+
+        let vote_record = &mut ctx.accounts.vote_record;
+        vote_record.bump = ctx.bumps["vote_record"];
+        vote_record.proposal = proposal.key();
+        vote_record.user = ctx.accounts.user.key();
+        vote_record.weight = weight;
+        vote_record.approve = approve;
+
+        emit!(event::Vote {
+            proposal: proposal.key(),
+            user: vote_record.user,
+            weight,
+            approve,
+        });
+
+        Ok(())
+    }
+
+    /// Anyone is able to finalize a proposal after its voting ends. It's
+    /// approved if votes reach the quorum and most of them approve it
+    pub fn finalize(ctx: Context<Finalize>) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        require!(
+            proposal.status == ProposalStatus::Voting,
+            ErrorCode::AlreadyFinalized
+        );
+        require_gte!(now()?, proposal.voting_end_time, ErrorCode::VotingNotEnded);
+
+        proposal.status = if proposal.total_weight() >= proposal.quorum
+            && proposal.yes_weight > proposal.no_weight
+        {
+            ProposalStatus::Approved
+        } else {
+            ProposalStatus::Rejected
+        };
+
+        emit!(event::FinalizeProposal {
+            proposal: proposal.key(),
+            status: proposal.status,
+            yes_weight: proposal.yes_weight,
+            no_weight: proposal.no_weight,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeGovernance<'info> {
+    pub primary_wallet: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(has_one = primary_wallet @ ErrorCode::WrongAuthority)]
+    pub staking_info: Account<'info, StakingInfo>,
+
+    #[account(init, payer = payer, space = Governance::LEN,
+              seeds = [Governance::SEED, staking_info.key().as_ref()], bump)]
+    pub governance: Account<'info, Governance>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateGovernance<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut, has_one = authority @ ErrorCode::WrongAuthority)]
+    pub governance: Account<'info, Governance>,
+}
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    pub proposer: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut, has_one = staking_info)]
+    pub governance: Account<'info, Governance>,
+
+    pub staking_info: Account<'info, StakingInfo>,
+
+    #[account(constraint = user_info.user == proposer.key() @ ErrorCode::WrongUserInfo,
+              constraint = user_info.staking_info == staking_info.key() @ ErrorCode::WrongUserInfo)]
+    pub user_info: Account<'info, UserInfo>,
+
+    #[account(init, payer = payer, space = Proposal::LEN,
+              seeds = [Proposal::SEED, governance.key().as_ref(), governance.proposals_number.to_le_bytes().as_ref()], bump)]
+    pub proposal: Account<'info, Proposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Vote<'info> {
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub governance: Account<'info, Governance>,
+
+    #[account(mut, has_one = governance)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(constraint = user_info.user == user.key() @ ErrorCode::WrongUserInfo,
+              constraint = user_info.staking_info == governance.staking_info @ ErrorCode::WrongUserInfo)]
+    pub user_info: Account<'info, UserInfo>,
+
+    #[account(init, payer = payer, space = VoteRecord::LEN,
+              seeds = [VoteRecord::SEED, proposal.key().as_ref(), user.key().as_ref()], bump)]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Finalize<'info> {
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Voting duration must be greater than zero")]
+    ZeroVotingDuration,
+
+    #[msg("Title is too long")]
+    TitleTooLong,
+
+    #[msg("URI is too long")]
+    UriTooLong,
+
+    #[msg("User has no active stake")]
+    NoActiveStake,
+
+    #[msg("Stake started on or after the day the proposal was created")]
+    StakeAfterProposal,
+
+    #[msg("User info doesn't belong to the user or the staking")]
+    WrongUserInfo,
+
+    #[msg("Voting of the proposal has ended")]
+    VotingEnded,
+
+    #[msg("Voting of the proposal hasn't ended yet")]
+    VotingNotEnded,
+
+    #[msg("Proposal is already finalized")]
+    AlreadyFinalized,
+
+    #[msg("Wrong authority")]
+    WrongAuthority,
+}
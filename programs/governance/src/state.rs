@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+
+pub const DESCRIMINATOR_LEN: usize = 8;
+pub const MAX_TITLE_LEN: usize = 64;
+pub const MAX_URI_LEN: usize = 200;
+
+/// Governance of a staking, votes of its users are weighted by their staked
+/// amounts
+#[account]
+pub struct Governance {
+    pub bump: u8,
+    pub authority: Pubkey,
+    pub staking_info: Pubkey,
+
+    // Minimum total weight of votes for a proposal to pass
+    pub quorum: u64,
+    pub voting_duration: u64,
+    pub proposals_number: u64,
+}
+
+impl Governance {
+    pub const LEN: usize = DESCRIMINATOR_LEN + 1 + 32 + 32 + 8 + 8 + 8;
+    pub const SEED: &'static [u8] = b"governance";
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ProposalStatus {
+    Voting,
+    Approved,
+    Rejected,
+}
+
+#[account]
+pub struct Proposal {
+    pub bump: u8,
+    pub governance: Pubkey,
+    pub proposer: Pubkey,
+    pub index: u64,
+    pub title: String,
+    pub uri: String,
+
+    // Only stakes started before the end of this day are able to vote
+    pub created_day: u64,
+    pub voting_end_time: u64,
+
+    // Quorum of the governance at the moment the proposal was created
+    pub quorum: u64,
+    pub yes_weight: u64,
+    pub no_weight: u64,
+    pub status: ProposalStatus,
+}
+
+impl Proposal {
+    pub const LEN: usize = DESCRIMINATOR_LEN
+        + 1
+        + 32
+        + 32
+        + 8
+        + (4 + MAX_TITLE_LEN)
+        + (4 + MAX_URI_LEN)
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + 1;
+    pub const SEED: &'static [u8] = b"proposal";
+
+    pub fn total_weight(&self) -> u64 {
+        self.yes_weight.saturating_add(self.no_weight)
+    }
+}
+
+/// Created by a vote, so every user votes once
+#[account]
+pub struct VoteRecord {
+    pub bump: u8,
+    pub proposal: Pubkey,
+    pub user: Pubkey,
+    pub weight: u64,
+    pub approve: bool,
+}
+
+impl VoteRecord {
+    pub const LEN: usize = DESCRIMINATOR_LEN + 1 + 32 + 32 + 8 + 1;
+    pub const SEED: &'static [u8] = b"vote";
+}
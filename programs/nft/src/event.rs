@@ -1,13 +1,131 @@
-use crate::state::NftType;
+use crate::state::{Fees, NftAttributes, NftType, Recipient};
 use anchor_lang::prelude::*;
 
 #[event]
 pub struct MintNft {
     pub mint: Pubkey,
     pub nft_type: NftType,
+    pub collection: Option<Pubkey>,
+}
+
+#[event]
+pub struct CreateCollection {
+    pub config: Pubkey,
+    pub mint: Pubkey,
 }
 
 #[event]
 pub struct UpdateNft {
     pub mint: Pubkey,
 }
+
+#[event]
+pub struct UpdateMinGameVersion {
+    pub mint: Pubkey,
+    pub min_game_version: u32,
+}
+
+#[event]
+pub struct UpgradeNftAttributes {
+    pub mint: Pubkey,
+    pub attributes: NftAttributes,
+}
+
+#[event]
+pub struct MigrateNftMetadata {
+    pub mint: Pubkey,
+    pub version: u8,
+}
+
+#[event]
+pub struct FinalizeNft {
+    pub mint: Pubkey,
+}
+
+#[event]
+pub struct SetAdminConfig {
+    pub primary_wallet: Pubkey,
+    pub threshold: u8,
+    pub signers: Vec<Pubkey>,
+}
+
+#[event]
+pub struct UpdateFees {
+    pub config: Pubkey,
+    pub fees: Fees,
+}
+
+#[event]
+pub struct UpdateRecipients {
+    pub config: Pubkey,
+    pub recipients: Vec<Recipient>,
+}
+
+#[event]
+pub struct ProposeConfigPrimaryWallet {
+    pub config: Pubkey,
+    pub primary_wallet: Pubkey,
+    pub new_primary_wallet: Pubkey,
+}
+
+#[event]
+pub struct AcceptConfigPrimaryWallet {
+    pub config: Pubkey,
+    pub old_primary_wallet: Pubkey,
+    pub new_primary_wallet: Pubkey,
+}
+
+#[event]
+pub struct UtilizeNft {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub number_of_uses: u64,
+    pub remaining: u64,
+}
+
+#[event]
+pub struct BurnNft {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+}
+
+#[event]
+pub struct ReclaimMetadata {
+    pub mint: Pubkey,
+}
+
+#[event]
+pub struct RegisterNftType {
+    pub config: Pubkey,
+    pub nft_type: NftType,
+    pub name: String,
+    pub fee: u64,
+}
+
+#[event]
+pub struct ListNft {
+    pub config: Pubkey,
+    pub mint: Pubkey,
+    pub price: u64,
+}
+
+#[event]
+pub struct DelistNft {
+    pub mint: Pubkey,
+}
+
+#[event]
+pub struct BuyNft {
+    pub mint: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+}
+
+#[event]
+pub struct BurnChillForAction {
+    pub config: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub action_id: u64,
+    pub memo: String,
+}
@@ -2,17 +2,27 @@ use anchor_lang::{
     prelude::*,
     solana_program::{program::invoke, program_option::COption},
 };
-use anchor_spl::token::{Mint, Token, TokenAccount};
-use metaplex_adapter::{Metadata, TokenMetadataProgram};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{burn, transfer, Burn, Mint, Token, TokenAccount, Transfer},
+};
+use metaplex_adapter::{MasterEdition, Metadata, TokenMetadataProgram};
 use mpl_token_metadata::{
     instruction::update_metadata_accounts_v2,
-    state::{Creator, DataV2, EDITION, PREFIX},
+    state::{DataV2, EDITION, PREFIX},
+};
+use state::{
+    AdminConfig, ChillNftMetadata, Config, ConfigRegistry, Fees, NftAttributes, NftListing,
+    NftType, NftTypeRegistry, PrimaryWalletTransfer, Recipient, RegisteredNftType,
+    MAX_ACTION_MEMO_LEN,
 };
-use state::{ChillNftMetadata, Config, Fees, NftType, Recipient, AUTHORITY_SHARE};
-use std::collections::HashSet;
 use utils::{
-    calculate_amounts, check_recipients, create_master_edition, create_metadata, transfer_chill,
-    NftArgs, TokenBuilder,
+    calculate_amounts, check_admin_config_args, check_admin_signers, check_fee_args,
+    check_fees_args, check_recipients, check_recipients_args, create_chill_metadata,
+    create_master_edition, create_metadata, load_nft_type_registry, nft_creators,
+    realloc_with_rent, release_nft_escrow, split_amount, transfer_chill, update_creators,
+    verify_collection, BatchNft, NftArgs, NftCreator, TokenBuilder, Uses,
+    COLLECTION_ACCOUNTS_NUMBER, MINT_BATCH_ACCOUNTS_NUMBER,
 };
 
 declare_id!("4xCEF9AVXzVXpEyG6BGp4MKisnSmKBrNDSyBduTrfoHC");
@@ -27,30 +37,70 @@ pub mod chill_nft {
 
     use super::*;
 
+    pub fn initialize_config_registry(ctx: Context<InitializeConfigRegistry>) -> Result<()> {
+        let config_registry = &mut ctx.accounts.config_registry;
+        config_registry.bump = ctx.bumps["config_registry"];
+        Ok(())
+    }
+
+    // The primary wallet creates its admin config once, after that privileged
+    // operations are approved by `threshold` of its signers
+    pub fn initialize_admin_config(
+        ctx: Context<InitializeAdminConfig>,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        check_admin_config_args(&signers, threshold)?;
+
+        let admin_config = &mut ctx.accounts.admin_config;
+        admin_config.bump = ctx.bumps["admin_config"];
+        admin_config.primary_wallet = ctx.accounts.primary_wallet.key();
+        admin_config.threshold = threshold;
+        admin_config.signers = signers;
+
+        emit!(event::SetAdminConfig {
+            primary_wallet: admin_config.primary_wallet,
+            threshold,
+            signers: admin_config.signers.clone(),
+        });
+
+        Ok(())
+    }
+
+    // Signers approving the update are passed as remaining accounts
+    pub fn update_admin_config(
+        ctx: Context<UpdateAdminConfig>,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        let admin_config = &mut ctx.accounts.admin_config;
+        check_admin_signers(admin_config, ctx.remaining_accounts)?;
+        check_admin_config_args(&signers, threshold)?;
+
+        admin_config.threshold = threshold;
+        admin_config.signers = signers;
+
+        emit!(event::SetAdminConfig {
+            primary_wallet: admin_config.primary_wallet,
+            threshold,
+            signers: admin_config.signers.clone(),
+        });
+
+        Ok(())
+    }
+
+    // Signers of the admin config are passed as remaining accounts
     pub fn initialize(
         ctx: Context<Initialize>,
         fees: Fees,
         recipients: Vec<Recipient>,
     ) -> Result<()> {
+        check_admin_signers(&ctx.accounts.admin_config, ctx.remaining_accounts)?;
+        check_fees_args(&fees, ctx.accounts.chill_mint.decimals)?;
+        check_recipients_args(&recipients)?;
+
         let config = &mut ctx.accounts.config;
         let bump = ctx.bumps["config"];
-        let set = recipients.iter().map(|r| r.address).collect::<HashSet<_>>();
-
-        require!(set.len() == recipients.len(), DuplicateRecipients);
-
-        require_gte!(
-            Config::MAX_RECIPIENT_NUMBER,
-            recipients.len(),
-            ErrorCode::MaximumRecipientsNumberExceeded,
-        );
-
-        if !recipients.is_empty() {
-            let mint_share_sum = recipients.iter().map(|r| r.mint_share).sum::<u8>();
-            let transaction_share_sum = recipients.iter().map(|r| r.transaction_share).sum::<u8>();
-
-            require_eq!(mint_share_sum, 100, ErrorCode::InvalidShares);
-            require_eq!(transaction_share_sum, 100, ErrorCode::InvalidShares);
-        }
 
         config.bump = bump;
         config.mint = ctx.accounts.chill_mint.key();
@@ -58,44 +108,92 @@ pub mod chill_nft {
         config.fees = fees;
         config.recipients = recipients;
 
+        let config_registry = &mut ctx.accounts.config_registry;
+        let config_number = config_registry.configs.len();
+
+        require_gt!(
+            ConfigRegistry::MAX_CONFIG_NUMBER,
+            config_number,
+            ErrorCode::ConfigRegistryIsFull,
+        );
+
+        realloc_with_rent(
+            &config_registry.to_account_info(),
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            ConfigRegistry::space(config_number + 1),
+        )?;
+
+        config_registry.configs.push(config.key());
+
+        Ok(())
+    }
+
+    // The collection NFT is owned by the primary wallet, which stays the
+    // update authority and verifies NFTs minted into the collection
+    pub fn create_collection(ctx: Context<CreateCollection>, args: NftArgs) -> Result<()> {
+        let creators = nft_creators(ctx.accounts.primary_wallet.key(), None);
+
+        let token_builder = TokenBuilder {
+            name: args.name,
+            symbol: args.symbol,
+            uri: args.uri,
+            creators: Some(creators),
+            seller_fee_basis_points: args.fees,
+            uses: None,
+        };
+
+        let accounts = &ctx.accounts;
+        create_metadata(
+            &accounts.primary_wallet,
+            &accounts.payer,
+            &accounts.collection_mint,
+            &accounts.collection_metadata,
+            &accounts.system_program,
+            &accounts.rent,
+            &accounts.token_metadata_program,
+            token_builder,
+        )?;
+
+        create_master_edition(
+            &accounts.primary_wallet,
+            &accounts.payer,
+            &accounts.collection_mint,
+            &accounts.collection_metadata,
+            &accounts.collection_master_edition,
+            &accounts.rent,
+            &accounts.token_metadata_program,
+        )?;
+
+        emit!(event::CreateCollection {
+            config: accounts.config.key(),
+            mint: accounts.collection_mint.key(),
+        });
+
         Ok(())
     }
 
+    // Remaining accounts are token accounts of the recipients followed by the
+    // collection mint, its metadata and its master edition if the collection
+    // is set
     pub fn mint_nft<'info>(
         ctx: Context<'_, '_, '_, 'info, MintNft<'info>>,
         nft_type: NftType,
         args: NftArgs,
         creator: Option<Pubkey>,
+        min_game_version: u32,
+        collection: Option<Pubkey>,
+        uses: Option<Uses>,
     ) -> Result<()> {
         let nft_chill_metadata = &mut ctx.accounts.nft_chill_metadata;
         let nft_chill_bump = ctx.bumps["nft_chill_metadata"];
         nft_chill_metadata.bump = nft_chill_bump;
         nft_chill_metadata.nft_type = nft_type;
+        nft_chill_metadata.min_game_version = min_game_version;
+        nft_chill_metadata.rent_receiver = ctx.accounts.config.primary_wallet;
+        nft_chill_metadata.version = ChillNftMetadata::VERSION;
 
-        let primary_wallet_key = ctx.accounts.primary_wallet.key();
-        let creators = match creator {
-            Some(creator) if creator != primary_wallet_key => {
-                vec![
-                    Creator {
-                        address: primary_wallet_key,
-                        verified: true,
-                        share: AUTHORITY_SHARE,
-                    },
-                    Creator {
-                        address: creator,
-                        verified: false,
-                        share: 100 - AUTHORITY_SHARE,
-                    },
-                ]
-            }
-            _ => {
-                vec![Creator {
-                    address: primary_wallet_key,
-                    verified: true,
-                    share: 100,
-                }]
-            }
-        };
+        let creators = nft_creators(ctx.accounts.primary_wallet.key(), creator);
 
         let token_builder = TokenBuilder {
             name: args.name,
@@ -103,6 +201,7 @@ pub mod chill_nft {
             uri: args.uri,
             creators: Some(creators),
             seller_fee_basis_points: args.fees,
+            uses,
         };
 
         let accounts = &ctx.accounts;
@@ -127,10 +226,31 @@ pub mod chill_nft {
             &accounts.token_metadata_program,
         )?;
 
-        let recipients = ctx.remaining_accounts;
+        let mut recipients = ctx.remaining_accounts;
+        if let Some(collection) = collection {
+            let collection_start = recipients
+                .len()
+                .checked_sub(COLLECTION_ACCOUNTS_NUMBER)
+                .ok_or(ErrorCode::WrongCollectionAccounts)?;
+
+            let (recipients_accounts, collection_accounts) = recipients.split_at(collection_start);
+            recipients = recipients_accounts;
+
+            verify_collection(
+                &accounts.primary_wallet,
+                &accounts.payer,
+                &accounts.nft_metadata,
+                collection,
+                collection_accounts,
+                &accounts.token_metadata_program,
+            )?;
+        }
+
         check_recipients(&accounts.config, recipients)?;
 
-        let recipients_amounts = calculate_amounts(&accounts.config, recipients, nft_type)?;
+        let registry = load_nft_type_registry(&accounts.nft_type_registry)?;
+        let recipients_amounts =
+            calculate_amounts(&accounts.config, recipients, nft_type, registry.as_ref())?;
         transfer_chill(
             &accounts.chill_payer,
             &accounts.chill_payer_token_account,
@@ -141,23 +261,180 @@ pub mod chill_nft {
 
         emit!(event::MintNft {
             mint: accounts.nft_mint.key(),
-            nft_type
+            nft_type,
+            collection,
         });
 
         Ok(())
     }
 
-    pub fn update_nft(ctx: Context<UpdateNft>, args: NftArgs) -> Result<()> {
+    // Remaining accounts are token accounts of the recipients followed by the
+    // mint, the metadata, the master edition and the chill metadata of every
+    // NFT and by the collection accounts if the collection is set. Recipients
+    // are checked once and receive a single transfer of fees for all NFTs
+    pub fn mint_nft_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, MintNftBatch<'info>>,
+        nfts: Vec<BatchNft>,
+        collection: Option<Pubkey>,
+    ) -> Result<()> {
+        let accounts = &ctx.accounts;
+        let recipients_number = accounts.config.recipients.len();
+        require_gte!(
+            ctx.remaining_accounts.len(),
+            recipients_number,
+            ErrorCode::WrongRecipientsList
+        );
+
+        let (recipients, nft_accounts) = ctx.remaining_accounts.split_at(recipients_number);
+        check_recipients(&accounts.config, recipients)?;
+
+        let (nft_accounts, collection_accounts) = match collection {
+            Some(_) => {
+                let collection_start = nft_accounts
+                    .len()
+                    .checked_sub(COLLECTION_ACCOUNTS_NUMBER)
+                    .ok_or(ErrorCode::WrongCollectionAccounts)?;
+                nft_accounts.split_at(collection_start)
+            }
+            None => (nft_accounts, &[][..]),
+        };
+
+        require!(
+            !nfts.is_empty()
+                && nft_accounts
+                    .chunks_exact(MINT_BATCH_ACCOUNTS_NUMBER)
+                    .remainder()
+                    .is_empty()
+                && nft_accounts.len() / MINT_BATCH_ACCOUNTS_NUMBER == nfts.len(),
+            ErrorCode::WrongMintBatch
+        );
+
+        let primary_wallet_key = accounts.primary_wallet.key();
+        let registry = load_nft_type_registry(&accounts.nft_type_registry)?;
+        let mut recipients_amounts = vec![0; recipients_number];
+
+        for (nft, nft_accounts) in nfts
+            .into_iter()
+            .zip(nft_accounts.chunks_exact(MINT_BATCH_ACCOUNTS_NUMBER))
+        {
+            let nft_mint = Account::<Mint>::try_from(&nft_accounts[0])?;
+            require!(
+                nft_mint.mint_authority == COption::Some(primary_wallet_key)
+                    && nft_mint.decimals == 0,
+                ErrorCode::WrongMintBatch
+            );
+
+            let nft_metadata = SystemAccount::try_from(&nft_accounts[1])?;
+            let nft_master_edition = SystemAccount::try_from(&nft_accounts[2])?;
+
+            create_chill_metadata(
+                &accounts.payer,
+                &nft_accounts[0],
+                &nft_accounts[3],
+                &accounts.system_program,
+                nft.nft_type,
+                nft.min_game_version,
+                accounts.config.primary_wallet,
+            )?;
+
+            let token_builder = TokenBuilder {
+                name: nft.args.name,
+                symbol: nft.args.symbol,
+                uri: nft.args.uri,
+                creators: Some(nft_creators(primary_wallet_key, nft.creator)),
+                seller_fee_basis_points: nft.args.fees,
+                uses: nft.uses,
+            };
+
+            create_metadata(
+                &accounts.primary_wallet,
+                &accounts.payer,
+                &nft_mint,
+                &nft_metadata,
+                &accounts.system_program,
+                &accounts.rent,
+                &accounts.token_metadata_program,
+                token_builder,
+            )?;
+
+            create_master_edition(
+                &accounts.primary_wallet,
+                &accounts.payer,
+                &nft_mint,
+                &nft_metadata,
+                &nft_master_edition,
+                &accounts.rent,
+                &accounts.token_metadata_program,
+            )?;
+
+            if let Some(collection) = collection {
+                verify_collection(
+                    &accounts.primary_wallet,
+                    &accounts.payer,
+                    &nft_metadata,
+                    collection,
+                    collection_accounts,
+                    &accounts.token_metadata_program,
+                )?;
+            }
+
+            let amounts = calculate_amounts(
+                &accounts.config,
+                recipients,
+                nft.nft_type,
+                registry.as_ref(),
+            )?;
+            for (total, amount) in recipients_amounts.iter_mut().zip(amounts) {
+                *total = amount
+                    .checked_add(*total)
+                    .ok_or(ErrorCode::ArithmeticOverflow)?;
+            }
+
+            emit!(event::MintNft {
+                mint: nft_mint.key(),
+                nft_type: nft.nft_type,
+                collection,
+            });
+        }
+
+        transfer_chill(
+            &accounts.chill_payer,
+            &accounts.chill_payer_token_account,
+            &accounts.token_program,
+            recipients,
+            recipients_amounts,
+        )?;
+
+        Ok(())
+    }
+
+    // Creators and the primary sale flag stay unchanged if they're not given.
+    // Metaplex doesn't allow to unset the primary sale flag
+    pub fn update_nft(
+        ctx: Context<UpdateNft>,
+        args: NftArgs,
+        creators: Option<Vec<NftCreator>>,
+        primary_sale_happened: Option<bool>,
+    ) -> Result<()> {
         let primary_wallet = &ctx.accounts.primary_wallet;
         let metadata = &ctx.accounts.nft_metadata;
         let token_metadata_program = &ctx.accounts.token_metadata_program;
 
+        let creators = match creators {
+            Some(creators) => Some(update_creators(
+                primary_wallet.key(),
+                metadata.data.creators.as_deref().unwrap_or_default(),
+                &creators,
+            )?),
+            None => metadata.data.creators.clone(),
+        };
+
         let data = DataV2 {
             name: args.name,
             symbol: args.symbol,
             uri: args.uri,
             seller_fee_basis_points: args.fees,
-            creators: metadata.data.creators.clone(),
+            creators,
             collection: metadata.collection.clone(),
             uses: metadata.uses.clone(),
         };
@@ -168,7 +445,7 @@ pub mod chill_nft {
             primary_wallet.key(),
             None,
             Some(data),
-            None,
+            primary_sale_happened,
             None,
         );
 
@@ -187,91 +464,1046 @@ pub mod chill_nft {
 
         Ok(())
     }
-}
 
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    pub primary_wallet: SystemAccount<'info>,
+    pub fn update_min_game_version(
+        ctx: Context<UpdateMinGameVersion>,
+        min_game_version: u32,
+    ) -> Result<()> {
+        let nft_chill_metadata = &mut ctx.accounts.nft_chill_metadata;
+        nft_chill_metadata.min_game_version = min_game_version;
 
-    #[account(mut)]
-    pub payer: Signer<'info>,
+        emit!(event::UpdateMinGameVersion {
+            mint: ctx.accounts.nft_metadata.mint,
+            min_game_version,
+        });
 
-    #[account(init, payer = payer, space = Config::LEN,
-              seeds = [Config::SEED, chill_mint.key().as_ref()], bump)]
-    pub config: Box<Account<'info, Config>>,
+        Ok(())
+    }
 
-    #[account(constraint = chill_mint.mint_authority == COption::Some(primary_wallet.key()))]
-    pub chill_mint: Account<'info, Mint>,
+    pub fn upgrade_nft_attributes(
+        ctx: Context<UpgradeNftAttributes>,
+        attributes: NftAttributes,
+    ) -> Result<()> {
+        let nft_chill_metadata = &mut ctx.accounts.nft_chill_metadata;
+        nft_chill_metadata.attributes = attributes;
 
-    pub system_program: Program<'info, System>,
-}
+        emit!(event::UpgradeNftAttributes {
+            mint: ctx.accounts.nft_metadata.mint,
+            attributes,
+        });
 
-#[derive(Accounts)]
-pub struct MintNft<'info> {
-    pub primary_wallet: Signer<'info>,
+        Ok(())
+    }
 
-    #[account(mut)]
-    pub payer: Signer<'info>,
+    // Anyone may migrate the chill metadata of the v1 layout paying for the
+    // extra space. Migrated NFTs are supported by every game version, aren't
+    // finalized and their attributes start from zero. The primary wallet which
+    // paid the rent of the account receives it back
+    pub fn migrate_nft_metadata(ctx: Context<MigrateNftMetadata>) -> Result<()> {
+        let nft_chill_metadata = &ctx.accounts.nft_chill_metadata;
+        require_keys_eq!(
+            *nft_chill_metadata.owner,
+            crate::ID,
+            ErrorCode::NftMetadataIsMigrated
+        );
+        require_eq!(
+            nft_chill_metadata.data_len(),
+            ChillNftMetadata::LEN_V1,
+            ErrorCode::NftMetadataIsMigrated
+        );
 
-    pub chill_payer: Signer<'info>,
+        let info = nft_chill_metadata.to_account_info();
+        let chill_metadata =
+            ChillNftMetadata::migrate_v1(&info.data.borrow(), ctx.accounts.config.primary_wallet)?;
 
-    #[account(mut, token::authority = chill_payer, token::mint = chill_mint)]
-    pub chill_payer_token_account: Box<Account<'info, TokenAccount>>,
+        realloc_with_rent(
+            &info,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            ChillNftMetadata::LEN,
+        )?;
 
-    #[account(seeds = [Config::SEED, config.mint.as_ref()], bump = config.bump)]
-    pub config: Box<Account<'info, Config>>,
+        chill_metadata.try_serialize(&mut &mut info.data.borrow_mut()[..])?;
 
-    #[account(address = config.mint)]
-    pub chill_mint: Box<Account<'info, Mint>>,
+        emit!(event::MigrateNftMetadata {
+            mint: ctx.accounts.nft_mint.key(),
+            version: ChillNftMetadata::VERSION,
+        });
 
-    #[account(mut, mint::authority = primary_wallet, mint::decimals = 0)]
-    pub nft_mint: Box<Account<'info, Mint>>,
+        Ok(())
+    }
 
-    #[account(mut, seeds = [PREFIX.as_bytes(), mpl_token_metadata::ID.as_ref(), nft_mint.key().as_ref()],
-              seeds::program = mpl_token_metadata::ID, bump)]
-    pub nft_metadata: SystemAccount<'info>,
+    pub fn finalize_nft(ctx: Context<FinalizeNft>) -> Result<()> {
+        let primary_wallet = &ctx.accounts.primary_wallet;
+        let metadata = &ctx.accounts.nft_metadata;
+        let token_metadata_program = &ctx.accounts.token_metadata_program;
 
-    #[account(mut, seeds = [PREFIX.as_bytes(), mpl_token_metadata::ID.as_ref(),
-              nft_mint.key().as_ref(), EDITION.as_bytes()], seeds::program = mpl_token_metadata::ID, bump)]
-    pub nft_master_edition: SystemAccount<'info>,
+        let ix = update_metadata_accounts_v2(
+            mpl_token_metadata::ID,
+            metadata.key(),
+            primary_wallet.key(),
+            None,
+            None,
+            None,
+            Some(false),
+        );
 
-    #[account(init, payer = payer, space = ChillNftMetadata::LEN,
-              seeds = [ChillNftMetadata::SEED, nft_mint.key().as_ref()], bump)]
-    pub nft_chill_metadata: Box<Account<'info, ChillNftMetadata>>,
+        invoke(
+            &ix,
+            &[
+                primary_wallet.to_account_info(),
+                metadata.to_account_info(),
+                token_metadata_program.to_account_info(),
+            ],
+        )?;
 
-    pub rent: Sysvar<'info, Rent>,
+        ctx.accounts.nft_chill_metadata.finalized = true;
 
-    pub system_program: Program<'info, System>,
+        emit!(event::FinalizeNft {
+            mint: ctx.accounts.nft_metadata.mint,
+        });
 
-    pub token_program: Program<'info, Token>,
+        Ok(())
+    }
 
-    pub token_metadata_program: Program<'info, TokenMetadataProgram>,
-}
+    pub fn update_fees(ctx: Context<UpdateConfig>, fees: Fees) -> Result<()> {
+        check_admin_signers(&ctx.accounts.admin_config, ctx.remaining_accounts)?;
+        check_fees_args(&fees, ctx.accounts.chill_mint.decimals)?;
 
-#[derive(Accounts)]
-pub struct UpdateNft<'info> {
-    pub primary_wallet: Signer<'info>,
+        let config = &mut ctx.accounts.config;
+        config.fees = fees;
 
-    #[account(mut)]
-    pub nft_metadata: Account<'info, Metadata>,
+        emit!(event::UpdateFees {
+            config: config.key(),
+            fees: config.fees.clone(),
+        });
 
-    pub token_metadata_program: Program<'info, TokenMetadataProgram>,
-}
+        Ok(())
+    }
 
-#[error_code]
-pub enum ErrorCode {
-    #[msg("Recipients list should have unique addresses")]
-    DuplicateRecipients,
+    // Custom types are appended to the registry, their index is the id
+    // stored in `NftType::Custom`. Their fees are approved and capped as the
+    // built-in ones
+    pub fn register_nft_type(ctx: Context<RegisterNftType>, name: String, fee: u64) -> Result<()> {
+        check_admin_signers(&ctx.accounts.admin_config, ctx.remaining_accounts)?;
+        check_fee_args(fee, ctx.accounts.chill_mint.decimals)?;
 
-    #[msg("Maximum recipients number exceeded")]
-    MaximumRecipientsNumberExceeded,
+        require!(
+            !name.is_empty()
+                && name.len() <= RegisteredNftType::MAX_NAME_LEN
+                && !NftType::BUILT_IN_NAMES.contains(&name.as_str()),
+            ErrorCode::WrongNftTypeName
+        );
 
-    #[msg("Wrong recipients list")]
-    WrongRecipientsList,
+        let registry = &mut ctx.accounts.nft_type_registry;
+        require!(registry.find(&name).is_none(), ErrorCode::WrongNftTypeName);
 
-    #[msg("Sum of all recipient shares must equal 100")]
-    InvalidShares,
+        let type_number = registry.types.len();
+        require_gt!(
+            NftTypeRegistry::MAX_TYPE_NUMBER,
+            type_number,
+            ErrorCode::NftTypeRegistryIsFull,
+        );
 
-    #[msg("Provided owner is not allowed")]
-    IllegalOwner,
+        registry.bump = ctx.bumps["nft_type_registry"];
+        registry.config = ctx.accounts.config.key();
+
+        realloc_with_rent(
+            &registry.to_account_info(),
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            NftTypeRegistry::space(type_number + 1),
+        )?;
+
+        registry.types.push(RegisteredNftType {
+            name: name.clone(),
+            fee,
+        });
+
+        emit!(event::RegisterNftType {
+            config: registry.config,
+            nft_type: NftType::Custom(type_number as u8),
+            name,
+            fee,
+        });
+
+        Ok(())
+    }
+
+    // Primary sale of an NFT owned by the primary wallet, the NFT is moved to
+    // the escrow until it is bought
+    pub fn list_nft(ctx: Context<ListNft>, price: u64) -> Result<()> {
+        require_gt!(price, 0, ErrorCode::WrongListingPrice);
+        require!(
+            !ctx.accounts.config.recipients.is_empty(),
+            ErrorCode::NoSaleRecipients
+        );
+
+        let nft_listing = &mut ctx.accounts.nft_listing;
+        nft_listing.bump = ctx.bumps["nft_listing"];
+        nft_listing.config = ctx.accounts.config.key();
+        nft_listing.seller = ctx.accounts.primary_wallet.key();
+        nft_listing.nft_mint = ctx.accounts.nft_mint.key();
+        nft_listing.price = price;
+
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.nft_token_account.to_account_info(),
+                to: ctx.accounts.nft_escrow.to_account_info(),
+                authority: ctx.accounts.primary_wallet.to_account_info(),
+            },
+        );
+
+        transfer(cpi_ctx, 1)?;
+
+        emit!(event::ListNft {
+            config: nft_listing.config,
+            mint: nft_listing.nft_mint,
+            price,
+        });
+
+        Ok(())
+    }
+
+    pub fn delist_nft(ctx: Context<DelistNft>) -> Result<()> {
+        let accounts = &ctx.accounts;
+        release_nft_escrow(
+            &accounts.nft_listing,
+            &accounts.nft_escrow,
+            &accounts.nft_token_account,
+            &accounts.seller,
+            &accounts.token_program,
+        )?;
+
+        emit!(event::DelistNft {
+            mint: accounts.nft_listing.nft_mint,
+        });
+
+        Ok(())
+    }
+
+    // Remaining accounts are token accounts of the recipients, the price is
+    // split by their transaction shares. The buyer passes the price it agrees
+    // to pay, so a relisted NFT is never bought for a different one. Without
+    // recipients nobody would be paid, so the NFT can only be delisted
+    pub fn buy_nft<'info>(
+        ctx: Context<'_, '_, '_, 'info, BuyNft<'info>>,
+        price: u64,
+    ) -> Result<()> {
+        let accounts = &ctx.accounts;
+        let nft_listing = &accounts.nft_listing;
+        require_eq!(nft_listing.price, price, ErrorCode::WrongListingPrice);
+
+        require!(
+            !accounts.config.recipients.is_empty(),
+            ErrorCode::NoSaleRecipients
+        );
+
+        let recipients = ctx.remaining_accounts;
+        check_recipients(&accounts.config, recipients)?;
+
+        let amounts = split_amount(&accounts.config, recipients, price, |r| r.transaction_share)?;
+        transfer_chill(
+            &accounts.buyer,
+            &accounts.buyer_chill_token_account,
+            &accounts.token_program,
+            recipients,
+            amounts,
+        )?;
+
+        release_nft_escrow(
+            nft_listing,
+            &accounts.nft_escrow,
+            &accounts.buyer_nft_token_account,
+            &accounts.seller,
+            &accounts.token_program,
+        )?;
+
+        emit!(event::BuyNft {
+            mint: nft_listing.nft_mint,
+            buyer: accounts.buyer.key(),
+            price,
+        });
+
+        Ok(())
+    }
+
+    // The config account is allocated for the maximum number of recipients,
+    // so the list is replaced in place
+    pub fn update_recipients(ctx: Context<UpdateConfig>, recipients: Vec<Recipient>) -> Result<()> {
+        check_admin_signers(&ctx.accounts.admin_config, ctx.remaining_accounts)?;
+        check_recipients_args(&recipients)?;
+
+        let config = &mut ctx.accounts.config;
+        config.recipients = recipients;
+
+        emit!(event::UpdateRecipients {
+            config: config.key(),
+            recipients: config.recipients.clone(),
+        });
+
+        Ok(())
+    }
+
+    // The primary wallet is rotated in two steps, so a config is never handed
+    // over to a key nobody controls. A new proposal replaces the pending one
+    pub fn propose_new_primary_wallet(
+        ctx: Context<ProposeNewPrimaryWallet>,
+        new_primary_wallet: Pubkey,
+    ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        require_keys_neq!(
+            new_primary_wallet,
+            config.primary_wallet,
+            ErrorCode::WrongNewPrimaryWallet
+        );
+
+        let transfer = &mut ctx.accounts.primary_wallet_transfer;
+        transfer.bump = ctx.bumps["primary_wallet_transfer"];
+        transfer.config = config.key();
+        transfer.new_primary_wallet = new_primary_wallet;
+
+        emit!(event::ProposeConfigPrimaryWallet {
+            config: config.key(),
+            primary_wallet: config.primary_wallet,
+            new_primary_wallet,
+        });
+
+        Ok(())
+    }
+
+    pub fn accept_primary_wallet(ctx: Context<AcceptPrimaryWallet>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let old_primary_wallet = config.primary_wallet;
+        config.primary_wallet = ctx.accounts.new_primary_wallet.key();
+
+        emit!(event::AcceptConfigPrimaryWallet {
+            config: config.key(),
+            old_primary_wallet,
+            new_primary_wallet: config.primary_wallet,
+        });
+
+        Ok(())
+    }
+
+    // Uses are consumed by the owner of the NFT. The token metadata program
+    // burns the NFT with the `Burn` use method when no uses remain
+    pub fn utilize_nft(ctx: Context<UtilizeNft>, number_of_uses: u64) -> Result<()> {
+        let owner = &ctx.accounts.owner;
+        let nft_mint = &ctx.accounts.nft_mint;
+        let nft_metadata = &ctx.accounts.nft_metadata;
+        let nft_token_account = &ctx.accounts.nft_token_account;
+
+        let ix = mpl_token_metadata::instruction::utilize(
+            mpl_token_metadata::ID,
+            nft_metadata.key(),
+            nft_token_account.key(),
+            nft_mint.key(),
+            None,
+            owner.key(),
+            owner.key(),
+            None,
+            number_of_uses,
+        );
+
+        invoke(
+            &ix,
+            &[
+                nft_metadata.to_account_info(),
+                nft_token_account.to_account_info(),
+                nft_mint.to_account_info(),
+                owner.to_account_info(),
+                owner.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.associated_token_program.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                ctx.accounts.token_metadata_program.to_account_info(),
+            ],
+        )?;
+
+        let nft_metadata = &mut ctx.accounts.nft_metadata;
+        nft_metadata.reload()?;
+
+        emit!(event::UtilizeNft {
+            mint: nft_mint.key(),
+            owner: owner.key(),
+            number_of_uses,
+            remaining: nft_metadata.uses.as_ref().map_or(0, |uses| uses.remaining),
+        });
+
+        Ok(())
+    }
+
+    // The metadata of the collection is the only remaining account, when the
+    // NFT is a verified member of a collection
+    pub fn burn_nft<'info>(ctx: Context<'_, '_, '_, 'info, BurnNft<'info>>) -> Result<()> {
+        let owner = &ctx.accounts.owner;
+        let nft_mint = &ctx.accounts.nft_mint;
+        let collection_metadata = ctx.remaining_accounts.first();
+
+        let ix = mpl_token_metadata::instruction::burn_nft(
+            mpl_token_metadata::ID,
+            ctx.accounts.nft_metadata.key(),
+            owner.key(),
+            nft_mint.key(),
+            ctx.accounts.nft_token_account.key(),
+            ctx.accounts.nft_master_edition.key(),
+            spl_token::ID,
+            collection_metadata.map(Key::key),
+        );
+
+        let mut account_infos = vec![
+            ctx.accounts.nft_metadata.to_account_info(),
+            owner.to_account_info(),
+            nft_mint.to_account_info(),
+            ctx.accounts.nft_token_account.to_account_info(),
+            ctx.accounts.nft_master_edition.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.token_metadata_program.to_account_info(),
+        ];
+        account_infos.extend(collection_metadata.cloned());
+
+        invoke(&ix, &account_infos)?;
+
+        emit!(event::BurnNft {
+            mint: nft_mint.key(),
+            owner: owner.key(),
+        });
+
+        Ok(())
+    }
+
+    pub fn reclaim_metadata(ctx: Context<ReclaimMetadata>) -> Result<()> {
+        emit!(event::ReclaimMetadata {
+            mint: ctx.accounts.nft_mint.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Burns CHILL of the owner as a payment for an in-game action. The game
+    /// backend verifies the action by the emitted event
+    pub fn burn_chill_for_action(
+        ctx: Context<BurnChillForAction>,
+        amount: u64,
+        action_id: u64,
+        memo: String,
+    ) -> Result<()> {
+        require_gt!(amount, 0, ErrorCode::ZeroBurnAmount);
+        require_gte!(
+            MAX_ACTION_MEMO_LEN,
+            memo.len(),
+            ErrorCode::ActionMemoTooLong
+        );
+
+        let owner = &ctx.accounts.owner;
+        burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.chill_mint.to_account_info(),
+                    from: ctx.accounts.owner_token_account.to_account_info(),
+                    authority: owner.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        emit!(event::BurnChillForAction {
+            config: ctx.accounts.config.key(),
+            owner: owner.key(),
+            amount,
+            action_id,
+            memo,
+        });
+
+        Ok(())
+    }
+
+    // The NFT program has no devnet tools yet, the view keeps deployments of
+    // all programs verifiable in the same way
+    pub fn view_nft_attributes(ctx: Context<ViewNftAttributes>) -> Result<NftAttributes> {
+        Ok(ctx.accounts.nft_chill_metadata.attributes)
+    }
+
+    pub fn view_devnet_tools_enabled(_ctx: Context<ViewState>) -> Result<bool> {
+        Ok(cfg!(feature = "devnet-tools"))
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfigRegistry<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(init, payer = payer, space = ConfigRegistry::LEN,
+              seeds = [ConfigRegistry::SEED], bump)]
+    pub config_registry: Account<'info, ConfigRegistry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeAdminConfig<'info> {
+    pub primary_wallet: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(init, payer = payer, space = AdminConfig::LEN,
+              seeds = [AdminConfig::SEED, primary_wallet.key().as_ref()], bump)]
+    pub admin_config: Account<'info, AdminConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateAdminConfig<'info> {
+    #[account(mut, seeds = [AdminConfig::SEED, admin_config.primary_wallet.as_ref()],
+              bump = admin_config.bump)]
+    pub admin_config: Account<'info, AdminConfig>,
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    /// CHECK: approved by signers of the admin config
+    pub primary_wallet: UncheckedAccount<'info>,
+
+    #[account(has_one = primary_wallet, seeds = [AdminConfig::SEED, primary_wallet.key().as_ref()],
+              bump = admin_config.bump)]
+    pub admin_config: Box<Account<'info, AdminConfig>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(init, payer = payer, space = Config::LEN,
+              seeds = [Config::SEED, chill_mint.key().as_ref()], bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(mut, seeds = [ConfigRegistry::SEED], bump = config_registry.bump)]
+    pub config_registry: Box<Account<'info, ConfigRegistry>>,
+
+    #[account(constraint = chill_mint.mint_authority == COption::Some(primary_wallet.key()))]
+    pub chill_mint: Account<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateCollection<'info> {
+    // The token metadata program requires the collection authority to be
+    // writable
+    #[account(mut)]
+    pub primary_wallet: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(has_one = primary_wallet, seeds = [Config::SEED, config.mint.as_ref()], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(mut, mint::authority = primary_wallet, mint::decimals = 0)]
+    pub collection_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut, seeds = [PREFIX.as_bytes(), mpl_token_metadata::ID.as_ref(), collection_mint.key().as_ref()],
+              seeds::program = mpl_token_metadata::ID, bump)]
+    pub collection_metadata: SystemAccount<'info>,
+
+    #[account(mut, seeds = [PREFIX.as_bytes(), mpl_token_metadata::ID.as_ref(),
+              collection_mint.key().as_ref(), EDITION.as_bytes()], seeds::program = mpl_token_metadata::ID, bump)]
+    pub collection_master_edition: SystemAccount<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+
+    pub token_metadata_program: Program<'info, TokenMetadataProgram>,
+}
+
+#[derive(Accounts)]
+pub struct MintNft<'info> {
+    // The token metadata program requires the collection authority to be
+    // writable
+    #[account(mut)]
+    pub primary_wallet: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub chill_payer: Signer<'info>,
+
+    #[account(mut, token::authority = chill_payer, token::mint = chill_mint)]
+    pub chill_payer_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(seeds = [Config::SEED, config.mint.as_ref()], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(address = config.mint)]
+    pub chill_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: deserialized only if it is created, see `load_nft_type_registry`
+    #[account(seeds = [NftTypeRegistry::SEED, config.key().as_ref()], bump)]
+    pub nft_type_registry: UncheckedAccount<'info>,
+
+    #[account(mut, mint::authority = primary_wallet, mint::decimals = 0)]
+    pub nft_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut, seeds = [PREFIX.as_bytes(), mpl_token_metadata::ID.as_ref(), nft_mint.key().as_ref()],
+              seeds::program = mpl_token_metadata::ID, bump)]
+    pub nft_metadata: SystemAccount<'info>,
+
+    #[account(mut, seeds = [PREFIX.as_bytes(), mpl_token_metadata::ID.as_ref(),
+              nft_mint.key().as_ref(), EDITION.as_bytes()], seeds::program = mpl_token_metadata::ID, bump)]
+    pub nft_master_edition: SystemAccount<'info>,
+
+    #[account(init, payer = payer, space = ChillNftMetadata::LEN,
+              seeds = [ChillNftMetadata::SEED, nft_mint.key().as_ref()], bump)]
+    pub nft_chill_metadata: Box<Account<'info, ChillNftMetadata>>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub token_metadata_program: Program<'info, TokenMetadataProgram>,
+}
+
+#[derive(Accounts)]
+pub struct MintNftBatch<'info> {
+    // The token metadata program requires the collection authority to be
+    // writable
+    #[account(mut)]
+    pub primary_wallet: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub chill_payer: Signer<'info>,
+
+    #[account(mut, token::authority = chill_payer, token::mint = chill_mint)]
+    pub chill_payer_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(seeds = [Config::SEED, config.mint.as_ref()], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(address = config.mint)]
+    pub chill_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: deserialized only if it is created, see `load_nft_type_registry`
+    #[account(seeds = [NftTypeRegistry::SEED, config.key().as_ref()], bump)]
+    pub nft_type_registry: UncheckedAccount<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub token_metadata_program: Program<'info, TokenMetadataProgram>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateNft<'info> {
+    pub primary_wallet: Signer<'info>,
+
+    #[account(mut)]
+    pub nft_metadata: Account<'info, Metadata>,
+
+    #[account(constraint = !nft_chill_metadata.finalized @ ErrorCode::NftIsFinalized,
+              seeds = [ChillNftMetadata::SEED, nft_metadata.mint.as_ref()], bump = nft_chill_metadata.bump)]
+    pub nft_chill_metadata: Account<'info, ChillNftMetadata>,
+
+    pub token_metadata_program: Program<'info, TokenMetadataProgram>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateMinGameVersion<'info> {
+    pub primary_wallet: Signer<'info>,
+
+    #[account(constraint = nft_metadata.update_authority == primary_wallet.key() @ ErrorCode::WrongUpdateAuthority)]
+    pub nft_metadata: Box<Account<'info, Metadata>>,
+
+    #[account(mut, seeds = [ChillNftMetadata::SEED, nft_metadata.mint.as_ref()], bump = nft_chill_metadata.bump)]
+    pub nft_chill_metadata: Account<'info, ChillNftMetadata>,
+}
+
+#[derive(Accounts)]
+pub struct UpgradeNftAttributes<'info> {
+    pub primary_wallet: Signer<'info>,
+
+    #[account(constraint = nft_metadata.update_authority == primary_wallet.key() @ ErrorCode::WrongUpdateAuthority)]
+    pub nft_metadata: Box<Account<'info, Metadata>>,
+
+    #[account(mut, seeds = [ChillNftMetadata::SEED, nft_metadata.mint.as_ref()], bump = nft_chill_metadata.bump)]
+    pub nft_chill_metadata: Account<'info, ChillNftMetadata>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateNftMetadata<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(seeds = [Config::SEED, config.mint.as_ref()], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    pub nft_mint: Account<'info, Mint>,
+
+    #[account(constraint = nft_metadata.update_authority == config.primary_wallet @ ErrorCode::WrongUpdateAuthority,
+              seeds = [PREFIX.as_bytes(), mpl_token_metadata::ID.as_ref(), nft_mint.key().as_ref()],
+              seeds::program = mpl_token_metadata::ID, bump)]
+    pub nft_metadata: Box<Account<'info, Metadata>>,
+
+    /// CHECK: the v1 layout cannot be deserialized as the current one
+    #[account(mut, seeds = [ChillNftMetadata::SEED, nft_mint.key().as_ref()], bump)]
+    pub nft_chill_metadata: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ViewNftAttributes<'info> {
+    pub nft_chill_metadata: Account<'info, ChillNftMetadata>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeNft<'info> {
+    pub primary_wallet: Signer<'info>,
+
+    #[account(mut, constraint = nft_metadata.update_authority == primary_wallet.key() @ ErrorCode::WrongUpdateAuthority)]
+    pub nft_metadata: Box<Account<'info, Metadata>>,
+
+    #[account(mut, constraint = !nft_chill_metadata.finalized @ ErrorCode::NftIsFinalized,
+              seeds = [ChillNftMetadata::SEED, nft_metadata.mint.as_ref()], bump = nft_chill_metadata.bump)]
+    pub nft_chill_metadata: Account<'info, ChillNftMetadata>,
+
+    pub token_metadata_program: Program<'info, TokenMetadataProgram>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    /// CHECK: approved by signers of the admin config
+    pub primary_wallet: UncheckedAccount<'info>,
+
+    #[account(has_one = primary_wallet, seeds = [AdminConfig::SEED, primary_wallet.key().as_ref()],
+              bump = admin_config.bump)]
+    pub admin_config: Box<Account<'info, AdminConfig>>,
+
+    #[account(mut, has_one = primary_wallet, seeds = [Config::SEED, config.mint.as_ref()], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(address = config.mint)]
+    pub chill_mint: Account<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterNftType<'info> {
+    /// CHECK: approved by signers of the admin config
+    pub primary_wallet: UncheckedAccount<'info>,
+
+    #[account(has_one = primary_wallet, seeds = [AdminConfig::SEED, primary_wallet.key().as_ref()],
+              bump = admin_config.bump)]
+    pub admin_config: Box<Account<'info, AdminConfig>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(has_one = primary_wallet, seeds = [Config::SEED, config.mint.as_ref()], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(address = config.mint)]
+    pub chill_mint: Account<'info, Mint>,
+
+    #[account(init_if_needed, payer = payer, space = NftTypeRegistry::space(0),
+              seeds = [NftTypeRegistry::SEED, config.key().as_ref()], bump)]
+    pub nft_type_registry: Account<'info, NftTypeRegistry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ListNft<'info> {
+    #[account(mut)]
+    pub primary_wallet: Signer<'info>,
+
+    #[account(has_one = primary_wallet, seeds = [Config::SEED, config.mint.as_ref()], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(mint::decimals = 0)]
+    pub nft_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut, token::mint = nft_mint, token::authority = primary_wallet)]
+    pub nft_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(init, payer = primary_wallet, space = NftListing::LEN,
+              seeds = [NftListing::SEED, nft_mint.key().as_ref()], bump)]
+    pub nft_listing: Box<Account<'info, NftListing>>,
+
+    #[account(init, payer = primary_wallet, associated_token::mint = nft_mint,
+              associated_token::authority = nft_listing)]
+    pub nft_escrow: Box<Account<'info, TokenAccount>>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct DelistNft<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(mut, token::mint = nft_listing.nft_mint, token::authority = seller)]
+    pub nft_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, has_one = seller, close = seller,
+              seeds = [NftListing::SEED, nft_listing.nft_mint.as_ref()], bump = nft_listing.bump)]
+    pub nft_listing: Box<Account<'info, NftListing>>,
+
+    #[account(mut, associated_token::mint = nft_listing.nft_mint,
+              associated_token::authority = nft_listing)]
+    pub nft_escrow: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct BuyNft<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(mut, token::authority = buyer, token::mint = chill_mint)]
+    pub buyer_chill_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(init_if_needed, payer = buyer, associated_token::mint = nft_mint,
+              associated_token::authority = buyer)]
+    pub buyer_nft_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(seeds = [Config::SEED, config.mint.as_ref()], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(address = config.mint)]
+    pub chill_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: the address is stored in the listing
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+
+    pub nft_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut, has_one = config, has_one = seller, has_one = nft_mint, close = seller,
+              seeds = [NftListing::SEED, nft_mint.key().as_ref()], bump = nft_listing.bump)]
+    pub nft_listing: Box<Account<'info, NftListing>>,
+
+    #[account(mut, associated_token::mint = nft_mint, associated_token::authority = nft_listing)]
+    pub nft_escrow: Box<Account<'info, TokenAccount>>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeNewPrimaryWallet<'info> {
+    #[account(mut)]
+    pub primary_wallet: Signer<'info>,
+
+    #[account(has_one = primary_wallet, seeds = [Config::SEED, config.mint.as_ref()], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(init_if_needed, payer = primary_wallet, space = PrimaryWalletTransfer::LEN,
+              seeds = [PrimaryWalletTransfer::SEED, config.key().as_ref()], bump)]
+    pub primary_wallet_transfer: Account<'info, PrimaryWalletTransfer>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptPrimaryWallet<'info> {
+    pub new_primary_wallet: Signer<'info>,
+
+    /// CHECK: receives the rent of the transfer account
+    #[account(mut)]
+    pub primary_wallet: UncheckedAccount<'info>,
+
+    #[account(mut, has_one = primary_wallet, seeds = [Config::SEED, config.mint.as_ref()], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(mut, close = primary_wallet, has_one = config, has_one = new_primary_wallet,
+              seeds = [PrimaryWalletTransfer::SEED, config.key().as_ref()], bump = primary_wallet_transfer.bump)]
+    pub primary_wallet_transfer: Account<'info, PrimaryWalletTransfer>,
+}
+
+#[derive(Accounts)]
+pub struct UtilizeNft<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub nft_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut, token::mint = nft_mint, token::authority = owner)]
+    pub nft_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = nft_metadata.mint == nft_mint.key())]
+    pub nft_metadata: Box<Account<'info, Metadata>>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+
+    pub token_metadata_program: Program<'info, TokenMetadataProgram>,
+}
+
+#[derive(Accounts)]
+pub struct BurnNft<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub nft_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut, token::mint = nft_mint, token::authority = owner)]
+    pub nft_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = nft_metadata.mint == nft_mint.key())]
+    pub nft_metadata: Box<Account<'info, Metadata>>,
+
+    #[account(mut, seeds = [PREFIX.as_bytes(), mpl_token_metadata::ID.as_ref(),
+              nft_mint.key().as_ref(), EDITION.as_bytes()], seeds::program = mpl_token_metadata::ID, bump)]
+    pub nft_master_edition: Box<Account<'info, MasterEdition>>,
+
+    #[account(mut, close = rent_receiver, has_one = rent_receiver,
+              seeds = [ChillNftMetadata::SEED, nft_mint.key().as_ref()], bump = nft_chill_metadata.bump)]
+    pub nft_chill_metadata: Box<Account<'info, ChillNftMetadata>>,
+
+    /// CHECK: the address is stored in the chill metadata
+    #[account(mut)]
+    pub rent_receiver: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub token_metadata_program: Program<'info, TokenMetadataProgram>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimMetadata<'info> {
+    #[account(constraint = nft_mint.supply == 0 @ ErrorCode::NftIsNotBurned)]
+    pub nft_mint: Account<'info, Mint>,
+
+    #[account(mut, close = rent_receiver, has_one = rent_receiver,
+              seeds = [ChillNftMetadata::SEED, nft_mint.key().as_ref()], bump = nft_chill_metadata.bump)]
+    pub nft_chill_metadata: Account<'info, ChillNftMetadata>,
+
+    /// CHECK: the address is stored in the chill metadata
+    #[account(mut)]
+    pub rent_receiver: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct BurnChillForAction<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(seeds = [Config::SEED, config.mint.as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, address = config.mint)]
+    pub chill_mint: Account<'info, Mint>,
+
+    #[account(mut, token::mint = chill_mint, token::authority = owner)]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ViewState {}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Recipients list should have unique addresses")]
+    DuplicateRecipients,
+
+    #[msg("Maximum recipients number exceeded")]
+    MaximumRecipientsNumberExceeded,
+
+    #[msg("Wrong recipients list")]
+    WrongRecipientsList,
+
+    #[msg("Sum of all recipient shares must equal 100")]
+    InvalidShares,
+
+    #[msg("Provided owner is not allowed")]
+    IllegalOwner,
+
+    #[msg("Config registry is full")]
+    ConfigRegistryIsFull,
+
+    #[msg("Primary wallet is not the update authority of the NFT")]
+    WrongUpdateAuthority,
+
+    #[msg("NFT is not burned")]
+    NftIsNotBurned,
+
+    #[msg("NFT is finalized")]
+    NftIsFinalized,
+
+    #[msg("Not enough signers of the admin config")]
+    NotEnoughAdminSigners,
+
+    #[msg("Admin config must have from 1 to 5 unique signers and a threshold not greater than their number")]
+    WrongAdminConfig,
+
+    #[msg("New primary wallet must differ from the current one")]
+    WrongNewPrimaryWallet,
+
+    #[msg("Wrong collection accounts")]
+    WrongCollectionAccounts,
+
+    #[msg("Wrong accounts of the NFTs minted by the batch")]
+    WrongMintBatch,
+
+    #[msg("Chill metadata is already migrated to the current layout")]
+    NftMetadataIsMigrated,
+
+    #[msg("NFT type is not registered in the config")]
+    UnknownNftType,
+
+    #[msg("NFT type name must be unique, up to 32 characters and differ from the built-in types")]
+    WrongNftTypeName,
+
+    #[msg("NFT type registry is full")]
+    NftTypeRegistryIsFull,
+
+    #[msg("Price of the listing must be positive and match the one the buyer agrees to pay")]
+    WrongListingPrice,
+
+    #[msg("Amount of burned CHILL must be greater than zero")]
+    ZeroBurnAmount,
+
+    #[msg("Memo of the action is too long")]
+    ActionMemoTooLong,
+
+    #[msg("Creators must be unique, include the primary wallet and have shares summing to 100")]
+    WrongCreators,
+
+    #[msg("Fee of an NFT type exceeds the maximum one")]
+    InvalidFees,
+
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+
+    #[msg("Config has no recipients to pay the price of the NFT to")]
+    NoSaleRecipients,
 }
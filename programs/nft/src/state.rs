@@ -1,9 +1,11 @@
+use crate::ErrorCode;
 use anchor_lang::prelude::*;
 use spl_token::{amount_to_ui_amount, ui_amount_to_amount};
 
 pub const DESCRIMINATOR_LEN: usize = 8;
 pub const VECTOR_PREFIX_LEN: usize = 4;
 pub const AUTHORITY_SHARE: u8 = 2;
+pub const MAX_ACTION_MEMO_LEN: usize = 64;
 
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct UiFees {
@@ -29,6 +31,9 @@ pub struct Fees {
 impl Fees {
     pub const LEN: usize = 8 + 8 + 8 + 8 + 8 + 8;
 
+    /// In whole tokens of the mint, fees above it are typos in decimals
+    pub const MAX_UI_FEE: u64 = 1_000_000;
+
     pub fn from_ui(ui_fees: UiFees, decimals: u8) -> Fees {
         Fees {
             character: ui_amount_to_amount(ui_fees.character, decimals),
@@ -51,14 +56,18 @@ impl Fees {
         }
     }
 
-    pub fn of(&self, nft_type: NftType) -> u64 {
+    // Fees of custom types are stored in the registry of the config
+    pub fn of(&self, nft_type: NftType, registry: Option<&NftTypeRegistry>) -> Option<u64> {
         match nft_type {
-            NftType::Character => self.character,
-            NftType::Pet => self.pet,
-            NftType::Emote => self.emote,
-            NftType::Tileset => self.tileset,
-            NftType::Item => self.item,
-            NftType::World => self.world,
+            NftType::Character => Some(self.character),
+            NftType::Pet => Some(self.pet),
+            NftType::Emote => Some(self.emote),
+            NftType::Tileset => Some(self.tileset),
+            NftType::Item => Some(self.item),
+            NftType::World => Some(self.world),
+            NftType::Custom(id) => registry
+                .and_then(|r| r.types.get(usize::from(id)))
+                .map(|t| t.fee),
         }
     }
 }
@@ -98,8 +107,76 @@ impl Config {
     pub const SEED: &'static [u8] = b"config";
 }
 
-#[repr(u8)]
-#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Debug)]
+// A primary wallet proposed by the current one, it becomes the primary wallet
+// of the config once it accepts the transfer
+#[account]
+pub struct PrimaryWalletTransfer {
+    pub bump: u8,
+    pub config: Pubkey,
+    pub new_primary_wallet: Pubkey,
+}
+
+impl PrimaryWalletTransfer {
+    pub const LEN: usize = DESCRIMINATOR_LEN + 1 + 32 + 32;
+
+    pub const SEED: &'static [u8] = b"primary-wallet-transfer";
+}
+
+// An NFT sold by the primary wallet for CHILL, the NFT is kept in the
+// associated token account of the listing until it is bought or delisted
+#[account]
+pub struct NftListing {
+    pub bump: u8,
+    pub config: Pubkey,
+    pub seller: Pubkey,
+    pub nft_mint: Pubkey,
+    pub price: u64,
+}
+
+impl NftListing {
+    pub const LEN: usize = DESCRIMINATOR_LEN + 1 + 32 + 32 + 32 + 8;
+
+    pub const SEED: &'static [u8] = b"nft-listing";
+}
+
+// Signers which approve privileged operations of the primary wallet in all
+// Chill programs
+#[account]
+pub struct AdminConfig {
+    pub bump: u8,
+    pub primary_wallet: Pubkey,
+    pub threshold: u8,
+    pub signers: Vec<Pubkey>,
+}
+
+impl AdminConfig {
+    pub const MAX_SIGNER_NUMBER: usize = 5;
+
+    pub const LEN: usize =
+        DESCRIMINATOR_LEN + 1 + 32 + 1 + VECTOR_PREFIX_LEN + Self::MAX_SIGNER_NUMBER * 32;
+
+    pub const SEED: &'static [u8] = b"admin-config";
+}
+
+#[account]
+pub struct ConfigRegistry {
+    pub bump: u8,
+    pub configs: Vec<Pubkey>,
+}
+
+impl ConfigRegistry {
+    pub const MAX_CONFIG_NUMBER: usize = 1024;
+
+    pub const LEN: usize = DESCRIMINATOR_LEN + 1 + VECTOR_PREFIX_LEN;
+
+    pub const SEED: &'static [u8] = b"config-registry";
+
+    pub fn space(config_number: usize) -> usize {
+        Self::LEN + config_number * 32
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Debug, PartialEq, Eq)]
 pub enum NftType {
     Character,
     Pet,
@@ -107,10 +184,18 @@ pub enum NftType {
     Tileset,
     Item,
     World,
+
+    // Index of the type in the registry of the config
+    Custom(u8),
 }
 
 impl NftType {
     pub const LEN: usize = 1;
+
+    pub const MAX_LEN: usize = Self::LEN + 1;
+
+    pub const BUILT_IN_NAMES: [&'static str; 6] =
+        ["character", "pet", "emote", "tileset", "item", "world"];
 }
 
 impl TryFrom<&str> for NftType {
@@ -129,44 +214,246 @@ impl TryFrom<&str> for NftType {
     }
 }
 
-impl TryFrom<u8> for NftType {
-    type Error = String;
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct RegisteredNftType {
+    pub name: String,
+    pub fee: u64,
+}
 
-    fn try_from(number: u8) -> core::result::Result<Self, Self::Error> {
-        const CHARACTER: u8 = NftType::Character as u8;
-        const PET: u8 = NftType::Pet as u8;
-        const EMOTE: u8 = NftType::Emote as u8;
-        const TILESET: u8 = NftType::Tileset as u8;
-        const ITEM: u8 = NftType::Item as u8;
-        const WORLD: u8 = NftType::World as u8;
-
-        match number {
-            CHARACTER => Ok(NftType::Character),
-            PET => Ok(NftType::Pet),
-            EMOTE => Ok(NftType::Emote),
-            TILESET => Ok(NftType::Tileset),
-            ITEM => Ok(NftType::Item),
-            WORLD => Ok(NftType::World),
-            _ => Err("Wrong nft type".to_owned()),
-        }
-    }
+impl RegisteredNftType {
+    pub const MAX_NAME_LEN: usize = 32;
+
+    pub const LEN: usize = VECTOR_PREFIX_LEN + Self::MAX_NAME_LEN + 8;
+}
+
+// NFT types registered by the primary wallet in addition to the built-in ones,
+// `NftType::Custom` holds the index of the type in this list
+#[account]
+pub struct NftTypeRegistry {
+    pub bump: u8,
+    pub config: Pubkey,
+    pub types: Vec<RegisteredNftType>,
 }
 
-#[allow(clippy::from_over_into)]
-impl Into<u8> for NftType {
-    fn into(self) -> u8 {
-        self as u8
+impl NftTypeRegistry {
+    pub const MAX_TYPE_NUMBER: usize = 64;
+
+    pub const LEN: usize = DESCRIMINATOR_LEN + 1 + 32 + VECTOR_PREFIX_LEN;
+
+    pub const SEED: &'static [u8] = b"nft-type-registry";
+
+    pub fn space(type_number: usize) -> usize {
+        Self::LEN + type_number * RegisteredNftType::LEN
+    }
+
+    pub fn find(&self, name: &str) -> Option<NftType> {
+        self.types
+            .iter()
+            .position(|t| t.name == name)
+            .and_then(|index| u8::try_from(index).ok())
+            .map(NftType::Custom)
     }
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NftAttributes {
+    pub level: u32,
+    pub rarity: u8,
+    pub xp: u64,
+
+    // Game specific values
+    pub custom: [u64; 4],
+}
+
+impl NftAttributes {
+    pub const LEN: usize = 4 + 1 + 8 + 8 * 4;
+}
+
 #[account]
 pub struct ChillNftMetadata {
     pub bump: u8,
     pub nft_type: NftType,
+
+    // Accounts of the v1 layout have no fields below
+    // until they are migrated by `migrate_nft_metadata`
+    pub min_game_version: u32,
+
+    // Receives the rent when the account is closed
+    pub rent_receiver: Pubkey,
+
+    // The Metaplex metadata is immutable, `update_nft` is not available
+    pub finalized: bool,
+
+    pub version: u8,
+    pub attributes: NftAttributes,
 }
 
 impl ChillNftMetadata {
-    pub const LEN: usize = DESCRIMINATOR_LEN + 1 + NftType::LEN;
+    // Layout of NFTs minted before versioning, which keep only their type
+    pub const LEN_V1: usize = DESCRIMINATOR_LEN + 1 + NftType::LEN;
+
+    // Custom NFT types take an extra byte over the v1 layout
+    pub const LEN: usize =
+        DESCRIMINATOR_LEN + 1 + NftType::MAX_LEN + 4 + 32 + 1 + 1 + NftAttributes::LEN;
+
+    pub const VERSION: u8 = 2;
 
     pub const SEED: &'static [u8] = b"chill-metadata";
+
+    pub fn is_supported_by(&self, game_version: u32) -> bool {
+        self.min_game_version <= game_version
+    }
+
+    /// Deserializes accounts of both layouts, v1 accounts get default values of
+    /// the fields they don't have. Their rent receiver stays unknown until they
+    /// are migrated
+    pub fn try_deserialize_versioned(data: &[u8]) -> Result<Self> {
+        if data.len() != Self::LEN_V1 {
+            return Self::try_deserialize(&mut &data[..]);
+        }
+
+        let mut data = data.to_vec();
+        data.resize(Self::LEN, 0);
+
+        let mut chill_metadata = Self::try_deserialize(&mut data.as_ref())?;
+        chill_metadata.version = 1;
+        Ok(chill_metadata)
+    }
+
+    /// The current layout of a v1 account. Its rent was paid by the primary
+    /// wallet of the config which minted the NFT
+    pub fn migrate_v1(data: &[u8], rent_receiver: Pubkey) -> Result<Self> {
+        let v1 = Self::try_deserialize_versioned(data)?;
+        require_eq!(v1.version, 1, ErrorCode::NftMetadataIsMigrated);
+
+        Ok(Self {
+            min_game_version: 0,
+            rent_receiver,
+            finalized: false,
+            version: Self::VERSION,
+            attributes: NftAttributes::default(),
+            ..v1
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Borsh data of the chill metadata of NFTs minted before versioning
+    fn v1_chill_metadata_data(nft_type: NftType) -> Vec<u8> {
+        let mut data = <ChillNftMetadata as anchor_lang::Discriminator>::discriminator().to_vec();
+        data.push(254);
+        nft_type.serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn deserialize_v1_chill_metadata() {
+        let chill_metadata = ChillNftMetadata {
+            bump: 254,
+            nft_type: NftType::Pet,
+            min_game_version: 3,
+            rent_receiver: Pubkey::new_unique(),
+            finalized: true,
+            version: ChillNftMetadata::VERSION,
+            attributes: NftAttributes {
+                level: 2,
+                ..NftAttributes::default()
+            },
+        };
+
+        let mut data = Vec::new();
+        chill_metadata.try_serialize(&mut data).unwrap();
+        data.resize(ChillNftMetadata::LEN, 0);
+
+        let current = ChillNftMetadata::try_deserialize_versioned(&data).unwrap();
+        assert_eq!(current.version, ChillNftMetadata::VERSION);
+        assert_eq!(current.min_game_version, 3);
+        assert_eq!(current.attributes.level, 2);
+
+        let data = v1_chill_metadata_data(NftType::Pet);
+        assert_eq!(data.len(), ChillNftMetadata::LEN_V1);
+
+        let v1 = ChillNftMetadata::try_deserialize_versioned(&data).unwrap();
+        assert_eq!(v1.version, 1);
+        assert_eq!(v1.bump, 254);
+        assert_eq!(v1.nft_type, NftType::Pet);
+        assert_eq!(v1.min_game_version, 0);
+        assert_eq!(v1.rent_receiver, Pubkey::default());
+        assert!(!v1.finalized);
+        assert_eq!(v1.attributes, NftAttributes::default());
+    }
+
+    #[test]
+    fn migrate_v1_chill_metadata() {
+        let primary_wallet = Pubkey::new_unique();
+        let mut data = v1_chill_metadata_data(NftType::Emote);
+
+        // The account is reallocated before the migration
+        data.resize(ChillNftMetadata::LEN, 0);
+        assert!(ChillNftMetadata::migrate_v1(&data, primary_wallet).is_err());
+        data.truncate(ChillNftMetadata::LEN_V1);
+
+        let migrated = ChillNftMetadata::migrate_v1(&data, primary_wallet).unwrap();
+        assert_eq!(migrated.bump, 254);
+        assert_eq!(migrated.nft_type, NftType::Emote);
+        assert_eq!(migrated.min_game_version, 0);
+        assert_eq!(migrated.rent_receiver, primary_wallet);
+        assert!(!migrated.finalized);
+        assert_eq!(migrated.version, ChillNftMetadata::VERSION);
+
+        let mut migrated_data = Vec::new();
+        migrated.try_serialize(&mut migrated_data).unwrap();
+        migrated_data.resize(ChillNftMetadata::LEN, 0);
+
+        let current = ChillNftMetadata::try_deserialize_versioned(&migrated_data).unwrap();
+        assert_eq!(current.rent_receiver, primary_wallet);
+        assert_eq!(current.version, ChillNftMetadata::VERSION);
+    }
+
+    #[test]
+    fn custom_nft_type_fits_chill_metadata() {
+        let chill_metadata = ChillNftMetadata {
+            bump: 254,
+            nft_type: NftType::Custom(7),
+            min_game_version: 0,
+            rent_receiver: Pubkey::new_unique(),
+            finalized: false,
+            version: ChillNftMetadata::VERSION,
+            attributes: NftAttributes::default(),
+        };
+
+        let mut data = Vec::new();
+        chill_metadata.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), ChillNftMetadata::LEN);
+
+        let deserialized = ChillNftMetadata::try_deserialize_versioned(&data).unwrap();
+        assert_eq!(deserialized.nft_type, NftType::Custom(7));
+    }
+
+    #[test]
+    fn fees_of_custom_nft_type() {
+        let fees = Fees {
+            pet: 5,
+            ..Fees::default()
+        };
+
+        let registry = NftTypeRegistry {
+            bump: 255,
+            config: Pubkey::new_unique(),
+            types: vec![RegisteredNftType {
+                name: "vehicle".to_owned(),
+                fee: 42,
+            }],
+        };
+
+        assert_eq!(fees.of(NftType::Pet, None), Some(5));
+        assert_eq!(fees.of(NftType::Custom(0), None), None);
+        assert_eq!(fees.of(NftType::Custom(0), Some(&registry)), Some(42));
+        assert_eq!(fees.of(NftType::Custom(1), Some(&registry)), None);
+        assert_eq!(registry.find("vehicle"), Some(NftType::Custom(0)));
+        assert_eq!(registry.find("pet"), None);
+    }
 }
@@ -1,26 +1,37 @@
 use crate::{
     metaplex_adapter::TokenMetadataProgram,
-    state::{Config, NftType},
+    state::{
+        AdminConfig, ChillNftMetadata, Config, Fees, NftAttributes, NftListing, NftType,
+        NftTypeRegistry, Recipient, AUTHORITY_SHARE,
+    },
     ErrorCode,
 };
 use anchor_lang::{
+    err,
     prelude::{
-        borsh, error, Account, AccountInfo, CpiContext, Program, Rent, Result, Signer, System,
-        SystemAccount, Sysvar,
+        borsh, error, Account, AccountInfo, CpiContext, Program, Pubkey, Rent, Result, Signer,
+        SolanaSysvar, System, SystemAccount, Sysvar,
     },
-    require, require_eq, require_keys_eq,
+    require, require_eq, require_gte, require_keys_eq,
     solana_program::{entrypoint::ProgramResult, program::invoke},
-    AccountDeserialize, AnchorDeserialize, AnchorSerialize, Key, ToAccountInfo,
+    system_program, AccountDeserialize, AccountSerialize, AnchorDeserialize, AnchorSerialize, Key,
+    ToAccountInfo,
+};
+use anchor_spl::token::{
+    close_account, transfer, CloseAccount, Mint, Token, TokenAccount, Transfer,
 };
-use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
 use mpl_token_metadata::{
-    instruction::{create_master_edition_v3, create_metadata_accounts_v2},
+    instruction::{
+        create_master_edition_v3, create_metadata_accounts_v2, set_and_verify_collection,
+    },
     state::Creator,
 };
 use std::collections::HashSet;
 
+pub const COLLECTION_ACCOUNTS_NUMBER: usize = 3;
+
 #[repr(C)]
-#[derive(AnchorSerialize, AnchorDeserialize)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct NftArgs {
     pub name: String,
     pub symbol: String,
@@ -28,12 +39,138 @@ pub struct NftArgs {
     pub fees: u16, // 10000 = 100%
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct NftCreator {
+    pub address: Pubkey,
+    pub share: u8,
+}
+
+/// Accounts of every NFT minted by `mint_nft_batch` are the mint, the
+/// metadata, the master edition and the chill metadata
+pub const MINT_BATCH_ACCOUNTS_NUMBER: usize = 4;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BatchNft {
+    pub nft_type: NftType,
+    pub args: NftArgs,
+    pub creator: Option<Pubkey>,
+    pub min_game_version: u32,
+    pub uses: Option<Uses>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UseMethod {
+    Burn,
+    Multiple,
+    Single,
+}
+
+impl TryFrom<&str> for UseMethod {
+    type Error = String;
+
+    fn try_from(string: &str) -> core::result::Result<Self, Self::Error> {
+        match string {
+            "burn" => Ok(UseMethod::Burn),
+            "multiple" => Ok(UseMethod::Multiple),
+            "single" => Ok(UseMethod::Single),
+            _ => Err("Wrong use method".to_owned()),
+        }
+    }
+}
+
+/// Consumable NFTs are utilized by their owners until no uses remain
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Uses {
+    pub method: UseMethod,
+    pub remaining: u64,
+    pub total: u64,
+}
+
+impl From<Uses> for mpl_token_metadata::state::Uses {
+    fn from(uses: Uses) -> Self {
+        let use_method = match uses.method {
+            UseMethod::Burn => mpl_token_metadata::state::UseMethod::Burn,
+            UseMethod::Multiple => mpl_token_metadata::state::UseMethod::Multiple,
+            UseMethod::Single => mpl_token_metadata::state::UseMethod::Single,
+        };
+
+        Self {
+            use_method,
+            remaining: uses.remaining,
+            total: uses.total,
+        }
+    }
+}
+
 pub struct TokenBuilder {
     pub name: String,
     pub symbol: String,
     pub uri: String,
     pub creators: Option<Vec<Creator>>,
     pub seller_fee_basis_points: u16,
+    pub uses: Option<Uses>,
+}
+
+/// The primary wallet is a verified creator of every NFT and shares royalties
+/// with the optional creator
+pub fn nft_creators(primary_wallet: Pubkey, creator: Option<Pubkey>) -> Vec<Creator> {
+    match creator {
+        Some(creator) if creator != primary_wallet => {
+            vec![
+                Creator {
+                    address: primary_wallet,
+                    verified: true,
+                    share: AUTHORITY_SHARE,
+                },
+                Creator {
+                    address: creator,
+                    verified: false,
+                    share: 100 - AUTHORITY_SHARE,
+                },
+            ]
+        }
+        _ => {
+            vec![Creator {
+                address: primary_wallet,
+                verified: true,
+                share: 100,
+            }]
+        }
+    }
+}
+
+/// Creators replacing the current ones of an NFT. The primary wallet must stay
+/// a creator, it's verified by its signature, other creators keep their
+/// current verification
+pub fn update_creators(
+    primary_wallet: Pubkey,
+    current: &[Creator],
+    creators: &[NftCreator],
+) -> Result<Vec<Creator>> {
+    let set = creators.iter().map(|c| c.address).collect::<HashSet<_>>();
+    let share_sum = creators.iter().map(|c| u16::from(c.share)).sum::<u16>();
+
+    require!(
+        set.len() == creators.len()
+            && set.contains(&primary_wallet)
+            && creators.len() <= mpl_token_metadata::state::MAX_CREATOR_LIMIT
+            && share_sum == 100,
+        ErrorCode::WrongCreators
+    );
+
+    let creators = creators
+        .iter()
+        .map(|creator| Creator {
+            address: creator.address,
+            verified: creator.address == primary_wallet
+                || current
+                    .iter()
+                    .any(|c| c.address == creator.address && c.verified),
+            share: creator.share,
+        })
+        .collect();
+
+    Ok(creators)
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -63,7 +200,7 @@ pub fn create_metadata<'info>(
             true,
             true,
             None,
-            None,
+            token_builder.uses.map(Into::into),
         ),
         &[
             primary_wallet.to_account_info(),
@@ -110,6 +247,56 @@ pub fn create_master_edition<'info>(
     )
 }
 
+/// Collection accounts are the collection mint, its metadata and its master
+/// edition. The metadata and the master edition are checked by the token
+/// metadata program
+pub fn verify_collection<'info>(
+    primary_wallet: &Signer<'info>,
+    payer: &Signer<'info>,
+    metadata: &SystemAccount<'info>,
+    collection: Pubkey,
+    collection_accounts: &[AccountInfo<'info>],
+    token_metadata_program: &Program<'info, TokenMetadataProgram>,
+) -> Result<()> {
+    let (collection_mint, collection_metadata, collection_master_edition) =
+        match collection_accounts {
+            [mint, metadata, master_edition] => (mint, metadata, master_edition),
+            _ => return err!(ErrorCode::WrongCollectionAccounts),
+        };
+
+    require_keys_eq!(
+        collection_mint.key(),
+        collection,
+        ErrorCode::WrongCollectionAccounts
+    );
+
+    invoke(
+        &set_and_verify_collection(
+            mpl_token_metadata::ID,
+            metadata.key(),
+            primary_wallet.key(),
+            payer.key(),
+            primary_wallet.key(),
+            collection_mint.key(),
+            collection_metadata.key(),
+            collection_master_edition.key(),
+            None,
+        ),
+        &[
+            metadata.to_account_info(),
+            primary_wallet.to_account_info(),
+            payer.to_account_info(),
+            primary_wallet.to_account_info(),
+            collection_mint.clone(),
+            collection_metadata.clone(),
+            collection_master_edition.clone(),
+            token_metadata_program.to_account_info(),
+        ],
+    )?;
+
+    Ok(())
+}
+
 pub fn sign_metadata<'info>(
     creator: &AccountInfo<'info>,
     metadata: &AccountInfo<'info>,
@@ -127,6 +314,98 @@ pub fn sign_metadata<'info>(
     )
 }
 
+/// The first remaining accounts must be at least `threshold` distinct signers
+/// of the admin config. Returns the rest of the remaining accounts
+pub fn check_admin_signers<'a, 'info>(
+    admin_config: &AdminConfig,
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> Result<&'a [AccountInfo<'info>]> {
+    let threshold = admin_config.threshold as usize;
+    require_gte!(
+        remaining_accounts.len(),
+        threshold,
+        ErrorCode::NotEnoughAdminSigners
+    );
+
+    let (signers, rest) = remaining_accounts.split_at(threshold);
+    let keys = signers
+        .iter()
+        .filter(|signer| signer.is_signer && admin_config.signers.contains(signer.key))
+        .map(|signer| signer.key)
+        .collect::<HashSet<_>>();
+
+    require_eq!(keys.len(), threshold, ErrorCode::NotEnoughAdminSigners);
+
+    Ok(rest)
+}
+
+pub fn check_admin_config_args(signers: &[Pubkey], threshold: u8) -> Result<()> {
+    let set = signers.iter().collect::<HashSet<_>>();
+    require_eq!(set.len(), signers.len(), ErrorCode::WrongAdminConfig);
+
+    require_gte!(
+        AdminConfig::MAX_SIGNER_NUMBER,
+        signers.len(),
+        ErrorCode::WrongAdminConfig
+    );
+
+    require!(
+        threshold > 0 && threshold as usize <= signers.len(),
+        ErrorCode::WrongAdminConfig
+    );
+
+    Ok(())
+}
+
+pub fn check_fee_args(fee: u64, decimals: u8) -> Result<()> {
+    let max_fee = 10u64
+        .checked_pow(decimals.into())
+        .and_then(|unit| unit.checked_mul(Fees::MAX_UI_FEE))
+        .unwrap_or(u64::MAX);
+
+    require_gte!(max_fee, fee, ErrorCode::InvalidFees);
+
+    Ok(())
+}
+
+pub fn check_fees_args(fees: &Fees, decimals: u8) -> Result<()> {
+    let all_fees = [
+        fees.character,
+        fees.pet,
+        fees.emote,
+        fees.tileset,
+        fees.item,
+        fees.world,
+    ];
+
+    for fee in all_fees {
+        check_fee_args(fee, decimals)?;
+    }
+
+    Ok(())
+}
+
+pub fn check_recipients_args(recipients: &[Recipient]) -> Result<()> {
+    let set = recipients.iter().map(|r| r.address).collect::<HashSet<_>>();
+    require_eq!(set.len(), recipients.len(), ErrorCode::DuplicateRecipients);
+
+    require_gte!(
+        Config::MAX_RECIPIENT_NUMBER,
+        recipients.len(),
+        ErrorCode::MaximumRecipientsNumberExceeded,
+    );
+
+    if !recipients.is_empty() {
+        let mint_share_sum = recipients.iter().map(|r| r.mint_share).sum::<u8>();
+        let transaction_share_sum = recipients.iter().map(|r| r.transaction_share).sum::<u8>();
+
+        require_eq!(mint_share_sum, 100, ErrorCode::InvalidShares);
+        require_eq!(transaction_share_sum, 100, ErrorCode::InvalidShares);
+    }
+
+    Ok(())
+}
+
 pub fn check_recipients(
     config: &Account<Config>,
     recipients_token_accounts: &[AccountInfo],
@@ -168,12 +447,27 @@ pub fn calculate_amounts(
     config: &Config,
     remaining_accounts: &[AccountInfo],
     nft_type: NftType,
+    registry: Option<&NftTypeRegistry>,
+) -> Result<Vec<u64>> {
+    let fees = config
+        .fees
+        .of(nft_type, registry)
+        .ok_or(ErrorCode::UnknownNftType)?;
+
+    split_amount(config, remaining_accounts, fees, |r| r.mint_share)
+}
+
+// The first recipient receives the remainder of the rounding
+pub fn split_amount(
+    config: &Config,
+    remaining_accounts: &[AccountInfo],
+    total: u64,
+    share: impl Fn(&Recipient) -> u8,
 ) -> Result<Vec<u64>> {
     if config.recipients.is_empty() {
         return Ok(Vec::new());
     }
 
-    let fees = config.fees.of(nft_type);
     let mut amounts = Vec::with_capacity(config.recipients.len());
     amounts.push(0);
 
@@ -194,8 +488,8 @@ pub fn calculate_amounts(
             .find(|r| r.address == token_account_owner)
             .unwrap();
 
-        let amount = (fees as u128)
-            .checked_mul(recipient.mint_share.into())
+        let amount = (total as u128)
+            .checked_mul(share(recipient).into())
             .and_then(|a| a.checked_div(100))
             .and_then(|a| a.try_into().ok())
             .unwrap();
@@ -203,10 +497,20 @@ pub fn calculate_amounts(
         amounts.push(amount);
     }
 
-    amounts[0] = fees.checked_sub(amounts.iter().sum()).unwrap();
+    amounts[0] = total.checked_sub(amounts.iter().sum()).unwrap();
     Ok(amounts)
 }
 
+// The registry account is not created until the first custom type is
+// registered, only built-in types are available without it
+pub fn load_nft_type_registry(account: &AccountInfo) -> Result<Option<NftTypeRegistry>> {
+    if *account.owner != crate::ID {
+        return Ok(None);
+    }
+
+    NftTypeRegistry::try_deserialize(&mut account.data.borrow().as_ref()).map(Some)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn transfer_chill<'info>(
     chill_payer: &Signer<'info>,
@@ -234,3 +538,177 @@ pub fn transfer_chill<'info>(
 
     Ok(())
 }
+
+// Sends the NFT out of the escrow of the listing and closes the escrow, its
+// rent goes back to the seller
+pub fn release_nft_escrow<'info>(
+    nft_listing: &Account<'info, NftListing>,
+    nft_escrow: &Account<'info, TokenAccount>,
+    receiver_token_account: &Account<'info, TokenAccount>,
+    seller: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    let seeds = &[
+        NftListing::SEED,
+        nft_listing.nft_mint.as_ref(),
+        &[nft_listing.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        Transfer {
+            from: nft_escrow.to_account_info(),
+            to: receiver_token_account.to_account_info(),
+            authority: nft_listing.to_account_info(),
+        },
+        signer,
+    );
+
+    transfer(ctx, 1)?;
+
+    let ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: nft_escrow.to_account_info(),
+            destination: seller.clone(),
+            authority: nft_listing.to_account_info(),
+        },
+        signer,
+    );
+
+    close_account(ctx)
+}
+
+pub fn create_chill_metadata<'info>(
+    payer: &Signer<'info>,
+    nft_mint: &AccountInfo<'info>,
+    nft_chill_metadata: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    nft_type: NftType,
+    min_game_version: u32,
+    rent_receiver: Pubkey,
+) -> Result<()> {
+    let (nft_chill_metadata_key, bump) =
+        Pubkey::find_program_address(&[ChillNftMetadata::SEED, nft_mint.key.as_ref()], &crate::ID);
+
+    require_keys_eq!(
+        nft_chill_metadata.key(),
+        nft_chill_metadata_key,
+        ErrorCode::WrongMintBatch
+    );
+
+    let bump_seed = [bump];
+    let seeds = &[ChillNftMetadata::SEED, nft_mint.key.as_ref(), &bump_seed];
+    let signers = &[seeds.as_ref()];
+
+    let cpi_context = CpiContext::new_with_signer(
+        system_program.to_account_info(),
+        system_program::CreateAccount {
+            from: payer.to_account_info(),
+            to: nft_chill_metadata.clone(),
+        },
+        signers,
+    );
+
+    system_program::create_account(
+        cpi_context,
+        Rent::get()?.minimum_balance(ChillNftMetadata::LEN),
+        ChillNftMetadata::LEN as u64,
+        &crate::ID,
+    )?;
+
+    let chill_metadata = ChillNftMetadata {
+        bump,
+        nft_type,
+        min_game_version,
+        rent_receiver,
+        finalized: false,
+        version: ChillNftMetadata::VERSION,
+        attributes: NftAttributes::default(),
+    };
+
+    let mut data = nft_chill_metadata.try_borrow_mut_data()?;
+    chill_metadata.try_serialize(&mut data.as_mut())?;
+
+    Ok(())
+}
+
+pub fn realloc_with_rent<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+) -> Result<()> {
+    let minimum_balance = Rent::get()?.minimum_balance(new_len);
+    let lamports = account.lamports();
+
+    if minimum_balance > lamports {
+        let cpi_context = CpiContext::new(
+            system_program.to_account_info(),
+            system_program::Transfer {
+                from: payer.to_account_info(),
+                to: account.clone(),
+            },
+        );
+
+        system_program::transfer(cpi_context, minimum_balance - lamports)?;
+    }
+
+    account.realloc(new_len, false).map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn updated_creators() {
+        let primary_wallet = Pubkey::new_unique();
+        let creator = Pubkey::new_unique();
+        let current = nft_creators(primary_wallet, None);
+
+        let share = |address, share| NftCreator { address, share };
+
+        let creators = update_creators(
+            primary_wallet,
+            &current,
+            &[share(primary_wallet, 10), share(creator, 90)],
+        )
+        .unwrap();
+        assert!(creators[0].verified);
+        assert!(!creators[1].verified);
+        assert_eq!(creators[1].share, 90);
+
+        assert!(update_creators(primary_wallet, &current, &[share(creator, 100)]).is_err());
+        assert!(update_creators(primary_wallet, &current, &[share(primary_wallet, 99)]).is_err());
+        assert!(update_creators(
+            primary_wallet,
+            &current,
+            &[share(primary_wallet, 50), share(primary_wallet, 50)]
+        )
+        .is_err());
+        assert!(update_creators(
+            primary_wallet,
+            &current,
+            &[share(primary_wallet, 200), share(creator, 156)]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn fees_args() {
+        let fees = |character| Fees {
+            character,
+            ..Default::default()
+        };
+
+        assert!(check_fees_args(&fees(1_000_000 * 10u64.pow(9)), 9).is_ok());
+        assert!(check_fees_args(&fees(1_000_000 * 10u64.pow(9) + 1), 9).is_err());
+        assert!(check_fees_args(&fees(1_000_001), 0).is_err());
+        assert!(check_fees_args(&fees(u64::MAX), 19).is_ok());
+
+        assert!(check_fee_args(1_000_000, 0).is_ok());
+        assert!(check_fee_args(1_000_001, 0).is_err());
+    }
+}
@@ -1,12 +1,19 @@
 use crate::{
-    state::{StakingInfo, StakingTokenAuthority, UserInfo, DAYS_IN_WINDOW},
-    InitializeArgs,
+    state::{
+        DelegateInfo, NftMultiplierInfo, PaidBoostInfo, PrimaryWalletTransfer, Redistribution,
+        RedistributionReceipt, ReferralInfo, StakingInfo, StakingTokenAuthority, UserInfo,
+        DELEGATE_SEED, DEVNET_FAUCET_DECIMALS, DEVNET_FAUCET_MINT_SEED, DEVNET_FAUCET_SEED,
+        NFT_MULTIPLIER_SEED, PAID_BOOST_SEED, PRIMARY_WALLET_TRANSFER_SEED, REDISTRIBUTION_SEED,
+        REFERRAL_SEED,
+    },
+    utils, InitializeArgs, StakingErrorCode,
 };
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
     token::{Mint, Token, TokenAccount},
 };
+use chill_nft::state::AdminConfig;
 
 #[derive(Accounts)]
 #[instruction(args: InitializeArgs)]
@@ -60,16 +67,35 @@ pub struct CloseUserInfo<'info> {
     pub recipient: UncheckedAccount<'info>,
 }
 
+// User infos to close are passed as remaining accounts
 #[derive(Accounts)]
-pub struct AddRewardTokens<'info> {
+pub struct CloseStakingAccountsBatch<'info> {
     pub primary_wallet: Signer<'info>,
 
+    #[account(has_one = primary_wallet)]
+    pub staking_info: Account<'info, StakingInfo>,
+
+    /// CHECK: treasury
+    #[account(mut)]
+    pub treasury: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddRewardTokens<'info> {
+    /// CHECK: approved by signers of the admin config
+    pub primary_wallet: UncheckedAccount<'info>,
+
+    #[account(has_one = primary_wallet, seeds = [AdminConfig::SEED, primary_wallet.key().as_ref()],
+              bump = admin_config.bump, seeds::program = chill_nft::ID)]
+    pub admin_config: Account<'info, AdminConfig>,
+
     pub token_account_authority: Signer<'info>,
 
     #[account(mut, token::authority = token_account_authority, token::mint = staking_info.mint)]
     pub token_account: Account<'info, TokenAccount>,
 
-    #[account(mut, has_one = primary_wallet)]
+    #[account(mut, has_one = primary_wallet,
+              constraint = utils::is_staking_info_migrated(&staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
     pub staking_info: Account<'info, StakingInfo>,
 
     #[account(seeds = [staking_info.key().as_ref()], bump = staking_token_authority.bump)]
@@ -83,9 +109,15 @@ pub struct AddRewardTokens<'info> {
 
 #[derive(Accounts)]
 pub struct RedeemRemainingRewardTokens<'info> {
-    pub primary_wallet: Signer<'info>,
+    /// CHECK: approved by signers of the admin config
+    pub primary_wallet: UncheckedAccount<'info>,
+
+    #[account(has_one = primary_wallet, seeds = [AdminConfig::SEED, primary_wallet.key().as_ref()],
+              bump = admin_config.bump, seeds::program = chill_nft::ID)]
+    pub admin_config: Account<'info, AdminConfig>,
 
-    #[account(mut, has_one = primary_wallet)]
+    #[account(mut, has_one = primary_wallet,
+              constraint = utils::is_staking_info_migrated(&staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
     pub staking_info: Account<'info, StakingInfo>,
 
     #[account(seeds = [staking_info.key().as_ref()], bump = staking_token_authority.bump)]
@@ -100,24 +132,145 @@ pub struct RedeemRemainingRewardTokens<'info> {
     pub token_program: Program<'info, Token>,
 }
 
+#[derive(Accounts)]
+pub struct StartRedistribution<'info> {
+    pub primary_wallet: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut, has_one = primary_wallet,
+              constraint = utils::is_staking_info_migrated(&staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
+    pub staking_info: Account<'info, StakingInfo>,
+
+    #[account(init, payer = payer, space = Redistribution::LEN,
+              seeds = [REDISTRIBUTION_SEED, staking_info.key().as_ref()], bump)]
+    pub redistribution: Account<'info, Redistribution>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RedistributeUnspentReward<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut, has_one = staking_info,
+              constraint = utils::is_user_info_migrated(&user_info, &staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
+    pub user_info: Account<'info, UserInfo>,
+
+    #[account(constraint = utils::is_staking_info_migrated(&staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
+    pub staking_info: Account<'info, StakingInfo>,
+
+    #[account(mut, has_one = staking_info, seeds = [REDISTRIBUTION_SEED, staking_info.key().as_ref()],
+              bump = redistribution.bump)]
+    pub redistribution: Account<'info, Redistribution>,
+
+    #[account(init, payer = payer, space = RedistributionReceipt::LEN,
+              seeds = [REDISTRIBUTION_SEED, user_info.key().as_ref()], bump)]
+    pub redistribution_receipt: Account<'info, RedistributionReceipt>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DevnetUpdateStakingInfo<'info> {
+    pub primary_wallet: Signer<'info>,
+
+    #[account(mut, has_one = primary_wallet,
+              constraint = utils::is_staking_info_migrated(&staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
+    pub staking_info: Account<'info, StakingInfo>,
+}
+
+#[derive(Accounts)]
+pub struct DevnetDowngradeStakingInfo<'info> {
+    pub primary_wallet: Signer<'info>,
+
+    /// CHECK: rewritten into the legacy layout, the primary wallet is checked
+    /// by the instruction
+    #[account(mut, owner = crate::ID)]
+    pub staking_info: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DevnetInitializeFaucet<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: mint authority of the faucet mint, holds no data
+    #[account(seeds = [DEVNET_FAUCET_SEED], bump)]
+    pub faucet_authority: UncheckedAccount<'info>,
+
+    #[account(init, payer = payer, mint::decimals = DEVNET_FAUCET_DECIMALS, mint::authority = faucet_authority,
+              seeds = [DEVNET_FAUCET_MINT_SEED], bump)]
+    pub faucet_mint: Account<'info, Mint>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct DevnetFaucet<'info> {
+    /// CHECK: mint authority of the faucet mint, holds no data
+    #[account(seeds = [DEVNET_FAUCET_SEED], bump)]
+    pub faucet_authority: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [DEVNET_FAUCET_MINT_SEED], bump)]
+    pub faucet_mint: Account<'info, Mint>,
+
+    #[account(mut, token::mint = faucet_mint)]
+    pub token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
 #[derive(Accounts)]
 pub struct ViewState {}
 
 #[derive(Accounts)]
 pub struct ViewStaking<'info> {
+    #[account(constraint = utils::is_staking_info_migrated(&staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
     pub staking_info: Account<'info, StakingInfo>,
 }
 
 #[derive(Accounts)]
 pub struct ViewUser<'info> {
+    #[account(has_one = staking_info,
+              constraint = utils::is_user_info_migrated(&user_info, &staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
     pub user_info: Account<'info, UserInfo>,
+
+    #[account(constraint = utils::is_staking_info_migrated(&staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
+    pub staking_info: Account<'info, StakingInfo>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyPoolSolvency<'info> {
+    #[account(constraint = utils::is_staking_info_migrated(&staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
+    pub staking_info: Account<'info, StakingInfo>,
+
+    #[account(seeds = [staking_info.key().as_ref()], bump = staking_token_authority.bump)]
+    pub staking_token_authority: Account<'info, StakingTokenAuthority>,
+
+    #[account(associated_token::mint = staking_info.mint, associated_token::authority = staking_token_authority)]
+    pub staking_token_account: Account<'info, TokenAccount>,
+}
+
+#[derive(Accounts)]
+pub struct Reconcile<'info> {
+    #[account(constraint = utils::is_staking_info_migrated(&staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
+    pub staking_info: Account<'info, StakingInfo>,
 }
 
 #[derive(Accounts)]
 pub struct ViewUserRewardAmount<'info> {
-    #[account(has_one = staking_info)]
+    #[account(has_one = staking_info,
+              constraint = utils::is_user_info_migrated(&user_info, &staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
     pub user_info: Account<'info, UserInfo>,
 
+    #[account(constraint = utils::is_staking_info_migrated(&staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
     pub staking_info: Account<'info, StakingInfo>,
 }
 
@@ -133,13 +286,48 @@ pub struct Stake<'info> {
     #[account(mut, token::authority = token_account_authority, token::mint = staking_info.mint)]
     pub from_token_account: Account<'info, TokenAccount>,
 
-    #[account(init_if_needed, payer = payer, space = UserInfo::LEN + DAYS_IN_WINDOW as usize,
-              seeds = [staking_info.key().as_ref(), user.key().as_ref()], bump)]
+    #[account(mut,
+              constraint = utils::is_staking_info_migrated(&staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
+    pub staking_info: Account<'info, StakingInfo>,
+
+    #[account(init_if_needed, payer = payer, space = UserInfo::LEN + staking_info.days_in_window() as usize,
+              seeds = [staking_info.key().as_ref(), user.key().as_ref()], bump,
+              constraint = utils::is_user_info_migrated(&user_info, &staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
     pub user_info: Account<'info, UserInfo>,
 
+    #[account(seeds = [staking_info.key().as_ref()], bump = staking_token_authority.bump)]
+    pub staking_token_authority: Account<'info, StakingTokenAuthority>,
+
+    #[account(mut, associated_token::mint = staking_info.mint, associated_token::authority = staking_token_authority)]
+    pub staking_token_account: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct StakeFor<'info> {
+    /// CHECK: owner of the stake, doesn't sign
+    pub beneficiary: UncheckedAccount<'info>,
+
+    pub token_account_authority: Signer<'info>,
+
     #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut, token::authority = token_account_authority, token::mint = staking_info.mint)]
+    pub from_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut,
+              constraint = utils::is_staking_info_migrated(&staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
     pub staking_info: Account<'info, StakingInfo>,
 
+    #[account(init_if_needed, payer = payer, space = UserInfo::LEN + staking_info.days_in_window() as usize,
+              seeds = [staking_info.key().as_ref(), beneficiary.key().as_ref()], bump,
+              constraint = utils::is_user_info_migrated(&user_info, &staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
+    pub user_info: Account<'info, UserInfo>,
+
     #[account(seeds = [staking_info.key().as_ref()], bump = staking_token_authority.bump)]
     pub staking_token_authority: Account<'info, StakingTokenAuthority>,
 
@@ -155,13 +343,92 @@ pub struct Stake<'info> {
 pub struct Claim<'info> {
     pub user: Signer<'info>,
 
-    #[account(mut, seeds = [staking_info.key().as_ref(), user.key().as_ref()], bump = user_info.bump)]
+    #[account(mut, seeds = [staking_info.key().as_ref(), user.key().as_ref()], bump = user_info.bump,
+              constraint = utils::is_user_info_migrated(&user_info, &staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
     pub user_info: Account<'info, UserInfo>,
 
     #[account(mut, token::mint = staking_info.mint)]
     pub recipient_token_account: Account<'info, TokenAccount>,
 
-    #[account(mut)]
+    #[account(mut,
+              constraint = utils::is_staking_info_migrated(&staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
+    pub staking_info: Account<'info, StakingInfo>,
+
+    #[account(seeds = [staking_info.key().as_ref()], bump = staking_token_authority.bump)]
+    pub staking_token_authority: Account<'info, StakingTokenAuthority>,
+
+    #[account(mut, associated_token::mint = staking_info.mint, associated_token::authority = staking_token_authority)]
+    pub staking_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct DelegateClaim<'info> {
+    pub delegate: Signer<'info>,
+
+    #[account(has_one = delegate, has_one = user_info,
+              seeds = [DELEGATE_SEED, user_info.key().as_ref()], bump = delegate_info.bump)]
+    pub delegate_info: Account<'info, DelegateInfo>,
+
+    #[account(mut, has_one = staking_info,
+              constraint = utils::is_user_info_migrated(&user_info, &staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
+    pub user_info: Account<'info, UserInfo>,
+
+    #[account(mut, token::mint = staking_info.mint, token::authority = user_info.user)]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut,
+              constraint = utils::is_staking_info_migrated(&staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
+    pub staking_info: Account<'info, StakingInfo>,
+
+    #[account(seeds = [staking_info.key().as_ref()], bump = staking_token_authority.bump)]
+    pub staking_token_authority: Account<'info, StakingTokenAuthority>,
+
+    #[account(mut, associated_token::mint = staking_info.mint, associated_token::authority = staking_token_authority)]
+    pub staking_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimReferralReward<'info> {
+    pub referrer: Signer<'info>,
+
+    #[account(mut, has_one = referrer, has_one = user_info,
+              seeds = [REFERRAL_SEED, user_info.key().as_ref()], bump = referral_info.bump)]
+    pub referral_info: Account<'info, ReferralInfo>,
+
+    #[account(mut, has_one = staking_info,
+              constraint = utils::is_user_info_migrated(&user_info, &staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
+    pub user_info: Account<'info, UserInfo>,
+
+    #[account(mut, token::mint = staking_info.mint)]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut,
+              constraint = utils::is_staking_info_migrated(&staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
+    pub staking_info: Account<'info, StakingInfo>,
+
+    #[account(seeds = [staking_info.key().as_ref()], bump = staking_token_authority.bump)]
+    pub staking_token_authority: Account<'info, StakingTokenAuthority>,
+
+    #[account(mut, associated_token::mint = staking_info.mint, associated_token::authority = staking_token_authority)]
+    pub staking_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimAndRestake<'info> {
+    pub user: Signer<'info>,
+
+    #[account(mut, seeds = [staking_info.key().as_ref(), user.key().as_ref()], bump = user_info.bump,
+              constraint = utils::is_user_info_migrated(&user_info, &staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
+    pub user_info: Account<'info, UserInfo>,
+
+    #[account(mut,
+              constraint = utils::is_staking_info_migrated(&staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
     pub staking_info: Account<'info, StakingInfo>,
 
     #[account(seeds = [staking_info.key().as_ref()], bump = staking_token_authority.bump)]
@@ -177,9 +444,216 @@ pub struct Claim<'info> {
 pub struct UserUpdatesUserInfo<'info> {
     pub user: Signer<'info>,
 
-    #[account(mut, seeds = [staking_info.key().as_ref(), user.key().as_ref()], bump = user_info.bump)]
+    #[account(mut, seeds = [staking_info.key().as_ref(), user.key().as_ref()], bump = user_info.bump,
+              constraint = utils::is_user_info_migrated(&user_info, &staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
+    pub user_info: Account<'info, UserInfo>,
+
+    #[account(mut,
+              constraint = utils::is_staking_info_migrated(&staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
+    pub staking_info: Account<'info, StakingInfo>,
+}
+
+#[derive(Accounts)]
+pub struct DelegateUpdatesUserInfo<'info> {
+    pub delegate: Signer<'info>,
+
+    #[account(has_one = delegate, has_one = user_info,
+              seeds = [DELEGATE_SEED, user_info.key().as_ref()], bump = delegate_info.bump)]
+    pub delegate_info: Account<'info, DelegateInfo>,
+
+    #[account(mut, has_one = staking_info,
+              constraint = utils::is_user_info_migrated(&user_info, &staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
+    pub user_info: Account<'info, UserInfo>,
+
+    #[account(mut,
+              constraint = utils::is_staking_info_migrated(&staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
+    pub staking_info: Account<'info, StakingInfo>,
+}
+
+#[derive(Accounts)]
+pub struct PaidBoost<'info> {
+    pub user: Signer<'info>,
+
+    #[account(mut, seeds = [staking_info.key().as_ref(), user.key().as_ref()], bump = user_info.bump,
+              constraint = utils::is_user_info_migrated(&user_info, &staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
+    pub user_info: Account<'info, UserInfo>,
+
+    #[account(mut, address = staking_info.mint)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut, token::authority = user, token::mint = staking_info.mint)]
+    pub from_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut,
+              constraint = utils::is_staking_info_migrated(&staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
+    pub staking_info: Account<'info, StakingInfo>,
+
+    #[account(mut, has_one = staking_info,
+              seeds = [PAID_BOOST_SEED, staking_info.key().as_ref()], bump = paid_boost_info.bump)]
+    pub paid_boost_info: Account<'info, PaidBoostInfo>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterNftCollection<'info> {
+    /// CHECK: approved by signers of the admin config
+    pub primary_wallet: UncheckedAccount<'info>,
+
+    #[account(has_one = primary_wallet, seeds = [AdminConfig::SEED, primary_wallet.key().as_ref()],
+              bump = admin_config.bump, seeds::program = chill_nft::ID)]
+    pub admin_config: Account<'info, AdminConfig>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut, has_one = primary_wallet,
+              constraint = utils::is_staking_info_migrated(&staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
+    pub staking_info: Account<'info, StakingInfo>,
+
+    #[account(init_if_needed, payer = payer, space = NftMultiplierInfo::LEN,
+              seeds = [NFT_MULTIPLIER_SEED, staking_info.key().as_ref()], bump)]
+    pub nft_multiplier_info: Account<'info, NftMultiplierInfo>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPaidBoostCost<'info> {
+    /// CHECK: approved by signers of the admin config
+    pub primary_wallet: UncheckedAccount<'info>,
+
+    #[account(has_one = primary_wallet, seeds = [AdminConfig::SEED, primary_wallet.key().as_ref()],
+              bump = admin_config.bump, seeds::program = chill_nft::ID)]
+    pub admin_config: Account<'info, AdminConfig>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(has_one = primary_wallet,
+              constraint = utils::is_staking_info_migrated(&staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
+    pub staking_info: Account<'info, StakingInfo>,
+
+    #[account(init_if_needed, payer = payer, space = PaidBoostInfo::LEN,
+              seeds = [PAID_BOOST_SEED, staking_info.key().as_ref()], bump)]
+    pub paid_boost_info: Account<'info, PaidBoostInfo>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetDelegate<'info> {
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(has_one = user)]
     pub user_info: Account<'info, UserInfo>,
 
+    #[account(init_if_needed, payer = payer, space = DelegateInfo::LEN,
+              seeds = [DELEGATE_SEED, user_info.key().as_ref()], bump)]
+    pub delegate_info: Account<'info, DelegateInfo>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeDelegate<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut, has_one = user, close = user)]
+    pub delegate_info: Account<'info, DelegateInfo>,
+}
+
+#[derive(Accounts)]
+pub struct SettleStake<'info> {
+    #[account(mut, has_one = staking_info,
+              constraint = utils::is_user_info_migrated(&user_info, &staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
+    pub user_info: Account<'info, UserInfo>,
+
+    #[account(mut,
+              constraint = utils::is_staking_info_migrated(&staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
+    pub staking_info: Account<'info, StakingInfo>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeNewPrimaryWallet<'info> {
+    #[account(mut)]
+    pub primary_wallet: Signer<'info>,
+
+    #[account(has_one = primary_wallet,
+              constraint = utils::is_staking_info_migrated(&staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
+    pub staking_info: Account<'info, StakingInfo>,
+
+    #[account(init_if_needed, payer = primary_wallet, space = PrimaryWalletTransfer::LEN,
+              seeds = [PRIMARY_WALLET_TRANSFER_SEED, staking_info.key().as_ref()], bump)]
+    pub primary_wallet_transfer: Account<'info, PrimaryWalletTransfer>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptPrimaryWallet<'info> {
+    pub new_primary_wallet: Signer<'info>,
+
+    /// CHECK: receives the rent of the transfer account
     #[account(mut)]
+    pub primary_wallet: UncheckedAccount<'info>,
+
+    #[account(mut, has_one = primary_wallet,
+              constraint = utils::is_staking_info_migrated(&staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
+    pub staking_info: Account<'info, StakingInfo>,
+
+    #[account(mut, close = primary_wallet, has_one = staking_info, has_one = new_primary_wallet,
+              seeds = [PRIMARY_WALLET_TRANSFER_SEED, staking_info.key().as_ref()],
+              bump = primary_wallet_transfer.bump)]
+    pub primary_wallet_transfer: Account<'info, PrimaryWalletTransfer>,
+}
+
+#[derive(Accounts)]
+pub struct PrimaryWalletUpdatesStakingInfo<'info> {
+    pub primary_wallet: Signer<'info>,
+
+    #[account(mut, has_one = primary_wallet,
+              constraint = utils::is_staking_info_migrated(&staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
     pub staking_info: Account<'info, StakingInfo>,
 }
+
+#[derive(Accounts)]
+pub struct MigrateStakingInfo<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: legacy layouts are too short to be deserialized, the instruction
+    /// reads the account and checks its mint
+    #[account(mut, owner = crate::ID)]
+    pub staking_info: UncheckedAccount<'info>,
+
+    #[account(seeds = [staking_info.key().as_ref()], bump = staking_token_authority.bump)]
+    pub staking_token_authority: Account<'info, StakingTokenAuthority>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(associated_token::mint = mint, associated_token::authority = staking_token_authority)]
+    pub staking_token_account: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateUserInfo<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: legacy layouts are too short to be deserialized, the instruction
+    /// reads the account and checks its staking info
+    #[account(mut, owner = crate::ID)]
+    pub user_info: UncheckedAccount<'info>,
+
+    #[account(constraint = utils::is_staking_info_migrated(&staking_info) @ StakingErrorCode::AccountIsNotMigrated)]
+    pub staking_info: Account<'info, StakingInfo>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -17,6 +17,18 @@ pub struct Claim {
     pub amount: u64,
 }
 
+#[event]
+pub struct ClaimFee {
+    pub user: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ClaimAndRestake {
+    pub user: Pubkey,
+    pub amount: u64,
+}
+
 #[event]
 pub struct TransferRewardToPendingAmount {
     pub user: Pubkey,
@@ -28,7 +40,74 @@ pub struct Boost {
     pub user: Pubkey,
 }
 
+#[event]
+pub struct PaidBoost {
+    pub user: Pubkey,
+    pub day: u64,
+    pub amount: u64,
+}
+
 #[event]
 pub struct Cancel {
     pub user: Pubkey,
 }
+
+#[event]
+pub struct Unstake {
+    pub user: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct StartRedistribution {
+    pub amount: u64,
+}
+
+#[event]
+pub struct RedistributeUnspentReward {
+    pub user: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RegisterReferrer {
+    pub user: Pubkey,
+    pub referrer: Pubkey,
+}
+
+#[event]
+pub struct ClaimReferralReward {
+    pub referrer: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct SetDelegate {
+    pub user: Pubkey,
+    pub delegate: Pubkey,
+}
+
+#[event]
+pub struct RevokeDelegate {
+    pub user: Pubkey,
+    pub delegate: Pubkey,
+}
+
+#[event]
+pub struct ProposeStakingPrimaryWallet {
+    pub primary_wallet: Pubkey,
+    pub new_primary_wallet: Pubkey,
+}
+
+#[event]
+pub struct AcceptStakingPrimaryWallet {
+    pub old_primary_wallet: Pubkey,
+    pub new_primary_wallet: Pubkey,
+}
+
+#[event]
+pub struct Pause {}
+
+#[event]
+pub struct Resume {}
@@ -29,7 +29,7 @@ where
         let free_space = data_len
             .checked_sub(offset)
             .and_then(|v| v.checked_div(elem_size))
-            .unwrap();
+            .ok_or(StakingErrorCode::WrongVectorSize)?;
 
         require_gte!(free_space, size, StakingErrorCode::WrongVectorSize);
 
@@ -52,9 +52,11 @@ where
         let from = index
             .checked_mul(self.elem_size)
             .and_then(|v| v.checked_add(self.offset))
-            .unwrap();
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
-        let to = from.checked_add(self.elem_size).unwrap();
+        let to = from
+            .checked_add(self.elem_size)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
         let data = self.data.borrow();
         let mut slice = &data[from..to];
 
@@ -67,9 +69,11 @@ where
         let from = index
             .checked_mul(self.elem_size)
             .and_then(|v| v.checked_add(self.offset))
-            .unwrap();
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
-        let to = from.checked_add(self.elem_size).unwrap();
+        let to = from
+            .checked_add(self.elem_size)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
         let mut data = self.data.borrow_mut();
 
         let raw_value = value.try_to_vec()?;
@@ -80,15 +84,16 @@ where
         Ok(())
     }
 
-    pub fn clear(&mut self) {
+    pub fn clear(&mut self) -> Result<()> {
         let from = self.offset;
         let to = self
             .size
             .checked_mul(self.elem_size)
             .and_then(|v| v.checked_add(from))
-            .unwrap();
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
         self.data.borrow_mut()[from..to].fill(0);
+        Ok(())
     }
 }
 
@@ -179,6 +184,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn lazy_vector_with_offset_after_end() {
+        let mut buffer = [0; 16];
+        let data = Rc::new(RefCell::new(buffer.as_mut()));
+
+        match LazyVector::<u64>::new(17, 0, 8, data) {
+            Err(Error::AnchorError(error)) => assert_eq!(
+                error.error_code_number,
+                u32::from(StakingErrorCode::WrongVectorSize)
+            ),
+            _ => panic!("Expected an error"),
+        }
+    }
+
     #[test]
     fn clear() {
         let mut vector = vec![0, 1, 2, 3, 4, 5];
@@ -191,7 +210,7 @@ mod tests {
         assert_eq!(lazy_vector.get(2).unwrap(), 4);
         assert!(lazy_vector.get(3).is_err());
 
-        lazy_vector.clear();
+        lazy_vector.clear().unwrap();
         assert_eq!(lazy_vector.get(0).unwrap(), 0);
         assert_eq!(lazy_vector.get(1).unwrap(), 0);
         assert_eq!(lazy_vector.get(2).unwrap(), 0);
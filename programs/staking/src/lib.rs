@@ -1,10 +1,15 @@
 use crate::{
     context::*,
-    lazy_vector::GetLazyVector,
-    state::{DAYS_IN_WINDOW, SEC_PER_DAY},
+    lazy_vector::{GetLazyVector, LazyVector},
+    state::{
+        Redistribution, ReferralInfo, StakingInfo, UserInfo, BASIS_POINTS, BOOST_MULTIPLIER,
+        DEVNET_FAUCET_SEED, MAX_DAYS_IN_WINDOW, MAX_NFT_REWARD_BONUS, SEC_PER_DAY,
+    },
 };
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, AccountsClose, Discriminator};
 use anchor_spl::token;
+use chill_nft::utils::check_admin_signers;
+use std::{cell::RefCell, rc::Rc};
 
 pub mod context;
 pub mod event;
@@ -19,22 +24,109 @@ pub struct InitializeArgs {
     pub start_time: u64,
     pub end_time: u64,
     pub min_stake_size: u64,
+    pub claim_fee: u16,
+    pub days_in_window: u16,
+    pub referral_fee: u16,
 }
 
 impl InitializeArgs {
     pub fn start_day(&self) -> u64 {
-        self.start_time.checked_div(SEC_PER_DAY).unwrap()
+        self.start_time / SEC_PER_DAY
     }
 
     pub fn end_day(&self) -> u64 {
-        self.end_time.checked_div(SEC_PER_DAY).unwrap()
+        self.end_time / SEC_PER_DAY
     }
 
-    pub fn total_days(&self) -> usize {
-        self.end_day().checked_sub(self.start_day()).unwrap() as usize
+    pub fn total_days(&self) -> Result<usize> {
+        self.end_day()
+            .checked_sub(self.start_day())
+            .map(|days| days as usize)
+            .ok_or_else(|| StakingErrorCode::InvalidDayIndex.into())
     }
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SolvencyReport {
+    pub token_account_amount: u64,
+    pub free_reward_amount: u64,
+    pub pending_withdrawal_amount: u64,
+    pub required_amount: u64,
+    pub is_solvent: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct ReconcileReport {
+    // Counters stored in the staking info
+    pub total_staked_amount: u64,
+    pub total_rewarded_amount: u64,
+    pub active_stakes_number: u64,
+
+    // Sum of staked amounts of all days. Stakes add to it, cancels and
+    // unstakes subtract from it, so it always equals the total staked amount
+    pub days_staked_amount: u64,
+
+    // Sums of the remaining accounts. Closed user infos are gone, so the
+    // sums of all accounts of the staking may only fall short of the stored
+    // counters. Referral rewards and tokens of the redistribution which are
+    // not distributed yet are included in the rewarded amount
+    pub users_number: u64,
+    pub users_staked_amount: u64,
+    pub users_rewarded_amount: u64,
+    pub users_active_stakes_number: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BoostedDay {
+    // Number of the day since the Unix epoch, i.e. its start time divided by
+    // the length of a day
+    pub day: u64,
+
+    // Days after the end of the staking are not rewarded
+    pub is_eligible: bool,
+    pub is_boosted: bool,
+
+    // Multiplier of the user share of the daily reward
+    pub multiplier: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BoostCalendar {
+    pub start_day: Option<u64>,
+
+    // A day for each day of the window of the active stake
+    pub days: Vec<BoostedDay>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct UserSummary {
+    pub start_day: Option<u64>,
+    pub staked_amount: u64,
+    pub pending_amount: u64,
+    pub rewarded_amount: u64,
+
+    // A flag for each day of the window of the active stake
+    pub boosted_days: Vec<bool>,
+
+    // Reward of the active stake at the end of its window if nobody else
+    // stakes and the user doesn't boost anymore
+    pub projected_reward: u64,
+
+    // Daily reward of the active stake and the current daily reward of the
+    // staking which new stakes receive
+    pub daily_staking_reward: u64,
+    pub daily_reward: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ProjectedReward {
+    // The user doesn't boost anymore
+    pub min_reward: u64,
+
+    // The user boosts every remaining day of the window
+    pub max_reward: u64,
+}
+
 #[program]
 pub mod chill_staking {
 
@@ -55,6 +147,10 @@ pub mod chill_staking {
         utils::current_day()
     }
 
+    pub fn view_devnet_tools_enabled(_ctx: Context<ViewState>) -> Result<bool> {
+        Ok(cfg!(feature = "devnet-tools"))
+    }
+
     pub fn view_staked_amount_in_day(ctx: Context<ViewStaking>, index: u64) -> Result<u64> {
         let staking_info = &ctx.accounts.staking_info;
         let staked_amounts = staking_info.get_vector()?;
@@ -67,12 +163,263 @@ pub mod chill_staking {
         Ok(staking_info.last_daily_reward)
     }
 
-    pub fn view_boosted_days_list(ctx: Context<ViewUser>) -> Result<Vec<bool>> {
+    pub fn view_boost_calendar(ctx: Context<ViewUser>) -> Result<BoostCalendar> {
         let user_info = &ctx.accounts.user_info;
+        let staking_info = &ctx.accounts.staking_info;
+
+        let start_day = match user_info.start_day {
+            Some(start_day) => start_day,
+            None => {
+                return Ok(BoostCalendar {
+                    start_day: None,
+                    days: Vec::new(),
+                })
+            }
+        };
+
+        let boosted_days = user_info.get_vector()?;
+        let days = (0..boosted_days.len())
+            .map(|index| {
+                let day = start_day
+                    .checked_add(index as u64)
+                    .ok_or(StakingErrorCode::InvalidDayIndex)?;
+                let is_eligible = day < staking_info.end_day;
+                let is_boosted = boosted_days.get(index)?;
+                let multiplier = match (is_eligible, is_boosted) {
+                    (false, _) => 0,
+                    (true, true) => BOOST_MULTIPLIER,
+                    (true, false) => 1,
+                };
+
+                Ok(BoostedDay {
+                    day,
+                    is_eligible,
+                    is_boosted,
+                    multiplier,
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(BoostCalendar {
+            start_day: Some(start_day),
+            days,
+        })
+    }
+
+    pub fn view_user_summary(ctx: Context<ViewUser>) -> Result<UserSummary> {
+        let user_info = &mut ctx.accounts.user_info;
+        let staking_info = &mut ctx.accounts.staking_info;
+
+        utils::update_state_accounts(user_info, staking_info)?;
+
+        let (boosted_days, projected_reward) = if user_info.has_active_stake() {
+            let vector = user_info.get_vector()?;
+            let boosted_days = (0..vector.len())
+                .map(|index| vector.get(index))
+                .collect::<Result<Vec<_>>>()?;
+
+            let (reward, _) = utils::calculate_stake_reward(user_info, staking_info)?;
+            (boosted_days, reward)
+        } else {
+            (Vec::new(), 0)
+        };
+
+        Ok(UserSummary {
+            start_day: user_info.start_day,
+            staked_amount: user_info.staked_amount,
+            pending_amount: user_info.pending_amount,
+            rewarded_amount: user_info.rewarded_amount,
+            boosted_days,
+            projected_reward,
+            daily_staking_reward: user_info.daily_staking_reward,
+            daily_reward: staking_info.last_daily_reward,
+        })
+    }
+
+    // Both bounds assume that nobody else stakes until the end of the window
+    pub fn view_projected_reward(ctx: Context<ViewUser>) -> Result<ProjectedReward> {
+        let user_info = &mut ctx.accounts.user_info;
+        let staking_info = &mut ctx.accounts.staking_info;
+
+        utils::update_state_accounts(user_info, staking_info)?;
+
+        require!(
+            user_info.has_active_stake(),
+            StakingErrorCode::NoActiveStake
+        );
+
+        let (min_reward, _) = utils::calculate_stake_reward(user_info, staking_info)?;
+
+        // Views cannot modify accounts, so future boosts are set in a copy
         let boosted_days = user_info.get_vector()?;
-        Ok((0..DAYS_IN_WINDOW)
-            .map(|i| boosted_days.get(i as usize).unwrap())
-            .collect())
+        let mut buffer = (0..boosted_days.len())
+            .map(|index| boosted_days.get(index).map(u8::from))
+            .collect::<Result<Vec<_>>>()?;
+
+        let size = buffer.len();
+        let data = Rc::new(RefCell::new(buffer.as_mut_slice()));
+        let mut future_boosted_days = LazyVector::new(0, size, 1, data)?;
+
+        let current_index = staking_info
+            .current_day()?
+            .checked_sub(user_info.start_day.ok_or(StakingErrorCode::NoActiveStake)?)
+            .ok_or(StakingErrorCode::InvalidDayIndex)? as usize;
+
+        for index in current_index..size {
+            future_boosted_days.set(index, &true)?;
+        }
+
+        let (max_reward, _) = utils::calculate_stake_reward_with_boosts(
+            user_info,
+            staking_info,
+            &future_boosted_days,
+        )?;
+
+        Ok(ProjectedReward {
+            min_reward,
+            max_reward,
+        })
+    }
+
+    pub fn verify_pool_solvency(ctx: Context<VerifyPoolSolvency>) -> Result<SolvencyReport> {
+        let staking_info = &ctx.accounts.staking_info;
+        let token_account_amount = ctx.accounts.staking_token_account.amount;
+        let required_amount = staking_info.required_token_amount()?;
+
+        Ok(SolvencyReport {
+            token_account_amount,
+            free_reward_amount: staking_info.free_reward_amount()?,
+            pending_withdrawal_amount: staking_info.total_pending_withdrawal_amount,
+            required_amount,
+            is_solvent: token_account_amount >= required_amount,
+        })
+    }
+
+    /// Remaining accounts are user infos of the staking, each one optionally
+    /// followed by its referral info, and the redistribution of the staking.
+    /// Pools with many users are reconciled by summing reports of batches
+    pub fn reconcile<'info>(
+        ctx: Context<'_, '_, '_, 'info, Reconcile<'info>>,
+    ) -> Result<ReconcileReport> {
+        let staking_info = &ctx.accounts.staking_info;
+        let staking_info_key = staking_info.key();
+
+        let staked_amounts = staking_info.get_vector()?;
+        let days_staked_amount = (0..staked_amounts.len())
+            .map(|index| staked_amounts.get(index))
+            .sum::<Result<u64>>()?;
+
+        if days_staked_amount != staking_info.total_staked_amount {
+            msg!(
+                "Total staked amount diverges: stored {}, days {}",
+                staking_info.total_staked_amount,
+                days_staked_amount
+            );
+        }
+
+        let mut report = ReconcileReport {
+            total_staked_amount: staking_info.total_staked_amount,
+            total_rewarded_amount: staking_info.total_rewarded_amount,
+            active_stakes_number: staking_info.active_stakes_number,
+            days_staked_amount,
+            ..ReconcileReport::default()
+        };
+
+        let mut keys = Vec::with_capacity(ctx.remaining_accounts.len());
+        let mut last_user_info = None;
+
+        for account_info in ctx.remaining_accounts {
+            require_keys_eq!(
+                *account_info.owner,
+                crate::ID,
+                StakingErrorCode::WrongReconcileAccount
+            );
+
+            let discriminator = account_info
+                .try_borrow_data()?
+                .get(..8)
+                .map(<[u8]>::to_vec)
+                .ok_or(StakingErrorCode::WrongReconcileAccount)?;
+
+            if discriminator == UserInfo::discriminator() {
+                let user_info = Account::<UserInfo>::try_from(account_info)?;
+                require_keys_eq!(
+                    user_info.staking_info,
+                    staking_info_key,
+                    StakingErrorCode::WrongReconcileAccount
+                );
+                require!(
+                    utils::is_user_info_migrated(&user_info, staking_info),
+                    StakingErrorCode::AccountIsNotMigrated
+                );
+
+                report.users_number = report
+                    .users_number
+                    .checked_add(1)
+                    .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+                report.users_staked_amount = report
+                    .users_staked_amount
+                    .checked_add(user_info.total_staked_amount)
+                    .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+                report.users_rewarded_amount = report
+                    .users_rewarded_amount
+                    .checked_add(user_info.total_rewarded_amount)
+                    .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+                if user_info.has_active_stake() {
+                    report.users_active_stakes_number = report
+                        .users_active_stakes_number
+                        .checked_add(1)
+                        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+                }
+
+                last_user_info = Some(account_info.key());
+            } else if discriminator == ReferralInfo::discriminator() {
+                let referral_info = Account::<ReferralInfo>::try_from(account_info)?;
+                require!(
+                    last_user_info == Some(referral_info.user_info),
+                    StakingErrorCode::WrongReconcileAccount
+                );
+
+                report.users_rewarded_amount = report
+                    .users_rewarded_amount
+                    .checked_add(referral_info.total_referral_amount)
+                    .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+            } else if discriminator == Redistribution::discriminator() {
+                let redistribution = Account::<Redistribution>::try_from(account_info)?;
+                require_keys_eq!(
+                    redistribution.staking_info,
+                    staking_info_key,
+                    StakingErrorCode::WrongReconcileAccount
+                );
+
+                let undistributed_amount = redistribution
+                    .amount
+                    .checked_sub(redistribution.distributed_amount)
+                    .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+                report.users_rewarded_amount = report
+                    .users_rewarded_amount
+                    .checked_add(undistributed_amount)
+                    .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+            } else {
+                return err!(StakingErrorCode::WrongReconcileAccount);
+            }
+
+            keys.push(account_info.key());
+        }
+
+        // Accounts passed twice would be counted twice
+        keys.sort_unstable();
+        keys.dedup();
+        require_eq!(
+            keys.len(),
+            ctx.remaining_accounts.len(),
+            StakingErrorCode::WrongReconcileAccount
+        );
+
+        Ok(report)
     }
 
     // Methods
@@ -96,11 +443,32 @@ pub mod chill_staking {
             StakingErrorCode::EndDayMustBeBiggerThanStartDay,
         );
 
+        require_gte!(
+            BASIS_POINTS,
+            u64::from(args.claim_fee),
+            StakingErrorCode::ClaimFeeOutOfRange
+        );
+
+        require!(
+            (1..=MAX_DAYS_IN_WINDOW).contains(&u64::from(args.days_in_window)),
+            StakingErrorCode::WindowLengthOutOfRange
+        );
+
+        require_gte!(
+            BASIS_POINTS,
+            u64::from(args.referral_fee),
+            StakingErrorCode::ReferralFeeOutOfRange
+        );
+
         staking_info.primary_wallet = ctx.accounts.primary_wallet.key();
         staking_info.mint = ctx.accounts.mint.key();
         staking_info.min_stake_size = args.min_stake_size;
         staking_info.start_day = start_day;
         staking_info.end_day = end_day;
+        staking_info.claim_fee = args.claim_fee;
+        staking_info.days_in_window = args.days_in_window;
+        staking_info.referral_fee = args.referral_fee;
+        staking_info.version = StakingInfo::VERSION;
 
         staking_info.get_vector()?;
 
@@ -123,7 +491,7 @@ pub mod chill_staking {
             .staked_amount
             .checked_add(user_info.pending_amount)
             .and_then(|v| v.checked_add(user_info.rewarded_amount))
-            .unwrap();
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
         require_eq!(
             remainings_tokens,
@@ -134,14 +502,54 @@ pub mod chill_staking {
         Ok(())
     }
 
+    // User infos without tokens to withdraw are closed by the primary wallet
+    // once the staking is finished, their rent goes to the treasury
+    pub fn close_staking_accounts_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, CloseStakingAccountsBatch<'info>>,
+    ) -> Result<()> {
+        let staking_info = &ctx.accounts.staking_info;
+        staking_info.assert_finished()?;
+
+        // A user info passed twice is already closed when it's met again, so
+        // its deserialization fails
+        for account_info in ctx.remaining_accounts {
+            let user_info = Account::<UserInfo>::try_from(account_info)?;
+            require_keys_eq!(
+                user_info.staking_info,
+                staking_info.key(),
+                StakingErrorCode::WrongUserInfoAccount
+            );
+
+            let remainings_tokens = user_info
+                .staked_amount
+                .checked_add(user_info.pending_amount)
+                .and_then(|v| v.checked_add(user_info.rewarded_amount))
+                .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+            require_eq!(
+                remainings_tokens,
+                0,
+                StakingErrorCode::UserInfoHasTokensToWithdraw
+            );
+
+            user_info.close(ctx.accounts.treasury.to_account_info())?;
+        }
+
+        Ok(())
+    }
+
+    // Signers of the admin config of the primary wallet are passed as remaining
+    // accounts
     pub fn add_reward_tokens(ctx: Context<AddRewardTokens>, amount: u64) -> Result<()> {
+        check_admin_signers(&ctx.accounts.admin_config, ctx.remaining_accounts)?;
+
         let staking_info = &mut ctx.accounts.staking_info;
         staking_info.assert_not_started()?;
 
         staking_info.reward_tokens_amount = staking_info
             .reward_tokens_amount
             .checked_add(amount)
-            .unwrap();
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
         let cpi_context = CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
@@ -162,15 +570,13 @@ pub mod chill_staking {
         ctx: Context<RedeemRemainingRewardTokens>,
         amount: u64,
     ) -> Result<()> {
+        check_admin_signers(&ctx.accounts.admin_config, ctx.remaining_accounts)?;
+
         let staking_info = &mut ctx.accounts.staking_info;
 
         staking_info.assert_finished()?;
 
-        let free_amount = staking_info
-            .reward_tokens_amount
-            .checked_sub(staking_info.total_rewarded_amount)
-            .unwrap();
-
+        let free_amount = staking_info.free_reward_amount()?;
         require_gte!(free_amount, amount, StakingErrorCode::InsufficientFunds);
 
         utils::transfer_tokens(
@@ -185,16 +591,24 @@ pub mod chill_staking {
         staking_info.reward_tokens_amount = staking_info
             .reward_tokens_amount
             .checked_sub(amount)
-            .unwrap();
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
         Ok(())
     }
 
-    pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
+    // The referral account of the user must be passed as the first remaining
+    // account with a referrer. Accounts of an NFT of the registered collection
+    // follow it to start the stake with the reward bonus of NFT holders
+    pub fn stake<'info>(
+        ctx: Context<'_, '_, '_, 'info, Stake<'info>>,
+        amount: u64,
+        referrer: Option<Pubkey>,
+    ) -> Result<()> {
         let user_info = &mut ctx.accounts.user_info;
         let staking_info = &mut ctx.accounts.staking_info;
 
         staking_info.assert_active()?;
+        staking_info.assert_not_paused()?;
 
         utils::update_state_accounts(user_info, staking_info)?;
 
@@ -202,7 +616,32 @@ pub mod chill_staking {
         user_info.user = ctx.accounts.user.key();
         user_info.staking_info = staking_info.key();
         user_info.bump = bump;
+        user_info.version = UserInfo::VERSION;
+
+        let mut nft_accounts = ctx.remaining_accounts;
+        if let Some(referrer) = referrer {
+            utils::register_referrer(
+                referrer,
+                ctx.remaining_accounts,
+                user_info,
+                &ctx.accounts.payer,
+                &ctx.accounts.system_program,
+            )?;
+            nft_accounts = &nft_accounts[1..];
+        }
 
+        // The account recording the stake which receives the bonus of the NFT
+        // follows the accounts of the NFT
+        let nft_holder = match nft_accounts {
+            [] => None,
+            [holder_accounts @ .., nft_bonus_info] => {
+                let nft_mint =
+                    utils::verify_nft_holder(holder_accounts, user_info.user, staking_info)?;
+                Some((nft_mint, nft_bonus_info))
+            }
+        };
+
+        let has_active_stake = user_info.has_active_stake();
         let cpi_context = CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
             token::Transfer {
@@ -218,60 +657,69 @@ pub mod chill_staking {
             amount
         });
 
-        if user_info.has_active_stake() {
-            require_neq!(amount, 0, StakingErrorCode::AddZeroTokensToPendingAmount);
-            user_info.pending_amount = user_info.pending_amount.checked_add(amount).unwrap();
-            return Ok(());
+        staking_info.total_pending_withdrawal_amount = staking_info
+            .total_pending_withdrawal_amount
+            .checked_add(amount)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+        utils::add_stake(user_info, staking_info, amount)?;
+
+        // The bonus is kept in the daily reward of the stake until it ends
+        if let Some((nft_mint, nft_bonus_info)) = nft_holder.filter(|_| !has_active_stake) {
+            utils::use_nft_bonus(
+                nft_bonus_info,
+                nft_mint,
+                user_info,
+                staking_info,
+                &ctx.accounts.payer,
+                &ctx.accounts.system_program,
+            )?;
+
+            user_info.daily_staking_reward =
+                staking_info.with_nft_reward_bonus(user_info.daily_staking_reward)?;
         }
 
-        let increment = user_info.pending_amount.checked_add(amount).unwrap();
-        user_info.staked_amount = user_info.staked_amount.checked_add(increment).unwrap();
-        user_info.pending_amount = 0;
-
-        require_neq!(
-            user_info.staked_amount,
-            0,
-            StakingErrorCode::StakeZeroTokens
-        );
-
-        require_gte!(
-            user_info.staked_amount,
-            staking_info.min_stake_size,
-            StakingErrorCode::SmallStakeSize
-        );
-
-        user_info.daily_staking_reward = staking_info.last_daily_reward;
-        user_info.start_day = Some(utils::current_day()?);
-        user_info.total_staked_amount = user_info
-            .total_staked_amount
-            .checked_add(user_info.staked_amount)
-            .unwrap();
+        Ok(())
+    }
 
-        let mut user_boosted_days = user_info.get_vector()?;
-        user_boosted_days.clear();
+    // Tokens of the authority are staked for the beneficiary, who alone can
+    // claim them. The beneficiary doesn't sign, so no referrer is registered
+    pub fn stake_for(ctx: Context<StakeFor>, amount: u64) -> Result<()> {
+        let user_info = &mut ctx.accounts.user_info;
+        let staking_info = &mut ctx.accounts.staking_info;
 
-        let mut staked_amounts = staking_info.get_vector()?;
-        let day_index = staking_info.day_index()? as usize;
-        let previous_amount = staked_amounts.get(day_index)?;
-        let new_amount = previous_amount
-            .checked_add(user_info.staked_amount)
-            .unwrap();
+        staking_info.assert_active()?;
+        staking_info.assert_not_paused()?;
 
-        staked_amounts.set(day_index, &new_amount)?;
+        utils::update_state_accounts(user_info, staking_info)?;
 
-        staking_info.last_day_with_stake = utils::current_day()?;
+        let bump = ctx.bumps["user_info"];
+        user_info.user = ctx.accounts.beneficiary.key();
+        user_info.staking_info = staking_info.key();
+        user_info.bump = bump;
+        user_info.version = UserInfo::VERSION;
 
-        staking_info.active_stakes_number =
-            staking_info.active_stakes_number.checked_add(1).unwrap();
+        let cpi_context = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.from_token_account.to_account_info(),
+                to: ctx.accounts.staking_token_account.to_account_info(),
+                authority: ctx.accounts.token_account_authority.to_account_info(),
+            },
+        );
 
-        staking_info.total_stakes_number = staking_info.total_stakes_number.checked_add(1).unwrap();
+        token::transfer(cpi_context, amount)?;
+        emit!(event::Stake {
+            user: ctx.accounts.beneficiary.key(),
+            amount
+        });
 
-        staking_info.total_staked_amount = staking_info
-            .total_staked_amount
-            .checked_add(user_info.staked_amount)
-            .unwrap();
+        staking_info.total_pending_withdrawal_amount = staking_info
+            .total_pending_withdrawal_amount
+            .checked_add(amount)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
-        Ok(())
+        utils::add_stake(user_info, staking_info, amount)
     }
 
     pub fn cancel(ctx: Context<UserUpdatesUserInfo>) -> Result<()> {
@@ -286,56 +734,77 @@ pub mod chill_staking {
         );
 
         let boosted_days = user_info.get_vector()?;
-        let boost_number = (0..DAYS_IN_WINDOW)
-            .map(|day| boosted_days.get(day as usize).unwrap() as u64)
-            .sum();
+        let boost_number = (0..boosted_days.len())
+            .map(|day| boosted_days.get(day).map(u64::from))
+            .sum::<Result<u64>>()?;
 
-        let user_start_day_index = user_info
+        let user_start_day = user_info
             .start_day
-            .unwrap()
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+        let user_start_day_index = user_start_day
             .checked_sub(staking_info.start_day)
-            .unwrap() as usize;
+            .ok_or(StakingErrorCode::InvalidDayIndex)? as usize;
 
         let mut staked_amounts = staking_info.get_vector()?;
         let total_staked_that_day = staked_amounts.get(user_start_day_index)?;
         let new_staked_amount = total_staked_that_day
             .checked_sub(user_info.staked_amount)
-            .unwrap();
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
         staked_amounts.set(user_start_day_index, &new_staked_amount)?;
 
-        staking_info.total_stakes_number = staking_info.total_stakes_number.checked_sub(1).unwrap();
+        staking_info.total_stakes_number = staking_info
+            .total_stakes_number
+            .checked_sub(1)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
-        staking_info.active_stakes_number =
-            staking_info.active_stakes_number.checked_sub(1).unwrap();
+        staking_info.active_stakes_number = staking_info
+            .active_stakes_number
+            .checked_sub(1)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+        let stake_weight = staking_info.stake_weight(user_start_day, user_info.staked_amount)?;
 
         staking_info.total_staked_amount = staking_info
             .total_staked_amount
             .checked_sub(user_info.staked_amount)
-            .unwrap();
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+        staking_info.total_stake_weight = staking_info
+            .total_stake_weight
+            .checked_sub(stake_weight)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
         staking_info.total_boost_number = staking_info
             .total_boost_number
             .checked_sub(boost_number)
-            .unwrap();
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
-        staking_info.total_cancel_number = staking_info.total_cancel_number.checked_add(1).unwrap();
+        staking_info.total_cancel_number = staking_info
+            .total_cancel_number
+            .checked_add(1)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
         user_info.start_day = None;
 
         user_info.total_boost_number = user_info
             .total_boost_number
             .checked_sub(boost_number)
-            .unwrap();
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
         user_info.total_staked_amount = user_info
             .total_staked_amount
             .checked_sub(user_info.staked_amount)
-            .unwrap();
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+        user_info.total_stake_weight = user_info
+            .total_stake_weight
+            .checked_sub(stake_weight)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
         user_info.pending_amount = user_info
             .pending_amount
             .checked_add(user_info.staked_amount)
-            .unwrap();
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
         user_info.staked_amount = 0;
 
@@ -346,7 +815,7 @@ pub mod chill_staking {
         Ok(())
     }
 
-    pub fn claim(ctx: Context<Claim>, amount: u64) -> Result<()> {
+    pub fn unstake(ctx: Context<UserUpdatesUserInfo>, amount: u64) -> Result<()> {
         require_neq!(amount, 0u64, StakingErrorCode::WithdrawZeroTokens);
 
         let user_info = &mut ctx.accounts.user_info;
@@ -354,28 +823,173 @@ pub mod chill_staking {
 
         utils::update_state_accounts(user_info, staking_info)?;
 
-        let total_amount = user_info
+        require!(
+            user_info.has_active_stake(),
+            StakingErrorCode::NoActiveStake
+        );
+
+        require_gt!(
+            user_info.staked_amount,
+            amount,
+            StakingErrorCode::InsufficientFunds
+        );
+
+        let remaining_amount = user_info
+            .staked_amount
+            .checked_sub(amount)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+        require_gte!(
+            remaining_amount,
+            staking_info.min_stake_size,
+            StakingErrorCode::SmallStakeSize
+        );
+
+        // The remaining stake is rewarded as if it was staked from the start day
+        let user_start_day = user_info
+            .start_day
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+        let user_start_day_index = user_start_day
+            .checked_sub(staking_info.start_day)
+            .ok_or(StakingErrorCode::InvalidDayIndex)? as usize;
+
+        let mut staked_amounts = staking_info.get_vector()?;
+        let total_staked_that_day = staked_amounts.get(user_start_day_index)?;
+        let new_staked_amount = total_staked_that_day
+            .checked_sub(amount)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+        staked_amounts.set(user_start_day_index, &new_staked_amount)?;
+
+        let stake_weight = staking_info.stake_weight(user_start_day, amount)?;
+
+        staking_info.total_staked_amount = staking_info
+            .total_staked_amount
+            .checked_sub(amount)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+        staking_info.total_stake_weight = staking_info
+            .total_stake_weight
+            .checked_sub(stake_weight)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+        user_info.total_staked_amount = user_info
+            .total_staked_amount
+            .checked_sub(amount)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+        user_info.total_stake_weight = user_info
+            .total_stake_weight
+            .checked_sub(stake_weight)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+        user_info.staked_amount = remaining_amount;
+        user_info.pending_amount = user_info
+            .pending_amount
+            .checked_add(amount)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+        emit!(event::Unstake {
+            user: ctx.accounts.user.key(),
+            amount
+        });
+
+        Ok(())
+    }
+
+    pub fn claim<'info>(ctx: Context<'_, '_, '_, 'info, Claim<'info>>, amount: u64) -> Result<()> {
+        utils::claim(
+            amount,
+            ctx.remaining_accounts,
+            &mut ctx.accounts.user_info,
+            &mut ctx.accounts.staking_info,
+            &ctx.accounts.staking_token_authority,
+            &ctx.accounts.staking_token_account,
+            &ctx.accounts.recipient_token_account,
+            &ctx.accounts.token_program,
+        )
+    }
+
+    // The amount is calculated by the transaction, so the reward of a stake
+    // which ends meanwhile is claimed too
+    pub fn claim_all<'info>(ctx: Context<'_, '_, '_, 'info, Claim<'info>>) -> Result<()> {
+        let user_info = &mut ctx.accounts.user_info;
+        let staking_info = &mut ctx.accounts.staking_info;
+
+        utils::update_state_accounts(user_info, staking_info)?;
+
+        let amount = user_info
             .rewarded_amount
             .checked_add(user_info.pending_amount)
-            .unwrap();
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+        utils::claim(
+            amount,
+            ctx.remaining_accounts,
+            user_info,
+            staking_info,
+            &ctx.accounts.staking_token_authority,
+            &ctx.accounts.staking_token_account,
+            &ctx.accounts.recipient_token_account,
+            &ctx.accounts.token_program,
+        )
+    }
+
+    // Tokens claimed by a delegate are transferred to a token account of the
+    // user only
+    pub fn delegate_claim<'info>(
+        ctx: Context<'_, '_, '_, 'info, DelegateClaim<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        utils::claim(
+            amount,
+            ctx.remaining_accounts,
+            &mut ctx.accounts.user_info,
+            &mut ctx.accounts.staking_info,
+            &ctx.accounts.staking_token_authority,
+            &ctx.accounts.staking_token_account,
+            &ctx.accounts.recipient_token_account,
+            &ctx.accounts.token_program,
+        )
+    }
 
-        require_gte!(total_amount, amount, StakingErrorCode::InsufficientFunds);
+    // Referral rewards accrue once stakes of the referred user end
+    pub fn claim_referral_reward(ctx: Context<ClaimReferralReward>) -> Result<()> {
+        let user_info = &mut ctx.accounts.user_info;
+        let staking_info = &mut ctx.accounts.staking_info;
+        let referral_info = &mut ctx.accounts.referral_info;
 
-        if amount > user_info.rewarded_amount {
-            user_info.pending_amount = u128::from(user_info.pending_amount)
-                .checked_add(user_info.rewarded_amount.into())
-                .and_then(|v| v.checked_sub(amount.into()))
-                .unwrap()
-                .try_into()
-                .unwrap();
+        utils::update_state_accounts(user_info, staking_info)?;
 
-            user_info.rewarded_amount = 0;
-        } else {
-            user_info.rewarded_amount = user_info.rewarded_amount.checked_sub(amount).unwrap();
-        }
+        let reward = user_info
+            .total_rewarded_amount
+            .checked_sub(referral_info.accounted_reward_amount)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
-        emit!(event::Claim {
-            user: ctx.accounts.user.key(),
+        let amount = staking_info.referral_fee_of(reward)?;
+        require_neq!(amount, 0, StakingErrorCode::WithdrawZeroTokens);
+
+        // Reward tokens are reserved for referral rewards by the daily reward
+        // calculation, but the redistribution of unspent tokens takes them.
+        // The reward stays unaccounted rather than being paid in part
+        require_gte!(
+            staking_info.free_reward_amount()?,
+            amount,
+            StakingErrorCode::ReferralRewardIsNotCovered
+        );
+
+        referral_info.accounted_reward_amount = user_info.total_rewarded_amount;
+        referral_info.total_referral_amount = referral_info
+            .total_referral_amount
+            .checked_add(amount)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+        staking_info.total_rewarded_amount = staking_info
+            .total_rewarded_amount
+            .checked_add(amount)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+        emit!(event::ClaimReferralReward {
+            referrer: referral_info.referrer,
+            user: user_info.user,
             amount
         });
 
@@ -389,6 +1003,57 @@ pub mod chill_staking {
         )
     }
 
+    pub fn claim_and_restake<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimAndRestake<'info>>,
+    ) -> Result<()> {
+        let user_info = &mut ctx.accounts.user_info;
+        let staking_info = &mut ctx.accounts.staking_info;
+
+        staking_info.assert_active()?;
+        staking_info.assert_not_paused()?;
+
+        utils::update_state_accounts(user_info, staking_info)?;
+
+        let reward = user_info.rewarded_amount;
+        require_neq!(reward, 0, StakingErrorCode::StakeZeroTokens);
+
+        // Restaked tokens are claimed later as pending ones, so the fee is taken now
+        let fee = staking_info.claim_fee_of(reward)?;
+        let amount = reward
+            .checked_sub(fee)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+        user_info.rewarded_amount = 0;
+        staking_info.total_pending_withdrawal_amount = staking_info
+            .total_pending_withdrawal_amount
+            .checked_sub(fee)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+        utils::add_stake(user_info, staking_info, amount)?;
+        emit!(event::ClaimAndRestake {
+            user: ctx.accounts.user.key(),
+            amount
+        });
+
+        if fee > 0 {
+            emit!(event::ClaimFee {
+                user: ctx.accounts.user.key(),
+                amount: fee
+            });
+
+            utils::transfer_claim_fee(
+                fee,
+                ctx.remaining_accounts,
+                staking_info,
+                &ctx.accounts.staking_token_authority,
+                &ctx.accounts.staking_token_account,
+                &ctx.accounts.token_program,
+            )?;
+        }
+
+        Ok(())
+    }
+
     pub fn transfer_reward_to_pending_amount(
         ctx: Context<UserUpdatesUserInfo>,
         amount: u64,
@@ -405,8 +1070,14 @@ pub mod chill_staking {
             StakingErrorCode::InsufficientFunds
         );
 
-        user_info.rewarded_amount = user_info.rewarded_amount.checked_sub(amount).unwrap();
-        user_info.pending_amount = user_info.pending_amount.checked_add(amount).unwrap();
+        user_info.rewarded_amount = user_info
+            .rewarded_amount
+            .checked_sub(amount)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+        user_info.pending_amount = user_info
+            .pending_amount
+            .checked_add(amount)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
         emit!(event::TransferRewardToPendingAmount {
             user: ctx.accounts.user.key(),
@@ -417,8 +1088,27 @@ pub mod chill_staking {
     }
 
     pub fn boost(ctx: Context<UserUpdatesUserInfo>) -> Result<()> {
+        utils::boost(&mut ctx.accounts.user_info, &mut ctx.accounts.staking_info)
+    }
+
+    pub fn delegate_boost(ctx: Context<DelegateUpdatesUserInfo>) -> Result<()> {
+        utils::boost(&mut ctx.accounts.user_info, &mut ctx.accounts.staking_info)
+    }
+
+    // The user burns tokens to boost a missed day of the window of the active
+    // stake. The reward is calculated when the stake ends, so past days are
+    // boosted as if the user boosted them in time
+    pub fn paid_boost(ctx: Context<PaidBoost>, day: u64) -> Result<()> {
         let user_info = &mut ctx.accounts.user_info;
         let staking_info = &mut ctx.accounts.staking_info;
+        let paid_boost_info = &mut ctx.accounts.paid_boost_info;
+
+        staking_info.assert_not_paused()?;
+        require_neq!(
+            paid_boost_info.cost,
+            0,
+            StakingErrorCode::PaidBoostIsDisabled
+        );
 
         utils::update_state_accounts(user_info, staking_info)?;
 
@@ -427,28 +1117,417 @@ pub mod chill_staking {
             StakingErrorCode::NoActiveStake
         );
 
-        let mut boosted_days = user_info.get_vector()?;
-        let current_day = utils::current_day()?;
-        let index = current_day
-            .checked_sub(user_info.start_day.unwrap())
-            .unwrap() as usize;
+        let current_index = utils::stake_day_index(user_info, staking_info)?;
+        require_gte!(current_index, day, StakingErrorCode::InvalidDayIndex);
+        utils::boost_day(user_info, day)?;
+        utils::count_boost(user_info, staking_info)?;
+
+        let cost = paid_boost_info.cost;
+        let cpi_context = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.from_token_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        );
+
+        token::burn(cpi_context, cost)?;
+
+        paid_boost_info.total_paid_boost_number = paid_boost_info
+            .total_paid_boost_number
+            .checked_add(1)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+        paid_boost_info.total_paid_boost_amount = paid_boost_info
+            .total_paid_boost_amount
+            .checked_add(cost)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+        emit!(event::PaidBoost {
+            user: user_info.user,
+            day,
+            amount: cost
+        });
+
+        Ok(())
+    }
+
+    // Daily rewards are calculated from the part of reward tokens which is left
+    // after the bonus, so the collection is registered before the start
+    pub fn register_nft_collection(
+        ctx: Context<RegisterNftCollection>,
+        collection: Pubkey,
+        reward_bonus: u8,
+    ) -> Result<()> {
+        check_admin_signers(&ctx.accounts.admin_config, ctx.remaining_accounts)?;
+        require_gte!(
+            MAX_NFT_REWARD_BONUS,
+            reward_bonus,
+            StakingErrorCode::InvalidNftRewardBonus
+        );
+
+        let staking_info = &mut ctx.accounts.staking_info;
+        staking_info.assert_not_started()?;
+        staking_info.nft_reward_bonus = reward_bonus;
+
+        let nft_multiplier_info = &mut ctx.accounts.nft_multiplier_info;
+        nft_multiplier_info.staking_info = staking_info.key();
+        nft_multiplier_info.collection = collection;
+        nft_multiplier_info.bump = ctx.bumps["nft_multiplier_info"];
+
+        Ok(())
+    }
+
+    pub fn set_paid_boost_cost(ctx: Context<SetPaidBoostCost>, cost: u64) -> Result<()> {
+        check_admin_signers(&ctx.accounts.admin_config, ctx.remaining_accounts)?;
+
+        let paid_boost_info = &mut ctx.accounts.paid_boost_info;
+        paid_boost_info.staking_info = ctx.accounts.staking_info.key();
+        paid_boost_info.bump = ctx.bumps["paid_boost_info"];
+        paid_boost_info.cost = cost;
+
+        Ok(())
+    }
+
+    // Layouts only append fields, so migrations move the vector which follows
+    // them. Anyone is able to migrate an account paying for its new rent
+    pub fn migrate_staking_info(ctx: Context<MigrateStakingInfo>) -> Result<()> {
+        let account = ctx.accounts.staking_info.to_account_info();
+        let legacy_info = StakingInfo::try_deserialize_versioned(&account.try_borrow_data()?)?;
+        let vector_len = utils::staking_info_vector_len(&legacy_info)?;
+        let version =
+            utils::layout_version(account.data_len(), vector_len, StakingInfo::fields_len)?;
+        require_gt!(
+            StakingInfo::VERSION,
+            version,
+            StakingErrorCode::AccountIsAlreadyMigrated
+        );
+
+        require_keys_eq!(
+            ctx.accounts.mint.key(),
+            legacy_info.mint,
+            ErrorCode::ConstraintTokenMint
+        );
+
+        utils::migrate_layout(
+            &account,
+            StakingInfo::fields_len(version).ok_or(StakingErrorCode::WrongVectorSize)?,
+            StakingInfo::LEN,
+            vector_len,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+        )?;
+
+        let mut staking_info = Account::<StakingInfo>::try_from(&account)?;
+
+        // Every token of the pool except free reward tokens belongs to users,
+        // they are staked, pending or rewarded ones
+        if version == 0 {
+            staking_info.total_pending_withdrawal_amount = ctx
+                .accounts
+                .staking_token_account
+                .amount
+                .saturating_sub(staking_info.free_reward_amount()?);
+        }
+
+        // Days of past stakes are unknown, every stake is counted as a whole
+        // window. Stakes cut by the staking end are the only ones it overrates
+        staking_info.total_stake_weight =
+            utils::legacy_stake_weight(staking_info.total_staked_amount, &staking_info)?;
+        staking_info.version = StakingInfo::VERSION;
+
+        staking_info.exit(&crate::ID)
+    }
+
+    pub fn migrate_user_info(ctx: Context<MigrateUserInfo>) -> Result<()> {
+        let account = ctx.accounts.user_info.to_account_info();
+        let staking_info = &ctx.accounts.staking_info;
+        let legacy_info = UserInfo::try_deserialize_versioned(&account.try_borrow_data()?)?;
+        require_keys_eq!(
+            legacy_info.staking_info,
+            staking_info.key(),
+            ErrorCode::ConstraintHasOne
+        );
+
+        let vector_len = staking_info.days_in_window() as usize;
+        let version = utils::layout_version(account.data_len(), vector_len, UserInfo::fields_len)?;
+        require_gt!(
+            UserInfo::VERSION,
+            version,
+            StakingErrorCode::AccountIsAlreadyMigrated
+        );
+
+        utils::migrate_layout(
+            &account,
+            UserInfo::fields_len(version).ok_or(StakingErrorCode::WrongVectorSize)?,
+            UserInfo::LEN,
+            vector_len,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+        )?;
+
+        let mut user_info = Account::<UserInfo>::try_from(&account)?;
+        user_info.total_stake_weight =
+            utils::legacy_stake_weight(user_info.total_staked_amount, staking_info)?;
+        user_info.version = UserInfo::VERSION;
+
+        user_info.exit(&crate::ID)
+    }
+
+    // A delegate is able to boost and claim on behalf of the user, e.g. a game
+    // backend holding a hot key. Setting a new delegate replaces the previous one
+    pub fn set_delegate(ctx: Context<SetDelegate>, delegate: Pubkey) -> Result<()> {
+        require_keys_neq!(
+            delegate,
+            ctx.accounts.user.key(),
+            StakingErrorCode::WrongDelegate
+        );
+
+        let delegate_info = &mut ctx.accounts.delegate_info;
+        delegate_info.user = ctx.accounts.user.key();
+        delegate_info.user_info = ctx.accounts.user_info.key();
+        delegate_info.delegate = delegate;
+        delegate_info.bump = ctx.bumps["delegate_info"];
+
+        emit!(event::SetDelegate {
+            user: delegate_info.user,
+            delegate
+        });
+
+        Ok(())
+    }
+
+    pub fn revoke_delegate(ctx: Context<RevokeDelegate>) -> Result<()> {
+        emit!(event::RevokeDelegate {
+            user: ctx.accounts.user.key(),
+            delegate: ctx.accounts.delegate_info.delegate
+        });
+
+        Ok(())
+    }
+
+    pub fn settle_stake(ctx: Context<SettleStake>) -> Result<()> {
+        let user_info = &mut ctx.accounts.user_info;
+        let staking_info = &mut ctx.accounts.staking_info;
+
+        utils::update_state_accounts(user_info, staking_info)
+    }
+
+    pub fn start_redistribution(ctx: Context<StartRedistribution>) -> Result<()> {
+        let staking_info = &mut ctx.accounts.staking_info;
+        staking_info.assert_finished()?;
 
         require_eq!(
-            boosted_days.get(index)?,
-            false,
-            StakingErrorCode::AlreadyBoosted
+            staking_info.active_stakes_number,
+            0,
+            StakingErrorCode::StakesAreNotSettled
         );
-        boosted_days.set(index, &true)?;
 
-        user_info.total_boost_number = user_info.total_boost_number.checked_add(1).unwrap();
-        staking_info.total_boost_number = staking_info.total_boost_number.checked_add(1).unwrap();
+        // Every stake is settled, so each reward token which was not rewarded
+        // is unspent. It includes rewards of days after the last stake window,
+        // which `total_unspent_amount` never counts
+        let amount = staking_info.free_reward_amount()?;
+        let total_weight = staking_info.total_stake_weight;
 
-        emit!(event::Boost {
-            user: ctx.accounts.user.key()
+        require_gt!(amount, 0, StakingErrorCode::NothingToRedistribute);
+        require_gt!(total_weight, 0, StakingErrorCode::NothingToRedistribute);
+
+        // Unspent tokens are reserved for users, so they cannot be redeemed
+        // by the primary wallet anymore
+        staking_info.total_rewarded_amount = staking_info
+            .total_rewarded_amount
+            .checked_add(amount)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+        staking_info.total_pending_withdrawal_amount = staking_info
+            .total_pending_withdrawal_amount
+            .checked_add(amount)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+        let redistribution = &mut ctx.accounts.redistribution;
+        redistribution.staking_info = staking_info.key();
+        redistribution.bump = ctx.bumps["redistribution"];
+        redistribution.amount = amount;
+        redistribution.total_weight = total_weight;
+
+        emit!(event::StartRedistribution { amount });
+
+        Ok(())
+    }
+
+    pub fn redistribute_unspent_reward(ctx: Context<RedistributeUnspentReward>) -> Result<()> {
+        let user_info = &mut ctx.accounts.user_info;
+        let redistribution = &mut ctx.accounts.redistribution;
+
+        let amount = redistribution.share_of(user_info.total_stake_weight)?;
+
+        user_info.rewarded_amount = user_info
+            .rewarded_amount
+            .checked_add(amount)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+        user_info.total_rewarded_amount = user_info
+            .total_rewarded_amount
+            .checked_add(amount)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+        redistribution.distributed_amount = redistribution
+            .distributed_amount
+            .checked_add(amount)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+        ctx.accounts.redistribution_receipt.amount = amount;
+
+        emit!(event::RedistributeUnspentReward {
+            user: user_info.user,
+            amount
         });
 
         Ok(())
     }
+
+    // The primary wallet is rotated in two steps, so a staking is never handed
+    // over to a key nobody controls. A new proposal replaces the pending one
+    pub fn propose_new_primary_wallet(
+        ctx: Context<ProposeNewPrimaryWallet>,
+        new_primary_wallet: Pubkey,
+    ) -> Result<()> {
+        let primary_wallet = ctx.accounts.primary_wallet.key();
+        require_keys_neq!(
+            new_primary_wallet,
+            primary_wallet,
+            StakingErrorCode::WrongNewPrimaryWallet
+        );
+
+        let transfer = &mut ctx.accounts.primary_wallet_transfer;
+        transfer.staking_info = ctx.accounts.staking_info.key();
+        transfer.new_primary_wallet = new_primary_wallet;
+        transfer.bump = ctx.bumps["primary_wallet_transfer"];
+
+        emit!(event::ProposeStakingPrimaryWallet {
+            primary_wallet,
+            new_primary_wallet
+        });
+
+        Ok(())
+    }
+
+    pub fn accept_primary_wallet(ctx: Context<AcceptPrimaryWallet>) -> Result<()> {
+        let staking_info = &mut ctx.accounts.staking_info;
+        let old_primary_wallet = staking_info.primary_wallet;
+        staking_info.primary_wallet = ctx.accounts.new_primary_wallet.key();
+
+        emit!(event::AcceptStakingPrimaryWallet {
+            old_primary_wallet,
+            new_primary_wallet: staking_info.primary_wallet
+        });
+
+        Ok(())
+    }
+
+    pub fn pause(ctx: Context<PrimaryWalletUpdatesStakingInfo>) -> Result<()> {
+        let staking_info = &mut ctx.accounts.staking_info;
+        require!(!staking_info.paused, StakingErrorCode::StakingIsPaused);
+
+        staking_info.paused = true;
+        emit!(event::Pause {});
+
+        Ok(())
+    }
+
+    pub fn resume(ctx: Context<PrimaryWalletUpdatesStakingInfo>) -> Result<()> {
+        let staking_info = &mut ctx.accounts.staking_info;
+        require!(staking_info.paused, StakingErrorCode::StakingIsNotPaused);
+
+        staking_info.paused = false;
+        emit!(event::Resume {});
+
+        Ok(())
+    }
+
+    // Devnet tools
+
+    pub fn devnet_advance_clock(ctx: Context<DevnetUpdateStakingInfo>, seconds: u64) -> Result<()> {
+        utils::assert_devnet_tools()?;
+
+        let staking_info = &mut ctx.accounts.staking_info;
+        staking_info.clock_offset = staking_info
+            .clock_offset
+            .checked_add(seconds)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+
+    pub fn devnet_force_settle(ctx: Context<DevnetUpdateStakingInfo>) -> Result<()> {
+        utils::assert_devnet_tools()?;
+
+        let staking_info = &mut ctx.accounts.staking_info;
+        let current_day = staking_info.current_day()?;
+        let remaining_days = staking_info.end_day.saturating_sub(current_day);
+
+        staking_info.clock_offset = remaining_days
+            .checked_mul(SEC_PER_DAY)
+            .and_then(|v| v.checked_add(staking_info.clock_offset))
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+
+    // Rewrites the staking info into the layout of pools initialized before
+    // versioning, so migrations are able to be tested on a local validator
+    pub fn devnet_downgrade_staking_info(ctx: Context<DevnetDowngradeStakingInfo>) -> Result<()> {
+        utils::assert_devnet_tools()?;
+
+        let account = ctx.accounts.staking_info.to_account_info();
+        let staking_info = Account::<StakingInfo>::try_from(&account)?;
+        require_keys_eq!(
+            staking_info.primary_wallet,
+            ctx.accounts.primary_wallet.key(),
+            ErrorCode::ConstraintHasOne
+        );
+        require!(
+            utils::is_staking_info_migrated(&staking_info),
+            StakingErrorCode::AccountIsNotMigrated
+        );
+
+        let vector_len = utils::staking_info_vector_len(&staking_info)?;
+        let legacy_len = StakingInfo::fields_len(0).ok_or(StakingErrorCode::WrongVectorSize)?;
+        let offset = StakingInfo::PENDING_WITHDRAWAL_OFFSET;
+
+        {
+            let mut data = account.try_borrow_mut_data()?;
+            data.copy_within(StakingInfo::LEN..StakingInfo::LEN + vector_len, legacy_len);
+            data[offset..offset + 8].fill(0);
+        }
+
+        account.realloc(legacy_len + vector_len, false)?;
+
+        Ok(())
+    }
+
+    pub fn devnet_initialize_faucet(_ctx: Context<DevnetInitializeFaucet>) -> Result<()> {
+        utils::assert_devnet_tools()
+    }
+
+    pub fn devnet_faucet(ctx: Context<DevnetFaucet>, amount: u64) -> Result<()> {
+        utils::assert_devnet_tools()?;
+
+        let bump = ctx.bumps["faucet_authority"];
+        let seeds = &[DEVNET_FAUCET_SEED, &[bump]];
+        let signers = &[&seeds[..]];
+
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::MintTo {
+                mint: ctx.accounts.faucet_mint.to_account_info(),
+                to: ctx.accounts.token_account.to_account_info(),
+                authority: ctx.accounts.faucet_authority.to_account_info(),
+            },
+            signers,
+        );
+
+        token::mint_to(cpi_context, amount)
+    }
 }
 
 #[error_code]
@@ -506,4 +1585,85 @@ pub enum StakingErrorCode {
 
     #[msg("Withdraw zero tokens")]
     WithdrawZeroTokens,
+
+    #[msg("Program is built without devnet tools")]
+    DevnetToolsDisabled,
+
+    #[msg("Claim fee must be from 0 to 10000 basis points")]
+    ClaimFeeOutOfRange,
+
+    #[msg("Wrong fee config")]
+    WrongFeeConfig,
+
+    #[msg("Staking has unsettled stakes")]
+    StakesAreNotSettled,
+
+    #[msg("Nothing to redistribute")]
+    NothingToRedistribute,
+
+    #[msg("Window length must be from 1 to 30 days")]
+    WindowLengthOutOfRange,
+
+    #[msg("Staking is paused")]
+    StakingIsPaused,
+
+    #[msg("Staking is not paused")]
+    StakingIsNotPaused,
+
+    #[msg("Referral fee must be from 0 to 10000 basis points")]
+    ReferralFeeOutOfRange,
+
+    #[msg("User cannot refer itself")]
+    SelfReferral,
+
+    #[msg("Wrong referral account")]
+    WrongReferralAccount,
+
+    #[msg("Referrer is already set")]
+    ReferrerAlreadySet,
+
+    #[msg("User cannot be its own delegate")]
+    WrongDelegate,
+
+    #[msg("New primary wallet must differ from the current one")]
+    WrongNewPrimaryWallet,
+
+    #[msg("Wrong account to reconcile")]
+    WrongReconcileAccount,
+
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+
+    #[msg("Day is out of the staking interval")]
+    InvalidDayIndex,
+
+    #[msg("Paid boosts are disabled")]
+    PaidBoostIsDisabled,
+
+    #[msg("Wrong account of the NFT")]
+    WrongNftHolderAccount,
+
+    #[msg("NFT is not of the registered collection")]
+    WrongNftCollection,
+
+    #[msg("Account has an outdated layout, migrate it first")]
+    AccountIsNotMigrated,
+
+    #[msg("Account already has the current layout")]
+    AccountIsAlreadyMigrated,
+
+    #[msg("User info is not of the staking")]
+    WrongUserInfoAccount,
+
+    #[msg("Token account is not of a recipient of the fee config")]
+    WrongRecipient,
+
+    #[msg("Free reward tokens don't cover the referral reward")]
+    ReferralRewardIsNotCovered,
+
+    #[msg("NFT gives the reward bonus to an active stake of another user")]
+    NftBonusIsUsed,
+
+    #[msg("NFT reward bonus exceeds the maximum one")]
+    InvalidNftRewardBonus,
 }
@@ -3,11 +3,15 @@ use crate::{
     utils, StakingErrorCode,
 };
 use anchor_lang::prelude::*;
+use ethnum::U256;
 use std::cmp;
 
 pub const DESCRIMINATOR_LEN: usize = 8;
 pub const VECTOR_SIZE_LEN: usize = 4;
-pub const DAYS_IN_WINDOW: u64 = 7;
+pub const DEFAULT_DAYS_IN_WINDOW: u64 = 7;
+pub const MAX_DAYS_IN_WINDOW: u64 = 30;
+pub const BASIS_POINTS: u64 = 10_000;
+pub const BOOST_MULTIPLIER: u64 = 2;
 
 #[cfg(not(feature = "short-day"))]
 pub const SEC_PER_DAY: u64 = 86400;
@@ -15,6 +19,20 @@ pub const SEC_PER_DAY: u64 = 86400;
 #[cfg(feature = "short-day")]
 pub const SEC_PER_DAY: u64 = 3;
 
+pub const DEVNET_FAUCET_SEED: &[u8] = b"devnet-faucet";
+pub const DEVNET_FAUCET_MINT_SEED: &[u8] = b"devnet-faucet-mint";
+pub const DEVNET_FAUCET_DECIMALS: u8 = 9;
+
+pub const REDISTRIBUTION_SEED: &[u8] = b"redistribution";
+pub const REFERRAL_SEED: &[u8] = b"referral";
+pub const DELEGATE_SEED: &[u8] = b"delegate";
+pub const PRIMARY_WALLET_TRANSFER_SEED: &[u8] = b"primary-wallet-transfer";
+pub const PAID_BOOST_SEED: &[u8] = b"paid-boost";
+pub const NFT_MULTIPLIER_SEED: &[u8] = b"nft-multiplier";
+pub const NFT_BONUS_SEED: &[u8] = b"nft-bonus";
+pub const PERCENTS: u64 = 100;
+pub const MAX_NFT_REWARD_BONUS: u8 = 100;
+
 #[account]
 pub struct StakingTokenAuthority {
     pub bump: u8,
@@ -53,14 +71,140 @@ pub struct StakingInfo {
     pub total_days_with_no_reward: u64,
     pub total_staked_amount: u64,
     pub total_rewarded_amount: u64,
+
+    // Staked, pending and rewarded tokens which users are able to withdraw
+    pub total_pending_withdrawal_amount: u64,
+
+    // Seconds added to the cluster time. Only devnet tools are able to set it
+    pub clock_offset: u64,
+
+    // Fee in basis points which is deducted from claimed rewards and
+    // transferred to the recipients of the NFT program config
+    pub claim_fee: u16,
+
+    // Number of days every stake lasts. Use `days_in_window()` to read it
+    pub days_in_window: u16,
+
+    // Set by the primary wallet to stop new stakes and boosts in an emergency.
+    // Users are still able to claim and cancel their stakes
+    pub paused: bool,
+
+    // Share of rewards of referred users in basis points which their
+    // referrers receive on top of them
+    pub referral_fee: u16,
+
+    // Reward in percent which holders of an NFT of the registered collection
+    // receive on top of the daily reward of their stakes
+    pub nft_reward_bonus: u8,
+
+    // Layout version of the account. Pools initialized before versioning have
+    // no such field and are upgraded by `migrate_staking_info`
+    pub version: u8,
+
+    // Sum of staked amounts multiplied by the days of their stakes. Weight of
+    // users in the redistribution of unspent rewards
+    pub total_stake_weight: u128,
 }
 
 impl StakingInfo {
-    pub const RESERVED: usize = 3 * 8;
-    pub const LEN: usize = DESCRIMINATOR_LEN + 32 * 2 + 8 * 17 + Self::RESERVED;
+    pub const VERSION: u8 = 2;
+    pub const LEN_V1: usize = DESCRIMINATOR_LEN + 32 * 2 + 8 * 19 + 2 + 2 + 1 + 2 + 1 + 1;
+    pub const LEN: usize = Self::LEN_V1 + 16;
+
+    // The withdrawal counter took reserved bytes, so pools of version 0 keep
+    // zero at its offset until `migrate_staking_info` fills it in
+    pub const PENDING_WITHDRAWAL_OFFSET: usize = DESCRIMINATOR_LEN + 32 * 2 + 8 * 17;
+
+    // Length of the fields of the layout version, the days vector follows
+    // them. Accounts created before versioning have version 0
+    pub fn fields_len(version: u8) -> Option<usize> {
+        match version {
+            0 => Some(Self::LEN_V1 - 1),
+            1 => Some(Self::LEN_V1),
+            Self::VERSION => Some(Self::LEN),
+            _ => None,
+        }
+    }
+
+    // Number of days the stake started on the day lasts. Stakes started less
+    // than a window before the staking end are cut by it
+    pub fn stake_days(&self, start_day: u64) -> Result<u64> {
+        let stake_end_day = cmp::min(
+            start_day
+                .checked_add(self.days_in_window())
+                .ok_or(StakingErrorCode::InvalidDayIndex)?,
+            self.end_day,
+        );
+
+        stake_end_day
+            .checked_sub(start_day)
+            .ok_or_else(|| StakingErrorCode::InvalidDayIndex.into())
+    }
+
+    pub fn stake_weight(&self, start_day: u64, amount: u64) -> Result<u128> {
+        (amount as u128)
+            .checked_mul(self.stake_days(start_day)?.into())
+            .ok_or_else(|| StakingErrorCode::ArithmeticOverflow.into())
+    }
+
+    // Older layouts are shorter than the current one, the fields they miss
+    // are read as zeros
+    pub fn try_deserialize_versioned(data: &[u8]) -> Result<Self> {
+        utils::try_deserialize_padded(data, Self::LEN)
+    }
+
+    // Pools initialized before the window length became configurable keep
+    // zero in its place
+    pub fn days_in_window(&self) -> u64 {
+        match self.days_in_window {
+            0 => DEFAULT_DAYS_IN_WINDOW,
+            days => days.into(),
+        }
+    }
+
+    pub fn current_day(&self) -> Result<u64> {
+        utils::current_day_with_offset(self.clock_offset)
+    }
+
+    pub fn claim_fee_of(&self, reward_amount: u64) -> Result<u64> {
+        (reward_amount as u128)
+            .checked_mul(self.claim_fee.into())
+            .and_then(|v| v.checked_div(BASIS_POINTS.into()))
+            .and_then(|v| v.try_into().ok())
+            .ok_or_else(|| StakingErrorCode::ArithmeticOverflow.into())
+    }
+
+    pub fn referral_fee_of(&self, reward_amount: u64) -> Result<u64> {
+        (reward_amount as u128)
+            .checked_mul(self.referral_fee.into())
+            .and_then(|v| v.checked_div(BASIS_POINTS.into()))
+            .and_then(|v| v.try_into().ok())
+            .ok_or_else(|| StakingErrorCode::ArithmeticOverflow.into())
+    }
+
+    // Referral rewards and bonuses of NFT holders are paid from reward tokens
+    // too, so daily rewards are calculated from the part which leaves enough
+    // tokens for them
+    pub fn reward_budget(&self) -> Result<u64> {
+        (self.reward_tokens_amount as u128)
+            .checked_mul(BASIS_POINTS.into())
+            .and_then(|v| v.checked_div(BASIS_POINTS as u128 + u128::from(self.referral_fee)))
+            .and_then(|v| v.checked_mul(PERCENTS.into()))
+            .and_then(|v| v.checked_div(PERCENTS as u128 + u128::from(self.nft_reward_bonus)))
+            .and_then(|v| v.try_into().ok())
+            .ok_or_else(|| StakingErrorCode::ArithmeticOverflow.into())
+    }
+
+    pub fn with_nft_reward_bonus(&self, daily_reward: u64) -> Result<u64> {
+        (daily_reward as u128)
+            .checked_mul(PERCENTS as u128 + u128::from(self.nft_reward_bonus))
+            .and_then(|v| v.checked_div(PERCENTS.into()))
+            .and_then(|v| v.try_into().ok())
+            .ok_or_else(|| StakingErrorCode::ArithmeticOverflow.into())
+    }
 
     pub fn assert_active(&self) -> Result<()> {
-        let current_day = utils::current_day()?;
+        let current_day = self.current_day()?;
 
         require_gt!(
             self.end_day,
@@ -77,8 +221,13 @@ impl StakingInfo {
         Ok(())
     }
 
+    pub fn assert_not_paused(&self) -> Result<()> {
+        require!(!self.paused, StakingErrorCode::StakingIsPaused);
+        Ok(())
+    }
+
     pub fn assert_not_started(&self) -> Result<()> {
-        let current_day = utils::current_day()?;
+        let current_day = self.current_day()?;
 
         require_gt!(
             self.end_day,
@@ -96,7 +245,7 @@ impl StakingInfo {
     }
 
     pub fn assert_finished(&self) -> Result<()> {
-        let current_day = utils::current_day()?;
+        let current_day = self.current_day()?;
         require_gte!(
             current_day,
             self.end_day,
@@ -107,7 +256,7 @@ impl StakingInfo {
     }
 
     pub fn update_daily_reward(&mut self) -> Result<()> {
-        let current_day = utils::current_day()?;
+        let current_day = self.current_day()?;
 
         if self.last_update_day == current_day
             || current_day < self.start_day
@@ -119,33 +268,35 @@ impl StakingInfo {
         let days_with_rewards_from_last_update = cmp::min(
             self.last_update_day
                 .checked_sub(self.last_day_with_stake)
-                .unwrap(),
-            DAYS_IN_WINDOW,
+                .ok_or(StakingErrorCode::InvalidDayIndex)?,
+            self.days_in_window(),
         );
 
         let days_with_rewards_from_current_day = cmp::min(
-            current_day.checked_sub(self.last_day_with_stake).unwrap(),
-            DAYS_IN_WINDOW,
+            current_day
+                .checked_sub(self.last_day_with_stake)
+                .ok_or(StakingErrorCode::InvalidDayIndex)?,
+            self.days_in_window(),
         );
 
         let days_with_rewards_since_last_update = days_with_rewards_from_current_day
             .checked_sub(days_with_rewards_from_last_update)
-            .unwrap();
+            .ok_or(StakingErrorCode::InvalidDayIndex)?;
 
         let days_without_reward_from_last_update = self
             .last_update_day
             .checked_sub(self.last_day_with_stake)
-            .and_then(|v| v.checked_sub(DAYS_IN_WINDOW))
+            .and_then(|v| v.checked_sub(self.days_in_window()))
             .unwrap_or(0);
 
         let days_without_reward_from_current_day = current_day
             .checked_sub(self.last_day_with_stake)
-            .and_then(|v| v.checked_sub(DAYS_IN_WINDOW))
+            .and_then(|v| v.checked_sub(self.days_in_window()))
             .unwrap_or(0);
 
         let days_without_reward_since_last_update = days_without_reward_from_current_day
             .checked_sub(days_without_reward_from_last_update)
-            .unwrap();
+            .ok_or(StakingErrorCode::InvalidDayIndex)?;
 
         let day_index = self.day_index()?;
         let days_with_no_reward = if self.total_stakes_number == 0 {
@@ -157,33 +308,33 @@ impl StakingInfo {
         self.total_days_with_no_reward = self
             .total_days_with_no_reward
             .checked_add(days_with_no_reward)
-            .unwrap();
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
-        let total_days = self.total_days();
+        let total_days = self.total_days()?;
         let unspent_amount = utils::calculate_unspent_amount_from_days_with_no_reward(
             days_with_no_reward,
             total_days,
-            self.reward_tokens_amount,
-        );
+            self.reward_budget()?,
+        )?;
 
         self.total_unspent_amount = self
             .total_unspent_amount
             .checked_add(unspent_amount)
-            .unwrap();
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
         self.rewarded_unspent_amount = self
             .daily_unspent_reward
             .checked_mul(days_with_rewards_since_last_update)
             .and_then(|v| v.checked_add(self.rewarded_unspent_amount))
-            .unwrap();
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
         let (new_daily_reward, daily_unspent_reward) = utils::calculate_daily_staking_reward(
             day_index,
             total_days,
             self.total_unspent_amount,
             self.rewarded_unspent_amount,
-            self.reward_tokens_amount,
-        );
+            self.reward_budget()?,
+        )?;
 
         self.daily_unspent_reward = daily_unspent_reward;
         self.last_daily_reward = new_daily_reward;
@@ -192,19 +343,33 @@ impl StakingInfo {
         Ok(())
     }
 
+    pub fn free_reward_amount(&self) -> Result<u64> {
+        self.reward_tokens_amount
+            .checked_sub(self.total_rewarded_amount)
+            .ok_or_else(|| StakingErrorCode::ArithmeticOverflow.into())
+    }
+
+    pub fn required_token_amount(&self) -> Result<u64> {
+        self.free_reward_amount()?
+            .checked_add(self.total_pending_withdrawal_amount)
+            .ok_or_else(|| StakingErrorCode::ArithmeticOverflow.into())
+    }
+
     pub fn day_index(&self) -> Result<u64> {
-        let current_day = utils::current_day()?;
+        let current_day = self.current_day()?;
         current_day
             .checked_sub(self.start_day)
             .ok_or_else(|| StakingErrorCode::StakingIsNotStarted.into())
     }
 
-    pub fn total_days(&self) -> u64 {
-        self.end_day.checked_sub(self.start_day).unwrap()
+    pub fn total_days(&self) -> Result<u64> {
+        self.end_day
+            .checked_sub(self.start_day)
+            .ok_or_else(|| StakingErrorCode::InvalidDayIndex.into())
     }
 
     pub fn is_finished(&self) -> Result<bool> {
-        let current_day = utils::current_day()?;
+        let current_day = self.current_day()?;
         Ok(self.end_day >= current_day)
     }
 }
@@ -212,11 +377,14 @@ impl StakingInfo {
 impl<'info> GetLazyVector<'info, u64> for Account<'info, StakingInfo> {
     fn get_vector(&self) -> Result<LazyVector<'info, u64>> {
         let account_info = self.to_account_info();
-        let days_amount = self.end_day.checked_sub(self.start_day).unwrap();
+        let days_amount = self
+            .total_days()?
+            .try_into()
+            .map_err(|_| StakingErrorCode::ArithmeticOverflow)?;
 
         LazyVector::new(
             StakingInfo::LEN,
-            days_amount.try_into().unwrap(),
+            days_amount,
             std::mem::size_of::<u64>(),
             account_info.data,
         )
@@ -239,21 +407,49 @@ pub struct UserInfo {
     pub total_staked_amount: u64,
     pub total_rewarded_amount: u64,
     pub total_boost_number: u64,
+
+    // Layout version of the account. User infos created before versioning
+    // have no such field and are upgraded by `migrate_user_info`
+    pub version: u8,
+
+    // Sum of staked amounts multiplied by the days of the stakes of the user
+    pub total_stake_weight: u128,
 }
 
 impl UserInfo {
-    pub const LEN: usize = DESCRIMINATOR_LEN + 32 + 32 + 1 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8;
+    pub const VERSION: u8 = 2;
+    pub const LEN_V1: usize =
+        DESCRIMINATOR_LEN + 32 + 32 + 1 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1;
+    pub const LEN: usize = Self::LEN_V1 + 16;
+
+    // Length of the fields of the layout version, the boosted days vector
+    // follows them. Accounts created before versioning have version 0
+    pub fn fields_len(version: u8) -> Option<usize> {
+        match version {
+            0 => Some(Self::LEN_V1 - 1),
+            1 => Some(Self::LEN_V1),
+            Self::VERSION => Some(Self::LEN),
+            _ => None,
+        }
+    }
+
+    // Boosted days of legacy accounts may be shorter than the added fields
+    pub fn try_deserialize_versioned(data: &[u8]) -> Result<Self> {
+        utils::try_deserialize_padded(data, Self::LEN)
+    }
 
     pub fn has_active_stake(&self) -> bool {
         self.start_day.is_some()
     }
 
-    pub fn has_ended_stake(&self, staking_end_day: u64) -> Result<bool> {
+    pub fn has_ended_stake(&self, staking_info: &StakingInfo) -> Result<bool> {
         self.start_day.map_or(Ok(false), |start_day| {
-            let current_day = utils::current_day()?;
+            let current_day = staking_info.current_day()?;
             let user_staking_end = cmp::min(
-                start_day.checked_add(DAYS_IN_WINDOW).unwrap(),
-                staking_end_day,
+                start_day
+                    .checked_add(staking_info.days_in_window())
+                    .ok_or(StakingErrorCode::InvalidDayIndex)?,
+                staking_info.end_day,
             );
 
             Ok(current_day >= user_staking_end)
@@ -261,14 +457,143 @@ impl UserInfo {
     }
 }
 
+// The account is allocated with a boosted day for each day of the window of
+// its staking
 impl<'info> GetLazyVector<'info, bool> for Account<'info, UserInfo> {
     fn get_vector(&self) -> Result<LazyVector<'info, bool>> {
         let account_info = self.to_account_info();
+        let days_in_window = account_info
+            .data_len()
+            .checked_sub(UserInfo::LEN)
+            .ok_or(StakingErrorCode::WrongVectorSize)?;
+
         LazyVector::new(
             UserInfo::LEN,
-            DAYS_IN_WINDOW.try_into().unwrap(),
+            days_in_window,
             std::mem::size_of::<bool>(),
             account_info.data,
         )
     }
 }
+
+#[account]
+pub struct Redistribution {
+    pub staking_info: Pubkey,
+    pub bump: u8,
+
+    // Unspent reward tokens shared between users at the end of the staking
+    pub amount: u64,
+
+    // Stake weight of the staking info, users receive shares proportional to
+    // their stake weights
+    pub total_weight: u128,
+    pub distributed_amount: u64,
+}
+
+impl Redistribution {
+    pub const LEN: usize = DESCRIMINATOR_LEN + 32 + 1 + 8 + 16 + 8;
+
+    pub fn share_of(&self, weight: u128) -> Result<u64> {
+        U256::from(self.amount)
+            .checked_mul(weight.into())
+            .and_then(|v| v.checked_div(self.total_weight.into()))
+            .and_then(|v| v.try_into().ok())
+            .ok_or_else(|| StakingErrorCode::ArithmeticOverflow.into())
+    }
+}
+
+#[account]
+pub struct RedistributionReceipt {
+    pub amount: u64,
+}
+
+impl RedistributionReceipt {
+    pub const LEN: usize = DESCRIMINATOR_LEN + 8;
+}
+
+#[account]
+pub struct ReferralInfo {
+    pub user_info: Pubkey,
+    pub referrer: Pubkey,
+    pub bump: u8,
+
+    // Total rewarded amount of the referred user which the referral reward is
+    // already paid for
+    pub accounted_reward_amount: u64,
+    pub total_referral_amount: u64,
+}
+
+impl ReferralInfo {
+    pub const LEN: usize = DESCRIMINATOR_LEN + 32 + 32 + 1 + 8 + 8;
+}
+
+#[account]
+pub struct DelegateInfo {
+    pub user: Pubkey,
+    pub user_info: Pubkey,
+    pub delegate: Pubkey,
+    pub bump: u8,
+}
+
+impl DelegateInfo {
+    pub const LEN: usize = DESCRIMINATOR_LEN + 32 + 32 + 32 + 1;
+}
+
+// Collection of NFTs whose holders receive the reward bonus of the staking
+#[account]
+pub struct NftMultiplierInfo {
+    pub staking_info: Pubkey,
+    pub collection: Pubkey,
+    pub bump: u8,
+}
+
+impl NftMultiplierInfo {
+    pub const LEN: usize = DESCRIMINATOR_LEN + 32 + 32 + 1;
+}
+
+// Stake which receives the reward bonus of an NFT. Other wallets receive the
+// bonus of the NFT only after the stake ends
+#[account]
+pub struct NftBonusInfo {
+    pub staking_info: Pubkey,
+    pub nft_mint: Pubkey,
+    pub user: Pubkey,
+    pub end_day: u64,
+    pub bump: u8,
+}
+
+impl NftBonusInfo {
+    pub const LEN: usize = DESCRIMINATOR_LEN + 32 + 32 + 32 + 8 + 1;
+}
+
+// Cost of a paid boost and statistics of paid boosts. StakingInfo has no room
+// for them either
+#[account]
+pub struct PaidBoostInfo {
+    pub staking_info: Pubkey,
+    pub bump: u8,
+
+    // Tokens burned for a paid boost, zero disables paid boosts
+    pub cost: u64,
+
+    // Statistics
+    pub total_paid_boost_number: u64,
+    pub total_paid_boost_amount: u64,
+}
+
+impl PaidBoostInfo {
+    pub const LEN: usize = DESCRIMINATOR_LEN + 32 + 1 + 8 + 8 + 8;
+}
+
+// StakingInfo has no room for a pending primary wallet, it is kept in a
+// separate account until the new primary wallet accepts the transfer
+#[account]
+pub struct PrimaryWalletTransfer {
+    pub staking_info: Pubkey,
+    pub new_primary_wallet: Pubkey,
+    pub bump: u8,
+}
+
+impl PrimaryWalletTransfer {
+    pub const LEN: usize = DESCRIMINATOR_LEN + 32 + 32 + 1;
+}
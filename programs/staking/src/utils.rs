@@ -1,9 +1,24 @@
 use crate::{
+    event,
     lazy_vector::{GetLazyVector, LazyVector},
-    state::{StakingInfo, StakingTokenAuthority, UserInfo, DAYS_IN_WINDOW, SEC_PER_DAY},
+    state::{
+        NftBonusInfo, NftMultiplierInfo, ReferralInfo, StakingInfo, StakingTokenAuthority,
+        UserInfo, BOOST_MULTIPLIER, NFT_BONUS_SEED, REFERRAL_SEED, SEC_PER_DAY,
+    },
+    StakingErrorCode,
+};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        program::{invoke, invoke_signed},
+        system_instruction,
+    },
 };
-use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount};
+use chill_nft::{
+    metaplex_adapter::Metadata,
+    state::{ChillNftMetadata, Config},
+};
 use ethnum::U256;
 use std::cmp;
 
@@ -12,7 +27,7 @@ pub fn transfer_tokens<'info>(
     staking_info: &Account<'info, StakingInfo>,
     staking_token_authority: &Account<'info, StakingTokenAuthority>,
     staking_token_account: &Account<'info, TokenAccount>,
-    recipient_token_account: &Account<'info, TokenAccount>,
+    recipient_token_account: &impl ToAccountInfo<'info>,
     token_program: &Program<'info, Token>,
 ) -> Result<()> {
     let staking_info_pubkey = staking_info.key();
@@ -35,22 +50,590 @@ pub fn transfer_tokens<'info>(
     token::transfer(cpi_context, amount)
 }
 
+/// Remaining accounts must contain the NFT program config of the staking mint
+/// followed by token accounts of all config recipients
+pub fn transfer_claim_fee<'info>(
+    fee: u64,
+    remaining_accounts: &[AccountInfo<'info>],
+    staking_info: &Account<'info, StakingInfo>,
+    staking_token_authority: &Account<'info, StakingTokenAuthority>,
+    staking_token_account: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    let (config_info, recipients_token_accounts) = remaining_accounts
+        .split_first()
+        .ok_or(StakingErrorCode::WrongFeeConfig)?;
+
+    let config = Account::<Config>::try_from(config_info)?;
+    require_keys_eq!(
+        config.mint,
+        staking_info.mint,
+        StakingErrorCode::WrongFeeConfig
+    );
+
+    require!(
+        !config.recipients.is_empty(),
+        StakingErrorCode::WrongFeeConfig
+    );
+
+    chill_nft::utils::check_recipients(&config, recipients_token_accounts)?;
+
+    let amounts = calculate_fee_amounts(fee, &config, recipients_token_accounts)?;
+    for (amount, recipient_token_account) in amounts.into_iter().zip(recipients_token_accounts) {
+        if amount > 0 {
+            transfer_tokens(
+                amount,
+                staking_info,
+                staking_token_authority,
+                staking_token_account,
+                recipient_token_account,
+                token_program,
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Splits the fee by transaction shares. The first recipient receives the remainder
+pub fn calculate_fee_amounts(
+    fee: u64,
+    config: &Config,
+    recipients_token_accounts: &[AccountInfo],
+) -> Result<Vec<u64>> {
+    let mut amounts = Vec::with_capacity(recipients_token_accounts.len());
+    amounts.push(0);
+
+    for recipient_token_account in recipients_token_accounts.iter().skip(1) {
+        let token_account =
+            TokenAccount::try_deserialize(&mut recipient_token_account.data.borrow().as_ref())?;
+
+        let recipient = config
+            .recipients
+            .iter()
+            .find(|r| r.address == token_account.owner)
+            .ok_or(StakingErrorCode::WrongRecipient)?;
+
+        let amount = (fee as u128)
+            .checked_mul(recipient.transaction_share.into())
+            .and_then(|a| a.checked_div(100))
+            .and_then(|a| a.try_into().ok())
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+        amounts.push(amount);
+    }
+
+    amounts[0] = fee
+        .checked_sub(amounts.iter().sum())
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+    Ok(amounts)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn claim<'info>(
+    amount: u64,
+    remaining_accounts: &[AccountInfo<'info>],
+    user_info: &mut Account<'info, UserInfo>,
+    staking_info: &mut Account<'info, StakingInfo>,
+    staking_token_authority: &Account<'info, StakingTokenAuthority>,
+    staking_token_account: &Account<'info, TokenAccount>,
+    recipient_token_account: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    require_neq!(amount, 0u64, StakingErrorCode::WithdrawZeroTokens);
+
+    update_state_accounts(user_info, staking_info)?;
+
+    let total_amount = user_info
+        .rewarded_amount
+        .checked_add(user_info.pending_amount)
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+    require_gte!(total_amount, amount, StakingErrorCode::InsufficientFunds);
+
+    let fee = staking_info.claim_fee_of(cmp::min(amount, user_info.rewarded_amount))?;
+
+    if amount > user_info.rewarded_amount {
+        user_info.pending_amount = u128::from(user_info.pending_amount)
+            .checked_add(user_info.rewarded_amount.into())
+            .and_then(|v| v.checked_sub(amount.into()))
+            .and_then(|v| v.try_into().ok())
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+        user_info.rewarded_amount = 0;
+    } else {
+        user_info.rewarded_amount = user_info
+            .rewarded_amount
+            .checked_sub(amount)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+    }
+
+    staking_info.total_pending_withdrawal_amount = staking_info
+        .total_pending_withdrawal_amount
+        .checked_sub(amount)
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+    emit!(event::Claim {
+        user: user_info.user,
+        amount
+    });
+
+    transfer_tokens(
+        amount
+            .checked_sub(fee)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?,
+        staking_info,
+        staking_token_authority,
+        staking_token_account,
+        recipient_token_account,
+        token_program,
+    )?;
+
+    if fee > 0 {
+        emit!(event::ClaimFee {
+            user: user_info.user,
+            amount: fee
+        });
+
+        transfer_claim_fee(
+            fee,
+            remaining_accounts,
+            staking_info,
+            staking_token_authority,
+            staking_token_account,
+            token_program,
+        )?;
+    }
+
+    Ok(())
+}
+
+pub fn boost<'info>(
+    user_info: &mut Account<'info, UserInfo>,
+    staking_info: &mut Account<'info, StakingInfo>,
+) -> Result<()> {
+    staking_info.assert_not_paused()?;
+
+    update_state_accounts(user_info, staking_info)?;
+
+    require!(
+        user_info.has_active_stake(),
+        StakingErrorCode::NoActiveStake
+    );
+
+    let index = stake_day_index(user_info, staking_info)?;
+    boost_day(user_info, index)?;
+    count_boost(user_info, staking_info)?;
+
+    emit!(event::Boost {
+        user: user_info.user
+    });
+
+    Ok(())
+}
+
+/// Every boosted day is counted, paid ones included, since `cancel` subtracts
+/// all boosted days of the stake
+pub fn count_boost(user_info: &mut UserInfo, staking_info: &mut StakingInfo) -> Result<()> {
+    user_info.total_boost_number = user_info
+        .total_boost_number
+        .checked_add(1)
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+    staking_info.total_boost_number = staking_info
+        .total_boost_number
+        .checked_add(1)
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+    Ok(())
+}
+
+/// Index of the current day in the window of the active stake of the user
+pub fn stake_day_index(
+    user_info: &Account<UserInfo>,
+    staking_info: &Account<StakingInfo>,
+) -> Result<u64> {
+    let current_day = staking_info.current_day()?;
+    current_day
+        .checked_sub(user_info.start_day.ok_or(StakingErrorCode::NoActiveStake)?)
+        .ok_or_else(|| StakingErrorCode::InvalidDayIndex.into())
+}
+
+pub fn boost_day(user_info: &Account<UserInfo>, index: u64) -> Result<()> {
+    let mut boosted_days = user_info.get_vector()?;
+    let index = index as usize;
+
+    require_eq!(
+        boosted_days.get(index)?,
+        false,
+        StakingErrorCode::AlreadyBoosted
+    );
+    boosted_days.set(index, &true)
+}
+
+/// Checks that the user holds an NFT of the collection registered for the
+/// staking and returns its mint. Accounts are the NFT multiplier account of
+/// the staking, the token account of the NFT, its Chill metadata and its
+/// Metaplex metadata
+pub fn verify_nft_holder<'info>(
+    nft_accounts: &[AccountInfo<'info>],
+    user: Pubkey,
+    staking_info: &Account<'info, StakingInfo>,
+) -> Result<Pubkey> {
+    let [multiplier_info, token_account, chill_metadata, metadata] = nft_accounts else {
+        return err!(StakingErrorCode::WrongNftHolderAccount);
+    };
+
+    let multiplier_info = Account::<NftMultiplierInfo>::try_from(multiplier_info)?;
+    require_keys_eq!(
+        multiplier_info.staking_info,
+        staking_info.key(),
+        StakingErrorCode::WrongNftHolderAccount
+    );
+
+    let token_account = Account::<TokenAccount>::try_from(token_account)?;
+    require_keys_eq!(
+        token_account.owner,
+        user,
+        StakingErrorCode::WrongNftHolderAccount
+    );
+    require_eq!(
+        token_account.amount,
+        1,
+        StakingErrorCode::WrongNftHolderAccount
+    );
+
+    // Only the NFT program creates accounts at addresses of Chill metadata
+    let (chill_metadata_address, _) = Pubkey::find_program_address(
+        &[ChillNftMetadata::SEED, token_account.mint.as_ref()],
+        &chill_nft::ID,
+    );
+    require_keys_eq!(
+        chill_metadata.key(),
+        chill_metadata_address,
+        StakingErrorCode::WrongNftHolderAccount
+    );
+    require_keys_eq!(
+        *chill_metadata.owner,
+        chill_nft::ID,
+        StakingErrorCode::WrongNftHolderAccount
+    );
+
+    let metadata = Account::<Metadata>::try_from(metadata)?;
+    require_keys_eq!(
+        metadata.mint,
+        token_account.mint,
+        StakingErrorCode::WrongNftHolderAccount
+    );
+
+    let collection = metadata
+        .collection
+        .as_ref()
+        .filter(|collection| collection.verified)
+        .ok_or(StakingErrorCode::WrongNftCollection)?;
+    require_keys_eq!(
+        collection.key,
+        multiplier_info.collection,
+        StakingErrorCode::WrongNftCollection
+    );
+
+    Ok(token_account.mint)
+}
+
+/// Records the stake which receives the bonus of the NFT, so the NFT can't be
+/// passed between wallets to give the bonus to several stakes at once
+pub fn use_nft_bonus<'info>(
+    nft_bonus_info: &AccountInfo<'info>,
+    nft_mint: Pubkey,
+    user_info: &Account<'info, UserInfo>,
+    staking_info: &Account<'info, StakingInfo>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let staking_info_key = staking_info.key();
+    let (address, bump) = Pubkey::find_program_address(
+        &[NFT_BONUS_SEED, staking_info_key.as_ref(), nft_mint.as_ref()],
+        &crate::ID,
+    );
+
+    require_keys_eq!(
+        nft_bonus_info.key(),
+        address,
+        StakingErrorCode::WrongNftHolderAccount
+    );
+
+    let start_day = user_info.start_day.ok_or(StakingErrorCode::NoActiveStake)?;
+    let end_day = start_day
+        .checked_add(staking_info.stake_days(start_day)?)
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+    if !nft_bonus_info.data_is_empty() {
+        let existing = Account::<NftBonusInfo>::try_from(nft_bonus_info)?;
+        require!(
+            existing.user == user_info.user || staking_info.current_day()? >= existing.end_day,
+            StakingErrorCode::NftBonusIsUsed
+        );
+    } else {
+        let ix = system_instruction::create_account(
+            payer.key,
+            nft_bonus_info.key,
+            Rent::get()?.minimum_balance(NftBonusInfo::LEN),
+            NftBonusInfo::LEN as u64,
+            &crate::ID,
+        );
+
+        invoke_signed(
+            &ix,
+            &[
+                payer.to_account_info(),
+                nft_bonus_info.clone(),
+                system_program.to_account_info(),
+            ],
+            &[&[
+                NFT_BONUS_SEED,
+                staking_info_key.as_ref(),
+                nft_mint.as_ref(),
+                &[bump],
+            ]],
+        )?;
+    }
+
+    let info = NftBonusInfo {
+        staking_info: staking_info_key,
+        nft_mint,
+        user: user_info.user,
+        end_day,
+        bump,
+    };
+
+    let mut data = nft_bonus_info.try_borrow_mut_data()?;
+    info.try_serialize(&mut data.as_mut())
+}
+
+/// Version of the layout of an account whose fields are followed by a vector.
+/// Layouts only append fields, so the version is the one whose fields and the
+/// vector make up the length of the account
+pub fn layout_version(
+    data_len: usize,
+    vector_len: usize,
+    fields_len: fn(u8) -> Option<usize>,
+) -> Result<u8> {
+    (0..=u8::MAX)
+        .map_while(|version| fields_len(version).map(|len| (version, len)))
+        .find(|(_, len)| len.checked_add(vector_len) == Some(data_len))
+        .map(|(version, _)| version)
+        .ok_or_else(|| StakingErrorCode::WrongVectorSize.into())
+}
+
+pub fn staking_info_vector_len(staking_info: &StakingInfo) -> Result<usize> {
+    staking_info
+        .total_days()?
+        .checked_mul(std::mem::size_of::<u64>() as u64)
+        .and_then(|len| len.try_into().ok())
+        .ok_or_else(|| StakingErrorCode::ArithmeticOverflow.into())
+}
+
+pub fn staking_info_version(staking_info: &Account<StakingInfo>) -> Result<u8> {
+    layout_version(
+        staking_info.to_account_info().data_len(),
+        staking_info_vector_len(staking_info)?,
+        StakingInfo::fields_len,
+    )
+}
+
+pub fn user_info_version(user_info: &Account<UserInfo>, staking_info: &StakingInfo) -> Result<u8> {
+    layout_version(
+        user_info.to_account_info().data_len(),
+        staking_info.days_in_window() as usize,
+        UserInfo::fields_len,
+    )
+}
+
+pub fn is_staking_info_migrated(staking_info: &Account<StakingInfo>) -> bool {
+    staking_info.version == StakingInfo::VERSION
+        && matches!(staking_info_version(staking_info), Ok(StakingInfo::VERSION))
+}
+
+/// User infos are created with the current layout and get its version with
+/// their first stake
+pub fn is_user_info_migrated(user_info: &Account<UserInfo>, staking_info: &StakingInfo) -> bool {
+    let is_new = user_info.user == Pubkey::default();
+    (is_new || user_info.version == UserInfo::VERSION)
+        && matches!(
+            user_info_version(user_info, staking_info),
+            Ok(UserInfo::VERSION)
+        )
+}
+
+/// Reads an account which may have an older layout than the current one.
+/// Short accounts are padded with zeros, so only fields of their layout are valid
+pub fn try_deserialize_padded<T: AccountDeserialize>(data: &[u8], len: usize) -> Result<T> {
+    let mut data = data.to_vec();
+    if data.len() < len {
+        data.resize(len, 0);
+    }
+
+    T::try_deserialize(&mut data.as_ref())
+}
+
+/// Stake weight of accounts of layouts without it. The total staked amount
+/// is counted as if every stake lasted a whole window
+pub fn legacy_stake_weight(total_staked_amount: u64, staking_info: &StakingInfo) -> Result<u128> {
+    (total_staked_amount as u128)
+        .checked_mul(staking_info.days_in_window().into())
+        .ok_or_else(|| StakingErrorCode::ArithmeticOverflow.into())
+}
+
+/// Moves the vector which follows the fields of the old layout behind the
+/// fields of the new one. The payer funds the rent of the added bytes
+pub fn migrate_layout<'info>(
+    account: &AccountInfo<'info>,
+    fields_len: usize,
+    new_fields_len: usize,
+    vector_len: usize,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let new_len = new_fields_len
+        .checked_add(vector_len)
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+    let rent = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+
+    if rent > 0 {
+        let ix = system_instruction::transfer(payer.key, account.key, rent);
+        invoke(
+            &ix,
+            &[
+                payer.to_account_info(),
+                account.clone(),
+                system_program.to_account_info(),
+            ],
+        )?;
+    }
+
+    account.realloc(new_len, false)?;
+
+    let mut data = account.try_borrow_mut_data()?;
+    data.copy_within(fields_len..fields_len + vector_len, new_fields_len);
+    data[fields_len..new_fields_len].fill(0);
+
+    Ok(())
+}
+
+/// Creates the referral account of the user, which must be the first remaining
+/// account. A referrer cannot be changed once it is set
+pub fn register_referrer<'info>(
+    referrer: Pubkey,
+    remaining_accounts: &[AccountInfo<'info>],
+    user_info: &Account<'info, UserInfo>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    require_keys_neq!(referrer, user_info.user, StakingErrorCode::SelfReferral);
+
+    let referral_info = remaining_accounts
+        .first()
+        .ok_or(StakingErrorCode::WrongReferralAccount)?;
+
+    let user_info_key = user_info.key();
+    let (address, bump) =
+        Pubkey::find_program_address(&[REFERRAL_SEED, user_info_key.as_ref()], &crate::ID);
+
+    require_keys_eq!(
+        referral_info.key(),
+        address,
+        StakingErrorCode::WrongReferralAccount
+    );
+
+    if !referral_info.data_is_empty() {
+        let existing = Account::<ReferralInfo>::try_from(referral_info)?;
+        require_keys_eq!(
+            existing.referrer,
+            referrer,
+            StakingErrorCode::ReferrerAlreadySet
+        );
+
+        return Ok(());
+    }
+
+    let ix = system_instruction::create_account(
+        payer.key,
+        referral_info.key,
+        Rent::get()?.minimum_balance(ReferralInfo::LEN),
+        ReferralInfo::LEN as u64,
+        &crate::ID,
+    );
+
+    invoke_signed(
+        &ix,
+        &[
+            payer.to_account_info(),
+            referral_info.clone(),
+            system_program.to_account_info(),
+        ],
+        &[&[REFERRAL_SEED, user_info_key.as_ref(), &[bump]]],
+    )?;
+
+    // Rewards received before the registration are not shared
+    let info = ReferralInfo {
+        user_info: user_info_key,
+        referrer,
+        bump,
+        accounted_reward_amount: user_info.total_rewarded_amount,
+        total_referral_amount: 0,
+    };
+
+    let mut data = referral_info.try_borrow_mut_data()?;
+    info.try_serialize(&mut data.as_mut())?;
+
+    emit!(event::RegisterReferrer {
+        user: user_info.user,
+        referrer,
+    });
+
+    Ok(())
+}
+
 pub fn current_day() -> Result<u64> {
+    current_day_with_offset(0)
+}
+
+pub fn current_day_with_offset(clock_offset: u64) -> Result<u64> {
     let clock = Clock::get()?;
-    let timestamp = clock.unix_timestamp as u64;
-    Ok(timestamp.checked_div(SEC_PER_DAY).unwrap())
+    let mut timestamp = clock.unix_timestamp as u64;
+    if cfg!(feature = "devnet-tools") {
+        timestamp = timestamp
+            .checked_add(clock_offset)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+    }
+
+    timestamp
+        .checked_div(SEC_PER_DAY)
+        .ok_or_else(|| StakingErrorCode::ArithmeticOverflow.into())
+}
+
+/// Devnet tools are only available in builds with the "devnet-tools" feature
+pub fn assert_devnet_tools() -> Result<()> {
+    require!(
+        cfg!(feature = "devnet-tools"),
+        StakingErrorCode::DevnetToolsDisabled
+    );
+
+    Ok(())
 }
 
 pub fn calculate_unspent_amount_from_days_with_no_reward(
     days_with_no_reward: u64,
     total_days: u64,
     reward_tokens_amount: u64,
-) -> u64 {
+) -> Result<u64> {
     U256::from(reward_tokens_amount)
         .checked_mul(days_with_no_reward.into())
         .and_then(|v| v.checked_div(total_days.into()))
-        .unwrap()
-        .as_u64()
+        .map(|v| v.as_u64())
+        .ok_or_else(|| StakingErrorCode::ArithmeticOverflow.into())
 }
 
 pub fn calculate_daily_staking_reward(
@@ -59,64 +642,76 @@ pub fn calculate_daily_staking_reward(
     unspent_amount: u64,
     rewarded_unspent_amount: u64,
     reward_tokens_amount: u64,
-) -> (u64, u64) {
-    let remaining_days = total_days.checked_sub(day_index).unwrap();
+) -> Result<(u64, u64)> {
+    let remaining_days = total_days
+        .checked_sub(day_index)
+        .ok_or(StakingErrorCode::InvalidDayIndex)?;
     let total_days = U256::from(total_days);
 
     let max_daily_reward_x_total_days = reward_tokens_amount;
     let max_rewarded_x_total_days = U256::from(max_daily_reward_x_total_days)
         .checked_mul(day_index.into())
-        .unwrap();
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
     let denomenator = U256::from(remaining_days)
         .checked_mul(U256::new(2))
         .and_then(|v| v.checked_mul(total_days))
-        .unwrap();
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
-    let unspent_amount_x_total_days = U256::from(unspent_amount).checked_mul(total_days).unwrap();
+    let unspent_amount_x_total_days = U256::from(unspent_amount)
+        .checked_mul(total_days)
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
     let rewarded_unspent_amount_x_total_days = U256::from(rewarded_unspent_amount)
         .checked_mul(total_days)
-        .unwrap();
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
     let remaining_unspent_amount_x_total_days = unspent_amount_x_total_days
         .checked_sub(rewarded_unspent_amount_x_total_days)
-        .unwrap();
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
     let reward_tokens_amount_x_total_days = U256::from(reward_tokens_amount)
         .checked_mul(total_days)
-        .unwrap();
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
     let numerator = reward_tokens_amount_x_total_days
         .checked_add(remaining_unspent_amount_x_total_days)
         .and_then(|v| v.checked_sub(max_rewarded_x_total_days))
-        .unwrap();
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
-    let daily_reward = numerator.checked_div(denomenator).unwrap().as_u64();
+    let daily_reward = numerator
+        .checked_div(denomenator)
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?
+        .as_u64();
 
-    let remaining_days_x_total_days = U256::from(remaining_days).checked_mul(total_days).unwrap();
+    let remaining_days_x_total_days = U256::from(remaining_days)
+        .checked_mul(total_days)
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
     let daily_unspent_reward = remaining_unspent_amount_x_total_days
         .checked_div(remaining_days_x_total_days)
-        .unwrap()
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?
         .as_u64();
 
-    (daily_reward, daily_unspent_reward)
+    Ok((daily_reward, daily_unspent_reward))
 }
 
 pub fn calculate_total_staked_amount_before_day(
     day_index: u64,
+    days_in_window: u64,
     staked_amounts: &LazyVector<u64>,
 ) -> Result<u64> {
     let mut total_staked = 0u64;
 
     let from_index = day_index
-        .checked_sub(DAYS_IN_WINDOW)
+        .checked_sub(days_in_window)
         .and_then(|v| v.checked_add(1))
         .unwrap_or(0);
 
     for index in from_index..day_index {
         let stake_amount = staked_amounts.get(index as usize)?;
-        total_staked = total_staked.checked_add(stake_amount).unwrap();
+        total_staked = total_staked
+            .checked_add(stake_amount)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
     }
 
     Ok(total_staked)
@@ -128,14 +723,20 @@ pub fn calculate_user_reward_with_unspent_rewards(
     user_boosted_days: &LazyVector<bool>,
     staked_amounts: &LazyVector<u64>,
     total_days: u64,
+    days_in_window: u64,
     daily_staking_reward: u64,
 ) -> Result<(u64, u64)> {
     let daily_staking_reward = U256::from(daily_staking_reward);
 
-    let mut total_staked_at_day_index =
-        calculate_total_staked_amount_before_day(user_start_day_index, staked_amounts)?;
+    let mut total_staked_at_day_index = calculate_total_staked_amount_before_day(
+        user_start_day_index,
+        days_in_window,
+        staked_amounts,
+    )?;
 
-    let last_stake_day = user_start_day_index.checked_add(DAYS_IN_WINDOW).unwrap();
+    let last_stake_day = user_start_day_index
+        .checked_add(days_in_window)
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
     let to = cmp::min(total_days, last_stake_day);
 
     let mut reward = 0u64;
@@ -144,94 +745,225 @@ pub fn calculate_user_reward_with_unspent_rewards(
         let staked_amount = staked_amounts.get(day_index as usize)?;
         total_staked_at_day_index = total_staked_at_day_index
             .checked_add(staked_amount)
-            .unwrap();
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
         let mut increase = daily_staking_reward
             .checked_mul(user_staked_amount.into())
-            .unwrap();
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
-        let boosted_day_index = day_index.checked_sub(user_start_day_index).unwrap();
+        let boosted_day_index = day_index
+            .checked_sub(user_start_day_index)
+            .ok_or(StakingErrorCode::InvalidDayIndex)?;
         let boost = user_boosted_days.get(boosted_day_index as usize)?;
         if boost {
-            increase = increase.checked_mul(U256::new(2)).unwrap();
+            increase = increase
+                .checked_mul(BOOST_MULTIPLIER.into())
+                .ok_or(StakingErrorCode::ArithmeticOverflow)?;
         }
 
         let increase = increase
             .checked_div(total_staked_at_day_index.into())
-            .unwrap()
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?
             .as_u64();
 
         if !boost {
-            remainings = remainings.checked_add(increase).unwrap();
+            remainings = remainings
+                .checked_add(increase)
+                .ok_or(StakingErrorCode::ArithmeticOverflow)?;
         }
 
-        reward = reward.checked_add(increase).unwrap();
+        reward = reward
+            .checked_add(increase)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
         let min_window_index_next_day = day_index
             .checked_add(1)
-            .and_then(|v| v.checked_sub(DAYS_IN_WINDOW));
+            .and_then(|v| v.checked_sub(days_in_window));
 
         if let Some(min_window_index_next_day) = min_window_index_next_day {
             let staked_amount = staked_amounts.get(min_window_index_next_day as usize)?;
             total_staked_at_day_index = total_staked_at_day_index
                 .checked_sub(staked_amount)
-                .unwrap();
+                .ok_or(StakingErrorCode::ArithmeticOverflow)?;
         }
     }
 
     Ok((reward, remainings))
 }
 
-pub fn update_state_accounts(
-    user_info: &mut Account<UserInfo>,
-    staking_info: &mut Account<StakingInfo>,
-) -> Result<()> {
-    staking_info.update_daily_reward()?;
-
-    let user_has_ended_stake = user_info.has_ended_stake(staking_info.end_day)?;
-    if !user_has_ended_stake {
-        return Ok(());
-    }
+/// Reward and unspent amount of the active stake of the user. Before the stake
+/// ends it only counts stakes which are already made
+pub fn calculate_stake_reward(
+    user_info: &Account<UserInfo>,
+    staking_info: &Account<StakingInfo>,
+) -> Result<(u64, u64)> {
+    calculate_stake_reward_with_boosts(user_info, staking_info, &user_info.get_vector()?)
+}
 
-    let total_days = staking_info.total_days();
+pub fn calculate_stake_reward_with_boosts(
+    user_info: &Account<UserInfo>,
+    staking_info: &Account<StakingInfo>,
+    user_boosted_days: &LazyVector<bool>,
+) -> Result<(u64, u64)> {
+    let total_days = staking_info.total_days()?;
+    let days_in_window = staking_info.days_in_window();
     let staking_start_day = staking_info.start_day;
     let staked_amounts = staking_info.get_vector()?;
 
-    let user_start_day = user_info.start_day.unwrap();
+    let user_start_day = user_info.start_day.ok_or(StakingErrorCode::NoActiveStake)?;
     let user_staked_amount = user_info.staked_amount;
     let daily_staking_reward = user_info.daily_staking_reward;
-    let user_start_day_index = user_start_day.checked_sub(staking_start_day).unwrap();
-    let user_boosted_days = user_info.get_vector()?;
+    let user_start_day_index = user_start_day
+        .checked_sub(staking_start_day)
+        .ok_or(StakingErrorCode::InvalidDayIndex)?;
 
-    let (reward, unspent_amount) = calculate_user_reward_with_unspent_rewards(
+    calculate_user_reward_with_unspent_rewards(
         user_staked_amount,
         user_start_day_index,
-        &user_boosted_days,
+        user_boosted_days,
         &staked_amounts,
         total_days,
+        days_in_window,
         daily_staking_reward,
-    )?;
+    )
+}
+
+pub fn update_state_accounts(
+    user_info: &mut Account<UserInfo>,
+    staking_info: &mut Account<StakingInfo>,
+) -> Result<()> {
+    staking_info.update_daily_reward()?;
+
+    let user_has_ended_stake = user_info.has_ended_stake(staking_info)?;
+    if !user_has_ended_stake {
+        return Ok(());
+    }
+
+    let (reward, unspent_amount) = calculate_stake_reward(user_info, staking_info)?;
 
     user_info.start_day = None;
-    user_info.total_rewarded_amount = user_info.total_rewarded_amount.checked_add(reward).unwrap();
-    user_info.rewarded_amount = user_info.rewarded_amount.checked_add(reward).unwrap();
+    user_info.total_rewarded_amount = user_info
+        .total_rewarded_amount
+        .checked_add(reward)
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+    user_info.rewarded_amount = user_info
+        .rewarded_amount
+        .checked_add(reward)
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
     user_info.pending_amount = user_info
         .pending_amount
         .checked_add(user_info.staked_amount)
-        .unwrap();
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
     user_info.staked_amount = 0;
 
-    staking_info.active_stakes_number = staking_info.active_stakes_number.checked_sub(1).unwrap();
+    staking_info.active_stakes_number = staking_info
+        .active_stakes_number
+        .checked_sub(1)
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
     staking_info.total_unspent_amount = staking_info
         .total_unspent_amount
         .checked_add(unspent_amount)
-        .unwrap();
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
     staking_info.total_rewarded_amount = staking_info
         .total_rewarded_amount
         .checked_add(reward)
-        .unwrap();
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+    staking_info.total_pending_withdrawal_amount = staking_info
+        .total_pending_withdrawal_amount
+        .checked_add(reward)
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+    Ok(())
+}
+
+/// Adds tokens to the stake of a user. If the user has an active stake, tokens
+/// are added to the pending amount until the stake ends
+pub fn add_stake(
+    user_info: &mut Account<UserInfo>,
+    staking_info: &mut Account<StakingInfo>,
+    amount: u64,
+) -> Result<()> {
+    if user_info.has_active_stake() {
+        require_neq!(amount, 0, StakingErrorCode::AddZeroTokensToPendingAmount);
+        user_info.pending_amount = user_info
+            .pending_amount
+            .checked_add(amount)
+            .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+        return Ok(());
+    }
+
+    let increment = user_info
+        .pending_amount
+        .checked_add(amount)
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+    user_info.staked_amount = user_info
+        .staked_amount
+        .checked_add(increment)
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+    user_info.pending_amount = 0;
+
+    require_neq!(
+        user_info.staked_amount,
+        0,
+        StakingErrorCode::StakeZeroTokens
+    );
+
+    require_gte!(
+        user_info.staked_amount,
+        staking_info.min_stake_size,
+        StakingErrorCode::SmallStakeSize
+    );
+
+    let start_day = staking_info.current_day()?;
+    let stake_weight = staking_info.stake_weight(start_day, user_info.staked_amount)?;
+
+    user_info.daily_staking_reward = staking_info.last_daily_reward;
+    user_info.start_day = Some(start_day);
+    user_info.total_staked_amount = user_info
+        .total_staked_amount
+        .checked_add(user_info.staked_amount)
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+    user_info.total_stake_weight = user_info
+        .total_stake_weight
+        .checked_add(stake_weight)
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+    let mut user_boosted_days = user_info.get_vector()?;
+    user_boosted_days.clear()?;
+
+    let mut staked_amounts = staking_info.get_vector()?;
+    let day_index = staking_info.day_index()? as usize;
+    let previous_amount = staked_amounts.get(day_index)?;
+    let new_amount = previous_amount
+        .checked_add(user_info.staked_amount)
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+    staked_amounts.set(day_index, &new_amount)?;
+
+    staking_info.last_day_with_stake = staking_info.current_day()?;
+
+    staking_info.active_stakes_number = staking_info
+        .active_stakes_number
+        .checked_add(1)
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+    staking_info.total_stakes_number = staking_info
+        .total_stakes_number
+        .checked_add(1)
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+    staking_info.total_staked_amount = staking_info
+        .total_staked_amount
+        .checked_add(user_info.staked_amount)
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
+
+    staking_info.total_stake_weight = staking_info
+        .total_stake_weight
+        .checked_add(stake_weight)
+        .ok_or(StakingErrorCode::ArithmeticOverflow)?;
 
     Ok(())
 }
@@ -239,7 +971,17 @@ pub fn update_state_accounts(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::{cell::RefCell, rc::Rc};
+    use crate::state::{Redistribution, DEFAULT_DAYS_IN_WINDOW};
+    use anchor_lang::solana_program::program_pack::Pack;
+    use anchor_spl::token::spl_token;
+    use std::{cell::RefCell, fmt::Debug, rc::Rc};
+
+    fn assert_error<T: Debug>(result: Result<T>, code: StakingErrorCode) {
+        match result.unwrap_err() {
+            Error::AnchorError(error) => assert_eq!(error.error_code_number, u32::from(code)),
+            error => panic!("Unexpected error: {}", error),
+        }
+    }
 
     #[test]
     fn daily_staking_reward() {
@@ -257,7 +999,8 @@ mod tests {
                 0,
                 total_rewarded_free_amount,
                 reward_tokens_amount,
-            );
+            )
+            .unwrap();
 
             total_rewarded_free_amount += rewarded_free_amount;
             assert_eq!(daily_reward, 500_000);
@@ -273,7 +1016,8 @@ mod tests {
                 1_000_000,
                 total_rewarded_free_amount,
                 reward_tokens_amount,
-            );
+            )
+            .unwrap();
 
             total_rewarded_free_amount += rewarded_free_amount;
             assert_eq!(50505, daily_reward / 10);
@@ -289,7 +1033,8 @@ mod tests {
                 2_000_000,
                 total_rewarded_free_amount,
                 reward_tokens_amount,
-            );
+            )
+            .unwrap();
 
             total_rewarded_free_amount += rewarded_free_amount;
             assert_eq!(51020, daily_reward / 10);
@@ -305,7 +1050,8 @@ mod tests {
                 10_000_000,
                 total_rewarded_free_amount,
                 reward_tokens_amount,
-            );
+            )
+            .unwrap();
 
             total_rewarded_free_amount += 2 * rewarded_free_amount;
             assert_eq!(55555, daily_reward / 10);
@@ -321,7 +1067,8 @@ mod tests {
                 500_000,
                 total_rewarded_free_amount,
                 reward_tokens_amount,
-            );
+            )
+            .unwrap();
 
             total_rewarded_free_amount += rewarded_free_amount;
             assert_eq!(50252, daily_reward / 10);
@@ -337,7 +1084,8 @@ mod tests {
                 1_000_000,
                 total_rewarded_free_amount,
                 reward_tokens_amount,
-            );
+            )
+            .unwrap();
 
             total_rewarded_free_amount += rewarded_free_amount;
             assert_eq!(50510, daily_reward / 10);
@@ -354,7 +1102,8 @@ mod tests {
                 1_500_000,
                 total_rewarded_free_amount,
                 reward_tokens_amount,
-            );
+            )
+            .unwrap();
 
             total_rewarded_free_amount += rewarded_free_amount;
             assert_eq!(50765, daily_reward / 10);
@@ -401,8 +1150,12 @@ mod tests {
         ];
 
         for (index, expected_value) in expected_values.iter().enumerate() {
-            let actual_value =
-                calculate_total_staked_amount_before_day(index as u64, &staked_amounts).unwrap();
+            let actual_value = calculate_total_staked_amount_before_day(
+                index as u64,
+                DEFAULT_DAYS_IN_WINDOW,
+                &staked_amounts,
+            )
+            .unwrap();
 
             assert_eq!(actual_value, *expected_value, "Index: {}", index);
         }
@@ -458,6 +1211,7 @@ mod tests {
             &boosted_days,
             &staked_amounts,
             total_days,
+            DEFAULT_DAYS_IN_WINDOW,
             daily_staking_reward,
         )
         .unwrap();
@@ -465,7 +1219,7 @@ mod tests {
         assert_eq!(reward, 510);
         assert_eq!(remainings, 30);
 
-        boosted_days.clear();
+        boosted_days.clear().unwrap();
 
         // User 2
         // 2: 1500 / 2500 * 100 = 60
@@ -483,6 +1237,7 @@ mod tests {
             &boosted_days,
             &staked_amounts,
             total_days,
+            DEFAULT_DAYS_IN_WINDOW,
             daily_staking_reward,
         )
         .unwrap();
@@ -506,6 +1261,7 @@ mod tests {
             &boosted_days,
             &staked_amounts,
             total_days,
+            DEFAULT_DAYS_IN_WINDOW,
             daily_staking_reward,
         )
         .unwrap();
@@ -529,6 +1285,7 @@ mod tests {
             &boosted_days,
             &staked_amounts,
             total_days,
+            DEFAULT_DAYS_IN_WINDOW,
             daily_staking_reward,
         )
         .unwrap();
@@ -549,6 +1306,7 @@ mod tests {
             &boosted_days,
             &staked_amounts,
             total_days,
+            DEFAULT_DAYS_IN_WINDOW,
             daily_staking_reward,
         )
         .unwrap();
@@ -563,10 +1321,262 @@ mod tests {
             &boosted_days,
             &staked_amounts,
             total_days,
+            DEFAULT_DAYS_IN_WINDOW,
             daily_staking_reward,
         )
         .unwrap();
         assert_eq!(reward, 0);
         assert_eq!(remainings, 0);
     }
+
+    #[test]
+    fn user_reward_with_long_window() {
+        let total_days = 20;
+        let days_in_window = 14;
+        let daily_staking_reward = 100;
+
+        let mut staked_amounts_buffer = [0u8; 160];
+        let staked_amounts_data = Rc::new(RefCell::new(staked_amounts_buffer.as_mut()));
+        let mut staked_amounts = LazyVector::new(0, 20, 8, staked_amounts_data).unwrap();
+
+        // User 1 staked 1000 tokens in day 0
+        staked_amounts.set(0, &1000).unwrap();
+
+        // User 2 staked 1000 tokens in day 10
+        staked_amounts.set(10, &1000).unwrap();
+
+        let mut boosted_days_buffer = [0u8; 14];
+        let boosted_days_data = Rc::new(RefCell::new(boosted_days_buffer.as_mut()));
+        let boosted_days = LazyVector::new(0, 14, 1, boosted_days_data).unwrap();
+
+        // User 1
+        // 0..9: 1000 / 1000 * 100 = 100
+        // 10..13: 1000 / 2000 * 100 = 50
+        // Total: 1200
+
+        let (reward, remainings) = calculate_user_reward_with_unspent_rewards(
+            1000,
+            0,
+            &boosted_days,
+            &staked_amounts,
+            total_days,
+            days_in_window,
+            daily_staking_reward,
+        )
+        .unwrap();
+
+        assert_eq!(reward, 1200);
+        assert_eq!(remainings, 1200);
+
+        // User 2
+        // 10..13: 1000 / 2000 * 100 = 50
+        // 14..19: 1000 / 1000 * 100 = 100
+        // Total: 800
+
+        let (reward, remainings) = calculate_user_reward_with_unspent_rewards(
+            1000,
+            10,
+            &boosted_days,
+            &staked_amounts,
+            total_days,
+            days_in_window,
+            daily_staking_reward,
+        )
+        .unwrap();
+
+        assert_eq!(reward, 800);
+        assert_eq!(remainings, 800);
+    }
+
+    #[test]
+    fn daily_staking_reward_after_end() {
+        assert_error(
+            calculate_daily_staking_reward(101, 100, 0, 0, 100_000_000),
+            StakingErrorCode::InvalidDayIndex,
+        );
+    }
+
+    #[test]
+    fn daily_staking_reward_with_overspent_unspent_amount() {
+        assert_error(
+            calculate_daily_staking_reward(10, 100, 1_000, 1_001, 100_000_000),
+            StakingErrorCode::ArithmeticOverflow,
+        );
+    }
+
+    #[test]
+    fn unspent_amount_without_days() {
+        assert_error(
+            calculate_unspent_amount_from_days_with_no_reward(1, 0, 100_000_000),
+            StakingErrorCode::ArithmeticOverflow,
+        );
+    }
+
+    #[test]
+    fn total_staked_amount_overflow() {
+        let mut buffer = [0; 3 * 8];
+        let data = Rc::new(RefCell::new(buffer.as_mut()));
+        let mut staked_amounts = LazyVector::new(0, 3, 8, data).unwrap();
+        staked_amounts.set(0, &u64::MAX).unwrap();
+        staked_amounts.set(1, &1).unwrap();
+
+        assert_error(
+            calculate_total_staked_amount_before_day(2, DEFAULT_DAYS_IN_WINDOW, &staked_amounts),
+            StakingErrorCode::ArithmeticOverflow,
+        );
+    }
+
+    #[test]
+    fn redistribution_share_without_weight() {
+        let redistribution = Redistribution {
+            staking_info: Pubkey::default(),
+            bump: 0,
+            amount: 1000,
+            total_weight: 0,
+            distributed_amount: 0,
+        };
+
+        assert_error(
+            redistribution.share_of(10),
+            StakingErrorCode::ArithmeticOverflow,
+        );
+    }
+
+    #[test]
+    fn redistribution_share_overflow() {
+        let redistribution = Redistribution {
+            staking_info: Pubkey::default(),
+            bump: 0,
+            amount: u64::MAX,
+            total_weight: 1,
+            distributed_amount: 0,
+        };
+
+        assert_error(
+            redistribution.share_of(2),
+            StakingErrorCode::ArithmeticOverflow,
+        );
+    }
+
+    #[test]
+    fn redistribution_share_of_large_weights() {
+        let redistribution = Redistribution {
+            staking_info: Pubkey::default(),
+            bump: 0,
+            amount: u64::MAX,
+            total_weight: u128::MAX,
+            distributed_amount: 0,
+        };
+
+        assert_eq!(redistribution.share_of(u128::MAX).unwrap(), u64::MAX);
+        assert_eq!(
+            redistribution.share_of(u128::MAX / 2).unwrap(),
+            u64::MAX / 2
+        );
+    }
+
+    #[test]
+    fn deserialize_legacy_user_info() {
+        let mut data = <UserInfo as anchor_lang::Discriminator>::discriminator().to_vec();
+        data.extend([7; 32]);
+        data.resize(UserInfo::LEN_V1 + DEFAULT_DAYS_IN_WINDOW as usize, 1);
+
+        assert!(UserInfo::try_deserialize(&mut data.as_ref()).is_err());
+
+        let user_info = UserInfo::try_deserialize_versioned(&data).unwrap();
+        assert_eq!(user_info.user, Pubkey::new_from_array([7; 32]));
+        assert_eq!(user_info.total_boost_number, u64::from_le_bytes([1; 8]));
+    }
+
+    #[test]
+    fn fee_amounts_of_unknown_recipient() {
+        let recipient = |address, transaction_share| chill_nft::state::Recipient {
+            address,
+            mint_share: 0,
+            transaction_share,
+        };
+
+        let owners = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let config = Config {
+            bump: 0,
+            primary_wallet: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            fees: Default::default(),
+            recipients: vec![recipient(owners[0], 70), recipient(owners[1], 30)],
+        };
+
+        let keys = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut lamports = [0, 0];
+        let mut data = owners.map(|owner| {
+            let token_account = spl_token::state::Account {
+                mint: config.mint,
+                owner,
+                state: spl_token::state::AccountState::Initialized,
+                ..Default::default()
+            };
+
+            let mut data = vec![0; spl_token::state::Account::LEN];
+            spl_token::state::Account::pack(token_account, &mut data).unwrap();
+            data
+        });
+
+        let accounts = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|((key, lamports), data)| {
+                AccountInfo::new(key, false, true, lamports, data, &token::ID, false, 0)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            calculate_fee_amounts(1001, &config, &accounts).unwrap(),
+            vec![701, 300]
+        );
+
+        let mut unknown_config = config.clone();
+        unknown_config.recipients[1].address = Pubkey::new_unique();
+        assert_error(
+            calculate_fee_amounts(1001, &unknown_config, &accounts),
+            StakingErrorCode::WrongRecipient,
+        );
+    }
+
+    #[test]
+    fn layout_versions() {
+        let days_len = 10 * 8;
+        let legacy_len = StakingInfo::LEN_V1 - 1 + days_len;
+        let current_len = StakingInfo::LEN + days_len;
+
+        assert_eq!(
+            layout_version(legacy_len, days_len, StakingInfo::fields_len).unwrap(),
+            0
+        );
+        assert_eq!(
+            layout_version(legacy_len + 1, days_len, StakingInfo::fields_len).unwrap(),
+            1
+        );
+        assert_eq!(
+            layout_version(current_len, days_len, StakingInfo::fields_len).unwrap(),
+            StakingInfo::VERSION
+        );
+        assert_error(
+            layout_version(current_len + 1, days_len, StakingInfo::fields_len),
+            StakingErrorCode::WrongVectorSize,
+        );
+
+        let window = DEFAULT_DAYS_IN_WINDOW as usize;
+        assert_eq!(
+            layout_version(UserInfo::LEN_V1 - 1 + window, window, UserInfo::fields_len).unwrap(),
+            0
+        );
+        assert_eq!(
+            layout_version(UserInfo::LEN_V1 + window, window, UserInfo::fields_len).unwrap(),
+            1
+        );
+        assert_eq!(
+            layout_version(UserInfo::LEN + window, window, UserInfo::fields_len).unwrap(),
+            UserInfo::VERSION
+        );
+    }
 }
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct InitializeVesting {
+    pub vesting: Pubkey,
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub total_amount: u64,
+    pub start_time: u64,
+    pub cliff_time: u64,
+    pub end_time: u64,
+}
+
+#[event]
+pub struct ClaimVested {
+    pub vesting: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RevokeVesting {
+    pub vesting: Pubkey,
+    pub unvested_amount: u64,
+}
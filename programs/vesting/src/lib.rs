@@ -0,0 +1,260 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount},
+};
+use state::Vesting;
+
+declare_id!("9xqa3bcFUqtqQztFMiaFpySy7UANNVUZhPY6FG1PcVQK");
+
+pub mod event;
+pub mod state;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct InitializeVestingArgs {
+    pub start_time: u64,
+    pub cliff_time: u64,
+    pub end_time: u64,
+    pub period: u64,
+    pub total_amount: u64,
+    pub revocable: bool,
+}
+
+fn now() -> Result<u64> {
+    Ok(Clock::get()?.unix_timestamp as u64)
+}
+
+#[program]
+pub mod chill_vesting {
+
+    use super::*;
+
+    /// Locks the total amount of the allocation in the vault of the vesting
+    pub fn initialize_vesting(
+        ctx: Context<InitializeVesting>,
+        args: InitializeVestingArgs,
+    ) -> Result<()> {
+        require_gt!(args.total_amount, 0, ErrorCode::ZeroAmount);
+        require!(
+            args.start_time <= args.cliff_time
+                && args.cliff_time <= args.end_time
+                && args.start_time < args.end_time,
+            ErrorCode::WrongTimeRange
+        );
+        require!(
+            args.period <= args.end_time - args.start_time,
+            ErrorCode::WrongPeriod
+        );
+
+        let vesting = &mut ctx.accounts.vesting;
+        vesting.bump = ctx.bumps["vesting"];
+        vesting.authority = ctx.accounts.authority.key();
+        vesting.beneficiary = ctx.accounts.beneficiary.key();
+        vesting.mint = ctx.accounts.mint.key();
+        vesting.start_time = args.start_time;
+        vesting.cliff_time = args.cliff_time;
+        vesting.end_time = args.end_time;
+        vesting.period = args.period;
+        vesting.total_amount = args.total_amount;
+        vesting.revocable = args.revocable;
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.authority_token_account.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ),
+            args.total_amount,
+        )?;
+
+        emit!(event::InitializeVesting {
+            vesting: vesting.key(),
+            beneficiary: vesting.beneficiary,
+            mint: vesting.mint,
+            total_amount: args.total_amount,
+            start_time: args.start_time,
+            cliff_time: args.cliff_time,
+            end_time: args.end_time,
+        });
+
+        Ok(())
+    }
+
+    /// Transfers tokens unlocked since the previous claim to the beneficiary
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        let vesting = &mut ctx.accounts.vesting;
+        let amount = vesting.claimable_amount(now()?);
+        require_gt!(amount, 0, ErrorCode::NothingToClaim);
+
+        vesting.claimed_amount = vesting.claimed_amount.checked_add(amount).unwrap();
+
+        let seeds = &[
+            Vesting::SEED,
+            vesting.authority.as_ref(),
+            vesting.beneficiary.as_ref(),
+            vesting.mint.as_ref(),
+            &[vesting.bump],
+        ];
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.beneficiary_token_account.to_account_info(),
+                    authority: vesting.to_account_info(),
+                },
+            )
+            .with_signer(&[seeds]),
+            amount,
+        )?;
+
+        emit!(event::ClaimVested {
+            vesting: vesting.key(),
+            beneficiary: vesting.beneficiary,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Returns tokens which are not unlocked yet to the treasury. Unlocked
+    /// tokens are left to the beneficiary, the vesting ends right away
+    pub fn revoke(ctx: Context<Revoke>) -> Result<()> {
+        let vesting = &mut ctx.accounts.vesting;
+        require!(vesting.revocable, ErrorCode::NotRevocable);
+        require!(!vesting.revoked, ErrorCode::AlreadyRevoked);
+
+        let now = now()?;
+        let vested_amount = vesting.vested_amount(now);
+        let unvested_amount = vesting.total_amount - vested_amount;
+
+        vesting.revoked = true;
+        vesting.total_amount = vested_amount;
+        vesting.end_time = vesting.end_time.min(now);
+        vesting.cliff_time = vesting.cliff_time.min(vesting.end_time);
+
+        let seeds = &[
+            Vesting::SEED,
+            vesting.authority.as_ref(),
+            vesting.beneficiary.as_ref(),
+            vesting.mint.as_ref(),
+            &[vesting.bump],
+        ];
+
+        if unvested_amount > 0 {
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Transfer {
+                        from: ctx.accounts.vault.to_account_info(),
+                        to: ctx.accounts.treasury_token_account.to_account_info(),
+                        authority: vesting.to_account_info(),
+                    },
+                )
+                .with_signer(&[seeds]),
+                unvested_amount,
+            )?;
+        }
+
+        emit!(event::RevokeVesting {
+            vesting: vesting.key(),
+            unvested_amount,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeVesting<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: any account is able to receive vested tokens
+    pub beneficiary: UncheckedAccount<'info>,
+
+    #[account(init, payer = payer, space = Vesting::LEN,
+              seeds = [Vesting::SEED, authority.key().as_ref(), beneficiary.key().as_ref(), mint.key().as_ref()], bump)]
+    pub vesting: Account<'info, Vesting>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut, token::authority = authority, token::mint = mint)]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    #[account(init, payer = payer, associated_token::mint = mint, associated_token::authority = vesting)]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    pub beneficiary: Signer<'info>,
+
+    #[account(mut, has_one = beneficiary @ ErrorCode::WrongBeneficiary)]
+    pub vesting: Account<'info, Vesting>,
+
+    #[account(mut, associated_token::mint = vesting.mint, associated_token::authority = vesting)]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = vesting.mint)]
+    pub beneficiary_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Revoke<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut, has_one = authority @ ErrorCode::WrongAuthority)]
+    pub vesting: Account<'info, Vesting>,
+
+    #[account(mut, associated_token::mint = vesting.mint, associated_token::authority = vesting)]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = vesting.mint)]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Amount must be greater than zero")]
+    ZeroAmount,
+
+    #[msg("Start time must not exceed the cliff and the cliff must not exceed the end time")]
+    WrongTimeRange,
+
+    #[msg("Period must not exceed the duration of the vesting")]
+    WrongPeriod,
+
+    #[msg("No unlocked tokens to claim")]
+    NothingToClaim,
+
+    #[msg("Vesting is not revocable")]
+    NotRevocable,
+
+    #[msg("Vesting is already revoked")]
+    AlreadyRevoked,
+
+    #[msg("Wrong authority")]
+    WrongAuthority,
+
+    #[msg("Wrong beneficiary")]
+    WrongBeneficiary,
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+pub const DESCRIMINATOR_LEN: usize = 8;
+
+/// Tokens of the vault are unlocked linearly from the start time, but nothing
+/// can be claimed before the cliff. With a non-zero period they are unlocked
+/// in steps of the period instead
+#[account]
+pub struct Vesting {
+    pub bump: u8,
+    pub authority: Pubkey,
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub start_time: u64,
+    pub cliff_time: u64,
+    pub end_time: u64,
+    pub period: u64,
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub revocable: bool,
+    pub revoked: bool,
+}
+
+impl Vesting {
+    pub const LEN: usize = DESCRIMINATOR_LEN + 1 + 32 * 3 + 8 * 6 + 1 + 1;
+    pub const SEED: &'static [u8] = b"vesting";
+
+    pub fn vested_amount(&self, now: u64) -> u64 {
+        if now < self.cliff_time {
+            return 0;
+        }
+
+        if now >= self.end_time {
+            return self.total_amount;
+        }
+
+        let mut elapsed = now.saturating_sub(self.start_time);
+        if let Some(steps) = elapsed.checked_div(self.period) {
+            elapsed = steps * self.period;
+        }
+
+        let duration = self.end_time - self.start_time;
+        (self.total_amount as u128 * elapsed as u128 / duration as u128) as u64
+    }
+
+    pub fn claimable_amount(&self, now: u64) -> u64 {
+        self.vested_amount(now).saturating_sub(self.claimed_amount)
+    }
+}
@@ -1,3 +1,4 @@
+use crate::state::DailyLimits;
 use anchor_lang::prelude::*;
 
 #[event]
@@ -5,6 +6,97 @@ pub struct CreateWallet {
     pub user: Pubkey,
 }
 
+#[event]
+pub struct MigrateWallet {
+    pub user: Pubkey,
+}
+
+#[event]
+pub struct DepositLamports {
+    pub depositor: Pubkey,
+    pub proxy_wallet: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct DepositFt {
+    pub depositor: Pubkey,
+    pub proxy_wallet: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct CreateSessionKey {
+    pub proxy_wallet: Pubkey,
+    pub signer: Pubkey,
+    pub expires_at_slot: u64,
+    pub permissions: u8,
+}
+
+#[event]
+pub struct RevokeSessionKey {
+    pub proxy_wallet: Pubkey,
+    pub signer: Pubkey,
+}
+
+#[event]
+pub struct WrapSol {
+    pub proxy_wallet: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct UnwrapSol {
+    pub proxy_wallet: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct StakeFromWallet {
+    pub proxy_wallet: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct MintNftFromWallet {
+    pub proxy_wallet: Pubkey,
+    pub nft_mint: Pubkey,
+    pub fee: u64,
+}
+
+#[event]
+pub struct FreezeWallet {
+    pub proxy_wallet: Pubkey,
+}
+
+#[event]
+pub struct UnfreezeWallet {
+    pub proxy_wallet: Pubkey,
+}
+
+#[event]
+pub struct SetSpendingLimits {
+    pub proxy_wallet: Pubkey,
+    pub user: DailyLimits,
+    pub primary_wallet: DailyLimits,
+}
+
+#[event]
+pub struct SetWithdrawalFee {
+    pub proxy_wallet: Pubkey,
+    pub fee_bps: u16,
+    pub fee_receiver: Pubkey,
+}
+
+// Lamports are withdrawn if there's no mint
+#[event]
+pub struct WithdrawalFee {
+    pub proxy_wallet: Pubkey,
+    pub mint: Option<Pubkey>,
+    pub amount: u64,
+}
+
 #[event]
 pub struct WithdrawLamports {
     pub authority: Pubkey,
@@ -21,3 +113,44 @@ pub struct WithdrawFt {
 pub struct WithdrawNft {
     pub authority: Pubkey,
 }
+
+#[event]
+pub struct ScheduleWithdrawal {
+    pub authority: Pubkey,
+    pub receiver: Pubkey,
+    pub amount: u64,
+    pub interval: u64,
+}
+
+#[event]
+pub struct ExecuteScheduledWithdrawal {
+    pub receiver: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct CancelScheduledWithdrawal {
+    pub authority: Pubkey,
+    pub receiver: Pubkey,
+}
+
+#[event]
+pub struct LendNft {
+    pub proxy_wallet: Pubkey,
+    pub mint: Pubkey,
+    pub price: u64,
+    pub duration: u64,
+}
+
+#[event]
+pub struct RentNft {
+    pub mint: Pubkey,
+    pub renter: Pubkey,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct ReclaimNft {
+    pub proxy_wallet: Pubkey,
+    pub mint: Pubkey,
+}
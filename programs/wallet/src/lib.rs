@@ -1,7 +1,32 @@
-use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
-use state::ProxyWallet;
-use utils::{check_authority, transfer_tokens};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        hash::hash,
+        instruction::Instruction,
+        program::{invoke, invoke_signed},
+    },
+    InstructionData,
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount},
+};
+use chill_nft::{
+    metaplex_adapter::{Metadata, TokenMetadataProgram},
+    program::ChillNft,
+    state::AdminConfig,
+    utils::{check_admin_signers, realloc_with_rent},
+};
+use chill_staking::program::ChillStaking;
+use state::{
+    DailyLimits, NftRental, ProxyWallet, RentalReceipt, SessionKey, WithdrawalLogEntry,
+    WithdrawalSchedule,
+};
+use utils::{
+    acting_authority, assert_devnet_tools, check_authority, check_creator, check_session_authority,
+    create_proxy_wallet, is_primary_sale, release_rental_escrow, set_primary_sale, take_ft_fee,
+    take_lamports_fee, transfer_lamports, transfer_tokens,
+};
 
 declare_id!("FSo9ozLkvW6HTCJ9XfK4eiBWkLCUcmiQ6F1d2kjtJf2Y");
 
@@ -14,6 +39,7 @@ pub mod chill_wallet {
 
     use super::*;
 
+    #[access_control(check_creator(&ctx.accounts.payer, &ctx.accounts.user, &ctx.accounts.primary_wallet))]
     pub fn create_wallet(ctx: Context<CreateWallet>) -> Result<()> {
         let bump = ctx.bumps["proxy_wallet"];
         let proxy_wallet = &mut ctx.accounts.proxy_wallet;
@@ -28,9 +54,254 @@ pub mod chill_wallet {
         Ok(())
     }
 
-    #[access_control(check_authority(&ctx.accounts.authority, &ctx.accounts.proxy_wallet))]
+    /// Remaining accounts are signers of the admin config of the primary wallet
+    /// followed by pairs of a user and its proxy wallet. Existing wallets are
+    /// skipped, so a failed batch might be sent again
+    pub fn create_wallets<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateWallets<'info>>,
+    ) -> Result<()> {
+        let remaining_accounts =
+            check_admin_signers(&ctx.accounts.admin_config, ctx.remaining_accounts)?;
+        require!(
+            !remaining_accounts.is_empty()
+                && remaining_accounts.chunks_exact(2).remainder().is_empty(),
+            ErrorCode::WrongWalletsList
+        );
+
+        for accounts in remaining_accounts.chunks_exact(2) {
+            let user = &accounts[0];
+            let created = create_proxy_wallet(
+                &ctx.accounts.payer,
+                &ctx.accounts.primary_wallet,
+                user,
+                &accounts[1],
+                &ctx.accounts.system_program,
+            )?;
+
+            if created {
+                emit!(event::CreateWallet { user: user.key() });
+            }
+        }
+
+        Ok(())
+    }
+
+    // Anyone may migrate a wallet of an older layout paying for the extra
+    // space. Deposit counters of migrated wallets start from zero, spending is
+    // not limited and the wallet is not frozen
+    pub fn migrate_wallet(ctx: Context<MigrateWallet>) -> Result<()> {
+        let proxy_wallet = &ctx.accounts.proxy_wallet;
+        require_keys_eq!(*proxy_wallet.owner, crate::ID, ErrorCode::WalletIsMigrated);
+        require!(
+            proxy_wallet.data_len() < ProxyWallet::LEN,
+            ErrorCode::WalletIsMigrated
+        );
+
+        realloc_with_rent(
+            &proxy_wallet.to_account_info(),
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            ProxyWallet::LEN,
+        )?;
+
+        emit!(event::MigrateWallet {
+            user: ctx.accounts.user.key()
+        });
+
+        Ok(())
+    }
+
+    pub fn deposit_lamports(ctx: Context<DepositLamports>, amount: u64) -> Result<()> {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.depositor.to_account_info(),
+                to: ctx.accounts.proxy_wallet.to_account_info(),
+            },
+        );
+
+        anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+        let proxy_wallet = &mut ctx.accounts.proxy_wallet;
+        proxy_wallet.total_money_deposited = proxy_wallet
+            .total_money_deposited
+            .checked_add(amount)
+            .unwrap();
+
+        emit!(event::DepositLamports {
+            depositor: ctx.accounts.depositor.key(),
+            proxy_wallet: proxy_wallet.key(),
+            amount
+        });
+
+        Ok(())
+    }
+
+    pub fn deposit_ft(ctx: Context<DepositFt>, amount: u64) -> Result<()> {
+        require!(!utils::is_nft(&ctx.accounts.mint), ErrorCode::TokenIsNft);
+
+        let cpi_context = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.depositor_token_account.to_account_info(),
+                to: ctx.accounts.proxy_wallet_token_account.to_account_info(),
+                authority: ctx.accounts.depositor.to_account_info(),
+            },
+        );
+
+        token::transfer(cpi_context, amount)?;
+
+        let proxy_wallet = &mut ctx.accounts.proxy_wallet;
+        proxy_wallet.total_ft_deposited =
+            proxy_wallet.total_ft_deposited.checked_add(amount).unwrap();
+
+        emit!(event::DepositFt {
+            depositor: ctx.accounts.depositor.key(),
+            proxy_wallet: proxy_wallet.key(),
+            mint: ctx.accounts.mint.key(),
+            amount
+        });
+
+        Ok(())
+    }
+
+    pub fn set_spending_limits(
+        ctx: Context<SetSpendingLimits>,
+        user: DailyLimits,
+        primary_wallet: DailyLimits,
+    ) -> Result<()> {
+        check_admin_signers(&ctx.accounts.admin_config, ctx.remaining_accounts)?;
+
+        let proxy_wallet = &mut ctx.accounts.proxy_wallet;
+        proxy_wallet.user_limit.limits = user;
+        proxy_wallet.primary_wallet_limit.limits = primary_wallet;
+
+        emit!(event::SetSpendingLimits {
+            proxy_wallet: proxy_wallet.key(),
+            user,
+            primary_wallet,
+        });
+
+        Ok(())
+    }
+
+    pub fn create_session_key(
+        ctx: Context<CreateSessionKey>,
+        expires_at_slot: u64,
+        permissions: u8,
+        lamports_allowance: u64,
+        ft_allowance: u64,
+    ) -> Result<()> {
+        require_gt!(
+            expires_at_slot,
+            Clock::get()?.slot,
+            ErrorCode::InvalidSessionKeyExpiry
+        );
+        require!(
+            permissions != 0 && permissions & !SessionKey::ALL_PERMISSIONS == 0,
+            ErrorCode::SessionKeyNotPermitted
+        );
+
+        let session_key = &mut ctx.accounts.session_key;
+        session_key.bump = ctx.bumps["session_key"];
+        session_key.proxy_wallet = ctx.accounts.proxy_wallet.key();
+        session_key.signer = ctx.accounts.session_signer.key();
+        session_key.expires_at_slot = expires_at_slot;
+        session_key.permissions = permissions;
+        session_key.lamports_allowance = lamports_allowance;
+        session_key.ft_allowance = ft_allowance;
+
+        emit!(event::CreateSessionKey {
+            proxy_wallet: session_key.proxy_wallet,
+            signer: session_key.signer,
+            expires_at_slot,
+            permissions,
+        });
+
+        Ok(())
+    }
+
+    pub fn revoke_session_key(ctx: Context<RevokeSessionKey>) -> Result<()> {
+        emit!(event::RevokeSessionKey {
+            proxy_wallet: ctx.accounts.proxy_wallet.key(),
+            signer: ctx.accounts.session_key.signer,
+        });
+
+        Ok(())
+    }
+
+    // Withdrawals of a frozen wallet fail until the primary wallet unfreezes it
+    pub fn freeze_wallet(ctx: Context<FreezeWallet>) -> Result<()> {
+        let proxy_wallet = &mut ctx.accounts.proxy_wallet;
+        require!(!proxy_wallet.frozen, ErrorCode::WalletFrozen);
+        proxy_wallet.frozen = true;
+
+        emit!(event::FreezeWallet {
+            proxy_wallet: proxy_wallet.key()
+        });
+
+        Ok(())
+    }
+
+    pub fn unfreeze_wallet(ctx: Context<FreezeWallet>) -> Result<()> {
+        let proxy_wallet = &mut ctx.accounts.proxy_wallet;
+        require!(proxy_wallet.frozen, ErrorCode::WalletIsNotFrozen);
+        proxy_wallet.frozen = false;
+
+        emit!(event::UnfreezeWallet {
+            proxy_wallet: proxy_wallet.key()
+        });
+
+        Ok(())
+    }
+
+    // Withdrawals of lamports and fungible tokens send `fee_bps` of their
+    // amounts to the fee receiver. Zero disables the fee
+    pub fn set_withdrawal_fee(
+        ctx: Context<SetWithdrawalFee>,
+        fee_bps: u16,
+        fee_receiver: Pubkey,
+    ) -> Result<()> {
+        check_admin_signers(&ctx.accounts.admin_config, ctx.remaining_accounts)?;
+        require_gte!(ProxyWallet::MAX_FEE_BPS, fee_bps, ErrorCode::InvalidFeeBps);
+
+        let proxy_wallet = &mut ctx.accounts.proxy_wallet;
+        require_keys_neq!(
+            proxy_wallet.key(),
+            fee_receiver,
+            ErrorCode::SenderIsRecipient
+        );
+
+        proxy_wallet.fee_bps = fee_bps;
+        proxy_wallet.fee_receiver = fee_receiver;
+
+        emit!(event::SetWithdrawalFee {
+            proxy_wallet: proxy_wallet.key(),
+            fee_bps,
+            fee_receiver,
+        });
+
+        Ok(())
+    }
+
+    #[access_control(check_session_authority(
+        &ctx.accounts.authority,
+        &ctx.accounts.proxy_wallet,
+        ctx.remaining_accounts,
+        SessionKey::WITHDRAW_LAMPORTS,
+        amount
+    ))]
     pub fn withdraw_lamports(ctx: Context<WithdrawLamports>, amount: u64) -> Result<()> {
-        let authority_key = ctx.accounts.authority.key();
+        let authority_key = acting_authority(&ctx.accounts.authority, &ctx.accounts.proxy_wallet);
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            ctx.accounts
+                .proxy_wallet
+                .spending_limit(&authority_key)
+                .spend_lamports(now, amount),
+            ErrorCode::SpendingLimitExceeded
+        );
+
         let proxy_wallet_info = ctx.accounts.proxy_wallet.to_account_info();
         let receiver_info = ctx.accounts.receiver.to_account_info();
 
@@ -40,21 +311,12 @@ pub mod chill_wallet {
             ErrorCode::SenderIsRecipient
         );
 
-        let rent = Rent::get()?;
-        let minimum_balance = rent.minimum_balance(ProxyWallet::LEN);
-
-        let proxy_wallet_balance = proxy_wallet_info
-            .lamports()
-            .checked_sub(minimum_balance)
-            .unwrap();
-
-        require_gte!(proxy_wallet_balance, amount, ErrorCode::InsufficientFunds);
-
-        let new_receiver_balance = receiver_info.lamports().checked_add(amount).unwrap();
-        let new_wallet_balance = proxy_wallet_info.lamports().checked_sub(amount).unwrap();
-
-        **receiver_info.lamports.borrow_mut() = new_receiver_balance;
-        **proxy_wallet_info.lamports.borrow_mut() = new_wallet_balance;
+        let receiver_amount = take_lamports_fee(
+            &mut ctx.accounts.proxy_wallet,
+            ctx.remaining_accounts,
+            amount,
+        )?;
+        transfer_lamports(&proxy_wallet_info, &receiver_info, receiver_amount)?;
 
         let proxy_wallet = &mut ctx.accounts.proxy_wallet;
         if authority_key == proxy_wallet.user {
@@ -69,6 +331,13 @@ pub mod chill_wallet {
                 .unwrap();
         }
 
+        proxy_wallet.log_withdrawal(WithdrawalLogEntry {
+            timestamp: now,
+            authority: authority_key,
+            mint: Pubkey::default(),
+            amount,
+        });
+
         emit!(event::WithdrawLamports {
             authority: authority_key,
             amount
@@ -77,8 +346,17 @@ pub mod chill_wallet {
         Ok(())
     }
 
-    #[access_control(check_authority(&ctx.accounts.authority, &ctx.accounts.proxy_wallet))]
-    pub fn withdraw_ft(ctx: Context<WithdrawFt>, amount: u64) -> Result<()> {
+    #[access_control(check_session_authority(
+        &ctx.accounts.authority,
+        &ctx.accounts.proxy_wallet,
+        ctx.remaining_accounts,
+        SessionKey::WITHDRAW_FT,
+        amount
+    ))]
+    pub fn withdraw_ft<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawFt<'info>>,
+        amount: u64,
+    ) -> Result<()> {
         let mint = &ctx.accounts.mint;
         let proxy_wallet = &mut ctx.accounts.proxy_wallet;
         let proxy_wallet_token_account = &ctx.accounts.proxy_wallet_token_account;
@@ -92,15 +370,31 @@ pub mod chill_wallet {
             ErrorCode::SenderIsRecipient
         );
 
+        let authority_key = acting_authority(&ctx.accounts.authority, proxy_wallet);
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            proxy_wallet
+                .spending_limit(&authority_key)
+                .spend_ft(now, amount),
+            ErrorCode::SpendingLimitExceeded
+        );
+
+        let receiver_amount = take_ft_fee(
+            proxy_wallet,
+            proxy_wallet_token_account,
+            ctx.remaining_accounts,
+            &ctx.accounts.token_program,
+            amount,
+        )?;
+
         transfer_tokens(
             proxy_wallet,
             &ctx.accounts.proxy_wallet_token_account,
             &ctx.accounts.receiver_token_account,
             &ctx.accounts.token_program,
-            amount,
+            receiver_amount,
         )?;
 
-        let authority_key = ctx.accounts.authority.key();
         if authority_key == proxy_wallet.user {
             proxy_wallet.total_ft_withdrawn_user = proxy_wallet
                 .total_ft_withdrawn_user
@@ -113,6 +407,13 @@ pub mod chill_wallet {
                 .unwrap();
         }
 
+        proxy_wallet.log_withdrawal(WithdrawalLogEntry {
+            timestamp: now,
+            authority: authority_key,
+            mint: mint.key(),
+            amount,
+        });
+
         emit!(event::WithdrawFt {
             authority: authority_key,
             amount
@@ -121,7 +422,113 @@ pub mod chill_wallet {
         Ok(())
     }
 
+    /// Remaining accounts are triples of a mint, a token account of the proxy
+    /// wallet and a token account of the receiver, followed by a token account
+    /// of the fee receiver if the wallet takes a withdrawal fee. Whole balances
+    /// are withdrawn
     #[access_control(check_authority(&ctx.accounts.authority, &ctx.accounts.proxy_wallet))]
+    pub fn withdraw_ft_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawFtBatch<'info>>,
+    ) -> Result<()> {
+        let remaining_accounts = ctx.remaining_accounts;
+        let chunk_len = if ctx.accounts.proxy_wallet.fee_bps > 0 {
+            4
+        } else {
+            3
+        };
+        require!(
+            !remaining_accounts.is_empty()
+                && remaining_accounts
+                    .chunks_exact(chunk_len)
+                    .remainder()
+                    .is_empty(),
+            ErrorCode::WrongWithdrawalList
+        );
+
+        let authority_key = ctx.accounts.authority.key();
+        let now = Clock::get()?.unix_timestamp;
+        let proxy_wallet = &mut ctx.accounts.proxy_wallet;
+        let mut total_amount: u64 = 0;
+
+        for accounts in remaining_accounts.chunks_exact(chunk_len) {
+            let mint = Account::<Mint>::try_from(&accounts[0])?;
+            let proxy_wallet_token_account = Account::<TokenAccount>::try_from(&accounts[1])?;
+            let receiver_token_account = Account::<TokenAccount>::try_from(&accounts[2])?;
+
+            require!(!utils::is_nft(&mint), ErrorCode::TokenIsNft);
+            require!(
+                proxy_wallet_token_account.owner == proxy_wallet.key()
+                    && proxy_wallet_token_account.mint == mint.key()
+                    && receiver_token_account.mint == mint.key(),
+                ErrorCode::WrongWithdrawalList
+            );
+            require_keys_neq!(
+                proxy_wallet_token_account.key(),
+                receiver_token_account.key(),
+                ErrorCode::SenderIsRecipient
+            );
+
+            let amount = proxy_wallet_token_account.amount;
+            require!(
+                proxy_wallet
+                    .spending_limit(&authority_key)
+                    .spend_ft(now, amount),
+                ErrorCode::SpendingLimitExceeded
+            );
+
+            let receiver_amount = take_ft_fee(
+                proxy_wallet,
+                &proxy_wallet_token_account,
+                &accounts[3..],
+                &ctx.accounts.token_program,
+                amount,
+            )?;
+
+            transfer_tokens(
+                proxy_wallet,
+                &proxy_wallet_token_account,
+                &receiver_token_account,
+                &ctx.accounts.token_program,
+                receiver_amount,
+            )?;
+
+            total_amount = total_amount.checked_add(amount).unwrap();
+
+            proxy_wallet.log_withdrawal(WithdrawalLogEntry {
+                timestamp: now,
+                authority: authority_key,
+                mint: mint.key(),
+                amount,
+            });
+
+            emit!(event::WithdrawFt {
+                authority: authority_key,
+                amount
+            });
+        }
+
+        if authority_key == proxy_wallet.user {
+            proxy_wallet.total_ft_withdrawn_user = proxy_wallet
+                .total_ft_withdrawn_user
+                .checked_add(total_amount)
+                .unwrap();
+        } else {
+            proxy_wallet.total_ft_withdrawn_primary_wallet = proxy_wallet
+                .total_ft_withdrawn_primary_wallet
+                .checked_add(total_amount)
+                .unwrap();
+        }
+
+        Ok(())
+    }
+
+    #[access_control(check_session_authority(
+        &ctx.accounts.authority,
+        &ctx.accounts.proxy_wallet,
+        ctx.remaining_accounts,
+        SessionKey::WITHDRAW_NFT,
+        1
+    ))]
     pub fn withdraw_nft(ctx: Context<WithdrawNft>) -> Result<()> {
         let nft_mint = &ctx.accounts.nft_mint;
         let proxy_wallet = &mut ctx.accounts.proxy_wallet;
@@ -144,7 +551,7 @@ pub mod chill_wallet {
             1,
         )?;
 
-        let authority_key = ctx.accounts.authority.key();
+        let authority_key = acting_authority(&ctx.accounts.authority, proxy_wallet);
         if authority_key == proxy_wallet.user {
             proxy_wallet.total_nft_withdrawn_user = proxy_wallet
                 .total_nft_withdrawn_user
@@ -157,92 +564,1121 @@ pub mod chill_wallet {
                 .unwrap();
         }
 
+        proxy_wallet.log_withdrawal(WithdrawalLogEntry {
+            timestamp: Clock::get()?.unix_timestamp,
+            authority: authority_key,
+            mint: nft_mint.key(),
+            amount: 1,
+        });
+
         emit!(event::WithdrawNft {
             authority: authority_key,
         });
 
         Ok(())
     }
-}
 
-#[derive(Accounts)]
-pub struct CreateWallet<'info> {
-    pub primary_wallet: SystemAccount<'info>,
+    /// Withdraws an NFT by the primary wallet. If it's the first sale of the
+    /// NFT, its primary sale is marked and the recipient becomes a creator in
+    /// the same transaction
+    pub fn transfer_nft_with_update(ctx: Context<TransferNftWithUpdate>) -> Result<()> {
+        let accounts = ctx.accounts;
+        let primary_wallet = &accounts.primary_wallet;
+        let proxy_wallet_token_account = &accounts.proxy_wallet_token_account;
+        let receiver_token_account = &accounts.receiver_token_account;
 
-    pub user: SystemAccount<'info>,
+        require!(utils::is_nft(&accounts.nft_mint), ErrorCode::TokenIsNotNft);
 
-    #[account(mut)]
-    pub payer: Signer<'info>,
+        require_keys_neq!(
+            proxy_wallet_token_account.key(),
+            receiver_token_account.key(),
+            ErrorCode::SenderIsRecipient
+        );
 
-    #[account(init, payer = payer, space = ProxyWallet::LEN,
-              seeds = [ProxyWallet::SEED, user.key.as_ref(), primary_wallet.key.as_ref()], bump)]
-    pub proxy_wallet: Account<'info, ProxyWallet>,
+        let recipient = receiver_token_account.owner;
+        if is_primary_sale(&accounts.nft_metadata, primary_wallet.key(), recipient) {
+            set_primary_sale(
+                primary_wallet,
+                &accounts.nft_metadata,
+                &accounts.token_metadata_program,
+                recipient,
+            )?;
+        }
 
-    pub system_program: Program<'info, System>,
-}
+        let proxy_wallet = &mut accounts.proxy_wallet;
+        transfer_tokens(
+            proxy_wallet,
+            proxy_wallet_token_account,
+            receiver_token_account,
+            &accounts.token_program,
+            1,
+        )?;
 
-#[derive(Accounts)]
-pub struct WithdrawLamports<'info> {
-    pub authority: Signer<'info>,
+        proxy_wallet.total_nft_withdrawn_primary_wallet = proxy_wallet
+            .total_nft_withdrawn_primary_wallet
+            .checked_add(1)
+            .unwrap();
 
-    #[account(mut)]
-    pub proxy_wallet: Account<'info, ProxyWallet>,
+        proxy_wallet.log_withdrawal(WithdrawalLogEntry {
+            timestamp: Clock::get()?.unix_timestamp,
+            authority: primary_wallet.key(),
+            mint: accounts.nft_mint.key(),
+            amount: 1,
+        });
 
-    /// CHECK: this account is not being read
-    #[account(mut)]
-    pub receiver: UncheckedAccount<'info>,
-}
+        emit!(event::WithdrawNft {
+            authority: primary_wallet.key(),
+        });
 
-#[derive(Accounts)]
-pub struct WithdrawFt<'info> {
-    pub authority: Signer<'info>,
+        Ok(())
+    }
 
-    #[account(mut)]
-    pub proxy_wallet: Account<'info, ProxyWallet>,
+    /// Moves lamports of the proxy wallet into its wSOL token account
+    #[access_control(check_authority(&ctx.accounts.authority, &ctx.accounts.proxy_wallet))]
+    pub fn wrap_sol(ctx: Context<WrapSol>, amount: u64) -> Result<()> {
+        let proxy_wallet_info = ctx.accounts.proxy_wallet.to_account_info();
+        let token_account_info = ctx.accounts.proxy_wallet_token_account.to_account_info();
 
-    pub mint: Account<'info, Mint>,
+        transfer_lamports(&proxy_wallet_info, &token_account_info, amount)?;
 
-    #[account(mut, token::authority = proxy_wallet, token::mint = mint)]
-    pub proxy_wallet_token_account: Account<'info, TokenAccount>,
+        let ix = token::spl_token::instruction::sync_native(&token::ID, token_account_info.key)?;
+        invoke(
+            &ix,
+            &[
+                token_account_info,
+                ctx.accounts.token_program.to_account_info(),
+            ],
+        )?;
 
-    #[account(mut, token::mint = mint)]
-    pub receiver_token_account: Account<'info, TokenAccount>,
+        emit!(event::WrapSol {
+            proxy_wallet: proxy_wallet_info.key(),
+            amount
+        });
 
-    pub token_program: Program<'info, Token>,
-}
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct WithdrawNft<'info> {
-    pub authority: Signer<'info>,
+    /// Closes a wSOL token account of the proxy wallet, its lamports are
+    /// returned to the proxy wallet
+    #[access_control(check_authority(&ctx.accounts.authority, &ctx.accounts.proxy_wallet))]
+    pub fn unwrap_sol(ctx: Context<UnwrapSol>) -> Result<()> {
+        let proxy_wallet = &ctx.accounts.proxy_wallet;
+        let token_account_info = ctx.accounts.proxy_wallet_token_account.to_account_info();
+        let amount = token_account_info.lamports();
 
-    #[account(mut)]
-    pub proxy_wallet: Account<'info, ProxyWallet>,
+        let seeds = &[
+            ProxyWallet::SEED,
+            proxy_wallet.user.as_ref(),
+            proxy_wallet.primary_wallet.as_ref(),
+            &[proxy_wallet.bump],
+        ];
 
-    pub nft_mint: Account<'info, Mint>,
+        token::close_account(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::CloseAccount {
+                    account: token_account_info,
+                    destination: proxy_wallet.to_account_info(),
+                    authority: proxy_wallet.to_account_info(),
+                },
+            )
+            .with_signer(&[seeds]),
+        )?;
 
-    #[account(mut, token::authority = proxy_wallet, token::mint = nft_mint)]
-    pub proxy_wallet_token_account: Account<'info, TokenAccount>,
+        emit!(event::UnwrapSol {
+            proxy_wallet: proxy_wallet.key(),
+            amount
+        });
 
-    #[account(mut, token::mint = nft_mint)]
-    pub receiver_token_account: Account<'info, TokenAccount>,
+        Ok(())
+    }
 
-    pub token_program: Program<'info, Token>,
-}
+    /// Stakes CHILL tokens of the proxy wallet for its user in chill_staking.
+    /// The stake is paid out to any account of the user, so the withdrawal fee
+    /// is taken from the amount and the rest is staked
+    pub fn stake_from_wallet<'info>(
+        ctx: Context<'_, '_, '_, 'info, StakeFromWallet<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let user_key = ctx.accounts.user.key();
+        let proxy_wallet = &mut ctx.accounts.proxy_wallet;
+        require!(
+            proxy_wallet.spending_limit(&user_key).spend_ft(now, amount),
+            ErrorCode::SpendingLimitExceeded
+        );
 
-#[error_code]
-pub enum ErrorCode {
-    #[msg("Insufficient funds")]
-    InsufficientFunds,
+        let staked_amount = take_ft_fee(
+            proxy_wallet,
+            &ctx.accounts.proxy_wallet_token_account,
+            ctx.remaining_accounts,
+            &ctx.accounts.token_program,
+            amount,
+        )?;
 
-    #[msg("Token is an NFT")]
-    TokenIsNft,
+        let seeds = &[
+            ProxyWallet::SEED,
+            proxy_wallet.user.as_ref(),
+            proxy_wallet.primary_wallet.as_ref(),
+            &[proxy_wallet.bump],
+        ];
 
-    #[msg("Token is not an NFT")]
-    TokenIsNotNft,
+        let accounts = chill_staking::accounts::Stake {
+            user: user_key,
+            token_account_authority: proxy_wallet.key(),
+            payer: ctx.accounts.payer.key(),
+            from_token_account: ctx.accounts.proxy_wallet_token_account.key(),
+            staking_info: ctx.accounts.staking_info.key(),
+            user_info: ctx.accounts.user_info.key(),
+            staking_token_authority: ctx.accounts.staking_token_authority.key(),
+            staking_token_account: ctx.accounts.staking_token_account.key(),
+            system_program: ctx.accounts.system_program.key(),
+            token_program: ctx.accounts.token_program.key(),
+        };
+        let data = chill_staking::instruction::Stake {
+            amount: staked_amount,
+            referrer: None,
+        };
+        let ix = Instruction {
+            program_id: chill_staking::ID,
+            accounts: accounts.to_account_metas(None),
+            data: data.data(),
+        };
 
-    #[msg("Wrong authority")]
-    WrongAuthority,
+        invoke_signed(
+            &ix,
+            &[
+                ctx.accounts.user.to_account_info(),
+                proxy_wallet.to_account_info(),
+                ctx.accounts.payer.to_account_info(),
+                ctx.accounts.proxy_wallet_token_account.to_account_info(),
+                ctx.accounts.staking_info.to_account_info(),
+                ctx.accounts.user_info.to_account_info(),
+                ctx.accounts.staking_token_authority.to_account_info(),
+                ctx.accounts.staking_token_account.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.staking_program.to_account_info(),
+            ],
+            &[seeds],
+        )?;
 
-    #[msg("Sender and recipient are same")]
-    SenderIsRecipient,
+        proxy_wallet.total_ft_withdrawn_user = proxy_wallet
+            .total_ft_withdrawn_user
+            .checked_add(amount)
+            .unwrap();
+
+        proxy_wallet.log_withdrawal(WithdrawalLogEntry {
+            timestamp: now,
+            authority: user_key,
+            mint: ctx.accounts.proxy_wallet_token_account.mint,
+            amount,
+        });
+
+        emit!(event::StakeFromWallet {
+            proxy_wallet: proxy_wallet.key(),
+            amount
+        });
+
+        Ok(())
+    }
+
+    /// Mints an NFT with chill_nft paying the CHILL fee from the proxy wallet.
+    /// `data` is the serialized `mint_nft` instruction, remaining accounts are
+    /// passed to it as they are
+    pub fn mint_nft_from_wallet<'info>(
+        ctx: Context<'_, '_, '_, 'info, MintNftFromWallet<'info>>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        require!(
+            data.starts_with(&hash(b"global:mint_nft").to_bytes()[..8]),
+            ErrorCode::WrongInstructionData
+        );
+
+        let accounts = &ctx.accounts;
+        let proxy_wallet = &accounts.proxy_wallet;
+        let balance_before = accounts.proxy_wallet_token_account.amount;
+
+        let mut ix = Instruction {
+            program_id: chill_nft::ID,
+            accounts: chill_nft::accounts::MintNft {
+                primary_wallet: accounts.primary_wallet.key(),
+                payer: accounts.payer.key(),
+                chill_payer: proxy_wallet.key(),
+                chill_payer_token_account: accounts.proxy_wallet_token_account.key(),
+                config: accounts.config.key(),
+                chill_mint: accounts.chill_mint.key(),
+                nft_type_registry: accounts.nft_type_registry.key(),
+                nft_mint: accounts.nft_mint.key(),
+                nft_metadata: accounts.nft_metadata.key(),
+                nft_master_edition: accounts.nft_master_edition.key(),
+                nft_chill_metadata: accounts.nft_chill_metadata.key(),
+                rent: accounts.rent.key(),
+                system_program: accounts.system_program.key(),
+                token_program: accounts.token_program.key(),
+                token_metadata_program: accounts.token_metadata_program.key(),
+            }
+            .to_account_metas(None),
+            data,
+        };
+        ix.accounts
+            .extend(ctx.remaining_accounts.iter().map(|account| {
+                if account.is_writable {
+                    AccountMeta::new(account.key(), false)
+                } else {
+                    AccountMeta::new_readonly(account.key(), false)
+                }
+            }));
+
+        let mut account_infos = vec![
+            accounts.primary_wallet.to_account_info(),
+            accounts.payer.to_account_info(),
+            proxy_wallet.to_account_info(),
+            accounts.proxy_wallet_token_account.to_account_info(),
+            accounts.config.to_account_info(),
+            accounts.chill_mint.to_account_info(),
+            accounts.nft_type_registry.to_account_info(),
+            accounts.nft_mint.to_account_info(),
+            accounts.nft_metadata.to_account_info(),
+            accounts.nft_master_edition.to_account_info(),
+            accounts.nft_chill_metadata.to_account_info(),
+            accounts.rent.to_account_info(),
+            accounts.system_program.to_account_info(),
+            accounts.token_program.to_account_info(),
+            accounts.token_metadata_program.to_account_info(),
+            accounts.nft_program.to_account_info(),
+        ];
+        account_infos.extend_from_slice(ctx.remaining_accounts);
+
+        let seeds = &[
+            ProxyWallet::SEED,
+            proxy_wallet.user.as_ref(),
+            proxy_wallet.primary_wallet.as_ref(),
+            &[proxy_wallet.bump],
+        ];
+        invoke_signed(&ix, &account_infos, &[seeds])?;
+
+        let proxy_wallet_token_account = &mut ctx.accounts.proxy_wallet_token_account;
+        proxy_wallet_token_account.reload()?;
+        let fee = balance_before
+            .checked_sub(proxy_wallet_token_account.amount)
+            .unwrap();
+
+        let now = Clock::get()?.unix_timestamp;
+        let primary_wallet_key = ctx.accounts.primary_wallet.key();
+        let proxy_wallet = &mut ctx.accounts.proxy_wallet;
+        require!(
+            proxy_wallet
+                .spending_limit(&primary_wallet_key)
+                .spend_ft(now, fee),
+            ErrorCode::SpendingLimitExceeded
+        );
+        proxy_wallet.total_ft_withdrawn_primary_wallet = proxy_wallet
+            .total_ft_withdrawn_primary_wallet
+            .checked_add(fee)
+            .unwrap();
+
+        proxy_wallet.log_withdrawal(WithdrawalLogEntry {
+            timestamp: now,
+            authority: primary_wallet_key,
+            mint: ctx.accounts.chill_mint.key(),
+            amount: fee,
+        });
+
+        emit!(event::MintNftFromWallet {
+            proxy_wallet: proxy_wallet.key(),
+            nft_mint: ctx.accounts.nft_mint.key(),
+            fee,
+        });
+
+        Ok(())
+    }
+
+    #[access_control(check_authority(&ctx.accounts.authority, &ctx.accounts.proxy_wallet))]
+    pub fn schedule_withdrawal(
+        ctx: Context<ScheduleWithdrawal>,
+        amount: u64,
+        interval: u64,
+    ) -> Result<()> {
+        require_keys_neq!(
+            ctx.accounts.proxy_wallet.key(),
+            ctx.accounts.receiver.key(),
+            ErrorCode::SenderIsRecipient
+        );
+
+        require_neq!(amount, 0, ErrorCode::ZeroWithdrawalAmount);
+        require!(
+            interval > 0 && interval <= i64::MAX as u64,
+            ErrorCode::InvalidWithdrawalInterval
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let schedule = &mut ctx.accounts.schedule;
+        schedule.bump = ctx.bumps["schedule"];
+        schedule.proxy_wallet = ctx.accounts.proxy_wallet.key();
+        schedule.authority = ctx.accounts.authority.key();
+        schedule.receiver = ctx.accounts.receiver.key();
+        schedule.amount = amount;
+        schedule.interval = interval;
+        schedule.next_withdrawal_time = now.checked_add(interval as i64).unwrap();
+
+        emit!(event::ScheduleWithdrawal {
+            authority: schedule.authority,
+            receiver: schedule.receiver,
+            amount,
+            interval,
+        });
+
+        Ok(())
+    }
+
+    pub fn execute_due_withdrawal(ctx: Context<ExecuteDueWithdrawal>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let schedule = &mut ctx.accounts.schedule;
+
+        require_gte!(
+            now,
+            schedule.next_withdrawal_time,
+            ErrorCode::WithdrawalIsNotDue
+        );
+
+        // The crank is permissionless, so due withdrawals are counted against
+        // the daily limits of the authority which scheduled them
+        let amount = schedule.amount;
+        require!(
+            ctx.accounts
+                .proxy_wallet
+                .spending_limit(&schedule.authority)
+                .spend_lamports(now, amount),
+            ErrorCode::SpendingLimitExceeded
+        );
+
+        let receiver_amount = take_lamports_fee(
+            &mut ctx.accounts.proxy_wallet,
+            ctx.remaining_accounts,
+            amount,
+        )?;
+        transfer_lamports(
+            &ctx.accounts.proxy_wallet.to_account_info(),
+            &ctx.accounts.receiver.to_account_info(),
+            receiver_amount,
+        )?;
+
+        schedule.advance(now);
+
+        let proxy_wallet = &mut ctx.accounts.proxy_wallet;
+        if schedule.authority == proxy_wallet.user {
+            proxy_wallet.total_money_withdrawn_user = proxy_wallet
+                .total_money_withdrawn_user
+                .checked_add(amount)
+                .unwrap();
+        } else {
+            proxy_wallet.total_money_withdrawn_primary_wallet = proxy_wallet
+                .total_money_withdrawn_primary_wallet
+                .checked_add(amount)
+                .unwrap();
+        }
+
+        proxy_wallet.log_withdrawal(WithdrawalLogEntry {
+            timestamp: now,
+            authority: schedule.authority,
+            mint: Pubkey::default(),
+            amount,
+        });
+
+        emit!(event::ExecuteScheduledWithdrawal {
+            receiver: schedule.receiver,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    #[access_control(check_authority(&ctx.accounts.authority, &ctx.accounts.proxy_wallet))]
+    pub fn cancel_scheduled_withdrawal(ctx: Context<CancelScheduledWithdrawal>) -> Result<()> {
+        emit!(event::CancelScheduledWithdrawal {
+            authority: ctx.accounts.authority.key(),
+            receiver: ctx.accounts.schedule.receiver,
+        });
+
+        Ok(())
+    }
+
+    // The NFT is lent for `duration` seconds per rental, renters pay `price`
+    // lamports to the proxy wallet
+    #[access_control(check_authority(&ctx.accounts.authority, &ctx.accounts.proxy_wallet))]
+    pub fn lend_nft(ctx: Context<LendNft>, price: u64, duration: u64) -> Result<()> {
+        require!(
+            utils::is_nft(&ctx.accounts.nft_mint),
+            ErrorCode::TokenIsNotNft
+        );
+        require!(
+            duration > 0 && duration <= i64::MAX as u64,
+            ErrorCode::InvalidRentalDuration
+        );
+
+        let rental = &mut ctx.accounts.rental;
+        rental.bump = ctx.bumps["rental"];
+        rental.proxy_wallet = ctx.accounts.proxy_wallet.key();
+        rental.nft_mint = ctx.accounts.nft_mint.key();
+        rental.price = price;
+        rental.duration = duration;
+        rental.rent_receiver = ctx.accounts.authority.key();
+
+        let receipt = &mut ctx.accounts.receipt;
+        receipt.bump = ctx.bumps["receipt"];
+        receipt.rental = rental.key();
+
+        transfer_tokens(
+            &ctx.accounts.proxy_wallet,
+            &ctx.accounts.proxy_wallet_token_account,
+            &ctx.accounts.rental_escrow,
+            &ctx.accounts.token_program,
+            1,
+        )?;
+
+        emit!(event::LendNft {
+            proxy_wallet: rental.proxy_wallet,
+            mint: rental.nft_mint,
+            price,
+            duration,
+        });
+
+        Ok(())
+    }
+
+    // The NFT is rented again only after the previous rental expires
+    pub fn rent_nft(ctx: Context<RentNft>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let rental = &ctx.accounts.rental;
+        let receipt = &mut ctx.accounts.receipt;
+        require!(!receipt.is_active(now), ErrorCode::NftIsRented);
+
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.renter.to_account_info(),
+                to: ctx.accounts.proxy_wallet.to_account_info(),
+            },
+        );
+
+        anchor_lang::system_program::transfer(cpi_context, rental.price)?;
+
+        receipt.renter = ctx.accounts.renter.key();
+        receipt.expires_at = now.checked_add(rental.duration as i64).unwrap();
+
+        emit!(event::RentNft {
+            mint: rental.nft_mint,
+            renter: receipt.renter,
+            expires_at: receipt.expires_at,
+        });
+
+        Ok(())
+    }
+
+    #[access_control(check_authority(&ctx.accounts.authority, &ctx.accounts.proxy_wallet))]
+    pub fn reclaim_nft(ctx: Context<ReclaimNft>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(!ctx.accounts.receipt.is_active(now), ErrorCode::NftIsRented);
+
+        let accounts = &ctx.accounts;
+        release_rental_escrow(
+            &accounts.rental,
+            &accounts.rental_escrow,
+            &accounts.proxy_wallet_token_account,
+            &accounts.rent_receiver,
+            &accounts.token_program,
+        )?;
+
+        emit!(event::ReclaimNft {
+            proxy_wallet: accounts.proxy_wallet.key(),
+            mint: accounts.rental.nft_mint,
+        });
+
+        Ok(())
+    }
+
+    /// Last withdrawals of the proxy wallet from the oldest to the latest
+    pub fn view_withdrawal_log(ctx: Context<ViewWallet>) -> Result<Vec<WithdrawalLogEntry>> {
+        Ok(ctx.accounts.proxy_wallet.withdrawals())
+    }
+
+    pub fn view_devnet_tools_enabled(_ctx: Context<ViewState>) -> Result<bool> {
+        Ok(cfg!(feature = "devnet-tools"))
+    }
+
+    #[access_control(check_authority(&ctx.accounts.authority, &ctx.accounts.proxy_wallet))]
+    pub fn devnet_make_withdrawal_due(ctx: Context<DevnetMakeWithdrawalDue>) -> Result<()> {
+        assert_devnet_tools()?;
+
+        let schedule = &mut ctx.accounts.schedule;
+        schedule.next_withdrawal_time = Clock::get()?.unix_timestamp;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CreateWallet<'info> {
+    pub primary_wallet: SystemAccount<'info>,
+
+    pub user: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(init, payer = payer, space = ProxyWallet::LEN,
+              seeds = [ProxyWallet::SEED, user.key.as_ref(), primary_wallet.key.as_ref()], bump)]
+    pub proxy_wallet: Box<Account<'info, ProxyWallet>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateWallets<'info> {
+    /// CHECK: approved by signers of the admin config
+    pub primary_wallet: UncheckedAccount<'info>,
+
+    #[account(has_one = primary_wallet, seeds = [AdminConfig::SEED, primary_wallet.key().as_ref()],
+              bump = admin_config.bump, seeds::program = chill_nft::ID)]
+    pub admin_config: Account<'info, AdminConfig>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateWallet<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub primary_wallet: SystemAccount<'info>,
+
+    pub user: SystemAccount<'info>,
+
+    /// CHECK: the v1 layout cannot be deserialized as the current one
+    #[account(mut, seeds = [ProxyWallet::SEED, user.key.as_ref(), primary_wallet.key.as_ref()],
+              bump)]
+    pub proxy_wallet: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositLamports<'info> {
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    #[account(mut)]
+    pub proxy_wallet: Box<Account<'info, ProxyWallet>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositFt<'info> {
+    pub depositor: Signer<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut, token::authority = depositor, token::mint = mint)]
+    pub depositor_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub proxy_wallet: Box<Account<'info, ProxyWallet>>,
+
+    #[account(mut, token::authority = proxy_wallet, token::mint = mint)]
+    pub proxy_wallet_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SetSpendingLimits<'info> {
+    /// CHECK: approved by signers of the admin config
+    pub primary_wallet: UncheckedAccount<'info>,
+
+    #[account(has_one = primary_wallet, seeds = [AdminConfig::SEED, primary_wallet.key().as_ref()],
+              bump = admin_config.bump, seeds::program = chill_nft::ID)]
+    pub admin_config: Account<'info, AdminConfig>,
+
+    #[account(mut, has_one = primary_wallet @ ErrorCode::WrongAuthority)]
+    pub proxy_wallet: Box<Account<'info, ProxyWallet>>,
+}
+
+#[derive(Accounts)]
+pub struct CreateSessionKey<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(has_one = user @ ErrorCode::WrongAuthority)]
+    pub proxy_wallet: Box<Account<'info, ProxyWallet>>,
+
+    /// CHECK: any key might sign for the session
+    pub session_signer: UncheckedAccount<'info>,
+
+    #[account(init, payer = user, space = SessionKey::LEN,
+              seeds = [SessionKey::SEED, proxy_wallet.key().as_ref(), session_signer.key.as_ref()],
+              bump)]
+    pub session_key: Account<'info, SessionKey>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeSessionKey<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(has_one = user @ ErrorCode::WrongAuthority)]
+    pub proxy_wallet: Box<Account<'info, ProxyWallet>>,
+
+    #[account(mut, has_one = proxy_wallet, close = user)]
+    pub session_key: Account<'info, SessionKey>,
+}
+
+#[derive(Accounts)]
+pub struct FreezeWallet<'info> {
+    pub primary_wallet: Signer<'info>,
+
+    #[account(mut, has_one = primary_wallet @ ErrorCode::WrongAuthority)]
+    pub proxy_wallet: Box<Account<'info, ProxyWallet>>,
+}
+
+#[derive(Accounts)]
+pub struct SetWithdrawalFee<'info> {
+    /// CHECK: approved by signers of the admin config
+    pub primary_wallet: UncheckedAccount<'info>,
+
+    #[account(has_one = primary_wallet, seeds = [AdminConfig::SEED, primary_wallet.key().as_ref()],
+              bump = admin_config.bump, seeds::program = chill_nft::ID)]
+    pub admin_config: Account<'info, AdminConfig>,
+
+    #[account(mut, has_one = primary_wallet @ ErrorCode::WrongAuthority)]
+    pub proxy_wallet: Box<Account<'info, ProxyWallet>>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawLamports<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut, constraint = !proxy_wallet.frozen @ ErrorCode::WalletFrozen)]
+    pub proxy_wallet: Box<Account<'info, ProxyWallet>>,
+
+    /// CHECK: this account is not being read
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFt<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut, constraint = !proxy_wallet.frozen @ ErrorCode::WalletFrozen)]
+    pub proxy_wallet: Box<Account<'info, ProxyWallet>>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut, token::authority = proxy_wallet, token::mint = mint)]
+    pub proxy_wallet_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = mint)]
+    pub receiver_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFtBatch<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut, constraint = !proxy_wallet.frozen @ ErrorCode::WalletFrozen)]
+    pub proxy_wallet: Box<Account<'info, ProxyWallet>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawNft<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut, constraint = !proxy_wallet.frozen @ ErrorCode::WalletFrozen)]
+    pub proxy_wallet: Box<Account<'info, ProxyWallet>>,
+
+    pub nft_mint: Account<'info, Mint>,
+
+    #[account(mut, token::authority = proxy_wallet, token::mint = nft_mint)]
+    pub proxy_wallet_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = nft_mint)]
+    pub receiver_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct TransferNftWithUpdate<'info> {
+    pub primary_wallet: Signer<'info>,
+
+    #[account(mut, has_one = primary_wallet @ ErrorCode::WrongAuthority,
+              constraint = !proxy_wallet.frozen @ ErrorCode::WalletFrozen)]
+    pub proxy_wallet: Box<Account<'info, ProxyWallet>>,
+
+    pub nft_mint: Account<'info, Mint>,
+
+    #[account(mut, constraint = nft_metadata.mint == nft_mint.key() @ ErrorCode::WrongNftMetadata)]
+    pub nft_metadata: Box<Account<'info, Metadata>>,
+
+    #[account(mut, token::authority = proxy_wallet, token::mint = nft_mint)]
+    pub proxy_wallet_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = nft_mint)]
+    pub receiver_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub token_metadata_program: Program<'info, TokenMetadataProgram>,
+}
+
+#[derive(Accounts)]
+pub struct WrapSol<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut, constraint = !proxy_wallet.frozen @ ErrorCode::WalletFrozen)]
+    pub proxy_wallet: Box<Account<'info, ProxyWallet>>,
+
+    #[account(mut, token::authority = proxy_wallet, token::mint = token::spl_token::native_mint::ID)]
+    pub proxy_wallet_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct UnwrapSol<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut, constraint = !proxy_wallet.frozen @ ErrorCode::WalletFrozen)]
+    pub proxy_wallet: Box<Account<'info, ProxyWallet>>,
+
+    #[account(mut, token::authority = proxy_wallet, token::mint = token::spl_token::native_mint::ID)]
+    pub proxy_wallet_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct StakeFromWallet<'info> {
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut, has_one = user @ ErrorCode::WrongAuthority,
+              constraint = !proxy_wallet.frozen @ ErrorCode::WalletFrozen)]
+    pub proxy_wallet: Box<Account<'info, ProxyWallet>>,
+
+    #[account(mut, token::authority = proxy_wallet)]
+    pub proxy_wallet_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: checked by chill_staking
+    #[account(mut)]
+    pub staking_info: UncheckedAccount<'info>,
+
+    /// CHECK: checked by chill_staking
+    #[account(mut)]
+    pub user_info: UncheckedAccount<'info>,
+
+    /// CHECK: checked by chill_staking
+    pub staking_token_authority: UncheckedAccount<'info>,
+
+    /// CHECK: checked by chill_staking
+    #[account(mut)]
+    pub staking_token_account: UncheckedAccount<'info>,
+
+    pub staking_program: Program<'info, ChillStaking>,
+
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct MintNftFromWallet<'info> {
+    #[account(mut)]
+    pub primary_wallet: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut, has_one = primary_wallet @ ErrorCode::WrongAuthority,
+              constraint = !proxy_wallet.frozen @ ErrorCode::WalletFrozen)]
+    pub proxy_wallet: Box<Account<'info, ProxyWallet>>,
+
+    #[account(mut, token::authority = proxy_wallet)]
+    pub proxy_wallet_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: checked by chill_nft
+    pub config: UncheckedAccount<'info>,
+
+    /// CHECK: checked by chill_nft
+    pub chill_mint: UncheckedAccount<'info>,
+
+    /// CHECK: checked by chill_nft
+    pub nft_type_registry: UncheckedAccount<'info>,
+
+    /// CHECK: checked by chill_nft
+    #[account(mut)]
+    pub nft_mint: UncheckedAccount<'info>,
+
+    /// CHECK: checked by chill_nft
+    #[account(mut)]
+    pub nft_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: checked by chill_nft
+    #[account(mut)]
+    pub nft_master_edition: UncheckedAccount<'info>,
+
+    /// CHECK: checked by chill_nft
+    #[account(mut)]
+    pub nft_chill_metadata: UncheckedAccount<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: checked by chill_nft
+    pub token_metadata_program: UncheckedAccount<'info>,
+
+    pub nft_program: Program<'info, ChillNft>,
+}
+
+#[derive(Accounts)]
+pub struct ScheduleWithdrawal<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub proxy_wallet: Box<Account<'info, ProxyWallet>>,
+
+    /// CHECK: this account is not being read
+    pub receiver: UncheckedAccount<'info>,
+
+    #[account(init, payer = authority, space = WithdrawalSchedule::LEN,
+              seeds = [WithdrawalSchedule::SEED, proxy_wallet.key().as_ref(), receiver.key.as_ref()], bump)]
+    pub schedule: Account<'info, WithdrawalSchedule>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteDueWithdrawal<'info> {
+    #[account(mut, constraint = !proxy_wallet.frozen @ ErrorCode::WalletFrozen)]
+    pub proxy_wallet: Box<Account<'info, ProxyWallet>>,
+
+    #[account(mut, has_one = proxy_wallet, has_one = receiver,
+              seeds = [WithdrawalSchedule::SEED, proxy_wallet.key().as_ref(), receiver.key.as_ref()],
+              bump = schedule.bump)]
+    pub schedule: Account<'info, WithdrawalSchedule>,
+
+    /// CHECK: this account is not being read
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelScheduledWithdrawal<'info> {
+    pub authority: Signer<'info>,
+
+    pub proxy_wallet: Box<Account<'info, ProxyWallet>>,
+
+    #[account(mut, has_one = proxy_wallet,
+              constraint = schedule.authority == schedule_authority.key() @ ErrorCode::WrongAuthority,
+              close = schedule_authority)]
+    pub schedule: Account<'info, WithdrawalSchedule>,
+
+    /// CHECK: receives the rent of the closed schedule
+    #[account(mut)]
+    pub schedule_authority: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct LendNft<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(constraint = !proxy_wallet.frozen @ ErrorCode::WalletFrozen)]
+    pub proxy_wallet: Box<Account<'info, ProxyWallet>>,
+
+    pub nft_mint: Account<'info, Mint>,
+
+    #[account(mut, token::authority = proxy_wallet, token::mint = nft_mint)]
+    pub proxy_wallet_token_account: Account<'info, TokenAccount>,
+
+    #[account(init, payer = authority, space = NftRental::LEN,
+              seeds = [NftRental::SEED, nft_mint.key().as_ref()], bump)]
+    pub rental: Account<'info, NftRental>,
+
+    #[account(init, payer = authority, associated_token::mint = nft_mint,
+              associated_token::authority = rental)]
+    pub rental_escrow: Account<'info, TokenAccount>,
+
+    #[account(init, payer = authority, space = RentalReceipt::LEN,
+              seeds = [RentalReceipt::SEED, rental.key().as_ref()], bump)]
+    pub receipt: Account<'info, RentalReceipt>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct RentNft<'info> {
+    #[account(mut)]
+    pub renter: Signer<'info>,
+
+    #[account(mut)]
+    pub proxy_wallet: Box<Account<'info, ProxyWallet>>,
+
+    #[account(has_one = proxy_wallet, seeds = [NftRental::SEED, rental.nft_mint.as_ref()],
+              bump = rental.bump)]
+    pub rental: Account<'info, NftRental>,
+
+    #[account(mut, has_one = rental, seeds = [RentalReceipt::SEED, rental.key().as_ref()],
+              bump = receipt.bump)]
+    pub receipt: Account<'info, RentalReceipt>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimNft<'info> {
+    pub authority: Signer<'info>,
+
+    pub proxy_wallet: Box<Account<'info, ProxyWallet>>,
+
+    #[account(mut, token::authority = proxy_wallet, token::mint = rental.nft_mint)]
+    pub proxy_wallet_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut, has_one = proxy_wallet, has_one = rent_receiver, close = rent_receiver,
+              seeds = [NftRental::SEED, rental.nft_mint.as_ref()], bump = rental.bump)]
+    pub rental: Account<'info, NftRental>,
+
+    #[account(mut, associated_token::mint = rental.nft_mint, associated_token::authority = rental)]
+    pub rental_escrow: Account<'info, TokenAccount>,
+
+    #[account(mut, has_one = rental, close = rent_receiver,
+              seeds = [RentalReceipt::SEED, rental.key().as_ref()], bump = receipt.bump)]
+    pub receipt: Account<'info, RentalReceipt>,
+
+    /// CHECK: the address is stored in the rental
+    #[account(mut)]
+    pub rent_receiver: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct DevnetMakeWithdrawalDue<'info> {
+    pub authority: Signer<'info>,
+
+    pub proxy_wallet: Box<Account<'info, ProxyWallet>>,
+
+    #[account(mut, has_one = proxy_wallet)]
+    pub schedule: Account<'info, WithdrawalSchedule>,
+}
+
+#[derive(Accounts)]
+pub struct ViewWallet<'info> {
+    pub proxy_wallet: Box<Account<'info, ProxyWallet>>,
+}
+
+#[derive(Accounts)]
+pub struct ViewState {}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Insufficient funds")]
+    InsufficientFunds,
+
+    #[msg("Token is an NFT")]
+    TokenIsNft,
+
+    #[msg("Token is not an NFT")]
+    TokenIsNotNft,
+
+    #[msg("Wrong authority")]
+    WrongAuthority,
+
+    #[msg("Sender and recipient are same")]
+    SenderIsRecipient,
+
+    #[msg("Only the user or the primary wallet can pay for the wallet creation")]
+    WrongCreator,
+
+    #[msg("Withdrawal amount must be greater than zero")]
+    ZeroWithdrawalAmount,
+
+    #[msg("Withdrawal interval must be greater than zero")]
+    InvalidWithdrawalInterval,
+
+    #[msg("Scheduled withdrawal is not due yet")]
+    WithdrawalIsNotDue,
+
+    #[msg("Program is built without devnet tools")]
+    DevnetToolsDisabled,
+
+    #[msg("Wallets list must consist of user and proxy wallet pairs")]
+    WrongWalletsList,
+
+    #[msg("Rental duration must be greater than zero")]
+    InvalidRentalDuration,
+
+    #[msg("NFT is rented until the current rental expires")]
+    NftIsRented,
+
+    #[msg("Proxy wallet is already migrated to the current layout")]
+    WalletIsMigrated,
+
+    #[msg("Daily spending limit of the authority is exceeded")]
+    SpendingLimitExceeded,
+
+    #[msg("Proxy wallet is frozen")]
+    WalletFrozen,
+
+    #[msg("Proxy wallet is not frozen")]
+    WalletIsNotFrozen,
+
+    #[msg("Withdrawal list must consist of mint and token accounts triples")]
+    WrongWithdrawalList,
+
+    #[msg("Session key must expire in a future slot")]
+    InvalidSessionKeyExpiry,
+
+    #[msg("Session key is expired")]
+    SessionKeyExpired,
+
+    #[msg("Session key is not permitted to run the instruction")]
+    SessionKeyNotPermitted,
+
+    #[msg("Allowance of the session key is exceeded")]
+    SessionKeyAllowanceExceeded,
+
+    #[msg("Instruction data doesn't match the instruction")]
+    WrongInstructionData,
+
+    #[msg("Withdrawal fee cannot exceed 10000 basis points")]
+    InvalidFeeBps,
+
+    #[msg("Wrong account of the fee receiver")]
+    WrongFeeReceiver,
+
+    #[msg("Metadata is not of the NFT")]
+    WrongNftMetadata,
 }
@@ -13,10 +13,353 @@ pub struct ProxyWallet {
     pub total_ft_withdrawn_primary_wallet: u64,
     pub total_nft_withdrawn_user: u64,
     pub total_nft_withdrawn_primary_wallet: u64,
+    pub total_money_deposited: u64,
+    pub total_ft_deposited: u64,
+    pub user_limit: SpendingLimit,
+    pub primary_wallet_limit: SpendingLimit,
+    pub frozen: bool,
+
+    // Share of every withdrawal of lamports and fungible tokens which is sent
+    // to the fee receiver, in basis points. Zero means no fee
+    pub fee_bps: u16,
+    pub fee_receiver: Pubkey,
+    pub total_money_fee: u64,
+    pub total_ft_fee: u64,
+
+    // Ring buffer of the last withdrawals, the head is the index of the slot
+    // which is overwritten by the next withdrawal
+    pub withdrawal_log: [WithdrawalLogEntry; 8],
+    pub withdrawal_log_head: u8,
 }
 
 impl ProxyWallet {
-    pub const LEN: usize = DESCRIMINATOR_LEN + 1 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8;
+    // Wallets created before deposit tracking have no deposit counters
+    pub const LEN_V1: usize = DESCRIMINATOR_LEN + 1 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8;
+
+    // Wallets created before spending limits have no limits
+    pub const LEN_V2: usize = Self::LEN_V1 + 8 + 8;
+
+    // Wallets created before freezing have no frozen flag
+    pub const LEN_V3: usize = Self::LEN_V2 + SpendingLimit::LEN + SpendingLimit::LEN;
+
+    // Wallets created before withdrawal fees have no fee
+    pub const LEN_V4: usize = Self::LEN_V3 + 1;
+
+    // Wallets created before the withdrawal log have no log
+    pub const LEN_V5: usize = Self::LEN_V4 + 2 + 32 + 8 + 8;
+
+    pub const LEN: usize = Self::LEN_V5 + WithdrawalLogEntry::LEN * Self::WITHDRAWAL_LOG_LEN + 1;
+
+    pub const WITHDRAWAL_LOG_LEN: usize = 8;
+
+    pub const MAX_FEE_BPS: u16 = 10_000;
 
     pub const SEED: &'static [u8] = b"wallet";
+
+    /// Deserializes wallets of every layout, fields missing in older layouts
+    /// are zeroed the same way as by `migrate_wallet`
+    pub fn try_deserialize_versioned(data: &[u8]) -> Result<Self> {
+        if data.len() >= Self::LEN {
+            return Self::try_deserialize(&mut &data[..]);
+        }
+
+        let mut data = data.to_vec();
+        data.resize(Self::LEN, 0);
+        Self::try_deserialize(&mut data.as_ref())
+    }
+
+    /// The fee is rounded down, so small withdrawals might take no fee
+    pub fn withdrawal_fee(&self, amount: u64) -> u64 {
+        (u128::from(amount) * u128::from(self.fee_bps) / u128::from(Self::MAX_FEE_BPS)) as u64
+    }
+
+    pub fn log_withdrawal(&mut self, entry: WithdrawalLogEntry) {
+        let head = self.withdrawal_log_head as usize % Self::WITHDRAWAL_LOG_LEN;
+        self.withdrawal_log[head] = entry;
+        self.withdrawal_log_head = ((head + 1) % Self::WITHDRAWAL_LOG_LEN) as u8;
+    }
+
+    /// Logged withdrawals from the oldest to the latest
+    pub fn withdrawals(&self) -> Vec<WithdrawalLogEntry> {
+        let head = self.withdrawal_log_head as usize % Self::WITHDRAWAL_LOG_LEN;
+        self.withdrawal_log[head..]
+            .iter()
+            .chain(&self.withdrawal_log[..head])
+            .filter(|entry| !entry.is_empty())
+            .copied()
+            .collect()
+    }
+
+    pub fn spending_limit(&mut self, authority: &Pubkey) -> &mut SpendingLimit {
+        if *authority == self.user {
+            &mut self.user_limit
+        } else {
+            &mut self.primary_wallet_limit
+        }
+    }
+}
+
+// The mint is the default pubkey for withdrawals of lamports, NFTs are logged
+// with an amount of 1
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq)]
+pub struct WithdrawalLogEntry {
+    pub timestamp: i64,
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+impl WithdrawalLogEntry {
+    pub const LEN: usize = 8 + 32 + 32 + 8;
+
+    pub fn is_empty(&self) -> bool {
+        self.authority == Pubkey::default()
+    }
+}
+
+// Zero means no limit
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct DailyLimits {
+    pub lamports: u64,
+    pub ft: u64,
+}
+
+impl DailyLimits {
+    pub const LEN: usize = 8 + 8;
+}
+
+// Spent amounts are reset by the first withdrawal of a new UTC day
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct SpendingLimit {
+    pub limits: DailyLimits,
+    pub day: i64,
+    pub spent_lamports: u64,
+    pub spent_ft: u64,
+}
+
+impl SpendingLimit {
+    pub const LEN: usize = DailyLimits::LEN + 8 + 8 + 8;
+
+    pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+    pub fn spend_lamports(&mut self, now: i64, amount: u64) -> bool {
+        self.start_day(now);
+        Self::spend(&mut self.spent_lamports, self.limits.lamports, amount)
+    }
+
+    pub fn spend_ft(&mut self, now: i64, amount: u64) -> bool {
+        self.start_day(now);
+        Self::spend(&mut self.spent_ft, self.limits.ft, amount)
+    }
+
+    fn start_day(&mut self, now: i64) {
+        let day = now / Self::SECONDS_PER_DAY;
+        if day != self.day {
+            self.day = day;
+            self.spent_lamports = 0;
+            self.spent_ft = 0;
+        }
+    }
+
+    fn spend(spent: &mut u64, limit: u64, amount: u64) -> bool {
+        let total = spent.saturating_add(amount);
+        if limit != 0 && total > limit {
+            return false;
+        }
+
+        *spent = total;
+        true
+    }
+}
+
+#[account]
+pub struct WithdrawalSchedule {
+    pub bump: u8,
+    pub proxy_wallet: Pubkey,
+    pub authority: Pubkey,
+    pub receiver: Pubkey,
+    pub amount: u64,
+    pub interval: u64,
+    pub next_withdrawal_time: i64,
+}
+
+impl WithdrawalSchedule {
+    pub const LEN: usize = DESCRIMINATOR_LEN + 1 + 32 + 32 + 32 + 8 + 8 + 8;
+
+    pub const SEED: &'static [u8] = b"schedule";
+
+    pub fn advance(&mut self, now: i64) {
+        // Missed periods are skipped, so a late crank transfers the amount only once
+        let interval = self.interval as i64;
+        let missed_periods = (now - self.next_withdrawal_time) / interval;
+        self.next_withdrawal_time = self
+            .next_withdrawal_time
+            .checked_add((missed_periods + 1).checked_mul(interval).unwrap())
+            .unwrap();
+    }
+}
+
+// A temporary signer which withdraws from the proxy wallet on behalf of the
+// user, e.g. a key of a game client. Allowances decrease with every withdrawal
+#[account]
+pub struct SessionKey {
+    pub bump: u8,
+    pub proxy_wallet: Pubkey,
+    pub signer: Pubkey,
+    pub expires_at_slot: u64,
+    pub permissions: u8,
+    pub lamports_allowance: u64,
+    pub ft_allowance: u64,
+}
+
+impl SessionKey {
+    pub const LEN: usize = DESCRIMINATOR_LEN + 1 + 32 + 32 + 8 + 1 + 8 + 8;
+
+    pub const SEED: &'static [u8] = b"session";
+
+    pub const WITHDRAW_LAMPORTS: u8 = 1 << 0;
+    pub const WITHDRAW_FT: u8 = 1 << 1;
+    pub const WITHDRAW_NFT: u8 = 1 << 2;
+    pub const ALL_PERMISSIONS: u8 =
+        Self::WITHDRAW_LAMPORTS | Self::WITHDRAW_FT | Self::WITHDRAW_NFT;
+}
+
+// An NFT of a proxy wallet lent for lamports. The NFT stays in the associated
+// token account of the rental until the lender reclaims it
+#[account]
+pub struct NftRental {
+    pub bump: u8,
+    pub proxy_wallet: Pubkey,
+    pub nft_mint: Pubkey,
+    pub price: u64,
+    pub duration: u64,
+
+    // Paid for the rental accounts and receives their rent back
+    pub rent_receiver: Pubkey,
+}
+
+impl NftRental {
+    pub const LEN: usize = DESCRIMINATOR_LEN + 1 + 32 + 32 + 8 + 8 + 32;
+
+    pub const SEED: &'static [u8] = b"rental";
+}
+
+// Usage rights of the current renter, the game lets the renter use the NFT
+// until `expires_at`
+#[account]
+pub struct RentalReceipt {
+    pub bump: u8,
+    pub rental: Pubkey,
+    pub renter: Pubkey,
+    pub expires_at: i64,
+}
+
+impl RentalReceipt {
+    pub const LEN: usize = DESCRIMINATOR_LEN + 1 + 32 + 32 + 8;
+
+    pub const SEED: &'static [u8] = b"rental-receipt";
+
+    pub fn is_active(&self, now: i64) -> bool {
+        now < self.expires_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Discriminator;
+
+    #[test]
+    fn deserialize_v1_proxy_wallet() {
+        let proxy_wallet = ProxyWallet {
+            bump: 255,
+            primary_wallet: Pubkey::new_unique(),
+            user: Pubkey::new_unique(),
+            total_money_withdrawn_user: 1,
+            total_money_withdrawn_primary_wallet: 2,
+            total_ft_withdrawn_user: 3,
+            total_ft_withdrawn_primary_wallet: 4,
+            total_nft_withdrawn_user: 5,
+            total_nft_withdrawn_primary_wallet: 6,
+            total_money_deposited: 7,
+            total_ft_deposited: 8,
+            user_limit: SpendingLimit::default(),
+            primary_wallet_limit: SpendingLimit::default(),
+            frozen: true,
+            fee_bps: 250,
+            fee_receiver: Pubkey::new_unique(),
+            total_money_fee: 9,
+            total_ft_fee: 10,
+            withdrawal_log: [WithdrawalLogEntry::default(); ProxyWallet::WITHDRAWAL_LOG_LEN],
+            withdrawal_log_head: 3,
+        };
+
+        let mut data = Vec::new();
+        proxy_wallet.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), ProxyWallet::LEN);
+
+        let current = ProxyWallet::try_deserialize_versioned(&data).unwrap();
+        assert_eq!(current.total_ft_deposited, 8);
+        assert!(current.frozen);
+        assert_eq!(current.total_ft_fee, 10);
+        assert_eq!(current.withdrawal_log_head, 3);
+
+        let v5 = ProxyWallet::try_deserialize_versioned(&data[..ProxyWallet::LEN_V5]).unwrap();
+        assert_eq!(v5.total_ft_fee, 10);
+        assert_eq!(v5.withdrawal_log_head, 0);
+        assert!(v5.withdrawals().is_empty());
+
+        let v4 = ProxyWallet::try_deserialize_versioned(&data[..ProxyWallet::LEN_V4]).unwrap();
+        assert!(v4.frozen);
+        assert_eq!(v4.fee_bps, 0);
+        assert_eq!(v4.fee_receiver, Pubkey::default());
+
+        let v1 = ProxyWallet::try_deserialize_versioned(&data[..ProxyWallet::LEN_V1]).unwrap();
+        assert_eq!(v1.user, proxy_wallet.user);
+        assert_eq!(v1.total_nft_withdrawn_primary_wallet, 6);
+        assert_eq!(v1.total_money_deposited, 0);
+        assert!(!v1.frozen);
+    }
+
+    #[test]
+    fn withdrawal_fee() {
+        let mut proxy_wallet =
+            ProxyWallet::try_deserialize_versioned(&ProxyWallet::discriminator()).unwrap();
+        assert_eq!(proxy_wallet.withdrawal_fee(1_000), 0);
+
+        proxy_wallet.fee_bps = 250;
+        assert_eq!(proxy_wallet.withdrawal_fee(1_000), 25);
+        assert_eq!(proxy_wallet.withdrawal_fee(39), 0);
+        assert_eq!(proxy_wallet.withdrawal_fee(u64::MAX), u64::MAX / 40);
+
+        proxy_wallet.fee_bps = ProxyWallet::MAX_FEE_BPS;
+        assert_eq!(proxy_wallet.withdrawal_fee(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn withdrawal_log() {
+        let mut proxy_wallet =
+            ProxyWallet::try_deserialize_versioned(&ProxyWallet::discriminator()).unwrap();
+        let authority = Pubkey::new_unique();
+        let entry = |timestamp| WithdrawalLogEntry {
+            timestamp,
+            authority,
+            mint: Pubkey::default(),
+            amount: 1,
+        };
+
+        proxy_wallet.log_withdrawal(entry(1));
+        proxy_wallet.log_withdrawal(entry(2));
+        assert_eq!(proxy_wallet.withdrawals(), vec![entry(1), entry(2)]);
+
+        let count = ProxyWallet::WITHDRAWAL_LOG_LEN as i64 + 3;
+        (3..=count).for_each(|timestamp| proxy_wallet.log_withdrawal(entry(timestamp)));
+
+        let timestamps = proxy_wallet
+            .withdrawals()
+            .iter()
+            .map(|entry| entry.timestamp)
+            .collect::<Vec<_>>();
+        assert_eq!(timestamps, (4..=count).collect::<Vec<_>>());
+    }
 }
@@ -1,6 +1,18 @@
-use crate::{state::ProxyWallet, ErrorCode};
-use anchor_lang::prelude::*;
+use crate::{
+    event,
+    state::{NftRental, ProxyWallet, SessionKey, SpendingLimit, WithdrawalLogEntry},
+    ErrorCode,
+};
+use anchor_lang::{prelude::*, solana_program::program::invoke, system_program};
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use chill_nft::{
+    metaplex_adapter::{Metadata, TokenMetadataProgram},
+    utils::nft_creators,
+};
+use mpl_token_metadata::{
+    instruction::update_metadata_accounts_v2,
+    state::{DataV2, TokenStandard},
+};
 
 pub fn check_authority(authority: &Signer, proxy_wallet: &Account<ProxyWallet>) -> Result<()> {
     let authority_key = authority.key();
@@ -43,10 +55,254 @@ pub fn check_authority(authority: &Signer, proxy_wallet: &Account<ProxyWallet>)
     Ok(())
 }
 
+/// Besides the user and the primary wallet, a session key of the user is
+/// allowed if its account is passed as the first remaining account. The
+/// allowance of the session key is reduced by the amount
+pub fn check_session_authority(
+    authority: &Signer,
+    proxy_wallet: &Account<ProxyWallet>,
+    remaining_accounts: &[AccountInfo],
+    permission: u8,
+    amount: u64,
+) -> Result<()> {
+    let authority_key = authority.key();
+    if authority_key == proxy_wallet.user || authority_key == proxy_wallet.primary_wallet {
+        return check_authority(authority, proxy_wallet);
+    }
+
+    let session_info = remaining_accounts
+        .first()
+        .ok_or(ErrorCode::WrongAuthority)?;
+    require!(session_info.is_writable, ErrorCode::WrongAuthority);
+
+    let mut session = Account::<SessionKey>::try_from(session_info)?;
+    require_keys_eq!(
+        session.proxy_wallet,
+        proxy_wallet.key(),
+        ErrorCode::WrongAuthority
+    );
+    require_keys_eq!(session.signer, authority_key, ErrorCode::WrongAuthority);
+    require_gte!(
+        session.expires_at_slot,
+        Clock::get()?.slot,
+        ErrorCode::SessionKeyExpired
+    );
+    require!(
+        session.permissions & permission != 0,
+        ErrorCode::SessionKeyNotPermitted
+    );
+
+    let allowance = match permission {
+        SessionKey::WITHDRAW_LAMPORTS => &mut session.lamports_allowance,
+        SessionKey::WITHDRAW_FT => &mut session.ft_allowance,
+        _ => return Ok(()),
+    };
+
+    *allowance = allowance
+        .checked_sub(amount)
+        .ok_or(ErrorCode::SessionKeyAllowanceExceeded)?;
+
+    session.exit(&crate::ID)
+}
+
+/// Withdrawals of session keys are accounted as withdrawals of the user
+pub fn acting_authority(authority: &Signer, proxy_wallet: &ProxyWallet) -> Pubkey {
+    if authority.key() == proxy_wallet.primary_wallet {
+        authority.key()
+    } else {
+        proxy_wallet.user
+    }
+}
+
+/// Fails unless the program is built with the "devnet-tools" feature
+pub fn assert_devnet_tools() -> Result<()> {
+    require!(
+        cfg!(feature = "devnet-tools"),
+        ErrorCode::DevnetToolsDisabled
+    );
+
+    Ok(())
+}
+
+pub fn check_creator(
+    payer: &Signer,
+    user: &SystemAccount,
+    primary_wallet: &SystemAccount,
+) -> Result<()> {
+    // Anyone is allowed to pay for a wallet by building the program with
+    // the "permissionless-create-wallet" feature
+    if cfg!(feature = "permissionless-create-wallet") {
+        return Ok(());
+    }
+
+    let payer_key = payer.key();
+    require!(
+        payer_key == user.key() || payer_key == primary_wallet.key(),
+        ErrorCode::WrongCreator
+    );
+
+    Ok(())
+}
+
+pub fn transfer_lamports(
+    proxy_wallet_info: &AccountInfo,
+    receiver_info: &AccountInfo,
+    amount: u64,
+) -> Result<()> {
+    let rent = Rent::get()?;
+    let minimum_balance = rent.minimum_balance(ProxyWallet::LEN);
+
+    let proxy_wallet_balance = proxy_wallet_info
+        .lamports()
+        .checked_sub(minimum_balance)
+        .unwrap();
+
+    require_gte!(proxy_wallet_balance, amount, ErrorCode::InsufficientFunds);
+
+    let new_receiver_balance = receiver_info.lamports().checked_add(amount).unwrap();
+    let new_wallet_balance = proxy_wallet_info.lamports().checked_sub(amount).unwrap();
+
+    **receiver_info.lamports.borrow_mut() = new_receiver_balance;
+    **proxy_wallet_info.lamports.borrow_mut() = new_wallet_balance;
+
+    Ok(())
+}
+
+/// A wallet which takes a withdrawal fee expects the account of its fee
+/// receiver as the last remaining account, after the session key if any
+pub fn fee_account<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> Result<&'a AccountInfo<'info>> {
+    let fee_account = remaining_accounts
+        .last()
+        .ok_or(ErrorCode::WrongFeeReceiver)?;
+    require!(fee_account.is_writable, ErrorCode::WrongFeeReceiver);
+
+    Ok(fee_account)
+}
+
+/// Sends the fee of the withdrawal to the fee receiver and returns the rest
+/// of the amount
+pub fn take_lamports_fee(
+    proxy_wallet: &mut Account<ProxyWallet>,
+    remaining_accounts: &[AccountInfo],
+    amount: u64,
+) -> Result<u64> {
+    let fee = proxy_wallet.withdrawal_fee(amount);
+    if fee == 0 {
+        return Ok(amount);
+    }
+
+    let fee_receiver = fee_account(remaining_accounts)?;
+    require_keys_eq!(
+        fee_receiver.key(),
+        proxy_wallet.fee_receiver,
+        ErrorCode::WrongFeeReceiver
+    );
+
+    transfer_lamports(&proxy_wallet.to_account_info(), fee_receiver, fee)?;
+
+    proxy_wallet.total_money_fee = proxy_wallet.total_money_fee.checked_add(fee).unwrap();
+
+    emit!(event::WithdrawalFee {
+        proxy_wallet: proxy_wallet.key(),
+        mint: None,
+        amount: fee,
+    });
+
+    Ok(amount - fee)
+}
+
+/// Same as `take_lamports_fee`, the fee is sent to a token account of the fee
+/// receiver
+pub fn take_ft_fee<'info>(
+    proxy_wallet: &mut Account<'info, ProxyWallet>,
+    proxy_wallet_token: &Account<'info, TokenAccount>,
+    remaining_accounts: &[AccountInfo<'info>],
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<u64> {
+    let fee = proxy_wallet.withdrawal_fee(amount);
+    if fee == 0 {
+        return Ok(amount);
+    }
+
+    let fee_token = Account::<TokenAccount>::try_from(fee_account(remaining_accounts)?)?;
+    require!(
+        fee_token.owner == proxy_wallet.fee_receiver && fee_token.mint == proxy_wallet_token.mint,
+        ErrorCode::WrongFeeReceiver
+    );
+
+    transfer_tokens(
+        proxy_wallet,
+        proxy_wallet_token,
+        &fee_token,
+        token_program,
+        fee,
+    )?;
+
+    proxy_wallet.total_ft_fee = proxy_wallet.total_ft_fee.checked_add(fee).unwrap();
+
+    emit!(event::WithdrawalFee {
+        proxy_wallet: proxy_wallet.key(),
+        mint: Some(proxy_wallet_token.mint),
+        amount: fee,
+    });
+
+    Ok(amount - fee)
+}
+
 pub fn is_nft(mint: &Account<Mint>) -> bool {
     mint.decimals == 0 && mint.supply == 1
 }
 
+/// The same conditions as of the first sale of an NFT transferred by the CLI
+pub fn is_primary_sale(metadata: &Metadata, authority: Pubkey, recipient: Pubkey) -> bool {
+    metadata.token_standard == Some(TokenStandard::NonFungible)
+        && authority != recipient
+        && metadata.update_authority == authority
+        && !metadata.primary_sale_happened
+}
+
+/// Marks the primary sale of the NFT and shares its royalties with the
+/// recipient, the primary wallet stays a verified creator
+pub fn set_primary_sale<'info>(
+    primary_wallet: &Signer<'info>,
+    metadata: &Account<'info, Metadata>,
+    token_metadata_program: &Program<'info, TokenMetadataProgram>,
+    recipient: Pubkey,
+) -> Result<()> {
+    let data = DataV2 {
+        name: metadata.data.name.clone(),
+        symbol: metadata.data.symbol.clone(),
+        uri: metadata.data.uri.clone(),
+        seller_fee_basis_points: metadata.data.seller_fee_basis_points,
+        creators: Some(nft_creators(primary_wallet.key(), Some(recipient))),
+        collection: metadata.collection.clone(),
+        uses: metadata.uses.clone(),
+    };
+
+    let ix = update_metadata_accounts_v2(
+        mpl_token_metadata::ID,
+        metadata.key(),
+        primary_wallet.key(),
+        None,
+        Some(data),
+        Some(true),
+        None,
+    );
+
+    invoke(
+        &ix,
+        &[
+            primary_wallet.to_account_info(),
+            metadata.to_account_info(),
+            token_metadata_program.to_account_info(),
+        ],
+    )
+    .map_err(Into::into)
+}
+
 pub fn transfer_tokens<'info>(
     proxy_wallet: &Account<'info, ProxyWallet>,
     proxy_wallet_token: &Account<'info, TokenAccount>,
@@ -78,3 +334,123 @@ pub fn transfer_tokens<'info>(
         amount,
     )
 }
+
+// Returns the lent NFT to the proxy wallet and closes the escrow of the rental
+pub fn release_rental_escrow<'info>(
+    rental: &Account<'info, NftRental>,
+    rental_escrow: &Account<'info, TokenAccount>,
+    proxy_wallet_token: &Account<'info, TokenAccount>,
+    rent_receiver: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    let seeds = &[NftRental::SEED, rental.nft_mint.as_ref(), &[rental.bump]];
+
+    token::transfer(
+        CpiContext::new(
+            token_program.to_account_info(),
+            token::Transfer {
+                from: rental_escrow.to_account_info(),
+                to: proxy_wallet_token.to_account_info(),
+                authority: rental.to_account_info(),
+            },
+        )
+        .with_signer(&[seeds]),
+        1,
+    )?;
+
+    token::close_account(
+        CpiContext::new(
+            token_program.to_account_info(),
+            token::CloseAccount {
+                account: rental_escrow.to_account_info(),
+                destination: rent_receiver.clone(),
+                authority: rental.to_account_info(),
+            },
+        )
+        .with_signer(&[seeds]),
+    )
+}
+
+/// Returns false if the proxy wallet already exists
+pub fn create_proxy_wallet<'info>(
+    payer: &Signer<'info>,
+    primary_wallet: &UncheckedAccount<'info>,
+    user: &AccountInfo<'info>,
+    proxy_wallet: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<bool> {
+    require_keys_eq!(*user.owner, system_program::ID, ErrorCode::WrongWalletsList);
+
+    let primary_wallet_key = primary_wallet.key();
+    let (proxy_wallet_key, bump) = Pubkey::find_program_address(
+        &[
+            ProxyWallet::SEED,
+            user.key.as_ref(),
+            primary_wallet_key.as_ref(),
+        ],
+        &crate::ID,
+    );
+
+    require_keys_eq!(
+        proxy_wallet.key(),
+        proxy_wallet_key,
+        ErrorCode::WrongWalletsList
+    );
+
+    if *proxy_wallet.owner == crate::ID {
+        return Ok(false);
+    }
+
+    let bump_seed = [bump];
+    let seeds = &[
+        ProxyWallet::SEED,
+        user.key.as_ref(),
+        primary_wallet_key.as_ref(),
+        &bump_seed,
+    ];
+    let signers = &[seeds.as_ref()];
+
+    let cpi_context = CpiContext::new_with_signer(
+        system_program.to_account_info(),
+        system_program::CreateAccount {
+            from: payer.to_account_info(),
+            to: proxy_wallet.clone(),
+        },
+        signers,
+    );
+
+    system_program::create_account(
+        cpi_context,
+        Rent::get()?.minimum_balance(ProxyWallet::LEN),
+        ProxyWallet::LEN as u64,
+        &crate::ID,
+    )?;
+
+    let wallet = ProxyWallet {
+        bump,
+        primary_wallet: primary_wallet_key,
+        user: user.key(),
+        total_money_withdrawn_user: 0,
+        total_money_withdrawn_primary_wallet: 0,
+        total_ft_withdrawn_user: 0,
+        total_ft_withdrawn_primary_wallet: 0,
+        total_nft_withdrawn_user: 0,
+        total_nft_withdrawn_primary_wallet: 0,
+        total_money_deposited: 0,
+        total_ft_deposited: 0,
+        user_limit: SpendingLimit::default(),
+        primary_wallet_limit: SpendingLimit::default(),
+        frozen: false,
+        fee_bps: 0,
+        fee_receiver: Pubkey::default(),
+        total_money_fee: 0,
+        total_ft_fee: 0,
+        withdrawal_log: [WithdrawalLogEntry::default(); ProxyWallet::WITHDRAWAL_LOG_LEN],
+        withdrawal_log_head: 0,
+    };
+
+    let mut data = proxy_wallet.try_borrow_mut_data()?;
+    wallet.try_serialize(&mut data.as_mut())?;
+
+    Ok(true)
+}
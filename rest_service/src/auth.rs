@@ -0,0 +1,227 @@
+use axum::{
+    async_trait,
+    extract::{FromRequest, RequestParts},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
+use std::{
+    collections::HashMap,
+    env, fs,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use thiserror::Error;
+
+pub const RESTAPI_API_KEYS_ENV: &str = "RESTAPI_API_KEYS";
+pub const RESTAPI_API_KEYS_FILE_ENV: &str = "RESTAPI_API_KEYS_FILE";
+pub const RESTAPI_RATE_LIMIT_ENV: &str = "RESTAPI_RATE_LIMIT";
+
+/// Requests per minute allowed for a key without its own limit
+pub const RESTAPI_RATE_LIMIT_DEFAULT: u32 = 60;
+
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Error, Debug)]
+pub enum AuthError {
+    #[error("Cannot read API keys: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Wrong rate limit '{0}'")]
+    WrongRateLimit(String),
+
+    #[error("Wrong API key on line {0}")]
+    WrongKeyLine(usize),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Rejection {
+    Unauthorized,
+    RateLimited,
+}
+
+struct Window {
+    start: Instant,
+    requests: u32,
+}
+
+/// API keys with their rate limits. Requests of every key are counted in
+/// fixed windows of a minute
+#[derive(Default)]
+pub struct Auth {
+    limits: HashMap<String, u32>,
+    windows: Mutex<HashMap<String, Window>>,
+}
+
+fn parse_limit(value: &str) -> Result<u32, AuthError> {
+    value
+        .parse()
+        .ok()
+        .filter(|limit| *limit > 0)
+        .ok_or_else(|| AuthError::WrongRateLimit(value.to_owned()))
+}
+
+impl Auth {
+    /// Loads comma separated keys from `RESTAPI_API_KEYS` and lines of
+    /// `<KEY> [REQUESTS_PER_MINUTE]` from the `RESTAPI_API_KEYS_FILE`. Keys
+    /// without a limit get the one of `RESTAPI_RATE_LIMIT`
+    pub fn load() -> Result<Self, AuthError> {
+        let default_limit = match env::var(RESTAPI_RATE_LIMIT_ENV) {
+            Ok(value) => parse_limit(&value)?,
+            Err(_) => RESTAPI_RATE_LIMIT_DEFAULT,
+        };
+
+        let mut auth = match env::var(RESTAPI_API_KEYS_FILE_ENV) {
+            Ok(path) => Self::parse(&fs::read_to_string(path)?, default_limit)?,
+            Err(_) => Self::default(),
+        };
+
+        if let Ok(keys) = env::var(RESTAPI_API_KEYS_ENV) {
+            for key in keys.split(',').map(str::trim).filter(|key| !key.is_empty()) {
+                auth.limits.insert(key.to_owned(), default_limit);
+            }
+        }
+
+        Ok(auth)
+    }
+
+    pub fn parse(content: &str, default_limit: u32) -> Result<Self, AuthError> {
+        let mut auth = Self::default();
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut words = line.split_whitespace();
+            let key = words.next().unwrap();
+            let limit = match words.next() {
+                Some(limit) => parse_limit(limit)?,
+                None => default_limit,
+            };
+
+            if words.next().is_some() {
+                return Err(AuthError::WrongKeyLine(index + 1));
+            }
+
+            auth.limits.insert(key.to_owned(), limit);
+        }
+
+        Ok(auth)
+    }
+
+    /// Requests are not authenticated if no key is configured
+    pub fn is_enabled(&self) -> bool {
+        !self.limits.is_empty()
+    }
+
+    pub fn check(&self, key: Option<&str>, now: Instant) -> Result<(), Rejection> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+
+        let key = key.ok_or(Rejection::Unauthorized)?;
+        let limit = *self.limits.get(key).ok_or(Rejection::Unauthorized)?;
+
+        let mut windows = self.windows.lock().unwrap();
+        let window = windows.entry(key.to_owned()).or_insert(Window {
+            start: now,
+            requests: 0,
+        });
+
+        if now.duration_since(window.start) >= RATE_LIMIT_WINDOW {
+            window.start = now;
+            window.requests = 0;
+        }
+
+        if window.requests >= limit {
+            return Err(Rejection::RateLimited);
+        }
+
+        window.requests += 1;
+        Ok(())
+    }
+}
+
+impl IntoResponse for Rejection {
+    fn into_response(self) -> Response {
+        let (status, error) = match self {
+            Rejection::Unauthorized => (StatusCode::UNAUTHORIZED, "API key is missing or wrong"),
+            Rejection::RateLimited => (StatusCode::TOO_MANY_REQUESTS, "rate limit is exceeded"),
+        };
+
+        (status, Json(json!({ "error": error }))).into_response()
+    }
+}
+
+/// Checks the `x-api-key` header of the request, used by the extractor
+/// middleware in front of the handlers
+pub struct ApiKey;
+
+#[async_trait]
+impl<B: Send> FromRequest<B> for ApiKey {
+    type Rejection = Rejection;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let auth = req
+            .extensions()
+            .and_then(|extensions| extensions.get::<Arc<Auth>>())
+            .cloned()
+            .ok_or(Rejection::Unauthorized)?;
+
+        let key = req
+            .headers()
+            .and_then(|headers| headers.get(API_KEY_HEADER))
+            .and_then(|value| value.to_str().ok());
+
+        auth.check(key, Instant::now()).map(|_| ApiKey)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_keys() {
+        let auth = Auth::parse("# comment\nfirst\n\nsecond 5\n", 10).unwrap();
+        assert_eq!(auth.limits["first"], 10);
+        assert_eq!(auth.limits["second"], 5);
+
+        assert!(matches!(
+            Auth::parse("first 0", 10),
+            Err(AuthError::WrongRateLimit(_))
+        ));
+        assert!(matches!(
+            Auth::parse("first 5 6", 10),
+            Err(AuthError::WrongKeyLine(1))
+        ));
+    }
+
+    #[test]
+    fn check_keys_and_limits() {
+        let auth = Auth::parse("first 2\nsecond 1", 10).unwrap();
+        let now = Instant::now();
+
+        assert_eq!(auth.check(None, now), Err(Rejection::Unauthorized));
+        assert_eq!(auth.check(Some("third"), now), Err(Rejection::Unauthorized));
+
+        assert_eq!(auth.check(Some("first"), now), Ok(()));
+        assert_eq!(auth.check(Some("first"), now), Ok(()));
+        assert_eq!(auth.check(Some("first"), now), Err(Rejection::RateLimited));
+        assert_eq!(auth.check(Some("second"), now), Ok(()));
+
+        let next_window = now + RATE_LIMIT_WINDOW;
+        assert_eq!(auth.check(Some("first"), next_window), Ok(()));
+    }
+
+    #[test]
+    fn disabled_without_keys() {
+        let auth = Auth::default();
+        assert!(!auth.is_enabled());
+        assert_eq!(auth.check(None, Instant::now()), Ok(()));
+    }
+}
@@ -0,0 +1,160 @@
+//! Push channel of the program events. Logs of the programs are received by
+//! the pubsub subscriptions and the decoded events are broadcast to every
+//! connected websocket
+
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Extension,
+    },
+    response::IntoResponse,
+};
+use chill_events::{event::Event, logs};
+use serde_json::json;
+use solana_client::{
+    pubsub_client::{PubsubClient, PubsubClientError, PubsubLogsClientSubscription},
+    rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter},
+};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+use std::thread;
+use tokio::sync::broadcast;
+
+pub const RESTAPI_WS_URL_ENV: &str = "RESTAPI_WS_URL";
+
+const PROGRAMS: [Pubkey; 3] = [chill_staking::ID, chill_nft::ID, chill_wallet::ID];
+
+// Slow clients lose the oldest events instead of holding the others back
+const CHANNEL_CAPACITY: usize = 1024;
+
+pub type Events = broadcast::Sender<String>;
+
+pub fn channel() -> Events {
+    broadcast::channel(CHANNEL_CAPACITY).0
+}
+
+/// Events which change balances shown by the game, the rest aren't pushed
+pub fn is_pushed(event: &Event) -> bool {
+    matches!(
+        event,
+        Event::Stake(_)
+            | Event::Claim(_)
+            | Event::Boost(_)
+            | Event::PaidBoost(_)
+            | Event::MintNft(_)
+            | Event::WithdrawLamports(_)
+            | Event::WithdrawFt(_)
+            | Event::WithdrawNft(_)
+            | Event::WithdrawalFee(_)
+    )
+}
+
+pub fn message(program: Pubkey, slot: u64, signature: &str, event: &Event) -> String {
+    json!({
+        "program": program.to_string(),
+        "slot": slot,
+        "signature": signature,
+        "event": event.to_json(),
+    })
+    .to_string()
+}
+
+/// Subscribes to logs of every program. Events are broadcast as long as the
+/// returned subscriptions are alive
+pub fn subscribe(
+    ws_url: &str,
+    events: &Events,
+) -> Result<Vec<PubsubLogsClientSubscription>, Box<PubsubClientError>> {
+    let mut subscriptions = Vec::new();
+    for program in PROGRAMS {
+        let (subscription, responses) = PubsubClient::logs_subscribe(
+            ws_url,
+            RpcTransactionLogsFilter::Mentions(vec![program.to_string()]),
+            RpcTransactionLogsConfig {
+                commitment: Some(CommitmentConfig::confirmed()),
+            },
+        )
+        .map_err(Box::new)?;
+
+        // A transaction might mention several programs, so only events of the
+        // subscribed one are taken to push them once
+        let events = events.clone();
+        thread::spawn(move || {
+            for response in responses {
+                if response.value.err.is_some() {
+                    continue;
+                }
+
+                for logged in logs::events(&response.value.logs, &[program]) {
+                    if is_pushed(&logged.event) {
+                        // Sending fails only while nobody is connected
+                        let _ = events.send(message(
+                            program,
+                            response.context.slot,
+                            &response.value.signature,
+                            &logged.event,
+                        ));
+                    }
+                }
+            }
+        });
+
+        subscriptions.push(subscription);
+    }
+
+    Ok(subscriptions)
+}
+
+pub async fn ws_events(
+    ws: WebSocketUpgrade,
+    Extension(events): Extension<Events>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| push(socket, events.subscribe()))
+}
+
+async fn push(mut socket: WebSocket, mut receiver: broadcast::Receiver<String>) {
+    loop {
+        tokio::select! {
+            event = receiver.recv() => match event {
+                Ok(event) => {
+                    if socket.send(Message::Text(event)).await.is_err() {
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            // Messages of the client are ignored, the socket is only watched
+            // for closing
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => continue,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chill_staking::event::Boost;
+    use serde_json::Value;
+
+    #[test]
+    fn pushed_events() {
+        let boost = Event::Boost(Boost {
+            user: Pubkey::new_unique(),
+        });
+        assert!(is_pushed(&boost));
+
+        let delist = Event::DelistNft(chill_nft::event::DelistNft {
+            mint: Pubkey::new_unique(),
+        });
+        assert!(!is_pushed(&delist));
+
+        let message = message(chill_staking::ID, 7, "signature", &boost);
+        let message: Value = serde_json::from_str(&message).unwrap();
+        assert_eq!(message["program"], chill_staking::ID.to_string());
+        assert_eq!(message["slot"], 7);
+        assert_eq!(message["event"]["name"], "Boost");
+    }
+}
@@ -0,0 +1,226 @@
+//! Prometheus metrics of the service. The layer counts requests of every
+//! route, the health checks measure the latency of the RPC node
+
+use axum::{
+    extract::{Extension, MatchedPath},
+    http::{header, Request, Response, StatusCode},
+    response::{Headers, IntoResponse},
+};
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tower::{Layer, Service};
+
+#[derive(Default)]
+struct EndpointMetrics {
+    requests: BTreeMap<u16, u64>,
+    errors: u64,
+    duration_seconds: f64,
+}
+
+#[derive(Default)]
+struct RpcMetrics {
+    checks: u64,
+    errors: u64,
+    latency_seconds: f64,
+}
+
+#[derive(Default)]
+pub struct Metrics {
+    endpoints: Mutex<BTreeMap<String, EndpointMetrics>>,
+    rpc: Mutex<RpcMetrics>,
+}
+
+impl Metrics {
+    /// Responses with 4xx and 5xx statuses are counted as errors
+    pub fn record_request(&self, endpoint: &str, status: StatusCode, duration: Duration) {
+        let mut endpoints = self.endpoints.lock().unwrap();
+        let metrics = endpoints.entry(endpoint.to_owned()).or_default();
+        *metrics.requests.entry(status.as_u16()).or_default() += 1;
+        if status.is_client_error() || status.is_server_error() {
+            metrics.errors += 1;
+        }
+        metrics.duration_seconds += duration.as_secs_f64();
+    }
+
+    pub fn record_rpc_check(&self, latency: Duration, is_error: bool) {
+        let mut rpc = self.rpc.lock().unwrap();
+        rpc.checks += 1;
+        if is_error {
+            rpc.errors += 1;
+        }
+        rpc.latency_seconds = latency.as_secs_f64();
+    }
+
+    /// Renders the metrics in the Prometheus text format
+    pub fn render(&self) -> String {
+        let endpoints = self.endpoints.lock().unwrap();
+        let rpc = self.rpc.lock().unwrap();
+        let mut text = String::new();
+
+        text.push_str("# HELP chill_rest_requests_total Requests served by the endpoint\n");
+        text.push_str("# TYPE chill_rest_requests_total counter\n");
+        for (endpoint, metrics) in endpoints.iter() {
+            for (status, count) in &metrics.requests {
+                writeln!(
+                    text,
+                    "chill_rest_requests_total{{endpoint=\"{}\",status=\"{}\"}} {}",
+                    endpoint, status, count
+                )
+                .unwrap();
+            }
+        }
+
+        text.push_str("# HELP chill_rest_errors_total Requests failed with 4xx or 5xx statuses\n");
+        text.push_str("# TYPE chill_rest_errors_total counter\n");
+        for (endpoint, metrics) in endpoints.iter() {
+            writeln!(
+                text,
+                "chill_rest_errors_total{{endpoint=\"{}\"}} {}",
+                endpoint, metrics.errors
+            )
+            .unwrap();
+        }
+
+        text.push_str("# HELP chill_rest_request_duration_seconds Time of serving requests\n");
+        text.push_str("# TYPE chill_rest_request_duration_seconds summary\n");
+        for (endpoint, metrics) in endpoints.iter() {
+            let count = metrics.requests.values().sum::<u64>();
+            writeln!(
+                text,
+                "chill_rest_request_duration_seconds_sum{{endpoint=\"{}\"}} {}",
+                endpoint, metrics.duration_seconds
+            )
+            .unwrap();
+            writeln!(
+                text,
+                "chill_rest_request_duration_seconds_count{{endpoint=\"{}\"}} {}",
+                endpoint, count
+            )
+            .unwrap();
+        }
+
+        text.push_str("# HELP chill_rest_rpc_latency_seconds Latency of the last health check\n");
+        text.push_str("# TYPE chill_rest_rpc_latency_seconds gauge\n");
+        writeln!(
+            text,
+            "chill_rest_rpc_latency_seconds {}",
+            rpc.latency_seconds
+        )
+        .unwrap();
+
+        text.push_str("# HELP chill_rest_rpc_checks_total Health checks of the RPC node\n");
+        text.push_str("# TYPE chill_rest_rpc_checks_total counter\n");
+        writeln!(text, "chill_rest_rpc_checks_total {}", rpc.checks).unwrap();
+
+        text.push_str("# HELP chill_rest_rpc_errors_total Failed health checks of the RPC node\n");
+        text.push_str("# TYPE chill_rest_rpc_errors_total counter\n");
+        writeln!(text, "chill_rest_rpc_errors_total {}", rpc.errors).unwrap();
+
+        text
+    }
+}
+
+/// Records every request of the routes it's added to by `route_layer`, so
+/// requests are labeled by the route instead of the raw path
+#[derive(Clone)]
+pub struct MetricsLayer {
+    metrics: Arc<Metrics>,
+}
+
+impl MetricsLayer {
+    pub fn new(metrics: Arc<Metrics>) -> Self {
+        Self { metrics }
+    }
+}
+
+impl<S> Layer<S> for MetricsLayer {
+    type Service = MetricsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MetricsService {
+            inner,
+            metrics: self.metrics.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct MetricsService<S> {
+    inner: S,
+    metrics: Arc<Metrics>,
+}
+
+impl<S, B, ResBody> Service<Request<B>> for MetricsService<S>
+where
+    S: Service<Request<B>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let endpoint = request
+            .extensions()
+            .get::<MatchedPath>()
+            .map(|path| path.as_str().to_owned())
+            .unwrap_or_else(|| "unknown".to_owned());
+        let metrics = self.metrics.clone();
+        let start = Instant::now();
+        let response = self.inner.call(request);
+
+        Box::pin(async move {
+            let response = response.await?;
+            metrics.record_request(&endpoint, response.status(), start.elapsed());
+            Ok(response)
+        })
+    }
+}
+
+pub async fn metrics(Extension(metrics): Extension<Arc<Metrics>>) -> impl IntoResponse {
+    (
+        Headers([(header::CONTENT_TYPE, "text/plain; version=0.0.4")]),
+        metrics.render(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_metrics() {
+        let metrics = Metrics::default();
+        let second = Duration::from_secs(1);
+        metrics.record_request("/balance", StatusCode::OK, second);
+        metrics.record_request("/balance", StatusCode::OK, second);
+        metrics.record_request("/balance", StatusCode::BAD_REQUEST, second);
+        metrics.record_rpc_check(Duration::from_millis(250), false);
+
+        let text = metrics.render();
+        assert!(
+            text.contains("chill_rest_requests_total{endpoint=\"/balance\",status=\"200\"} 2\n")
+        );
+        assert!(
+            text.contains("chill_rest_requests_total{endpoint=\"/balance\",status=\"400\"} 1\n")
+        );
+        assert!(text.contains("chill_rest_errors_total{endpoint=\"/balance\"} 1\n"));
+        assert!(text.contains("chill_rest_request_duration_seconds_sum{endpoint=\"/balance\"} 3\n"));
+        assert!(
+            text.contains("chill_rest_request_duration_seconds_count{endpoint=\"/balance\"} 3\n")
+        );
+        assert!(text.contains("chill_rest_rpc_latency_seconds 0.25\n"));
+        assert!(text.contains("chill_rest_rpc_errors_total 0\n"));
+    }
+}
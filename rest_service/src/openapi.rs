@@ -0,0 +1,138 @@
+//! OpenAPI schema of the service, the game team generates typed clients by it
+
+use crate::{
+    pagination::{ConfigsPage, PageParams},
+    staking::StakingStats,
+    BalanceReq, BalanceRes, ConfigRes, ConfigsReq, CreateWalletReq, CreateWalletRes, CreatorRes,
+    ErrorRes, FeesRes, HealthRes, InfoReq, InfoRes, MintNftReq, MintNftRes, NftRes, RecipientRes,
+    StakingActionReq, StakingHealthReq, StakingHealthRes, TransactionRes,
+};
+use axum::{response::Html, Json};
+use utoipa::{
+    openapi::{
+        security::{ApiKey, ApiKeyValue, SecurityScheme},
+        OpenApi as OpenApiDoc,
+    },
+    Modify, OpenApi,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        crate::balance,
+        crate::info,
+        crate::create_wallet,
+        crate::staking_health,
+        crate::staking_stats,
+        crate::staking_stake,
+        crate::staking_claim,
+        crate::staking_boost,
+        crate::staking_cancel,
+        crate::nft,
+        crate::mint_nft,
+        crate::configs,
+        crate::health,
+    ),
+    components(schemas(
+        BalanceReq,
+        BalanceRes,
+        InfoReq,
+        InfoRes,
+        FeesRes,
+        RecipientRes,
+        CreateWalletReq,
+        CreateWalletRes,
+        StakingHealthReq,
+        StakingHealthRes,
+        StakingStats,
+        StakingActionReq,
+        TransactionRes,
+        ConfigsReq,
+        ConfigRes,
+        ConfigsPage,
+        PageParams,
+        MintNftReq,
+        MintNftRes,
+        NftRes,
+        CreatorRes,
+        HealthRes,
+        ErrorRes,
+    )),
+    modifiers(&ApiKeyAddon),
+    security(("api_key" = []))
+)]
+pub struct ApiDoc;
+
+struct ApiKeyAddon;
+
+impl Modify for ApiKeyAddon {
+    fn modify(&self, openapi: &mut OpenApiDoc) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "api_key",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new(
+                crate::auth::API_KEY_HEADER,
+            ))),
+        );
+    }
+}
+
+pub async fn openapi_json() -> Json<OpenApiDoc> {
+    Json(ApiDoc::openapi())
+}
+
+// Swagger UI is loaded from the CDN, so the service serves only the page
+const DOCS_PAGE: &str = r##"<!DOCTYPE html>
+<html>
+<head>
+  <title>Chill REST service</title>
+  <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css" />
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js"></script>
+  <script>
+    SwaggerUIBundle({ url: "/openapi.json", dom_id: "#swagger-ui" });
+  </script>
+</body>
+</html>
+"##;
+
+pub async fn docs() -> Html<&'static str> {
+    Html(DOCS_PAGE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_route_is_documented() {
+        let doc = ApiDoc::openapi();
+        for path in [
+            "/balance",
+            "/info",
+            "/create-wallet",
+            "/staking/health",
+            "/staking/{staking_info}",
+            "/staking/stake",
+            "/staking/claim",
+            "/staking/boost",
+            "/staking/cancel",
+            "/nft/{mint}",
+            "/nft/mint",
+            "/configs",
+            "/health",
+        ] {
+            assert!(doc.paths.paths.contains_key(path), "{} is missing", path);
+        }
+
+        // Every referenced schema is listed in the components
+        let json = serde_json::to_string(&doc).unwrap();
+        let schemas = doc.components.unwrap().schemas;
+        for reference in json.split("#/components/schemas/").skip(1) {
+            let name = &reference[..reference.find('"').unwrap()];
+            assert!(schemas.contains_key(name), "{} is not listed", name);
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use crate::ConfigRes;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use utoipa::ToSchema;
+
+pub const DEFAULT_PAGE_LIMIT: usize = 50;
+pub const MAX_PAGE_LIMIT: usize = 100;
+
+/// Paging parameters accepted by every list endpoint
+#[derive(Deserialize, Default, ToSchema)]
+pub struct PageParams {
+    #[serde(default)]
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+/// Response envelope of every list endpoint. The `next_cursor` is absent on
+/// the last page
+#[derive(Serialize, ToSchema)]
+// Schemas of the pages served by the list endpoints
+#[aliases(ConfigsPage = Page<ConfigRes>)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PageError {
+    InvalidLimit(usize),
+    InvalidCursor(String),
+}
+
+impl fmt::Display for PageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PageError::InvalidLimit(limit) => {
+                write!(
+                    f,
+                    "limit must be from 1 to {}, got {}",
+                    MAX_PAGE_LIMIT, limit
+                )
+            }
+            PageError::InvalidCursor(cursor) => write!(f, "invalid cursor '{}'", cursor),
+        }
+    }
+}
+
+/// Key which orders items of a list. A cursor is the encoded key of the last
+/// item of a page, so the order stays stable while accounts are added
+pub trait PageKey: Ord + Sized {
+    fn encode(&self) -> String;
+    fn decode(cursor: &str) -> Option<Self>;
+}
+
+/// Orders by a pubkey in base58
+impl PageKey for String {
+    fn encode(&self) -> String {
+        self.clone()
+    }
+
+    fn decode(cursor: &str) -> Option<Self> {
+        if cursor.is_empty() {
+            return None;
+        }
+        Some(cursor.to_owned())
+    }
+}
+
+/// Orders by a slot, then by a pubkey in base58
+impl PageKey for (u64, String) {
+    fn encode(&self) -> String {
+        format!("{}.{}", self.0, self.1)
+    }
+
+    fn decode(cursor: &str) -> Option<Self> {
+        let (slot, pubkey) = cursor.split_once('.')?;
+        let slot = slot.parse().ok()?;
+        let pubkey = String::decode(pubkey)?;
+        Some((slot, pubkey))
+    }
+}
+
+/// Sorts items by their keys and returns the page following the cursor
+pub fn paginate<T, K: PageKey>(
+    items: Vec<T>,
+    key: impl Fn(&T) -> K,
+    params: &PageParams,
+) -> Result<Page<T>, PageError> {
+    let limit = params.limit.unwrap_or(DEFAULT_PAGE_LIMIT);
+    if limit == 0 || limit > MAX_PAGE_LIMIT {
+        return Err(PageError::InvalidLimit(limit));
+    }
+
+    let after = match &params.cursor {
+        Some(cursor) => {
+            Some(K::decode(cursor).ok_or_else(|| PageError::InvalidCursor(cursor.clone()))?)
+        }
+        None => None,
+    };
+
+    let mut items = items
+        .into_iter()
+        .map(|item| (key(&item), item))
+        .filter(|(item_key, _)| after.as_ref().is_none_or(|after| item_key > after))
+        .collect::<Vec<_>>();
+
+    items.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let next_cursor = if items.len() > limit {
+        items.truncate(limit);
+        items.last().map(|(item_key, _)| item_key.encode())
+    } else {
+        None
+    };
+
+    Ok(Page {
+        items: items.into_iter().map(|(_, item)| item).collect(),
+        next_cursor,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(limit: usize, cursor: Option<String>) -> PageParams {
+        PageParams {
+            limit: Some(limit),
+            cursor,
+        }
+    }
+
+    #[test]
+    fn pages_follow_each_other() {
+        let items = vec!["d", "a", "c", "b", "e"];
+        let key = |item: &&str| item.to_string();
+
+        let page = paginate(items.clone(), key, &params(2, None)).unwrap();
+        assert_eq!(page.items, vec!["a", "b"]);
+        assert_eq!(page.next_cursor, Some("b".to_owned()));
+
+        let page = paginate(items.clone(), key, &params(2, page.next_cursor)).unwrap();
+        assert_eq!(page.items, vec!["c", "d"]);
+
+        let page = paginate(items, key, &params(2, page.next_cursor)).unwrap();
+        assert_eq!(page.items, vec!["e"]);
+        assert_eq!(page.next_cursor, None);
+    }
+
+    #[test]
+    fn slot_cursor() {
+        let items = vec![(7, "a"), (3, "b"), (3, "a")];
+        let key = |item: &(u64, &str)| (item.0, item.1.to_string());
+
+        let page = paginate(items.clone(), key, &params(1, None)).unwrap();
+        assert_eq!(page.items, vec![(3, "a")]);
+        assert_eq!(page.next_cursor, Some("3.a".to_owned()));
+
+        let page = paginate(items, key, &params(5, page.next_cursor)).unwrap();
+        assert_eq!(page.items, vec![(3, "b"), (7, "a")]);
+    }
+
+    #[test]
+    fn invalid_params() {
+        let key = |item: &(u64, &str)| (item.0, item.1.to_string());
+        let cursor = Some("abc".to_owned());
+
+        assert_eq!(
+            paginate(vec![], key, &params(0, None)).err(),
+            Some(PageError::InvalidLimit(0))
+        );
+        assert_eq!(
+            paginate(vec![], key, &params(MAX_PAGE_LIMIT + 1, None)).err(),
+            Some(PageError::InvalidLimit(MAX_PAGE_LIMIT + 1))
+        );
+        assert_eq!(
+            paginate(vec![], key, &params(1, cursor)).err(),
+            Some(PageError::InvalidCursor("abc".to_owned()))
+        );
+    }
+}
@@ -1,18 +1,48 @@
-use chill_cli::app::App;
-use chill_cli::cli::{RPC_URL, MINT, ACCOUNT, PAYER, PRIMARY_WALLET, PROGRAM_ID};
+mod auth;
+mod events;
+mod metrics;
+mod openapi;
+mod pagination;
+mod staking;
+mod vault;
+
+use chill_cli::api;
+use chill_cli::cli::{mint_file, parse_mint};
+use chill_cli::client::RpcBudget;
+use chill_cli::error::{AppError, CliError};
+use chill_cli::nonblocking::AsyncClient;
+use chill_nft::utils::NftArgs;
 
 use axum::{
     routing::{get, post},
     http::StatusCode,
-    response::IntoResponse,
+    response::{IntoResponse, Response},
+    extract::{extractor_middleware, Extension, Path, Query},
     Json, Router
 };
 use serde::{Deserialize, Serialize};
-use serde_json::json;
-use std::net::SocketAddr;
+use solana_clap_utils::input_validators::normalize_to_url_if_moniker;
+use anchor_client::Cluster;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair},
+    signer::Signer,
+};
+use spl_associated_token_account::get_associated_token_address;
+use auth::{ApiKey, Auth};
+use events::{ws_events, RESTAPI_WS_URL_ENV};
+use metrics::{Metrics, MetricsLayer};
+use openapi::{docs, openapi_json};
+use pagination::{paginate, PageParams};
+use staking::StakingStats;
+use vault::{Vault, DEFAULT_ALIAS, RESTAPI_VAULT_PASSPHRASE_ENV};
+use utoipa::{IntoParams, ToSchema};
+use std::{fs, net::SocketAddr, process::exit, rc::Rc, str::FromStr, sync::Arc, time::Instant};
 
 pub const RESTAPI_PORT_ENV: &str = "RESTAPI_PORT";
 pub const RESTAPI_PORT_DEFAULT: u16 = 3000;
+pub const RESTAPI_PRIMARY_WALLET_ENV: &str = "RESTAPI_PRIMARY_WALLET";
 
 fn get_port() -> u16 {
     match std::env::var(RESTAPI_PORT_ENV) {
@@ -36,13 +66,183 @@ fn get_port() -> u16 {
     RESTAPI_PORT_DEFAULT
 }
 
+// Usage: service-blockchain-server seal-vault <OUTPUT> <ALIAS>=<KEYPAIR_FILE>...
+fn seal_vault(arguments: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let (output, keys) = arguments.split_first().ok_or("output file is not specified")?;
+    let passphrase = std::env::var(RESTAPI_VAULT_PASSPHRASE_ENV)
+        .map_err(|_| format!("{RESTAPI_VAULT_PASSPHRASE_ENV} is not set"))?;
+
+    let mut vault = Vault::default();
+    for key in keys {
+        let (alias, path) = key.split_once('=').ok_or("keys must be <ALIAS>=<KEYPAIR_FILE>")?;
+        let keypair = solana_sdk::signature::read_keypair_file(path)?;
+        vault.insert(alias, &keypair);
+    }
+
+    fs::write(output, vault.seal(passphrase.as_bytes())?)?;
+    Ok(())
+}
+
+// A keypair field of a request is either an alias of a vault key or a path to
+// a keypair file. Empty fields are signed by the default vault key. Signers of
+// the client are not `Send`, so the keypair is moved to the blocking client
+fn keypair(vault: &Vault, value: &str) -> Result<Keypair, String> {
+    let alias = if value.is_empty() { DEFAULT_ALIAS } else { value };
+    if let Some(keypair) = vault.keypair(alias) {
+        return Ok(keypair);
+    }
+
+    read_keypair_file(value)
+        .map_err(|_| format!("key '{}' is neither in the vault nor a keypair file", alias))
+}
+
+fn parse_pubkey(name: &str, value: &str) -> Result<Pubkey, String> {
+    Pubkey::from_str(value).map_err(|e| format!("{} is not a valid pubkey: {}", name, e))
+}
+
+fn parse_program_id(value: &str, default: Pubkey) -> Result<Pubkey, String> {
+    if value.is_empty() {
+        Ok(default)
+    } else {
+        parse_pubkey("program_id", value)
+    }
+}
+
+// Requests name the cluster by its URL or moniker, devnet is used by default
+// like in the CLI
+fn cluster_moniker(url: &str) -> &str {
+    if url.is_empty() { "devnet" } else { url }
+}
+
+// Calls of the client run on the blocking threads of tokio, so concurrent
+// requests don't block each other
+fn client(url: &str) -> AsyncClient {
+    AsyncClient::init(&normalize_to_url_if_moniker(cluster_moniker(url)), RpcBudget::default())
+}
+
+// Requests without a mint use the one saved by the CLI for the cluster
+fn mint(mint_address: &str, url: &str) -> Result<Pubkey, String> {
+    if !mint_address.is_empty() {
+        return parse_pubkey("mint_address", mint_address);
+    }
+
+    let cluster = Cluster::from_str(cluster_moniker(url)).map_err(|e| e.to_string())?;
+    match parse_mint(mint_file(&cluster)) {
+        Ok(Some(mint)) => Ok(mint),
+        Ok(None) => Err("mint_address is not specified and no mint is saved for the cluster".to_owned()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn bad_request(error: String) -> Response {
+    (StatusCode::BAD_REQUEST, Json(ErrorRes { error })).into_response()
+}
+
+// The server signs minted NFTs by the primary wallet only if the wallet is
+// configured, otherwise the transaction is left for the primary wallet to sign
+fn primary_wallet(vault: &Vault) -> Result<Option<Keypair>, String> {
+    match std::env::var(RESTAPI_PRIMARY_WALLET_ENV) {
+        Ok(value) => keypair(vault, &value).map(Some),
+        Err(_) => Ok(None),
+    }
+}
+
+fn error_response(error: AppError) -> Response {
+    match error {
+        AppError::InternalError(e)
+            if matches!(
+                e.downcast_ref(),
+                Some(CliError::StakingInfoNotFound(_))
+                    | Some(CliError::MetadataNotFound(_))
+                    | Some(CliError::ChillMetadataNotFound)
+            ) =>
+            (StatusCode::NOT_FOUND, Json(ErrorRes { error: e.to_string() })).into_response(),
+        e =>
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorRes { error: e.to_string() })).into_response(),
+    }
+}
+
 #[tokio::main]
 async fn main() {
+    let arguments = std::env::args().skip(1).collect::<Vec<_>>();
+    if arguments.first().map(String::as_str) == Some("seal-vault") {
+        if let Err(e) = seal_vault(&arguments[1..]) {
+            println!("{}", e);
+            exit(1);
+        }
+        return;
+    }
+
+    let vault = match Vault::load() {
+        Ok(vault) => vault,
+        Err(e) => {
+            println!("{}", e);
+            exit(1);
+        }
+    };
+
+    let auth = match Auth::load() {
+        Ok(auth) => auth,
+        Err(e) => {
+            println!("{}", e);
+            exit(1);
+        }
+    };
+
+    if let Err(e) = primary_wallet(&vault) {
+        println!("{RESTAPI_PRIMARY_WALLET_ENV}: {}", e);
+        exit(1);
+    }
+
+    if !auth.is_enabled() {
+        println!("API keys are not set, requests are not authenticated");
+    }
+
+    let events = events::channel();
+    let _subscriptions = match std::env::var(RESTAPI_WS_URL_ENV) {
+        Ok(ws_url) => match events::subscribe(&ws_url, &events) {
+            Ok(subscriptions) => subscriptions,
+            Err(e) => {
+                println!("{RESTAPI_WS_URL_ENV}: {}", e);
+                exit(1);
+            }
+        },
+        Err(_) => {
+            println!("{RESTAPI_WS_URL_ENV} wasn't set, no events are pushed");
+            Vec::new()
+        }
+    };
+
+    let metrics = Arc::new(Metrics::default());
+
+    // Every route except the root one, the events, the docs and the monitoring
+    // ones requires the `x-api-key` header. Browsers can't set headers of
+    // websockets, and the events are public anyway
     let app = Router::new()
-        .route("/", get(root))
         .route("/balance", post(balance))
         .route("/info", post(info))
-        .route("/create-wallet", post(create_wallet));
+        .route("/create-wallet", post(create_wallet))
+        .route("/staking/health", post(staking_health))
+        .route("/staking/stake", post(staking_stake))
+        .route("/staking/claim", post(staking_claim))
+        .route("/staking/boost", post(staking_boost))
+        .route("/staking/cancel", post(staking_cancel))
+        .route("/staking/:staking_info", get(staking_stats))
+        .route("/configs", post(configs))
+        .route("/nft/mint", post(mint_nft))
+        .route("/nft/:mint", get(nft))
+        .route_layer(extractor_middleware::<ApiKey>())
+        .route("/", get(root))
+        .route("/ws/events", get(ws_events))
+        .route("/openapi.json", get(openapi_json))
+        .route("/docs", get(docs))
+        .route("/health", get(health))
+        .route("/metrics", get(metrics::metrics))
+        .route_layer(MetricsLayer::new(metrics.clone()))
+        .layer(Extension(Arc::new(auth)))
+        .layer(Extension(Arc::new(vault)))
+        .layer(Extension(events))
+        .layer(Extension(metrics));
 
     let addr = SocketAddr::from(([127, 0, 0, 1], get_port()));
     println!("listening on {}", addr);
@@ -56,137 +256,538 @@ async fn root() -> &'static str {
     "Rest full blockchain server is working!"
 }
 
+#[utoipa::path(get, path = "/health", tag = "monitoring", params(HealthReq), security(()),
+    responses((status = 200, body = HealthRes),
+              (status = 503, description = "The RPC node is unavailable", body = ErrorRes)))]
+async fn health(
+    Extension(metrics): Extension<Arc<Metrics>>,
+    Query(health_req): Query<HealthReq>,
+) -> impl IntoResponse {
+
+    let start = Instant::now();
+    let slot = client(&health_req.url).slot().await;
+    let latency = start.elapsed();
+    metrics.record_rpc_check(latency, slot.is_err());
+
+    match slot {
+        Ok(slot) =>
+            (StatusCode::OK,
+             Json(HealthRes { slot, rpc_latency_ms: latency.as_millis() as u64 })).into_response(),
+        Err(e) =>
+            (StatusCode::SERVICE_UNAVAILABLE, Json(ErrorRes { error: e.to_string() })).into_response(),
+    }
+}
+
+#[utoipa::path(post, path = "/balance", tag = "token", request_body = BalanceReq,
+    responses((status = 200, body = BalanceRes), (status = 400, body = ErrorRes)))]
 async fn balance(
     Json(balance_req): Json<BalanceReq>,
 ) -> impl IntoResponse {
 
-    let mut args: String = "./chill-cli balance".into();
-
-    if !balance_req.url.is_empty() {
-        args.push_str(&format!(" --{} {}", RPC_URL, balance_req.url));
-    }
-    if !balance_req.mint_address.is_empty() {
-        args.push_str(&format!(" --{} {}", MINT, balance_req.mint_address));
-    }
-    if !balance_req.account.is_empty() {
-        args.push_str(&format!(" --{} {}", ACCOUNT, balance_req.account));
-    }
+    let parsed = mint(&balance_req.mint_address, &balance_req.url).and_then(|mint| {
+        Ok((mint, parse_pubkey("account", &balance_req.account)?))
+    });
+    let (mint, account) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => return bad_request(e),
+    };
 
-    let args = args.split_whitespace().collect::<Vec<&str>>();
+    let balance = client(&balance_req.url)
+        .run(move |client| api::balance(client, mint, account))
+        .await;
 
-    let app_init_result = App::init_from_save(&args);
-    if let Err(e) = app_init_result {
-        return (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))).into_response();
+    match balance {
+        Ok(balance) => (StatusCode::OK, Json(BalanceRes { balance })).into_response(),
+        Err(e) => error_response(e),
     }
-    let processed_data_result = app_init_result.unwrap().run_with_result();
-    match processed_data_result {
-        Ok(chill_cli::app::ProcessedData::Balance(balance)) =>
-            return (StatusCode::OK, Json(BalanceRes { balance })).into_response(),
-        Ok(_) =>
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "wrong processed data"}))).into_response(),
-        Err(e) => 
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))).into_response(),
-    };
 }
 
+#[utoipa::path(post, path = "/info", tag = "token", request_body = InfoReq,
+    responses((status = 200, body = InfoRes), (status = 400, body = ErrorRes)))]
 async fn info(
     Json(info_req): Json<InfoReq>,
 ) -> impl IntoResponse {
 
-    let mut args: String = "./chill-cli info".into();
-
-    if !info_req.url.is_empty() {
-        args.push_str(&format!(" --{} {}", RPC_URL, info_req.url));
-    }
-    if !info_req.mint_address.is_empty() {
-        args.push_str(&format!(" --{} {}", MINT, info_req.mint_address));
-    }
-
-    let args = args.split_whitespace().collect::<Vec<&str>>();
-
-    let app_init_result = App::init_from_save(&args);
-    if let Err(e) = app_init_result {
-        return (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))).into_response();
-    }
-    let processed_data_result = app_init_result.unwrap().run_with_result();
-    match processed_data_result {
-        Ok(chill_cli::app::ProcessedData::Info(info)) =>
-            return (StatusCode::OK, Json(InfoRes { info })).into_response(),
-        Ok(_) =>
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "wrong processed data"}))).into_response(),
-        Err(e) => 
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))).into_response(),
+    let mint = match mint(&info_req.mint_address, &info_req.url) {
+        Ok(mint) => mint,
+        Err(e) => return bad_request(e),
     };
 
+    let info = client(&info_req.url)
+        .run(move |client| api::info(client, mint, chill_nft::ID))
+        .await;
+
+    match info {
+        Ok(info) => {
+            let recipients = info
+                .recipients
+                .iter()
+                .map(|recipient| RecipientRes {
+                    address: recipient.address.to_string(),
+                    mint_share: recipient.mint_share,
+                    transaction_share: recipient.transaction_share,
+                })
+                .collect();
+
+            (StatusCode::OK,
+             Json(InfoRes {
+                 authority: info.authority.map(|authority| authority.to_string()),
+                 decimals: info.decimals,
+                 fees: FeesRes {
+                     character: info.fees.character,
+                     pet: info.fees.pet,
+                     emote: info.fees.emote,
+                     tileset: info.fees.tileset,
+                     item: info.fees.item,
+                     world: info.fees.world,
+                 },
+                 recipients,
+             })).into_response()
+        },
+        Err(e) => error_response(e),
+    }
 }
 
 
+#[utoipa::path(post, path = "/create-wallet", tag = "wallet", request_body = CreateWalletReq,
+    responses((status = 200, body = CreateWalletRes), (status = 400, body = ErrorRes)))]
 async fn create_wallet(
+    Extension(vault): Extension<Arc<Vault>>,
     Json(create_wallet_req): Json<CreateWalletReq>,
 ) -> impl IntoResponse {
 
-    let mut args: String = "./chill-cli create-wallet".into();
+    let parsed = (|| {
+        let account = parse_pubkey("account", &create_wallet_req.account)?;
+        let payer = keypair(&vault, &create_wallet_req.payer)?;
+        // The primary wallet only approves the wallet, so its pubkey is enough
+        let primary_wallet = match Pubkey::from_str(&create_wallet_req.primary_wallet) {
+            Ok(pubkey) => pubkey,
+            Err(_) => keypair(&vault, &create_wallet_req.primary_wallet)?.pubkey(),
+        };
+        let program_id = parse_program_id(&create_wallet_req.program_id, chill_wallet::ID)?;
+
+        Ok((account, payer, primary_wallet, program_id))
+    })();
+
+    let (account, payer, primary_wallet, program_id) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => return bad_request(e),
+    };
 
-    if !create_wallet_req.url.is_empty() {
-        args.push_str(&format!(" --{} {}", RPC_URL, create_wallet_req.url));
+    let wallet = client(&create_wallet_req.url)
+        .run(move |client| {
+            api::create_wallet(client, Rc::new(payer), account, primary_wallet, program_id)
+        })
+        .await;
+
+    match wallet {
+        Ok((wallet, signature)) =>
+            (StatusCode::OK,
+             Json(CreateWalletRes { wallet: wallet.to_string(), signature: signature.to_string()})).into_response(),
+        Err(e) => error_response(e),
     }
-    if !create_wallet_req.account.is_empty() {
-        args.push_str(&format!(" --{} {}", ACCOUNT, create_wallet_req.account));
+}
+
+#[utoipa::path(post, path = "/staking/health", tag = "staking", request_body = StakingHealthReq,
+    responses((status = 200, body = StakingHealthRes),
+              (status = 503, description = "The pool is insolvent", body = StakingHealthRes),
+              (status = 404, body = ErrorRes)))]
+async fn staking_health(
+    Extension(vault): Extension<Arc<Vault>>,
+    Json(staking_health_req): Json<StakingHealthReq>,
+) -> impl IntoResponse {
+
+    let parsed = (|| {
+        let payer = keypair(&vault, &staking_health_req.payer)?;
+        let program_id = parse_program_id(&staking_health_req.program_id, chill_staking::ID)?;
+        let staking_info = parse_pubkey("staking_info", &staking_health_req.staking_info)?;
+
+        Ok((payer, staking_info, program_id))
+    })();
+
+    let (payer, staking_info, program_id) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => return bad_request(e),
+    };
+
+    let report = client(&staking_health_req.url)
+        .run(move |client| api::staking_audit(client, Rc::new(payer), staking_info, program_id))
+        .await;
+
+    match report {
+        Ok(report) => {
+            let status = if report.is_solvent {
+                StatusCode::OK
+            } else {
+                StatusCode::SERVICE_UNAVAILABLE
+            };
+            (status,
+             Json(StakingHealthRes {
+                 token_account_amount: report.token_account_amount,
+                 free_reward_amount: report.free_reward_amount,
+                 pending_withdrawal_amount: report.pending_withdrawal_amount,
+                 required_amount: report.required_amount,
+                 is_solvent: report.is_solvent,
+             })).into_response()
+        },
+        Err(e) => error_response(e),
     }
-    if !create_wallet_req.payer.is_empty() {
-        args.push_str(&format!(" --{} {}", PAYER, create_wallet_req.payer));
+}
+
+#[utoipa::path(get, path = "/staking/{staking_info}", tag = "staking",
+    params(("staking_info" = String, Path, description = "Address of the staking info"), StakingStatsReq),
+    responses((status = 200, body = StakingStats), (status = 404, body = ErrorRes)))]
+async fn staking_stats(
+    Path(staking_info): Path<String>,
+    Query(staking_stats_req): Query<StakingStatsReq>,
+) -> impl IntoResponse {
+
+    let address = match parse_pubkey("staking_info", &staking_info) {
+        Ok(address) => address,
+        Err(e) => return bad_request(e),
+    };
+
+    let client = client(&staking_stats_req.url);
+
+    let stats = tokio::try_join!(client.staking_info(address), client.clock())
+        .map(|(staking, clock)| StakingStats::new(staking_info, &staking, clock.unix_timestamp));
+
+    match stats {
+        Ok(stats) => (StatusCode::OK, Json(stats)).into_response(),
+        Err(e) => error_response(e),
     }
-    if !create_wallet_req.primary_wallet.is_empty() {
-        args.push_str(&format!(" --{} {}", PRIMARY_WALLET, create_wallet_req.primary_wallet));
+}
+
+#[derive(Clone, Copy)]
+enum StakingAction {
+    Stake,
+    Claim,
+    Boost,
+    Cancel,
+}
+
+// Builds the transaction of the user action without signing it, the user
+// signs and sends it by a wallet in the browser. The server holds no user keys
+async fn staking_action_transaction(action: StakingAction, req: StakingActionReq) -> Response {
+    let parsed = (|| {
+        let user = parse_pubkey("user", &req.user)?;
+        let staking_info = parse_pubkey("staking_info", &req.staking_info)?;
+        let program_id = parse_program_id(&req.program_id, chill_staking::ID)?;
+        let referrer = if req.referrer.is_empty() {
+            None
+        } else {
+            Some(parse_pubkey("referrer", &req.referrer)?)
+        };
+
+        Ok((user, staking_info, program_id, referrer))
+    })();
+
+    let (user, staking_info, program_id, referrer) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => return bad_request(e),
+    };
+
+    let transaction = client(&req.url).run(move |client| {
+        let staking = client.staking_info(staking_info)?;
+        let user_token_account = get_associated_token_address(&user, &staking.mint);
+        let ix: Instruction = match action {
+            StakingAction::Stake => chill_sdk::instruction::stake(
+                user,
+                user,
+                user,
+                user_token_account,
+                staking_info,
+                staking.mint,
+                req.amount,
+                referrer,
+                program_id,
+            ),
+            StakingAction::Claim => {
+                let mut fee_config = None;
+                let mut recipients_token_accounts = Vec::new();
+                if staking.claim_fee > 0 {
+                    let config = client.config(staking.mint, chill_nft::ID)?;
+                    fee_config = Some(chill_sdk::pda::config(staking.mint, chill_nft::ID));
+                    recipients_token_accounts = config
+                        .recipients
+                        .iter()
+                        .map(|recipient| get_associated_token_address(&recipient.address, &staking.mint))
+                        .collect();
+                }
+
+                chill_sdk::instruction::claim(
+                    user,
+                    user_token_account,
+                    staking_info,
+                    staking.mint,
+                    req.amount,
+                    fee_config,
+                    &recipients_token_accounts,
+                    program_id,
+                )
+            },
+            StakingAction::Boost => chill_sdk::instruction::boost(user, staking_info, program_id),
+            StakingAction::Cancel => chill_sdk::instruction::cancel(user, staking_info, program_id),
+        };
+
+        client.export_transaction(&[ix], user)
+    }).await;
+
+    match transaction {
+        Ok(transaction) => (StatusCode::OK, Json(TransactionRes { transaction })).into_response(),
+        Err(e) => error_response(e),
     }
-    if !create_wallet_req.program_id.is_empty() {
-        args.push_str(&format!(" --{} {}", PROGRAM_ID, create_wallet_req.program_id));
+}
+
+#[utoipa::path(post, path = "/staking/stake", tag = "staking", request_body = StakingActionReq,
+    responses((status = 200, description = "Unsigned transaction", body = TransactionRes), (status = 404, body = ErrorRes)))]
+async fn staking_stake(Json(req): Json<StakingActionReq>) -> impl IntoResponse {
+    staking_action_transaction(StakingAction::Stake, req).await
+}
+
+#[utoipa::path(post, path = "/staking/claim", tag = "staking", request_body = StakingActionReq,
+    responses((status = 200, description = "Unsigned transaction", body = TransactionRes), (status = 404, body = ErrorRes)))]
+async fn staking_claim(Json(req): Json<StakingActionReq>) -> impl IntoResponse {
+    staking_action_transaction(StakingAction::Claim, req).await
+}
+
+#[utoipa::path(post, path = "/staking/boost", tag = "staking", request_body = StakingActionReq,
+    responses((status = 200, description = "Unsigned transaction", body = TransactionRes), (status = 404, body = ErrorRes)))]
+async fn staking_boost(Json(req): Json<StakingActionReq>) -> impl IntoResponse {
+    staking_action_transaction(StakingAction::Boost, req).await
+}
+
+#[utoipa::path(post, path = "/staking/cancel", tag = "staking", request_body = StakingActionReq,
+    responses((status = 200, description = "Unsigned transaction", body = TransactionRes), (status = 404, body = ErrorRes)))]
+async fn staking_cancel(Json(req): Json<StakingActionReq>) -> impl IntoResponse {
+    staking_action_transaction(StakingAction::Cancel, req).await
+}
+
+#[utoipa::path(get, path = "/nft/{mint}", tag = "nft",
+    params(("mint" = String, Path, description = "Mint of the NFT"), NftReq),
+    responses((status = 200, body = NftRes), (status = 404, body = ErrorRes)))]
+async fn nft(
+    Path(nft_mint): Path<String>,
+    Query(nft_req): Query<NftReq>,
+) -> impl IntoResponse {
+
+    let parsed = (|| {
+        let nft_mint = parse_pubkey("mint", &nft_mint)?;
+        let chill_mint = mint(&nft_req.mint_address, &nft_req.url)?;
+        let program_id = parse_program_id(&nft_req.program_id, chill_nft::ID)?;
+
+        Ok((nft_mint, chill_mint, program_id))
+    })();
+
+    let (nft_mint, chill_mint, program_id) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => return bad_request(e),
+    };
+
+    let info = client(&nft_req.url)
+        .run(move |client| api::nft(client, nft_mint, chill_mint, program_id))
+        .await;
+
+    match info {
+        Ok(info) => {
+            // Metaplex pads the strings by zeros up to their max length
+            let data = &info.metadata.data;
+            let creators = data
+                .creators
+                .iter()
+                .flatten()
+                .map(|creator| CreatorRes {
+                    address: creator.address.to_string(),
+                    verified: creator.verified,
+                    share: creator.share,
+                })
+                .collect();
+
+            (StatusCode::OK,
+             Json(NftRes {
+                 mint: nft_mint.to_string(),
+                 name: data.name.trim_end_matches('\0').to_owned(),
+                 symbol: data.symbol.trim_end_matches('\0').to_owned(),
+                 uri: data.uri.trim_end_matches('\0').to_owned(),
+                 seller_fee_basis_points: data.seller_fee_basis_points,
+                 creators,
+                 update_authority: info.metadata.update_authority.to_string(),
+                 is_mutable: info.metadata.is_mutable,
+                 collection: info.metadata.collection.map(|collection| collection.key.to_string()),
+                 nft_type: info.nft_type_name,
+                 min_game_version: info.chill_metadata.min_game_version,
+                 rent_receiver: info.chill_metadata.rent_receiver.to_string(),
+                 finalized: info.chill_metadata.finalized,
+                 version: info.chill_metadata.version,
+                 attributes: chill_sdk::view::nft_attributes_to_json(&info.chill_metadata.attributes),
+             })).into_response()
+        },
+        Err(e) => error_response(e),
     }
+}
+
+// Builds the transaction which mints a new NFT. The mint keypair is generated
+// here and signs right away, the configured primary wallet signs as well
+#[utoipa::path(post, path = "/nft/mint", tag = "nft", request_body = MintNftReq,
+    responses((status = 200, body = MintNftRes), (status = 400, body = ErrorRes)))]
+async fn mint_nft(
+    Extension(vault): Extension<Arc<Vault>>,
+    Json(mint_nft_req): Json<MintNftReq>,
+) -> impl IntoResponse {
 
-    let args = args.split_whitespace().collect::<Vec<&str>>();
+    let parsed = (|| {
+        let chill_mint = mint(&mint_nft_req.mint_address, &mint_nft_req.url)?;
+        let program_id = parse_program_id(&mint_nft_req.program_id, chill_nft::ID)?;
+        let recipient = parse_pubkey("recipient", &mint_nft_req.recipient)?;
+        let signer = primary_wallet(&vault)?;
+        let primary_wallet = match &signer {
+            Some(signer) => signer.pubkey(),
+            None => parse_pubkey("primary_wallet", &mint_nft_req.primary_wallet)?,
+        };
+        let payer = if mint_nft_req.payer.is_empty() {
+            primary_wallet
+        } else {
+            parse_pubkey("payer", &mint_nft_req.payer)?
+        };
+
+        Ok((chill_mint, program_id, recipient, signer, primary_wallet, payer))
+    })();
+
+    let (chill_mint, program_id, recipient, signer, primary_wallet, payer) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => return bad_request(e),
+    };
+
+    let args = NftArgs {
+        name: mint_nft_req.name,
+        symbol: mint_nft_req.symbol,
+        uri: mint_nft_req.uri,
+        fees: mint_nft_req.fees,
+    };
 
-    let app_init_result = App::init_from_save(&args);
-    if let Err(e) = app_init_result {
-        return (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))).into_response();
+    let nft_type = mint_nft_req.nft_type;
+    let min_game_version = mint_nft_req.min_game_version;
+    let transaction = client(&mint_nft_req.url).run(move |client| {
+        let nft_type = api::nft_type(client, &nft_type, chill_mint, program_id)?;
+        let signers = signer.iter().map(|signer| signer as &dyn Signer).collect::<Vec<_>>();
+        client.mint_nft_transaction(
+            primary_wallet,
+            payer,
+            recipient,
+            chill_mint,
+            nft_type,
+            args,
+            min_game_version,
+            program_id,
+            &signers,
+        )
+    }).await;
+
+    match transaction {
+        Ok((nft_mint, transaction)) =>
+            (StatusCode::OK,
+             Json(MintNftRes { nft_mint: nft_mint.to_string(), transaction })).into_response(),
+        Err(e) => error_response(e),
     }
-    let processed_data_result = app_init_result.unwrap().run_with_result();
-    match processed_data_result {
-        Ok(chill_cli::app::ProcessedData::CreateWallet{wallet, signature}) =>
-            return (StatusCode::OK,
-                    Json(CreateWalletRes { wallet: wallet.to_string(), signature: signature.to_string()})).into_response(),
-        Ok(_) =>
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "wrong processed data"}))).into_response(),
-        Err(e) => 
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))).into_response(),
+}
+
+#[utoipa::path(post, path = "/configs", tag = "nft", request_body = ConfigsReq,
+    responses((status = 200, body = ConfigsPage), (status = 400, body = ErrorRes)))]
+async fn configs(
+    Json(configs_req): Json<ConfigsReq>,
+) -> impl IntoResponse {
+
+    let program_id = match parse_program_id(&configs_req.program_id, chill_nft::ID) {
+        Ok(program_id) => program_id,
+        Err(e) => return bad_request(e),
     };
+
+    let configs = client(&configs_req.url)
+        .run(move |client| api::configs(client, program_id))
+        .await;
+
+    match configs {
+        Ok(configs) => {
+            let items = configs
+                .into_iter()
+                .map(|(pubkey, config)| ConfigRes {
+                    config: pubkey.to_string(),
+                    mint: config.mint.to_string(),
+                    primary_wallet: config.primary_wallet.to_string(),
+                })
+                .filter(|item| configs_req.mint.is_empty() || item.mint == configs_req.mint)
+                .collect();
+
+            match paginate(items, |item: &ConfigRes| item.config.clone(), &configs_req.page) {
+                Ok(page) => (StatusCode::OK, Json(page)).into_response(),
+                Err(e) => bad_request(e.to_string()),
+            }
+        },
+        Err(e) => error_response(e),
+    }
 }
 
+#[derive(Serialize, ToSchema)]
+struct ErrorRes {
+    error: String,
+}
 
+#[derive(Deserialize, IntoParams)]
+struct HealthReq {
+    #[serde(default)]
+    url: String,
+}
 
-#[derive(Deserialize)]
+#[derive(Serialize, ToSchema)]
+struct HealthRes {
+    slot: u64,
+    rpc_latency_ms: u64,
+}
+
+#[derive(Deserialize, ToSchema)]
 struct BalanceReq {
     url: String,
     mint_address: String,
-    account: String,    
+    account: String,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 struct BalanceRes {
     balance: f64,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 struct InfoReq {
     url: String,
     mint_address: String,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
+struct FeesRes {
+    character: f64,
+    pet: f64,
+    emote: f64,
+    tileset: f64,
+    item: f64,
+    world: f64,
+}
+
+#[derive(Serialize, ToSchema)]
+struct RecipientRes {
+    address: String,
+    mint_share: u8,
+    transaction_share: u8,
+}
+
+#[derive(Serialize, ToSchema)]
 struct InfoRes {
-    info: String,
+    authority: Option<String>,
+    decimals: u8,
+    fees: FeesRes,
+    recipients: Vec<RecipientRes>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 struct CreateWalletReq {
     url: String,
     account: String,
@@ -195,8 +796,132 @@ struct CreateWalletReq {
     program_id: String,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 struct CreateWalletRes {
     wallet: String,
     signature: String,
-}
\ No newline at end of file
+}
+
+#[derive(Deserialize, ToSchema)]
+struct StakingHealthReq {
+    url: String,
+    payer: String,
+    program_id: String,
+    staking_info: String,
+}
+
+#[derive(Serialize, ToSchema)]
+struct StakingHealthRes {
+    token_account_amount: u64,
+    free_reward_amount: u64,
+    pending_withdrawal_amount: u64,
+    required_amount: u64,
+    is_solvent: bool,
+}
+
+#[derive(Deserialize, IntoParams)]
+struct StakingStatsReq {
+    #[serde(default)]
+    url: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+struct StakingActionReq {
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    program_id: String,
+    user: String,
+    staking_info: String,
+    #[serde(default)]
+    amount: u64,
+    #[serde(default)]
+    referrer: String,
+}
+
+#[derive(Serialize, ToSchema)]
+struct TransactionRes {
+    transaction: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+struct ConfigsReq {
+    url: String,
+    program_id: String,
+    mint: String,
+    #[serde(flatten)]
+    page: PageParams,
+}
+
+#[derive(Serialize, ToSchema)]
+struct ConfigRes {
+    config: String,
+    mint: String,
+    primary_wallet: String,
+}
+
+#[derive(Deserialize, IntoParams)]
+struct NftReq {
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    program_id: String,
+    #[serde(default)]
+    mint_address: String,
+}
+
+#[derive(Serialize, ToSchema)]
+struct CreatorRes {
+    address: String,
+    verified: bool,
+    share: u8,
+}
+
+#[derive(Serialize, ToSchema)]
+struct NftRes {
+    mint: String,
+    name: String,
+    symbol: String,
+    uri: String,
+    seller_fee_basis_points: u16,
+    creators: Vec<CreatorRes>,
+    update_authority: String,
+    is_mutable: bool,
+    collection: Option<String>,
+    nft_type: Option<String>,
+    min_game_version: u32,
+    rent_receiver: String,
+    finalized: bool,
+    version: u8,
+    #[schema(value_type = Object)]
+    attributes: serde_json::Value,
+}
+
+#[derive(Deserialize, ToSchema)]
+struct MintNftReq {
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    program_id: String,
+    #[serde(default)]
+    mint_address: String,
+    #[serde(default)]
+    primary_wallet: String,
+    #[serde(default)]
+    payer: String,
+    recipient: String,
+    nft_type: String,
+    name: String,
+    symbol: String,
+    uri: String,
+    #[serde(default)]
+    fees: u16,
+    #[serde(default)]
+    min_game_version: u32,
+}
+
+#[derive(Serialize, ToSchema)]
+struct MintNftRes {
+    nft_mint: String,
+    transaction: String,
+}
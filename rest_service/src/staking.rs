@@ -0,0 +1,59 @@
+use chill_staking::state::{StakingInfo, SEC_PER_DAY};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Statistics of a staking pool, the daily reward is the one calculated by
+/// the last transaction of the pool
+#[derive(Serialize, ToSchema)]
+pub struct StakingStats {
+    pub staking_info: String,
+    pub mint: String,
+    pub total_staked_amount: u64,
+    pub active_stakes_number: u64,
+    pub daily_reward: u64,
+    pub days_elapsed: u64,
+    pub days_remaining: u64,
+    pub reward_tokens_remaining: u64,
+}
+
+impl StakingStats {
+    pub fn new(staking_info: String, staking: &StakingInfo, unix_timestamp: i64) -> Self {
+        let current_day = (unix_timestamp.max(0) as u64)
+            .saturating_add(staking.clock_offset)
+            .checked_div(SEC_PER_DAY)
+            .unwrap();
+        let (days_elapsed, days_remaining) =
+            staking_days(staking.start_day, staking.end_day, current_day);
+
+        StakingStats {
+            staking_info,
+            mint: staking.mint.to_string(),
+            total_staked_amount: staking.total_staked_amount,
+            active_stakes_number: staking.active_stakes_number,
+            daily_reward: staking.last_daily_reward,
+            days_elapsed,
+            days_remaining,
+            reward_tokens_remaining: staking.free_reward_amount().unwrap_or_default(),
+        }
+    }
+}
+
+fn staking_days(start_day: u64, end_day: u64, current_day: u64) -> (u64, u64) {
+    let total_days = end_day.saturating_sub(start_day);
+    let days_elapsed = current_day.saturating_sub(start_day).min(total_days);
+    (days_elapsed, total_days - days_elapsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_of_staking() {
+        assert_eq!(staking_days(10, 40, 5), (0, 30));
+        assert_eq!(staking_days(10, 40, 10), (0, 30));
+        assert_eq!(staking_days(10, 40, 25), (15, 15));
+        assert_eq!(staking_days(10, 40, 40), (30, 0));
+        assert_eq!(staking_days(10, 40, 100), (30, 0));
+    }
+}
@@ -0,0 +1,196 @@
+use ring::{
+    aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN},
+    pbkdf2,
+    rand::{SecureRandom, SystemRandom},
+};
+use solana_sdk::signature::Keypair;
+use std::{collections::HashMap, env, fs, num::NonZeroU32};
+use thiserror::Error;
+use zeroize::Zeroizing;
+
+pub const RESTAPI_VAULT_ENV: &str = "RESTAPI_VAULT";
+pub const RESTAPI_VAULT_PASSPHRASE_ENV: &str = "RESTAPI_VAULT_PASSPHRASE";
+pub const RESTAPI_KEY_ENV_PREFIX: &str = "RESTAPI_KEY_";
+
+/// Signs requests which don't reference any key
+pub const DEFAULT_ALIAS: &str = "default";
+
+const MAGIC: &[u8] = b"CHILLVAULT1";
+const SALT_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+const PBKDF2_ITERATIONS: u32 = 100_000;
+
+#[derive(Error, Debug)]
+pub enum VaultError {
+    #[error("Cannot read the vault: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{0} is not set")]
+    PassphraseNotSet(&'static str),
+
+    #[error("Wrong vault format")]
+    WrongFormat,
+
+    #[error("Cannot decrypt the vault, the passphrase is wrong or the file is corrupted")]
+    DecryptionFailed,
+
+    #[error("Cannot encrypt the vault")]
+    EncryptionFailed,
+
+    #[error("Key '{0}' is not a keypair")]
+    InvalidKeypair(String),
+}
+
+/// Keypairs of the REST signer referenced by aliases. The secret bytes are
+/// zeroized when the vault is dropped
+#[derive(Default)]
+pub struct Vault {
+    keys: HashMap<String, Zeroizing<Vec<u8>>>,
+}
+
+fn encryption_key(passphrase: &[u8], salt: &[u8]) -> Result<LessSafeKey, VaultError> {
+    let mut key = Zeroizing::new([0; KEY_LEN]);
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        NonZeroU32::new(PBKDF2_ITERATIONS).unwrap(),
+        salt,
+        passphrase,
+        &mut key[..],
+    );
+
+    let key = UnboundKey::new(&CHACHA20_POLY1305, &key[..]).map_err(|_| VaultError::WrongFormat)?;
+    Ok(LessSafeKey::new(key))
+}
+
+fn parse_keypair(alias: &str, bytes: Zeroizing<Vec<u8>>) -> Result<Zeroizing<Vec<u8>>, VaultError> {
+    Keypair::from_bytes(&bytes).map_err(|_| VaultError::InvalidKeypair(alias.to_owned()))?;
+    Ok(bytes)
+}
+
+impl Vault {
+    /// Loads the vault file from `RESTAPI_VAULT` decrypted with
+    /// `RESTAPI_VAULT_PASSPHRASE`, then adds keypairs from `RESTAPI_KEY_<ALIAS>`
+    /// variables in the format of a Solana keypair file
+    pub fn load() -> Result<Self, VaultError> {
+        let mut vault = match env::var(RESTAPI_VAULT_ENV) {
+            Ok(path) => {
+                let passphrase = env::var(RESTAPI_VAULT_PASSPHRASE_ENV)
+                    .map(Zeroizing::new)
+                    .map_err(|_| VaultError::PassphraseNotSet(RESTAPI_VAULT_PASSPHRASE_ENV))?;
+                Self::open(&fs::read(path)?, passphrase.as_bytes())?
+            }
+            Err(_) => Self::default(),
+        };
+
+        for (name, value) in env::vars() {
+            if let Some(alias) = name.strip_prefix(RESTAPI_KEY_ENV_PREFIX) {
+                let alias = alias.to_lowercase();
+                let value = Zeroizing::new(value);
+                let bytes = serde_json::from_str::<Vec<u8>>(&value)
+                    .map(Zeroizing::new)
+                    .map_err(|_| VaultError::InvalidKeypair(alias.clone()))?;
+                let bytes = parse_keypair(&alias, bytes)?;
+                vault.keys.insert(alias, bytes);
+            }
+        }
+
+        Ok(vault)
+    }
+
+    /// Decrypts a vault sealed by `Vault::seal`
+    pub fn open(data: &[u8], passphrase: &[u8]) -> Result<Self, VaultError> {
+        let data = data.strip_prefix(MAGIC).ok_or(VaultError::WrongFormat)?;
+        if data.len() < SALT_LEN + NONCE_LEN {
+            return Err(VaultError::WrongFormat);
+        }
+
+        let (salt, data) = data.split_at(SALT_LEN);
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| VaultError::WrongFormat)?;
+
+        let mut plaintext = Zeroizing::new(ciphertext.to_vec());
+        let plaintext_len = encryption_key(passphrase, salt)?
+            .open_in_place(nonce, Aad::from(MAGIC), &mut plaintext[..])
+            .map_err(|_| VaultError::DecryptionFailed)?
+            .len();
+
+        let keys = serde_json::from_slice::<HashMap<String, Vec<u8>>>(&plaintext[..plaintext_len])
+            .map_err(|_| VaultError::WrongFormat)?;
+
+        let mut vault = Self::default();
+        for (alias, bytes) in keys {
+            let bytes = parse_keypair(&alias, Zeroizing::new(bytes))?;
+            vault.keys.insert(alias, bytes);
+        }
+
+        Ok(vault)
+    }
+
+    /// Encrypts the keypairs with a key derived from the passphrase by
+    /// PBKDF2-HMAC-SHA256 using ChaCha20-Poly1305
+    pub fn seal(&self, passphrase: &[u8]) -> Result<Vec<u8>, VaultError> {
+        let rng = SystemRandom::new();
+        let mut salt = [0; SALT_LEN];
+        let mut nonce = [0; NONCE_LEN];
+        rng.fill(&mut salt)
+            .map_err(|_| VaultError::EncryptionFailed)?;
+        rng.fill(&mut nonce)
+            .map_err(|_| VaultError::EncryptionFailed)?;
+
+        let keys = self
+            .keys
+            .iter()
+            .map(|(alias, bytes)| (alias, bytes.as_slice()))
+            .collect::<HashMap<_, _>>();
+        let mut data = Zeroizing::new(serde_json::to_vec(&keys).unwrap());
+
+        encryption_key(passphrase, &salt)?
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(MAGIC),
+                &mut *data,
+            )
+            .map_err(|_| VaultError::EncryptionFailed)?;
+
+        Ok([MAGIC, &salt, &nonce, data.as_slice()].concat())
+    }
+
+    pub fn insert(&mut self, alias: &str, keypair: &Keypair) {
+        self.keys.insert(
+            alias.to_owned(),
+            Zeroizing::new(keypair.to_bytes().to_vec()),
+        );
+    }
+
+    pub fn keypair(&self, alias: &str) -> Option<Keypair> {
+        let bytes = self.keys.get(alias)?;
+        Some(Keypair::from_bytes(bytes).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signer::Signer;
+
+    #[test]
+    fn seal_and_open() {
+        let keypair = Keypair::new();
+        let mut vault = Vault::default();
+        vault.insert("payer", &keypair);
+
+        let data = vault.seal(b"passphrase").unwrap();
+        let vault = Vault::open(&data, b"passphrase").unwrap();
+        assert_eq!(vault.keypair("payer").unwrap().pubkey(), keypair.pubkey());
+        assert!(vault.keypair(DEFAULT_ALIAS).is_none());
+
+        assert!(matches!(
+            Vault::open(&data, b"wrong"),
+            Err(VaultError::DecryptionFailed)
+        ));
+        assert!(matches!(
+            Vault::open(&data[1..], b"passphrase"),
+            Err(VaultError::WrongFormat)
+        ));
+    }
+}
@@ -0,0 +1,2380 @@
+use crate::pda;
+use anchor_lang::{
+    prelude::{AccountMeta, Pubkey},
+    solana_program::{instruction::Instruction, system_program, sysvar::rent},
+    InstructionData, ToAccountMetas,
+};
+use anchor_spl::associated_token::get_associated_token_address;
+use chill_nft::{
+    state::{Fees, NftAttributes, NftType, Recipient},
+    utils::{BatchNft, NftArgs, Uses},
+};
+use chill_vesting::InitializeVestingArgs;
+use chill_wallet::state::DailyLimits;
+
+fn instruction(
+    program_id: Pubkey,
+    accounts: impl ToAccountMetas,
+    data: impl InstructionData,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// The referral account of the user is passed if the referrer is specified
+#[allow(clippy::too_many_arguments)]
+pub fn stake(
+    user: Pubkey,
+    token_account_authority: Pubkey,
+    payer: Pubkey,
+    from_token_account: Pubkey,
+    staking_info: Pubkey,
+    mint: Pubkey,
+    amount: u64,
+    referrer: Option<Pubkey>,
+    program_id: Pubkey,
+) -> Instruction {
+    let staking_token_authority = pda::staking_token_authority(staking_info, program_id);
+    let staking_token_account = get_associated_token_address(&staking_token_authority, &mint);
+    let user_info = pda::user_info(staking_info, user, program_id);
+
+    let mut ix = instruction(
+        program_id,
+        chill_staking::accounts::Stake {
+            user,
+            token_account_authority,
+            payer,
+            from_token_account,
+            user_info,
+            staking_info,
+            staking_token_authority,
+            staking_token_account,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+        },
+        chill_staking::instruction::Stake { amount, referrer },
+    );
+
+    if referrer.is_some() {
+        let referral = pda::referral(user_info, program_id);
+        ix.accounts.push(AccountMeta::new(referral, false));
+    }
+
+    ix
+}
+
+/// Stakes tokens of the authority for the beneficiary, who owns the stake
+#[allow(clippy::too_many_arguments)]
+pub fn stake_for(
+    beneficiary: Pubkey,
+    token_account_authority: Pubkey,
+    payer: Pubkey,
+    from_token_account: Pubkey,
+    staking_info: Pubkey,
+    mint: Pubkey,
+    amount: u64,
+    program_id: Pubkey,
+) -> Instruction {
+    let staking_token_authority = pda::staking_token_authority(staking_info, program_id);
+
+    instruction(
+        program_id,
+        chill_staking::accounts::StakeFor {
+            beneficiary,
+            token_account_authority,
+            payer,
+            from_token_account,
+            user_info: pda::user_info(staking_info, beneficiary, program_id),
+            staking_info,
+            staking_token_authority,
+            staking_token_account: get_associated_token_address(&staking_token_authority, &mint),
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+        },
+        chill_staking::instruction::StakeFor { amount },
+    )
+}
+
+/// Accounts of an NFT of the user which are passed to `stake` after the
+/// referral account to receive the reward bonus of NFT holders. The last one
+/// records the stake which receives the bonus of the NFT
+pub fn nft_holder_accounts(
+    user: Pubkey,
+    staking_info: Pubkey,
+    nft_mint: Pubkey,
+    program_id: Pubkey,
+) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new_readonly(pda::nft_multiplier_info(staking_info, program_id), false),
+        AccountMeta::new_readonly(get_associated_token_address(&user, &nft_mint), false),
+        AccountMeta::new_readonly(pda::chill_metadata(nft_mint, chill_nft::ID), false),
+        AccountMeta::new_readonly(pda::metadata(nft_mint), false),
+        AccountMeta::new(
+            pda::nft_bonus_info(staking_info, nft_mint, program_id),
+            false,
+        ),
+    ]
+}
+
+/// The approval of the admin config is required, its signers are appended with
+/// `admin_signer_metas`
+pub fn register_nft_collection(
+    primary_wallet: Pubkey,
+    payer: Pubkey,
+    staking_info: Pubkey,
+    collection: Pubkey,
+    reward_bonus: u8,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_staking::accounts::RegisterNftCollection {
+            primary_wallet,
+            admin_config: pda::admin_config(primary_wallet, chill_nft::ID),
+            payer,
+            staking_info,
+            nft_multiplier_info: pda::nft_multiplier_info(staking_info, program_id),
+            system_program: system_program::ID,
+        },
+        chill_staking::instruction::RegisterNftCollection {
+            collection,
+            reward_bonus,
+        },
+    )
+}
+
+pub fn unstake(user: Pubkey, staking_info: Pubkey, amount: u64, program_id: Pubkey) -> Instruction {
+    instruction(
+        program_id,
+        chill_staking::accounts::UserUpdatesUserInfo {
+            user,
+            user_info: pda::user_info(staking_info, user, program_id),
+            staking_info,
+        },
+        chill_staking::instruction::Unstake { amount },
+    )
+}
+
+pub fn cancel(user: Pubkey, staking_info: Pubkey, program_id: Pubkey) -> Instruction {
+    instruction(
+        program_id,
+        chill_staking::accounts::UserUpdatesUserInfo {
+            user,
+            user_info: pda::user_info(staking_info, user, program_id),
+            staking_info,
+        },
+        chill_staking::instruction::Cancel,
+    )
+}
+
+pub fn boost(user: Pubkey, staking_info: Pubkey, program_id: Pubkey) -> Instruction {
+    instruction(
+        program_id,
+        chill_staking::accounts::UserUpdatesUserInfo {
+            user,
+            user_info: pda::user_info(staking_info, user, program_id),
+            staking_info,
+        },
+        chill_staking::instruction::Boost,
+    )
+}
+
+/// Burns the cost of a paid boost from the token account of the user. The day
+/// is an index in the window of the active stake
+pub fn paid_boost(
+    user: Pubkey,
+    from_token_account: Pubkey,
+    staking_info: Pubkey,
+    mint: Pubkey,
+    day: u64,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_staking::accounts::PaidBoost {
+            user,
+            user_info: pda::user_info(staking_info, user, program_id),
+            mint,
+            from_token_account,
+            staking_info,
+            paid_boost_info: pda::paid_boost_info(staking_info, program_id),
+            token_program: anchor_spl::token::ID,
+        },
+        chill_staking::instruction::PaidBoost { day },
+    )
+}
+
+/// The approval of the admin config is required, its signers are appended with
+/// `admin_signer_metas`
+pub fn set_paid_boost_cost(
+    primary_wallet: Pubkey,
+    payer: Pubkey,
+    staking_info: Pubkey,
+    cost: u64,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_staking::accounts::SetPaidBoostCost {
+            primary_wallet,
+            admin_config: pda::admin_config(primary_wallet, chill_nft::ID),
+            payer,
+            staking_info,
+            paid_boost_info: pda::paid_boost_info(staking_info, program_id),
+            system_program: system_program::ID,
+        },
+        chill_staking::instruction::SetPaidBoostCost { cost },
+    )
+}
+
+/// The token account of the pool fills in the withdrawal counter of legacy
+/// pools, which had no such field
+pub fn migrate_staking_info(
+    payer: Pubkey,
+    staking_info: Pubkey,
+    mint: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    let staking_token_authority = pda::staking_token_authority(staking_info, program_id);
+
+    instruction(
+        program_id,
+        chill_staking::accounts::MigrateStakingInfo {
+            payer,
+            staking_info,
+            staking_token_authority,
+            mint,
+            staking_token_account: get_associated_token_address(&staking_token_authority, &mint),
+            system_program: system_program::ID,
+        },
+        chill_staking::instruction::MigrateStakingInfo,
+    )
+}
+
+/// The user info is passed by its address, so legacy accounts found by their
+/// length are migrated without reading their users
+pub fn migrate_user_info(
+    payer: Pubkey,
+    user_info: Pubkey,
+    staking_info: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_staking::accounts::MigrateUserInfo {
+            payer,
+            user_info,
+            staking_info,
+            system_program: system_program::ID,
+        },
+        chill_staking::instruction::MigrateUserInfo,
+    )
+}
+
+/// The NFT program config of the mint and token accounts of its recipients are
+/// required if the staking takes a claim fee
+#[allow(clippy::too_many_arguments)]
+pub fn claim(
+    user: Pubkey,
+    recipient_token_account: Pubkey,
+    staking_info: Pubkey,
+    mint: Pubkey,
+    amount: u64,
+    fee_config: Option<Pubkey>,
+    recipients_token_accounts: &[Pubkey],
+    program_id: Pubkey,
+) -> Instruction {
+    let staking_token_authority = pda::staking_token_authority(staking_info, program_id);
+    let staking_token_account = get_associated_token_address(&staking_token_authority, &mint);
+
+    let mut ix = instruction(
+        program_id,
+        chill_staking::accounts::Claim {
+            user,
+            user_info: pda::user_info(staking_info, user, program_id),
+            recipient_token_account,
+            staking_info,
+            staking_token_authority,
+            staking_token_account,
+            token_program: anchor_spl::token::ID,
+        },
+        chill_staking::instruction::Claim { amount },
+    );
+
+    if let Some(config) = fee_config {
+        ix.accounts.push(AccountMeta::new_readonly(config, false));
+        ix.accounts.extend(
+            recipients_token_accounts
+                .iter()
+                .map(|pubkey| AccountMeta::new(*pubkey, false)),
+        );
+    }
+
+    ix
+}
+
+/// Claims all rewarded and pending tokens of the user. Accounts are the ones of
+/// `claim`
+pub fn claim_all(
+    user: Pubkey,
+    recipient_token_account: Pubkey,
+    staking_info: Pubkey,
+    mint: Pubkey,
+    fee_config: Option<Pubkey>,
+    recipients_token_accounts: &[Pubkey],
+    program_id: Pubkey,
+) -> Instruction {
+    let mut ix = claim(
+        user,
+        recipient_token_account,
+        staking_info,
+        mint,
+        0,
+        fee_config,
+        recipients_token_accounts,
+        program_id,
+    );
+
+    ix.data = chill_staking::instruction::ClaimAll.data();
+    ix
+}
+
+/// The NFT program config of the mint and token accounts of its recipients are
+/// required if the staking takes a claim fee
+pub fn claim_and_restake(
+    user: Pubkey,
+    staking_info: Pubkey,
+    mint: Pubkey,
+    fee_config: Option<Pubkey>,
+    recipients_token_accounts: &[Pubkey],
+    program_id: Pubkey,
+) -> Instruction {
+    let staking_token_authority = pda::staking_token_authority(staking_info, program_id);
+    let staking_token_account = get_associated_token_address(&staking_token_authority, &mint);
+
+    let mut ix = instruction(
+        program_id,
+        chill_staking::accounts::ClaimAndRestake {
+            user,
+            user_info: pda::user_info(staking_info, user, program_id),
+            staking_info,
+            staking_token_authority,
+            staking_token_account,
+            token_program: anchor_spl::token::ID,
+        },
+        chill_staking::instruction::ClaimAndRestake,
+    );
+
+    if let Some(config) = fee_config {
+        ix.accounts.push(AccountMeta::new_readonly(config, false));
+        ix.accounts.extend(
+            recipients_token_accounts
+                .iter()
+                .map(|pubkey| AccountMeta::new(*pubkey, false)),
+        );
+    }
+
+    ix
+}
+
+pub fn claim_referral_reward(
+    referrer: Pubkey,
+    user_info: Pubkey,
+    recipient_token_account: Pubkey,
+    staking_info: Pubkey,
+    mint: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    let staking_token_authority = pda::staking_token_authority(staking_info, program_id);
+    let staking_token_account = get_associated_token_address(&staking_token_authority, &mint);
+
+    instruction(
+        program_id,
+        chill_staking::accounts::ClaimReferralReward {
+            referrer,
+            referral_info: pda::referral(user_info, program_id),
+            user_info,
+            recipient_token_account,
+            staking_info,
+            staking_token_authority,
+            staking_token_account,
+            token_program: anchor_spl::token::ID,
+        },
+        chill_staking::instruction::ClaimReferralReward,
+    )
+}
+
+pub fn set_delegate(
+    user: Pubkey,
+    payer: Pubkey,
+    staking_info: Pubkey,
+    delegate: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    let user_info = pda::user_info(staking_info, user, program_id);
+
+    instruction(
+        program_id,
+        chill_staking::accounts::SetDelegate {
+            user,
+            payer,
+            user_info,
+            delegate_info: pda::delegate_info(user_info, program_id),
+            system_program: system_program::ID,
+        },
+        chill_staking::instruction::SetDelegate { delegate },
+    )
+}
+
+pub fn revoke_delegate(user: Pubkey, staking_info: Pubkey, program_id: Pubkey) -> Instruction {
+    let user_info = pda::user_info(staking_info, user, program_id);
+
+    instruction(
+        program_id,
+        chill_staking::accounts::RevokeDelegate {
+            user,
+            delegate_info: pda::delegate_info(user_info, program_id),
+        },
+        chill_staking::instruction::RevokeDelegate,
+    )
+}
+
+pub fn delegate_boost(
+    delegate: Pubkey,
+    user_info: Pubkey,
+    staking_info: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_staking::accounts::DelegateUpdatesUserInfo {
+            delegate,
+            delegate_info: pda::delegate_info(user_info, program_id),
+            user_info,
+            staking_info,
+        },
+        chill_staking::instruction::DelegateBoost,
+    )
+}
+
+/// The recipient token account must be owned by the user. The NFT program
+/// config of the mint and token accounts of its recipients are required if
+/// the staking takes a claim fee
+#[allow(clippy::too_many_arguments)]
+pub fn delegate_claim(
+    delegate: Pubkey,
+    user_info: Pubkey,
+    recipient_token_account: Pubkey,
+    staking_info: Pubkey,
+    mint: Pubkey,
+    amount: u64,
+    fee_config: Option<Pubkey>,
+    recipients_token_accounts: &[Pubkey],
+    program_id: Pubkey,
+) -> Instruction {
+    let staking_token_authority = pda::staking_token_authority(staking_info, program_id);
+    let staking_token_account = get_associated_token_address(&staking_token_authority, &mint);
+
+    let mut ix = instruction(
+        program_id,
+        chill_staking::accounts::DelegateClaim {
+            delegate,
+            delegate_info: pda::delegate_info(user_info, program_id),
+            user_info,
+            recipient_token_account,
+            staking_info,
+            staking_token_authority,
+            staking_token_account,
+            token_program: anchor_spl::token::ID,
+        },
+        chill_staking::instruction::DelegateClaim { amount },
+    );
+
+    if let Some(config) = fee_config {
+        ix.accounts.push(AccountMeta::new_readonly(config, false));
+        ix.accounts.extend(
+            recipients_token_accounts
+                .iter()
+                .map(|pubkey| AccountMeta::new(*pubkey, false)),
+        );
+    }
+
+    ix
+}
+
+pub fn settle_stake(user_info: Pubkey, staking_info: Pubkey, program_id: Pubkey) -> Instruction {
+    instruction(
+        program_id,
+        chill_staking::accounts::SettleStake {
+            user_info,
+            staking_info,
+        },
+        chill_staking::instruction::SettleStake,
+    )
+}
+
+pub fn start_redistribution(
+    primary_wallet: Pubkey,
+    payer: Pubkey,
+    staking_info: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_staking::accounts::StartRedistribution {
+            primary_wallet,
+            payer,
+            staking_info,
+            redistribution: pda::redistribution(staking_info, program_id),
+            system_program: system_program::ID,
+        },
+        chill_staking::instruction::StartRedistribution,
+    )
+}
+
+pub fn pause(primary_wallet: Pubkey, staking_info: Pubkey, program_id: Pubkey) -> Instruction {
+    instruction(
+        program_id,
+        chill_staking::accounts::PrimaryWalletUpdatesStakingInfo {
+            primary_wallet,
+            staking_info,
+        },
+        chill_staking::instruction::Pause,
+    )
+}
+
+pub fn propose_staking_primary_wallet(
+    primary_wallet: Pubkey,
+    staking_info: Pubkey,
+    new_primary_wallet: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_staking::accounts::ProposeNewPrimaryWallet {
+            primary_wallet,
+            staking_info,
+            primary_wallet_transfer: pda::primary_wallet_transfer(staking_info, program_id),
+            system_program: system_program::ID,
+        },
+        chill_staking::instruction::ProposeNewPrimaryWallet { new_primary_wallet },
+    )
+}
+
+pub fn accept_staking_primary_wallet(
+    new_primary_wallet: Pubkey,
+    primary_wallet: Pubkey,
+    staking_info: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_staking::accounts::AcceptPrimaryWallet {
+            new_primary_wallet,
+            primary_wallet,
+            staking_info,
+            primary_wallet_transfer: pda::primary_wallet_transfer(staking_info, program_id),
+        },
+        chill_staking::instruction::AcceptPrimaryWallet,
+    )
+}
+
+pub fn resume(primary_wallet: Pubkey, staking_info: Pubkey, program_id: Pubkey) -> Instruction {
+    instruction(
+        program_id,
+        chill_staking::accounts::PrimaryWalletUpdatesStakingInfo {
+            primary_wallet,
+            staking_info,
+        },
+        chill_staking::instruction::Resume,
+    )
+}
+
+pub fn redistribute_unspent_reward(
+    payer: Pubkey,
+    user_info: Pubkey,
+    staking_info: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_staking::accounts::RedistributeUnspentReward {
+            payer,
+            user_info,
+            staking_info,
+            redistribution: pda::redistribution(staking_info, program_id),
+            redistribution_receipt: pda::redistribution_receipt(user_info, program_id),
+            system_program: system_program::ID,
+        },
+        chill_staking::instruction::RedistributeUnspentReward,
+    )
+}
+
+/// Accounts are user infos, each one optionally followed by its referral
+/// info, and the redistribution of the staking
+pub fn reconcile(staking_info: Pubkey, accounts: &[Pubkey], program_id: Pubkey) -> Instruction {
+    let mut ix = instruction(
+        program_id,
+        chill_staking::accounts::Reconcile { staking_info },
+        chill_staking::instruction::Reconcile,
+    );
+
+    ix.accounts.extend(
+        accounts
+            .iter()
+            .map(|pubkey| AccountMeta::new_readonly(*pubkey, false)),
+    );
+
+    ix
+}
+
+pub fn close_user_info(
+    user: Pubkey,
+    staking_info: Pubkey,
+    recipient: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_staking::accounts::CloseUserInfo {
+            user,
+            user_info: pda::user_info(staking_info, user, program_id),
+            recipient,
+        },
+        chill_staking::instruction::CloseUserInfo,
+    )
+}
+
+pub fn close_staking_accounts_batch(
+    primary_wallet: Pubkey,
+    staking_info: Pubkey,
+    treasury: Pubkey,
+    user_infos: &[Pubkey],
+    program_id: Pubkey,
+) -> Instruction {
+    let mut ix = instruction(
+        program_id,
+        chill_staking::accounts::CloseStakingAccountsBatch {
+            primary_wallet,
+            staking_info,
+            treasury,
+        },
+        chill_staking::instruction::CloseStakingAccountsBatch,
+    );
+
+    ix.accounts.extend(
+        user_infos
+            .iter()
+            .map(|pubkey| AccountMeta::new(*pubkey, false)),
+    );
+
+    ix
+}
+
+pub fn close_staking_info(
+    primary_wallet: Pubkey,
+    staking_info: Pubkey,
+    recipient: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_staking::accounts::CloseStakingInfo {
+            primary_wallet,
+            staking_info,
+            recipient,
+        },
+        chill_staking::instruction::CloseStakingInfo,
+    )
+}
+
+pub fn initialize_staking(
+    primary_wallet: Pubkey,
+    payer: Pubkey,
+    staking_info: Pubkey,
+    mint: Pubkey,
+    args: chill_staking::InitializeArgs,
+    program_id: Pubkey,
+) -> Instruction {
+    let staking_token_authority = pda::staking_token_authority(staking_info, program_id);
+
+    instruction(
+        program_id,
+        chill_staking::accounts::Initialize {
+            primary_wallet,
+            payer,
+            staking_info,
+            staking_token_authority,
+            staking_token_account: get_associated_token_address(&staking_token_authority, &mint),
+            mint,
+            system_program: system_program::ID,
+            rent: rent::ID,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+        },
+        chill_staking::instruction::Initialize { args },
+    )
+}
+
+/// The approval of the admin config is required, its signers are appended with
+/// `admin_signer_metas`
+pub fn add_reward_tokens(
+    primary_wallet: Pubkey,
+    token_account_authority: Pubkey,
+    token_account: Pubkey,
+    staking_info: Pubkey,
+    mint: Pubkey,
+    amount: u64,
+    program_id: Pubkey,
+) -> Instruction {
+    let staking_token_authority = pda::staking_token_authority(staking_info, program_id);
+
+    instruction(
+        program_id,
+        chill_staking::accounts::AddRewardTokens {
+            primary_wallet,
+            admin_config: pda::admin_config(primary_wallet, chill_nft::ID),
+            token_account_authority,
+            token_account,
+            staking_info,
+            staking_token_authority,
+            staking_token_account: get_associated_token_address(&staking_token_authority, &mint),
+            token_program: anchor_spl::token::ID,
+        },
+        chill_staking::instruction::AddRewardTokens { amount },
+    )
+}
+
+/// The approval of the admin config is required, its signers are appended with
+/// `admin_signer_metas`
+pub fn redeem_remaining_reward_tokens(
+    primary_wallet: Pubkey,
+    recipient_token_account: Pubkey,
+    staking_info: Pubkey,
+    mint: Pubkey,
+    amount: u64,
+    program_id: Pubkey,
+) -> Instruction {
+    let staking_token_authority = pda::staking_token_authority(staking_info, program_id);
+
+    instruction(
+        program_id,
+        chill_staking::accounts::RedeemRemainingRewardTokens {
+            primary_wallet,
+            admin_config: pda::admin_config(primary_wallet, chill_nft::ID),
+            staking_info,
+            staking_token_authority,
+            staking_token_account: get_associated_token_address(&staking_token_authority, &mint),
+            recipient_token_account,
+            token_program: anchor_spl::token::ID,
+        },
+        chill_staking::instruction::RedeemRemainingRewardTokens { amount },
+    )
+}
+
+pub fn transfer_reward_to_pending_amount(
+    user: Pubkey,
+    staking_info: Pubkey,
+    amount: u64,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_staking::accounts::UserUpdatesUserInfo {
+            user,
+            user_info: pda::user_info(staking_info, user, program_id),
+            staking_info,
+        },
+        chill_staking::instruction::TransferRewardToPendingAmount { amount },
+    )
+}
+
+/// A view returning `SolvencyReport`, it's meant to be simulated
+pub fn verify_pool_solvency(staking_info: Pubkey, mint: Pubkey, program_id: Pubkey) -> Instruction {
+    let staking_token_authority = pda::staking_token_authority(staking_info, program_id);
+
+    instruction(
+        program_id,
+        chill_staking::accounts::VerifyPoolSolvency {
+            staking_info,
+            staking_token_authority,
+            staking_token_account: get_associated_token_address(&staking_token_authority, &mint),
+        },
+        chill_staking::instruction::VerifyPoolSolvency,
+    )
+}
+
+/// A view returning the reward amount of the user, it's meant to be simulated
+pub fn view_user_reward_amount(
+    user_info: Pubkey,
+    staking_info: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_staking::accounts::ViewUserRewardAmount {
+            user_info,
+            staking_info,
+        },
+        chill_staking::instruction::ViewUserRewardAmount,
+    )
+}
+
+/// A view decoded by `view::decode_user_summary`, it's meant to be simulated
+pub fn view_user_summary(
+    user_info: Pubkey,
+    staking_info: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_staking::accounts::ViewUser {
+            user_info,
+            staking_info,
+        },
+        chill_staking::instruction::ViewUserSummary,
+    )
+}
+
+/// A view decoded by `view::decode_boost_calendar`, it's meant to be simulated
+pub fn view_boost_calendar(
+    user_info: Pubkey,
+    staking_info: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_staking::accounts::ViewUser {
+            user_info,
+            staking_info,
+        },
+        chill_staking::instruction::ViewBoostCalendar,
+    )
+}
+
+/// A view decoded by `view::decode_projected_reward`, it's meant to be
+/// simulated
+pub fn view_projected_reward(
+    user_info: Pubkey,
+    staking_info: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_staking::accounts::ViewUser {
+            user_info,
+            staking_info,
+        },
+        chill_staking::instruction::ViewProjectedReward,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn mint_nft(
+    primary_wallet: Pubkey,
+    payer: Pubkey,
+    chill_payer: Pubkey,
+    chill_payer_token_account: Pubkey,
+    chill_mint: Pubkey,
+    nft_mint: Pubkey,
+    nft_type: NftType,
+    args: NftArgs,
+    creator: Option<Pubkey>,
+    min_game_version: u32,
+    recipients_token_accounts: &[Pubkey],
+    collection: Option<Pubkey>,
+    uses: Option<Uses>,
+    program_id: Pubkey,
+) -> Instruction {
+    let config = pda::config(chill_mint, program_id);
+    let mut ix = instruction(
+        program_id,
+        chill_nft::accounts::MintNft {
+            primary_wallet,
+            payer,
+            chill_payer,
+            chill_payer_token_account,
+            config,
+            chill_mint,
+            nft_type_registry: pda::nft_type_registry(config, program_id),
+            nft_mint,
+            nft_metadata: pda::metadata(nft_mint),
+            nft_master_edition: pda::master_edition(nft_mint),
+            nft_chill_metadata: pda::chill_metadata(nft_mint, program_id),
+            rent: rent::ID,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            token_metadata_program: mpl_token_metadata::ID,
+        },
+        chill_nft::instruction::MintNft {
+            nft_type,
+            args,
+            creator,
+            min_game_version,
+            collection,
+            uses,
+        },
+    );
+
+    ix.accounts.extend(
+        recipients_token_accounts
+            .iter()
+            .map(|pubkey| AccountMeta::new(*pubkey, false)),
+    );
+
+    if let Some(collection) = collection {
+        ix.accounts.extend(collection_metas(collection));
+    }
+
+    ix
+}
+
+/// Every NFT mint must have a single token minted and the primary wallet as
+/// its mint authority
+#[allow(clippy::too_many_arguments)]
+pub fn mint_nft_batch(
+    primary_wallet: Pubkey,
+    payer: Pubkey,
+    chill_payer: Pubkey,
+    chill_payer_token_account: Pubkey,
+    chill_mint: Pubkey,
+    nft_mints: &[Pubkey],
+    nfts: Vec<BatchNft>,
+    recipients_token_accounts: &[Pubkey],
+    collection: Option<Pubkey>,
+    program_id: Pubkey,
+) -> Instruction {
+    let config = pda::config(chill_mint, program_id);
+    let mut ix = instruction(
+        program_id,
+        chill_nft::accounts::MintNftBatch {
+            primary_wallet,
+            payer,
+            chill_payer,
+            chill_payer_token_account,
+            config,
+            chill_mint,
+            nft_type_registry: pda::nft_type_registry(config, program_id),
+            rent: rent::ID,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            token_metadata_program: mpl_token_metadata::ID,
+        },
+        chill_nft::instruction::MintNftBatch { nfts, collection },
+    );
+
+    ix.accounts.extend(
+        recipients_token_accounts
+            .iter()
+            .map(|pubkey| AccountMeta::new(*pubkey, false)),
+    );
+
+    for nft_mint in nft_mints {
+        ix.accounts.extend([
+            AccountMeta::new(*nft_mint, false),
+            AccountMeta::new(pda::metadata(*nft_mint), false),
+            AccountMeta::new(pda::master_edition(*nft_mint), false),
+            AccountMeta::new(pda::chill_metadata(*nft_mint, program_id), false),
+        ]);
+    }
+
+    if let Some(collection) = collection {
+        ix.accounts.extend(collection_metas(collection));
+    }
+
+    ix
+}
+
+/// Collection accounts passed to `mint_nft` after the recipients token accounts
+/// and to `mint_nft_batch` after the accounts of the NFTs
+pub fn collection_metas(collection_mint: Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new_readonly(collection_mint, false),
+        AccountMeta::new_readonly(pda::metadata(collection_mint), false),
+        AccountMeta::new_readonly(pda::master_edition(collection_mint), false),
+    ]
+}
+
+pub fn create_collection(
+    primary_wallet: Pubkey,
+    payer: Pubkey,
+    chill_mint: Pubkey,
+    collection_mint: Pubkey,
+    args: NftArgs,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_nft::accounts::CreateCollection {
+            primary_wallet,
+            payer,
+            config: pda::config(chill_mint, program_id),
+            collection_mint,
+            collection_metadata: pda::metadata(collection_mint),
+            collection_master_edition: pda::master_edition(collection_mint),
+            rent: rent::ID,
+            system_program: system_program::ID,
+            token_metadata_program: mpl_token_metadata::ID,
+        },
+        chill_nft::instruction::CreateCollection { args },
+    )
+}
+
+pub fn initialize_admin_config(
+    primary_wallet: Pubkey,
+    payer: Pubkey,
+    signers: Vec<Pubkey>,
+    threshold: u8,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_nft::accounts::InitializeAdminConfig {
+            primary_wallet,
+            payer,
+            admin_config: pda::admin_config(primary_wallet, program_id),
+            system_program: system_program::ID,
+        },
+        chill_nft::instruction::InitializeAdminConfig { signers, threshold },
+    )
+}
+
+/// The update is approved by `approving_signers` of the current admin config
+pub fn update_admin_config(
+    primary_wallet: Pubkey,
+    approving_signers: &[Pubkey],
+    signers: Vec<Pubkey>,
+    threshold: u8,
+    program_id: Pubkey,
+) -> Instruction {
+    let mut ix = instruction(
+        program_id,
+        chill_nft::accounts::UpdateAdminConfig {
+            admin_config: pda::admin_config(primary_wallet, program_id),
+        },
+        chill_nft::instruction::UpdateAdminConfig { signers, threshold },
+    );
+
+    ix.accounts.extend(admin_signer_metas(approving_signers));
+    ix
+}
+
+/// Signers of the admin config passed as remaining accounts of instructions
+/// which require their approval
+pub fn admin_signer_metas(signers: &[Pubkey]) -> Vec<AccountMeta> {
+    signers
+        .iter()
+        .map(|signer| AccountMeta::new_readonly(*signer, true))
+        .collect()
+}
+
+/// The approval of the admin config is required, its signers are appended with
+/// `admin_signer_metas`
+pub fn update_fees(
+    primary_wallet: Pubkey,
+    chill_mint: Pubkey,
+    fees: Fees,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_nft::accounts::UpdateConfig {
+            primary_wallet,
+            admin_config: pda::admin_config(primary_wallet, program_id),
+            config: pda::config(chill_mint, program_id),
+            chill_mint,
+        },
+        chill_nft::instruction::UpdateFees { fees },
+    )
+}
+
+/// The approval of the admin config is required, its signers are appended with
+/// `admin_signer_metas`
+pub fn register_nft_type(
+    primary_wallet: Pubkey,
+    payer: Pubkey,
+    chill_mint: Pubkey,
+    name: String,
+    fee: u64,
+    program_id: Pubkey,
+) -> Instruction {
+    let config = pda::config(chill_mint, program_id);
+    instruction(
+        program_id,
+        chill_nft::accounts::RegisterNftType {
+            primary_wallet,
+            admin_config: pda::admin_config(primary_wallet, program_id),
+            payer,
+            config,
+            chill_mint,
+            nft_type_registry: pda::nft_type_registry(config, program_id),
+            system_program: system_program::ID,
+        },
+        chill_nft::instruction::RegisterNftType { name, fee },
+    )
+}
+
+/// The approval of the admin config is required, its signers are appended with
+/// `admin_signer_metas`
+pub fn update_recipients(
+    primary_wallet: Pubkey,
+    chill_mint: Pubkey,
+    recipients: Vec<Recipient>,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_nft::accounts::UpdateConfig {
+            primary_wallet,
+            admin_config: pda::admin_config(primary_wallet, program_id),
+            config: pda::config(chill_mint, program_id),
+            chill_mint,
+        },
+        chill_nft::instruction::UpdateRecipients { recipients },
+    )
+}
+
+pub fn propose_config_primary_wallet(
+    primary_wallet: Pubkey,
+    chill_mint: Pubkey,
+    new_primary_wallet: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    let config = pda::config(chill_mint, program_id);
+
+    instruction(
+        program_id,
+        chill_nft::accounts::ProposeNewPrimaryWallet {
+            primary_wallet,
+            config,
+            primary_wallet_transfer: pda::primary_wallet_transfer(config, program_id),
+            system_program: system_program::ID,
+        },
+        chill_nft::instruction::ProposeNewPrimaryWallet { new_primary_wallet },
+    )
+}
+
+pub fn accept_config_primary_wallet(
+    new_primary_wallet: Pubkey,
+    primary_wallet: Pubkey,
+    chill_mint: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    let config = pda::config(chill_mint, program_id);
+
+    instruction(
+        program_id,
+        chill_nft::accounts::AcceptPrimaryWallet {
+            new_primary_wallet,
+            primary_wallet,
+            config,
+            primary_wallet_transfer: pda::primary_wallet_transfer(config, program_id),
+        },
+        chill_nft::instruction::AcceptPrimaryWallet,
+    )
+}
+
+pub fn upgrade_nft_attributes(
+    primary_wallet: Pubkey,
+    nft_mint: Pubkey,
+    attributes: NftAttributes,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_nft::accounts::UpgradeNftAttributes {
+            primary_wallet,
+            nft_metadata: pda::metadata(nft_mint),
+            nft_chill_metadata: pda::chill_metadata(nft_mint, program_id),
+        },
+        chill_nft::instruction::UpgradeNftAttributes { attributes },
+    )
+}
+
+/// The config of the CHILL mint which minted the NFT gets the rent of the
+/// migrated chill metadata
+pub fn migrate_nft_metadata(
+    payer: Pubkey,
+    chill_mint: Pubkey,
+    nft_mint: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_nft::accounts::MigrateNftMetadata {
+            payer,
+            config: pda::config(chill_mint, program_id),
+            nft_mint,
+            nft_metadata: pda::metadata(nft_mint),
+            nft_chill_metadata: pda::chill_metadata(nft_mint, program_id),
+            system_program: system_program::ID,
+        },
+        chill_nft::instruction::MigrateNftMetadata,
+    )
+}
+
+pub fn utilize_nft(
+    owner: Pubkey,
+    nft_mint: Pubkey,
+    nft_token_account: Pubkey,
+    number_of_uses: u64,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_nft::accounts::UtilizeNft {
+            owner,
+            nft_mint,
+            nft_token_account,
+            nft_metadata: pda::metadata(nft_mint),
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+            token_metadata_program: mpl_token_metadata::ID,
+        },
+        chill_nft::instruction::UtilizeNft { number_of_uses },
+    )
+}
+
+/// `collection_mint` is required for verified members of a collection
+pub fn burn_nft(
+    owner: Pubkey,
+    nft_mint: Pubkey,
+    nft_token_account: Pubkey,
+    rent_receiver: Pubkey,
+    collection_mint: Option<Pubkey>,
+    program_id: Pubkey,
+) -> Instruction {
+    let mut ix = instruction(
+        program_id,
+        chill_nft::accounts::BurnNft {
+            owner,
+            nft_mint,
+            nft_token_account,
+            nft_metadata: pda::metadata(nft_mint),
+            nft_master_edition: pda::master_edition(nft_mint),
+            nft_chill_metadata: pda::chill_metadata(nft_mint, program_id),
+            rent_receiver,
+            token_program: anchor_spl::token::ID,
+            token_metadata_program: mpl_token_metadata::ID,
+        },
+        chill_nft::instruction::BurnNft,
+    );
+
+    if let Some(collection_mint) = collection_mint {
+        ix.accounts
+            .push(AccountMeta::new(pda::metadata(collection_mint), false));
+    }
+
+    ix
+}
+
+pub fn list_nft(
+    primary_wallet: Pubkey,
+    chill_mint: Pubkey,
+    nft_mint: Pubkey,
+    nft_token_account: Pubkey,
+    price: u64,
+    program_id: Pubkey,
+) -> Instruction {
+    let nft_listing = pda::nft_listing(nft_mint, program_id);
+    instruction(
+        program_id,
+        chill_nft::accounts::ListNft {
+            primary_wallet,
+            config: pda::config(chill_mint, program_id),
+            nft_mint,
+            nft_token_account,
+            nft_listing,
+            nft_escrow: get_associated_token_address(&nft_listing, &nft_mint),
+            rent: rent::ID,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+        },
+        chill_nft::instruction::ListNft { price },
+    )
+}
+
+pub fn delist_nft(
+    seller: Pubkey,
+    nft_mint: Pubkey,
+    nft_token_account: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    let nft_listing = pda::nft_listing(nft_mint, program_id);
+    instruction(
+        program_id,
+        chill_nft::accounts::DelistNft {
+            seller,
+            nft_token_account,
+            nft_listing,
+            nft_escrow: get_associated_token_address(&nft_listing, &nft_mint),
+            token_program: anchor_spl::token::ID,
+        },
+        chill_nft::instruction::DelistNft,
+    )
+}
+
+/// The NFT is received by the associated token account of the buyer, which is
+/// created if needed
+#[allow(clippy::too_many_arguments)]
+pub fn buy_nft(
+    buyer: Pubkey,
+    buyer_chill_token_account: Pubkey,
+    chill_mint: Pubkey,
+    seller: Pubkey,
+    nft_mint: Pubkey,
+    price: u64,
+    recipients_token_accounts: &[Pubkey],
+    program_id: Pubkey,
+) -> Instruction {
+    let nft_listing = pda::nft_listing(nft_mint, program_id);
+    let mut ix = instruction(
+        program_id,
+        chill_nft::accounts::BuyNft {
+            buyer,
+            buyer_chill_token_account,
+            buyer_nft_token_account: get_associated_token_address(&buyer, &nft_mint),
+            config: pda::config(chill_mint, program_id),
+            chill_mint,
+            seller,
+            nft_mint,
+            nft_listing,
+            nft_escrow: get_associated_token_address(&nft_listing, &nft_mint),
+            rent: rent::ID,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+        },
+        chill_nft::instruction::BuyNft { price },
+    );
+
+    ix.accounts.extend(
+        recipients_token_accounts
+            .iter()
+            .map(|pubkey| AccountMeta::new(*pubkey, false)),
+    );
+
+    ix
+}
+
+pub fn burn_chill_for_action(
+    owner: Pubkey,
+    owner_token_account: Pubkey,
+    chill_mint: Pubkey,
+    amount: u64,
+    action_id: u64,
+    memo: String,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_nft::accounts::BurnChillForAction {
+            owner,
+            config: pda::config(chill_mint, program_id),
+            chill_mint,
+            owner_token_account,
+            token_program: anchor_spl::token::ID,
+        },
+        chill_nft::instruction::BurnChillForAction {
+            amount,
+            action_id,
+            memo,
+        },
+    )
+}
+
+pub fn reclaim_metadata(
+    nft_mint: Pubkey,
+    rent_receiver: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_nft::accounts::ReclaimMetadata {
+            nft_mint,
+            nft_chill_metadata: pda::chill_metadata(nft_mint, program_id),
+            rent_receiver,
+        },
+        chill_nft::instruction::ReclaimMetadata,
+    )
+}
+
+pub fn create_wallet(
+    payer: Pubkey,
+    user: Pubkey,
+    primary_wallet: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_wallet::accounts::CreateWallet {
+            primary_wallet,
+            user,
+            payer,
+            proxy_wallet: pda::proxy_wallet(user, primary_wallet, program_id),
+            system_program: system_program::ID,
+        },
+        chill_wallet::instruction::CreateWallet,
+    )
+}
+
+/// Creates proxy wallets of the users at once. The approval of the admin
+/// config is required
+pub fn create_wallets(
+    payer: Pubkey,
+    primary_wallet: Pubkey,
+    admin_signers: &[Pubkey],
+    users: &[Pubkey],
+    program_id: Pubkey,
+) -> Instruction {
+    let mut ix = instruction(
+        program_id,
+        chill_wallet::accounts::CreateWallets {
+            primary_wallet,
+            admin_config: pda::admin_config(primary_wallet, chill_nft::ID),
+            payer,
+            system_program: system_program::ID,
+        },
+        chill_wallet::instruction::CreateWallets,
+    );
+
+    ix.accounts.extend(admin_signer_metas(admin_signers));
+
+    for user in users {
+        let proxy_wallet = pda::proxy_wallet(*user, primary_wallet, program_id);
+        ix.accounts.extend([
+            AccountMeta::new_readonly(*user, false),
+            AccountMeta::new(proxy_wallet, false),
+        ]);
+    }
+
+    ix
+}
+
+pub fn migrate_wallet(
+    payer: Pubkey,
+    user: Pubkey,
+    primary_wallet: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_wallet::accounts::MigrateWallet {
+            payer,
+            primary_wallet,
+            user,
+            proxy_wallet: pda::proxy_wallet(user, primary_wallet, program_id),
+            system_program: system_program::ID,
+        },
+        chill_wallet::instruction::MigrateWallet,
+    )
+}
+
+pub fn deposit_lamports(
+    depositor: Pubkey,
+    proxy_wallet: Pubkey,
+    amount: u64,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_wallet::accounts::DepositLamports {
+            depositor,
+            proxy_wallet,
+            system_program: system_program::ID,
+        },
+        chill_wallet::instruction::DepositLamports { amount },
+    )
+}
+
+pub fn deposit_ft(
+    depositor: Pubkey,
+    proxy_wallet: Pubkey,
+    mint: Pubkey,
+    amount: u64,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_wallet::accounts::DepositFt {
+            depositor,
+            mint,
+            depositor_token_account: get_associated_token_address(&depositor, &mint),
+            proxy_wallet,
+            proxy_wallet_token_account: get_associated_token_address(&proxy_wallet, &mint),
+            token_program: anchor_spl::token::ID,
+        },
+        chill_wallet::instruction::DepositFt { amount },
+    )
+}
+
+/// The approval of the admin config is required, its signers are appended with
+/// `admin_signer_metas`
+pub fn set_spending_limits(
+    primary_wallet: Pubkey,
+    proxy_wallet: Pubkey,
+    user: DailyLimits,
+    primary_wallet_limits: DailyLimits,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_wallet::accounts::SetSpendingLimits {
+            primary_wallet,
+            admin_config: pda::admin_config(primary_wallet, chill_nft::ID),
+            proxy_wallet,
+        },
+        chill_wallet::instruction::SetSpendingLimits {
+            user,
+            primary_wallet: primary_wallet_limits,
+        },
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_session_key(
+    user: Pubkey,
+    proxy_wallet: Pubkey,
+    session_signer: Pubkey,
+    expires_at_slot: u64,
+    permissions: u8,
+    lamports_allowance: u64,
+    ft_allowance: u64,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_wallet::accounts::CreateSessionKey {
+            user,
+            proxy_wallet,
+            session_signer,
+            session_key: pda::session_key(proxy_wallet, session_signer, program_id),
+            system_program: system_program::ID,
+        },
+        chill_wallet::instruction::CreateSessionKey {
+            expires_at_slot,
+            permissions,
+            lamports_allowance,
+            ft_allowance,
+        },
+    )
+}
+
+pub fn revoke_session_key(
+    user: Pubkey,
+    proxy_wallet: Pubkey,
+    session_signer: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_wallet::accounts::RevokeSessionKey {
+            user,
+            proxy_wallet,
+            session_key: pda::session_key(proxy_wallet, session_signer, program_id),
+        },
+        chill_wallet::instruction::RevokeSessionKey,
+    )
+}
+
+/// Lets a session signer run `withdraw_lamports`, `withdraw_ft` and
+/// `withdraw_nft` when appended to their accounts
+pub fn session_key_meta(
+    proxy_wallet: Pubkey,
+    session_signer: Pubkey,
+    program_id: Pubkey,
+) -> AccountMeta {
+    AccountMeta::new(
+        pda::session_key(proxy_wallet, session_signer, program_id),
+        false,
+    )
+}
+
+/// Lets a wallet which takes a withdrawal fee run `withdraw_lamports` and
+/// `execute_due_withdrawal` when appended to their accounts, after the session
+/// key meta if any
+pub fn fee_receiver_meta(fee_receiver: Pubkey) -> AccountMeta {
+    AccountMeta::new(fee_receiver, false)
+}
+
+/// Same as `fee_receiver_meta` for `withdraw_ft`, the fee is sent to the
+/// associated token account of the fee receiver
+pub fn fee_token_account_meta(fee_receiver: Pubkey, mint: Pubkey) -> AccountMeta {
+    AccountMeta::new(get_associated_token_address(&fee_receiver, &mint), false)
+}
+
+/// The approval of the admin config is required, its signers are appended with
+/// `admin_signer_metas`
+pub fn set_withdrawal_fee(
+    primary_wallet: Pubkey,
+    proxy_wallet: Pubkey,
+    fee_bps: u16,
+    fee_receiver: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_wallet::accounts::SetWithdrawalFee {
+            primary_wallet,
+            admin_config: pda::admin_config(primary_wallet, chill_nft::ID),
+            proxy_wallet,
+        },
+        chill_wallet::instruction::SetWithdrawalFee {
+            fee_bps,
+            fee_receiver,
+        },
+    )
+}
+
+/// A view decoded by `view::decode_withdrawal_log`, it's meant to be
+/// simulated
+pub fn view_withdrawal_log(proxy_wallet: Pubkey, program_id: Pubkey) -> Instruction {
+    instruction(
+        program_id,
+        chill_wallet::accounts::ViewWallet { proxy_wallet },
+        chill_wallet::instruction::ViewWithdrawalLog,
+    )
+}
+
+pub fn freeze_wallet(
+    primary_wallet: Pubkey,
+    proxy_wallet: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_wallet::accounts::FreezeWallet {
+            primary_wallet,
+            proxy_wallet,
+        },
+        chill_wallet::instruction::FreezeWallet,
+    )
+}
+
+pub fn unfreeze_wallet(
+    primary_wallet: Pubkey,
+    proxy_wallet: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_wallet::accounts::FreezeWallet {
+            primary_wallet,
+            proxy_wallet,
+        },
+        chill_wallet::instruction::UnfreezeWallet,
+    )
+}
+
+pub fn withdraw_lamports(
+    authority: Pubkey,
+    proxy_wallet: Pubkey,
+    receiver: Pubkey,
+    amount: u64,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_wallet::accounts::WithdrawLamports {
+            authority,
+            proxy_wallet,
+            receiver,
+        },
+        chill_wallet::instruction::WithdrawLamports { amount },
+    )
+}
+
+pub fn withdraw_ft(
+    authority: Pubkey,
+    proxy_wallet: Pubkey,
+    mint: Pubkey,
+    receiver_token_account: Pubkey,
+    amount: u64,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_wallet::accounts::WithdrawFt {
+            authority,
+            proxy_wallet,
+            mint,
+            proxy_wallet_token_account: get_associated_token_address(&proxy_wallet, &mint),
+            receiver_token_account,
+            token_program: anchor_spl::token::ID,
+        },
+        chill_wallet::instruction::WithdrawFt { amount },
+    )
+}
+
+/// Withdraws whole balances of the associated token accounts of the proxy
+/// wallet for the mints to the associated token accounts of the receiver. The
+/// fee receiver is required if the wallet takes a withdrawal fee
+pub fn withdraw_ft_batch(
+    authority: Pubkey,
+    proxy_wallet: Pubkey,
+    receiver: Pubkey,
+    mints: &[Pubkey],
+    fee_receiver: Option<Pubkey>,
+    program_id: Pubkey,
+) -> Instruction {
+    let mut ix = instruction(
+        program_id,
+        chill_wallet::accounts::WithdrawFtBatch {
+            authority,
+            proxy_wallet,
+            token_program: anchor_spl::token::ID,
+        },
+        chill_wallet::instruction::WithdrawFtBatch,
+    );
+
+    for mint in mints {
+        ix.accounts.extend([
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(get_associated_token_address(&proxy_wallet, mint), false),
+            AccountMeta::new(get_associated_token_address(&receiver, mint), false),
+        ]);
+
+        if let Some(fee_receiver) = fee_receiver {
+            ix.accounts
+                .push(fee_token_account_meta(fee_receiver, *mint));
+        }
+    }
+
+    ix
+}
+
+pub fn withdraw_nft(
+    authority: Pubkey,
+    proxy_wallet: Pubkey,
+    nft_mint: Pubkey,
+    receiver_token_account: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_wallet::accounts::WithdrawNft {
+            authority,
+            proxy_wallet,
+            nft_mint,
+            proxy_wallet_token_account: get_associated_token_address(&proxy_wallet, &nft_mint),
+            receiver_token_account,
+            token_program: anchor_spl::token::ID,
+        },
+        chill_wallet::instruction::WithdrawNft,
+    )
+}
+
+/// Withdraws the NFT by the primary wallet, marking its primary sale if it's
+/// the first one
+pub fn transfer_nft_with_update(
+    primary_wallet: Pubkey,
+    proxy_wallet: Pubkey,
+    nft_mint: Pubkey,
+    receiver_token_account: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_wallet::accounts::TransferNftWithUpdate {
+            primary_wallet,
+            proxy_wallet,
+            nft_mint,
+            nft_metadata: pda::metadata(nft_mint),
+            proxy_wallet_token_account: get_associated_token_address(&proxy_wallet, &nft_mint),
+            receiver_token_account,
+            token_program: anchor_spl::token::ID,
+            token_metadata_program: mpl_token_metadata::ID,
+        },
+        chill_wallet::instruction::TransferNftWithUpdate,
+    )
+}
+
+/// Wraps lamports of the proxy wallet into its associated wSOL token account,
+/// which must exist
+pub fn wrap_sol(
+    authority: Pubkey,
+    proxy_wallet: Pubkey,
+    amount: u64,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_wallet::accounts::WrapSol {
+            authority,
+            proxy_wallet,
+            proxy_wallet_token_account: get_associated_token_address(
+                &proxy_wallet,
+                &anchor_spl::token::spl_token::native_mint::ID,
+            ),
+            token_program: anchor_spl::token::ID,
+        },
+        chill_wallet::instruction::WrapSol { amount },
+    )
+}
+
+/// Closes the associated wSOL token account of the proxy wallet
+pub fn unwrap_sol(authority: Pubkey, proxy_wallet: Pubkey, program_id: Pubkey) -> Instruction {
+    instruction(
+        program_id,
+        chill_wallet::accounts::UnwrapSol {
+            authority,
+            proxy_wallet,
+            proxy_wallet_token_account: get_associated_token_address(
+                &proxy_wallet,
+                &anchor_spl::token::spl_token::native_mint::ID,
+            ),
+            token_program: anchor_spl::token::ID,
+        },
+        chill_wallet::instruction::UnwrapSol,
+    )
+}
+
+/// Stakes CHILL tokens of the associated token account of the proxy wallet
+/// for the user. The fee receiver is required if the wallet takes a withdrawal
+/// fee
+#[allow(clippy::too_many_arguments)]
+pub fn stake_from_wallet(
+    user: Pubkey,
+    payer: Pubkey,
+    proxy_wallet: Pubkey,
+    staking_info: Pubkey,
+    mint: Pubkey,
+    amount: u64,
+    fee_receiver: Option<Pubkey>,
+    program_id: Pubkey,
+) -> Instruction {
+    let staking_token_authority = pda::staking_token_authority(staking_info, chill_staking::ID);
+
+    let mut ix = instruction(
+        program_id,
+        chill_wallet::accounts::StakeFromWallet {
+            user,
+            payer,
+            proxy_wallet,
+            proxy_wallet_token_account: get_associated_token_address(&proxy_wallet, &mint),
+            staking_info,
+            user_info: pda::user_info(staking_info, user, chill_staking::ID),
+            staking_token_authority,
+            staking_token_account: get_associated_token_address(&staking_token_authority, &mint),
+            staking_program: chill_staking::ID,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+        },
+        chill_wallet::instruction::StakeFromWallet { amount },
+    );
+
+    if let Some(fee_receiver) = fee_receiver {
+        ix.accounts.push(fee_token_account_meta(fee_receiver, mint));
+    }
+
+    ix
+}
+
+/// Mints an NFT paying the CHILL fee from the associated token account of the
+/// proxy wallet
+#[allow(clippy::too_many_arguments)]
+pub fn mint_nft_from_wallet(
+    primary_wallet: Pubkey,
+    payer: Pubkey,
+    proxy_wallet: Pubkey,
+    chill_mint: Pubkey,
+    nft_mint: Pubkey,
+    nft_type: NftType,
+    args: NftArgs,
+    creator: Option<Pubkey>,
+    min_game_version: u32,
+    recipients_token_accounts: &[Pubkey],
+    collection: Option<Pubkey>,
+    uses: Option<Uses>,
+    program_id: Pubkey,
+) -> Instruction {
+    let proxy_wallet_token_account = get_associated_token_address(&proxy_wallet, &chill_mint);
+    let nft_ix = mint_nft(
+        primary_wallet,
+        payer,
+        proxy_wallet,
+        proxy_wallet_token_account,
+        chill_mint,
+        nft_mint,
+        nft_type,
+        args,
+        creator,
+        min_game_version,
+        recipients_token_accounts,
+        collection,
+        uses,
+        chill_nft::ID,
+    );
+
+    let config = pda::config(chill_mint, chill_nft::ID);
+    let mut ix = instruction(
+        program_id,
+        chill_wallet::accounts::MintNftFromWallet {
+            primary_wallet,
+            payer,
+            proxy_wallet,
+            proxy_wallet_token_account,
+            config,
+            chill_mint,
+            nft_type_registry: pda::nft_type_registry(config, chill_nft::ID),
+            nft_mint,
+            nft_metadata: pda::metadata(nft_mint),
+            nft_master_edition: pda::master_edition(nft_mint),
+            nft_chill_metadata: pda::chill_metadata(nft_mint, chill_nft::ID),
+            rent: rent::ID,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            token_metadata_program: mpl_token_metadata::ID,
+            nft_program: chill_nft::ID,
+        },
+        chill_wallet::instruction::MintNftFromWallet { data: nft_ix.data },
+    );
+
+    // Recipients and collection accounts follow the accounts of `mint_nft`,
+    // which are the same as the wallet ones without the nft program
+    let mint_nft_accounts_number = ix.accounts.len() - 1;
+    ix.accounts
+        .extend(nft_ix.accounts.into_iter().skip(mint_nft_accounts_number));
+
+    ix
+}
+
+pub fn lend_nft(
+    authority: Pubkey,
+    proxy_wallet: Pubkey,
+    nft_mint: Pubkey,
+    price: u64,
+    duration: u64,
+    program_id: Pubkey,
+) -> Instruction {
+    let rental = pda::nft_rental(nft_mint, program_id);
+    instruction(
+        program_id,
+        chill_wallet::accounts::LendNft {
+            authority,
+            proxy_wallet,
+            nft_mint,
+            proxy_wallet_token_account: get_associated_token_address(&proxy_wallet, &nft_mint),
+            rental,
+            rental_escrow: get_associated_token_address(&rental, &nft_mint),
+            receipt: pda::rental_receipt(rental, program_id),
+            rent: rent::ID,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+        },
+        chill_wallet::instruction::LendNft { price, duration },
+    )
+}
+
+pub fn rent_nft(
+    renter: Pubkey,
+    proxy_wallet: Pubkey,
+    nft_mint: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    let rental = pda::nft_rental(nft_mint, program_id);
+    instruction(
+        program_id,
+        chill_wallet::accounts::RentNft {
+            renter,
+            proxy_wallet,
+            rental,
+            receipt: pda::rental_receipt(rental, program_id),
+            system_program: system_program::ID,
+        },
+        chill_wallet::instruction::RentNft,
+    )
+}
+
+pub fn reclaim_nft(
+    authority: Pubkey,
+    proxy_wallet: Pubkey,
+    nft_mint: Pubkey,
+    rent_receiver: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    let rental = pda::nft_rental(nft_mint, program_id);
+    instruction(
+        program_id,
+        chill_wallet::accounts::ReclaimNft {
+            authority,
+            proxy_wallet,
+            proxy_wallet_token_account: get_associated_token_address(&proxy_wallet, &nft_mint),
+            rental,
+            rental_escrow: get_associated_token_address(&rental, &nft_mint),
+            receipt: pda::rental_receipt(rental, program_id),
+            rent_receiver,
+            token_program: anchor_spl::token::ID,
+        },
+        chill_wallet::instruction::ReclaimNft,
+    )
+}
+
+pub fn schedule_withdrawal(
+    authority: Pubkey,
+    proxy_wallet: Pubkey,
+    receiver: Pubkey,
+    amount: u64,
+    interval: u64,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_wallet::accounts::ScheduleWithdrawal {
+            authority,
+            proxy_wallet,
+            receiver,
+            schedule: pda::withdrawal_schedule(proxy_wallet, receiver, program_id),
+            system_program: system_program::ID,
+        },
+        chill_wallet::instruction::ScheduleWithdrawal { amount, interval },
+    )
+}
+
+pub fn execute_due_withdrawal(
+    proxy_wallet: Pubkey,
+    receiver: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_wallet::accounts::ExecuteDueWithdrawal {
+            proxy_wallet,
+            schedule: pda::withdrawal_schedule(proxy_wallet, receiver, program_id),
+            receiver,
+        },
+        chill_wallet::instruction::ExecuteDueWithdrawal,
+    )
+}
+
+/// The rent of the schedule is returned to its authority
+pub fn cancel_scheduled_withdrawal(
+    authority: Pubkey,
+    proxy_wallet: Pubkey,
+    receiver: Pubkey,
+    schedule_authority: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_wallet::accounts::CancelScheduledWithdrawal {
+            authority,
+            proxy_wallet,
+            schedule: pda::withdrawal_schedule(proxy_wallet, receiver, program_id),
+            schedule_authority,
+        },
+        chill_wallet::instruction::CancelScheduledWithdrawal,
+    )
+}
+
+pub fn initialize_token_drop(
+    authority: Pubkey,
+    payer: Pubkey,
+    mint: Pubkey,
+    authority_token_account: Pubkey,
+    merkle_root: [u8; 32],
+    total_amount: u64,
+    program_id: Pubkey,
+) -> Instruction {
+    let distributor = pda::distributor(authority, merkle_root, program_id);
+    instruction(
+        program_id,
+        chill_drop::accounts::InitializeTokenDrop {
+            authority,
+            payer,
+            distributor,
+            mint,
+            authority_token_account,
+            vault: get_associated_token_address(&distributor, &mint),
+            rent: rent::ID,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+        },
+        chill_drop::instruction::InitializeTokenDrop {
+            merkle_root,
+            total_amount,
+        },
+    )
+}
+
+pub fn initialize_whitelist_drop(
+    authority: Pubkey,
+    payer: Pubkey,
+    mint: Pubkey,
+    merkle_root: [u8; 32],
+    total_slots: u64,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_drop::accounts::InitializeWhitelistDrop {
+            authority,
+            payer,
+            distributor: pda::distributor(authority, merkle_root, program_id),
+            mint,
+            system_program: system_program::ID,
+        },
+        chill_drop::instruction::InitializeWhitelistDrop {
+            merkle_root,
+            total_slots,
+        },
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn claim_drop(
+    claimant: Pubkey,
+    payer: Pubkey,
+    distributor: Pubkey,
+    mint: Pubkey,
+    claimant_token_account: Pubkey,
+    index: u64,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_drop::accounts::Claim {
+            claimant,
+            payer,
+            distributor,
+            claim_status: pda::claim_status(distributor, index, program_id),
+            vault: get_associated_token_address(&distributor, &mint),
+            claimant_token_account,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+        },
+        chill_drop::instruction::Claim {
+            index,
+            amount,
+            proof,
+        },
+    )
+}
+
+pub fn claim_slots(
+    claimant: Pubkey,
+    payer: Pubkey,
+    distributor: Pubkey,
+    index: u64,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_drop::accounts::ClaimSlots {
+            claimant,
+            payer,
+            distributor,
+            claim_status: pda::claim_status(distributor, index, program_id),
+            system_program: system_program::ID,
+        },
+        chill_drop::instruction::ClaimSlots {
+            index,
+            amount,
+            proof,
+        },
+    )
+}
+
+pub fn withdraw_unclaimed(
+    authority: Pubkey,
+    distributor: Pubkey,
+    mint: Pubkey,
+    recipient_token_account: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_drop::accounts::WithdrawUnclaimed {
+            authority,
+            distributor,
+            vault: get_associated_token_address(&distributor, &mint),
+            recipient_token_account,
+            token_program: anchor_spl::token::ID,
+        },
+        chill_drop::instruction::WithdrawUnclaimed,
+    )
+}
+
+pub fn initialize_vesting(
+    authority: Pubkey,
+    payer: Pubkey,
+    beneficiary: Pubkey,
+    mint: Pubkey,
+    authority_token_account: Pubkey,
+    args: InitializeVestingArgs,
+    program_id: Pubkey,
+) -> Instruction {
+    let vesting = pda::vesting(authority, beneficiary, mint, program_id);
+    instruction(
+        program_id,
+        chill_vesting::accounts::InitializeVesting {
+            authority,
+            payer,
+            beneficiary,
+            vesting,
+            mint,
+            authority_token_account,
+            vault: get_associated_token_address(&vesting, &mint),
+            rent: rent::ID,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+        },
+        chill_vesting::instruction::InitializeVesting { args },
+    )
+}
+
+pub fn claim_vested(
+    beneficiary: Pubkey,
+    vesting: Pubkey,
+    mint: Pubkey,
+    beneficiary_token_account: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_vesting::accounts::ClaimVested {
+            beneficiary,
+            vesting,
+            vault: get_associated_token_address(&vesting, &mint),
+            beneficiary_token_account,
+            token_program: anchor_spl::token::ID,
+        },
+        chill_vesting::instruction::ClaimVested,
+    )
+}
+
+pub fn revoke_vesting(
+    authority: Pubkey,
+    vesting: Pubkey,
+    mint: Pubkey,
+    treasury_token_account: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_vesting::accounts::Revoke {
+            authority,
+            vesting,
+            vault: get_associated_token_address(&vesting, &mint),
+            treasury_token_account,
+            token_program: anchor_spl::token::ID,
+        },
+        chill_vesting::instruction::Revoke,
+    )
+}
+
+pub fn initialize_governance(
+    primary_wallet: Pubkey,
+    payer: Pubkey,
+    staking_info: Pubkey,
+    quorum: u64,
+    voting_duration: u64,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_governance::accounts::InitializeGovernance {
+            primary_wallet,
+            payer,
+            staking_info,
+            governance: pda::governance(staking_info, program_id),
+            system_program: system_program::ID,
+        },
+        chill_governance::instruction::InitializeGovernance {
+            quorum,
+            voting_duration,
+        },
+    )
+}
+
+pub fn update_governance(
+    authority: Pubkey,
+    governance: Pubkey,
+    quorum: u64,
+    voting_duration: u64,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_governance::accounts::UpdateGovernance {
+            authority,
+            governance,
+        },
+        chill_governance::instruction::UpdateGovernance {
+            quorum,
+            voting_duration,
+        },
+    )
+}
+
+/// The index of the proposal is the number of proposals of the governance
+#[allow(clippy::too_many_arguments)]
+pub fn create_proposal(
+    proposer: Pubkey,
+    payer: Pubkey,
+    staking_info: Pubkey,
+    index: u64,
+    title: String,
+    uri: String,
+    staking_program_id: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    let governance = pda::governance(staking_info, program_id);
+    instruction(
+        program_id,
+        chill_governance::accounts::CreateProposal {
+            proposer,
+            payer,
+            governance,
+            staking_info,
+            user_info: pda::user_info(staking_info, proposer, staking_program_id),
+            proposal: pda::proposal(governance, index, program_id),
+            system_program: system_program::ID,
+        },
+        chill_governance::instruction::CreateProposal { title, uri },
+    )
+}
+
+pub fn vote(
+    user: Pubkey,
+    payer: Pubkey,
+    staking_info: Pubkey,
+    proposal: Pubkey,
+    approve: bool,
+    staking_program_id: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    instruction(
+        program_id,
+        chill_governance::accounts::Vote {
+            user,
+            payer,
+            governance: pda::governance(staking_info, program_id),
+            proposal,
+            user_info: pda::user_info(staking_info, user, staking_program_id),
+            vote_record: pda::vote_record(proposal, user, program_id),
+            system_program: system_program::ID,
+        },
+        chill_governance::instruction::Vote { approve },
+    )
+}
+
+pub fn finalize_proposal(proposal: Pubkey, program_id: Pubkey) -> Instruction {
+    instruction(
+        program_id,
+        chill_governance::accounts::Finalize { proposal },
+        chill_governance::instruction::Finalize,
+    )
+}
@@ -0,0 +1,5 @@
+pub mod instruction;
+pub mod merkle;
+pub mod pda;
+pub mod view;
+pub mod wasm;
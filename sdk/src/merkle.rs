@@ -0,0 +1,103 @@
+use anchor_lang::prelude::Pubkey;
+use chill_drop::utils::{leaf, node};
+
+/// Merkle tree of a drop, the leaf of a claim is built from its index, claimant
+/// and amount exactly as `chill_drop` does. A node without a sibling is moved
+/// up a layer as it is
+pub struct MerkleTree {
+    layers: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    pub fn new(claims: &[(Pubkey, u64)]) -> Self {
+        let leaves = claims
+            .iter()
+            .enumerate()
+            .map(|(index, (claimant, amount))| leaf(index as u64, *claimant, *amount))
+            .collect::<Vec<_>>();
+
+        let mut layers = vec![leaves];
+        while layers.last().unwrap().len() > 1 {
+            let layer = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => node(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            layers.push(layer);
+        }
+
+        Self { layers }
+    }
+
+    /// Root of an empty tree is zeroed
+    pub fn root(&self) -> [u8; 32] {
+        self.layers
+            .last()
+            .and_then(|layer| layer.first())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn proof(&self, index: usize) -> Vec<[u8; 32]> {
+        let mut index = index;
+        let mut proof = Vec::new();
+        for layer in &self.layers[..self.layers.len() - 1] {
+            if let Some(sibling) = layer.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+
+        proof
+    }
+}
+
+/// Hashes are stored and passed to JS as hex strings
+pub fn to_hex(hash: &[u8; 32]) -> String {
+    hash.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+pub fn from_hex(string: &str) -> Option<[u8; 32]> {
+    let string = string.strip_prefix("0x").unwrap_or(string);
+    if string.len() != 64 || !string.is_ascii() {
+        return None;
+    }
+
+    let mut hash = [0; 32];
+    for (byte, chunk) in hash.iter_mut().zip(string.as_bytes().chunks(2)) {
+        *byte = u8::from_str_radix(std::str::from_utf8(chunk).ok()?, 16).ok()?;
+    }
+
+    Some(hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chill_drop::utils::verify;
+
+    #[test]
+    fn proofs() {
+        for size in 1..10 {
+            let claims = (0..size)
+                .map(|amount| (Pubkey::new_unique(), amount))
+                .collect::<Vec<_>>();
+            let tree = MerkleTree::new(&claims);
+            let root = tree.root();
+
+            for (index, (claimant, amount)) in claims.iter().enumerate() {
+                let proof = tree.proof(index);
+                let index = index as u64;
+                assert!(verify(&proof, &root, leaf(index, *claimant, *amount)));
+                assert!(!verify(&proof, &root, leaf(index, *claimant, amount + 1)));
+            }
+
+            assert_eq!(from_hex(&to_hex(&root)), Some(root));
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use anchor_lang::prelude::Pubkey;
+use chill_drop::state::{ClaimStatus, Distributor};
+use chill_governance::state::{Governance, Proposal, VoteRecord};
+use chill_nft::state::{
+    AdminConfig, ChillNftMetadata, Config, ConfigRegistry, NftListing, NftTypeRegistry,
+};
+use chill_staking::state::{
+    DELEGATE_SEED, DEVNET_FAUCET_MINT_SEED, NFT_BONUS_SEED, NFT_MULTIPLIER_SEED, PAID_BOOST_SEED,
+    PRIMARY_WALLET_TRANSFER_SEED, REDISTRIBUTION_SEED, REFERRAL_SEED,
+};
+use chill_vesting::state::Vesting;
+use chill_wallet::state::{NftRental, ProxyWallet, RentalReceipt, SessionKey, WithdrawalSchedule};
+use mpl_token_metadata::state::{EDITION, PREFIX};
+
+pub fn staking_token_authority(staking_info: Pubkey, program_id: Pubkey) -> Pubkey {
+    let seeds = &[staking_info.as_ref()];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn user_info(staking_info: Pubkey, user: Pubkey, program_id: Pubkey) -> Pubkey {
+    let seeds = &[staking_info.as_ref(), user.as_ref()];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn redistribution(staking_info: Pubkey, program_id: Pubkey) -> Pubkey {
+    let seeds = &[REDISTRIBUTION_SEED, staking_info.as_ref()];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn redistribution_receipt(user_info: Pubkey, program_id: Pubkey) -> Pubkey {
+    let seeds = &[REDISTRIBUTION_SEED, user_info.as_ref()];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn referral(user_info: Pubkey, program_id: Pubkey) -> Pubkey {
+    let seeds = &[REFERRAL_SEED, user_info.as_ref()];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn delegate_info(user_info: Pubkey, program_id: Pubkey) -> Pubkey {
+    let seeds = &[DELEGATE_SEED, user_info.as_ref()];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn nft_multiplier_info(staking_info: Pubkey, program_id: Pubkey) -> Pubkey {
+    let seeds = &[NFT_MULTIPLIER_SEED, staking_info.as_ref()];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn nft_bonus_info(staking_info: Pubkey, nft_mint: Pubkey, program_id: Pubkey) -> Pubkey {
+    let seeds = &[NFT_BONUS_SEED, staking_info.as_ref(), nft_mint.as_ref()];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn paid_boost_info(staking_info: Pubkey, program_id: Pubkey) -> Pubkey {
+    let seeds = &[PAID_BOOST_SEED, staking_info.as_ref()];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+/// Pending primary wallet of a config of the NFT program or of a staking
+pub fn primary_wallet_transfer(account: Pubkey, program_id: Pubkey) -> Pubkey {
+    let seeds = &[PRIMARY_WALLET_TRANSFER_SEED, account.as_ref()];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn devnet_faucet_mint(program_id: Pubkey) -> Pubkey {
+    let seeds = &[DEVNET_FAUCET_MINT_SEED];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn config(mint: Pubkey, program_id: Pubkey) -> Pubkey {
+    let seeds = &[Config::SEED, mint.as_ref()];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn config_registry(program_id: Pubkey) -> Pubkey {
+    let seeds = &[ConfigRegistry::SEED];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn nft_type_registry(config: Pubkey, program_id: Pubkey) -> Pubkey {
+    let seeds = &[NftTypeRegistry::SEED, config.as_ref()];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn admin_config(primary_wallet: Pubkey, program_id: Pubkey) -> Pubkey {
+    let seeds = &[AdminConfig::SEED, primary_wallet.as_ref()];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn chill_metadata(mint: Pubkey, program_id: Pubkey) -> Pubkey {
+    let seeds = &[ChillNftMetadata::SEED, mint.as_ref()];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn nft_listing(nft_mint: Pubkey, program_id: Pubkey) -> Pubkey {
+    let seeds = &[NftListing::SEED, nft_mint.as_ref()];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn proxy_wallet(user: Pubkey, primary_wallet: Pubkey, program_id: Pubkey) -> Pubkey {
+    let seeds = &[ProxyWallet::SEED, user.as_ref(), primary_wallet.as_ref()];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn withdrawal_schedule(proxy_wallet: Pubkey, receiver: Pubkey, program_id: Pubkey) -> Pubkey {
+    let seeds = &[
+        WithdrawalSchedule::SEED,
+        proxy_wallet.as_ref(),
+        receiver.as_ref(),
+    ];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn nft_rental(nft_mint: Pubkey, program_id: Pubkey) -> Pubkey {
+    let seeds = &[NftRental::SEED, nft_mint.as_ref()];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn rental_receipt(rental: Pubkey, program_id: Pubkey) -> Pubkey {
+    let seeds = &[RentalReceipt::SEED, rental.as_ref()];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn session_key(proxy_wallet: Pubkey, signer: Pubkey, program_id: Pubkey) -> Pubkey {
+    let seeds = &[SessionKey::SEED, proxy_wallet.as_ref(), signer.as_ref()];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn distributor(authority: Pubkey, merkle_root: [u8; 32], program_id: Pubkey) -> Pubkey {
+    let seeds = &[Distributor::SEED, authority.as_ref(), merkle_root.as_ref()];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn claim_status(distributor: Pubkey, index: u64, program_id: Pubkey) -> Pubkey {
+    let seeds = &[
+        ClaimStatus::SEED,
+        distributor.as_ref(),
+        &index.to_le_bytes(),
+    ];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn vesting(authority: Pubkey, beneficiary: Pubkey, mint: Pubkey, program_id: Pubkey) -> Pubkey {
+    let seeds = &[
+        Vesting::SEED,
+        authority.as_ref(),
+        beneficiary.as_ref(),
+        mint.as_ref(),
+    ];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn governance(staking_info: Pubkey, program_id: Pubkey) -> Pubkey {
+    let seeds = &[Governance::SEED, staking_info.as_ref()];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn proposal(governance: Pubkey, index: u64, program_id: Pubkey) -> Pubkey {
+    let seeds = &[Proposal::SEED, governance.as_ref(), &index.to_le_bytes()];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn vote_record(proposal: Pubkey, user: Pubkey, program_id: Pubkey) -> Pubkey {
+    let seeds = &[VoteRecord::SEED, proposal.as_ref(), user.as_ref()];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn metadata(mint: Pubkey) -> Pubkey {
+    let seeds = &[
+        PREFIX.as_bytes(),
+        mpl_token_metadata::ID.as_ref(),
+        mint.as_ref(),
+    ];
+
+    Pubkey::find_program_address(seeds, &mpl_token_metadata::ID).0
+}
+
+pub fn master_edition(mint: Pubkey) -> Pubkey {
+    let seeds = &[
+        PREFIX.as_bytes(),
+        mpl_token_metadata::ID.as_ref(),
+        mint.as_ref(),
+        EDITION.as_bytes(),
+    ];
+
+    Pubkey::find_program_address(seeds, &mpl_token_metadata::ID).0
+}
@@ -0,0 +1,143 @@
+use anchor_lang::{prelude::Pubkey, AnchorDeserialize};
+use chill_nft::state::NftAttributes;
+use chill_staking::{state::SEC_PER_DAY, BoostCalendar, ProjectedReward, UserSummary};
+use chill_wallet::state::WithdrawalLogEntry;
+use serde_json::{json, Value};
+
+pub use chill_events::event::nft_attributes_to_json;
+
+/// Decodes base64 encoded return data of the `view_boost_calendar` view
+pub fn decode_boost_calendar(data: &str) -> Option<BoostCalendar> {
+    let data = base64::decode(data).ok()?;
+    BoostCalendar::try_from_slice(&data).ok()
+}
+
+/// Decodes base64 encoded return data of the `view_user_summary` view
+pub fn decode_user_summary(data: &str) -> Option<UserSummary> {
+    let data = base64::decode(data).ok()?;
+    UserSummary::try_from_slice(&data).ok()
+}
+
+/// Decodes base64 encoded return data of the `view_projected_reward` view
+pub fn decode_projected_reward(data: &str) -> Option<ProjectedReward> {
+    let data = base64::decode(data).ok()?;
+    ProjectedReward::try_from_slice(&data).ok()
+}
+
+/// Decodes base64 encoded return data of the `view_nft_attributes` view
+pub fn decode_nft_attributes(data: &str) -> Option<NftAttributes> {
+    let data = base64::decode(data).ok()?;
+    NftAttributes::try_from_slice(&data).ok()
+}
+
+/// Decodes base64 encoded return data of the `view_withdrawal_log` view
+pub fn decode_withdrawal_log(data: &str) -> Option<Vec<WithdrawalLogEntry>> {
+    let data = base64::decode(data).ok()?;
+    Vec::<WithdrawalLogEntry>::try_from_slice(&data).ok()
+}
+
+/// Start time of the day in seconds since the Unix epoch
+pub fn day_timestamp(day: u64) -> u64 {
+    day.checked_mul(SEC_PER_DAY).unwrap()
+}
+
+/// Formats the day as a UTC date "YYYY-MM-DD". Days start at UTC midnight,
+/// so the date doesn't depend on a time zone of the caller
+pub fn day_to_date(day: u64) -> String {
+    // Converts days since 1970-01-01 to a date of the proleptic Gregorian
+    // calendar with eras of 400 years starting from 0000-03-01
+    let days = day as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+
+    let day_of_month = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{:04}-{:02}-{:02}", year, month, day_of_month)
+}
+
+pub fn boost_calendar_to_json(calendar: &BoostCalendar) -> Value {
+    let days = calendar
+        .days
+        .iter()
+        .map(|day| {
+            json!({
+                "day": day.day,
+                "date": day_to_date(day.day),
+                "timestamp": day_timestamp(day.day),
+                "isEligible": day.is_eligible,
+                "isBoosted": day.is_boosted,
+                "multiplier": day.multiplier,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "startDay": calendar.start_day,
+        "days": days,
+    })
+}
+
+/// Amounts are encoded as strings since they might exceed the safe integer
+/// range of JavaScript
+pub fn user_summary_to_json(summary: &UserSummary) -> Value {
+    json!({
+        "startDay": summary.start_day,
+        "startDate": summary.start_day.map(day_to_date),
+        "stakedAmount": summary.staked_amount.to_string(),
+        "pendingAmount": summary.pending_amount.to_string(),
+        "rewardedAmount": summary.rewarded_amount.to_string(),
+        "boostedDays": summary.boosted_days,
+        "projectedReward": summary.projected_reward.to_string(),
+        "dailyStakingReward": summary.daily_staking_reward.to_string(),
+        "dailyReward": summary.daily_reward.to_string(),
+    })
+}
+
+pub fn projected_reward_to_json(reward: &ProjectedReward) -> Value {
+    json!({
+        "minReward": reward.min_reward.to_string(),
+        "maxReward": reward.max_reward.to_string(),
+    })
+}
+
+/// The mint is null for withdrawals of lamports
+pub fn withdrawal_log_to_json(entries: &[WithdrawalLogEntry]) -> Value {
+    let entries = entries
+        .iter()
+        .map(|entry| {
+            let mint = (entry.mint != Pubkey::default()).then(|| entry.mint.to_string());
+            json!({
+                "timestamp": entry.timestamp,
+                "authority": entry.authority.to_string(),
+                "mint": mint,
+                "amount": entry.amount.to_string(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    Value::Array(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dates() {
+        assert_eq!(day_to_date(0), "1970-01-01");
+        assert_eq!(day_to_date(59), "1970-03-01");
+        assert_eq!(day_to_date(11_016), "2000-02-29");
+        assert_eq!(day_to_date(19_358), "2023-01-01");
+        assert_eq!(day_to_date(20_818), "2026-12-31");
+    }
+}
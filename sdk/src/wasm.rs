@@ -0,0 +1,1080 @@
+use crate::{instruction, merkle, pda, view};
+use anchor_lang::{prelude::Pubkey, solana_program::instruction::Instruction};
+use chill_nft::{
+    state::NftType,
+    utils::{NftArgs, UseMethod, Uses},
+};
+use serde_json::json;
+use std::str::FromStr;
+use wasm_bindgen::prelude::*;
+
+type JsResult<T> = Result<T, JsValue>;
+
+fn pubkey(string: &str) -> JsResult<Pubkey> {
+    Pubkey::from_str(string)
+        .map_err(|_| JsValue::from_str(&format!("Cannot parse '{}' as a public key", string)))
+}
+
+fn hash(string: &str) -> JsResult<[u8; 32]> {
+    merkle::from_hex(string)
+        .ok_or_else(|| JsValue::from_str(&format!("Cannot parse '{}' as a hex hash", string)))
+}
+
+fn program_id(program_id: Option<String>, default: Pubkey) -> JsResult<Pubkey> {
+    program_id.as_deref().map(pubkey).unwrap_or(Ok(default))
+}
+
+// Matches the fields of `TransactionInstruction` from @solana/web3.js
+fn instruction_to_json(ix: Instruction) -> String {
+    let keys = ix
+        .accounts
+        .iter()
+        .map(|meta| {
+            json!({
+                "pubkey": meta.pubkey.to_string(),
+                "isSigner": meta.is_signer,
+                "isWritable": meta.is_writable,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "programId": ix.program_id.to_string(),
+        "keys": keys,
+        "data": base64::encode(ix.data),
+    })
+    .to_string()
+}
+
+//
+// PDAs
+//
+
+#[wasm_bindgen(js_name = stakingTokenAuthorityPda)]
+pub fn staking_token_authority_pda(
+    staking_info: &str,
+    staking_program_id: Option<String>,
+) -> JsResult<String> {
+    let program_id = program_id(staking_program_id, chill_staking::ID)?;
+    Ok(pda::staking_token_authority(pubkey(staking_info)?, program_id).to_string())
+}
+
+#[wasm_bindgen(js_name = userInfoPda)]
+pub fn user_info_pda(
+    staking_info: &str,
+    user: &str,
+    staking_program_id: Option<String>,
+) -> JsResult<String> {
+    let program_id = program_id(staking_program_id, chill_staking::ID)?;
+    Ok(pda::user_info(pubkey(staking_info)?, pubkey(user)?, program_id).to_string())
+}
+
+#[wasm_bindgen(js_name = referralPda)]
+pub fn referral_pda(user_info: &str, staking_program_id: Option<String>) -> JsResult<String> {
+    let program_id = program_id(staking_program_id, chill_staking::ID)?;
+    Ok(pda::referral(pubkey(user_info)?, program_id).to_string())
+}
+
+#[wasm_bindgen(js_name = delegateInfoPda)]
+pub fn delegate_info_pda(user_info: &str, staking_program_id: Option<String>) -> JsResult<String> {
+    let program_id = program_id(staking_program_id, chill_staking::ID)?;
+    Ok(pda::delegate_info(pubkey(user_info)?, program_id).to_string())
+}
+
+#[wasm_bindgen(js_name = configPda)]
+pub fn config_pda(mint: &str, nft_program_id: Option<String>) -> JsResult<String> {
+    let program_id = program_id(nft_program_id, chill_nft::ID)?;
+    Ok(pda::config(pubkey(mint)?, program_id).to_string())
+}
+
+#[wasm_bindgen(js_name = configRegistryPda)]
+pub fn config_registry_pda(nft_program_id: Option<String>) -> JsResult<String> {
+    let program_id = program_id(nft_program_id, chill_nft::ID)?;
+    Ok(pda::config_registry(program_id).to_string())
+}
+
+#[wasm_bindgen(js_name = nftTypeRegistryPda)]
+pub fn nft_type_registry_pda(config: &str, nft_program_id: Option<String>) -> JsResult<String> {
+    let program_id = program_id(nft_program_id, chill_nft::ID)?;
+    Ok(pda::nft_type_registry(pubkey(config)?, program_id).to_string())
+}
+
+#[wasm_bindgen(js_name = adminConfigPda)]
+pub fn admin_config_pda(primary_wallet: &str, nft_program_id: Option<String>) -> JsResult<String> {
+    let program_id = program_id(nft_program_id, chill_nft::ID)?;
+    Ok(pda::admin_config(pubkey(primary_wallet)?, program_id).to_string())
+}
+
+#[wasm_bindgen(js_name = chillMetadataPda)]
+pub fn chill_metadata_pda(nft_mint: &str, nft_program_id: Option<String>) -> JsResult<String> {
+    let program_id = program_id(nft_program_id, chill_nft::ID)?;
+    Ok(pda::chill_metadata(pubkey(nft_mint)?, program_id).to_string())
+}
+
+#[wasm_bindgen(js_name = nftListingPda)]
+pub fn nft_listing_pda(nft_mint: &str, nft_program_id: Option<String>) -> JsResult<String> {
+    let program_id = program_id(nft_program_id, chill_nft::ID)?;
+    Ok(pda::nft_listing(pubkey(nft_mint)?, program_id).to_string())
+}
+
+#[wasm_bindgen(js_name = proxyWalletPda)]
+pub fn proxy_wallet_pda(
+    user: &str,
+    primary_wallet: &str,
+    wallet_program_id: Option<String>,
+) -> JsResult<String> {
+    let program_id = program_id(wallet_program_id, chill_wallet::ID)?;
+    Ok(pda::proxy_wallet(pubkey(user)?, pubkey(primary_wallet)?, program_id).to_string())
+}
+
+#[wasm_bindgen(js_name = withdrawalSchedulePda)]
+pub fn withdrawal_schedule_pda(
+    proxy_wallet: &str,
+    receiver: &str,
+    wallet_program_id: Option<String>,
+) -> JsResult<String> {
+    let program_id = program_id(wallet_program_id, chill_wallet::ID)?;
+    Ok(pda::withdrawal_schedule(pubkey(proxy_wallet)?, pubkey(receiver)?, program_id).to_string())
+}
+
+#[wasm_bindgen(js_name = rentalReceiptPda)]
+pub fn rental_receipt_pda(nft_mint: &str, wallet_program_id: Option<String>) -> JsResult<String> {
+    let program_id = program_id(wallet_program_id, chill_wallet::ID)?;
+    let rental = pda::nft_rental(pubkey(nft_mint)?, program_id);
+    Ok(pda::rental_receipt(rental, program_id).to_string())
+}
+
+#[wasm_bindgen(js_name = sessionKeyPda)]
+pub fn session_key_pda(
+    proxy_wallet: &str,
+    signer: &str,
+    wallet_program_id: Option<String>,
+) -> JsResult<String> {
+    Ok(pda::session_key(
+        pubkey(proxy_wallet)?,
+        pubkey(signer)?,
+        program_id(wallet_program_id, chill_wallet::ID)?,
+    )
+    .to_string())
+}
+
+#[wasm_bindgen(js_name = distributorPda)]
+pub fn distributor_pda(
+    authority: &str,
+    merkle_root: &str,
+    drop_program_id: Option<String>,
+) -> JsResult<String> {
+    let program_id = program_id(drop_program_id, chill_drop::ID)?;
+    Ok(pda::distributor(pubkey(authority)?, hash(merkle_root)?, program_id).to_string())
+}
+
+#[wasm_bindgen(js_name = claimStatusPda)]
+pub fn claim_status_pda(
+    distributor: &str,
+    index: u64,
+    drop_program_id: Option<String>,
+) -> JsResult<String> {
+    let program_id = program_id(drop_program_id, chill_drop::ID)?;
+    Ok(pda::claim_status(pubkey(distributor)?, index, program_id).to_string())
+}
+
+#[wasm_bindgen(js_name = vestingPda)]
+pub fn vesting_pda(
+    authority: &str,
+    beneficiary: &str,
+    mint: &str,
+    vesting_program_id: Option<String>,
+) -> JsResult<String> {
+    let program_id = program_id(vesting_program_id, chill_vesting::ID)?;
+    let vesting = pda::vesting(
+        pubkey(authority)?,
+        pubkey(beneficiary)?,
+        pubkey(mint)?,
+        program_id,
+    );
+    Ok(vesting.to_string())
+}
+
+#[wasm_bindgen(js_name = governancePda)]
+pub fn governance_pda(
+    staking_info: &str,
+    governance_program_id: Option<String>,
+) -> JsResult<String> {
+    let program_id = program_id(governance_program_id, chill_governance::ID)?;
+    Ok(pda::governance(pubkey(staking_info)?, program_id).to_string())
+}
+
+#[wasm_bindgen(js_name = proposalPda)]
+pub fn proposal_pda(
+    governance: &str,
+    index: u64,
+    governance_program_id: Option<String>,
+) -> JsResult<String> {
+    let program_id = program_id(governance_program_id, chill_governance::ID)?;
+    Ok(pda::proposal(pubkey(governance)?, index, program_id).to_string())
+}
+
+#[wasm_bindgen(js_name = metadataPda)]
+pub fn metadata_pda(mint: &str) -> JsResult<String> {
+    Ok(pda::metadata(pubkey(mint)?).to_string())
+}
+
+#[wasm_bindgen(js_name = masterEditionPda)]
+pub fn master_edition_pda(mint: &str) -> JsResult<String> {
+    Ok(pda::master_edition(pubkey(mint)?).to_string())
+}
+
+//
+// Instructions
+//
+
+#[allow(clippy::too_many_arguments)]
+#[wasm_bindgen(js_name = stakeInstruction)]
+pub fn stake_instruction(
+    user: &str,
+    token_account_authority: &str,
+    payer: &str,
+    from_token_account: &str,
+    staking_info: &str,
+    mint: &str,
+    amount: u64,
+    referrer: Option<String>,
+    staking_program_id: Option<String>,
+) -> JsResult<String> {
+    let referrer = referrer.as_deref().map(pubkey).transpose()?;
+    let ix = instruction::stake(
+        pubkey(user)?,
+        pubkey(token_account_authority)?,
+        pubkey(payer)?,
+        pubkey(from_token_account)?,
+        pubkey(staking_info)?,
+        pubkey(mint)?,
+        amount,
+        referrer,
+        program_id(staking_program_id, chill_staking::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[wasm_bindgen(js_name = unstakeInstruction)]
+pub fn unstake_instruction(
+    user: &str,
+    staking_info: &str,
+    amount: u64,
+    staking_program_id: Option<String>,
+) -> JsResult<String> {
+    let ix = instruction::unstake(
+        pubkey(user)?,
+        pubkey(staking_info)?,
+        amount,
+        program_id(staking_program_id, chill_staking::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[wasm_bindgen(js_name = claimReferralRewardInstruction)]
+pub fn claim_referral_reward_instruction(
+    referrer: &str,
+    user_info: &str,
+    recipient_token_account: &str,
+    staking_info: &str,
+    mint: &str,
+    staking_program_id: Option<String>,
+) -> JsResult<String> {
+    let ix = instruction::claim_referral_reward(
+        pubkey(referrer)?,
+        pubkey(user_info)?,
+        pubkey(recipient_token_account)?,
+        pubkey(staking_info)?,
+        pubkey(mint)?,
+        program_id(staking_program_id, chill_staking::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[wasm_bindgen(js_name = claimAndRestakeInstruction)]
+pub fn claim_and_restake_instruction(
+    user: &str,
+    staking_info: &str,
+    mint: &str,
+    fee_config: Option<String>,
+    recipients_token_accounts: Vec<String>,
+    staking_program_id: Option<String>,
+) -> JsResult<String> {
+    let fee_config = fee_config.as_deref().map(pubkey).transpose()?;
+    let recipients_token_accounts = recipients_token_accounts
+        .iter()
+        .map(|address| pubkey(address))
+        .collect::<JsResult<Vec<_>>>()?;
+
+    let ix = instruction::claim_and_restake(
+        pubkey(user)?,
+        pubkey(staking_info)?,
+        pubkey(mint)?,
+        fee_config,
+        &recipients_token_accounts,
+        program_id(staking_program_id, chill_staking::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[wasm_bindgen(js_name = setDelegateInstruction)]
+pub fn set_delegate_instruction(
+    user: &str,
+    payer: &str,
+    staking_info: &str,
+    delegate: &str,
+    staking_program_id: Option<String>,
+) -> JsResult<String> {
+    let ix = instruction::set_delegate(
+        pubkey(user)?,
+        pubkey(payer)?,
+        pubkey(staking_info)?,
+        pubkey(delegate)?,
+        program_id(staking_program_id, chill_staking::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[wasm_bindgen(js_name = revokeDelegateInstruction)]
+pub fn revoke_delegate_instruction(
+    user: &str,
+    staking_info: &str,
+    staking_program_id: Option<String>,
+) -> JsResult<String> {
+    let ix = instruction::revoke_delegate(
+        pubkey(user)?,
+        pubkey(staking_info)?,
+        program_id(staking_program_id, chill_staking::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[wasm_bindgen(js_name = delegateBoostInstruction)]
+pub fn delegate_boost_instruction(
+    delegate: &str,
+    user_info: &str,
+    staking_info: &str,
+    staking_program_id: Option<String>,
+) -> JsResult<String> {
+    let ix = instruction::delegate_boost(
+        pubkey(delegate)?,
+        pubkey(user_info)?,
+        pubkey(staking_info)?,
+        program_id(staking_program_id, chill_staking::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[allow(clippy::too_many_arguments)]
+#[wasm_bindgen(js_name = delegateClaimInstruction)]
+pub fn delegate_claim_instruction(
+    delegate: &str,
+    user_info: &str,
+    recipient_token_account: &str,
+    staking_info: &str,
+    mint: &str,
+    amount: u64,
+    fee_config: Option<String>,
+    recipients_token_accounts: Vec<String>,
+    staking_program_id: Option<String>,
+) -> JsResult<String> {
+    let fee_config = fee_config.as_deref().map(pubkey).transpose()?;
+    let recipients_token_accounts = recipients_token_accounts
+        .iter()
+        .map(|address| pubkey(address))
+        .collect::<JsResult<Vec<_>>>()?;
+
+    let ix = instruction::delegate_claim(
+        pubkey(delegate)?,
+        pubkey(user_info)?,
+        pubkey(recipient_token_account)?,
+        pubkey(staking_info)?,
+        pubkey(mint)?,
+        amount,
+        fee_config,
+        &recipients_token_accounts,
+        program_id(staking_program_id, chill_staking::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[wasm_bindgen(js_name = settleStakeInstruction)]
+pub fn settle_stake_instruction(
+    user_info: &str,
+    staking_info: &str,
+    staking_program_id: Option<String>,
+) -> JsResult<String> {
+    let ix = instruction::settle_stake(
+        pubkey(user_info)?,
+        pubkey(staking_info)?,
+        program_id(staking_program_id, chill_staking::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[wasm_bindgen(js_name = redistributeUnspentRewardInstruction)]
+pub fn redistribute_unspent_reward_instruction(
+    payer: &str,
+    user_info: &str,
+    staking_info: &str,
+    staking_program_id: Option<String>,
+) -> JsResult<String> {
+    let ix = instruction::redistribute_unspent_reward(
+        pubkey(payer)?,
+        pubkey(user_info)?,
+        pubkey(staking_info)?,
+        program_id(staking_program_id, chill_staking::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[allow(clippy::too_many_arguments)]
+#[wasm_bindgen(js_name = mintNftInstruction)]
+pub fn mint_nft_instruction(
+    primary_wallet: &str,
+    payer: &str,
+    chill_payer: &str,
+    chill_payer_token_account: &str,
+    chill_mint: &str,
+    nft_mint: &str,
+    nft_type: &str,
+    name: String,
+    symbol: String,
+    uri: String,
+    fees: u16,
+    creator: Option<String>,
+    min_game_version: u32,
+    recipients_token_accounts: Vec<String>,
+    collection: Option<String>,
+    use_method: Option<String>,
+    total_uses: Option<u64>,
+    nft_program_id: Option<String>,
+) -> JsResult<String> {
+    // Custom types are passed by their id in the registry of the config
+    let nft_type = NftType::try_from(nft_type)
+        .or_else(|e| nft_type.parse().map(NftType::Custom).map_err(|_| e))
+        .map_err(|e| JsValue::from_str(&e))?;
+    let creator = creator.as_deref().map(pubkey).transpose()?;
+    let collection = collection.as_deref().map(pubkey).transpose()?;
+    let uses = match (use_method, total_uses) {
+        (Some(method), Some(total)) => Some(Uses {
+            method: UseMethod::try_from(method.as_str()).map_err(|e| JsValue::from_str(&e))?,
+            remaining: total,
+            total,
+        }),
+        _ => None,
+    };
+    let recipients_token_accounts = recipients_token_accounts
+        .iter()
+        .map(|address| pubkey(address))
+        .collect::<JsResult<Vec<_>>>()?;
+
+    let args = NftArgs {
+        name,
+        symbol,
+        uri,
+        fees,
+    };
+
+    let ix = instruction::mint_nft(
+        pubkey(primary_wallet)?,
+        pubkey(payer)?,
+        pubkey(chill_payer)?,
+        pubkey(chill_payer_token_account)?,
+        pubkey(chill_mint)?,
+        pubkey(nft_mint)?,
+        nft_type,
+        args,
+        creator,
+        min_game_version,
+        &recipients_token_accounts,
+        collection,
+        uses,
+        program_id(nft_program_id, chill_nft::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[allow(clippy::too_many_arguments)]
+#[wasm_bindgen(js_name = createCollectionInstruction)]
+pub fn create_collection_instruction(
+    primary_wallet: &str,
+    payer: &str,
+    chill_mint: &str,
+    collection_mint: &str,
+    name: String,
+    symbol: String,
+    uri: String,
+    fees: u16,
+    nft_program_id: Option<String>,
+) -> JsResult<String> {
+    let args = NftArgs {
+        name,
+        symbol,
+        uri,
+        fees,
+    };
+
+    let ix = instruction::create_collection(
+        pubkey(primary_wallet)?,
+        pubkey(payer)?,
+        pubkey(chill_mint)?,
+        pubkey(collection_mint)?,
+        args,
+        program_id(nft_program_id, chill_nft::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[wasm_bindgen(js_name = utilizeNftInstruction)]
+pub fn utilize_nft_instruction(
+    owner: &str,
+    nft_mint: &str,
+    nft_token_account: &str,
+    number_of_uses: u64,
+    nft_program_id: Option<String>,
+) -> JsResult<String> {
+    let ix = instruction::utilize_nft(
+        pubkey(owner)?,
+        pubkey(nft_mint)?,
+        pubkey(nft_token_account)?,
+        number_of_uses,
+        program_id(nft_program_id, chill_nft::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[wasm_bindgen(js_name = burnNftInstruction)]
+pub fn burn_nft_instruction(
+    owner: &str,
+    nft_mint: &str,
+    nft_token_account: &str,
+    rent_receiver: &str,
+    collection_mint: Option<String>,
+    nft_program_id: Option<String>,
+) -> JsResult<String> {
+    let collection_mint = collection_mint.as_deref().map(pubkey).transpose()?;
+    let ix = instruction::burn_nft(
+        pubkey(owner)?,
+        pubkey(nft_mint)?,
+        pubkey(nft_token_account)?,
+        pubkey(rent_receiver)?,
+        collection_mint,
+        program_id(nft_program_id, chill_nft::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[allow(clippy::too_many_arguments)]
+#[wasm_bindgen(js_name = buyNftInstruction)]
+pub fn buy_nft_instruction(
+    buyer: &str,
+    buyer_chill_token_account: &str,
+    chill_mint: &str,
+    seller: &str,
+    nft_mint: &str,
+    price: u64,
+    recipients_token_accounts: Vec<String>,
+    nft_program_id: Option<String>,
+) -> JsResult<String> {
+    let recipients_token_accounts = recipients_token_accounts
+        .iter()
+        .map(|address| pubkey(address))
+        .collect::<JsResult<Vec<_>>>()?;
+
+    let ix = instruction::buy_nft(
+        pubkey(buyer)?,
+        pubkey(buyer_chill_token_account)?,
+        pubkey(chill_mint)?,
+        pubkey(seller)?,
+        pubkey(nft_mint)?,
+        price,
+        &recipients_token_accounts,
+        program_id(nft_program_id, chill_nft::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[wasm_bindgen(js_name = burnChillForActionInstruction)]
+pub fn burn_chill_for_action_instruction(
+    owner: &str,
+    owner_token_account: &str,
+    chill_mint: &str,
+    amount: u64,
+    action_id: u64,
+    memo: String,
+    nft_program_id: Option<String>,
+) -> JsResult<String> {
+    let ix = instruction::burn_chill_for_action(
+        pubkey(owner)?,
+        pubkey(owner_token_account)?,
+        pubkey(chill_mint)?,
+        amount,
+        action_id,
+        memo,
+        program_id(nft_program_id, chill_nft::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[wasm_bindgen(js_name = reclaimMetadataInstruction)]
+pub fn reclaim_metadata_instruction(
+    nft_mint: &str,
+    rent_receiver: &str,
+    nft_program_id: Option<String>,
+) -> JsResult<String> {
+    let ix = instruction::reclaim_metadata(
+        pubkey(nft_mint)?,
+        pubkey(rent_receiver)?,
+        program_id(nft_program_id, chill_nft::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[wasm_bindgen(js_name = depositLamportsInstruction)]
+pub fn deposit_lamports_instruction(
+    depositor: &str,
+    proxy_wallet: &str,
+    amount: u64,
+    wallet_program_id: Option<String>,
+) -> JsResult<String> {
+    let ix = instruction::deposit_lamports(
+        pubkey(depositor)?,
+        pubkey(proxy_wallet)?,
+        amount,
+        program_id(wallet_program_id, chill_wallet::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[wasm_bindgen(js_name = depositFtInstruction)]
+pub fn deposit_ft_instruction(
+    depositor: &str,
+    proxy_wallet: &str,
+    mint: &str,
+    amount: u64,
+    wallet_program_id: Option<String>,
+) -> JsResult<String> {
+    let ix = instruction::deposit_ft(
+        pubkey(depositor)?,
+        pubkey(proxy_wallet)?,
+        pubkey(mint)?,
+        amount,
+        program_id(wallet_program_id, chill_wallet::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[allow(clippy::too_many_arguments)]
+#[wasm_bindgen(js_name = createSessionKeyInstruction)]
+pub fn create_session_key_instruction(
+    user: &str,
+    proxy_wallet: &str,
+    session_signer: &str,
+    expires_at_slot: u64,
+    permissions: u8,
+    lamports_allowance: u64,
+    ft_allowance: u64,
+    wallet_program_id: Option<String>,
+) -> JsResult<String> {
+    let ix = instruction::create_session_key(
+        pubkey(user)?,
+        pubkey(proxy_wallet)?,
+        pubkey(session_signer)?,
+        expires_at_slot,
+        permissions,
+        lamports_allowance,
+        ft_allowance,
+        program_id(wallet_program_id, chill_wallet::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[wasm_bindgen(js_name = revokeSessionKeyInstruction)]
+pub fn revoke_session_key_instruction(
+    user: &str,
+    proxy_wallet: &str,
+    session_signer: &str,
+    wallet_program_id: Option<String>,
+) -> JsResult<String> {
+    let ix = instruction::revoke_session_key(
+        pubkey(user)?,
+        pubkey(proxy_wallet)?,
+        pubkey(session_signer)?,
+        program_id(wallet_program_id, chill_wallet::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[wasm_bindgen(js_name = withdrawLamportsInstruction)]
+pub fn withdraw_lamports_instruction(
+    authority: &str,
+    proxy_wallet: &str,
+    receiver: &str,
+    amount: u64,
+    wallet_program_id: Option<String>,
+) -> JsResult<String> {
+    let ix = instruction::withdraw_lamports(
+        pubkey(authority)?,
+        pubkey(proxy_wallet)?,
+        pubkey(receiver)?,
+        amount,
+        program_id(wallet_program_id, chill_wallet::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[wasm_bindgen(js_name = withdrawFtInstruction)]
+pub fn withdraw_ft_instruction(
+    authority: &str,
+    proxy_wallet: &str,
+    mint: &str,
+    receiver_token_account: &str,
+    amount: u64,
+    wallet_program_id: Option<String>,
+) -> JsResult<String> {
+    let ix = instruction::withdraw_ft(
+        pubkey(authority)?,
+        pubkey(proxy_wallet)?,
+        pubkey(mint)?,
+        pubkey(receiver_token_account)?,
+        amount,
+        program_id(wallet_program_id, chill_wallet::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[wasm_bindgen(js_name = withdrawFtBatchInstruction)]
+pub fn withdraw_ft_batch_instruction(
+    authority: &str,
+    proxy_wallet: &str,
+    receiver: &str,
+    mints: Vec<String>,
+    fee_receiver: Option<String>,
+    wallet_program_id: Option<String>,
+) -> JsResult<String> {
+    let mints = mints
+        .iter()
+        .map(|address| pubkey(address))
+        .collect::<JsResult<Vec<_>>>()?;
+    let fee_receiver = fee_receiver.as_deref().map(pubkey).transpose()?;
+
+    let ix = instruction::withdraw_ft_batch(
+        pubkey(authority)?,
+        pubkey(proxy_wallet)?,
+        pubkey(receiver)?,
+        &mints,
+        fee_receiver,
+        program_id(wallet_program_id, chill_wallet::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[wasm_bindgen(js_name = rentNftInstruction)]
+pub fn rent_nft_instruction(
+    renter: &str,
+    proxy_wallet: &str,
+    nft_mint: &str,
+    wallet_program_id: Option<String>,
+) -> JsResult<String> {
+    let ix = instruction::rent_nft(
+        pubkey(renter)?,
+        pubkey(proxy_wallet)?,
+        pubkey(nft_mint)?,
+        program_id(wallet_program_id, chill_wallet::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[wasm_bindgen(js_name = withdrawNftInstruction)]
+pub fn withdraw_nft_instruction(
+    authority: &str,
+    proxy_wallet: &str,
+    nft_mint: &str,
+    receiver_token_account: &str,
+    wallet_program_id: Option<String>,
+) -> JsResult<String> {
+    let ix = instruction::withdraw_nft(
+        pubkey(authority)?,
+        pubkey(proxy_wallet)?,
+        pubkey(nft_mint)?,
+        pubkey(receiver_token_account)?,
+        program_id(wallet_program_id, chill_wallet::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[wasm_bindgen(js_name = transferNftWithUpdateInstruction)]
+pub fn transfer_nft_with_update_instruction(
+    primary_wallet: &str,
+    proxy_wallet: &str,
+    nft_mint: &str,
+    receiver_token_account: &str,
+    wallet_program_id: Option<String>,
+) -> JsResult<String> {
+    let ix = instruction::transfer_nft_with_update(
+        pubkey(primary_wallet)?,
+        pubkey(proxy_wallet)?,
+        pubkey(nft_mint)?,
+        pubkey(receiver_token_account)?,
+        program_id(wallet_program_id, chill_wallet::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[wasm_bindgen(js_name = wrapSolInstruction)]
+pub fn wrap_sol_instruction(
+    authority: &str,
+    proxy_wallet: &str,
+    amount: u64,
+    wallet_program_id: Option<String>,
+) -> JsResult<String> {
+    let ix = instruction::wrap_sol(
+        pubkey(authority)?,
+        pubkey(proxy_wallet)?,
+        amount,
+        program_id(wallet_program_id, chill_wallet::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[wasm_bindgen(js_name = unwrapSolInstruction)]
+pub fn unwrap_sol_instruction(
+    authority: &str,
+    proxy_wallet: &str,
+    wallet_program_id: Option<String>,
+) -> JsResult<String> {
+    let ix = instruction::unwrap_sol(
+        pubkey(authority)?,
+        pubkey(proxy_wallet)?,
+        program_id(wallet_program_id, chill_wallet::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[allow(clippy::too_many_arguments)]
+#[wasm_bindgen(js_name = stakeFromWalletInstruction)]
+pub fn stake_from_wallet_instruction(
+    user: &str,
+    payer: &str,
+    proxy_wallet: &str,
+    staking_info: &str,
+    mint: &str,
+    amount: u64,
+    fee_receiver: Option<String>,
+    wallet_program_id: Option<String>,
+) -> JsResult<String> {
+    let fee_receiver = fee_receiver.as_deref().map(pubkey).transpose()?;
+
+    let ix = instruction::stake_from_wallet(
+        pubkey(user)?,
+        pubkey(payer)?,
+        pubkey(proxy_wallet)?,
+        pubkey(staking_info)?,
+        pubkey(mint)?,
+        amount,
+        fee_receiver,
+        program_id(wallet_program_id, chill_wallet::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[allow(clippy::too_many_arguments)]
+#[wasm_bindgen(js_name = claimDropInstruction)]
+pub fn claim_drop_instruction(
+    claimant: &str,
+    payer: &str,
+    distributor: &str,
+    mint: &str,
+    claimant_token_account: &str,
+    index: u64,
+    amount: u64,
+    proof: Vec<String>,
+    drop_program_id: Option<String>,
+) -> JsResult<String> {
+    let proof = proof
+        .iter()
+        .map(|node| hash(node))
+        .collect::<JsResult<Vec<_>>>()?;
+    let ix = instruction::claim_drop(
+        pubkey(claimant)?,
+        pubkey(payer)?,
+        pubkey(distributor)?,
+        pubkey(mint)?,
+        pubkey(claimant_token_account)?,
+        index,
+        amount,
+        proof,
+        program_id(drop_program_id, chill_drop::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[wasm_bindgen(js_name = claimSlotsInstruction)]
+pub fn claim_slots_instruction(
+    claimant: &str,
+    payer: &str,
+    distributor: &str,
+    index: u64,
+    amount: u64,
+    proof: Vec<String>,
+    drop_program_id: Option<String>,
+) -> JsResult<String> {
+    let proof = proof
+        .iter()
+        .map(|node| hash(node))
+        .collect::<JsResult<Vec<_>>>()?;
+    let ix = instruction::claim_slots(
+        pubkey(claimant)?,
+        pubkey(payer)?,
+        pubkey(distributor)?,
+        index,
+        amount,
+        proof,
+        program_id(drop_program_id, chill_drop::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[wasm_bindgen(js_name = claimVestedInstruction)]
+pub fn claim_vested_instruction(
+    beneficiary: &str,
+    vesting: &str,
+    mint: &str,
+    beneficiary_token_account: &str,
+    vesting_program_id: Option<String>,
+) -> JsResult<String> {
+    let ix = instruction::claim_vested(
+        pubkey(beneficiary)?,
+        pubkey(vesting)?,
+        pubkey(mint)?,
+        pubkey(beneficiary_token_account)?,
+        program_id(vesting_program_id, chill_vesting::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[wasm_bindgen(js_name = voteInstruction)]
+pub fn vote_instruction(
+    user: &str,
+    payer: &str,
+    staking_info: &str,
+    proposal: &str,
+    approve: bool,
+    staking_program_id: Option<String>,
+    governance_program_id: Option<String>,
+) -> JsResult<String> {
+    let ix = instruction::vote(
+        pubkey(user)?,
+        pubkey(payer)?,
+        pubkey(staking_info)?,
+        pubkey(proposal)?,
+        approve,
+        program_id(staking_program_id, chill_staking::ID)?,
+        program_id(governance_program_id, chill_governance::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+#[wasm_bindgen(js_name = executeDueWithdrawalInstruction)]
+pub fn execute_due_withdrawal_instruction(
+    proxy_wallet: &str,
+    receiver: &str,
+    wallet_program_id: Option<String>,
+) -> JsResult<String> {
+    let ix = instruction::execute_due_withdrawal(
+        pubkey(proxy_wallet)?,
+        pubkey(receiver)?,
+        program_id(wallet_program_id, chill_wallet::ID)?,
+    );
+
+    Ok(instruction_to_json(ix))
+}
+
+//
+// Events
+//
+
+/// Decodes a "Program data: <base64>" log line into a JSON string
+#[wasm_bindgen(js_name = decodeEvent)]
+pub fn decode_event(log: &str) -> Option<String> {
+    chill_events::parse_event(log).map(|event| event.to_json().to_string())
+}
+
+//
+// Views
+//
+
+/// Decodes base64 return data of the `view_boost_calendar` view into a JSON
+/// string. Every day has its UTC date and start timestamp
+#[wasm_bindgen(js_name = decodeBoostCalendar)]
+pub fn decode_boost_calendar(data: &str) -> Option<String> {
+    view::decode_boost_calendar(data)
+        .map(|calendar| view::boost_calendar_to_json(&calendar).to_string())
+}
+
+/// Decodes base64 return data of the `view_user_summary` view into a JSON
+/// string
+#[wasm_bindgen(js_name = decodeUserSummary)]
+pub fn decode_user_summary(data: &str) -> Option<String> {
+    view::decode_user_summary(data).map(|summary| view::user_summary_to_json(&summary).to_string())
+}
+
+/// Decodes base64 return data of the `view_projected_reward` view into a JSON
+/// string
+#[wasm_bindgen(js_name = decodeProjectedReward)]
+pub fn decode_projected_reward(data: &str) -> Option<String> {
+    view::decode_projected_reward(data)
+        .map(|reward| view::projected_reward_to_json(&reward).to_string())
+}
+
+/// Decodes base64 return data of the `view_nft_attributes` view into a JSON
+/// string
+#[wasm_bindgen(js_name = decodeNftAttributes)]
+pub fn decode_nft_attributes(data: &str) -> Option<String> {
+    view::decode_nft_attributes(data)
+        .map(|attributes| view::nft_attributes_to_json(&attributes).to_string())
+}
+
+/// Decodes base64 return data of the `view_withdrawal_log` view into a JSON
+/// string
+#[wasm_bindgen(js_name = decodeWithdrawalLog)]
+pub fn decode_withdrawal_log(data: &str) -> Option<String> {
+    view::decode_withdrawal_log(data)
+        .map(|entries| view::withdrawal_log_to_json(&entries).to_string())
+}
@@ -0,0 +1,241 @@
+//! Local `solana-test-validator` with the Chill programs loaded, so the
+//! integration tests don't depend on the Devnet RPC and its airdrop limits.
+//!
+//! Programs are taken from `target/deploy` built by `anchor build`. The
+//! Metaplex metadata program is loaded from `CHILL_MPL_METADATA_SO` if it's
+//! set, otherwise it's cloned from Mainnet
+
+use anchor_client::solana_sdk::{
+    native_token::sol_to_lamports,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use chill_cli::{
+    client::{Client, RpcBudget},
+    error::AppError,
+};
+use std::{
+    env, fs,
+    net::TcpListener,
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+use thiserror::Error;
+
+pub const MPL_METADATA_SO_ENV: &str = "CHILL_MPL_METADATA_SO";
+
+const VALIDATOR: &str = "solana-test-validator";
+const MAINNET_URL: &str = "https://api.mainnet-beta.solana.com";
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Error)]
+pub enum HarnessError {
+    #[error("{VALIDATOR} is not found, install Solana Tool Suite")]
+    ValidatorNotFound,
+
+    #[error("Program '{0}' is not built, run `anchor build`")]
+    ProgramNotBuilt(PathBuf),
+
+    #[error("Validator exited before it started: {0}")]
+    ValidatorExited(String),
+
+    #[error("Validator didn't start in {0:?}")]
+    StartupTimeout(Duration),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("Client error: {0}")]
+    Client(String),
+}
+
+impl From<AppError> for HarnessError {
+    fn from(error: AppError) -> Self {
+        HarnessError::Client(error.to_string())
+    }
+}
+
+pub type Result<T> = core::result::Result<T, HarnessError>;
+
+/// Programs of the workspace by the names of their `.so` files
+pub fn programs() -> Vec<(&'static str, Pubkey)> {
+    vec![
+        ("chill_drop", chill_drop::ID),
+        ("chill_governance", chill_governance::ID),
+        ("chill_nft", chill_nft::ID),
+        ("chill_staking", chill_staking::ID),
+        ("chill_vesting", chill_vesting::ID),
+        ("chill_wallet", chill_wallet::ID),
+    ]
+}
+
+fn free_port() -> Result<u16> {
+    Ok(TcpListener::bind("127.0.0.1:0")?.local_addr()?.port())
+}
+
+pub struct TestValidatorBuilder {
+    deploy_dir: PathBuf,
+    programs: Vec<(Pubkey, PathBuf)>,
+    mpl_metadata_so: Option<PathBuf>,
+}
+
+impl Default for TestValidatorBuilder {
+    fn default() -> Self {
+        let deploy_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../target/deploy");
+        Self {
+            deploy_dir,
+            programs: Vec::new(),
+            mpl_metadata_so: env::var_os(MPL_METADATA_SO_ENV).map(PathBuf::from),
+        }
+    }
+}
+
+impl TestValidatorBuilder {
+    pub fn deploy_dir(mut self, deploy_dir: impl Into<PathBuf>) -> Self {
+        self.deploy_dir = deploy_dir.into();
+        self
+    }
+
+    /// Loads a program besides the Chill ones
+    pub fn program(mut self, program_id: Pubkey, so: impl Into<PathBuf>) -> Self {
+        self.programs.push((program_id, so.into()));
+        self
+    }
+
+    pub fn start(self) -> Result<TestValidator> {
+        let mut programs = programs()
+            .into_iter()
+            .map(|(name, program_id)| (program_id, self.deploy_dir.join(format!("{}.so", name))))
+            .chain(self.programs)
+            .collect::<Vec<_>>();
+
+        if let Some(so) = self.mpl_metadata_so.clone() {
+            programs.push((mpl_token_metadata::ID, so));
+        }
+
+        if let Some((_, so)) = programs.iter().find(|(_, so)| !so.exists()) {
+            return Err(HarnessError::ProgramNotBuilt(so.clone()));
+        }
+
+        let ledger = env::temp_dir().join(format!("chill-test-ledger-{}", free_port()?));
+        let rpc_port = free_port()?;
+
+        let mut command = Command::new(VALIDATOR);
+        command
+            .arg("--ledger")
+            .arg(&ledger)
+            .arg("--reset")
+            .arg("--quiet")
+            .args(["--rpc-port", &rpc_port.to_string()])
+            .args(["--faucet-port", &free_port()?.to_string()])
+            .args(["--gossip-port", &free_port()?.to_string()])
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped());
+
+        for (program_id, so) in &programs {
+            command
+                .arg("--bpf-program")
+                .arg(program_id.to_string())
+                .arg(so);
+        }
+
+        if self.mpl_metadata_so.is_none() {
+            command
+                .args(["--url", MAINNET_URL])
+                .args(["--clone", &mpl_token_metadata::ID.to_string()]);
+        }
+
+        let child = command.spawn().map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => HarnessError::ValidatorNotFound,
+            _ => e.into(),
+        })?;
+
+        let mut validator = TestValidator {
+            child,
+            ledger,
+            rpc_url: format!("http://127.0.0.1:{}", rpc_port),
+        };
+
+        validator.wait_for_startup()?;
+        Ok(validator)
+    }
+}
+
+/// The validator is killed and its ledger is removed when it's dropped
+pub struct TestValidator {
+    child: Child,
+    ledger: PathBuf,
+    rpc_url: String,
+}
+
+impl TestValidator {
+    pub fn builder() -> TestValidatorBuilder {
+        TestValidatorBuilder::default()
+    }
+
+    pub fn start() -> Result<Self> {
+        Self::builder().start()
+    }
+
+    fn wait_for_startup(&mut self) -> Result<()> {
+        let client = self.client();
+        let start = Instant::now();
+
+        while client.slot().is_err() {
+            if let Some(status) = self.child.try_wait()? {
+                let mut output = String::new();
+                if let Some(mut stderr) = self.child.stderr.take() {
+                    std::io::Read::read_to_string(&mut stderr, &mut output)?;
+                }
+
+                let message = if output.is_empty() {
+                    status.to_string()
+                } else {
+                    output
+                };
+                return Err(HarnessError::ValidatorExited(message));
+            }
+
+            if start.elapsed() > STARTUP_TIMEOUT {
+                return Err(HarnessError::StartupTimeout(STARTUP_TIMEOUT));
+            }
+
+            thread::sleep(Duration::from_millis(500));
+        }
+
+        Ok(())
+    }
+
+    pub fn rpc_url(&self) -> &str {
+        &self.rpc_url
+    }
+
+    /// Client connected to the validator without an RPC budget
+    pub fn client(&self) -> Client {
+        Client::init(&self.rpc_url, RpcBudget::default())
+    }
+
+    /// Airdrops lamports from the faucet of the validator, which is not rate
+    /// limited
+    pub fn fund(&self, address: Pubkey, lamports: u64) -> Result<()> {
+        self.client().airdrop(address, lamports)?;
+        Ok(())
+    }
+
+    /// Returns a new keypair with 10 SOL
+    pub fn funded_keypair(&self) -> Result<Keypair> {
+        let keypair = Keypair::new();
+        self.fund(keypair.pubkey(), sol_to_lamports(10.0))?;
+        Ok(keypair)
+    }
+}
+
+impl Drop for TestValidator {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let _ = fs::remove_dir_all(&self.ledger);
+    }
+}
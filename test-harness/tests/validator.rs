@@ -0,0 +1,24 @@
+use anchor_client::solana_sdk::{native_token::sol_to_lamports, signer::Signer};
+use chill_test_harness::{programs, TestValidator};
+
+#[test]
+#[ignore = "requires solana-test-validator and the programs built by `anchor build`"]
+fn start_validator_with_programs() {
+    let validator = TestValidator::start().unwrap();
+    let client = validator.client();
+
+    for (name, program_id) in programs() {
+        assert!(
+            client.is_program_deployed(program_id).unwrap(),
+            "{} is not deployed",
+            name
+        );
+    }
+    assert!(client.is_program_deployed(mpl_token_metadata::ID).unwrap());
+
+    let payer = validator.funded_keypair().unwrap();
+    assert_eq!(
+        client.balance(payer.pubkey()).unwrap(),
+        sol_to_lamports(10.0)
+    );
+}
@@ -0,0 +1,191 @@
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as dropUtils from "./utils";
+import { AnchorError, BN, Program } from "@project-serum/anchor";
+import { ChillDrop } from "../../target/types/chill_drop";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+import {
+  ASSOCIATED_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+} from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("Drop", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillDrop as Program<ChillDrop>;
+
+  const amounts = [1_000_000_000, 2_000_000_000, 3_000_000_000];
+  const totalAmount = amounts.reduce((a, b) => a + b);
+
+  let authority: Keypair;
+  let mint: PublicKey;
+  let claimants: Keypair[];
+  const claimantTokens: PublicKey[] = [];
+  let tree: dropUtils.MerkleTree;
+  let distributor: PublicKey;
+  let vault: PublicKey;
+
+  async function claim(index: number, amount: number, proof: number[][]) {
+    const claimant = claimants[index];
+    await program.methods
+      .claim(new BN(index), new BN(amount), proof)
+      .accounts({
+        claimant: claimant.publicKey,
+        payer: claimant.publicKey,
+        distributor,
+        claimStatus: await dropUtils.getClaimStatusPubkey(
+          distributor,
+          index,
+          program.programId
+        ),
+        vault,
+        claimantTokenAccount: claimantTokens[index],
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([claimant])
+      .rpc();
+  }
+
+  before(async () => {
+    authority = await utils.keypairWithSol();
+    mint = await utils.createMint(authority.publicKey, 9);
+    claimants = await Promise.all(amounts.map(() => utils.keypairWithSol()));
+
+    const authorityToken = await utils.createTokenAccount(
+      authority.publicKey,
+      mint
+    );
+    await utils.mintTokens(authority, mint, authorityToken, totalAmount);
+
+    for (const claimant of claimants) {
+      claimantTokens.push(
+        await utils.createTokenAccount(claimant.publicKey, mint)
+      );
+    }
+
+    tree = new dropUtils.MerkleTree(
+      claimants.map((claimant, index) => ({
+        claimant: claimant.publicKey,
+        amount: amounts[index],
+      }))
+    );
+
+    distributor = await dropUtils.getDistributorPubkey(
+      authority.publicKey,
+      tree.root(),
+      program.programId
+    );
+    vault = await utils.getAssociatedTokenAddress(distributor, mint);
+
+    await program.methods
+      .initializeTokenDrop(tree.root(), new BN(totalAmount))
+      .accounts({
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        distributor,
+        mint,
+        authorityTokenAccount: authorityToken,
+        vault,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+      })
+      .signers([authority])
+      .rpc();
+
+    assert.equal(await utils.tokenBalance(vault), totalAmount);
+  });
+
+  it("Try to claim more tokens than in the tree", async () => {
+    await assert.rejects(
+      claim(0, amounts[0] + 1, tree.proof(0)),
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "InvalidProof");
+        return true;
+      }
+    );
+  });
+
+  it("Claim tokens", async () => {
+    await claim(1, amounts[1], tree.proof(1));
+    await claim(2, amounts[2], tree.proof(2));
+
+    assert.equal(await utils.tokenBalance(vault), amounts[0]);
+    assert.equal(await utils.tokenBalance(claimantTokens[1]), amounts[1]);
+
+    const info = await program.account.distributor.fetch(distributor);
+    assert.equal(info.claimedAmount.toNumber(), amounts[1] + amounts[2]);
+    assert.equal(info.claimsNumber.toNumber(), 2);
+  });
+
+  it("Try to claim twice", async () => {
+    await assert.rejects(claim(1, amounts[1], tree.proof(1)));
+  });
+
+  it("Withdraw unclaimed tokens", async () => {
+    const recipient = await utils.createTokenAccount(authority.publicKey, mint);
+
+    await program.methods
+      .withdrawUnclaimed()
+      .accounts({
+        authority: authority.publicKey,
+        distributor,
+        vault,
+        recipientTokenAccount: recipient,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([authority])
+      .rpc();
+
+    assert.equal(await utils.tokenBalance(vault), 0);
+    assert.equal(await utils.tokenBalance(recipient), amounts[0]);
+  });
+
+  it("Claim whitelist slots", async () => {
+    const claimant = claimants[0];
+    const slotsTree = new dropUtils.MerkleTree([
+      { claimant: claimant.publicKey, amount: 2 },
+    ]);
+    const slotsDistributor = await dropUtils.getDistributorPubkey(
+      authority.publicKey,
+      slotsTree.root(),
+      program.programId
+    );
+
+    await program.methods
+      .initializeWhitelistDrop(slotsTree.root(), new BN(2))
+      .accounts({
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        distributor: slotsDistributor,
+        mint,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    const claimStatus = await dropUtils.getClaimStatusPubkey(
+      slotsDistributor,
+      0,
+      program.programId
+    );
+
+    await program.methods
+      .claimSlots(new BN(0), new BN(2), slotsTree.proof(0))
+      .accounts({
+        claimant: claimant.publicKey,
+        payer: claimant.publicKey,
+        distributor: slotsDistributor,
+        claimStatus,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([claimant])
+      .rpc();
+
+    const status = await program.account.claimStatus.fetch(claimStatus);
+    assert.ok(status.claimant.equals(claimant.publicKey));
+    assert.equal(status.amount.toNumber(), 2);
+  });
+});
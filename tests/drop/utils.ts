@@ -0,0 +1,100 @@
+import { BN } from "@project-serum/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { keccak_256 } from "js-sha3";
+
+export interface Claim {
+  claimant: PublicKey;
+  amount: number;
+}
+
+function keccak(...parts: Buffer[]): Buffer {
+  return Buffer.from(keccak_256.arrayBuffer(Buffer.concat(parts)));
+}
+
+export function leaf(index: number, claimant: PublicKey, amount: number) {
+  return keccak(
+    Buffer.from([0]),
+    new BN(index).toArrayLike(Buffer, "le", 8),
+    claimant.toBuffer(),
+    new BN(amount).toArrayLike(Buffer, "le", 8)
+  );
+}
+
+export function node(a: Buffer, b: Buffer): Buffer {
+  const [left, right] = Buffer.compare(a, b) <= 0 ? [a, b] : [b, a];
+  return keccak(Buffer.from([1]), left, right);
+}
+
+// Built the same way as `MerkleTree` of the SDK, a node without a sibling is
+// moved up a layer as it is
+export class MerkleTree {
+  layers: Buffer[][];
+
+  constructor(claims: Claim[]) {
+    this.layers = [
+      claims.map((claim, index) => leaf(index, claim.claimant, claim.amount)),
+    ];
+
+    while (this.layers[this.layers.length - 1].length > 1) {
+      const layer = this.layers[this.layers.length - 1];
+      const next: Buffer[] = [];
+      for (let i = 0; i < layer.length; i += 2) {
+        next.push(
+          i + 1 < layer.length ? node(layer[i], layer[i + 1]) : layer[i]
+        );
+      }
+      this.layers.push(next);
+    }
+  }
+
+  root(): number[] {
+    return Array.from(this.layers[this.layers.length - 1][0]);
+  }
+
+  proof(index: number): number[][] {
+    const proof: number[][] = [];
+    for (const layer of this.layers.slice(0, -1)) {
+      const sibling = layer[index ^ 1];
+      if (sibling) {
+        proof.push(Array.from(sibling));
+      }
+      index = Math.floor(index / 2);
+    }
+
+    return proof;
+  }
+}
+
+export async function getDistributorPubkey(
+  authority: PublicKey,
+  merkleRoot: number[],
+  programId: PublicKey
+): Promise<PublicKey> {
+  return (
+    await PublicKey.findProgramAddress(
+      [
+        Buffer.from("distributor"),
+        authority.toBuffer(),
+        Buffer.from(merkleRoot),
+      ],
+      programId
+    )
+  )[0];
+}
+
+export async function getClaimStatusPubkey(
+  distributor: PublicKey,
+  index: number,
+  programId: PublicKey
+): Promise<PublicKey> {
+  return (
+    await PublicKey.findProgramAddress(
+      [
+        Buffer.from("claim"),
+        distributor.toBuffer(),
+        new BN(index).toArrayLike(Buffer, "le", 8),
+      ],
+      programId
+    )
+  )[0];
+}
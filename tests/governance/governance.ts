@@ -0,0 +1,249 @@
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as stakingUtils from "../staking/utils";
+import { AnchorError, BN, Program } from "@project-serum/anchor";
+import { ChillGovernance } from "../../target/types/chill_governance";
+import { ChillStaking } from "../../target/types/chill_staking";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("Governance", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillGovernance as Program<ChillGovernance>;
+  const stakingProgram = anchor.workspace
+    .ChillStaking as Program<ChillStaking>;
+  const primaryWallet = Keypair.generate();
+
+  const totalDays = 10;
+  const stakeAmounts = [100_000, 200_000, 50_000];
+  const votingDuration = 20;
+
+  let payer: Keypair;
+  let chillMint: PublicKey;
+  let stakingInfo: PublicKey;
+  let stakingTokenAuthority: PublicKey;
+  let stakingTokenAccount: PublicKey;
+  let governance: PublicKey;
+  let proposal: PublicKey;
+  const users: Keypair[] = [];
+  const userInfos: PublicKey[] = [];
+
+  async function getPda(seeds: Buffer[]): Promise<PublicKey> {
+    return (await PublicKey.findProgramAddress(seeds, program.programId))[0];
+  }
+
+  async function vote(index: number, approve: boolean) {
+    const user = users[index];
+    await program.methods
+      .vote(approve)
+      .accounts({
+        user: user.publicKey,
+        payer: payer.publicKey,
+        governance,
+        proposal,
+        userInfo: userInfos[index],
+        voteRecord: await getPda([
+          Buffer.from("vote"),
+          proposal.toBuffer(),
+          user.publicKey.toBuffer(),
+        ]),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user, payer])
+      .rpc();
+  }
+
+  async function finalize() {
+    await program.methods.finalize().accounts({ proposal }).rpc();
+  }
+
+  async function stake(
+    user: Keypair,
+    userInfo: PublicKey,
+    tokenAccount: PublicKey,
+    amount: number
+  ) {
+    await stakingProgram.methods
+      .stake(new BN(amount), null)
+      .accounts({
+        user: user.publicKey,
+        payer: payer.publicKey,
+        tokenAccountAuthority: user.publicKey,
+        userInfo,
+        fromTokenAccount: tokenAccount,
+        stakingInfo,
+        stakingTokenAuthority,
+        stakingTokenAccount,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user, payer])
+      .rpc();
+  }
+
+  before(async () => {
+    payer = await utils.keypairWithSol();
+    chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+
+    stakingInfo = await stakingUtils.initializeStaking(
+      primaryWallet,
+      payer,
+      totalDays,
+      chillMint,
+      stakingProgram
+    );
+
+    stakingTokenAuthority = await stakingUtils.getStakingAuthority(
+      stakingInfo,
+      stakingProgram.programId
+    );
+    stakingTokenAccount = await utils.getAssociatedTokenAddress(
+      stakingTokenAuthority,
+      chillMint
+    );
+
+    const info = await stakingProgram.account.stakingInfo.fetch(stakingInfo);
+    await stakingUtils.waitUntil(stakingProgram, info.startDay.toNumber());
+
+    for (const amount of stakeAmounts) {
+      const [user, tokenAccount] =
+        await stakingUtils.createUserWithTokenAccount(
+          chillMint,
+          primaryWallet,
+          amount
+        );
+      const userInfo = await stakingUtils.getUserInfoPubkey(
+        user.publicKey,
+        stakingInfo,
+        stakingProgram.programId
+      );
+
+      await stake(user, userInfo, tokenAccount, amount);
+
+      users.push(user);
+      userInfos.push(userInfo);
+    }
+
+    // Stakes started on the day of the proposal don't vote
+    await stakingUtils.waitForDay(stakingProgram);
+
+    governance = await getPda([
+      Buffer.from("governance"),
+      stakingInfo.toBuffer(),
+    ]);
+    await program.methods
+      .initializeGovernance(new BN(stakeAmounts[1]), new BN(votingDuration))
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        payer: payer.publicKey,
+        stakingInfo,
+        governance,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([primaryWallet, payer])
+      .rpc();
+
+    proposal = await getPda([
+      Buffer.from("proposal"),
+      governance.toBuffer(),
+      new BN(0).toArrayLike(Buffer, "le", 8),
+    ]);
+    await program.methods
+      .createProposal("Lower the claim fee", "https://chill.com/proposals/0")
+      .accounts({
+        proposer: users[0].publicKey,
+        payer: payer.publicKey,
+        governance,
+        stakingInfo,
+        userInfo: userInfos[0],
+        proposal,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([users[0], payer])
+      .rpc();
+  });
+
+  it("Try to finalize the proposal before the end of voting", async () => {
+    await assert.rejects(finalize(), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "VotingNotEnded");
+      return true;
+    });
+  });
+
+  it("Vote weighted by staked amounts", async () => {
+    await vote(0, false);
+    await vote(1, true);
+
+    const info = await program.account.proposal.fetch(proposal);
+    assert.equal(info.noWeight.toNumber(), stakeAmounts[0]);
+    assert.equal(info.yesWeight.toNumber(), stakeAmounts[1]);
+  });
+
+  it("Try to vote twice", async () => {
+    await assert.rejects(vote(1, true));
+  });
+
+  it("Try to vote again with restaked tokens", async () => {
+    await stakingProgram.methods
+      .cancel()
+      .accounts({
+        user: users[0].publicKey,
+        userInfo: userInfos[0],
+        stakingInfo,
+      })
+      .signers([users[0]])
+      .rpc();
+
+    const [user, tokenAccount] = await stakingUtils.createUserWithTokenAccount(
+      chillMint,
+      primaryWallet,
+      0
+    );
+    const userInfo = await stakingUtils.getUserInfoPubkey(
+      user.publicKey,
+      stakingInfo,
+      stakingProgram.programId
+    );
+
+    await stakingProgram.methods
+      .claim(new BN(stakeAmounts[0]))
+      .accounts({
+        user: users[0].publicKey,
+        userInfo: userInfos[0],
+        stakingInfo,
+        stakingTokenAuthority,
+        stakingTokenAccount,
+        recipientTokenAccount: tokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([users[0]])
+      .rpc();
+
+    await stake(user, userInfo, tokenAccount, stakeAmounts[0]);
+    users.push(user);
+    userInfos.push(userInfo);
+
+    await assert.rejects(vote(users.length - 1, true), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "StakeAfterProposal");
+      return true;
+    });
+  });
+
+  it("Finalize the proposal", async () => {
+    const info = await program.account.proposal.fetch(proposal);
+    while ((await utils.getCurrentTime()) < info.votingEndTime.toNumber()) {
+      await stakingUtils.pause(1000);
+    }
+
+    await assert.rejects(vote(2, true), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "VotingEnded");
+      return true;
+    });
+
+    await finalize();
+
+    const finalized = await program.account.proposal.fetch(proposal);
+    assert.deepEqual(finalized.status, { approved: {} });
+  });
+});
@@ -0,0 +1,133 @@
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as nftUtils from "./utils";
+import { AnchorError, AnchorProvider, Program } from "@project-serum/anchor";
+import { ChillNft } from "../../target/types/chill_nft";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+
+describe("NFT | Admin config", () => {
+  anchor.setProvider(AnchorProvider.env());
+  const program = anchor.workspace.ChillNft as Program<ChillNft>;
+
+  const primaryWallet = Keypair.generate();
+  const signers = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+  const signerPubkeys = signers.map((signer) => signer.publicKey);
+
+  let payer: Keypair;
+  let adminConfig: PublicKey;
+
+  async function assertError(promise: Promise<unknown>, code: string) {
+    await assert.rejects(promise, (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, code);
+      return true;
+    });
+  }
+
+  async function updateAdminConfig(
+    approvingSigners: Keypair[],
+    newSigners: PublicKey[],
+    threshold: number
+  ) {
+    await program.methods
+      .updateAdminConfig(newSigners, threshold)
+      .accounts({ adminConfig })
+      .remainingAccounts(
+        nftUtils.adminSignerMetas(approvingSigners.map((s) => s.publicKey))
+      )
+      .signers(approvingSigners)
+      .rpc();
+  }
+
+  before(async () => {
+    payer = await utils.keypairWithSol();
+    adminConfig = await nftUtils.getAdminConfigPubkey(
+      primaryWallet.publicKey,
+      program.programId
+    );
+  });
+
+  it("Try to initialize with a too large threshold", async () => {
+    await assertError(
+      program.methods
+        .initializeAdminConfig(signerPubkeys, signerPubkeys.length + 1)
+        .accounts({
+          primaryWallet: primaryWallet.publicKey,
+          payer: payer.publicKey,
+          adminConfig,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([primaryWallet, payer])
+        .rpc(),
+      "WrongAdminConfig"
+    );
+  });
+
+  it("Initialize 2-of-3 admin config", async () => {
+    await nftUtils.initializeAdminConfig(
+      program,
+      primaryWallet,
+      signerPubkeys,
+      2
+    );
+
+    const info = await program.account.adminConfig.fetch(adminConfig);
+    assert.ok(info.primaryWallet.equals(primaryWallet.publicKey));
+    assert.equal(info.threshold, 2);
+    assert.deepEqual(info.signers, signerPubkeys);
+  });
+
+  it("Try to update with one signer", async () => {
+    await assertError(
+      updateAdminConfig([signers[0]], signerPubkeys, 1),
+      "NotEnoughAdminSigners"
+    );
+  });
+
+  it("Try to update with the same signer twice", async () => {
+    await assertError(
+      updateAdminConfig([signers[0], signers[0]], signerPubkeys, 1),
+      "NotEnoughAdminSigners"
+    );
+  });
+
+  it("Initialize NFT program without the primary wallet", async () => {
+    const chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+    const config = await nftUtils.getNftConfigPubkey(
+      chillMint,
+      program.programId
+    );
+
+    const configRegistry = await nftUtils.initializeConfigRegistry(
+      program,
+      payer
+    );
+
+    await program.methods
+      .initialize(nftUtils.randomFees(), nftUtils.randomRecipients())
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        adminConfig,
+        payer: payer.publicKey,
+        config,
+        configRegistry,
+        chillMint,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(nftUtils.adminSignerMetas(signerPubkeys.slice(1)))
+      .signers([payer, signers[1], signers[2]])
+      .rpc();
+
+    const info = await program.account.config.fetch(config);
+    assert.ok(info.primaryWallet.equals(primaryWallet.publicKey));
+  });
+
+  it("Replace signers", async () => {
+    const newSigners = [Keypair.generate().publicKey, signerPubkeys[0]];
+    await updateAdminConfig([signers[0], signers[2]], newSigners, 1);
+
+    const info = await program.account.adminConfig.fetch(adminConfig);
+    assert.equal(info.threshold, 1);
+    assert.deepEqual(info.signers, newSigners);
+  });
+});
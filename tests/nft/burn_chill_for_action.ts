@@ -0,0 +1,113 @@
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as nftUtils from "./utils";
+import {
+  AnchorError,
+  AnchorProvider,
+  BN,
+  Program,
+} from "@project-serum/anchor";
+import { ChillNft } from "../../target/types/chill_nft";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("NFT | Burn CHILL for action", () => {
+  anchor.setProvider(AnchorProvider.env());
+  const program = anchor.workspace.ChillNft as Program<ChillNft>;
+
+  const initialTokenBalance = 1_000_000_000;
+  const amount = 250_000_000;
+  const actionId = new BN(7);
+  const memo = "rename:Bob the cat";
+
+  let primaryWallet: Keypair;
+  let owner: Keypair;
+  let chillMint: PublicKey;
+  let ownerTokenAccount: PublicKey;
+  let config: PublicKey;
+
+  async function burnChillForAction(burnAmount: number, burnMemo: string) {
+    return await program.methods
+      .burnChillForAction(new BN(burnAmount), actionId, burnMemo)
+      .accounts({
+        owner: owner.publicKey,
+        config,
+        chillMint,
+        ownerTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([owner])
+      .rpc();
+  }
+
+  before(async () => {
+    primaryWallet = await utils.keypairWithSol();
+    owner = await utils.keypairWithSol();
+    chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+    ownerTokenAccount = await utils.createTokenAccount(
+      owner.publicKey,
+      chillMint
+    );
+
+    await utils.mintTokens(
+      primaryWallet,
+      chillMint,
+      ownerTokenAccount,
+      initialTokenBalance
+    );
+
+    const configRegistry = await nftUtils.initializeConfigRegistry(
+      program,
+      primaryWallet
+    );
+
+    const adminConfig = await nftUtils.initializeAdminConfig(
+      program,
+      primaryWallet
+    );
+
+    config = await nftUtils.getNftConfigPubkey(chillMint, program.programId);
+
+    await program.methods
+      .initialize(nftUtils.randomFees(), nftUtils.randomRecipients())
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        adminConfig,
+        payer: primaryWallet.publicKey,
+        config,
+        configRegistry,
+        chillMint,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(nftUtils.adminSignerMetas([primaryWallet.publicKey]))
+      .signers([primaryWallet])
+      .rpc();
+  });
+
+  it("Try to burn zero CHILL", async () => {
+    await assert.rejects(burnChillForAction(0, memo), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "ZeroBurnAmount");
+      return true;
+    });
+  });
+
+  it("Try to burn CHILL with a too long memo", async () => {
+    await assert.rejects(
+      burnChillForAction(amount, "a".repeat(65)),
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "ActionMemoTooLong");
+        return true;
+      }
+    );
+  });
+
+  it("Burn CHILL for action", async () => {
+    await burnChillForAction(amount, memo);
+
+    assert.equal(
+      await utils.tokenBalance(ownerTokenAccount),
+      initialTokenBalance - amount
+    );
+  });
+});
@@ -0,0 +1,149 @@
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as nftUtils from "./utils";
+import { AnchorProvider, Program } from "@project-serum/anchor";
+import { ChillNft } from "../../target/types/chill_nft";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+} from "@solana/web3.js";
+import { programs } from "@metaplex/js";
+import * as assert from "assert";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("NFT | Burn NFT", () => {
+  anchor.setProvider(AnchorProvider.env());
+  const program = anchor.workspace.ChillNft as Program<ChillNft>;
+  const Metadata = programs.metadata;
+
+  let primaryWallet: Keypair;
+  let user: Keypair;
+  let chillMint: PublicKey;
+  let config: PublicKey;
+
+  let nftMint: PublicKey;
+  let nftToken: PublicKey;
+  let nftChillMetadata: PublicKey;
+
+  async function burnNft(owner: Keypair, nftTokenAccount: PublicKey) {
+    await program.methods
+      .burnNft()
+      .accounts({
+        owner: owner.publicKey,
+        nftMint,
+        nftTokenAccount,
+        nftMetadata: await Metadata.Metadata.getPDA(nftMint),
+        nftMasterEdition: await Metadata.MasterEdition.getPDA(nftMint),
+        nftChillMetadata,
+        rentReceiver: primaryWallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMetadataProgram: Metadata.MetadataProgram.PUBKEY,
+      })
+      .signers([owner])
+      .rpc();
+  }
+
+  before(async () => {
+    primaryWallet = await utils.keypairWithSol();
+    user = await utils.keypairWithSol();
+    chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+    const chillPayerTokenAccount = await utils.createTokenAccount(
+      primaryWallet.publicKey,
+      chillMint
+    );
+
+    const configRegistry = await nftUtils.initializeConfigRegistry(
+      program,
+      primaryWallet
+    );
+
+    const adminConfig = await nftUtils.initializeAdminConfig(
+      program,
+      primaryWallet
+    );
+
+    config = await nftUtils.getNftConfigPubkey(chillMint, program.programId);
+
+    await program.methods
+      .initialize(nftUtils.randomFees(), [])
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        adminConfig,
+        payer: primaryWallet.publicKey,
+        config,
+        configRegistry,
+        chillMint,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(nftUtils.adminSignerMetas([primaryWallet.publicKey]))
+      .signers([primaryWallet])
+      .rpc();
+
+    nftMint = await utils.createMint(primaryWallet.publicKey, 0);
+    nftToken = await utils.createTokenAccount(user.publicKey, nftMint);
+    await utils.mintTokens(primaryWallet, nftMint, nftToken, 1);
+
+    nftChillMetadata = await nftUtils.getChillMetadataPubkey(
+      nftMint,
+      program.programId
+    );
+
+    await program.methods
+      .mintNft(
+        nftUtils.randomNftType(),
+        nftUtils.randomNftArgs(),
+        null,
+        0,
+        null,
+        null
+      )
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        payer: primaryWallet.publicKey,
+        chillPayer: primaryWallet.publicKey,
+        chillPayerTokenAccount,
+        config,
+        chillMint,
+        nftTypeRegistry: await nftUtils.getNftTypeRegistryPubkey(
+          config,
+          program.programId
+        ),
+        nftMint,
+        nftMetadata: await Metadata.Metadata.getPDA(nftMint),
+        nftMasterEdition: await Metadata.MasterEdition.getPDA(nftMint),
+        nftChillMetadata,
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMetadataProgram: Metadata.MetadataProgram.PUBKEY,
+      })
+      .signers([primaryWallet])
+      .rpc();
+  });
+
+  it("Try to burn NFT by not an owner", async () => {
+    const wrongOwner = await utils.keypairWithSol();
+    await assert.rejects(burnNft(wrongOwner, nftToken));
+  });
+
+  it("Burn NFT", async () => {
+    const connection = program.provider.connection;
+    const chillMetadata = await connection.getAccountInfo(nftChillMetadata);
+    const chillMetadataRent = chillMetadata.lamports;
+    const balanceBefore = await connection.getBalance(primaryWallet.publicKey);
+
+    await burnNft(user, nftToken);
+
+    assert.equal(await connection.getAccountInfo(nftChillMetadata), null);
+    assert.equal(await connection.getAccountInfo(nftToken), null);
+    assert.equal(
+      await connection.getAccountInfo(await Metadata.Metadata.getPDA(nftMint)),
+      null
+    );
+
+    const balanceAfter = await connection.getBalance(primaryWallet.publicKey);
+    assert.equal(balanceAfter - balanceBefore, chillMetadataRent);
+  });
+});
@@ -0,0 +1,252 @@
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as nftUtils from "./utils";
+import {
+  AnchorError,
+  AnchorProvider,
+  BN,
+  Program,
+} from "@project-serum/anchor";
+import { ChillNft } from "../../target/types/chill_nft";
+import {
+  AccountMeta,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+} from "@solana/web3.js";
+import * as assert from "assert";
+import {
+  ASSOCIATED_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+} from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("NFT | Buy NFT", () => {
+  anchor.setProvider(AnchorProvider.env());
+  const program = anchor.workspace.ChillNft as Program<ChillNft>;
+
+  const price = new BN(1_000_000);
+  const initialTokenBalance = 1_000_000_000;
+  const recipients = nftUtils.randomRecipients();
+  const recipientsTokenAccounts: AccountMeta[] = [];
+
+  let primaryWallet: Keypair;
+  let buyer: Keypair;
+  let chillMint: PublicKey;
+  let buyerChillTokenAccount: PublicKey;
+  let adminConfig: PublicKey;
+  let config: PublicKey;
+
+  async function getNftListingPubkey(nftMint: PublicKey): Promise<PublicKey> {
+    return (
+      await PublicKey.findProgramAddress(
+        [anchor.utils.bytes.utf8.encode("nft-listing"), nftMint.toBytes()],
+        program.programId
+      )
+    )[0];
+  }
+
+  async function listNft(): Promise<[PublicKey, PublicKey]> {
+    const nftMint = await utils.createMint(primaryWallet.publicKey, 0);
+    const nftToken = await utils.createTokenAccount(
+      primaryWallet.publicKey,
+      nftMint
+    );
+    await utils.mintTokens(primaryWallet, nftMint, nftToken, 1);
+
+    const nftListing = await getNftListingPubkey(nftMint);
+    await program.methods
+      .listNft(price)
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        config,
+        nftMint,
+        nftTokenAccount: nftToken,
+        nftListing,
+        nftEscrow: await utils.getAssociatedTokenAddress(nftListing, nftMint),
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+      })
+      .signers([primaryWallet])
+      .rpc();
+
+    return [nftMint, nftToken];
+  }
+
+  async function buyNft(
+    nftMint: PublicKey,
+    agreedPrice: BN,
+    recipientMetas = recipientsTokenAccounts
+  ) {
+    const nftListing = await getNftListingPubkey(nftMint);
+    await program.methods
+      .buyNft(agreedPrice)
+      .accounts({
+        buyer: buyer.publicKey,
+        buyerChillTokenAccount,
+        buyerNftTokenAccount: await utils.getAssociatedTokenAddress(
+          buyer.publicKey,
+          nftMint
+        ),
+        config,
+        chillMint,
+        seller: primaryWallet.publicKey,
+        nftMint,
+        nftListing,
+        nftEscrow: await utils.getAssociatedTokenAddress(nftListing, nftMint),
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+      })
+      .remainingAccounts(recipientMetas)
+      .signers([buyer])
+      .rpc();
+  }
+
+  before(async () => {
+    primaryWallet = await utils.keypairWithSol();
+    buyer = await utils.keypairWithSol();
+    chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+    buyerChillTokenAccount = await utils.createTokenAccount(
+      buyer.publicKey,
+      chillMint
+    );
+
+    await utils.mintTokens(
+      primaryWallet,
+      chillMint,
+      buyerChillTokenAccount,
+      initialTokenBalance
+    );
+
+    for (const recipient of recipients) {
+      const tokenAccount = await utils.createTokenAccount(
+        recipient.address,
+        chillMint
+      );
+
+      recipientsTokenAccounts.push({
+        pubkey: tokenAccount,
+        isSigner: false,
+        isWritable: true,
+      });
+    }
+
+    const configRegistry = await nftUtils.initializeConfigRegistry(
+      program,
+      primaryWallet
+    );
+
+    adminConfig = await nftUtils.initializeAdminConfig(program, primaryWallet);
+
+    config = await nftUtils.getNftConfigPubkey(chillMint, program.programId);
+
+    await program.methods
+      .initialize(nftUtils.randomFees(), recipients)
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        adminConfig,
+        payer: primaryWallet.publicKey,
+        config,
+        configRegistry,
+        chillMint,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(nftUtils.adminSignerMetas([primaryWallet.publicKey]))
+      .signers([primaryWallet])
+      .rpc();
+  });
+
+  it("Try to buy NFT for a wrong price", async () => {
+    const [nftMint] = await listNft();
+    await assert.rejects(
+      buyNft(nftMint, price.subn(1)),
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "WrongListingPrice");
+        return true;
+      }
+    );
+  });
+
+  it("Buy NFT", async () => {
+    const [nftMint] = await listNft();
+    await buyNft(nftMint, price);
+
+    const buyerNftToken = await utils.getAssociatedTokenAddress(
+      buyer.publicKey,
+      nftMint
+    );
+    assert.equal(await utils.tokenBalance(buyerNftToken), 1);
+    assert.equal(
+      await utils.tokenBalance(buyerChillTokenAccount),
+      initialTokenBalance - price.toNumber()
+    );
+
+    let recipientsBalance = 0;
+    for (const tokenAccount of recipientsTokenAccounts) {
+      recipientsBalance += await utils.tokenBalance(tokenAccount.pubkey);
+    }
+    assert.equal(recipientsBalance, price.toNumber());
+
+    const connection = program.provider.connection;
+    const nftListing = await getNftListingPubkey(nftMint);
+    assert.equal(await connection.getAccountInfo(nftListing), null);
+  });
+
+  it("Delist NFT", async () => {
+    const [nftMint, nftToken] = await listNft();
+    const nftListing = await getNftListingPubkey(nftMint);
+    const nftEscrow = await utils.getAssociatedTokenAddress(
+      nftListing,
+      nftMint
+    );
+
+    await program.methods
+      .delistNft()
+      .accounts({
+        seller: primaryWallet.publicKey,
+        nftTokenAccount: nftToken,
+        nftListing,
+        nftEscrow,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([primaryWallet])
+      .rpc();
+
+    assert.equal(await utils.tokenBalance(nftToken), 1);
+
+    const connection = program.provider.connection;
+    assert.equal(await connection.getAccountInfo(nftListing), null);
+    assert.equal(await connection.getAccountInfo(nftEscrow), null);
+  });
+
+  it("Try to list and buy NFT without recipients", async () => {
+    const [nftMint] = await listNft();
+
+    await program.methods
+      .updateRecipients([])
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        adminConfig,
+        config,
+        chillMint,
+      })
+      .remainingAccounts(nftUtils.adminSignerMetas([primaryWallet.publicKey]))
+      .signers([primaryWallet])
+      .rpc();
+
+    // Nobody would be paid, so the NFT would be handed over for free
+    await assert.rejects(buyNft(nftMint, price, []), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "NoSaleRecipients");
+      return true;
+    });
+
+    await assert.rejects(listNft(), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "NoSaleRecipients");
+      return true;
+    });
+  });
+});
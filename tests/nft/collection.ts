@@ -0,0 +1,302 @@
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as nftUtils from "./utils";
+import { AnchorError, AnchorProvider, Program } from "@project-serum/anchor";
+import { ChillNft } from "../../target/types/chill_nft";
+import {
+  AccountMeta,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+} from "@solana/web3.js";
+import { programs } from "@metaplex/js";
+import * as assert from "assert";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("NFT | Collection", () => {
+  anchor.setProvider(AnchorProvider.env());
+  const program = anchor.workspace.ChillNft as Program<ChillNft>;
+  const Metadata = programs.metadata;
+
+  let primaryWallet: Keypair;
+  let chillMint: PublicKey;
+  let chillPayerTokenAccount: PublicKey;
+  let config: PublicKey;
+  let collectionMint: PublicKey;
+
+  async function collectionMetas(mint: PublicKey): Promise<AccountMeta[]> {
+    return [
+      { pubkey: mint, isSigner: false, isWritable: false },
+      {
+        pubkey: await Metadata.Metadata.getPDA(mint),
+        isSigner: false,
+        isWritable: false,
+      },
+      {
+        pubkey: await Metadata.MasterEdition.getPDA(mint),
+        isSigner: false,
+        isWritable: false,
+      },
+    ];
+  }
+
+  async function mintNft(
+    collection: PublicKey | null,
+    remainingAccounts: AccountMeta[]
+  ): Promise<PublicKey> {
+    const nftMint = await utils.createMint(primaryWallet.publicKey, 0);
+    const nftToken = await utils.createTokenAccount(
+      primaryWallet.publicKey,
+      nftMint
+    );
+    await utils.mintTokens(primaryWallet, nftMint, nftToken, 1);
+
+    await program.methods
+      .mintNft(
+        nftUtils.randomNftType(),
+        nftUtils.randomNftArgs(),
+        null,
+        0,
+        collection,
+        null
+      )
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        payer: primaryWallet.publicKey,
+        chillPayer: primaryWallet.publicKey,
+        chillPayerTokenAccount,
+        config,
+        chillMint,
+        nftTypeRegistry: await nftUtils.getNftTypeRegistryPubkey(
+          config,
+          program.programId
+        ),
+        nftMint,
+        nftMetadata: await Metadata.Metadata.getPDA(nftMint),
+        nftMasterEdition: await Metadata.MasterEdition.getPDA(nftMint),
+        nftChillMetadata: await nftUtils.getChillMetadataPubkey(
+          nftMint,
+          program.programId
+        ),
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMetadataProgram: Metadata.MetadataProgram.PUBKEY,
+      })
+      .remainingAccounts(remainingAccounts)
+      .signers([primaryWallet])
+      .rpc();
+
+    return nftMint;
+  }
+
+  async function mintNftBatch(
+    collection: PublicKey,
+    number: number,
+    payer: Keypair = primaryWallet
+  ): Promise<PublicKey[]> {
+    const nftMints: PublicKey[] = [];
+    const nftAccounts: AccountMeta[] = [];
+    for (let i = 0; i < number; i++) {
+      const nftMint = await utils.createMint(primaryWallet.publicKey, 0);
+      const nftToken = await utils.createTokenAccount(
+        primaryWallet.publicKey,
+        nftMint
+      );
+      await utils.mintTokens(primaryWallet, nftMint, nftToken, 1);
+
+      const pubkeys = [
+        nftMint,
+        await Metadata.Metadata.getPDA(nftMint),
+        await Metadata.MasterEdition.getPDA(nftMint),
+        await nftUtils.getChillMetadataPubkey(nftMint, program.programId),
+      ];
+
+      nftMints.push(nftMint);
+      nftAccounts.push(
+        ...pubkeys.map((pubkey) => ({
+          pubkey,
+          isSigner: false,
+          isWritable: true,
+        }))
+      );
+    }
+
+    const batch = nftMints.map(() => ({
+      nftType: nftUtils.randomNftType(),
+      args: nftUtils.randomNftArgs(),
+      creator: null,
+      minGameVersion: 0,
+      uses: null,
+    }));
+
+    await program.methods
+      .mintNftBatch(batch, collection)
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        payer: payer.publicKey,
+        chillPayer: primaryWallet.publicKey,
+        chillPayerTokenAccount,
+        config,
+        chillMint,
+        nftTypeRegistry: await nftUtils.getNftTypeRegistryPubkey(
+          config,
+          program.programId
+        ),
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMetadataProgram: Metadata.MetadataProgram.PUBKEY,
+      })
+      .remainingAccounts([
+        ...nftAccounts,
+        ...(await collectionMetas(collection)),
+      ])
+      .signers([primaryWallet, payer])
+      .rpc();
+
+    return nftMints;
+  }
+
+  before(async () => {
+    primaryWallet = await utils.keypairWithSol();
+    chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+    chillPayerTokenAccount = await utils.createTokenAccount(
+      primaryWallet.publicKey,
+      chillMint
+    );
+
+    const configRegistry = await nftUtils.initializeConfigRegistry(
+      program,
+      primaryWallet
+    );
+
+    const adminConfig = await nftUtils.initializeAdminConfig(
+      program,
+      primaryWallet
+    );
+
+    config = await nftUtils.getNftConfigPubkey(chillMint, program.programId);
+
+    await program.methods
+      .initialize(nftUtils.randomFees(), [])
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        adminConfig,
+        payer: primaryWallet.publicKey,
+        config,
+        configRegistry,
+        chillMint,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(nftUtils.adminSignerMetas([primaryWallet.publicKey]))
+      .signers([primaryWallet])
+      .rpc();
+  });
+
+  it("Create collection", async () => {
+    collectionMint = await utils.createMint(primaryWallet.publicKey, 0);
+    const collectionToken = await utils.createTokenAccount(
+      primaryWallet.publicKey,
+      collectionMint
+    );
+    await utils.mintTokens(primaryWallet, collectionMint, collectionToken, 1);
+
+    const collectionMetadata = await Metadata.Metadata.getPDA(collectionMint);
+    await program.methods
+      .createCollection(nftUtils.randomNftArgs())
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        payer: primaryWallet.publicKey,
+        config,
+        collectionMint,
+        collectionMetadata,
+        collectionMasterEdition: await Metadata.MasterEdition.getPDA(
+          collectionMint
+        ),
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        tokenMetadataProgram: Metadata.MetadataProgram.PUBKEY,
+      })
+      .signers([primaryWallet])
+      .rpc();
+
+    const metadata = await Metadata.Metadata.load(
+      program.provider.connection,
+      collectionMetadata
+    );
+    assert.equal(
+      metadata.data.updateAuthority,
+      primaryWallet.publicKey.toString()
+    );
+  });
+
+  it("Try to mint into a collection without collection accounts", async () => {
+    await assert.rejects(mintNft(collectionMint, []), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "WrongCollectionAccounts");
+      return true;
+    });
+  });
+
+  it("Try to mint with accounts of another collection", async () => {
+    const otherMint = Keypair.generate().publicKey;
+    await assert.rejects(
+      mintNft(collectionMint, await collectionMetas(otherMint)),
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "WrongCollectionAccounts");
+        return true;
+      }
+    );
+  });
+
+  it("Mint NFT into the collection", async () => {
+    const nftMint = await mintNft(
+      collectionMint,
+      await collectionMetas(collectionMint)
+    );
+
+    const metadata = await Metadata.Metadata.load(
+      program.provider.connection,
+      await Metadata.Metadata.getPDA(nftMint)
+    );
+    assert.equal(metadata.data.collection.key, collectionMint.toString());
+    assert.ok(metadata.data.collection.verified);
+  });
+
+  it("Mint NFT batch into the collection", async () => {
+    const nftMints = await mintNftBatch(collectionMint, 2);
+
+    for (const nftMint of nftMints) {
+      const metadata = await Metadata.Metadata.load(
+        program.provider.connection,
+        await Metadata.Metadata.getPDA(nftMint)
+      );
+      assert.equal(metadata.data.collection.key, collectionMint.toString());
+      assert.ok(metadata.data.collection.verified);
+    }
+  });
+
+  it("Mint NFT batch into the collection with a separate payer", async () => {
+    // The collection authority must be writable even if it pays for nothing
+    const payer = await utils.keypairWithSol();
+    const [nftMint] = await mintNftBatch(collectionMint, 1, payer);
+
+    const metadata = await Metadata.Metadata.load(
+      program.provider.connection,
+      await Metadata.Metadata.getPDA(nftMint)
+    );
+    assert.equal(metadata.data.collection.key, collectionMint.toString());
+    assert.ok(metadata.data.collection.verified);
+  });
+
+  it("Mint NFT without a collection", async () => {
+    const nftMint = await mintNft(null, []);
+
+    const metadata = await Metadata.Metadata.load(
+      program.provider.connection,
+      await Metadata.Metadata.getPDA(nftMint)
+    );
+    assert.equal(metadata.data.collection, null);
+  });
+});
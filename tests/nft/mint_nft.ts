@@ -9,6 +9,7 @@ import {
   PublicKey,
   SystemProgram,
   SYSVAR_RENT_PUBKEY,
+  Transaction,
 } from "@solana/web3.js";
 import { programs } from "@metaplex/js";
 import * as assert from "assert";
@@ -19,15 +20,20 @@ describe("NFT | Initialize", () => {
   const program = anchor.workspace.ChillNft as Program<ChillNft>;
 
   const primaryWallet = Keypair.generate();
+  const adminSigners = nftUtils.adminSignerMetas([primaryWallet.publicKey]);
 
   let payer: Keypair;
   let config: PublicKey;
+  let configRegistry: PublicKey;
+  let adminConfig: PublicKey;
   let chillMint: PublicKey;
 
   before(async () => {
     payer = await utils.keypairWithSol();
     chillMint = await utils.createMint(primaryWallet.publicKey, 9);
     config = await nftUtils.getNftConfigPubkey(chillMint, program.programId);
+    configRegistry = await nftUtils.initializeConfigRegistry(program, payer);
+    adminConfig = await nftUtils.initializeAdminConfig(program, primaryWallet);
   });
 
   it("Try to initialize with wrong mint authority", async () => {
@@ -46,12 +52,15 @@ describe("NFT | Initialize", () => {
           .initialize(fees, recipients)
           .accounts({
             primaryWallet: primaryWallet.publicKey,
+            adminConfig,
             payer: payer.publicKey,
             config: wrongConfig,
+            configRegistry,
             chillMint: wrongChillMint,
             systemProgram: SystemProgram.programId,
           })
-          .signers([payer])
+          .remainingAccounts(adminSigners)
+          .signers([payer, primaryWallet])
           .rpc();
       },
       (err: anchor.AnchorError) => {
@@ -62,6 +71,41 @@ describe("NFT | Initialize", () => {
     );
   });
 
+  it("Try to initialize without primary wallet signature", async () => {
+    const fees = nftUtils.randomFees();
+    const recipients = nftUtils.randomRecipients();
+
+    const ix = await program.methods
+      .initialize(fees, recipients)
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        adminConfig,
+        payer: payer.publicKey,
+        config,
+        configRegistry,
+        chillMint,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(adminSigners)
+      .instruction();
+
+    ix.keys
+      .filter((meta) => meta.pubkey.equals(primaryWallet.publicKey))
+      .forEach((meta) => (meta.isSigner = false));
+
+    await assert.rejects(
+      async () => {
+        await program.provider.sendAndConfirm(new Transaction().add(ix), [
+          payer,
+        ]);
+      },
+      (err: Error) => {
+        assert.ok(err.message.includes("0x1779"));
+        return true;
+      }
+    );
+  });
+
   it("Try to initialize with invalid recipients number", async () => {
     const fees = nftUtils.randomFees();
     const recipients = nftUtils.randomRecipients(nftUtils.MAX_RECIPIENTS + 1);
@@ -72,12 +116,15 @@ describe("NFT | Initialize", () => {
           .initialize(fees, recipients)
           .accounts({
             primaryWallet: primaryWallet.publicKey,
+            adminConfig,
             payer: payer.publicKey,
             config,
+            configRegistry,
             chillMint,
             systemProgram: SystemProgram.programId,
           })
-          .signers([payer])
+          .remainingAccounts(adminSigners)
+          .signers([payer, primaryWallet])
           .rpc();
       },
       (err: anchor.AnchorError) => {
@@ -116,12 +163,15 @@ describe("NFT | Initialize", () => {
             .initialize(fees, recipients)
             .accounts({
               primaryWallet: primaryWallet.publicKey,
+              adminConfig,
               payer: payer.publicKey,
               config,
+              configRegistry,
               chillMint,
               systemProgram: SystemProgram.programId,
             })
-            .signers([payer])
+            .remainingAccounts(adminSigners)
+            .signers([payer, primaryWallet])
             .rpc();
         },
         (err: anchor.AnchorError) => {
@@ -143,12 +193,15 @@ describe("NFT | Initialize", () => {
           .initialize(fees, recipients)
           .accounts({
             primaryWallet: primaryWallet.publicKey,
+            adminConfig,
             payer: payer.publicKey,
             config,
+            configRegistry,
             chillMint,
             systemProgram: SystemProgram.programId,
           })
-          .signers([payer])
+          .remainingAccounts(adminSigners)
+          .signers([payer, primaryWallet])
           .rpc();
       },
       (err: anchor.AnchorError) => {
@@ -166,12 +219,15 @@ describe("NFT | Initialize", () => {
       .initialize(fees, recipients)
       .accounts({
         primaryWallet: primaryWallet.publicKey,
+        adminConfig,
         payer: payer.publicKey,
         config,
+        configRegistry,
         chillMint,
         systemProgram: SystemProgram.programId,
       })
-      .signers([payer])
+      .remainingAccounts(adminSigners)
+      .signers([payer, primaryWallet])
       .rpc();
 
     const configData = await program.account.config.fetch(config);
@@ -180,6 +236,11 @@ describe("NFT | Initialize", () => {
       JSON.stringify(configData.recipients),
       JSON.stringify(recipients)
     );
+
+    const configRegistryData = await program.account.configRegistry.fetch(
+      configRegistry
+    );
+    assert.ok(configRegistryData.configs.some((c) => c.equals(config)));
   });
 
   it("Try to initialize twice", async () => {
@@ -191,12 +252,15 @@ describe("NFT | Initialize", () => {
         .initialize(fees, recipients)
         .accounts({
           primaryWallet: primaryWallet.publicKey,
+          adminConfig,
           payer: payer.publicKey,
           config,
+          configRegistry,
           chillMint,
           systemProgram: SystemProgram.programId,
         })
-        .signers([payer])
+        .remainingAccounts(adminSigners)
+        .signers([payer, primaryWallet])
         .rpc();
     });
   });
@@ -209,12 +273,15 @@ describe("NFT | Update", () => {
   const Metadata = programs.metadata;
 
   const primaryWallet = Keypair.generate();
+  const adminSigners = nftUtils.adminSignerMetas([primaryWallet.publicKey]);
   const chillPayer = Keypair.generate();
   const user = Keypair.generate();
 
   let payer: Keypair;
   let chillPayerTokenAccount: PublicKey;
   let config: PublicKey;
+  let configRegistry: PublicKey;
+  let adminConfig: PublicKey;
   let chillMint: PublicKey;
 
   const fees = nftUtils.randomFees();
@@ -230,6 +297,8 @@ describe("NFT | Update", () => {
     payer = await utils.keypairWithSol();
     chillMint = await utils.createMint(primaryWallet.publicKey, 9);
     config = await nftUtils.getNftConfigPubkey(chillMint, program.programId);
+    configRegistry = await nftUtils.initializeConfigRegistry(program, payer);
+    adminConfig = await nftUtils.initializeAdminConfig(program, primaryWallet);
 
     for (let i = 0; i < recipients.length; i++) {
       const tokenAccount = await utils.createTokenAccount(
@@ -250,12 +319,15 @@ describe("NFT | Update", () => {
       .initialize(fees, recipients)
       .accounts({
         primaryWallet: primaryWallet.publicKey,
+        adminConfig,
         payer: payer.publicKey,
         config,
+        configRegistry,
         chillMint,
         systemProgram: SystemProgram.programId,
       })
-      .signers([payer])
+      .remainingAccounts(adminSigners)
+      .signers([payer, primaryWallet])
       .rpc();
 
     chillPayerTokenAccount = await utils.createTokenAccount(
@@ -285,9 +357,10 @@ describe("NFT | Update", () => {
 
     const nftType = nftUtils.randomNftType();
     const nftArgs = nftUtils.randomNftArgs();
+    const minGameVersion = 3;
 
     await program.methods
-      .mintNft(nftType, nftArgs, null)
+      .mintNft(nftType, nftArgs, null, minGameVersion, null, null)
       .accounts({
         primaryWallet: primaryWallet.publicKey,
         payer: payer.publicKey,
@@ -295,6 +368,10 @@ describe("NFT | Update", () => {
         chillPayerTokenAccount,
         config,
         chillMint,
+        nftTypeRegistry: await nftUtils.getNftTypeRegistryPubkey(
+          config,
+          program.programId
+        ),
         nftMint,
         nftMetadata,
         nftMasterEdition,
@@ -316,6 +393,8 @@ describe("NFT | Update", () => {
       JSON.stringify(chillMetadata.nftType),
       JSON.stringify(nftType)
     );
+    assert.equal(chillMetadata.minGameVersion, minGameVersion);
+    assert.deepEqual(chillMetadata.rentReceiver, primaryWallet.publicKey);
 
     const chillFeesAmount = nftUtils.feesOf(fees, nftType).toNumber();
     const recipientsTokenAmounts: number[] = [];
@@ -366,11 +445,17 @@ describe("NFT | Update", () => {
 
   it("Update NFT", async () => {
     const newNftArgs = nftUtils.randomNftArgs();
+    const nftChillMetadata = await nftUtils.getChillMetadataPubkey(
+      nftMint,
+      program.programId
+    );
+
     await program.methods
-      .updateNft(newNftArgs)
+      .updateNft(newNftArgs, null, null)
       .accounts({
         primaryWallet: primaryWallet.publicKey,
         nftMetadata,
+        nftChillMetadata,
         tokenMetadataProgram: Metadata.MetadataProgram.PUBKEY,
       })
       .signers([primaryWallet])
@@ -392,4 +477,246 @@ describe("NFT | Update", () => {
     assert.equal(creators[0].verified, true);
     assert.equal(creators[0].share, 100);
   });
+
+  it("Try to update NFT creators without the primary wallet", async () => {
+    const nftChillMetadata = await nftUtils.getChillMetadataPubkey(
+      nftMint,
+      program.programId
+    );
+
+    await assert.rejects(
+      async () => {
+        await program.methods
+          .updateNft(
+            nftUtils.randomNftArgs(),
+            [{ address: Keypair.generate().publicKey, share: 100 }],
+            null
+          )
+          .accounts({
+            primaryWallet: primaryWallet.publicKey,
+            nftMetadata,
+            nftChillMetadata,
+            tokenMetadataProgram: Metadata.MetadataProgram.PUBKEY,
+          })
+          .signers([primaryWallet])
+          .rpc();
+      },
+      (err: anchor.AnchorError) => {
+        assert.equal(err.error.errorCode.code, "WrongCreators");
+        return true;
+      }
+    );
+  });
+
+  it("Update NFT creators and primary sale", async () => {
+    const creator = Keypair.generate().publicKey;
+    const nftChillMetadata = await nftUtils.getChillMetadataPubkey(
+      nftMint,
+      program.programId
+    );
+
+    await program.methods
+      .updateNft(
+        nftUtils.randomNftArgs(),
+        [
+          { address: primaryWallet.publicKey, share: 10 },
+          { address: creator, share: 90 },
+        ],
+        true
+      )
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        nftMetadata,
+        nftChillMetadata,
+        tokenMetadataProgram: Metadata.MetadataProgram.PUBKEY,
+      })
+      .signers([primaryWallet])
+      .rpc();
+
+    const metadata = await Metadata.Metadata.load(
+      program.provider.connection,
+      nftMetadata
+    );
+    assert.equal(metadata.data.primarySaleHappened, true);
+
+    const creators = metadata.data.data.creators;
+    assert.equal(creators.length, 2);
+    assert.equal(creators[0].address, primaryWallet.publicKey.toString());
+    assert.equal(creators[0].verified, true);
+    assert.equal(creators[0].share, 10);
+    assert.equal(creators[1].address, creator.toString());
+    assert.equal(creators[1].verified, false);
+    assert.equal(creators[1].share, 90);
+  });
+
+  it("Update min game version", async () => {
+    const nftChillMetadata = await nftUtils.getChillMetadataPubkey(
+      nftMint,
+      program.programId
+    );
+
+    await program.methods
+      .updateMinGameVersion(5)
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        nftMetadata,
+        nftChillMetadata,
+      })
+      .signers([primaryWallet])
+      .rpc();
+
+    const chillMetadata = await program.account.chillNftMetadata.fetch(
+      nftChillMetadata
+    );
+    assert.equal(chillMetadata.minGameVersion, 5);
+  });
+
+  it("Try to update min game version with wrong authority", async () => {
+    const nftChillMetadata = await nftUtils.getChillMetadataPubkey(
+      nftMint,
+      program.programId
+    );
+
+    await assert.rejects(
+      async () => {
+        await program.methods
+          .updateMinGameVersion(0)
+          .accounts({
+            primaryWallet: payer.publicKey,
+            nftMetadata,
+            nftChillMetadata,
+          })
+          .signers([payer])
+          .rpc();
+      },
+      (err: anchor.AnchorError) => {
+        assert.equal(err.error.errorCode.code, "WrongUpdateAuthority");
+        return true;
+      }
+    );
+  });
+
+  it("Finalize NFT", async () => {
+    const nftChillMetadata = await nftUtils.getChillMetadataPubkey(
+      nftMint,
+      program.programId
+    );
+
+    await program.methods
+      .finalizeNft()
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        nftMetadata,
+        nftChillMetadata,
+        tokenMetadataProgram: Metadata.MetadataProgram.PUBKEY,
+      })
+      .signers([primaryWallet])
+      .rpc();
+
+    const metadata = await Metadata.Metadata.load(
+      program.provider.connection,
+      nftMetadata
+    );
+    assert.equal(metadata.data.isMutable, false);
+
+    const chillMetadata = await program.account.chillNftMetadata.fetch(
+      nftChillMetadata
+    );
+    assert.equal(chillMetadata.finalized, true);
+  });
+
+  it("Try to update finalized NFT", async () => {
+    const nftChillMetadata = await nftUtils.getChillMetadataPubkey(
+      nftMint,
+      program.programId
+    );
+
+    await assert.rejects(
+      async () => {
+        await program.methods
+          .updateNft(nftUtils.randomNftArgs(), null, null)
+          .accounts({
+            primaryWallet: primaryWallet.publicKey,
+            nftMetadata,
+            nftChillMetadata,
+            tokenMetadataProgram: Metadata.MetadataProgram.PUBKEY,
+          })
+          .signers([primaryWallet])
+          .rpc();
+      },
+      (err: anchor.AnchorError) => {
+        assert.equal(err.error.errorCode.code, "NftIsFinalized");
+        return true;
+      }
+    );
+  });
+
+  it("Try to reclaim metadata of existing NFT", async () => {
+    const nftChillMetadata = await nftUtils.getChillMetadataPubkey(
+      nftMint,
+      program.programId
+    );
+
+    await assert.rejects(
+      async () => {
+        await program.methods
+          .reclaimMetadata()
+          .accounts({
+            nftMint,
+            nftChillMetadata,
+            rentReceiver: primaryWallet.publicKey,
+          })
+          .rpc();
+      },
+      (err: anchor.AnchorError) => {
+        assert.equal(err.error.errorCode.code, "NftIsNotBurned");
+        return true;
+      }
+    );
+  });
+
+  it("Reclaim metadata of burned NFT", async () => {
+    const connection = program.provider.connection;
+    const nftChillMetadata = await nftUtils.getChillMetadataPubkey(
+      nftMint,
+      program.programId
+    );
+
+    await utils.burnTokens(user, nftMint, nftToken, 1);
+
+    await assert.rejects(
+      async () => {
+        await program.methods
+          .reclaimMetadata()
+          .accounts({
+            nftMint,
+            nftChillMetadata,
+            rentReceiver: payer.publicKey,
+          })
+          .rpc();
+      },
+      (err: anchor.AnchorError) => {
+        assert.equal(err.error.errorCode.code, "ConstraintHasOne");
+        return true;
+      }
+    );
+
+    const rent = (await connection.getAccountInfo(nftChillMetadata)).lamports;
+    const balance = await connection.getBalance(primaryWallet.publicKey);
+
+    await program.methods
+      .reclaimMetadata()
+      .accounts({
+        nftMint,
+        nftChillMetadata,
+        rentReceiver: primaryWallet.publicKey,
+      })
+      .rpc();
+
+    assert.equal(await connection.getAccountInfo(nftChillMetadata), null);
+    assert.equal(
+      await connection.getBalance(primaryWallet.publicKey),
+      balance + rent
+    );
+  });
 });
@@ -0,0 +1,197 @@
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as nftUtils from "./utils";
+import { AnchorError, AnchorProvider, Program } from "@project-serum/anchor";
+import { ChillNft } from "../../target/types/chill_nft";
+import {
+  AccountMeta,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+} from "@solana/web3.js";
+import { programs } from "@metaplex/js";
+import * as assert from "assert";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("NFT | Mint NFT batch", () => {
+  anchor.setProvider(AnchorProvider.env());
+  const program = anchor.workspace.ChillNft as Program<ChillNft>;
+  const Metadata = programs.metadata;
+
+  const fees = nftUtils.randomFees();
+  const recipients = nftUtils.randomRecipients();
+  const initialTokenBalance = 1_000_000_000;
+  const recipientsTokenAccounts: AccountMeta[] = [];
+
+  let primaryWallet: Keypair;
+  let chillMint: PublicKey;
+  let chillPayerTokenAccount: PublicKey;
+  let config: PublicKey;
+
+  async function nftAccounts(nftMint: PublicKey): Promise<AccountMeta[]> {
+    const pubkeys = [
+      nftMint,
+      await Metadata.Metadata.getPDA(nftMint),
+      await Metadata.MasterEdition.getPDA(nftMint),
+      await nftUtils.getChillMetadataPubkey(nftMint, program.programId),
+    ];
+
+    return pubkeys.map((pubkey) => ({
+      pubkey,
+      isSigner: false,
+      isWritable: true,
+    }));
+  }
+
+  async function createNftMint(): Promise<PublicKey> {
+    const nftMint = await utils.createMint(primaryWallet.publicKey, 0);
+    const nftToken = await utils.createTokenAccount(
+      primaryWallet.publicKey,
+      nftMint
+    );
+    await utils.mintTokens(primaryWallet, nftMint, nftToken, 1);
+    return nftMint;
+  }
+
+  async function mintNftBatch(
+    nfts: nftUtils.NftType[],
+    nftMints: PublicKey[]
+  ) {
+    const nftMintsAccounts: AccountMeta[] = [];
+    for (const nftMint of nftMints) {
+      nftMintsAccounts.push(...(await nftAccounts(nftMint)));
+    }
+
+    const batch = nfts.map((nftType) => ({
+      nftType,
+      args: nftUtils.randomNftArgs(),
+      creator: null,
+      minGameVersion: 1,
+      uses: null,
+    }));
+
+    await program.methods
+      .mintNftBatch(batch, null)
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        payer: primaryWallet.publicKey,
+        chillPayer: primaryWallet.publicKey,
+        chillPayerTokenAccount,
+        config,
+        chillMint,
+        nftTypeRegistry: await nftUtils.getNftTypeRegistryPubkey(
+          config,
+          program.programId
+        ),
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMetadataProgram: Metadata.MetadataProgram.PUBKEY,
+      })
+      .remainingAccounts([...recipientsTokenAccounts, ...nftMintsAccounts])
+      .signers([primaryWallet])
+      .rpc();
+  }
+
+  before(async () => {
+    primaryWallet = await utils.keypairWithSol();
+    chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+    chillPayerTokenAccount = await utils.createTokenAccount(
+      primaryWallet.publicKey,
+      chillMint
+    );
+
+    await utils.mintTokens(
+      primaryWallet,
+      chillMint,
+      chillPayerTokenAccount,
+      initialTokenBalance
+    );
+
+    for (const recipient of recipients) {
+      const tokenAccount = await utils.createTokenAccount(
+        recipient.address,
+        chillMint
+      );
+
+      recipientsTokenAccounts.push({
+        pubkey: tokenAccount,
+        isSigner: false,
+        isWritable: true,
+      });
+    }
+
+    const configRegistry = await nftUtils.initializeConfigRegistry(
+      program,
+      primaryWallet
+    );
+
+    const adminConfig = await nftUtils.initializeAdminConfig(
+      program,
+      primaryWallet
+    );
+
+    config = await nftUtils.getNftConfigPubkey(chillMint, program.programId);
+
+    await program.methods
+      .initialize(fees, recipients)
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        adminConfig,
+        payer: primaryWallet.publicKey,
+        config,
+        configRegistry,
+        chillMint,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(nftUtils.adminSignerMetas([primaryWallet.publicKey]))
+      .signers([primaryWallet])
+      .rpc();
+  });
+
+  it("Try to mint with a wrong number of NFT accounts", async () => {
+    const nftTypes = [nftUtils.randomNftType(), nftUtils.randomNftType()];
+    const nftMint = await createNftMint();
+
+    await assert.rejects(
+      mintNftBatch(nftTypes, [nftMint]),
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "WrongMintBatch");
+        return true;
+      }
+    );
+  });
+
+  it("Mint NFT batch", async () => {
+    const nftTypes = [nftUtils.randomNftType(), nftUtils.randomNftType()];
+    const nftMints = [await createNftMint(), await createNftMint()];
+
+    await mintNftBatch(nftTypes, nftMints);
+
+    for (const index in nftMints) {
+      const chillMetadata = await program.account.chillNftMetadata.fetch(
+        await nftUtils.getChillMetadataPubkey(
+          nftMints[index],
+          program.programId
+        )
+      );
+
+      assert.equal(
+        JSON.stringify(chillMetadata.nftType),
+        JSON.stringify(nftTypes[index])
+      );
+      assert.equal(chillMetadata.minGameVersion, 1);
+      assert.ok(chillMetadata.rentReceiver.equals(primaryWallet.publicKey));
+    }
+
+    const chillFeesAmount = nftTypes
+      .map((nftType) => nftUtils.feesOf(fees, nftType).toNumber())
+      .reduce((a, b) => a + b);
+
+    assert.equal(
+      await utils.tokenBalance(chillPayerTokenAccount),
+      initialTokenBalance - chillFeesAmount
+    );
+  });
+});
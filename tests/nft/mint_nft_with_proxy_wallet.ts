@@ -244,15 +244,28 @@ describe("NFT | Mint with proxy wallet", () => {
 
   it("Initialize NFT program", async () => {
     config = await nftUtils.getNftConfigPubkey(chillMint, nftProgram.programId);
+    const configRegistry = await nftUtils.initializeConfigRegistry(
+      nftProgram,
+      primaryWallet
+    );
+
+    const adminConfig = await nftUtils.initializeAdminConfig(
+      nftProgram,
+      primaryWallet
+    );
+
     await nftProgram.methods
       .initialize(fees, recipients)
       .accounts({
         primaryWallet: primaryWallet.publicKey,
+        adminConfig,
         payer: primaryWallet.publicKey,
         config,
+        configRegistry,
         chillMint,
         systemProgram: SystemProgram.programId,
       })
+      .remainingAccounts(nftUtils.adminSignerMetas([primaryWallet.publicKey]))
       .rpc();
   });
 
@@ -398,7 +411,7 @@ describe("NFT | Mint with proxy wallet", () => {
     );
 
     const mintNftIx = await nftProgram.methods
-      .mintNft(nftType, nftArgs, user.publicKey)
+      .mintNft(nftType, nftArgs, user.publicKey, 0, null, null)
       .accounts({
         primaryWallet: primaryWallet.publicKey,
         payer: primaryWallet.publicKey,
@@ -406,6 +419,10 @@ describe("NFT | Mint with proxy wallet", () => {
         chillPayerTokenAccount: primaryWalletChill,
         config,
         chillMint,
+        nftTypeRegistry: await nftUtils.getNftTypeRegistryPubkey(
+          config,
+          nftProgram.programId
+        ),
         nftMint: nftMint.publicKey,
         nftMetadata,
         nftMasterEdition,
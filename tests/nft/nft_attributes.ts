@@ -0,0 +1,188 @@
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as nftUtils from "./utils";
+import {
+  AnchorError,
+  AnchorProvider,
+  BN,
+  Program,
+} from "@project-serum/anchor";
+import { ChillNft } from "../../target/types/chill_nft";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+} from "@solana/web3.js";
+import { programs } from "@metaplex/js";
+import * as assert from "assert";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("NFT | Attributes", () => {
+  anchor.setProvider(AnchorProvider.env());
+  const program = anchor.workspace.ChillNft as Program<ChillNft>;
+  const Metadata = programs.metadata;
+
+  let primaryWallet: Keypair;
+  let user: Keypair;
+  let chillMint: PublicKey;
+  let config: PublicKey;
+
+  let nftMint: PublicKey;
+  let nftToken: PublicKey;
+  let nftChillMetadata: PublicKey;
+
+  const attributes = {
+    level: 7,
+    rarity: 3,
+    xp: new BN(1500),
+    custom: [new BN(1), new BN(2), new BN(0), new BN(4)],
+  };
+
+  async function upgradeNftAttributes(signer: Keypair) {
+    await program.methods
+      .upgradeNftAttributes(attributes)
+      .accounts({
+        primaryWallet: signer.publicKey,
+        nftMetadata: await Metadata.Metadata.getPDA(nftMint),
+        nftChillMetadata,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  before(async () => {
+    primaryWallet = await utils.keypairWithSol();
+    user = await utils.keypairWithSol();
+    chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+    const chillPayerTokenAccount = await utils.createTokenAccount(
+      primaryWallet.publicKey,
+      chillMint
+    );
+
+    const configRegistry = await nftUtils.initializeConfigRegistry(
+      program,
+      primaryWallet
+    );
+
+    const adminConfig = await nftUtils.initializeAdminConfig(
+      program,
+      primaryWallet
+    );
+
+    config = await nftUtils.getNftConfigPubkey(chillMint, program.programId);
+
+    await program.methods
+      .initialize(nftUtils.randomFees(), [])
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        adminConfig,
+        payer: primaryWallet.publicKey,
+        config,
+        configRegistry,
+        chillMint,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(nftUtils.adminSignerMetas([primaryWallet.publicKey]))
+      .signers([primaryWallet])
+      .rpc();
+
+    nftMint = await utils.createMint(primaryWallet.publicKey, 0);
+    nftToken = await utils.createTokenAccount(user.publicKey, nftMint);
+    await utils.mintTokens(primaryWallet, nftMint, nftToken, 1);
+
+    nftChillMetadata = await nftUtils.getChillMetadataPubkey(
+      nftMint,
+      program.programId
+    );
+
+    await program.methods
+      .mintNft(
+        nftUtils.randomNftType(),
+        nftUtils.randomNftArgs(),
+        null,
+        0,
+        null,
+        null
+      )
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        payer: primaryWallet.publicKey,
+        chillPayer: primaryWallet.publicKey,
+        chillPayerTokenAccount,
+        config,
+        chillMint,
+        nftTypeRegistry: await nftUtils.getNftTypeRegistryPubkey(
+          config,
+          program.programId
+        ),
+        nftMint,
+        nftMetadata: await Metadata.Metadata.getPDA(nftMint),
+        nftMasterEdition: await Metadata.MasterEdition.getPDA(nftMint),
+        nftChillMetadata,
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMetadataProgram: Metadata.MetadataProgram.PUBKEY,
+      })
+      .signers([primaryWallet])
+      .rpc();
+  });
+
+  it("New NFT has the current layout", async () => {
+    const info = await program.account.chillNftMetadata.fetch(nftChillMetadata);
+    assert.equal(info.version, 2);
+    assert.equal(info.attributes.level, 0);
+  });
+
+  it("Try to upgrade attributes by a wrong primary wallet", async () => {
+    const wrongPrimaryWallet = await utils.keypairWithSol();
+    await assert.rejects(
+      upgradeNftAttributes(wrongPrimaryWallet),
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "WrongUpdateAuthority");
+        return true;
+      }
+    );
+  });
+
+  it("Upgrade attributes", async () => {
+    await upgradeNftAttributes(primaryWallet);
+
+    const info = await program.account.chillNftMetadata.fetch(nftChillMetadata);
+    assert.equal(info.attributes.level, attributes.level);
+    assert.equal(info.attributes.rarity, attributes.rarity);
+    assert.equal(info.attributes.xp.toNumber(), attributes.xp.toNumber());
+    assert.deepEqual(
+      info.attributes.custom.map((value: BN) => value.toNumber()),
+      [1, 2, 0, 4]
+    );
+
+    const attributesView = await program.methods
+      .viewNftAttributes()
+      .accounts({ nftChillMetadata })
+      .view();
+    assert.equal(attributesView.level, attributes.level);
+  });
+
+  it("Try to migrate the current layout", async () => {
+    await assert.rejects(
+      program.methods
+        .migrateNftMetadata()
+        .accounts({
+          payer: user.publicKey,
+          config,
+          nftMint,
+          nftMetadata: await Metadata.Metadata.getPDA(nftMint),
+          nftChillMetadata,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc(),
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "NftMetadataIsMigrated");
+        return true;
+      }
+    );
+  });
+});
@@ -0,0 +1,239 @@
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as nftUtils from "./utils";
+import {
+  AnchorError,
+  AnchorProvider,
+  BN,
+  Program,
+} from "@project-serum/anchor";
+import { ChillNft } from "../../target/types/chill_nft";
+import {
+  AccountMeta,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+} from "@solana/web3.js";
+import { programs } from "@metaplex/js";
+import * as assert from "assert";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("NFT | NFT type registry", () => {
+  anchor.setProvider(AnchorProvider.env());
+  const program = anchor.workspace.ChillNft as Program<ChillNft>;
+  const Metadata = programs.metadata;
+
+  const fee = new BN(12_345);
+  const initialTokenBalance = 1_000_000_000;
+  const recipients = nftUtils.randomRecipients();
+  const recipientsTokenAccounts: AccountMeta[] = [];
+
+  let primaryWallet: Keypair;
+  let chillMint: PublicKey;
+  let chillPayerTokenAccount: PublicKey;
+  let adminConfig: PublicKey;
+  let config: PublicKey;
+  let nftTypeRegistry: PublicKey;
+
+  async function registerNftType(
+    signer: Keypair,
+    name: string,
+    typeFee: BN = fee
+  ) {
+    await program.methods
+      .registerNftType(name, typeFee)
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        adminConfig,
+        payer: signer.publicKey,
+        config,
+        chillMint,
+        nftTypeRegistry,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(nftUtils.adminSignerMetas([signer.publicKey]))
+      .signers([signer])
+      .rpc();
+  }
+
+  async function mintNft(nftType: nftUtils.NftType): Promise<PublicKey> {
+    const nftMint = await utils.createMint(primaryWallet.publicKey, 0);
+    const nftToken = await utils.createTokenAccount(
+      primaryWallet.publicKey,
+      nftMint
+    );
+    await utils.mintTokens(primaryWallet, nftMint, nftToken, 1);
+
+    await program.methods
+      .mintNft(nftType, nftUtils.randomNftArgs(), null, 0, null, null)
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        payer: primaryWallet.publicKey,
+        chillPayer: primaryWallet.publicKey,
+        chillPayerTokenAccount,
+        config,
+        chillMint,
+        nftTypeRegistry,
+        nftMint,
+        nftMetadata: await Metadata.Metadata.getPDA(nftMint),
+        nftMasterEdition: await Metadata.MasterEdition.getPDA(nftMint),
+        nftChillMetadata: await nftUtils.getChillMetadataPubkey(
+          nftMint,
+          program.programId
+        ),
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMetadataProgram: Metadata.MetadataProgram.PUBKEY,
+      })
+      .remainingAccounts(recipientsTokenAccounts)
+      .signers([primaryWallet])
+      .rpc();
+
+    return nftMint;
+  }
+
+  before(async () => {
+    primaryWallet = await utils.keypairWithSol();
+    chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+    chillPayerTokenAccount = await utils.createTokenAccount(
+      primaryWallet.publicKey,
+      chillMint
+    );
+
+    await utils.mintTokens(
+      primaryWallet,
+      chillMint,
+      chillPayerTokenAccount,
+      initialTokenBalance
+    );
+
+    for (const recipient of recipients) {
+      const tokenAccount = await utils.createTokenAccount(
+        recipient.address,
+        chillMint
+      );
+
+      recipientsTokenAccounts.push({
+        pubkey: tokenAccount,
+        isSigner: false,
+        isWritable: true,
+      });
+    }
+
+    const configRegistry = await nftUtils.initializeConfigRegistry(
+      program,
+      primaryWallet
+    );
+
+    adminConfig = await nftUtils.initializeAdminConfig(program, primaryWallet);
+
+    config = await nftUtils.getNftConfigPubkey(chillMint, program.programId);
+    nftTypeRegistry = await nftUtils.getNftTypeRegistryPubkey(
+      config,
+      program.programId
+    );
+
+    await program.methods
+      .initialize(nftUtils.randomFees(), recipients)
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        adminConfig,
+        payer: primaryWallet.publicKey,
+        config,
+        configRegistry,
+        chillMint,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(nftUtils.adminSignerMetas([primaryWallet.publicKey]))
+      .signers([primaryWallet])
+      .rpc();
+  });
+
+  it("Mint a built-in type without the registry", async () => {
+    await mintNft(nftUtils.randomNftType());
+  });
+
+  it("Try to register a type by a wrong signer", async () => {
+    const wrongSigner = await utils.keypairWithSol();
+    await assert.rejects(
+      registerNftType(wrongSigner, "vehicle"),
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "NotEnoughAdminSigners");
+        return true;
+      }
+    );
+  });
+
+  it("Try to register a type with a fee above the maximum", async () => {
+    const maxFee = new BN(nftUtils.MAX_UI_FEE).mul(new BN(10).pow(new BN(9)));
+    await assert.rejects(
+      registerNftType(primaryWallet, "vehicle", maxFee.addn(1)),
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "InvalidFees");
+        return true;
+      }
+    );
+  });
+
+  it("Try to register a built-in type", async () => {
+    await assert.rejects(
+      registerNftType(primaryWallet, "pet"),
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "WrongNftTypeName");
+        return true;
+      }
+    );
+  });
+
+  it("Register a type", async () => {
+    await registerNftType(primaryWallet, "vehicle");
+
+    const registry = await program.account.nftTypeRegistry.fetch(
+      nftTypeRegistry
+    );
+    assert.ok(registry.config.equals(config));
+    assert.equal(registry.types.length, 1);
+    assert.equal(registry.types[0].name, "vehicle");
+    assert.equal(registry.types[0].fee.toNumber(), fee.toNumber());
+  });
+
+  it("Try to register the same type twice", async () => {
+    await assert.rejects(
+      registerNftType(primaryWallet, "vehicle"),
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "WrongNftTypeName");
+        return true;
+      }
+    );
+  });
+
+  it("Try to mint an unknown type", async () => {
+    await assert.rejects(
+      mintNft({ custom: { 0: 1 } }),
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "UnknownNftType");
+        return true;
+      }
+    );
+  });
+
+  it("Mint a registered type", async () => {
+    const balanceBefore = await utils.tokenBalance(chillPayerTokenAccount);
+    const nftMint = await mintNft({ custom: { 0: 0 } });
+
+    const chillMetadata = await program.account.chillNftMetadata.fetch(
+      await nftUtils.getChillMetadataPubkey(nftMint, program.programId)
+    );
+    assert.equal(
+      JSON.stringify(chillMetadata.nftType),
+      JSON.stringify({ custom: { 0: 0 } })
+    );
+
+    assert.equal(
+      balanceBefore - (await utils.tokenBalance(chillPayerTokenAccount)),
+      fee.toNumber()
+    );
+  });
+});
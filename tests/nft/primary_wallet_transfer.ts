@@ -0,0 +1,125 @@
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as nftUtils from "./utils";
+import { AnchorError, AnchorProvider, Program } from "@project-serum/anchor";
+import { ChillNft } from "../../target/types/chill_nft";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+
+describe("NFT | Primary wallet transfer", () => {
+  anchor.setProvider(AnchorProvider.env());
+  const program = anchor.workspace.ChillNft as Program<ChillNft>;
+  const connection = program.provider.connection;
+
+  const newPrimaryWallet = Keypair.generate();
+
+  let primaryWallet: Keypair;
+  let config: PublicKey;
+  let primaryWalletTransfer: PublicKey;
+
+  async function propose(proposer: Keypair, newPrimaryWallet: PublicKey) {
+    await program.methods
+      .proposeNewPrimaryWallet(newPrimaryWallet)
+      .accounts({
+        primaryWallet: proposer.publicKey,
+        config,
+        primaryWalletTransfer,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([proposer])
+      .rpc();
+  }
+
+  async function accept(acceptor: Keypair) {
+    await program.methods
+      .acceptPrimaryWallet()
+      .accounts({
+        newPrimaryWallet: acceptor.publicKey,
+        primaryWallet: primaryWallet.publicKey,
+        config,
+        primaryWalletTransfer,
+      })
+      .signers([acceptor])
+      .rpc();
+  }
+
+  before(async () => {
+    primaryWallet = await utils.keypairWithSol();
+    const chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+    const configRegistry = await nftUtils.initializeConfigRegistry(
+      program,
+      primaryWallet
+    );
+
+    const adminConfig = await nftUtils.initializeAdminConfig(
+      program,
+      primaryWallet
+    );
+
+    config = await nftUtils.getNftConfigPubkey(chillMint, program.programId);
+    primaryWalletTransfer = (
+      await PublicKey.findProgramAddress(
+        [Buffer.from("primary-wallet-transfer"), config.toBytes()],
+        program.programId
+      )
+    )[0];
+
+    await program.methods
+      .initialize(nftUtils.randomFees(), nftUtils.randomRecipients())
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        adminConfig,
+        payer: primaryWallet.publicKey,
+        config,
+        configRegistry,
+        chillMint,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(nftUtils.adminSignerMetas([primaryWallet.publicKey]))
+      .signers([primaryWallet])
+      .rpc();
+  });
+
+  it("Try to propose by a wrong primary wallet", async () => {
+    const wrongPrimaryWallet = await utils.keypairWithSol();
+    await assert.rejects(
+      propose(wrongPrimaryWallet, newPrimaryWallet.publicKey)
+    );
+  });
+
+  it("Try to propose the current primary wallet", async () => {
+    await assert.rejects(
+      propose(primaryWallet, primaryWallet.publicKey),
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "WrongNewPrimaryWallet");
+        return true;
+      }
+    );
+  });
+
+  it("Propose a new primary wallet", async () => {
+    await propose(primaryWallet, Keypair.generate().publicKey);
+
+    // A new proposal replaces the pending one
+    await propose(primaryWallet, newPrimaryWallet.publicKey);
+
+    const transfer = await program.account.primaryWalletTransfer.fetch(
+      primaryWalletTransfer
+    );
+
+    assert.ok(transfer.config.equals(config));
+    assert.ok(transfer.newPrimaryWallet.equals(newPrimaryWallet.publicKey));
+  });
+
+  it("Try to accept by a wrong wallet", async () => {
+    await assert.rejects(accept(Keypair.generate()));
+  });
+
+  it("Accept the primary wallet", async () => {
+    await accept(newPrimaryWallet);
+
+    const info = await program.account.config.fetch(config);
+    assert.ok(info.primaryWallet.equals(newPrimaryWallet.publicKey));
+    assert.equal(await connection.getAccountInfo(primaryWalletTransfer), null);
+  });
+});
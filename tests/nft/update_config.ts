@@ -0,0 +1,148 @@
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as nftUtils from "./utils";
+import {
+  AnchorError,
+  AnchorProvider,
+  BN,
+  Program,
+} from "@project-serum/anchor";
+import { ChillNft } from "../../target/types/chill_nft";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+
+describe("NFT | Update config", () => {
+  anchor.setProvider(AnchorProvider.env());
+  const program = anchor.workspace.ChillNft as Program<ChillNft>;
+
+  let primaryWallet: Keypair;
+  let adminConfig: PublicKey;
+  let chillMint: PublicKey;
+  let config: PublicKey;
+
+  function accounts() {
+    return {
+      primaryWallet: primaryWallet.publicKey,
+      adminConfig,
+      config,
+      chillMint,
+    };
+  }
+
+  async function updateFees(
+    fees: nftUtils.Fees,
+    signer: Keypair = primaryWallet
+  ) {
+    await program.methods
+      .updateFees(fees)
+      .accounts(accounts())
+      .remainingAccounts(nftUtils.adminSignerMetas([signer.publicKey]))
+      .signers([signer])
+      .rpc();
+  }
+
+  async function updateRecipients(
+    recipients: nftUtils.Recipient[],
+    signer: Keypair = primaryWallet
+  ) {
+    await program.methods
+      .updateRecipients(recipients)
+      .accounts(accounts())
+      .remainingAccounts(nftUtils.adminSignerMetas([signer.publicKey]))
+      .signers([signer])
+      .rpc();
+  }
+
+  async function assertError(promise: Promise<unknown>, code: string) {
+    await assert.rejects(promise, (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, code);
+      return true;
+    });
+  }
+
+  before(async () => {
+    primaryWallet = await utils.keypairWithSol();
+    chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+    const configRegistry = await nftUtils.initializeConfigRegistry(
+      program,
+      primaryWallet
+    );
+
+    adminConfig = await nftUtils.initializeAdminConfig(program, primaryWallet);
+
+    config = await nftUtils.getNftConfigPubkey(chillMint, program.programId);
+
+    await program.methods
+      .initialize(nftUtils.randomFees(), nftUtils.randomRecipients())
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        adminConfig,
+        payer: primaryWallet.publicKey,
+        config,
+        configRegistry,
+        chillMint,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(nftUtils.adminSignerMetas([primaryWallet.publicKey]))
+      .signers([primaryWallet])
+      .rpc();
+  });
+
+  it("Update fees", async () => {
+    const fees = nftUtils.randomFees();
+    await updateFees(fees);
+
+    const info = await program.account.config.fetch(config);
+    assert.equal(JSON.stringify(info.fees), JSON.stringify(fees));
+  });
+
+  it("Try to update fees by a wrong signer", async () => {
+    const wrongSigner = await utils.keypairWithSol();
+    await assertError(
+      updateFees(nftUtils.randomFees(), wrongSigner),
+      "NotEnoughAdminSigners"
+    );
+  });
+
+  it("Try to update fees above the maximum one", async () => {
+    const fees = nftUtils.randomFees();
+    fees.item = new BN(nftUtils.MAX_UI_FEE).mul(new BN(10).pow(new BN(9)));
+    await updateFees(fees);
+
+    fees.item = fees.item.addn(1);
+    await assertError(updateFees(fees), "InvalidFees");
+
+    const info = await program.account.config.fetch(config);
+    assert.equal(info.fees.item.toString(), fees.item.subn(1).toString());
+  });
+
+  it("Try to update recipients by a wrong signer", async () => {
+    const wrongSigner = await utils.keypairWithSol();
+    await assertError(
+      updateRecipients(nftUtils.randomRecipients(), wrongSigner),
+      "NotEnoughAdminSigners"
+    );
+  });
+
+  it("Try to update recipients with invalid shares", async () => {
+    const recipients = nftUtils.randomRecipients(2);
+    recipients[0].mintShare += 1;
+    await assertError(updateRecipients(recipients), "InvalidShares");
+  });
+
+  it("Try to update too many recipients", async () => {
+    const recipients = nftUtils.randomRecipients(nftUtils.MAX_RECIPIENTS + 1);
+    await assertError(
+      updateRecipients(recipients),
+      "MaximumRecipientsNumberExceeded"
+    );
+  });
+
+  it("Update recipients", async () => {
+    const recipients = nftUtils.randomRecipients(nftUtils.MAX_RECIPIENTS);
+    await updateRecipients(recipients);
+
+    const info = await program.account.config.fetch(config);
+    assert.equal(JSON.stringify(info.recipients), JSON.stringify(recipients));
+  });
+});
@@ -0,0 +1,145 @@
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as nftUtils from "./utils";
+import { BN, AnchorProvider, Program } from "@project-serum/anchor";
+import { ChillNft } from "../../target/types/chill_nft";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+} from "@solana/web3.js";
+import { programs } from "@metaplex/js";
+import * as assert from "assert";
+import {
+  ASSOCIATED_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+} from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("NFT | Utilize NFT", () => {
+  anchor.setProvider(AnchorProvider.env());
+  const program = anchor.workspace.ChillNft as Program<ChillNft>;
+  const Metadata = programs.metadata;
+
+  let primaryWallet: Keypair;
+  let user: Keypair;
+  let chillMint: PublicKey;
+  let config: PublicKey;
+
+  let nftMint: PublicKey;
+  let nftToken: PublicKey;
+  let nftChillMetadata: PublicKey;
+
+  async function utilizeNft(owner: Keypair, numberOfUses: number) {
+    await program.methods
+      .utilizeNft(new BN(numberOfUses))
+      .accounts({
+        owner: owner.publicKey,
+        nftMint,
+        nftTokenAccount: nftToken,
+        nftMetadata: await Metadata.Metadata.getPDA(nftMint),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        tokenMetadataProgram: Metadata.MetadataProgram.PUBKEY,
+      })
+      .signers([owner])
+      .rpc();
+  }
+
+  before(async () => {
+    primaryWallet = await utils.keypairWithSol();
+    user = await utils.keypairWithSol();
+    chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+    const chillPayerTokenAccount = await utils.createTokenAccount(
+      primaryWallet.publicKey,
+      chillMint
+    );
+
+    const configRegistry = await nftUtils.initializeConfigRegistry(
+      program,
+      primaryWallet
+    );
+
+    const adminConfig = await nftUtils.initializeAdminConfig(
+      program,
+      primaryWallet
+    );
+
+    config = await nftUtils.getNftConfigPubkey(chillMint, program.programId);
+
+    await program.methods
+      .initialize(nftUtils.randomFees(), [])
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        adminConfig,
+        payer: primaryWallet.publicKey,
+        config,
+        configRegistry,
+        chillMint,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(nftUtils.adminSignerMetas([primaryWallet.publicKey]))
+      .signers([primaryWallet])
+      .rpc();
+
+    nftMint = await utils.createMint(primaryWallet.publicKey, 0);
+    nftToken = await utils.createTokenAccount(user.publicKey, nftMint);
+    await utils.mintTokens(primaryWallet, nftMint, nftToken, 1);
+
+    nftChillMetadata = await nftUtils.getChillMetadataPubkey(
+      nftMint,
+      program.programId
+    );
+
+    await program.methods
+      .mintNft(
+        nftUtils.randomNftType(),
+        nftUtils.randomNftArgs(),
+        null,
+        0,
+        null,
+        { method: { multiple: {} }, remaining: new BN(3), total: new BN(3) }
+      )
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        payer: primaryWallet.publicKey,
+        chillPayer: primaryWallet.publicKey,
+        chillPayerTokenAccount,
+        config,
+        chillMint,
+        nftTypeRegistry: await nftUtils.getNftTypeRegistryPubkey(
+          config,
+          program.programId
+        ),
+        nftMint,
+        nftMetadata: await Metadata.Metadata.getPDA(nftMint),
+        nftMasterEdition: await Metadata.MasterEdition.getPDA(nftMint),
+        nftChillMetadata,
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMetadataProgram: Metadata.MetadataProgram.PUBKEY,
+      })
+      .signers([primaryWallet])
+      .rpc();
+  });
+
+  it("Try to utilize NFT by not an owner", async () => {
+    const wrongOwner = await utils.keypairWithSol();
+    await assert.rejects(utilizeNft(wrongOwner, 1));
+  });
+
+  it("Utilize NFT", async () => {
+    await utilizeNft(user, 2);
+  });
+
+  it("Try to utilize more than remaining uses", async () => {
+    await assert.rejects(utilizeNft(user, 2));
+  });
+
+  it("Utilize the last use", async () => {
+    await utilizeNft(user, 1);
+    await assert.rejects(utilizeNft(user, 1));
+  });
+});
@@ -1,6 +1,12 @@
 import * as anchor from "@project-serum/anchor";
-import { BN } from "@project-serum/anchor";
-import { Keypair, PublicKey } from "@solana/web3.js";
+import { BN, Program } from "@project-serum/anchor";
+import {
+  AccountMeta,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+} from "@solana/web3.js";
+import { ChillNft } from "../../target/types/chill_nft";
 import { randomNumber } from "../utils";
 
 export async function getNftConfigPubkey(
@@ -15,6 +21,99 @@ export async function getNftConfigPubkey(
   )[0];
 }
 
+export async function getConfigRegistryPubkey(
+  programId: PublicKey
+): Promise<PublicKey> {
+  return (
+    await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("config-registry")],
+      programId
+    )
+  )[0];
+}
+
+export async function initializeConfigRegistry(
+  program: Program<ChillNft>,
+  payer: Keypair
+): Promise<PublicKey> {
+  const configRegistry = await getConfigRegistryPubkey(program.programId);
+  const account = await program.provider.connection.getAccountInfo(
+    configRegistry
+  );
+
+  if (account == null) {
+    await program.methods
+      .initializeConfigRegistry()
+      .accounts({
+        payer: payer.publicKey,
+        configRegistry,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([payer])
+      .rpc();
+  }
+
+  return configRegistry;
+}
+
+export async function getAdminConfigPubkey(
+  primaryWallet: PublicKey,
+  programId: PublicKey
+): Promise<PublicKey> {
+  return (
+    await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("admin-config"), primaryWallet.toBytes()],
+      programId
+    )
+  )[0];
+}
+
+/**
+ * Creates an admin config of the primary wallet if it doesn't exist. By
+ * default the primary wallet is the only signer of the config
+ */
+export async function initializeAdminConfig(
+  program: Program<ChillNft>,
+  primaryWallet: Keypair,
+  signers?: PublicKey[],
+  threshold?: number
+): Promise<PublicKey> {
+  const adminConfig = await getAdminConfigPubkey(
+    primaryWallet.publicKey,
+    program.programId
+  );
+
+  const account = await program.provider.connection.getAccountInfo(
+    adminConfig
+  );
+
+  if (account == null) {
+    await program.methods
+      .initializeAdminConfig(
+        signers ?? [primaryWallet.publicKey],
+        threshold ?? 1
+      )
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        payer: program.provider.publicKey,
+        adminConfig,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([primaryWallet])
+      .rpc();
+  }
+
+  return adminConfig;
+}
+
+export function adminSignerMetas(signers: PublicKey[]): AccountMeta[] {
+  return signers.map((pubkey) => ({
+    pubkey,
+    isSigner: true,
+    isWritable: false,
+  }));
+}
+
 export async function getChillMetadataPubkey(
   nftMint: PublicKey,
   programId: PublicKey
@@ -27,6 +126,18 @@ export async function getChillMetadataPubkey(
   )[0];
 }
 
+export async function getNftTypeRegistryPubkey(
+  config: PublicKey,
+  programId: PublicKey
+): Promise<PublicKey> {
+  return (
+    await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("nft-type-registry"), config.toBytes()],
+      programId
+    )
+  )[0];
+}
+
 const nftTypes = [
   "character",
   "pet",
@@ -37,6 +148,7 @@ const nftTypes = [
 ] as const;
 
 export const MAX_RECIPIENTS = 3;
+export const MAX_UI_FEE = 1_000_000;
 
 export type Fees = { [K in typeof nftTypes[number]]: BN };
 export type NftType = Pick<keyof Fees, never>;
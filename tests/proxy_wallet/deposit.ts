@@ -0,0 +1,160 @@
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as walletUtils from "./utils";
+import { AnchorError, BN, Program } from "@project-serum/anchor";
+import { ChillWallet } from "../../target/types/chill_wallet";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("Proxy wallet | Deposit", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillWallet as Program<ChillWallet>;
+  const connection = program.provider.connection;
+
+  const lamports = 1_000_000;
+  const chillTokensAmount = 1_000_000_000;
+
+  let primaryWallet: Keypair;
+  let depositor: Keypair;
+  let proxyWallet: PublicKey;
+
+  async function depositFt(
+    mint: PublicKey,
+    depositorTokenAccount: PublicKey,
+    proxyWalletTokenAccount: PublicKey,
+    amount: number
+  ) {
+    await program.methods
+      .depositFt(new BN(amount))
+      .accounts({
+        depositor: depositor.publicKey,
+        mint,
+        depositorTokenAccount,
+        proxyWallet,
+        proxyWalletTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([depositor])
+      .rpc();
+  }
+
+  before(async () => {
+    primaryWallet = await utils.keypairWithSol();
+    depositor = await utils.keypairWithSol();
+    const user = Keypair.generate();
+
+    proxyWallet = await walletUtils.getWalletPubkey(
+      user.publicKey,
+      primaryWallet.publicKey,
+      program.programId
+    );
+
+    await program.methods
+      .createWallet()
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        user: user.publicKey,
+        payer: primaryWallet.publicKey,
+        proxyWallet,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([primaryWallet])
+      .rpc();
+  });
+
+  it("Deposit lamports", async () => {
+    const balanceBefore = await connection.getBalance(proxyWallet);
+
+    await program.methods
+      .depositLamports(new BN(lamports))
+      .accounts({
+        depositor: depositor.publicKey,
+        proxyWallet,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([depositor])
+      .rpc();
+
+    const balanceAfter = await connection.getBalance(proxyWallet);
+    assert.equal(balanceAfter - balanceBefore, lamports);
+
+    const wallet = await program.account.proxyWallet.fetch(proxyWallet);
+    assert.equal(wallet.totalMoneyDeposited.toNumber(), lamports);
+  });
+
+  it("Deposit fungible tokens", async () => {
+    const chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+    const depositorToken = await utils.createTokenAccount(
+      depositor.publicKey,
+      chillMint
+    );
+    const proxyWalletToken = await utils.createTokenAccount(
+      proxyWallet,
+      chillMint
+    );
+    await utils.mintTokens(
+      primaryWallet,
+      chillMint,
+      depositorToken,
+      chillTokensAmount
+    );
+
+    await depositFt(
+      chillMint,
+      depositorToken,
+      proxyWalletToken,
+      chillTokensAmount
+    );
+
+    assert.equal(await utils.tokenBalance(depositorToken), 0);
+    assert.equal(await utils.tokenBalance(proxyWalletToken), chillTokensAmount);
+
+    const wallet = await program.account.proxyWallet.fetch(proxyWallet);
+    assert.equal(wallet.totalFtDeposited.toNumber(), chillTokensAmount);
+  });
+
+  it("Try to deposit an NFT as fungible tokens", async () => {
+    const nftMint = await utils.createMint(primaryWallet.publicKey, 0);
+    const depositorToken = await utils.createTokenAccount(
+      depositor.publicKey,
+      nftMint
+    );
+    const proxyWalletToken = await utils.createTokenAccount(
+      proxyWallet,
+      nftMint
+    );
+    await utils.mintTokens(primaryWallet, nftMint, depositorToken, 1);
+
+    await assert.rejects(
+      depositFt(nftMint, depositorToken, proxyWalletToken, 1),
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "TokenIsNft");
+        return true;
+      }
+    );
+  });
+
+  it("Try to migrate a wallet of the current layout", async () => {
+    const wallet = await program.account.proxyWallet.fetch(proxyWallet);
+    await assert.rejects(
+      async () => {
+        await program.methods
+          .migrateWallet()
+          .accounts({
+            payer: depositor.publicKey,
+            primaryWallet: wallet.primaryWallet,
+            user: wallet.user,
+            proxyWallet,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([depositor])
+          .rpc();
+      },
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "WalletIsMigrated");
+        return true;
+      }
+    );
+  });
+});
@@ -0,0 +1,102 @@
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as walletUtils from "./utils";
+import { AnchorError, BN, Program } from "@project-serum/anchor";
+import { ChillWallet } from "../../target/types/chill_wallet";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+
+describe("Proxy wallet | Freeze", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillWallet as Program<ChillWallet>;
+
+  const lamports = 1_000_000;
+
+  let primaryWallet: Keypair;
+  let user: Keypair;
+  let receiver: PublicKey;
+  let proxyWallet: PublicKey;
+
+  async function withdrawLamports() {
+    await program.methods
+      .withdrawLamports(new BN(lamports))
+      .accounts({
+        authority: user.publicKey,
+        proxyWallet,
+        receiver,
+      })
+      .signers([user])
+      .rpc();
+  }
+
+  before(async () => {
+    primaryWallet = await utils.keypairWithSol();
+    user = await utils.keypairWithSol();
+    receiver = (await utils.keypairWithSol()).publicKey;
+
+    proxyWallet = await walletUtils.getWalletPubkey(
+      user.publicKey,
+      primaryWallet.publicKey,
+      program.programId
+    );
+
+    await program.methods
+      .createWallet()
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        user: user.publicKey,
+        payer: primaryWallet.publicKey,
+        proxyWallet,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([primaryWallet])
+      .rpc();
+
+    await utils.airdrop(proxyWallet, 10 * lamports);
+  });
+
+  it("Try to freeze the wallet by the user", async () => {
+    await assert.rejects(
+      async () => {
+        await program.methods
+          .freezeWallet()
+          .accounts({ primaryWallet: user.publicKey, proxyWallet })
+          .signers([user])
+          .rpc();
+      },
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "WrongAuthority");
+        return true;
+      }
+    );
+  });
+
+  it("Freeze the wallet", async () => {
+    await program.methods
+      .freezeWallet()
+      .accounts({ primaryWallet: primaryWallet.publicKey, proxyWallet })
+      .signers([primaryWallet])
+      .rpc();
+
+    const wallet = await program.account.proxyWallet.fetch(proxyWallet);
+    assert.ok(wallet.frozen);
+
+    await assert.rejects(withdrawLamports(), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "WalletFrozen");
+      return true;
+    });
+  });
+
+  it("Unfreeze the wallet", async () => {
+    await program.methods
+      .unfreezeWallet()
+      .accounts({ primaryWallet: primaryWallet.publicKey, proxyWallet })
+      .signers([primaryWallet])
+      .rpc();
+
+    const wallet = await program.account.proxyWallet.fetch(proxyWallet);
+    assert.ok(!wallet.frozen);
+
+    await withdrawLamports();
+  });
+});
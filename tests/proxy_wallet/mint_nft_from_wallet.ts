@@ -0,0 +1,184 @@
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as nftUtils from "../nft/utils";
+import * as walletUtils from "./utils";
+import { AnchorError, Program } from "@project-serum/anchor";
+import { ChillNft } from "../../target/types/chill_nft";
+import { ChillWallet } from "../../target/types/chill_wallet";
+import {
+  AccountMeta,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+} from "@solana/web3.js";
+import { programs } from "@metaplex/js";
+import * as assert from "assert";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("Proxy wallet | Mint NFT from wallet", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillWallet as Program<ChillWallet>;
+  const nftProgram = anchor.workspace.ChillNft as Program<ChillNft>;
+  const Metadata = programs.metadata;
+
+  const fees = nftUtils.randomFees();
+  const recipients = nftUtils.randomRecipients();
+  const initialTokenBalance = 1_000_000_000;
+  const recipientsTokenAccounts: AccountMeta[] = [];
+
+  let primaryWallet: Keypair;
+  let user: Keypair;
+  let proxyWallet: PublicKey;
+  let proxyWalletToken: PublicKey;
+  let chillMint: PublicKey;
+  let config: PublicKey;
+
+  async function mintNftFromWallet(data: Buffer) {
+    const nftMint = await utils.createMint(primaryWallet.publicKey, 0);
+    const nftToken = await utils.createTokenAccount(proxyWallet, nftMint);
+    await utils.mintTokens(primaryWallet, nftMint, nftToken, 1);
+
+    await program.methods
+      .mintNftFromWallet(data)
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        payer: primaryWallet.publicKey,
+        proxyWallet,
+        proxyWalletTokenAccount: proxyWalletToken,
+        config,
+        chillMint,
+        nftTypeRegistry: await nftUtils.getNftTypeRegistryPubkey(
+          config,
+          nftProgram.programId
+        ),
+        nftMint,
+        nftMetadata: await Metadata.Metadata.getPDA(nftMint),
+        nftMasterEdition: await Metadata.MasterEdition.getPDA(nftMint),
+        nftChillMetadata: await nftUtils.getChillMetadataPubkey(
+          nftMint,
+          nftProgram.programId
+        ),
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMetadataProgram: Metadata.MetadataProgram.PUBKEY,
+        nftProgram: nftProgram.programId,
+      })
+      .remainingAccounts(recipientsTokenAccounts)
+      .signers([primaryWallet])
+      .rpc();
+  }
+
+  before(async () => {
+    primaryWallet = await utils.keypairWithSol();
+    user = await utils.keypairWithSol();
+    chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+    config = await nftUtils.getNftConfigPubkey(chillMint, nftProgram.programId);
+
+    const configRegistry = await nftUtils.initializeConfigRegistry(
+      nftProgram,
+      primaryWallet
+    );
+    const adminConfig = await nftUtils.initializeAdminConfig(
+      nftProgram,
+      primaryWallet
+    );
+
+    for (const recipient of recipients) {
+      const tokenAccount = await utils.createTokenAccount(
+        recipient.address,
+        chillMint
+      );
+      recipientsTokenAccounts.push({
+        pubkey: tokenAccount,
+        isSigner: false,
+        isWritable: true,
+      });
+    }
+
+    await nftProgram.methods
+      .initialize(fees, recipients)
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        adminConfig,
+        payer: primaryWallet.publicKey,
+        config,
+        configRegistry,
+        chillMint,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(nftUtils.adminSignerMetas([primaryWallet.publicKey]))
+      .signers([primaryWallet])
+      .rpc();
+
+    proxyWallet = await walletUtils.getWalletPubkey(
+      user.publicKey,
+      primaryWallet.publicKey,
+      program.programId
+    );
+
+    await program.methods
+      .createWallet()
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        user: user.publicKey,
+        payer: primaryWallet.publicKey,
+        proxyWallet,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([primaryWallet])
+      .rpc();
+
+    proxyWalletToken = await utils.createTokenAccount(proxyWallet, chillMint);
+    await utils.mintTokens(
+      primaryWallet,
+      chillMint,
+      proxyWalletToken,
+      initialTokenBalance
+    );
+  });
+
+  it("Try to pass data of another instruction", async () => {
+    const data = nftProgram.coder.instruction.encode("utilizeNft", {
+      numberOfUses: new anchor.BN(1),
+    });
+
+    await assert.rejects(mintNftFromWallet(data), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "WrongInstructionData");
+      return true;
+    });
+  });
+
+  it("Mint NFT paying the fee from the proxy wallet", async () => {
+    const nftType = nftUtils.randomNftType();
+    const data = nftProgram.coder.instruction.encode("mintNft", {
+      nftType,
+      args: nftUtils.randomNftArgs(),
+      creator: null,
+      minGameVersion: 0,
+      collection: null,
+      uses: null,
+    });
+
+    await mintNftFromWallet(data);
+
+    const fee = nftUtils.feesOf(fees, nftType).toNumber();
+    assert.equal(
+      await utils.tokenBalance(proxyWalletToken),
+      initialTokenBalance - fee
+    );
+
+    const wallet = await program.account.proxyWallet.fetch(proxyWallet);
+    assert.equal(wallet.totalFtWithdrawnPrimaryWallet.toNumber(), fee);
+
+    const log = await program.methods
+      .viewWithdrawalLog()
+      .accounts({ proxyWallet })
+      .view();
+    assert.equal(log.length, 1);
+    assert.ok(log[0].authority.equals(primaryWallet.publicKey));
+    assert.ok(log[0].mint.equals(chillMint));
+    assert.equal(log[0].amount.toNumber(), fee);
+  });
+});
@@ -0,0 +1,171 @@
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as walletUtils from "./utils";
+import { AnchorError, BN, Program } from "@project-serum/anchor";
+import { ChillWallet } from "../../target/types/chill_wallet";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+} from "@solana/web3.js";
+import * as assert from "assert";
+import {
+  ASSOCIATED_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+} from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("Proxy wallet | NFT rental", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillWallet as Program<ChillWallet>;
+  const connection = program.provider.connection;
+
+  const price = 1_000_000;
+  const duration = 1;
+
+  let primaryWallet: Keypair;
+  let renter: Keypair;
+  let proxyWallet: PublicKey;
+  let proxyWalletNftToken: PublicKey;
+  let nftMint: PublicKey;
+  let rental: PublicKey;
+  let rentalEscrow: PublicKey;
+  let receipt: PublicKey;
+
+  async function rentNft(renter: Keypair) {
+    await program.methods
+      .rentNft()
+      .accounts({
+        renter: renter.publicKey,
+        proxyWallet,
+        rental,
+        receipt,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([renter])
+      .rpc();
+  }
+
+  async function reclaimNft() {
+    await program.methods
+      .reclaimNft()
+      .accounts({
+        authority: primaryWallet.publicKey,
+        proxyWallet,
+        proxyWalletTokenAccount: proxyWalletNftToken,
+        rental,
+        rentalEscrow,
+        receipt,
+        rentReceiver: primaryWallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([primaryWallet])
+      .rpc();
+  }
+
+  before(async () => {
+    primaryWallet = await utils.keypairWithSol();
+    renter = await utils.keypairWithSol();
+    const user = Keypair.generate();
+
+    proxyWallet = await walletUtils.getWalletPubkey(
+      user.publicKey,
+      primaryWallet.publicKey,
+      program.programId
+    );
+
+    await program.methods
+      .createWallet()
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        user: user.publicKey,
+        payer: primaryWallet.publicKey,
+        proxyWallet,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([primaryWallet])
+      .rpc();
+
+    nftMint = await utils.createMint(primaryWallet.publicKey, 0);
+    proxyWalletNftToken = await utils.createTokenAccount(proxyWallet, nftMint);
+    await utils.mintTokens(primaryWallet, nftMint, proxyWalletNftToken, 1);
+
+    rental = (
+      await PublicKey.findProgramAddress(
+        [anchor.utils.bytes.utf8.encode("rental"), nftMint.toBytes()],
+        program.programId
+      )
+    )[0];
+
+    receipt = (
+      await PublicKey.findProgramAddress(
+        [anchor.utils.bytes.utf8.encode("rental-receipt"), rental.toBytes()],
+        program.programId
+      )
+    )[0];
+
+    rentalEscrow = await utils.getAssociatedTokenAddress(rental, nftMint);
+  });
+
+  it("Lend NFT", async () => {
+    await program.methods
+      .lendNft(new BN(price), new BN(duration))
+      .accounts({
+        authority: primaryWallet.publicKey,
+        proxyWallet,
+        nftMint,
+        proxyWalletTokenAccount: proxyWalletNftToken,
+        rental,
+        rentalEscrow,
+        receipt,
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+      })
+      .signers([primaryWallet])
+      .rpc();
+
+    assert.equal(await utils.tokenBalance(proxyWalletNftToken), 0);
+    assert.equal(await utils.tokenBalance(rentalEscrow), 1);
+
+    const rentalData = await program.account.nftRental.fetch(rental);
+    assert.ok(rentalData.proxyWallet.equals(proxyWallet));
+    assert.equal(rentalData.price.toNumber(), price);
+    assert.equal(rentalData.duration.toNumber(), duration);
+  });
+
+  it("Rent NFT", async () => {
+    const balanceBefore = await connection.getBalance(proxyWallet);
+    await rentNft(renter);
+    const balanceAfter = await connection.getBalance(proxyWallet);
+    assert.equal(balanceAfter - balanceBefore, price);
+
+    const receiptData = await program.account.rentalReceipt.fetch(receipt);
+    assert.ok(receiptData.renter.equals(renter.publicKey));
+    assert.ok(receiptData.expiresAt.toNumber() > 0);
+  });
+
+  it("Try to rent or reclaim NFT during an active rental", async () => {
+    const secondRenter = await utils.keypairWithSol();
+    await assert.rejects(rentNft(secondRenter), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "NftIsRented");
+      return true;
+    });
+
+    await assert.rejects(reclaimNft(), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "NftIsRented");
+      return true;
+    });
+  });
+
+  it("Reclaim NFT after the rental expires", async () => {
+    await walletUtils.pause(3000);
+    await reclaimNft();
+
+    assert.equal(await utils.tokenBalance(proxyWalletNftToken), 1);
+    assert.equal(await connection.getAccountInfo(rental), null);
+    assert.equal(await connection.getAccountInfo(receipt), null);
+    assert.equal(await connection.getAccountInfo(rentalEscrow), null);
+  });
+});
@@ -1,7 +1,9 @@
 import * as anchor from "@project-serum/anchor";
+import * as nftUtils from "../nft/utils";
 import * as utils from "../utils";
 import * as walletUtils from "./utils";
 import { AnchorError, BN, Program } from "@project-serum/anchor";
+import { ChillNft } from "../../target/types/chill_nft";
 import { ChillWallet } from "../../target/types/chill_wallet";
 import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
 import * as assert from "assert";
@@ -10,6 +12,7 @@ import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
 describe("Proxy wallet", () => {
   anchor.setProvider(anchor.AnchorProvider.env());
   const program = anchor.workspace.ChillWallet as Program<ChillWallet>;
+  const nftProgram = anchor.workspace.ChillNft as Program<ChillNft>;
   const connection = program.provider.connection;
 
   const mintAuthority = Keypair.generate();
@@ -23,6 +26,8 @@ describe("Proxy wallet", () => {
   let wrongAuthorty: Keypair;
   let proxyWallet: PublicKey;
   let primaryProxyWallet: PublicKey;
+  let adminConfig: PublicKey;
+  const adminSigners = nftUtils.adminSignerMetas([primaryWallet.publicKey]);
 
   let chillMint: PublicKey;
   let nftMint: PublicKey;
@@ -38,6 +43,11 @@ describe("Proxy wallet", () => {
     payer = await utils.keypairWithSol();
     wrongAuthorty = await utils.keypairWithSol();
     receiver = await utils.keypairWithSol();
+    await utils.airdrop(primaryWallet.publicKey, 1_000_000_000);
+    adminConfig = await nftUtils.initializeAdminConfig(
+      nftProgram,
+      primaryWallet
+    );
 
     proxyWallet = await walletUtils.getWalletPubkey(
       user.publicKey,
@@ -103,17 +113,39 @@ describe("Proxy wallet", () => {
     );
   });
 
+  it("Try to create proxy wallet with third-party payer", async () => {
+    await assert.rejects(
+      async () => {
+        await program.methods
+          .createWallet()
+          .accounts({
+            primaryWallet: primaryWallet.publicKey,
+            user: user.publicKey,
+            payer: payer.publicKey,
+            proxyWallet,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([payer])
+          .rpc();
+      },
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "WrongCreator");
+        return true;
+      }
+    );
+  });
+
   it("Create proxy wallet", async () => {
     await program.methods
       .createWallet()
       .accounts({
         primaryWallet: primaryWallet.publicKey,
         user: user.publicKey,
-        payer: payer.publicKey,
+        payer: primaryWallet.publicKey,
         proxyWallet,
         systemProgram: SystemProgram.programId,
       })
-      .signers([payer])
+      .signers([primaryWallet])
       .rpc();
 
     const wallet = await program.account.proxyWallet.fetch(proxyWallet);
@@ -133,11 +165,11 @@ describe("Proxy wallet", () => {
       .accounts({
         primaryWallet: primaryWallet.publicKey,
         user: primaryWallet.publicKey,
-        payer: payer.publicKey,
+        payer: primaryWallet.publicKey,
         proxyWallet: primaryProxyWallet,
         systemProgram: SystemProgram.programId,
       })
-      .signers([payer])
+      .signers([primaryWallet])
       .rpc();
 
     const wallet = await program.account.proxyWallet.fetch(primaryProxyWallet);
@@ -151,6 +183,84 @@ describe("Proxy wallet", () => {
     assert.equal(wallet.totalNftWithdrawnPrimaryWallet.toNumber(), 0);
   });
 
+  it("Try to create proxy wallets with wrong wallets list", async () => {
+    const users = [Keypair.generate().publicKey, Keypair.generate().publicKey];
+    const wrongWallet = await walletUtils.getWalletPubkey(
+      users[0],
+      primaryWallet.publicKey,
+      program.programId
+    );
+
+    await assert.rejects(
+      async () => {
+        await program.methods
+          .createWallets()
+          .accounts({
+            primaryWallet: primaryWallet.publicKey,
+            adminConfig,
+            payer: payer.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts([
+            ...adminSigners,
+            { pubkey: users[1], isSigner: false, isWritable: false },
+            { pubkey: wrongWallet, isSigner: false, isWritable: true },
+          ])
+          .signers([primaryWallet, payer])
+          .rpc();
+      },
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "WrongWalletsList");
+        return true;
+      }
+    );
+  });
+
+  it("Create proxy wallets in bulk", async () => {
+    const users = [user.publicKey];
+    for (let i = 0; i < 5; i++) {
+      users.push(Keypair.generate().publicKey);
+    }
+
+    const wallets: PublicKey[] = [];
+    for (const account of users) {
+      wallets.push(
+        await walletUtils.getWalletPubkey(
+          account,
+          primaryWallet.publicKey,
+          program.programId
+        )
+      );
+    }
+
+    const remainingAccounts = users.flatMap((account, i) => [
+      { pubkey: account, isSigner: false, isWritable: false },
+      { pubkey: wallets[i], isSigner: false, isWritable: true },
+    ]);
+
+    // The wallet of the first user already exists and must be skipped
+    await program.methods
+      .createWallets()
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        adminConfig,
+        payer: payer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts([...adminSigners, ...remainingAccounts])
+      .signers([primaryWallet, payer])
+      .rpc();
+
+    for (let i = 0; i < users.length; i++) {
+      const wallet = await program.account.proxyWallet.fetch(wallets[i]);
+      assert.deepEqual(wallet.primaryWallet, primaryWallet.publicKey);
+      assert.deepEqual(wallet.user, users[i]);
+      assert.equal(wallet.totalMoneyWithdrawnUser.toNumber(), 0);
+      assert.equal(wallet.totalFtWithdrawnUser.toNumber(), 0);
+      assert.equal(wallet.totalNftWithdrawnUser.toNumber(), 0);
+    }
+  });
+
   it("Try to withdraw lamports with wrong authority", async () => {
     await assert.rejects(
       async () => {
@@ -642,4 +752,161 @@ describe("Proxy wallet", () => {
     assert.equal(await utils.tokenBalance(receiverNftToken), 1);
     assert.equal(JSON.stringify(newProxyState), JSON.stringify(proxyAccount));
   });
+
+  it("Try to schedule withdrawal with zero interval", async () => {
+    await utils.airdrop(user.publicKey, 1_000_000_000);
+
+    const schedule = await walletUtils.getWithdrawalSchedulePubkey(
+      proxyWallet,
+      receiver.publicKey,
+      program.programId
+    );
+
+    await assert.rejects(
+      async () => {
+        await program.methods
+          .scheduleWithdrawal(new BN(lamports), new BN(0))
+          .accounts({
+            authority: user.publicKey,
+            proxyWallet,
+            receiver: receiver.publicKey,
+            schedule,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([user])
+          .rpc();
+      },
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "InvalidWithdrawalInterval");
+        return true;
+      }
+    );
+  });
+
+  it("Try to schedule withdrawal with wrong authority", async () => {
+    const schedule = await walletUtils.getWithdrawalSchedulePubkey(
+      proxyWallet,
+      receiver.publicKey,
+      program.programId
+    );
+
+    await assert.rejects(
+      async () => {
+        await program.methods
+          .scheduleWithdrawal(new BN(lamports), new BN(1))
+          .accounts({
+            authority: wrongAuthorty.publicKey,
+            proxyWallet,
+            receiver: receiver.publicKey,
+            schedule,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([wrongAuthorty])
+          .rpc();
+      },
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "WrongAuthority");
+        return true;
+      }
+    );
+  });
+
+  it("Schedule and execute withdrawal", async () => {
+    await utils.transferLamports(payer, proxyWallet, lamports);
+
+    const schedule = await walletUtils.getWithdrawalSchedulePubkey(
+      proxyWallet,
+      receiver.publicKey,
+      program.programId
+    );
+
+    const interval = 2;
+    const amount = lamports / 2;
+
+    await program.methods
+      .scheduleWithdrawal(new BN(amount), new BN(interval))
+      .accounts({
+        authority: user.publicKey,
+        proxyWallet,
+        receiver: receiver.publicKey,
+        schedule,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user])
+      .rpc();
+
+    const scheduleAccount = await program.account.withdrawalSchedule.fetch(
+      schedule
+    );
+    assert.ok(scheduleAccount.proxyWallet.equals(proxyWallet));
+    assert.ok(scheduleAccount.authority.equals(user.publicKey));
+    assert.ok(scheduleAccount.receiver.equals(receiver.publicKey));
+    assert.equal(scheduleAccount.amount.toNumber(), amount);
+    assert.equal(scheduleAccount.interval.toNumber(), interval);
+
+    const executeDueWithdrawal = () =>
+      program.methods
+        .executeDueWithdrawal()
+        .accounts({
+          proxyWallet,
+          schedule,
+          receiver: receiver.publicKey,
+        })
+        .rpc();
+
+    await assert.rejects(executeDueWithdrawal, (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "WithdrawalIsNotDue");
+      return true;
+    });
+
+    await walletUtils.pause((interval + 1) * 1000);
+
+    const proxyAccount = await program.account.proxyWallet.fetch(proxyWallet);
+    const initialReceiverBalance = await connection.getBalance(
+      receiver.publicKey
+    );
+    proxyAccount.totalMoneyWithdrawnUser.iadd(new BN(amount));
+
+    await executeDueWithdrawal();
+
+    const newReceiverBalance = await connection.getBalance(receiver.publicKey);
+    const newProxyState = await program.account.proxyWallet.fetch(proxyWallet);
+    const newScheduleAccount = await program.account.withdrawalSchedule.fetch(
+      schedule
+    );
+
+    assert.equal(newReceiverBalance - initialReceiverBalance, amount);
+    assert.equal(JSON.stringify(newProxyState), JSON.stringify(proxyAccount));
+    assert.ok(
+      newScheduleAccount.nextWithdrawalTime.gt(
+        scheduleAccount.nextWithdrawalTime
+      )
+    );
+
+    await assert.rejects(executeDueWithdrawal, (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "WithdrawalIsNotDue");
+      return true;
+    });
+  });
+
+  it("Cancel scheduled withdrawal", async () => {
+    const schedule = await walletUtils.getWithdrawalSchedulePubkey(
+      proxyWallet,
+      receiver.publicKey,
+      program.programId
+    );
+
+    await program.methods
+      .cancelScheduledWithdrawal()
+      .accounts({
+        authority: primaryWallet.publicKey,
+        proxyWallet,
+        schedule,
+        scheduleAuthority: user.publicKey,
+      })
+      .signers([primaryWallet])
+      .rpc();
+
+    assert.equal(await connection.getAccountInfo(schedule), null);
+  });
 });
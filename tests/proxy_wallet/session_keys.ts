@@ -0,0 +1,173 @@
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as walletUtils from "./utils";
+import { AnchorError, BN, Program } from "@project-serum/anchor";
+import { ChillWallet } from "../../target/types/chill_wallet";
+import {
+  AccountMeta,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+} from "@solana/web3.js";
+import * as assert from "assert";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("Proxy wallet | Session keys", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillWallet as Program<ChillWallet>;
+  const connection = program.provider.connection;
+
+  const WITHDRAW_FT = 1 << 1;
+  const ftAllowance = 1_000_000;
+
+  let primaryWallet: Keypair;
+  let user: Keypair;
+  let sessionSigner: Keypair;
+  let proxyWallet: PublicKey;
+  let sessionKey: PublicKey;
+  let mint: PublicKey;
+  let proxyWalletToken: PublicKey;
+  let receiverToken: PublicKey;
+
+  function sessionKeyMeta(): AccountMeta {
+    return { pubkey: sessionKey, isSigner: false, isWritable: true };
+  }
+
+  async function withdrawFt(amount: number) {
+    await program.methods
+      .withdrawFt(new BN(amount))
+      .accounts({
+        authority: sessionSigner.publicKey,
+        proxyWallet,
+        mint,
+        proxyWalletTokenAccount: proxyWalletToken,
+        receiverTokenAccount: receiverToken,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts([sessionKeyMeta()])
+      .signers([sessionSigner])
+      .rpc();
+  }
+
+  before(async () => {
+    primaryWallet = await utils.keypairWithSol();
+    user = await utils.keypairWithSol();
+    sessionSigner = await utils.keypairWithSol();
+
+    proxyWallet = await walletUtils.getWalletPubkey(
+      user.publicKey,
+      primaryWallet.publicKey,
+      program.programId
+    );
+
+    [sessionKey] = await PublicKey.findProgramAddress(
+      [
+        Buffer.from("session"),
+        proxyWallet.toBuffer(),
+        sessionSigner.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+
+    await program.methods
+      .createWallet()
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        user: user.publicKey,
+        payer: primaryWallet.publicKey,
+        proxyWallet,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([primaryWallet])
+      .rpc();
+
+    mint = await utils.createMint(primaryWallet.publicKey, 9);
+    proxyWalletToken = await utils.createTokenAccount(proxyWallet, mint);
+    receiverToken = await utils.createTokenAccount(
+      sessionSigner.publicKey,
+      mint
+    );
+    await utils.mintTokens(
+      primaryWallet,
+      mint,
+      proxyWalletToken,
+      2 * ftAllowance
+    );
+    await utils.airdrop(proxyWallet, 1_000_000);
+  });
+
+  it("Create a session key", async () => {
+    const slot = await connection.getSlot();
+
+    await program.methods
+      .createSessionKey(
+        new BN(slot + 1_000),
+        WITHDRAW_FT,
+        new BN(0),
+        new BN(ftAllowance)
+      )
+      .accounts({
+        user: user.publicKey,
+        proxyWallet,
+        sessionSigner: sessionSigner.publicKey,
+        sessionKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user])
+      .rpc();
+
+    const session = await program.account.sessionKey.fetch(sessionKey);
+    assert.ok(session.signer.equals(sessionSigner.publicKey));
+    assert.equal(session.permissions, WITHDRAW_FT);
+    assert.equal(session.ftAllowance.toNumber(), ftAllowance);
+  });
+
+  it("Withdraw fungible tokens by the session signer", async () => {
+    await withdrawFt(ftAllowance / 2);
+
+    assert.equal(await utils.tokenBalance(receiverToken), ftAllowance / 2);
+
+    const session = await program.account.sessionKey.fetch(sessionKey);
+    assert.equal(session.ftAllowance.toNumber(), ftAllowance / 2);
+  });
+
+  it("Try to withdraw over the allowance", async () => {
+    await assert.rejects(withdrawFt(ftAllowance), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "SessionKeyAllowanceExceeded");
+      return true;
+    });
+  });
+
+  it("Try to withdraw lamports by the session signer", async () => {
+    await assert.rejects(
+      async () => {
+        await program.methods
+          .withdrawLamports(new BN(1))
+          .accounts({
+            authority: sessionSigner.publicKey,
+            proxyWallet,
+            receiver: sessionSigner.publicKey,
+          })
+          .remainingAccounts([sessionKeyMeta()])
+          .signers([sessionSigner])
+          .rpc();
+      },
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "SessionKeyNotPermitted");
+        return true;
+      }
+    );
+  });
+
+  it("Revoke the session key", async () => {
+    await program.methods
+      .revokeSessionKey()
+      .accounts({ user: user.publicKey, proxyWallet, sessionKey })
+      .signers([user])
+      .rpc();
+
+    assert.equal(await connection.getAccountInfo(sessionKey), null);
+
+    await assert.rejects(withdrawFt(1));
+  });
+});
@@ -0,0 +1,157 @@
+import * as anchor from "@project-serum/anchor";
+import * as nftUtils from "../nft/utils";
+import * as utils from "../utils";
+import * as walletUtils from "./utils";
+import { AnchorError, BN, Program } from "@project-serum/anchor";
+import { ChillNft } from "../../target/types/chill_nft";
+import { ChillWallet } from "../../target/types/chill_wallet";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+
+describe("Proxy wallet | Spending limits", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillWallet as Program<ChillWallet>;
+
+  const dailyLamports = 1_000_000;
+
+  let primaryWallet: Keypair;
+  let user: Keypair;
+  let receiver: PublicKey;
+  let proxyWallet: PublicKey;
+  let adminConfig: PublicKey;
+
+  async function setSpendingLimits(authority: Keypair) {
+    await program.methods
+      .setSpendingLimits(
+        { lamports: new BN(dailyLamports), ft: new BN(0) },
+        { lamports: new BN(0), ft: new BN(0) }
+      )
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        adminConfig,
+        proxyWallet,
+      })
+      .remainingAccounts(nftUtils.adminSignerMetas([authority.publicKey]))
+      .signers([authority])
+      .rpc();
+  }
+
+  async function withdrawLamports(authority: Keypair, amount: number) {
+    await program.methods
+      .withdrawLamports(new BN(amount))
+      .accounts({
+        authority: authority.publicKey,
+        proxyWallet,
+        receiver,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  before(async () => {
+    primaryWallet = await utils.keypairWithSol();
+    user = await utils.keypairWithSol();
+    receiver = (await utils.keypairWithSol()).publicKey;
+    adminConfig = await nftUtils.initializeAdminConfig(
+      anchor.workspace.ChillNft as Program<ChillNft>,
+      primaryWallet
+    );
+
+    proxyWallet = await walletUtils.getWalletPubkey(
+      user.publicKey,
+      primaryWallet.publicKey,
+      program.programId
+    );
+
+    await program.methods
+      .createWallet()
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        user: user.publicKey,
+        payer: primaryWallet.publicKey,
+        proxyWallet,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([primaryWallet])
+      .rpc();
+
+    await utils.airdrop(proxyWallet, 10 * dailyLamports);
+  });
+
+  it("Try to set spending limits without admin signers", async () => {
+    await assert.rejects(setSpendingLimits(user), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "NotEnoughAdminSigners");
+      return true;
+    });
+  });
+
+  it("Set spending limits", async () => {
+    await setSpendingLimits(primaryWallet);
+
+    const wallet = await program.account.proxyWallet.fetch(proxyWallet);
+    assert.equal(wallet.userLimit.limits.lamports.toNumber(), dailyLamports);
+    assert.equal(wallet.primaryWalletLimit.limits.lamports.toNumber(), 0);
+  });
+
+  it("Withdraw lamports within the daily limit", async () => {
+    await withdrawLamports(user, dailyLamports / 2);
+    await withdrawLamports(user, dailyLamports / 2);
+
+    const wallet = await program.account.proxyWallet.fetch(proxyWallet);
+    assert.equal(wallet.userLimit.spentLamports.toNumber(), dailyLamports);
+  });
+
+  it("Try to withdraw lamports over the daily limit", async () => {
+    await assert.rejects(withdrawLamports(user, 1), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "SpendingLimitExceeded");
+      return true;
+    });
+  });
+
+  it("Try to execute a scheduled withdrawal over the daily limit", async () => {
+    const schedule = await walletUtils.getWithdrawalSchedulePubkey(
+      proxyWallet,
+      receiver,
+      program.programId
+    );
+
+    const interval = 1;
+    await program.methods
+      .scheduleWithdrawal(new BN(1), new BN(interval))
+      .accounts({
+        authority: user.publicKey,
+        proxyWallet,
+        receiver,
+        schedule,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user])
+      .rpc();
+
+    await walletUtils.pause((interval + 1) * 1000);
+
+    await assert.rejects(
+      program.methods
+        .executeDueWithdrawal()
+        .accounts({ proxyWallet, schedule, receiver })
+        .rpc(),
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "SpendingLimitExceeded");
+        return true;
+      }
+    );
+
+    const wallet = await program.account.proxyWallet.fetch(proxyWallet);
+    assert.equal(wallet.userLimit.spentLamports.toNumber(), dailyLamports);
+  });
+
+  it("Withdraw lamports by the primary wallet without a limit", async () => {
+    await withdrawLamports(primaryWallet, 2 * dailyLamports);
+
+    const wallet = await program.account.proxyWallet.fetch(proxyWallet);
+    assert.equal(
+      wallet.primaryWalletLimit.spentLamports.toNumber(),
+      2 * dailyLamports
+    );
+  });
+});
@@ -0,0 +1,135 @@
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as stakingUtils from "../staking/utils";
+import * as walletUtils from "./utils";
+import { BN, Program } from "@project-serum/anchor";
+import { ChillStaking } from "../../target/types/chill_staking";
+import { ChillWallet } from "../../target/types/chill_wallet";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("Proxy wallet | Stake from wallet", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillWallet as Program<ChillWallet>;
+  const stakingProgram = anchor.workspace
+    .ChillStaking as Program<ChillStaking>;
+  const stakingPrimaryWallet = Keypair.generate();
+
+  const totalDays = 10;
+  const stakeAmount = 100_000;
+  const rewardTokensAmount = 100_000_000;
+
+  let primaryWallet: Keypair;
+  let user: Keypair;
+  let proxyWallet: PublicKey;
+  let proxyWalletToken: PublicKey;
+  let chillMint: PublicKey;
+  let stakingInfo: PublicKey;
+  let stakingTokenAuthority: PublicKey;
+  let stakingTokenAccount: PublicKey;
+  let userInfo: PublicKey;
+
+  before(async () => {
+    primaryWallet = await utils.keypairWithSol();
+    user = await utils.keypairWithSol();
+    chillMint = await utils.createMint(stakingPrimaryWallet.publicKey, 9);
+
+    stakingInfo = await stakingUtils.initializeStaking(
+      stakingPrimaryWallet,
+      primaryWallet,
+      totalDays,
+      chillMint,
+      stakingProgram
+    );
+
+    await stakingUtils.addRewardTokens(
+      rewardTokensAmount,
+      stakingPrimaryWallet,
+      chillMint,
+      stakingInfo,
+      stakingProgram
+    );
+
+    proxyWallet = await walletUtils.getWalletPubkey(
+      user.publicKey,
+      primaryWallet.publicKey,
+      program.programId
+    );
+
+    await program.methods
+      .createWallet()
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        user: user.publicKey,
+        payer: primaryWallet.publicKey,
+        proxyWallet,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([primaryWallet])
+      .rpc();
+
+    proxyWalletToken = await utils.createTokenAccount(proxyWallet, chillMint);
+    await utils.mintTokens(
+      stakingPrimaryWallet,
+      chillMint,
+      proxyWalletToken,
+      stakeAmount
+    );
+
+    userInfo = await stakingUtils.getUserInfoPubkey(
+      user.publicKey,
+      stakingInfo,
+      stakingProgram.programId
+    );
+    stakingTokenAuthority = await stakingUtils.getStakingAuthority(
+      stakingInfo,
+      stakingProgram.programId
+    );
+    stakingTokenAccount = await utils.getAssociatedTokenAddress(
+      stakingTokenAuthority,
+      chillMint
+    );
+
+    const info = await stakingProgram.account.stakingInfo.fetch(stakingInfo);
+    await stakingUtils.waitUntil(stakingProgram, info.startDay.toNumber());
+  });
+
+  it("Stake tokens of the proxy wallet", async () => {
+    await program.methods
+      .stakeFromWallet(new BN(stakeAmount))
+      .accounts({
+        user: user.publicKey,
+        payer: primaryWallet.publicKey,
+        proxyWallet,
+        proxyWalletTokenAccount: proxyWalletToken,
+        stakingInfo,
+        userInfo,
+        stakingTokenAuthority,
+        stakingTokenAccount,
+        stakingProgram: stakingProgram.programId,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user, primaryWallet])
+      .rpc();
+
+    assert.equal(await utils.tokenBalance(proxyWalletToken), 0);
+
+    const info = await stakingProgram.account.userInfo.fetch(userInfo);
+    assert.ok(info.user.equals(user.publicKey));
+    assert.equal(info.stakedAmount.toNumber(), stakeAmount);
+
+    const wallet = await program.account.proxyWallet.fetch(proxyWallet);
+    assert.equal(wallet.totalFtWithdrawnUser.toNumber(), stakeAmount);
+
+    const log = await program.methods
+      .viewWithdrawalLog()
+      .accounts({ proxyWallet })
+      .view();
+    assert.equal(log.length, 1);
+    assert.ok(log[0].authority.equals(user.publicKey));
+    assert.ok(log[0].mint.equals(chillMint));
+    assert.equal(log[0].amount.toNumber(), stakeAmount);
+  });
+});
@@ -0,0 +1,227 @@
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as nftUtils from "../nft/utils";
+import * as walletUtils from "./utils";
+import { AnchorError, Program } from "@project-serum/anchor";
+import { ChillNft } from "../../target/types/chill_nft";
+import { ChillWallet } from "../../target/types/chill_wallet";
+import {
+  AccountMeta,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+} from "@solana/web3.js";
+import { programs } from "@metaplex/js";
+import * as assert from "assert";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("Proxy wallet | Transfer NFT with update", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillWallet as Program<ChillWallet>;
+  const nftProgram = anchor.workspace.ChillNft as Program<ChillNft>;
+  const Metadata = programs.metadata;
+
+  const fees = nftUtils.randomFees();
+  const recipients = nftUtils.randomRecipients();
+  const recipientsTokenAccounts: AccountMeta[] = [];
+
+  let primaryWallet: Keypair;
+  let user: Keypair;
+  let buyer: Keypair;
+  let proxyWallet: PublicKey;
+  let proxyWalletToken: PublicKey;
+  let chillMint: PublicKey;
+  let config: PublicKey;
+  let nftMint: PublicKey;
+  let nftToken: PublicKey;
+  let buyerToken: PublicKey;
+
+  async function transferNft(signer: Keypair, receiverTokenAccount: PublicKey) {
+    await program.methods
+      .transferNftWithUpdate()
+      .accounts({
+        primaryWallet: signer.publicKey,
+        proxyWallet,
+        nftMint,
+        nftMetadata: await Metadata.Metadata.getPDA(nftMint),
+        proxyWalletTokenAccount: nftToken,
+        receiverTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMetadataProgram: Metadata.MetadataProgram.PUBKEY,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  async function loadMetadata() {
+    return await Metadata.Metadata.load(
+      program.provider.connection,
+      await Metadata.Metadata.getPDA(nftMint)
+    );
+  }
+
+  before(async () => {
+    primaryWallet = await utils.keypairWithSol();
+    user = await utils.keypairWithSol();
+    buyer = await utils.keypairWithSol();
+    chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+    config = await nftUtils.getNftConfigPubkey(chillMint, nftProgram.programId);
+
+    const configRegistry = await nftUtils.initializeConfigRegistry(
+      nftProgram,
+      primaryWallet
+    );
+    const adminConfig = await nftUtils.initializeAdminConfig(
+      nftProgram,
+      primaryWallet
+    );
+
+    for (const recipient of recipients) {
+      const tokenAccount = await utils.createTokenAccount(
+        recipient.address,
+        chillMint
+      );
+      recipientsTokenAccounts.push({
+        pubkey: tokenAccount,
+        isSigner: false,
+        isWritable: true,
+      });
+    }
+
+    await nftProgram.methods
+      .initialize(fees, recipients)
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        adminConfig,
+        payer: primaryWallet.publicKey,
+        config,
+        configRegistry,
+        chillMint,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(nftUtils.adminSignerMetas([primaryWallet.publicKey]))
+      .signers([primaryWallet])
+      .rpc();
+
+    proxyWallet = await walletUtils.getWalletPubkey(
+      user.publicKey,
+      primaryWallet.publicKey,
+      program.programId
+    );
+
+    await program.methods
+      .createWallet()
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        user: user.publicKey,
+        payer: primaryWallet.publicKey,
+        proxyWallet,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([primaryWallet])
+      .rpc();
+
+    proxyWalletToken = await utils.createTokenAccount(proxyWallet, chillMint);
+    await utils.mintTokens(
+      primaryWallet,
+      chillMint,
+      proxyWalletToken,
+      1_000_000_000
+    );
+
+    nftMint = await utils.createMint(primaryWallet.publicKey, 0);
+    nftToken = await utils.createTokenAccount(proxyWallet, nftMint);
+    await utils.mintTokens(primaryWallet, nftMint, nftToken, 1);
+
+    const data = nftProgram.coder.instruction.encode("mintNft", {
+      nftType: nftUtils.randomNftType(),
+      args: nftUtils.randomNftArgs(),
+      creator: null,
+      minGameVersion: 0,
+      collection: null,
+      uses: null,
+    });
+
+    await program.methods
+      .mintNftFromWallet(data)
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        payer: primaryWallet.publicKey,
+        proxyWallet,
+        proxyWalletTokenAccount: proxyWalletToken,
+        config,
+        chillMint,
+        nftTypeRegistry: await nftUtils.getNftTypeRegistryPubkey(
+          config,
+          nftProgram.programId
+        ),
+        nftMint,
+        nftMetadata: await Metadata.Metadata.getPDA(nftMint),
+        nftMasterEdition: await Metadata.MasterEdition.getPDA(nftMint),
+        nftChillMetadata: await nftUtils.getChillMetadataPubkey(
+          nftMint,
+          nftProgram.programId
+        ),
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMetadataProgram: Metadata.MetadataProgram.PUBKEY,
+        nftProgram: nftProgram.programId,
+      })
+      .remainingAccounts(recipientsTokenAccounts)
+      .signers([primaryWallet])
+      .rpc();
+  });
+
+  it("Try to transfer the NFT by the user", async () => {
+    const userToken = await utils.createTokenAccount(user.publicKey, nftMint);
+    await assert.rejects(transferNft(user, userToken), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "WrongAuthority");
+      return true;
+    });
+  });
+
+  it("First sale updates the metadata", async () => {
+    const metadata = await loadMetadata();
+    assert.equal(metadata.data.primarySaleHappened, false);
+
+    buyerToken = await utils.createTokenAccount(buyer.publicKey, nftMint);
+    await transferNft(primaryWallet, buyerToken);
+
+    assert.equal(await utils.tokenBalance(buyerToken), 1);
+    assert.equal(await utils.tokenBalance(nftToken), 0);
+
+    const updated = await loadMetadata();
+    assert.equal(updated.data.primarySaleHappened, true);
+
+    const creators = updated.data.data.creators;
+    assert.equal(creators.length, 2);
+    assert.equal(creators[0].address, primaryWallet.publicKey.toString());
+    assert.equal(creators[0].verified, true);
+    assert.equal(creators[0].share, 2);
+    assert.equal(creators[1].address, buyer.publicKey.toString());
+    assert.equal(creators[1].verified, false);
+    assert.equal(creators[1].share, 98);
+
+    const wallet = await program.account.proxyWallet.fetch(proxyWallet);
+    assert.equal(wallet.totalNftWithdrawnPrimaryWallet.toNumber(), 1);
+  });
+
+  it("Next sale keeps the metadata", async () => {
+    await utils.transferTokens(buyer, buyerToken, nftToken, 1);
+
+    const otherBuyer = await utils.keypairWithSol();
+    const otherBuyerToken = await utils.createTokenAccount(
+      otherBuyer.publicKey,
+      nftMint
+    );
+    await transferNft(primaryWallet, otherBuyerToken);
+
+    assert.equal(await utils.tokenBalance(otherBuyerToken), 1);
+
+    const creators = (await loadMetadata()).data.data.creators;
+    assert.equal(creators.length, 2);
+    assert.equal(creators[1].address, buyer.publicKey.toString());
+  });
+});
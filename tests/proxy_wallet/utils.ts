@@ -17,3 +17,24 @@ export async function getWalletPubkey(
     )
   )[0];
 }
+
+export async function getWithdrawalSchedulePubkey(
+  proxyWallet: PublicKey,
+  receiver: PublicKey,
+  programId: PublicKey
+): Promise<PublicKey> {
+  return (
+    await PublicKey.findProgramAddress(
+      [
+        anchor.utils.bytes.utf8.encode("schedule"),
+        proxyWallet.toBytes(),
+        receiver.toBytes(),
+      ],
+      programId
+    )
+  )[0];
+}
+
+export async function pause(ms: number): Promise<void> {
+  await new Promise((f) => setTimeout(f, ms));
+}
@@ -0,0 +1,112 @@
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as walletUtils from "./utils";
+import { AnchorError, Program } from "@project-serum/anchor";
+import { ChillWallet } from "../../target/types/chill_wallet";
+import {
+  AccountMeta,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+} from "@solana/web3.js";
+import * as assert from "assert";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("Proxy wallet | Withdraw FT batch", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillWallet as Program<ChillWallet>;
+
+  const amounts = [1_000_000_000, 2_000_000];
+
+  let primaryWallet: Keypair;
+  let user: Keypair;
+  let proxyWallet: PublicKey;
+  const proxyWalletTokens: PublicKey[] = [];
+  const receiverTokens: PublicKey[] = [];
+  const withdrawalList: AccountMeta[] = [];
+
+  async function withdrawFtBatch(remainingAccounts: AccountMeta[]) {
+    await program.methods
+      .withdrawFtBatch()
+      .accounts({
+        authority: user.publicKey,
+        proxyWallet,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(remainingAccounts)
+      .signers([user])
+      .rpc();
+  }
+
+  before(async () => {
+    primaryWallet = await utils.keypairWithSol();
+    user = await utils.keypairWithSol();
+
+    proxyWallet = await walletUtils.getWalletPubkey(
+      user.publicKey,
+      primaryWallet.publicKey,
+      program.programId
+    );
+
+    await program.methods
+      .createWallet()
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        user: user.publicKey,
+        payer: primaryWallet.publicKey,
+        proxyWallet,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([primaryWallet])
+      .rpc();
+
+    for (const amount of amounts) {
+      const mint = await utils.createMint(primaryWallet.publicKey, 9);
+      const proxyWalletToken = await utils.createTokenAccount(
+        proxyWallet,
+        mint
+      );
+      const receiverToken = await utils.createTokenAccount(
+        user.publicKey,
+        mint
+      );
+      await utils.mintTokens(primaryWallet, mint, proxyWalletToken, amount);
+
+      proxyWalletTokens.push(proxyWalletToken);
+      receiverTokens.push(receiverToken);
+      withdrawalList.push(
+        { pubkey: mint, isSigner: false, isWritable: false },
+        { pubkey: proxyWalletToken, isSigner: false, isWritable: true },
+        { pubkey: receiverToken, isSigner: false, isWritable: true }
+      );
+    }
+  });
+
+  it("Try to withdraw with an incomplete withdrawal list", async () => {
+    await assert.rejects(
+      withdrawFtBatch(withdrawalList.slice(0, 2)),
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "WrongWithdrawalList");
+        return true;
+      }
+    );
+  });
+
+  it("Withdraw FT batch", async () => {
+    await withdrawFtBatch(withdrawalList);
+
+    for (const index in amounts) {
+      assert.equal(await utils.tokenBalance(proxyWalletTokens[index]), 0);
+      assert.equal(
+        await utils.tokenBalance(receiverTokens[index]),
+        amounts[index]
+      );
+    }
+
+    const wallet = await program.account.proxyWallet.fetch(proxyWallet);
+    assert.equal(
+      wallet.totalFtWithdrawnUser.toNumber(),
+      amounts.reduce((a, b) => a + b)
+    );
+  });
+});
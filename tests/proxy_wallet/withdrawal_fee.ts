@@ -0,0 +1,251 @@
+import * as anchor from "@project-serum/anchor";
+import * as nftUtils from "../nft/utils";
+import * as stakingUtils from "../staking/utils";
+import * as utils from "../utils";
+import * as walletUtils from "./utils";
+import { AnchorError, BN, Program } from "@project-serum/anchor";
+import { ChillNft } from "../../target/types/chill_nft";
+import { ChillStaking } from "../../target/types/chill_staking";
+import { ChillWallet } from "../../target/types/chill_wallet";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("Proxy wallet | Withdrawal fee", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillWallet as Program<ChillWallet>;
+  const stakingProgram = anchor.workspace
+    .ChillStaking as Program<ChillStaking>;
+
+  const lamports = 1_000_000;
+  const feeBps = 250;
+  const fee = (lamports * feeBps) / 10_000;
+  const stakeAmount = 100_000;
+  const stakeFee = (stakeAmount * feeBps) / 10_000;
+
+  let primaryWallet: Keypair;
+  let user: Keypair;
+  let receiver: PublicKey;
+  let feeReceiver: PublicKey;
+  let proxyWallet: PublicKey;
+  let adminConfig: PublicKey;
+  let chillMint: PublicKey;
+  let proxyWalletToken: PublicKey;
+  let feeReceiverToken: PublicKey;
+  let stakingInfo: PublicKey;
+
+  async function setFee(signer: Keypair, bps: number) {
+    await program.methods
+      .setWithdrawalFee(bps, feeReceiver)
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        adminConfig,
+        proxyWallet,
+      })
+      .remainingAccounts(nftUtils.adminSignerMetas([signer.publicKey]))
+      .signers([signer])
+      .rpc();
+  }
+
+  async function withdrawLamports(feeAccounts: PublicKey[]) {
+    await program.methods
+      .withdrawLamports(new BN(lamports))
+      .accounts({
+        authority: user.publicKey,
+        proxyWallet,
+        receiver,
+      })
+      .remainingAccounts(
+        feeAccounts.map((pubkey) => ({
+          pubkey,
+          isSigner: false,
+          isWritable: true,
+        }))
+      )
+      .signers([user])
+      .rpc();
+  }
+
+  async function stakeFromWallet(feeAccounts: PublicKey[]) {
+    const stakingTokenAuthority = await stakingUtils.getStakingAuthority(
+      stakingInfo,
+      stakingProgram.programId
+    );
+
+    await program.methods
+      .stakeFromWallet(new BN(stakeAmount))
+      .accounts({
+        user: user.publicKey,
+        payer: primaryWallet.publicKey,
+        proxyWallet,
+        proxyWalletTokenAccount: proxyWalletToken,
+        stakingInfo,
+        userInfo: await stakingUtils.getUserInfoPubkey(
+          user.publicKey,
+          stakingInfo,
+          stakingProgram.programId
+        ),
+        stakingTokenAuthority,
+        stakingTokenAccount: await utils.getAssociatedTokenAddress(
+          stakingTokenAuthority,
+          chillMint
+        ),
+        stakingProgram: stakingProgram.programId,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(
+        feeAccounts.map((pubkey) => ({
+          pubkey,
+          isSigner: false,
+          isWritable: true,
+        }))
+      )
+      .signers([user, primaryWallet])
+      .rpc();
+  }
+
+  before(async () => {
+    primaryWallet = await utils.keypairWithSol();
+    user = await utils.keypairWithSol();
+    receiver = (await utils.keypairWithSol()).publicKey;
+    feeReceiver = (await utils.keypairWithSol()).publicKey;
+    adminConfig = await nftUtils.initializeAdminConfig(
+      anchor.workspace.ChillNft as Program<ChillNft>,
+      primaryWallet
+    );
+
+    proxyWallet = await walletUtils.getWalletPubkey(
+      user.publicKey,
+      primaryWallet.publicKey,
+      program.programId
+    );
+
+    await program.methods
+      .createWallet()
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        user: user.publicKey,
+        payer: primaryWallet.publicKey,
+        proxyWallet,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([primaryWallet])
+      .rpc();
+
+    await utils.airdrop(proxyWallet, 10 * lamports);
+
+    const stakingPrimaryWallet = await utils.keypairWithSol();
+    chillMint = await utils.createMint(stakingPrimaryWallet.publicKey, 9);
+    stakingInfo = await stakingUtils.initializeStaking(
+      stakingPrimaryWallet,
+      primaryWallet,
+      10,
+      chillMint,
+      stakingProgram
+    );
+
+    proxyWalletToken = await utils.createTokenAccount(proxyWallet, chillMint);
+    feeReceiverToken = await utils.createTokenAccount(feeReceiver, chillMint);
+    await utils.mintTokens(
+      stakingPrimaryWallet,
+      chillMint,
+      proxyWalletToken,
+      stakeAmount
+    );
+
+    const info = await stakingProgram.account.stakingInfo.fetch(stakingInfo);
+    await stakingUtils.waitUntil(stakingProgram, info.startDay.toNumber());
+  });
+
+  it("Try to set the fee by the user", async () => {
+    await assert.rejects(setFee(user, feeBps), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "NotEnoughAdminSigners");
+      return true;
+    });
+  });
+
+  it("Try to set a fee above 100%", async () => {
+    await assert.rejects(setFee(primaryWallet, 10_001), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "InvalidFeeBps");
+      return true;
+    });
+  });
+
+  it("Try to withdraw without the fee receiver", async () => {
+    await setFee(primaryWallet, feeBps);
+
+    const wallet = await program.account.proxyWallet.fetch(proxyWallet);
+    assert.equal(wallet.feeBps, feeBps);
+    assert.ok(wallet.feeReceiver.equals(feeReceiver));
+
+    await assert.rejects(withdrawLamports([]), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "WrongFeeReceiver");
+      return true;
+    });
+
+    await assert.rejects(withdrawLamports([receiver]), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "WrongFeeReceiver");
+      return true;
+    });
+  });
+
+  it("Withdrawal pays the fee", async () => {
+    const connection = program.provider.connection;
+    const receiverBalance = await connection.getBalance(receiver);
+    const feeReceiverBalance = await connection.getBalance(feeReceiver);
+
+    await withdrawLamports([feeReceiver]);
+
+    assert.equal(
+      await connection.getBalance(receiver),
+      receiverBalance + lamports - fee
+    );
+    assert.equal(
+      await connection.getBalance(feeReceiver),
+      feeReceiverBalance + fee
+    );
+
+    const wallet = await program.account.proxyWallet.fetch(proxyWallet);
+    assert.equal(wallet.totalMoneyFee.toNumber(), fee);
+    assert.equal(wallet.totalMoneyWithdrawnUser.toNumber(), lamports);
+  });
+
+  it("Staking from the wallet pays the fee", async () => {
+    // The stake is paid out to any account of the user after a cancel
+    await assert.rejects(stakeFromWallet([]), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "WrongFeeReceiver");
+      return true;
+    });
+
+    await stakeFromWallet([feeReceiverToken]);
+
+    assert.equal(await utils.tokenBalance(proxyWalletToken), 0);
+    assert.equal(await utils.tokenBalance(feeReceiverToken), stakeFee);
+
+    const info = await stakingProgram.account.userInfo.fetch(
+      await stakingUtils.getUserInfoPubkey(
+        user.publicKey,
+        stakingInfo,
+        stakingProgram.programId
+      )
+    );
+    assert.equal(info.stakedAmount.toNumber(), stakeAmount - stakeFee);
+
+    const wallet = await program.account.proxyWallet.fetch(proxyWallet);
+    assert.equal(wallet.totalFtFee.toNumber(), stakeFee);
+    assert.equal(wallet.totalFtWithdrawnUser.toNumber(), stakeAmount);
+  });
+
+  it("Withdrawal without the fee", async () => {
+    await setFee(primaryWallet, 0);
+
+    const balance = await program.provider.connection.getBalance(receiver);
+    await withdrawLamports([]);
+
+    assert.equal(
+      await program.provider.connection.getBalance(receiver),
+      balance + lamports
+    );
+  });
+});
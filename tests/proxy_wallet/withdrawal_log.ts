@@ -0,0 +1,120 @@
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as walletUtils from "./utils";
+import { BN, Program } from "@project-serum/anchor";
+import { ChillWallet } from "../../target/types/chill_wallet";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+import * as assert from "assert";
+
+describe("Proxy wallet | Withdrawal log", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillWallet as Program<ChillWallet>;
+
+  const lamports = 1_000_000;
+  const tokens = 1_000;
+  const logLength = 8;
+
+  let primaryWallet: Keypair;
+  let user: Keypair;
+  let receiver: PublicKey;
+  let proxyWallet: PublicKey;
+  let mint: PublicKey;
+  let proxyWalletToken: PublicKey;
+  let receiverToken: PublicKey;
+
+  async function withdrawLamports(amount: number) {
+    await program.methods
+      .withdrawLamports(new BN(amount))
+      .accounts({
+        authority: user.publicKey,
+        proxyWallet,
+        receiver,
+      })
+      .signers([user])
+      .rpc();
+  }
+
+  async function withdrawalLog() {
+    return await program.methods
+      .viewWithdrawalLog()
+      .accounts({ proxyWallet })
+      .view();
+  }
+
+  before(async () => {
+    primaryWallet = await utils.keypairWithSol();
+    user = await utils.keypairWithSol();
+    receiver = (await utils.keypairWithSol()).publicKey;
+
+    proxyWallet = await walletUtils.getWalletPubkey(
+      user.publicKey,
+      primaryWallet.publicKey,
+      program.programId
+    );
+
+    await program.methods
+      .createWallet()
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        user: user.publicKey,
+        payer: primaryWallet.publicKey,
+        proxyWallet,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([primaryWallet])
+      .rpc();
+
+    await utils.airdrop(proxyWallet, 100 * lamports);
+
+    mint = await utils.createMint(primaryWallet.publicKey, 9);
+    proxyWalletToken = await utils.createTokenAccount(proxyWallet, mint);
+    receiverToken = await utils.createTokenAccount(receiver, mint);
+    await utils.mintTokens(primaryWallet, mint, proxyWalletToken, tokens);
+  });
+
+  it("New wallet has no withdrawals", async () => {
+    assert.deepEqual(await withdrawalLog(), []);
+  });
+
+  it("Withdrawals are logged", async () => {
+    await withdrawLamports(lamports);
+
+    await program.methods
+      .withdrawFt(new BN(tokens))
+      .accounts({
+        authority: primaryWallet.publicKey,
+        mint,
+        proxyWallet,
+        proxyWalletTokenAccount: proxyWalletToken,
+        receiverTokenAccount: receiverToken,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([primaryWallet])
+      .rpc();
+
+    const log = await withdrawalLog();
+    assert.equal(log.length, 2);
+
+    assert.ok(log[0].authority.equals(user.publicKey));
+    assert.ok(log[0].mint.equals(PublicKey.default));
+    assert.equal(log[0].amount.toNumber(), lamports);
+
+    assert.ok(log[1].authority.equals(primaryWallet.publicKey));
+    assert.ok(log[1].mint.equals(mint));
+    assert.equal(log[1].amount.toNumber(), tokens);
+    assert.ok(log[1].timestamp.toNumber() >= log[0].timestamp.toNumber());
+  });
+
+  it("Only the last withdrawals are kept", async () => {
+    for (let i = 1; i <= logLength; i++) {
+      await withdrawLamports(i);
+    }
+
+    const log = await withdrawalLog();
+    assert.equal(log.length, logLength);
+    log.forEach((entry, index) => {
+      assert.equal(entry.amount.toNumber(), index + 1);
+    });
+  });
+});
@@ -0,0 +1,96 @@
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as walletUtils from "./utils";
+import { AnchorError, BN, Program } from "@project-serum/anchor";
+import { ChillWallet } from "../../target/types/chill_wallet";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("Proxy wallet | Wrap SOL", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillWallet as Program<ChillWallet>;
+  const connection = program.provider.connection;
+
+  const nativeMint = new PublicKey(
+    "So11111111111111111111111111111111111111112"
+  );
+  const lamports = 1_000_000;
+
+  let primaryWallet: Keypair;
+  let user: Keypair;
+  let proxyWallet: PublicKey;
+  let proxyWalletToken: PublicKey;
+
+  async function wrapSol(authority: Keypair, amount: number) {
+    await program.methods
+      .wrapSol(new BN(amount))
+      .accounts({
+        authority: authority.publicKey,
+        proxyWallet,
+        proxyWalletTokenAccount: proxyWalletToken,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  before(async () => {
+    primaryWallet = await utils.keypairWithSol();
+    user = await utils.keypairWithSol();
+
+    proxyWallet = await walletUtils.getWalletPubkey(
+      user.publicKey,
+      primaryWallet.publicKey,
+      program.programId
+    );
+
+    await program.methods
+      .createWallet()
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        user: user.publicKey,
+        payer: primaryWallet.publicKey,
+        proxyWallet,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([primaryWallet])
+      .rpc();
+
+    proxyWalletToken = await utils.createTokenAccount(proxyWallet, nativeMint);
+    await utils.airdrop(proxyWallet, 10 * lamports);
+  });
+
+  it("Try to wrap SOL by a stranger", async () => {
+    const stranger = await utils.keypairWithSol();
+    await assert.rejects(wrapSol(stranger, lamports), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "WrongAuthority");
+      return true;
+    });
+  });
+
+  it("Wrap SOL", async () => {
+    await wrapSol(user, lamports);
+    assert.equal(await utils.tokenBalance(proxyWalletToken), lamports);
+  });
+
+  it("Unwrap SOL", async () => {
+    const balanceBefore = await connection.getBalance(proxyWallet);
+    const tokenAccountLamports = await connection.getBalance(proxyWalletToken);
+
+    await program.methods
+      .unwrapSol()
+      .accounts({
+        authority: user.publicKey,
+        proxyWallet,
+        proxyWalletTokenAccount: proxyWalletToken,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    const balanceAfter = await connection.getBalance(proxyWallet);
+    assert.equal(balanceAfter - balanceBefore, tokenAccountLamports);
+    assert.equal(await connection.getAccountInfo(proxyWalletToken), null);
+  });
+});
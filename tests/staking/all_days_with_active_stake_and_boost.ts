@@ -233,7 +233,7 @@ describe("Staking simulation | All days with active stake and boost", () => {
   it("Day 0", async () => {
     await stakingUtils.waitUntil(program, startDay);
     await program.methods
-      .stake(new BN(20_000))
+      .stake(new BN(20_000), null)
       .accounts(firstStakeAccounts)
       .signers([firstUser, payer])
       .rpc();
@@ -267,7 +267,7 @@ describe("Staking simulation | All days with active stake and boost", () => {
     await stakingUtils.waitUntil(program, startDay + 1);
 
     await program.methods
-      .stake(new BN(20_000))
+      .stake(new BN(20_000), null)
       .accounts(secondStakeAccounts)
       .signers([secondUser, payer])
       .rpc();
@@ -315,7 +315,7 @@ describe("Staking simulation | All days with active stake and boost", () => {
     await stakingUtils.waitUntil(program, startDay + 4);
 
     await program.methods
-      .stake(new BN(30_000))
+      .stake(new BN(30_000), null)
       .accounts(thirdStakeAccounts)
       .signers([thirdUser, payer])
       .rpc();
@@ -350,13 +350,13 @@ describe("Staking simulation | All days with active stake and boost", () => {
     await stakingUtils.waitUntil(program, startDay + 5);
 
     await program.methods
-      .stake(new BN(20_000))
+      .stake(new BN(20_000), null)
       .accounts(firstStakeAccounts)
       .signers([firstUser, payer])
       .rpc();
 
     await program.methods
-      .stake(new BN(20_000))
+      .stake(new BN(20_000), null)
       .accounts(secondStakeAccounts)
       .signers([secondUser, payer])
       .rpc();
@@ -404,7 +404,7 @@ describe("Staking simulation | All days with active stake and boost", () => {
     await stakingUtils.waitUntil(program, startDay + 8);
 
     await program.methods
-      .stake(new BN(15_000))
+      .stake(new BN(15_000), null)
       .accounts(secondStakeAccounts)
       .signers([secondUser, payer])
       .rpc();
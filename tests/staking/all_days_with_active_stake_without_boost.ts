@@ -154,13 +154,13 @@ describe("Staking simulation | All days with active stake without boost", () =>
   it("Day 0", async () => {
     await stakingUtils.waitUntil(program, startDay + 0);
     await program.methods
-      .stake(new BN(20_000))
+      .stake(new BN(20_000), null)
       .accounts(firstStakeAccounts)
       .signers([firstUser, payer])
       .rpc();
 
     await program.methods
-      .stake(new BN(20_000))
+      .stake(new BN(20_000), null)
       .accounts(secondStakeAccounts)
       .signers([secondUser, payer])
       .rpc();
@@ -258,7 +258,7 @@ describe("Staking simulation | All days with active stake without boost", () =>
     assert.equal(tokenBalance, expectedBalance.toNumber());
 
     await program.methods
-      .stake(new BN(20_000))
+      .stake(new BN(20_000), null)
       .accounts(secondStakeAccounts)
       .signers([secondUser, payer])
       .rpc();
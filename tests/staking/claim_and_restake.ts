@@ -0,0 +1,158 @@
+import * as assert from "assert";
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as stakingUtils from "./utils";
+import { AnchorError, BN, Program } from "@project-serum/anchor";
+import { ChillStaking } from "../../target/types/chill_staking";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("Staking simulation | Claim and restake", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillStaking as Program<ChillStaking>;
+  const primaryWallet = Keypair.generate();
+
+  const totalDays = 20;
+  const stakeAmount = 100_000;
+  const rewardTokensAmount = 100_000_000;
+
+  let payer: Keypair;
+  let chillMint: PublicKey;
+  let stakingInfo: PublicKey;
+  let startDay: number;
+
+  let user: Keypair;
+  let tokenAccount: PublicKey;
+  let userInfo: PublicKey;
+  let stakeAccounts: stakingUtils.StakeAccounts;
+  let restakeAccounts: stakingUtils.ClaimAndRestakeAccounts;
+
+  before(async () => {
+    payer = await utils.keypairWithSol();
+    chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+
+    stakingInfo = await stakingUtils.initializeStaking(
+      primaryWallet,
+      payer,
+      totalDays,
+      chillMint,
+      program
+    );
+
+    await stakingUtils.addRewardTokens(
+      rewardTokensAmount,
+      primaryWallet,
+      chillMint,
+      stakingInfo,
+      program
+    );
+
+    [user, tokenAccount] = await stakingUtils.createUserWithTokenAccount(
+      chillMint,
+      primaryWallet,
+      stakeAmount
+    );
+
+    const info = await program.account.stakingInfo.fetch(stakingInfo);
+    startDay = info.startDay.toNumber();
+
+    const stakingTokenAuthority = await stakingUtils.getStakingAuthority(
+      stakingInfo,
+      program.programId
+    );
+
+    const stakingTokenAccount = await utils.getAssociatedTokenAddress(
+      stakingTokenAuthority,
+      chillMint
+    );
+
+    userInfo = await stakingUtils.getUserInfoPubkey(
+      user.publicKey,
+      stakingInfo,
+      program.programId
+    );
+
+    stakeAccounts = {
+      user: user.publicKey,
+      payer: payer.publicKey,
+      tokenAccountAuthority: user.publicKey,
+      userInfo,
+      fromTokenAccount: tokenAccount,
+      stakingInfo,
+      stakingTokenAuthority,
+      stakingTokenAccount,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+
+    restakeAccounts = {
+      user: user.publicKey,
+      userInfo,
+      stakingInfo,
+      stakingTokenAuthority,
+      stakingTokenAccount,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+
+    await stakingUtils.waitUntil(program, startDay);
+  });
+
+  function claimAndRestake(): Promise<string> {
+    return program.methods
+      .claimAndRestake()
+      .accounts(restakeAccounts)
+      .signers([user])
+      .rpc();
+  }
+
+  it("Stake tokens", async () => {
+    await program.methods
+      .stake(new BN(stakeAmount), null)
+      .accounts(stakeAccounts)
+      .signers([user, payer])
+      .rpc();
+  });
+
+  it("Try to restake without rewards", async () => {
+    await assert.rejects(claimAndRestake, (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "StakeZeroTokens");
+      return true;
+    });
+  });
+
+  it("Restake rewards with the ended stake", async () => {
+    await stakingUtils.waitUntil(program, startDay + 7);
+
+    const reward = await stakingUtils.getUserRewardFromSimulation(
+      program,
+      userInfo,
+      stakingInfo
+    );
+    assert.ok(reward.toNumber() > 0);
+
+    const before = await program.account.stakingInfo.fetch(stakingInfo);
+    await claimAndRestake();
+
+    // Staked tokens return to the pending amount which is staked again
+    const info = await program.account.userInfo.fetch(userInfo);
+    assert.equal(info.stakedAmount.toNumber(), stakeAmount + reward.toNumber());
+    assert.equal(info.pendingAmount.toNumber(), 0);
+    assert.equal(info.rewardedAmount.toNumber(), 0);
+    assert.ok(info.startDay.toNumber() >= startDay + 7);
+
+    const after = await program.account.stakingInfo.fetch(stakingInfo);
+    assert.equal(after.activeStakesNumber.toNumber(), 1);
+    assert.equal(
+      after.totalPendingWithdrawalAmount.toNumber(),
+      before.totalPendingWithdrawalAmount.toNumber() + reward.toNumber()
+    );
+
+    const stakedInDay = await program.methods
+      .viewStakedAmountInDay(info.startDay.subn(startDay))
+      .accounts({ stakingInfo })
+      .view();
+
+    assert.equal(stakedInDay.toNumber(), info.stakedAmount.toNumber());
+    assert.equal(await utils.tokenBalance(tokenAccount), 0);
+  });
+});
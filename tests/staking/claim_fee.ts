@@ -0,0 +1,321 @@
+import * as assert from "assert";
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as nftUtils from "../nft/utils";
+import * as stakingUtils from "./utils";
+import { AnchorError, BN, Program } from "@project-serum/anchor";
+import { ChillNft } from "../../target/types/chill_nft";
+import { ChillStaking } from "../../target/types/chill_staking";
+import {
+  AccountMeta,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+} from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("Staking simulation | Claim fee", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillStaking as Program<ChillStaking>;
+  const nftProgram = anchor.workspace.ChillNft as Program<ChillNft>;
+  const primaryWallet = Keypair.generate();
+
+  const totalDays = 10;
+  const claimFee = 1000;
+  const stakeAmount = 100_000;
+  const rewardTokensAmount = 100_000_000;
+
+  const firstRecipient = Keypair.generate();
+  const secondRecipient = Keypair.generate();
+
+  let payer: Keypair;
+  let chillMint: PublicKey;
+  let config: PublicKey;
+  let stakingInfo: PublicKey;
+  let startDay: number;
+
+  let user: Keypair;
+  let tokenAccount: PublicKey;
+  let firstRecipientTokenAccount: PublicKey;
+  let secondRecipientTokenAccount: PublicKey;
+  let claimAccounts: stakingUtils.ClaimAccounts;
+
+  before(async () => {
+    payer = await utils.keypairWithSol();
+    chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+
+    config = await nftUtils.getNftConfigPubkey(
+      chillMint,
+      nftProgram.programId
+    );
+
+    const configRegistry = await nftUtils.initializeConfigRegistry(
+      nftProgram,
+      payer
+    );
+
+    const adminConfig = await nftUtils.initializeAdminConfig(
+      nftProgram,
+      primaryWallet
+    );
+
+    const recipients = [
+      {
+        address: firstRecipient.publicKey,
+        mintShare: 50,
+        transactionShare: 30,
+      },
+      {
+        address: secondRecipient.publicKey,
+        mintShare: 50,
+        transactionShare: 70,
+      },
+    ];
+
+    await nftProgram.methods
+      .initialize(nftUtils.randomFees(), recipients)
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        adminConfig,
+        payer: payer.publicKey,
+        config,
+        configRegistry,
+        chillMint,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(nftUtils.adminSignerMetas([primaryWallet.publicKey]))
+      .signers([payer, primaryWallet])
+      .rpc();
+
+    firstRecipientTokenAccount = await utils.createTokenAccount(
+      firstRecipient.publicKey,
+      chillMint
+    );
+
+    secondRecipientTokenAccount = await utils.createTokenAccount(
+      secondRecipient.publicKey,
+      chillMint
+    );
+
+    stakingInfo = await stakingUtils.initializeStaking(
+      primaryWallet,
+      payer,
+      totalDays,
+      chillMint,
+      program,
+      claimFee
+    );
+
+    await stakingUtils.addRewardTokens(
+      rewardTokensAmount,
+      primaryWallet,
+      chillMint,
+      stakingInfo,
+      program
+    );
+
+    let tokenAccountAuthority: Keypair;
+    [tokenAccountAuthority, tokenAccount] =
+      await stakingUtils.createUserWithTokenAccount(
+        chillMint,
+        primaryWallet,
+        stakeAmount
+      );
+
+    user = tokenAccountAuthority;
+
+    const info = await program.account.stakingInfo.fetch(stakingInfo);
+    startDay = info.startDay.toNumber();
+
+    const stakingTokenAuthority = await stakingUtils.getStakingAuthority(
+      stakingInfo,
+      program.programId
+    );
+
+    const stakingTokenAccount = await utils.getAssociatedTokenAddress(
+      stakingTokenAuthority,
+      chillMint
+    );
+
+    const userInfo = await stakingUtils.getUserInfoPubkey(
+      user.publicKey,
+      stakingInfo,
+      program.programId
+    );
+
+    await stakingUtils.waitUntil(program, startDay);
+    await program.methods
+      .stake(new BN(stakeAmount), null)
+      .accounts({
+        user: user.publicKey,
+        payer: payer.publicKey,
+        tokenAccountAuthority: user.publicKey,
+        userInfo,
+        fromTokenAccount: tokenAccount,
+        stakingInfo,
+        stakingTokenAuthority,
+        stakingTokenAccount,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user, payer])
+      .rpc();
+
+    claimAccounts = {
+      user: user.publicKey,
+      userInfo,
+      recipientTokenAccount: tokenAccount,
+      stakingInfo,
+      stakingTokenAuthority,
+      stakingTokenAccount,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+  });
+
+  it("Try to initialize with claim fee out of range", async () => {
+    await assert.rejects(
+      async () => {
+        await stakingUtils.initializeStaking(
+          primaryWallet,
+          payer,
+          totalDays,
+          chillMint,
+          program,
+          10_001
+        );
+      },
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "ClaimFeeOutOfRange");
+        return true;
+      }
+    );
+  });
+
+  it("Check claim fee", async () => {
+    const info = await program.account.stakingInfo.fetch(stakingInfo);
+    assert.equal(info.claimFee, claimFee);
+  });
+
+  it("Try to claim rewards without fee config", async () => {
+    await stakingUtils.waitUntil(program, startDay + 7);
+
+    await assert.rejects(
+      async () => {
+        await program.methods
+          .claim(new BN(1000))
+          .accounts(claimAccounts)
+          .signers([user])
+          .rpc();
+      },
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "WrongFeeConfig");
+        return true;
+      }
+    );
+  });
+
+  it("Try to claim rewards without all recipients", async () => {
+    await assert.rejects(
+      async () => {
+        await program.methods
+          .claim(new BN(1000))
+          .accounts(claimAccounts)
+          .remainingAccounts([
+            { pubkey: config, isSigner: false, isWritable: false },
+            {
+              pubkey: firstRecipientTokenAccount,
+              isSigner: false,
+              isWritable: true,
+            },
+          ])
+          .signers([user])
+          .rpc();
+      },
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "WrongRecipientsList");
+        return true;
+      }
+    );
+  });
+
+  it("Claim rewards with fee", async () => {
+    const remainingAccounts: AccountMeta[] = [
+      { pubkey: config, isSigner: false, isWritable: false },
+      {
+        pubkey: firstRecipientTokenAccount,
+        isSigner: false,
+        isWritable: true,
+      },
+      {
+        pubkey: secondRecipientTokenAccount,
+        isSigner: false,
+        isWritable: true,
+      },
+    ];
+
+    const reward = 100_000;
+    const fee = (reward * claimFee) / 10_000;
+    const secondRecipientFee = (fee * 70) / 100;
+    const firstRecipientFee = fee - secondRecipientFee;
+
+    await program.methods
+      .claim(new BN(reward))
+      .accounts(claimAccounts)
+      .remainingAccounts(remainingAccounts)
+      .signers([user])
+      .rpc();
+
+    assert.equal(await utils.tokenBalance(tokenAccount), reward - fee);
+    assert.equal(
+      await utils.tokenBalance(firstRecipientTokenAccount),
+      firstRecipientFee
+    );
+    assert.equal(
+      await utils.tokenBalance(secondRecipientTokenAccount),
+      secondRecipientFee
+    );
+  });
+
+  it("Claim staked tokens without fee", async () => {
+    const userInfo = await program.account.userInfo.fetch(
+      claimAccounts.userInfo as PublicKey
+    );
+
+    const balance = await utils.tokenBalance(tokenAccount);
+    const firstRecipientBalance = await utils.tokenBalance(
+      firstRecipientTokenAccount
+    );
+
+    const rewardedAmount = userInfo.rewardedAmount.toNumber();
+    const amount = rewardedAmount + userInfo.pendingAmount.toNumber();
+    const fee = Math.floor((rewardedAmount * claimFee) / 10_000);
+
+    await program.methods
+      .claim(new BN(amount))
+      .accounts(claimAccounts)
+      .remainingAccounts([
+        { pubkey: config, isSigner: false, isWritable: false },
+        {
+          pubkey: firstRecipientTokenAccount,
+          isSigner: false,
+          isWritable: true,
+        },
+        {
+          pubkey: secondRecipientTokenAccount,
+          isSigner: false,
+          isWritable: true,
+        },
+      ])
+      .signers([user])
+      .rpc();
+
+    assert.equal(
+      await utils.tokenBalance(tokenAccount),
+      balance + amount - fee
+    );
+    assert.ok(
+      (await utils.tokenBalance(firstRecipientTokenAccount)) >=
+        firstRecipientBalance
+    );
+  });
+});
@@ -0,0 +1,174 @@
+import * as assert from "assert";
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as stakingUtils from "./utils";
+import { AnchorError, BN, Program } from "@project-serum/anchor";
+import { ChillStaking } from "../../target/types/chill_staking";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("Staking simulation | Close user infos batch", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillStaking as Program<ChillStaking>;
+
+  const totalDays = 10;
+  const stakeAmount = 100_000;
+  const rewardTokensAmount = 100_000_000;
+
+  let primaryWallet: Keypair;
+  let payer: Keypair;
+  let treasury: Keypair;
+  let chillMint: PublicKey;
+  let stakingInfo: PublicKey;
+  let stakingTokenAuthority: PublicKey;
+  let stakingTokenAccount: PublicKey;
+  let endDay: number;
+
+  const users: [Keypair, PublicKey, PublicKey][] = [];
+
+  async function closeBatch(userInfos: PublicKey[]) {
+    await program.methods
+      .closeStakingAccountsBatch()
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        stakingInfo,
+        treasury: treasury.publicKey,
+      })
+      .remainingAccounts(
+        userInfos.map((pubkey) => ({
+          pubkey,
+          isSigner: false,
+          isWritable: true,
+        }))
+      )
+      .signers([primaryWallet])
+      .rpc();
+  }
+
+  before(async () => {
+    primaryWallet = await utils.keypairWithSol();
+    payer = await utils.keypairWithSol();
+    treasury = Keypair.generate();
+    chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+
+    stakingInfo = await stakingUtils.initializeStaking(
+      primaryWallet,
+      payer,
+      totalDays,
+      chillMint,
+      program
+    );
+
+    await stakingUtils.addRewardTokens(
+      rewardTokensAmount,
+      primaryWallet,
+      chillMint,
+      stakingInfo,
+      program
+    );
+
+    const info = await program.account.stakingInfo.fetch(stakingInfo);
+    endDay = info.endDay.toNumber();
+
+    stakingTokenAuthority = await stakingUtils.getStakingAuthority(
+      stakingInfo,
+      program.programId
+    );
+
+    stakingTokenAccount = await utils.getAssociatedTokenAddress(
+      stakingTokenAuthority,
+      chillMint
+    );
+
+    await stakingUtils.waitUntil(program, info.startDay.toNumber());
+
+    for (let i = 0; i < 3; i++) {
+      const [user, tokenAccount] =
+        await stakingUtils.createUserWithTokenAccount(
+          chillMint,
+          primaryWallet,
+          stakeAmount
+        );
+
+      const userInfo = await stakingUtils.getUserInfoPubkey(
+        user.publicKey,
+        stakingInfo,
+        program.programId
+      );
+
+      await program.methods
+        .stake(new BN(stakeAmount), null)
+        .accounts({
+          user: user.publicKey,
+          payer: payer.publicKey,
+          tokenAccountAuthority: user.publicKey,
+          userInfo,
+          fromTokenAccount: tokenAccount,
+          stakingInfo,
+          stakingTokenAuthority,
+          stakingTokenAccount,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user, payer])
+        .rpc();
+
+      users.push([user, tokenAccount, userInfo]);
+    }
+  });
+
+  it("Try to close user infos before the end of the staking", async () => {
+    await assert.rejects(closeBatch([users[0][2]]), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "StakingIsNotFinished");
+      return true;
+    });
+  });
+
+  it("Try to close a user info with tokens to withdraw", async () => {
+    await stakingUtils.waitUntil(program, endDay);
+
+    await assert.rejects(closeBatch([users[2][2]]), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "UserInfoHasTokensToWithdraw");
+      return true;
+    });
+  });
+
+  it("Close user infos of claimed stakes", async () => {
+    const claimed = users.slice(0, 2);
+    for (const [user, tokenAccount, userInfo] of claimed) {
+      await program.methods
+        .claimAll()
+        .accounts({
+          user: user.publicKey,
+          userInfo,
+          recipientTokenAccount: tokenAccount,
+          stakingInfo,
+          stakingTokenAuthority,
+          stakingTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+    }
+
+    const userInfos = claimed.map(([, , userInfo]) => userInfo);
+    let rent = 0;
+    for (const userInfo of userInfos) {
+      rent += await program.provider.connection.getBalance(userInfo);
+    }
+
+    await closeBatch(userInfos);
+
+    for (const userInfo of userInfos) {
+      assert.equal(
+        await program.provider.connection.getAccountInfo(userInfo),
+        null
+      );
+    }
+
+    assert.equal(
+      await program.provider.connection.getBalance(treasury.publicKey),
+      rent
+    );
+  });
+});
@@ -0,0 +1,195 @@
+import * as assert from "assert";
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as stakingUtils from "./utils";
+import { BN, Program } from "@project-serum/anchor";
+import { ChillStaking } from "../../target/types/chill_staking";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("Staking | Delegate", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillStaking as Program<ChillStaking>;
+  const primaryWallet = Keypair.generate();
+
+  const totalDays = 10;
+  const daysInWindow = 2;
+  const stakeAmount = 100_000;
+  const rewardTokensAmount = 100_000_000;
+
+  let payer: Keypair;
+  let chillMint: PublicKey;
+  let stakingInfo: PublicKey;
+  let stakingTokenAuthority: PublicKey;
+  let stakingTokenAccount: PublicKey;
+  let startDay: number;
+
+  let user: Keypair;
+  let tokenAccount: PublicKey;
+  let userInfo: PublicKey;
+
+  let delegate: Keypair;
+  let delegateInfo: PublicKey;
+
+  async function delegateBoost() {
+    await program.methods
+      .delegateBoost()
+      .accounts({
+        delegate: delegate.publicKey,
+        delegateInfo,
+        userInfo,
+        stakingInfo,
+      })
+      .signers([delegate])
+      .rpc();
+  }
+
+  async function delegateClaim(recipientTokenAccount: PublicKey) {
+    await program.methods
+      .delegateClaim(new BN(stakeAmount))
+      .accounts({
+        delegate: delegate.publicKey,
+        delegateInfo,
+        userInfo,
+        recipientTokenAccount,
+        stakingInfo,
+        stakingTokenAuthority,
+        stakingTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([delegate])
+      .rpc();
+  }
+
+  before(async () => {
+    payer = await utils.keypairWithSol();
+    chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+
+    stakingInfo = await stakingUtils.initializeStaking(
+      primaryWallet,
+      payer,
+      totalDays,
+      chillMint,
+      program,
+      0,
+      daysInWindow
+    );
+
+    await stakingUtils.addRewardTokens(
+      rewardTokensAmount,
+      primaryWallet,
+      chillMint,
+      stakingInfo,
+      program
+    );
+
+    [user, tokenAccount] = await stakingUtils.createUserWithTokenAccount(
+      chillMint,
+      primaryWallet,
+      stakeAmount
+    );
+
+    userInfo = await stakingUtils.getUserInfoPubkey(
+      user.publicKey,
+      stakingInfo,
+      program.programId
+    );
+
+    delegate = Keypair.generate();
+    delegateInfo = (
+      await PublicKey.findProgramAddress(
+        [Buffer.from("delegate"), userInfo.toBytes()],
+        program.programId
+      )
+    )[0];
+
+    stakingTokenAuthority = await stakingUtils.getStakingAuthority(
+      stakingInfo,
+      program.programId
+    );
+
+    stakingTokenAccount = await utils.getAssociatedTokenAddress(
+      stakingTokenAuthority,
+      chillMint
+    );
+
+    const info = await program.account.stakingInfo.fetch(stakingInfo);
+    startDay = info.startDay.toNumber();
+    await stakingUtils.waitUntil(program, startDay);
+
+    await program.methods
+      .stake(new BN(stakeAmount), null)
+      .accounts({
+        user: user.publicKey,
+        payer: payer.publicKey,
+        tokenAccountAuthority: user.publicKey,
+        userInfo,
+        fromTokenAccount: tokenAccount,
+        stakingInfo,
+        stakingTokenAuthority,
+        stakingTokenAccount,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user, payer])
+      .rpc();
+  });
+
+  it("Try to boost without a delegate", async () => {
+    await assert.rejects(delegateBoost());
+  });
+
+  it("Set delegate", async () => {
+    await program.methods
+      .setDelegate(delegate.publicKey)
+      .accounts({
+        user: user.publicKey,
+        payer: payer.publicKey,
+        userInfo,
+        delegateInfo,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user, payer])
+      .rpc();
+
+    const info = await program.account.delegateInfo.fetch(delegateInfo);
+    assert.ok(info.user.equals(user.publicKey));
+    assert.ok(info.userInfo.equals(userInfo));
+    assert.ok(info.delegate.equals(delegate.publicKey));
+  });
+
+  it("Boost by the delegate", async () => {
+    await delegateBoost();
+
+    const info = await program.account.userInfo.fetch(userInfo);
+    assert.equal(info.totalBoostNumber.toNumber(), 1);
+  });
+
+  it("Try to claim to a token account of the delegate", async () => {
+    await stakingUtils.waitUntil(program, startDay + daysInWindow);
+
+    const delegateTokenAccount = await utils.createTokenAccount(
+      delegate.publicKey,
+      chillMint
+    );
+
+    await assert.rejects(delegateClaim(delegateTokenAccount));
+  });
+
+  it("Claim by the delegate", async () => {
+    await delegateClaim(tokenAccount);
+    assert.equal(await utils.tokenBalance(tokenAccount), stakeAmount);
+  });
+
+  it("Revoke delegate", async () => {
+    await program.methods
+      .revokeDelegate()
+      .accounts({ user: user.publicKey, delegateInfo })
+      .signers([user])
+      .rpc();
+
+    const connection = anchor.getProvider().connection;
+    assert.equal(await connection.getAccountInfo(delegateInfo), null);
+    await assert.rejects(delegateClaim(tokenAccount));
+  });
+});
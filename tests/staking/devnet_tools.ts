@@ -0,0 +1,154 @@
+import * as assert from "assert";
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as stakingUtils from "./utils";
+import { AnchorError, BN, Program } from "@project-serum/anchor";
+import { ChillStaking } from "../../target/types/chill_staking";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+} from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+
+// Requires the staking program built with the "devnet-tools" feature
+describe("Staking simulation | Devnet tools", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillStaking as Program<ChillStaking>;
+  const primaryWallet = Keypair.generate();
+  const totalDays = 10;
+
+  let payer: Keypair;
+  let chillMint: PublicKey;
+  let stakingInfo: PublicKey;
+
+  let faucetAuthority: PublicKey;
+  let faucetMint: PublicKey;
+
+  before(async () => {
+    payer = await utils.keypairWithSol();
+    chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+    stakingInfo = await stakingUtils.initializeStaking(
+      primaryWallet,
+      payer,
+      totalDays,
+      chillMint,
+      program
+    );
+
+    [faucetAuthority] = await PublicKey.findProgramAddress(
+      [Buffer.from("devnet-faucet")],
+      program.programId
+    );
+
+    [faucetMint] = await PublicKey.findProgramAddress(
+      [Buffer.from("devnet-faucet-mint")],
+      program.programId
+    );
+  });
+
+  it("Check that devnet tools are enabled", async () => {
+    const enabled = await program.methods.viewDevnetToolsEnabled().view();
+    assert.equal(enabled, true);
+  });
+
+  it("Try to advance clock with wrong primary wallet", async () => {
+    const wrongPrimaryWallet = Keypair.generate();
+    await assert.rejects(
+      async () => {
+        await program.methods
+          .devnetAdvanceClock(new BN(stakingUtils.SEC_IN_DAY))
+          .accounts({
+            primaryWallet: wrongPrimaryWallet.publicKey,
+            stakingInfo,
+          })
+          .signers([wrongPrimaryWallet])
+          .rpc();
+      },
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "ConstraintHasOne");
+        return true;
+      }
+    );
+  });
+
+  it("Advance clock", async () => {
+    await program.methods
+      .devnetAdvanceClock(new BN(stakingUtils.SEC_IN_DAY))
+      .accounts({ primaryWallet: primaryWallet.publicKey, stakingInfo })
+      .signers([primaryWallet])
+      .rpc();
+
+    const info = await program.account.stakingInfo.fetch(stakingInfo);
+    assert.equal(info.clockOffset.toNumber(), stakingUtils.SEC_IN_DAY);
+  });
+
+  it("Force settle", async () => {
+    await program.methods
+      .devnetForceSettle()
+      .accounts({ primaryWallet: primaryWallet.publicKey, stakingInfo })
+      .signers([primaryWallet])
+      .rpc();
+
+    const info = await program.account.stakingInfo.fetch(stakingInfo);
+    const currentTime = await utils.getCurrentTime();
+    const currentDay = Math.floor(
+      (currentTime + info.clockOffset.toNumber()) / stakingUtils.SEC_IN_DAY
+    );
+
+    assert.ok(currentDay >= info.endDay.toNumber());
+  });
+
+  it("Try to add reward tokens after force settle", async () => {
+    await assert.rejects(
+      async () => {
+        await stakingUtils.addRewardTokens(
+          1000,
+          primaryWallet,
+          chillMint,
+          stakingInfo,
+          program
+        );
+      },
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "StakingIsFinished");
+        return true;
+      }
+    );
+  });
+
+  it("Get tokens from faucet", async () => {
+    await program.methods
+      .devnetInitializeFaucet()
+      .accounts({
+        payer: payer.publicKey,
+        faucetAuthority,
+        faucetMint,
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([payer])
+      .rpc();
+
+    const user = Keypair.generate();
+    const tokenAccount = await utils.createTokenAccount(
+      user.publicKey,
+      faucetMint
+    );
+
+    const amount = 1_000_000;
+    await program.methods
+      .devnetFaucet(new BN(amount))
+      .accounts({
+        faucetAuthority,
+        faucetMint,
+        tokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    assert.equal(await utils.tokenBalance(tokenAccount), amount);
+  });
+});
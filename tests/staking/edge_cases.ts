@@ -1,8 +1,10 @@
 import * as assert from "assert";
 import * as anchor from "@project-serum/anchor";
 import * as utils from "../utils";
+import * as nftUtils from "../nft/utils";
 import * as stakingUtils from "./utils";
 import { AnchorError, BN, Program } from "@project-serum/anchor";
+import { ChillNft } from "../../target/types/chill_nft";
 import { ChillStaking } from "../../target/types/chill_staking";
 import {
   Keypair,
@@ -19,6 +21,7 @@ import {
 describe("Staking simulation | Edge cases", () => {
   anchor.setProvider(anchor.AnchorProvider.env());
   const program = anchor.workspace.ChillStaking as Program<ChillStaking>;
+  const nftProgram = anchor.workspace.ChillNft as Program<ChillNft>;
   const primaryWallet = Keypair.generate();
 
   let payer: Keypair;
@@ -107,7 +110,14 @@ describe("Staking simulation | Edge cases", () => {
     await assert.rejects(
       async () => {
         await program.methods
-          .initialize({ startTime: wrongStartTime, endTime, minStakeSize })
+          .initialize({
+            startTime: wrongStartTime,
+            endTime,
+            minStakeSize,
+            claimFee: 0,
+            daysInWindow: 7,
+            referralFee: 0,
+          })
           .accounts(initializeAccounts)
           .preInstructions([createStakingAccountInstruction])
           .signers([primaryWallet, payer, stakingInfoKeypair])
@@ -123,7 +133,14 @@ describe("Staking simulation | Edge cases", () => {
   it("Try to initialize with endDay <= startDay", async () => {
     await assert.rejects(async () => {
       await program.methods
-        .initialize({ startTime: endTime, endTime: startTime, minStakeSize })
+        .initialize({
+          startTime: endTime,
+          endTime: startTime,
+          minStakeSize,
+          claimFee: 0,
+          daysInWindow: 7,
+          referralFee: 0,
+        })
         .accounts(initializeAccounts)
         .preInstructions([createStakingAccountInstruction])
         .signers([primaryWallet, payer, stakingInfoKeypair])
@@ -131,9 +148,42 @@ describe("Staking simulation | Edge cases", () => {
     });
   });
 
+  it("Try to initialize with a wrong window length", async () => {
+    for (const daysInWindow of [0, 31]) {
+      await assert.rejects(
+        async () => {
+          await program.methods
+            .initialize({
+              startTime,
+              endTime,
+              minStakeSize,
+              claimFee: 0,
+              daysInWindow,
+              referralFee: 0,
+            })
+            .accounts(initializeAccounts)
+            .preInstructions([createStakingAccountInstruction])
+            .signers([primaryWallet, payer, stakingInfoKeypair])
+            .rpc();
+        },
+        (err: AnchorError) => {
+          assert.equal(err.error.errorCode.code, "WindowLengthOutOfRange");
+          return true;
+        }
+      );
+    }
+  });
+
   it("Initialize", async () => {
     await program.methods
-      .initialize({ startTime, endTime, minStakeSize })
+      .initialize({
+        startTime,
+        endTime,
+        minStakeSize,
+        claimFee: 0,
+        daysInWindow: 7,
+        referralFee: 0,
+      })
       .accounts(initializeAccounts)
       .preInstructions([createStakingAccountInstruction])
       .signers([primaryWallet, payer, stakingInfoKeypair])
@@ -156,7 +206,14 @@ describe("Staking simulation | Edge cases", () => {
   it("Try to initialize twice", async () => {
     await assert.rejects(async () => {
       await program.methods
-        .initialize({ startTime, endTime, minStakeSize })
+        .initialize({
+          startTime,
+          endTime,
+          minStakeSize,
+          claimFee: 0,
+          daysInWindow: 7,
+          referralFee: 0,
+        })
         .accounts(initializeAccounts)
         .signers([primaryWallet, payer])
         .rpc();
@@ -205,7 +262,7 @@ describe("Staking simulation | Edge cases", () => {
     await assert.rejects(
       async () => {
         await program.methods
-          .stake(new BN(stakeAmount))
+          .stake(new BN(stakeAmount), null)
           .accounts(stakeAccounts)
           .signers([user, payer, tokenAccountAuthority])
           .rpc();
@@ -263,7 +320,7 @@ describe("Staking simulation | Edge cases", () => {
     await assert.rejects(
       async () => {
         await program.methods
-          .stake(new BN(0))
+          .stake(new BN(0), null)
           .accounts(stakeAccounts)
           .signers([user, payer, tokenAccountAuthority])
           .rpc();
@@ -279,7 +336,7 @@ describe("Staking simulation | Edge cases", () => {
     await assert.rejects(
       async () => {
         await program.methods
-          .stake(minStakeSize.subn(1))
+          .stake(minStakeSize.subn(1), null)
           .accounts(stakeAccounts)
           .signers([user, payer, tokenAccountAuthority])
           .rpc();
@@ -293,7 +350,7 @@ describe("Staking simulation | Edge cases", () => {
 
   it("Stake once", async () => {
     await program.methods
-      .stake(new BN(stakeAmount))
+      .stake(new BN(stakeAmount), null)
       .accounts(stakeAccounts)
       .signers([user, payer, tokenAccountAuthority])
       .rpc();
@@ -326,7 +383,7 @@ describe("Staking simulation | Edge cases", () => {
     await assert.rejects(
       async () => {
         await program.methods
-          .stake(new BN(0))
+          .stake(new BN(0), null)
           .accounts(stakeAccounts)
           .signers([user, payer, tokenAccountAuthority])
           .rpc();
@@ -342,7 +399,7 @@ describe("Staking simulation | Edge cases", () => {
     await stakingUtils.waitUntil(program, startDay + 1);
 
     await program.methods
-      .stake(new BN(stakeAmount))
+      .stake(new BN(stakeAmount), null)
       .accounts(stakeAccounts)
       .signers([user, payer, tokenAccountAuthority])
       .rpc();
@@ -407,23 +464,84 @@ describe("Staking simulation | Edge cases", () => {
     );
   });
 
-  it("Check boosted days array", async () => {
-    const boostedDays: Array<boolean> = await program.methods
-      .viewBoostedDaysList()
+  it("Check boost calendar", async () => {
+    const calendar = await program.methods
+      .viewBoostCalendar()
+      .accounts({
+        userInfo: userInfoPubkey,
+        stakingInfo: stakingInfoPubkey,
+      })
+      .view();
+
+    const userInfo = await program.account.userInfo.fetch(userInfoPubkey);
+    const userStartDay = userInfo.startDay.toNumber();
+    assert.equal(calendar.startDay.toNumber(), userStartDay);
+
+    assert.deepEqual(
+      calendar.days.map((day) => day.isBoosted),
+      [false, false, true, false, false, false, false]
+    );
+
+    calendar.days.forEach((day, index) => {
+      assert.equal(day.day.toNumber(), userStartDay + index);
+      assert.equal(day.isEligible, userStartDay + index < startDay + totalDays);
+
+      const multiplier = !day.isEligible ? 0 : day.isBoosted ? 2 : 1;
+      assert.equal(day.multiplier.toNumber(), multiplier);
+    });
+  });
+
+  it("Check user summary", async () => {
+    const summary = await program.methods
+      .viewUserSummary()
       .accounts({
         userInfo: userInfoPubkey,
+        stakingInfo: stakingInfoPubkey,
       })
       .view();
 
-    assert.deepEqual(boostedDays, [
-      false,
-      false,
-      true,
-      false,
-      false,
-      false,
-      false,
-    ]);
+    const userInfo = await program.account.userInfo.fetch(userInfoPubkey);
+    assert.equal(summary.startDay.toNumber(), userInfo.startDay.toNumber());
+    assert.equal(
+      summary.stakedAmount.toNumber(),
+      userInfo.stakedAmount.toNumber()
+    );
+    assert.equal(
+      summary.pendingAmount.toNumber(),
+      userInfo.pendingAmount.toNumber()
+    );
+    assert.equal(
+      summary.dailyStakingReward.toNumber(),
+      userInfo.dailyStakingReward.toNumber()
+    );
+    assert.deepEqual(
+      summary.boostedDays,
+      [false, false, true, false, false, false, false]
+    );
+    assert.ok(summary.projectedReward.toNumber() > 0);
+  });
+
+  it("Check projected reward", async () => {
+    const accounts = {
+      userInfo: userInfoPubkey,
+      stakingInfo: stakingInfoPubkey,
+    };
+
+    const summary = await program.methods
+      .viewUserSummary()
+      .accounts(accounts)
+      .view();
+
+    const projected = await program.methods
+      .viewProjectedReward()
+      .accounts(accounts)
+      .view();
+
+    assert.equal(
+      projected.minReward.toNumber(),
+      summary.projectedReward.toNumber()
+    );
+    assert.ok(projected.maxReward.toNumber() > projected.minReward.toNumber());
   });
 
   it("Claim pending amount", async () => {
@@ -667,7 +785,7 @@ describe("Staking simulation | Edge cases", () => {
     await stakingUtils.waitUntil(program, startDay + 8);
 
     await program.methods
-      .stake(new BN(0))
+      .stake(new BN(0), null)
       .accounts(stakeAccounts)
       .signers([user, payer, tokenAccountAuthority])
       .rpc();
@@ -798,7 +916,7 @@ describe("Staking simulation | Edge cases", () => {
     await assert.rejects(
       async () => {
         await program.methods
-          .stake(new BN(1))
+          .stake(new BN(1), null)
           .accounts(stakeAccounts)
           .signers([user, payer, tokenAccountAuthority])
           .rpc();
@@ -830,6 +948,71 @@ describe("Staking simulation | Edge cases", () => {
     stakingUtils.assertStakingInfoEqual(stakingInfo, expectedStakingInfo);
   });
 
+  it("Verify pool solvency", async () => {
+    const stakingInfo = await program.account.stakingInfo.fetch(
+      stakingInfoPubkey
+    );
+
+    const report = await program.methods
+      .verifyPoolSolvency()
+      .accounts({
+        stakingInfo: stakingInfoPubkey,
+        stakingTokenAuthority,
+        stakingTokenAccount,
+      })
+      .view();
+
+    const tokenBalance = await utils.tokenBalance(stakingTokenAccount);
+    const freeRewardAmount = stakingInfo.rewardTokensAmount.sub(
+      stakingInfo.totalRewardedAmount
+    );
+
+    assert.equal(report.tokenAccountAmount.toNumber(), tokenBalance);
+    assert.ok(report.freeRewardAmount.eq(freeRewardAmount));
+    assert.ok(report.pendingWithdrawalAmount.eqn(0));
+    assert.ok(report.requiredAmount.eq(freeRewardAmount));
+    assert.ok(report.isSolvent);
+  });
+
+  it("Reconcile", async () => {
+    const stakingInfo = await program.account.stakingInfo.fetch(
+      stakingInfoPubkey
+    );
+
+    const userInfo = await program.account.userInfo.fetch(userInfoPubkey);
+
+    const report = await program.methods
+      .reconcile()
+      .accounts({ stakingInfo: stakingInfoPubkey })
+      .remainingAccounts([
+        { pubkey: userInfoPubkey, isSigner: false, isWritable: false },
+      ])
+      .view();
+
+    assert.ok(report.totalStakedAmount.eq(stakingInfo.totalStakedAmount));
+    assert.ok(report.daysStakedAmount.eq(stakingInfo.totalStakedAmount));
+    assert.ok(report.totalRewardedAmount.eq(stakingInfo.totalRewardedAmount));
+    assert.ok(report.activeStakesNumber.eq(stakingInfo.activeStakesNumber));
+    assert.ok(report.usersNumber.eqn(1));
+    assert.ok(report.usersStakedAmount.eq(userInfo.totalStakedAmount));
+    assert.ok(report.usersRewardedAmount.eq(userInfo.totalRewardedAmount));
+    assert.ok(
+      report.usersActiveStakesNumber.eq(stakingInfo.activeStakesNumber)
+    );
+
+    // Duplicate accounts would be counted twice
+    await assert.rejects(async () => {
+      await program.methods
+        .reconcile()
+        .accounts({ stakingInfo: stakingInfoPubkey })
+        .remainingAccounts([
+          { pubkey: userInfoPubkey, isSigner: false, isWritable: false },
+          { pubkey: userInfoPubkey, isSigner: false, isWritable: false },
+        ])
+        .view();
+    });
+  });
+
   it("Redeem remainings", async () => {
     let stakingInfo = await program.account.stakingInfo.fetch(
       stakingInfoPubkey
@@ -842,16 +1025,23 @@ describe("Staking simulation | Edge cases", () => {
     let tokenBalance = await utils.tokenBalance(tokenAccount);
     const expectedBalace = tokenBalance + remainings.toNumber();
 
+    const adminConfig = await nftUtils.initializeAdminConfig(
+      nftProgram,
+      primaryWallet
+    );
+
     await program.methods
       .redeemRemainingRewardTokens(remainings)
       .accounts({
         primaryWallet: primaryWallet.publicKey,
+        adminConfig,
         stakingInfo: stakingInfoPubkey,
         stakingTokenAuthority,
         stakingTokenAccount,
         recipientTokenAccount: tokenAccount,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
+      .remainingAccounts(nftUtils.adminSignerMetas([primaryWallet.publicKey]))
       .signers([primaryWallet])
       .rpc();
 
@@ -865,18 +1055,27 @@ describe("Staking simulation | Edge cases", () => {
   });
 
   it("Try to redeem again", async () => {
+    const adminConfig = await nftUtils.getAdminConfigPubkey(
+      primaryWallet.publicKey,
+      nftProgram.programId
+    );
+
     await assert.rejects(
       async () => {
         await program.methods
           .redeemRemainingRewardTokens(new BN(1))
           .accounts({
             primaryWallet: primaryWallet.publicKey,
+            adminConfig,
             stakingInfo: stakingInfoPubkey,
             stakingTokenAuthority,
             stakingTokenAccount,
             recipientTokenAccount: tokenAccount,
             tokenProgram: TOKEN_PROGRAM_ID,
           })
+          .remainingAccounts(
+            nftUtils.adminSignerMetas([primaryWallet.publicKey])
+          )
           .signers([primaryWallet])
           .rpc();
       },
@@ -0,0 +1,188 @@
+import * as assert from "assert";
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as stakingUtils from "./utils";
+import { AnchorError, BN, Program } from "@project-serum/anchor";
+import { ChillStaking } from "../../target/types/chill_staking";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+
+// Requires the staking program built with the "devnet-tools" feature
+describe("Staking simulation | Migrate", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillStaking as Program<ChillStaking>;
+
+  const totalDays = 10;
+  const stakeAmount = 100_000;
+  const rewardTokensAmount = 100_000_000;
+
+  let primaryWallet: Keypair;
+  let payer: Keypair;
+  let chillMint: PublicKey;
+  let stakingInfo: PublicKey;
+  let stakingTokenAuthority: PublicKey;
+  let stakingTokenAccount: PublicKey;
+
+  async function migrateStakingInfo(tokenAccount = stakingTokenAccount) {
+    await program.methods
+      .migrateStakingInfo()
+      .accounts({
+        payer: payer.publicKey,
+        stakingInfo,
+        stakingTokenAuthority,
+        mint: chillMint,
+        stakingTokenAccount: tokenAccount,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([payer])
+      .rpc();
+  }
+
+  before(async () => {
+    primaryWallet = await utils.keypairWithSol();
+    payer = await utils.keypairWithSol();
+    chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+
+    stakingInfo = await stakingUtils.initializeStaking(
+      primaryWallet,
+      payer,
+      totalDays,
+      chillMint,
+      program
+    );
+
+    stakingTokenAuthority = await stakingUtils.getStakingAuthority(
+      stakingInfo,
+      program.programId
+    );
+
+    stakingTokenAccount = await utils.getAssociatedTokenAddress(
+      stakingTokenAuthority,
+      chillMint
+    );
+  });
+
+  it("Staking info is created with the current layout", async () => {
+    const info = await program.account.stakingInfo.fetch(stakingInfo);
+    assert.equal(info.version, 2);
+  });
+
+  it("Try to migrate the staking info of the current layout", async () => {
+    await assert.rejects(migrateStakingInfo(), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "AccountIsAlreadyMigrated");
+      return true;
+    });
+  });
+
+  it("Claim from a pool migrated from the legacy layout", async () => {
+    await stakingUtils.addRewardTokens(
+      rewardTokensAmount,
+      primaryWallet,
+      chillMint,
+      stakingInfo,
+      program
+    );
+
+    const [user, tokenAccount] = await stakingUtils.createUserWithTokenAccount(
+      chillMint,
+      primaryWallet,
+      stakeAmount
+    );
+
+    const userInfo = await stakingUtils.getUserInfoPubkey(
+      user.publicKey,
+      stakingInfo,
+      program.programId
+    );
+
+    const info = await program.account.stakingInfo.fetch(stakingInfo);
+    await stakingUtils.waitUntil(program, info.startDay.toNumber());
+
+    await program.methods
+      .stake(new BN(stakeAmount), null)
+      .accounts({
+        user: user.publicKey,
+        payer: payer.publicKey,
+        tokenAccountAuthority: user.publicKey,
+        userInfo,
+        fromTokenAccount: tokenAccount,
+        stakingInfo,
+        stakingTokenAuthority,
+        stakingTokenAccount,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user, payer])
+      .rpc();
+
+    await stakingUtils.waitForWeek(program);
+    const currentInfo = await program.account.stakingInfo.fetch(stakingInfo);
+    const pendingWithdrawalAmount =
+      currentInfo.totalPendingWithdrawalAmount.toNumber();
+    assert.equal(currentInfo.totalStakeWeight.toNumber(), stakeAmount * 7);
+
+    // Legacy pools have zero in the reserved bytes of the withdrawal counter
+    await program.methods
+      .devnetDowngradeStakingInfo()
+      .accounts({ primaryWallet: primaryWallet.publicKey, stakingInfo })
+      .signers([primaryWallet])
+      .rpc();
+
+    const legacyInfo = await program.account.stakingInfo.fetch(stakingInfo);
+    assert.equal(legacyInfo.totalPendingWithdrawalAmount.toNumber(), 0);
+
+    // An empty account of the pool authority would zero the withdrawal counter
+    const otherTokenAccount = await utils.createTokenAccount(
+      stakingTokenAuthority,
+      chillMint
+    );
+
+    await assert.rejects(
+      migrateStakingInfo(otherTokenAccount),
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "ConstraintAssociated");
+        return true;
+      }
+    );
+
+    await migrateStakingInfo();
+
+    const migratedInfo = await program.account.stakingInfo.fetch(stakingInfo);
+    assert.equal(migratedInfo.version, 2);
+    assert.equal(
+      migratedInfo.totalPendingWithdrawalAmount.toNumber(),
+      pendingWithdrawalAmount
+    );
+
+    // The stake lasted a whole window, so the legacy weight is the same
+    assert.equal(migratedInfo.totalStakeWeight.toNumber(), stakeAmount * 7);
+
+    const reward = await stakingUtils.getUserRewardFromSimulation(
+      program,
+      userInfo,
+      stakingInfo
+    );
+
+    await program.methods
+      .claimAll()
+      .accounts({
+        user: user.publicKey,
+        userInfo,
+        recipientTokenAccount: tokenAccount,
+        stakingInfo,
+        stakingTokenAuthority,
+        stakingTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    assert.equal(
+      await utils.tokenBalance(tokenAccount),
+      stakeAmount + reward.toNumber()
+    );
+
+    const claimedInfo = await program.account.stakingInfo.fetch(stakingInfo);
+    assert.equal(claimedInfo.totalPendingWithdrawalAmount.toNumber(), 0);
+  });
+});
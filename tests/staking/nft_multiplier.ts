@@ -0,0 +1,417 @@
+import * as assert from "assert";
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as nftUtils from "../nft/utils";
+import * as stakingUtils from "./utils";
+import { AnchorError, BN, Program } from "@project-serum/anchor";
+import { ChillNft } from "../../target/types/chill_nft";
+import { ChillStaking } from "../../target/types/chill_staking";
+import {
+  AccountMeta,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+} from "@solana/web3.js";
+import { programs } from "@metaplex/js";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("Staking simulation | NFT multiplier", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillStaking as Program<ChillStaking>;
+  const nftProgram = anchor.workspace.ChillNft as Program<ChillNft>;
+  const Metadata = programs.metadata;
+
+  const totalDays = 10;
+  const stakeAmount = 100_000;
+  const rewardTokensAmount = 100_000_000;
+  const rewardBonus = 50;
+
+  let primaryWallet: Keypair;
+  let payer: Keypair;
+  let chillMint: PublicKey;
+  let config: PublicKey;
+  let collectionMint: PublicKey;
+  let collectionNft: PublicKey;
+  let otherNft: PublicKey;
+  let stakingInfo: PublicKey;
+  let nftMultiplierInfo: PublicKey;
+
+  async function mintNft(collection: PublicKey | null): Promise<PublicKey> {
+    const nftMint = await utils.createMint(primaryWallet.publicKey, 0);
+    const nftToken = await utils.createTokenAccount(
+      primaryWallet.publicKey,
+      nftMint
+    );
+    await utils.mintTokens(primaryWallet, nftMint, nftToken, 1);
+
+    const remainingAccounts: AccountMeta[] = collection
+      ? [
+          { pubkey: collection, isSigner: false, isWritable: false },
+          {
+            pubkey: await Metadata.Metadata.getPDA(collection),
+            isSigner: false,
+            isWritable: false,
+          },
+          {
+            pubkey: await Metadata.MasterEdition.getPDA(collection),
+            isSigner: false,
+            isWritable: false,
+          },
+        ]
+      : [];
+
+    await nftProgram.methods
+      .mintNft(
+        nftUtils.randomNftType(),
+        nftUtils.randomNftArgs(),
+        null,
+        0,
+        collection,
+        null
+      )
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        payer: primaryWallet.publicKey,
+        chillPayer: primaryWallet.publicKey,
+        chillPayerTokenAccount: await utils.createTokenAccount(
+          primaryWallet.publicKey,
+          chillMint
+        ),
+        config,
+        chillMint,
+        nftTypeRegistry: await nftUtils.getNftTypeRegistryPubkey(
+          config,
+          nftProgram.programId
+        ),
+        nftMint,
+        nftMetadata: await Metadata.Metadata.getPDA(nftMint),
+        nftMasterEdition: await Metadata.MasterEdition.getPDA(nftMint),
+        nftChillMetadata: await nftUtils.getChillMetadataPubkey(
+          nftMint,
+          nftProgram.programId
+        ),
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMetadataProgram: Metadata.MetadataProgram.PUBKEY,
+      })
+      .remainingAccounts(remainingAccounts)
+      .signers([primaryWallet])
+      .rpc();
+
+    return nftMint;
+  }
+
+  async function nftHolderMetas(
+    user: PublicKey,
+    nftMint: PublicKey
+  ): Promise<AccountMeta[]> {
+    const tokenAccounts =
+      await program.provider.connection.getTokenAccountsByOwner(user, {
+        mint: nftMint,
+      });
+
+    const nftBonusInfo = (
+      await PublicKey.findProgramAddress(
+        [Buffer.from("nft-bonus"), stakingInfo.toBytes(), nftMint.toBytes()],
+        program.programId
+      )
+    )[0];
+
+    return [nftMultiplierInfo, tokenAccounts.value[0].pubkey]
+      .concat([
+        await nftUtils.getChillMetadataPubkey(nftMint, nftProgram.programId),
+        await Metadata.Metadata.getPDA(nftMint),
+      ])
+      .map((pubkey) => ({ pubkey, isSigner: false, isWritable: false }))
+      .concat([{ pubkey: nftBonusInfo, isSigner: false, isWritable: true }]);
+  }
+
+  async function registerCollection(bonus: number = rewardBonus) {
+    const adminConfig = await nftUtils.initializeAdminConfig(
+      nftProgram,
+      primaryWallet
+    );
+
+    await program.methods
+      .registerNftCollection(collectionMint, bonus)
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        adminConfig,
+        payer: payer.publicKey,
+        stakingInfo,
+        nftMultiplierInfo,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(nftUtils.adminSignerMetas([primaryWallet.publicKey]))
+      .signers([primaryWallet, payer])
+      .rpc();
+  }
+
+  async function stake(
+    user: Keypair,
+    tokenAccount: PublicKey,
+    remainingAccounts: AccountMeta[]
+  ): Promise<PublicKey> {
+    const stakingTokenAuthority = await stakingUtils.getStakingAuthority(
+      stakingInfo,
+      program.programId
+    );
+
+    const userInfo = await stakingUtils.getUserInfoPubkey(
+      user.publicKey,
+      stakingInfo,
+      program.programId
+    );
+
+    await program.methods
+      .stake(new BN(stakeAmount), null)
+      .accounts({
+        user: user.publicKey,
+        payer: payer.publicKey,
+        tokenAccountAuthority: user.publicKey,
+        userInfo,
+        fromTokenAccount: tokenAccount,
+        stakingInfo,
+        stakingTokenAuthority,
+        stakingTokenAccount: await utils.getAssociatedTokenAddress(
+          stakingTokenAuthority,
+          chillMint
+        ),
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(remainingAccounts)
+      .signers([user, payer])
+      .rpc();
+
+    return userInfo;
+  }
+
+  before(async () => {
+    primaryWallet = await utils.keypairWithSol();
+    payer = await utils.keypairWithSol();
+    chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+    config = await nftUtils.getNftConfigPubkey(
+      chillMint,
+      nftProgram.programId
+    );
+
+    const configRegistry = await nftUtils.initializeConfigRegistry(
+      nftProgram,
+      primaryWallet
+    );
+
+    const adminConfig = await nftUtils.initializeAdminConfig(
+      nftProgram,
+      primaryWallet
+    );
+
+    await nftProgram.methods
+      .initialize(nftUtils.randomFees(), [])
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        adminConfig,
+        payer: primaryWallet.publicKey,
+        config,
+        configRegistry,
+        chillMint,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(nftUtils.adminSignerMetas([primaryWallet.publicKey]))
+      .signers([primaryWallet])
+      .rpc();
+
+    collectionMint = await utils.createMint(primaryWallet.publicKey, 0);
+    const collectionToken = await utils.createTokenAccount(
+      primaryWallet.publicKey,
+      collectionMint
+    );
+    await utils.mintTokens(primaryWallet, collectionMint, collectionToken, 1);
+
+    await nftProgram.methods
+      .createCollection(nftUtils.randomNftArgs())
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        payer: primaryWallet.publicKey,
+        config,
+        collectionMint,
+        collectionMetadata: await Metadata.Metadata.getPDA(collectionMint),
+        collectionMasterEdition: await Metadata.MasterEdition.getPDA(
+          collectionMint
+        ),
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        tokenMetadataProgram: Metadata.MetadataProgram.PUBKEY,
+      })
+      .signers([primaryWallet])
+      .rpc();
+
+    collectionNft = await mintNft(collectionMint);
+    otherNft = await mintNft(null);
+
+    stakingInfo = await stakingUtils.initializeStaking(
+      primaryWallet,
+      payer,
+      totalDays,
+      chillMint,
+      program
+    );
+
+    nftMultiplierInfo = (
+      await PublicKey.findProgramAddress(
+        [Buffer.from("nft-multiplier"), stakingInfo.toBytes()],
+        program.programId
+      )
+    )[0];
+
+    await registerCollection();
+
+    await stakingUtils.addRewardTokens(
+      rewardTokensAmount,
+      primaryWallet,
+      chillMint,
+      stakingInfo,
+      program
+    );
+  });
+
+  it("Collection is registered", async () => {
+    const info = await program.account.nftMultiplierInfo.fetch(
+      nftMultiplierInfo
+    );
+    assert.ok(info.collection.equals(collectionMint));
+    assert.ok(info.stakingInfo.equals(stakingInfo));
+
+    const staking = await program.account.stakingInfo.fetch(stakingInfo);
+    assert.equal(staking.nftRewardBonus, rewardBonus);
+  });
+
+  it("Try to register the collection after start", async () => {
+    const info = await program.account.stakingInfo.fetch(stakingInfo);
+    await stakingUtils.waitUntil(program, info.startDay.toNumber());
+
+    await assert.rejects(registerCollection(), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "StakingIsAlreadyStarted");
+      return true;
+    });
+  });
+
+  it("Try to register the collection with a too large bonus", async () => {
+    await assert.rejects(registerCollection(101), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "InvalidNftRewardBonus");
+      return true;
+    });
+  });
+
+  it("Try to stake with an NFT out of the collection", async () => {
+    const tokenAccount = await utils.createTokenAccount(
+      primaryWallet.publicKey,
+      chillMint
+    );
+    await utils.mintTokens(primaryWallet, chillMint, tokenAccount, stakeAmount);
+
+    await assert.rejects(
+      stake(
+        primaryWallet,
+        tokenAccount,
+        await nftHolderMetas(primaryWallet.publicKey, otherNft)
+      ),
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "WrongNftCollection");
+        return true;
+      }
+    );
+  });
+
+  it("Try to stake with an NFT of another user", async () => {
+    const [user, tokenAccount] = await stakingUtils.createUserWithTokenAccount(
+      chillMint,
+      primaryWallet,
+      stakeAmount
+    );
+
+    await assert.rejects(
+      stake(
+        user,
+        tokenAccount,
+        await nftHolderMetas(primaryWallet.publicKey, collectionNft)
+      ),
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "WrongNftHolderAccount");
+        return true;
+      }
+    );
+  });
+
+  it("NFT holder receives the bonus", async () => {
+    const tokenAccount = await utils.createTokenAccount(
+      primaryWallet.publicKey,
+      chillMint
+    );
+    await utils.mintTokens(primaryWallet, chillMint, tokenAccount, stakeAmount);
+
+    const userInfo = await stake(
+      primaryWallet,
+      tokenAccount,
+      await nftHolderMetas(primaryWallet.publicKey, collectionNft)
+    );
+
+    const staking = await program.account.stakingInfo.fetch(stakingInfo);
+    const info = await program.account.userInfo.fetch(userInfo);
+    assert.equal(
+      info.dailyStakingReward.toNumber(),
+      Math.floor((staking.lastDailyReward.toNumber() * (100 + rewardBonus)) / 100)
+    );
+  });
+
+  it("Try to pass the NFT to another user for a second bonus", async () => {
+    const [user, tokenAccount] = await stakingUtils.createUserWithTokenAccount(
+      chillMint,
+      primaryWallet,
+      stakeAmount
+    );
+
+    const nftTokenAccounts =
+      await program.provider.connection.getTokenAccountsByOwner(
+        primaryWallet.publicKey,
+        { mint: collectionNft }
+      );
+    await utils.transferTokens(
+      primaryWallet,
+      nftTokenAccounts.value[0].pubkey,
+      await utils.createTokenAccount(user.publicKey, collectionNft),
+      1
+    );
+
+    await assert.rejects(
+      stake(
+        user,
+        tokenAccount,
+        await nftHolderMetas(user.publicKey, collectionNft)
+      ),
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "NftBonusIsUsed");
+        return true;
+      }
+    );
+  });
+
+  it("Stake without an NFT receives no bonus", async () => {
+    const [user, tokenAccount] = await stakingUtils.createUserWithTokenAccount(
+      chillMint,
+      primaryWallet,
+      stakeAmount
+    );
+
+    const userInfo = await stake(user, tokenAccount, []);
+
+    const staking = await program.account.stakingInfo.fetch(stakingInfo);
+    const info = await program.account.userInfo.fetch(userInfo);
+    assert.equal(
+      info.dailyStakingReward.toNumber(),
+      staking.lastDailyReward.toNumber()
+    );
+  });
+});
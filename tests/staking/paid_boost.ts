@@ -0,0 +1,253 @@
+import * as assert from "assert";
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as nftUtils from "../nft/utils";
+import * as stakingUtils from "./utils";
+import { AnchorError, BN, Program } from "@project-serum/anchor";
+import { ChillNft } from "../../target/types/chill_nft";
+import { ChillStaking } from "../../target/types/chill_staking";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("Staking simulation | Paid boost", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillStaking as Program<ChillStaking>;
+
+  const totalDays = 10;
+  const stakeAmount = 100_000;
+  const boostCost = 1_000;
+  const rewardTokensAmount = 100_000_000;
+
+  let primaryWallet: Keypair;
+  let payer: Keypair;
+  let chillMint: PublicKey;
+  let stakingInfo: PublicKey;
+  let paidBoostInfo: PublicKey;
+  let startDay: number;
+
+  let user: Keypair;
+  let tokenAccount: PublicKey;
+  let userInfo: PublicKey;
+
+  async function setCost(signer: Keypair, cost: number) {
+    const adminConfig = await nftUtils.initializeAdminConfig(
+      anchor.workspace.ChillNft as Program<ChillNft>,
+      primaryWallet
+    );
+
+    await program.methods
+      .setPaidBoostCost(new BN(cost))
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        adminConfig,
+        payer: payer.publicKey,
+        stakingInfo,
+        paidBoostInfo,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(nftUtils.adminSignerMetas([signer.publicKey]))
+      .signers([signer, payer])
+      .rpc();
+  }
+
+  async function paidBoost(
+    day: number,
+    staker: Keypair = user,
+    stakerTokenAccount: PublicKey = tokenAccount
+  ) {
+    await program.methods
+      .paidBoost(new BN(day))
+      .accounts({
+        user: staker.publicKey,
+        userInfo: await stakingUtils.getUserInfoPubkey(
+          staker.publicKey,
+          stakingInfo,
+          program.programId
+        ),
+        mint: chillMint,
+        fromTokenAccount: stakerTokenAccount,
+        stakingInfo,
+        paidBoostInfo,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([staker])
+      .rpc();
+  }
+
+  async function stake(staker: Keypair, stakerTokenAccount: PublicKey) {
+    const stakingTokenAuthority = await stakingUtils.getStakingAuthority(
+      stakingInfo,
+      program.programId
+    );
+
+    await program.methods
+      .stake(new BN(stakeAmount), null)
+      .accounts({
+        user: staker.publicKey,
+        payer: payer.publicKey,
+        tokenAccountAuthority: staker.publicKey,
+        userInfo: await stakingUtils.getUserInfoPubkey(
+          staker.publicKey,
+          stakingInfo,
+          program.programId
+        ),
+        fromTokenAccount: stakerTokenAccount,
+        stakingInfo,
+        stakingTokenAuthority,
+        stakingTokenAccount: await utils.getAssociatedTokenAddress(
+          stakingTokenAuthority,
+          chillMint
+        ),
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([staker, payer])
+      .rpc();
+  }
+
+  before(async () => {
+    primaryWallet = await utils.keypairWithSol();
+    payer = await utils.keypairWithSol();
+    chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+
+    stakingInfo = await stakingUtils.initializeStaking(
+      primaryWallet,
+      payer,
+      totalDays,
+      chillMint,
+      program
+    );
+
+    await stakingUtils.addRewardTokens(
+      rewardTokensAmount,
+      primaryWallet,
+      chillMint,
+      stakingInfo,
+      program
+    );
+
+    [user, tokenAccount] = await stakingUtils.createUserWithTokenAccount(
+      chillMint,
+      primaryWallet,
+      stakeAmount + boostCost
+    );
+
+    const info = await program.account.stakingInfo.fetch(stakingInfo);
+    startDay = info.startDay.toNumber();
+
+    userInfo = await stakingUtils.getUserInfoPubkey(
+      user.publicKey,
+      stakingInfo,
+      program.programId
+    );
+
+    paidBoostInfo = (
+      await PublicKey.findProgramAddress(
+        [Buffer.from("paid-boost"), stakingInfo.toBytes()],
+        program.programId
+      )
+    )[0];
+
+    await stakingUtils.waitUntil(program, startDay);
+    await stake(user, tokenAccount);
+  });
+
+  it("Try to set the cost by the user", async () => {
+    await assert.rejects(setCost(user, boostCost), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "NotEnoughAdminSigners");
+      return true;
+    });
+  });
+
+  it("Try to boost when paid boosts are disabled", async () => {
+    await setCost(primaryWallet, 0);
+    await assert.rejects(paidBoost(0), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "PaidBoostIsDisabled");
+      return true;
+    });
+  });
+
+  it("Try to boost a future day", async () => {
+    await setCost(primaryWallet, boostCost);
+    await assert.rejects(paidBoost(1), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "InvalidDayIndex");
+      return true;
+    });
+  });
+
+  it("Boost a missed day", async () => {
+    await stakingUtils.waitUntil(program, startDay + 2);
+    await paidBoost(0);
+
+    assert.equal(await utils.tokenBalance(tokenAccount), 0);
+
+    const info = await program.account.paidBoostInfo.fetch(paidBoostInfo);
+    assert.equal(info.cost.toNumber(), boostCost);
+    assert.equal(info.totalPaidBoostNumber.toNumber(), 1);
+    assert.equal(info.totalPaidBoostAmount.toNumber(), boostCost);
+
+    const staking = await program.account.stakingInfo.fetch(stakingInfo);
+    assert.equal(staking.totalBoostNumber.toNumber(), 1);
+
+    const userAccount = await program.account.userInfo.fetch(userInfo);
+    assert.equal(userAccount.totalBoostNumber.toNumber(), 1);
+  });
+
+  it("Try to boost the day twice", async () => {
+    await assert.rejects(paidBoost(0), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "AlreadyBoosted");
+      return true;
+    });
+  });
+
+  it("Cancel a stake with a paid boost", async () => {
+    const [otherUser, otherTokenAccount] =
+      await stakingUtils.createUserWithTokenAccount(
+        chillMint,
+        primaryWallet,
+        stakeAmount + boostCost
+      );
+    const otherUserInfo = await stakingUtils.getUserInfoPubkey(
+      otherUser.publicKey,
+      stakingInfo,
+      program.programId
+    );
+
+    await stake(otherUser, otherTokenAccount);
+    await paidBoost(0, otherUser, otherTokenAccount);
+
+    await program.methods
+      .cancel()
+      .accounts({
+        user: otherUser.publicKey,
+        userInfo: otherUserInfo,
+        stakingInfo,
+      })
+      .signers([otherUser])
+      .rpc();
+
+    const userAccount = await program.account.userInfo.fetch(otherUserInfo);
+    assert.equal(userAccount.totalBoostNumber.toNumber(), 0);
+    assert.equal(userAccount.startDay, null);
+
+    const staking = await program.account.stakingInfo.fetch(stakingInfo);
+    assert.equal(staking.totalBoostNumber.toNumber(), 1);
+
+    const info = await program.account.paidBoostInfo.fetch(paidBoostInfo);
+    assert.equal(info.totalPaidBoostNumber.toNumber(), 2);
+  });
+
+  it("Paid boost doubles the reward of the day", async () => {
+    await stakingUtils.waitUntil(program, startDay + 7);
+
+    const info = await program.account.userInfo.fetch(userInfo);
+    const reward = await stakingUtils.getUserRewardFromSimulation(
+      program,
+      userInfo,
+      stakingInfo
+    );
+
+    // The single staker receives the whole daily reward, twice on day 0
+    assert.equal(reward.toNumber(), info.dailyStakingReward.toNumber() * 8);
+  });
+});
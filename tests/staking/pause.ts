@@ -0,0 +1,185 @@
+import * as assert from "assert";
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as stakingUtils from "./utils";
+import { AnchorError, BN, Program } from "@project-serum/anchor";
+import { ChillStaking } from "../../target/types/chill_staking";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("Staking | Pause", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillStaking as Program<ChillStaking>;
+  const primaryWallet = Keypair.generate();
+
+  const totalDays = 10;
+  const stakeAmount = 100_000;
+  const rewardTokensAmount = 100_000_000;
+
+  let payer: Keypair;
+  let chillMint: PublicKey;
+  let stakingInfo: PublicKey;
+  let stakingTokenAuthority: PublicKey;
+  let stakingTokenAccount: PublicKey;
+
+  let user: Keypair;
+  let tokenAccount: PublicKey;
+  let userInfo: PublicKey;
+
+  async function stake() {
+    await program.methods
+      .stake(new BN(stakeAmount), null)
+      .accounts({
+        user: user.publicKey,
+        payer: payer.publicKey,
+        tokenAccountAuthority: user.publicKey,
+        userInfo,
+        fromTokenAccount: tokenAccount,
+        stakingInfo,
+        stakingTokenAuthority,
+        stakingTokenAccount,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user, payer])
+      .rpc();
+  }
+
+  async function assertPaused(promise: Promise<unknown>) {
+    await assert.rejects(promise, (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "StakingIsPaused");
+      return true;
+    });
+  }
+
+  before(async () => {
+    payer = await utils.keypairWithSol();
+    chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+
+    stakingInfo = await stakingUtils.initializeStaking(
+      primaryWallet,
+      payer,
+      totalDays,
+      chillMint,
+      program
+    );
+
+    await stakingUtils.addRewardTokens(
+      rewardTokensAmount,
+      primaryWallet,
+      chillMint,
+      stakingInfo,
+      program
+    );
+
+    [user, tokenAccount] = await stakingUtils.createUserWithTokenAccount(
+      chillMint,
+      primaryWallet,
+      stakeAmount
+    );
+
+    userInfo = await stakingUtils.getUserInfoPubkey(
+      user.publicKey,
+      stakingInfo,
+      program.programId
+    );
+
+    stakingTokenAuthority = await stakingUtils.getStakingAuthority(
+      stakingInfo,
+      program.programId
+    );
+
+    stakingTokenAccount = await utils.getAssociatedTokenAddress(
+      stakingTokenAuthority,
+      chillMint
+    );
+
+    const info = await program.account.stakingInfo.fetch(stakingInfo);
+    await stakingUtils.waitUntil(program, info.startDay.toNumber());
+
+    await stake();
+  });
+
+  it("Try to pause with a wrong primary wallet", async () => {
+    const wrongPrimaryWallet = Keypair.generate();
+
+    await assert.rejects(async () => {
+      await program.methods
+        .pause()
+        .accounts({ primaryWallet: wrongPrimaryWallet.publicKey, stakingInfo })
+        .signers([wrongPrimaryWallet])
+        .rpc();
+    });
+  });
+
+  it("Pause staking", async () => {
+    await program.methods
+      .pause()
+      .accounts({ primaryWallet: primaryWallet.publicKey, stakingInfo })
+      .signers([primaryWallet])
+      .rpc();
+
+    const info = await program.account.stakingInfo.fetch(stakingInfo);
+    assert.equal(info.paused, true);
+
+    await assertPaused(
+      program.methods
+        .pause()
+        .accounts({ primaryWallet: primaryWallet.publicKey, stakingInfo })
+        .signers([primaryWallet])
+        .rpc()
+    );
+  });
+
+  it("Try to boost while paused", async () => {
+    await assertPaused(
+      program.methods
+        .boost()
+        .accounts({ user: user.publicKey, userInfo, stakingInfo })
+        .signers([user])
+        .rpc()
+    );
+  });
+
+  it("Cancel and claim while paused", async () => {
+    await program.methods
+      .cancel()
+      .accounts({ user: user.publicKey, userInfo, stakingInfo })
+      .signers([user])
+      .rpc();
+
+    await program.methods
+      .claim(new BN(stakeAmount))
+      .accounts({
+        user: user.publicKey,
+        userInfo,
+        recipientTokenAccount: tokenAccount,
+        stakingInfo,
+        stakingTokenAuthority,
+        stakingTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    assert.equal(await utils.tokenBalance(tokenAccount), stakeAmount);
+  });
+
+  it("Try to stake while paused", async () => {
+    await assertPaused(stake());
+  });
+
+  it("Resume staking", async () => {
+    await program.methods
+      .resume()
+      .accounts({ primaryWallet: primaryWallet.publicKey, stakingInfo })
+      .signers([primaryWallet])
+      .rpc();
+
+    const info = await program.account.stakingInfo.fetch(stakingInfo);
+    assert.equal(info.paused, false);
+
+    await stake();
+    assert.equal(await utils.tokenBalance(tokenAccount), 0);
+  });
+});
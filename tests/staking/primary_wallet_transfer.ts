@@ -0,0 +1,104 @@
+import * as assert from "assert";
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as stakingUtils from "./utils";
+import { AnchorError, Program } from "@project-serum/anchor";
+import { ChillStaking } from "../../target/types/chill_staking";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+
+describe("Staking | Primary wallet transfer", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillStaking as Program<ChillStaking>;
+  const connection = program.provider.connection;
+
+  const newPrimaryWallet = Keypair.generate();
+
+  let primaryWallet: Keypair;
+  let stakingInfo: PublicKey;
+  let primaryWalletTransfer: PublicKey;
+
+  async function propose(proposer: Keypair, newPrimaryWallet: PublicKey) {
+    await program.methods
+      .proposeNewPrimaryWallet(newPrimaryWallet)
+      .accounts({
+        primaryWallet: proposer.publicKey,
+        stakingInfo,
+        primaryWalletTransfer,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([proposer])
+      .rpc();
+  }
+
+  async function accept(acceptor: Keypair) {
+    await program.methods
+      .acceptPrimaryWallet()
+      .accounts({
+        newPrimaryWallet: acceptor.publicKey,
+        primaryWallet: primaryWallet.publicKey,
+        stakingInfo,
+        primaryWalletTransfer,
+      })
+      .signers([acceptor])
+      .rpc();
+  }
+
+  before(async () => {
+    primaryWallet = await utils.keypairWithSol();
+    const payer = await utils.keypairWithSol();
+    const chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+
+    stakingInfo = await stakingUtils.initializeStaking(
+      primaryWallet,
+      payer,
+      10,
+      chillMint,
+      program
+    );
+
+    primaryWalletTransfer = (
+      await PublicKey.findProgramAddress(
+        [Buffer.from("primary-wallet-transfer"), stakingInfo.toBytes()],
+        program.programId
+      )
+    )[0];
+  });
+
+  it("Try to propose the current primary wallet", async () => {
+    await assert.rejects(
+      propose(primaryWallet, primaryWallet.publicKey),
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "WrongNewPrimaryWallet");
+        return true;
+      }
+    );
+  });
+
+  it("Propose a new primary wallet", async () => {
+    await propose(primaryWallet, newPrimaryWallet.publicKey);
+
+    const transfer = await program.account.primaryWalletTransfer.fetch(
+      primaryWalletTransfer
+    );
+
+    assert.ok(transfer.stakingInfo.equals(stakingInfo));
+    assert.ok(transfer.newPrimaryWallet.equals(newPrimaryWallet.publicKey));
+
+    const info = await program.account.stakingInfo.fetch(stakingInfo);
+    assert.ok(info.primaryWallet.equals(primaryWallet.publicKey));
+  });
+
+  it("Try to accept by a wrong wallet", async () => {
+    await assert.rejects(accept(Keypair.generate()));
+  });
+
+  it("Accept the primary wallet", async () => {
+    await accept(newPrimaryWallet);
+
+    const info = await program.account.stakingInfo.fetch(stakingInfo);
+    assert.ok(info.primaryWallet.equals(newPrimaryWallet.publicKey));
+    assert.equal(await connection.getAccountInfo(primaryWalletTransfer), null);
+
+    await assert.rejects(propose(primaryWallet, Keypair.generate().publicKey));
+  });
+});
@@ -0,0 +1,265 @@
+import * as assert from "assert";
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as stakingUtils from "./utils";
+import { AnchorError, BN, Program } from "@project-serum/anchor";
+import { ChillStaking } from "../../target/types/chill_staking";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+
+const REDISTRIBUTION_SEED = "redistribution";
+
+describe("Staking simulation | Redistribution of unspent rewards", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillStaking as Program<ChillStaking>;
+  const primaryWallet = Keypair.generate();
+
+  const totalDays = 10;
+  const daysInWindow = 3;
+  const referralFee = 1000;
+  const stakeAmounts = [100_000, 300_000];
+  const rewardTokensAmount = 100_000_000;
+
+  // The second stake is cut by the staking end, the referral fee leaves
+  // reward tokens nobody receives
+  const stakeStartDays = [0, 8];
+  const stakeWeights = [100_000 * 3, 300_000 * 2];
+
+  let payer: Keypair;
+  let chillMint: PublicKey;
+  let stakingInfo: PublicKey;
+  let stakingTokenAuthority: PublicKey;
+  let stakingTokenAccount: PublicKey;
+  let redistribution: PublicKey;
+  let startDay: number;
+
+  const users: Keypair[] = [];
+  const tokenAccounts: PublicKey[] = [];
+  const userInfos: PublicKey[] = [];
+
+  async function findAddress(seed: PublicKey): Promise<PublicKey> {
+    return (
+      await PublicKey.findProgramAddress(
+        [Buffer.from(REDISTRIBUTION_SEED), seed.toBytes()],
+        program.programId
+      )
+    )[0];
+  }
+
+  function startRedistribution(): Promise<string> {
+    return program.methods
+      .startRedistribution()
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        payer: payer.publicKey,
+        stakingInfo,
+        redistribution,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([primaryWallet, payer])
+      .rpc();
+  }
+
+  async function redistributeUnspentReward(index: number): Promise<string> {
+    return program.methods
+      .redistributeUnspentReward()
+      .accounts({
+        payer: payer.publicKey,
+        userInfo: userInfos[index],
+        stakingInfo,
+        redistribution,
+        redistributionReceipt: await findAddress(userInfos[index]),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([payer])
+      .rpc();
+  }
+
+  before(async () => {
+    payer = await utils.keypairWithSol();
+    chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+
+    stakingInfo = await stakingUtils.initializeStaking(
+      primaryWallet,
+      payer,
+      totalDays,
+      chillMint,
+      program,
+      0,
+      daysInWindow,
+      referralFee
+    );
+
+    await stakingUtils.addRewardTokens(
+      rewardTokensAmount,
+      primaryWallet,
+      chillMint,
+      stakingInfo,
+      program
+    );
+
+    stakingTokenAuthority = await stakingUtils.getStakingAuthority(
+      stakingInfo,
+      program.programId
+    );
+
+    stakingTokenAccount = await utils.getAssociatedTokenAddress(
+      stakingTokenAuthority,
+      chillMint
+    );
+
+    redistribution = await findAddress(stakingInfo);
+
+    for (const amount of stakeAmounts) {
+      const [user, tokenAccount] =
+        await stakingUtils.createUserWithTokenAccount(
+          chillMint,
+          primaryWallet,
+          amount
+        );
+
+      users.push(user);
+      tokenAccounts.push(tokenAccount);
+      userInfos.push(
+        await stakingUtils.getUserInfoPubkey(
+          user.publicKey,
+          stakingInfo,
+          program.programId
+        )
+      );
+    }
+
+    const info = await program.account.stakingInfo.fetch(stakingInfo);
+    startDay = info.startDay.toNumber();
+    await stakingUtils.waitUntil(program, startDay);
+  });
+
+  it("Stake tokens", async () => {
+    for (let i = 0; i < users.length; i++) {
+      await stakingUtils.waitUntil(program, startDay + stakeStartDays[i]);
+      await program.methods
+        .stake(new BN(stakeAmounts[i]), null)
+        .accounts({
+          user: users[i].publicKey,
+          payer: payer.publicKey,
+          tokenAccountAuthority: users[i].publicKey,
+          userInfo: userInfos[i],
+          fromTokenAccount: tokenAccounts[i],
+          stakingInfo,
+          stakingTokenAuthority,
+          stakingTokenAccount,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([users[i], payer])
+        .rpc();
+    }
+  });
+
+  it("Try to start redistribution before the staking end", async () => {
+    await assert.rejects(startRedistribution, (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "StakingIsNotFinished");
+      return true;
+    });
+  });
+
+  it("Try to start redistribution with unsettled stakes", async () => {
+    await stakingUtils.waitUntil(program, startDay + totalDays);
+
+    await assert.rejects(startRedistribution, (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "StakesAreNotSettled");
+      return true;
+    });
+  });
+
+  it("Settle stakes", async () => {
+    for (const userInfo of userInfos) {
+      await program.methods
+        .settleStake()
+        .accounts({ userInfo, stakingInfo })
+        .rpc();
+    }
+
+    const info = await program.account.stakingInfo.fetch(stakingInfo);
+    assert.equal(info.activeStakesNumber.toNumber(), 0);
+  });
+
+  it("Start redistribution", async () => {
+    const before = await program.account.stakingInfo.fetch(stakingInfo);
+    const unspentAmount =
+      rewardTokensAmount - before.totalRewardedAmount.toNumber();
+    assert.ok(unspentAmount > 0);
+
+    await startRedistribution();
+
+    const info = await program.account.redistribution.fetch(redistribution);
+    assert.equal(info.amount.toNumber(), unspentAmount);
+    assert.equal(
+      info.totalWeight.toNumber(),
+      stakeWeights.reduce((a, b) => a + b)
+    );
+
+    const after = await program.account.stakingInfo.fetch(stakingInfo);
+    assert.equal(after.totalRewardedAmount.toNumber(), rewardTokensAmount);
+    assert.equal(
+      after.totalPendingWithdrawalAmount.toNumber(),
+      before.totalPendingWithdrawalAmount.toNumber() + unspentAmount
+    );
+  });
+
+  it("Redistribute unspent rewards by stake-days", async () => {
+    const info = await program.account.redistribution.fetch(redistribution);
+    const totalWeight = info.totalWeight.toNumber();
+
+    for (let i = 0; i < users.length; i++) {
+      const before = await program.account.userInfo.fetch(userInfos[i]);
+      assert.equal(before.totalStakeWeight.toNumber(), stakeWeights[i]);
+      await redistributeUnspentReward(i);
+
+      const share = Math.floor(
+        (info.amount.toNumber() * stakeWeights[i]) / totalWeight
+      );
+
+      const after = await program.account.userInfo.fetch(userInfos[i]);
+      assert.equal(
+        after.rewardedAmount.toNumber(),
+        before.rewardedAmount.toNumber() + share
+      );
+
+      const receipt = await program.account.redistributionReceipt.fetch(
+        await findAddress(userInfos[i])
+      );
+      assert.equal(receipt.amount.toNumber(), share);
+    }
+  });
+
+  it("Try to redistribute twice to the same user", async () => {
+    await assert.rejects(async () => {
+      await redistributeUnspentReward(0);
+    });
+  });
+
+  it("Claim redistributed rewards", async () => {
+    const info = await program.account.userInfo.fetch(userInfos[0]);
+    const amount = info.rewardedAmount.add(info.pendingAmount);
+
+    await program.methods
+      .claim(amount)
+      .accounts({
+        user: users[0].publicKey,
+        userInfo: userInfos[0],
+        recipientTokenAccount: tokenAccounts[0],
+        stakingInfo,
+        stakingTokenAuthority,
+        stakingTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([users[0]])
+      .rpc();
+
+    assert.equal(
+      await utils.tokenBalance(tokenAccounts[0]),
+      amount.toNumber()
+    );
+  });
+});
@@ -0,0 +1,245 @@
+import * as assert from "assert";
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as stakingUtils from "./utils";
+import { AnchorError, BN, Program } from "@project-serum/anchor";
+import { ChillStaking } from "../../target/types/chill_staking";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("Staking | Referral", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillStaking as Program<ChillStaking>;
+  const primaryWallet = Keypair.generate();
+
+  const totalDays = 10;
+  const daysInWindow = 2;
+  const referralFee = 1000;
+  const stakeAmount = 100_000;
+  const rewardTokensAmount = 100_000_000;
+
+  let payer: Keypair;
+  let chillMint: PublicKey;
+  let stakingInfo: PublicKey;
+  let stakingTokenAuthority: PublicKey;
+  let stakingTokenAccount: PublicKey;
+  let startDay: number;
+
+  let user: Keypair;
+  let tokenAccount: PublicKey;
+  let userInfo: PublicKey;
+  let referralInfo: PublicKey;
+
+  let referrer: Keypair;
+  let referrerTokenAccount: PublicKey;
+
+  async function stake(referrer: PublicKey | null) {
+    await program.methods
+      .stake(new BN(stakeAmount), referrer)
+      .accounts({
+        user: user.publicKey,
+        payer: payer.publicKey,
+        tokenAccountAuthority: user.publicKey,
+        userInfo,
+        fromTokenAccount: tokenAccount,
+        stakingInfo,
+        stakingTokenAuthority,
+        stakingTokenAccount,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        { pubkey: referralInfo, isSigner: false, isWritable: true },
+      ])
+      .signers([user, payer])
+      .rpc();
+  }
+
+  async function claimReferralReward() {
+    await program.methods
+      .claimReferralReward()
+      .accounts({
+        referrer: referrer.publicKey,
+        referralInfo,
+        userInfo,
+        recipientTokenAccount: referrerTokenAccount,
+        stakingInfo,
+        stakingTokenAuthority,
+        stakingTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([referrer])
+      .rpc();
+  }
+
+  async function assertError(promise: Promise<unknown>, code: string) {
+    await assert.rejects(promise, (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, code);
+      return true;
+    });
+  }
+
+  before(async () => {
+    payer = await utils.keypairWithSol();
+    chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+
+    stakingInfo = await stakingUtils.initializeStaking(
+      primaryWallet,
+      payer,
+      totalDays,
+      chillMint,
+      program,
+      0,
+      daysInWindow,
+      referralFee
+    );
+
+    await stakingUtils.addRewardTokens(
+      rewardTokensAmount,
+      primaryWallet,
+      chillMint,
+      stakingInfo,
+      program
+    );
+
+    [user, tokenAccount] = await stakingUtils.createUserWithTokenAccount(
+      chillMint,
+      primaryWallet,
+      stakeAmount
+    );
+
+    referrer = Keypair.generate();
+    referrerTokenAccount = await utils.createTokenAccount(
+      referrer.publicKey,
+      chillMint
+    );
+
+    userInfo = await stakingUtils.getUserInfoPubkey(
+      user.publicKey,
+      stakingInfo,
+      program.programId
+    );
+
+    referralInfo = (
+      await PublicKey.findProgramAddress(
+        [Buffer.from("referral"), userInfo.toBytes()],
+        program.programId
+      )
+    )[0];
+
+    stakingTokenAuthority = await stakingUtils.getStakingAuthority(
+      stakingInfo,
+      program.programId
+    );
+
+    stakingTokenAccount = await utils.getAssociatedTokenAddress(
+      stakingTokenAuthority,
+      chillMint
+    );
+
+    const info = await program.account.stakingInfo.fetch(stakingInfo);
+    assert.equal(info.referralFee, referralFee);
+
+    startDay = info.startDay.toNumber();
+    await stakingUtils.waitUntil(program, startDay);
+  });
+
+  it("Try to refer itself", async () => {
+    await assertError(stake(user.publicKey), "SelfReferral");
+  });
+
+  it("Stake with a referrer", async () => {
+    await stake(referrer.publicKey);
+
+    const info = await program.account.referralInfo.fetch(referralInfo);
+    assert.ok(info.userInfo.equals(userInfo));
+    assert.ok(info.referrer.equals(referrer.publicKey));
+    assert.equal(info.accountedRewardAmount.toNumber(), 0);
+    assert.equal(info.totalReferralAmount.toNumber(), 0);
+  });
+
+  it("Try to claim before the window ends", async () => {
+    await assertError(claimReferralReward(), "WithdrawZeroTokens");
+  });
+
+  it("Claim referral reward", async () => {
+    await stakingUtils.waitUntil(program, startDay + daysInWindow);
+    await claimReferralReward();
+
+    const userInfoAccount = await program.account.userInfo.fetch(userInfo);
+    const reward = userInfoAccount.totalRewardedAmount.toNumber();
+    const referralReward = Math.floor((reward * referralFee) / 10_000);
+
+    assert.ok(reward > 0);
+    assert.equal(
+      await utils.tokenBalance(referrerTokenAccount),
+      referralReward
+    );
+
+    const info = await program.account.referralInfo.fetch(referralInfo);
+    assert.equal(info.accountedRewardAmount.toNumber(), reward);
+    assert.equal(info.totalReferralAmount.toNumber(), referralReward);
+
+    await assertError(claimReferralReward(), "WithdrawZeroTokens");
+  });
+
+  it("Try to change the referrer", async () => {
+    const newReferrer = Keypair.generate().publicKey;
+    await assertError(stake(newReferrer), "ReferrerAlreadySet");
+  });
+
+  it("Try to claim referral reward taken by the redistribution", async () => {
+    await program.methods
+      .stake(new BN(0), null)
+      .accounts({
+        user: user.publicKey,
+        payer: payer.publicKey,
+        tokenAccountAuthority: user.publicKey,
+        userInfo,
+        fromTokenAccount: tokenAccount,
+        stakingInfo,
+        stakingTokenAuthority,
+        stakingTokenAccount,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user, payer])
+      .rpc();
+
+    await stakingUtils.waitUntil(program, startDay + totalDays);
+    await program.methods
+      .settleStake()
+      .accounts({ userInfo, stakingInfo })
+      .rpc();
+
+    const redistribution = (
+      await PublicKey.findProgramAddress(
+        [Buffer.from("redistribution"), stakingInfo.toBytes()],
+        program.programId
+      )
+    )[0];
+
+    await program.methods
+      .startRedistribution()
+      .accounts({
+        primaryWallet: primaryWallet.publicKey,
+        payer: payer.publicKey,
+        stakingInfo,
+        redistribution,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([primaryWallet, payer])
+      .rpc();
+
+    const before = await program.account.referralInfo.fetch(referralInfo);
+    await assertError(claimReferralReward(), "ReferralRewardIsNotCovered");
+
+    // The reward stays unaccounted instead of being lost
+    const after = await program.account.referralInfo.fetch(referralInfo);
+    const userInfoAccount = await program.account.userInfo.fetch(userInfo);
+    assert.ok(
+      userInfoAccount.totalRewardedAmount.gt(after.accountedRewardAmount)
+    );
+    assert.ok(after.accountedRewardAmount.eq(before.accountedRewardAmount));
+  });
+});
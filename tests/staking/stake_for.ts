@@ -0,0 +1,166 @@
+import * as assert from "assert";
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as stakingUtils from "./utils";
+import { AnchorError, BN, Program } from "@project-serum/anchor";
+import { ChillStaking } from "../../target/types/chill_staking";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("Staking simulation | Stake for", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillStaking as Program<ChillStaking>;
+  const primaryWallet = Keypair.generate();
+
+  const totalDays = 10;
+  const stakeAmount = 100_000;
+  const rewardTokensAmount = 100_000_000;
+
+  let payer: Keypair;
+  let chillMint: PublicKey;
+  let stakingInfo: PublicKey;
+  let startDay: number;
+
+  let treasury: Keypair;
+  let treasuryTokenAccount: PublicKey;
+  let beneficiary: Keypair;
+  let beneficiaryTokenAccount: PublicKey;
+  let userInfo: PublicKey;
+  let stakingTokenAuthority: PublicKey;
+  let stakingTokenAccount: PublicKey;
+
+  before(async () => {
+    payer = await utils.keypairWithSol();
+    chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+
+    stakingInfo = await stakingUtils.initializeStaking(
+      primaryWallet,
+      payer,
+      totalDays,
+      chillMint,
+      program
+    );
+
+    await stakingUtils.addRewardTokens(
+      rewardTokensAmount,
+      primaryWallet,
+      chillMint,
+      stakingInfo,
+      program
+    );
+
+    [treasury, treasuryTokenAccount] =
+      await stakingUtils.createUserWithTokenAccount(
+        chillMint,
+        primaryWallet,
+        stakeAmount
+      );
+
+    [beneficiary, beneficiaryTokenAccount] =
+      await stakingUtils.createUserWithTokenAccount(
+        chillMint,
+        primaryWallet,
+        0
+      );
+
+    const info = await program.account.stakingInfo.fetch(stakingInfo);
+    startDay = info.startDay.toNumber();
+
+    stakingTokenAuthority = await stakingUtils.getStakingAuthority(
+      stakingInfo,
+      program.programId
+    );
+
+    stakingTokenAccount = await utils.getAssociatedTokenAddress(
+      stakingTokenAuthority,
+      chillMint
+    );
+
+    userInfo = await stakingUtils.getUserInfoPubkey(
+      beneficiary.publicKey,
+      stakingInfo,
+      program.programId
+    );
+
+    await stakingUtils.waitUntil(program, startDay);
+  });
+
+  it("Stake for the beneficiary", async () => {
+    await program.methods
+      .stakeFor(new BN(stakeAmount))
+      .accounts({
+        beneficiary: beneficiary.publicKey,
+        tokenAccountAuthority: treasury.publicKey,
+        payer: payer.publicKey,
+        fromTokenAccount: treasuryTokenAccount,
+        userInfo,
+        stakingInfo,
+        stakingTokenAuthority,
+        stakingTokenAccount,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([treasury, payer])
+      .rpc();
+
+    const info = await program.account.userInfo.fetch(userInfo);
+    assert.ok(info.user.equals(beneficiary.publicKey));
+    assert.equal(info.stakedAmount.toNumber(), stakeAmount);
+    assert.equal(info.startDay.toNumber(), startDay);
+
+    assert.equal(await utils.tokenBalance(treasuryTokenAccount), 0);
+  });
+
+  it("Try to claim by the treasury", async () => {
+    await stakingUtils.waitUntil(program, startDay + 7);
+
+    await assert.rejects(
+      async () => {
+        await program.methods
+          .claimAll()
+          .accounts({
+            user: treasury.publicKey,
+            userInfo,
+            recipientTokenAccount: treasuryTokenAccount,
+            stakingInfo,
+            stakingTokenAuthority,
+            stakingTokenAccount,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([treasury])
+          .rpc();
+      },
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "ConstraintSeeds");
+        return true;
+      }
+    );
+  });
+
+  it("Claim by the beneficiary", async () => {
+    const reward = await stakingUtils.getUserRewardFromSimulation(
+      program,
+      userInfo,
+      stakingInfo
+    );
+
+    await program.methods
+      .claimAll()
+      .accounts({
+        user: beneficiary.publicKey,
+        userInfo,
+        recipientTokenAccount: beneficiaryTokenAccount,
+        stakingInfo,
+        stakingTokenAuthority,
+        stakingTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([beneficiary])
+      .rpc();
+
+    assert.equal(
+      await utils.tokenBalance(beneficiaryTokenAccount),
+      stakeAmount + reward.toNumber()
+    );
+  });
+});
@@ -139,13 +139,13 @@ describe("Staking simulation | Staking with cancellation", () => {
     await stakingUtils.waitUntil(program, startDay);
 
     await program.methods
-      .stake(new BN(20_000))
+      .stake(new BN(20_000), null)
       .accounts(firstStakeAccounts)
       .signers([firstUser, payer])
       .rpc();
 
     await program.methods
-      .stake(new BN(20_000))
+      .stake(new BN(20_000), null)
       .accounts({
         user: secondUser.publicKey,
         payer: payer.publicKey,
@@ -201,7 +201,7 @@ describe("Staking simulation | Staking with cancellation", () => {
     await stakingUtils.waitUntil(program, startDay + 1);
 
     await program.methods
-      .stake(new BN(20_000))
+      .stake(new BN(20_000), null)
       .accounts(firstStakeAccounts)
       .signers([firstUser, payer])
       .rpc();
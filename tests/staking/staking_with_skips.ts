@@ -126,7 +126,7 @@ describe("Staking simulation | Staking with skips", () => {
   it("Day 1", async () => {
     await stakingUtils.waitUntil(program, startDay + 1);
     await program.methods
-      .stake(new BN(20_000))
+      .stake(new BN(20_000), null)
       .accounts({
         user: firstUser.publicKey,
         payer: payer.publicKey,
@@ -260,7 +260,7 @@ describe("Staking simulation | Staking with skips", () => {
     await stakingUtils.waitUntil(program, startDay + 9);
 
     await program.methods
-      .stake(new BN(20_000))
+      .stake(new BN(20_000), null)
       .accounts({
         user: secondUser.publicKey,
         payer: payer.publicKey,
@@ -0,0 +1,225 @@
+import * as assert from "assert";
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as stakingUtils from "./utils";
+import { AnchorError, BN, Program } from "@project-serum/anchor";
+import { ChillStaking } from "../../target/types/chill_staking";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("Staking simulation | Unstake", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillStaking as Program<ChillStaking>;
+  const primaryWallet = Keypair.generate();
+
+  const totalDays = 10;
+  const stakeAmount = 100_000;
+  const unstakeAmount = 40_000;
+  const rewardTokensAmount = 100_000_000;
+
+  let payer: Keypair;
+  let chillMint: PublicKey;
+  let stakingInfo: PublicKey;
+  let startDay: number;
+
+  let user: Keypair;
+  let tokenAccount: PublicKey;
+  let userInfo: PublicKey;
+  let stakeAccounts: stakingUtils.StakeAccounts;
+  let claimAccounts: stakingUtils.ClaimAccounts;
+
+  before(async () => {
+    payer = await utils.keypairWithSol();
+    chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+
+    stakingInfo = await stakingUtils.initializeStaking(
+      primaryWallet,
+      payer,
+      totalDays,
+      chillMint,
+      program
+    );
+
+    await stakingUtils.addRewardTokens(
+      rewardTokensAmount,
+      primaryWallet,
+      chillMint,
+      stakingInfo,
+      program
+    );
+
+    [user, tokenAccount] = await stakingUtils.createUserWithTokenAccount(
+      chillMint,
+      primaryWallet,
+      stakeAmount
+    );
+
+    const info = await program.account.stakingInfo.fetch(stakingInfo);
+    startDay = info.startDay.toNumber();
+
+    const stakingTokenAuthority = await stakingUtils.getStakingAuthority(
+      stakingInfo,
+      program.programId
+    );
+
+    const stakingTokenAccount = await utils.getAssociatedTokenAddress(
+      stakingTokenAuthority,
+      chillMint
+    );
+
+    userInfo = await stakingUtils.getUserInfoPubkey(
+      user.publicKey,
+      stakingInfo,
+      program.programId
+    );
+
+    stakeAccounts = {
+      user: user.publicKey,
+      payer: payer.publicKey,
+      tokenAccountAuthority: user.publicKey,
+      userInfo,
+      fromTokenAccount: tokenAccount,
+      stakingInfo,
+      stakingTokenAuthority,
+      stakingTokenAccount,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+
+    claimAccounts = {
+      user: user.publicKey,
+      userInfo,
+      recipientTokenAccount: tokenAccount,
+      stakingInfo,
+      stakingTokenAuthority,
+      stakingTokenAccount,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+
+    await stakingUtils.waitUntil(program, startDay);
+  });
+
+  function unstake(amount: number): Promise<string> {
+    return program.methods
+      .unstake(new BN(amount))
+      .accounts({ user: user.publicKey, userInfo, stakingInfo })
+      .signers([user])
+      .rpc();
+  }
+
+  it("Stake tokens", async () => {
+    await program.methods
+      .stake(new BN(stakeAmount), null)
+      .accounts(stakeAccounts)
+      .signers([user, payer])
+      .rpc();
+  });
+
+  it("Try to unstake zero tokens", async () => {
+    await assert.rejects(
+      async () => {
+        await unstake(0);
+      },
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "WithdrawZeroTokens");
+        return true;
+      }
+    );
+  });
+
+  it("Try to unstake the whole stake", async () => {
+    await assert.rejects(
+      async () => {
+        await unstake(stakeAmount);
+      },
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "InsufficientFunds");
+        return true;
+      }
+    );
+  });
+
+  it("Unstake part of tokens", async () => {
+    await stakingUtils.waitUntil(program, startDay + 2);
+    await unstake(unstakeAmount);
+
+    const remainingAmount = stakeAmount - unstakeAmount;
+    const info = await program.account.userInfo.fetch(userInfo);
+    assert.equal(info.stakedAmount.toNumber(), remainingAmount);
+    assert.equal(info.pendingAmount.toNumber(), unstakeAmount);
+    assert.equal(info.totalStakedAmount.toNumber(), remainingAmount);
+    assert.equal(info.startDay.toNumber(), startDay);
+
+    const staking = await program.account.stakingInfo.fetch(stakingInfo);
+    assert.equal(staking.totalStakedAmount.toNumber(), remainingAmount);
+    assert.equal(staking.activeStakesNumber.toNumber(), 1);
+    assert.equal(staking.totalPendingWithdrawalAmount.toNumber(), stakeAmount);
+
+    const stakedInDay = await program.methods
+      .viewStakedAmountInDay(new BN(0))
+      .accounts({ stakingInfo })
+      .view();
+
+    assert.equal(stakedInDay.toNumber(), remainingAmount);
+  });
+
+  it("Claim unstaked tokens", async () => {
+    await program.methods
+      .claim(new BN(unstakeAmount))
+      .accounts(claimAccounts)
+      .signers([user])
+      .rpc();
+
+    assert.equal(await utils.tokenBalance(tokenAccount), unstakeAmount);
+  });
+
+  it("Remaining stake is rewarded for the whole window", async () => {
+    await stakingUtils.waitUntil(program, startDay + 7);
+
+    const info = await program.account.userInfo.fetch(userInfo);
+    const reward = await stakingUtils.getUserRewardFromSimulation(
+      program,
+      userInfo,
+      stakingInfo
+    );
+
+    // The single staker receives the whole daily reward
+    assert.equal(reward.toNumber(), info.dailyStakingReward.toNumber() * 7);
+  });
+
+  it("Try to unstake after the window end", async () => {
+    await assert.rejects(
+      async () => {
+        await unstake(1);
+      },
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "NoActiveStake");
+        return true;
+      }
+    );
+  });
+
+  it("Claim all rewarded and pending tokens", async () => {
+    const reward = await stakingUtils.getUserRewardFromSimulation(
+      program,
+      userInfo,
+      stakingInfo
+    );
+
+    await program.methods
+      .claimAll()
+      .accounts(claimAccounts)
+      .signers([user])
+      .rpc();
+
+    const remainingAmount = stakeAmount - unstakeAmount;
+    assert.equal(
+      await utils.tokenBalance(tokenAccount),
+      unstakeAmount + remainingAmount + reward.toNumber()
+    );
+
+    const info = await program.account.userInfo.fetch(userInfo);
+    assert.equal(info.rewardedAmount.toNumber(), 0);
+    assert.equal(info.pendingAmount.toNumber(), 0);
+  });
+});
@@ -1,5 +1,6 @@
 import * as assert from "assert";
-import { Accounts, BN, Program } from "@project-serum/anchor";
+import * as nftUtils from "../nft/utils";
+import { Accounts, BN, Program, workspace } from "@project-serum/anchor";
 import { TypeDef } from "@project-serum/anchor/dist/cjs/program/namespace/types";
 import {
   ASSOCIATED_PROGRAM_ID,
@@ -12,6 +13,7 @@ import {
   SYSVAR_RENT_PUBKEY,
   TransactionInstruction,
 } from "@solana/web3.js";
+import { ChillNft } from "../../target/types/chill_nft";
 import { ChillStaking } from "../../target/types/chill_staking";
 import {
   createTokenAccount,
@@ -22,26 +24,40 @@ import {
 
 export const SEC_IN_DAY = 3;
 
+type IdlAccount<Name extends string> = Extract<
+  ChillStaking["accounts"][number],
+  { name: Name }
+>;
+
+type IdlInstruction<Name extends string> = Extract<
+  ChillStaking["instructions"][number],
+  { name: Name }
+>;
+
 export type StakingInfo = TypeDef<
-  ChillStaking["accounts"][1],
+  IdlAccount<"stakingInfo">,
   ChillStaking["accounts"][number]
 >;
 
 export type UserInfo = TypeDef<
-  ChillStaking["accounts"][2],
+  IdlAccount<"userInfo">,
   ChillStaking["accounts"][number]
 >;
 
 export type InitializeAccounts = Accounts<
-  ChillStaking["instructions"][5]["accounts"][number]
+  IdlInstruction<"initialize">["accounts"][number]
 >;
 
 export type StakeAccounts = Accounts<
-  ChillStaking["instructions"][10]["accounts"][number]
+  IdlInstruction<"stake">["accounts"][number]
 >;
 
 export type ClaimAccounts = Accounts<
-  ChillStaking["instructions"][12]["accounts"][number]
+  IdlInstruction<"claim">["accounts"][number]
+>;
+
+export type ClaimAndRestakeAccounts = Accounts<
+  IdlInstruction<"claimAndRestake">["accounts"][number]
 >;
 
 export function getDefaultStakingInfo(): StakingInfo {
@@ -65,6 +81,12 @@ export function getDefaultStakingInfo(): StakingInfo {
     totalStakedAmount: new BN(0),
     totalStakesNumber: new BN(0),
     totalUnspentAmount: new BN(0),
+    totalPendingWithdrawalAmount: new BN(0),
+    clockOffset: new BN(0),
+    claimFee: 0,
+    daysInWindow: 7,
+    paused: false,
+    referralFee: 0,
   };
 }
 
@@ -183,7 +205,7 @@ export async function createStakingAccountInstruction(
   const provider = program.provider;
   const connection = provider.connection;
 
-  const space = 232 + totalDays * 8;
+  const space = 233 + totalDays * 8;
   const lamports = await connection.getMinimumBalanceForRentExemption(space);
 
   return SystemProgram.createAccount({
@@ -200,8 +222,23 @@ export async function initializeStaking(
   payer: Keypair,
   totalDays: number,
   chillMint: PublicKey,
-  program: Program<ChillStaking>
+  program: Program<ChillStaking>,
+  claimFee?: number,
+  daysInWindow?: number,
+  referralFee?: number
 ): Promise<PublicKey> {
+  if (claimFee == null) {
+    claimFee = 0;
+  }
+
+  if (daysInWindow == null) {
+    daysInWindow = 7;
+  }
+
+  if (referralFee == null) {
+    referralFee = 0;
+  }
+
   const stakingInfoKeypair = Keypair.generate();
   const stakingInfoPubkey = stakingInfoKeypair.publicKey;
   const createStakingInstruction = await createStakingAccountInstruction(
@@ -227,7 +264,14 @@ export async function initializeStaking(
   const minStakeSize = new BN(0);
 
   await program.methods
-    .initialize({ startTime, endTime, minStakeSize })
+    .initialize({
+      startTime,
+      endTime,
+      minStakeSize,
+      claimFee,
+      daysInWindow,
+      referralFee,
+    })
     .accounts({
       primaryWallet: primaryWallet.publicKey,
       payer: payer.publicKey,
@@ -272,10 +316,16 @@ export async function addRewardTokens(
 
   await mintTokens(primaryWallet, chillMint, tokenAccount, amount);
 
+  const adminConfig = await nftUtils.initializeAdminConfig(
+    workspace.ChillNft as Program<ChillNft>,
+    primaryWallet
+  );
+
   await program.methods
     .addRewardTokens(new BN(amount))
     .accounts({
       primaryWallet: primaryWallet.publicKey,
+      adminConfig,
       tokenAccountAuthority: tokenAuthority.publicKey,
       tokenAccount,
       stakingInfo,
@@ -283,6 +333,7 @@ export async function addRewardTokens(
       stakingTokenAccount,
       tokenProgram: TOKEN_PROGRAM_ID,
     })
+    .remainingAccounts(nftUtils.adminSignerMetas([primaryWallet.publicKey]))
     .signers([primaryWallet, tokenAuthority])
     .rpc();
 }
@@ -0,0 +1,133 @@
+import * as assert from "assert";
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import * as stakingUtils from "./utils";
+import { BN, Program } from "@project-serum/anchor";
+import { ChillStaking } from "../../target/types/chill_staking";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@project-serum/anchor/dist/cjs/utils/token";
+
+describe("Staking simulation | Window length", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillStaking as Program<ChillStaking>;
+  const primaryWallet = Keypair.generate();
+
+  const totalDays = 20;
+  const daysInWindow = 14;
+  const stakeAmount = 100_000;
+  const rewardTokensAmount = 100_000_000;
+
+  let payer: Keypair;
+  let chillMint: PublicKey;
+  let stakingInfo: PublicKey;
+  let startDay: number;
+
+  let user: Keypair;
+  let tokenAccount: PublicKey;
+  let userInfo: PublicKey;
+
+  before(async () => {
+    payer = await utils.keypairWithSol();
+    chillMint = await utils.createMint(primaryWallet.publicKey, 9);
+
+    stakingInfo = await stakingUtils.initializeStaking(
+      primaryWallet,
+      payer,
+      totalDays,
+      chillMint,
+      program,
+      0,
+      daysInWindow
+    );
+
+    await stakingUtils.addRewardTokens(
+      rewardTokensAmount,
+      primaryWallet,
+      chillMint,
+      stakingInfo,
+      program
+    );
+
+    [user, tokenAccount] = await stakingUtils.createUserWithTokenAccount(
+      chillMint,
+      primaryWallet,
+      stakeAmount
+    );
+
+    userInfo = await stakingUtils.getUserInfoPubkey(
+      user.publicKey,
+      stakingInfo,
+      program.programId
+    );
+
+    const info = await program.account.stakingInfo.fetch(stakingInfo);
+    assert.equal(info.daysInWindow, daysInWindow);
+
+    startDay = info.startDay.toNumber();
+    await stakingUtils.waitUntil(program, startDay);
+  });
+
+  it("Stake tokens", async () => {
+    const stakingTokenAuthority = await stakingUtils.getStakingAuthority(
+      stakingInfo,
+      program.programId
+    );
+
+    await program.methods
+      .stake(new BN(stakeAmount), null)
+      .accounts({
+        user: user.publicKey,
+        payer: payer.publicKey,
+        tokenAccountAuthority: user.publicKey,
+        userInfo,
+        fromTokenAccount: tokenAccount,
+        stakingInfo,
+        stakingTokenAuthority,
+        stakingTokenAccount: await utils.getAssociatedTokenAddress(
+          stakingTokenAuthority,
+          chillMint
+        ),
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user, payer])
+      .rpc();
+
+    const calendar = await program.methods
+      .viewBoostCalendar()
+      .accounts({ userInfo, stakingInfo })
+      .view();
+
+    assert.equal(calendar.days.length, daysInWindow);
+  });
+
+  it("Boost after the default window length", async () => {
+    await stakingUtils.waitUntil(program, startDay + 8);
+
+    await program.methods
+      .boost()
+      .accounts({ user: user.publicKey, userInfo, stakingInfo })
+      .signers([user])
+      .rpc();
+
+    const info = await program.account.userInfo.fetch(userInfo);
+    assert.equal(info.totalBoostNumber.toNumber(), 1);
+  });
+
+  it("Stake ends with the window", async () => {
+    await stakingUtils.waitUntil(program, startDay + daysInWindow);
+
+    await program.methods
+      .settleStake()
+      .accounts({ userInfo, stakingInfo })
+      .rpc();
+
+    const info = await program.account.userInfo.fetch(userInfo);
+    assert.equal(info.startDay, null);
+    assert.equal(info.pendingAmount.toNumber(), stakeAmount);
+    assert.ok(info.rewardedAmount.toNumber() > 0);
+
+    const staking = await program.account.stakingInfo.fetch(stakingInfo);
+    assert.equal(staking.activeStakesNumber.toNumber(), 0);
+  });
+});
@@ -176,6 +176,25 @@ export async function mintTokens(
     .rpc();
 }
 
+export async function burnTokens(
+  authority: Keypair,
+  mint: PublicKey,
+  tokenAccount: PublicKey,
+  amount: number
+) {
+  const tokenProgram = anchor.Spl.token();
+
+  await tokenProgram.methods
+    .burn(new BN(amount))
+    .accounts({
+      source: tokenAccount,
+      mint,
+      authority: authority.publicKey,
+    })
+    .signers([authority])
+    .rpc();
+}
+
 export function randomNumber(max?: number): number {
   if (max == null) {
     max = 1_000_000;
@@ -0,0 +1,166 @@
+import * as anchor from "@project-serum/anchor";
+import * as utils from "../utils";
+import { AnchorError, BN, Program } from "@project-serum/anchor";
+import { ChillVesting } from "../../target/types/chill_vesting";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import * as assert from "assert";
+import {
+  ASSOCIATED_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+} from "@project-serum/anchor/dist/cjs/utils/token";
+
+interface VestingAccounts {
+  vesting: PublicKey;
+  mint: PublicKey;
+  vault: PublicKey;
+  treasuryToken: PublicKey;
+  beneficiaryToken: PublicKey;
+}
+
+describe("Vesting", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.ChillVesting as Program<ChillVesting>;
+
+  const totalAmount = 1_000_000_000_000;
+  const duration = 1_000_000;
+
+  let authority: Keypair;
+  let beneficiary: Keypair;
+
+  async function initializeVesting(
+    startTime: number,
+    cliffTime: number
+  ): Promise<VestingAccounts> {
+    const mint = await utils.createMint(authority.publicKey, 9);
+    const treasuryToken = await utils.createTokenAccount(
+      authority.publicKey,
+      mint
+    );
+    const beneficiaryToken = await utils.createTokenAccount(
+      beneficiary.publicKey,
+      mint
+    );
+    await utils.mintTokens(authority, mint, treasuryToken, totalAmount);
+
+    const [vesting] = await PublicKey.findProgramAddress(
+      [
+        Buffer.from("vesting"),
+        authority.publicKey.toBuffer(),
+        beneficiary.publicKey.toBuffer(),
+        mint.toBuffer(),
+      ],
+      program.programId
+    );
+    const vault = await utils.getAssociatedTokenAddress(vesting, mint);
+
+    await program.methods
+      .initializeVesting({
+        startTime: new BN(startTime),
+        cliffTime: new BN(cliffTime),
+        endTime: new BN(startTime + duration),
+        period: new BN(0),
+        totalAmount: new BN(totalAmount),
+        revocable: true,
+      })
+      .accounts({
+        authority: authority.publicKey,
+        payer: authority.publicKey,
+        beneficiary: beneficiary.publicKey,
+        vesting,
+        mint,
+        authorityTokenAccount: treasuryToken,
+        vault,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+      })
+      .signers([authority])
+      .rpc();
+
+    return { vesting, mint, vault, treasuryToken, beneficiaryToken };
+  }
+
+  async function claimVested(signer: Keypair, accounts: VestingAccounts) {
+    await program.methods
+      .claimVested()
+      .accounts({
+        beneficiary: signer.publicKey,
+        vesting: accounts.vesting,
+        vault: accounts.vault,
+        beneficiaryTokenAccount: accounts.beneficiaryToken,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  async function revoke(accounts: VestingAccounts) {
+    await program.methods
+      .revoke()
+      .accounts({
+        authority: authority.publicKey,
+        vesting: accounts.vesting,
+        vault: accounts.vault,
+        treasuryTokenAccount: accounts.treasuryToken,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  before(async () => {
+    authority = await utils.keypairWithSol();
+    beneficiary = await utils.keypairWithSol();
+  });
+
+  it("Claim unlocked tokens", async () => {
+    const now = await utils.getCurrentTime();
+    const accounts = await initializeVesting(
+      now - duration / 2,
+      now - duration / 2
+    );
+
+    await assert.rejects(
+      claimVested(authority, accounts),
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "WrongBeneficiary");
+        return true;
+      }
+    );
+
+    await claimVested(beneficiary, accounts);
+
+    const claimed = await utils.tokenBalance(accounts.beneficiaryToken);
+    assert.ok(claimed >= totalAmount / 2);
+    assert.ok(claimed < totalAmount);
+
+    const vesting = await program.account.vesting.fetch(accounts.vesting);
+    assert.equal(vesting.claimedAmount.toNumber(), claimed);
+  });
+
+  it("Revoke the vesting before its cliff", async () => {
+    const now = await utils.getCurrentTime();
+    const accounts = await initializeVesting(now, now + duration / 2);
+
+    await assert.rejects(
+      claimVested(beneficiary, accounts),
+      (err: AnchorError) => {
+        assert.equal(err.error.errorCode.code, "NothingToClaim");
+        return true;
+      }
+    );
+
+    await revoke(accounts);
+    assert.equal(await utils.tokenBalance(accounts.treasuryToken), totalAmount);
+
+    const vesting = await program.account.vesting.fetch(accounts.vesting);
+    assert.ok(vesting.revoked);
+    assert.equal(vesting.totalAmount.toNumber(), 0);
+
+    await assert.rejects(revoke(accounts), (err: AnchorError) => {
+      assert.equal(err.error.errorCode.code, "AlreadyRevoked");
+      return true;
+    });
+  });
+});